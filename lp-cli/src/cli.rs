@@ -0,0 +1,134 @@
+//! Command-line definitions
+//!
+//! The clap command tree lives here, rather than in `main`, so the completion generator
+//! and tests can reach it.
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+
+use crate::config::CliConfig;
+use std::io::Write;
+
+#[derive(Parser)]
+#[command(name = "lp-cli")]
+#[command(about = "LightPlayer CLI - Server and client modes")]
+pub struct Cli {
+    /// Print machine-readable JSON instead of human-readable text
+    #[arg(long, global = true)]
+    pub json: bool,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run server from a directory
+    Serve {
+        /// Server directory (defaults to current directory)
+        dir: Option<std::path::PathBuf>,
+        /// Initialize server directory (create server.json if missing)
+        #[arg(long)]
+        init: bool,
+        /// Use in-memory filesystem instead of disk
+        #[arg(long)]
+        memory: bool,
+    },
+    /// Connect to server and sync local project
+    Dev {
+        /// Project directory (defaults to `dir` from the CLI config)
+        dir: Option<std::path::PathBuf>,
+        /// Push local project to server. Optionally specify remote host (e.g., ws://localhost:2812/, serial:auto, emu, or mdns to find one).
+        /// If --push is specified without a host, uses in-memory server.
+        #[arg(long, value_name = "HOST")]
+        push: Option<Option<String>>,
+        /// Run without UI (headless mode)
+        #[arg(long)]
+        headless: bool,
+    },
+    /// Render a project offline, writing each texture as a PNG image
+    Render {
+        /// Project directory (defaults to `dir` from the CLI config)
+        dir: Option<std::path::PathBuf>,
+        /// Directory to write the images to
+        #[arg(long, default_value = "render")]
+        out: std::path::PathBuf,
+        /// Project time to render at, in milliseconds
+        #[arg(long, default_value_t = 0)]
+        time_ms: u32,
+        /// Render again whenever the project's files change
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Create a new project
+    Create {
+        /// Project directory
+        dir: std::path::PathBuf,
+        /// Project name (defaults to directory name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Project UID (auto-generated if not provided)
+        #[arg(long)]
+        uid: Option<String>,
+    },
+    /// List the projects available on and loaded by a server
+    ListProjects {
+        /// Server host (e.g., ws://localhost:2812/, or mdns to find one); defaults to `host`
+        /// from the CLI config, then an in-memory server
+        #[arg(long, value_name = "HOST")]
+        host: Option<String>,
+    },
+    /// Interactively send commands to a server
+    Repl {
+        /// Server host (e.g., ws://localhost:2812/, or mdns to find one); defaults to `host`
+        /// from the CLI config, then an in-memory server
+        #[arg(long, value_name = "HOST")]
+        host: Option<String>,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+}
+
+impl Command {
+    /// Fill flags omitted on the command line from the CLI config
+    pub fn with_defaults(self, config: &CliConfig) -> Command {
+        match self {
+            Command::Dev {
+                dir,
+                push,
+                headless,
+            } => Command::Dev {
+                dir: dir.or_else(|| config.dir.clone()),
+                push: push.or_else(|| config.push.unwrap_or(false).then(|| config.host.clone())),
+                headless,
+            },
+            Command::Render {
+                dir,
+                out,
+                time_ms,
+                watch,
+            } => Command::Render {
+                dir: dir.or_else(|| config.dir.clone()),
+                out,
+                time_ms,
+                watch,
+            },
+            Command::ListProjects { host } => Command::ListProjects {
+                host: host.or_else(|| config.host.clone()),
+            },
+            Command::Repl { host } => Command::Repl {
+                host: host.or_else(|| config.host.clone()),
+            },
+            command => command,
+        }
+    }
+}
+
+/// Write a completion script for `shell` covering the whole command tree
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
@@ -3,7 +3,6 @@
 //! Provides `client_connect()` function that creates appropriate `ClientTransport`
 //! based on a `HostSpecifier`.
 
-use anyhow::{Context, Result};
 #[cfg(feature = "serial")]
 use lp_client::transport_serial::{
     create_emulator_serial_transport_pair, create_hardware_serial_transport_pair,
@@ -13,7 +12,7 @@ use lp_client::{ClientTransport, HostSpecifier, WebSocketClientTransport};
 use lp_riscv_elf::load_elf;
 #[cfg(feature = "serial")]
 use lp_riscv_emu::{
-    LogLevel, Riscv32Emulator, TimeMode,
+    LogLevel, TimeMode,
     test_util::{BinaryBuildConfig, ensure_binary_built},
 };
 #[cfg(feature = "serial")]
@@ -24,6 +23,8 @@ use std::sync::{Arc, Mutex};
 use crate::client::local_server::LocalServerTransport;
 #[cfg(feature = "serial")]
 use crate::client::serial_port::detect_serial_port;
+use crate::error::CliError;
+use lp_model::TransportError;
 
 /// Connect to a server using the specified host specifier
 ///
@@ -37,7 +38,9 @@ use crate::client::serial_port::detect_serial_port;
 /// # Returns
 ///
 /// * `Ok(Box<dyn ClientTransport + Send>)` if connection succeeded
-/// * `Err` if connection failed or transport type not supported
+/// * `Err(CliError::ConnectionRefused)` if nothing is listening at a websocket address
+/// * `Err(CliError::ConnectionFailed)` if connecting failed for another reason
+/// * `Err(CliError::Unsupported)` if the transport type isn't enabled in this build
 ///
 /// # Examples
 ///
@@ -57,40 +60,48 @@ use crate::client::serial_port::detect_serial_port;
 /// # Ok(())
 /// # }
 /// ```
-pub fn client_connect(spec: HostSpecifier) -> Result<Box<dyn ClientTransport>> {
+pub fn client_connect(spec: HostSpecifier) -> Result<Box<dyn ClientTransport>, CliError> {
     match spec {
         HostSpecifier::Local => {
             // Create local server transport (now implements ClientTransport directly)
-            let local_server = LocalServerTransport::new()?;
+            let local_server =
+                LocalServerTransport::new().map_err(|e| connection_failed("local server", e))?;
             Ok(Box::new(local_server))
         }
         HostSpecifier::WebSocket { url } => {
             // WebSocketClientTransport::new is async, but client_connect is sync
             // We need to use tokio runtime to connect
-            let rt = tokio::runtime::Runtime::new()
-                .map_err(|e| anyhow::anyhow!("Failed to create tokio runtime: {e}"))?;
-            let transport = rt
-                .block_on(WebSocketClientTransport::new(&url))
-                .map_err(|e| anyhow::anyhow!("Failed to connect to {url}: {e}"))?;
+            let rt = tokio::runtime::Runtime::new().map_err(|e| CliError::Io {
+                context: "Failed to create tokio runtime".to_string(),
+                source: e,
+            })?;
+            let transport =
+                rt.block_on(WebSocketClientTransport::new(&url))
+                    .map_err(|e| match e {
+                        TransportError::ConnectionRefused(_) => CliError::ConnectionRefused {
+                            target: url.clone(),
+                        },
+                        e => connection_failed(&url, e),
+                    })?;
             Ok(Box::new(transport))
         }
         #[cfg(feature = "serial")]
         HostSpecifier::Serial { port, baud_rate } => {
             // Detect/select serial port
             let port_config = detect_serial_port(port.as_deref(), baud_rate.as_ref().copied())
-                .context("Failed to detect serial port")?;
+                .map_err(|e| connection_failed("serial port", e))?;
 
             // Create hardware serial transport
             let transport =
                 create_hardware_serial_transport_pair(&port_config.port, port_config.baud_rate)
-                    .map_err(|e| anyhow::anyhow!("Failed to create serial transport: {e}"))?;
+                    .map_err(|e| connection_failed(&port_config.port, e))?;
 
             Ok(Box::new(transport))
         }
         #[cfg(not(feature = "serial"))]
-        HostSpecifier::Serial { .. } => {
-            bail!("Serial transport requires 'serial' feature to be enabled");
-        }
+        HostSpecifier::Serial { .. } => Err(CliError::Unsupported(
+            "Serial transport requires 'serial' feature to be enabled".to_string(),
+        )),
         #[cfg(feature = "serial")]
         HostSpecifier::Emulator => {
             // Build fw-emu binary
@@ -99,17 +110,22 @@ pub fn client_connect(spec: HostSpecifier) -> Result<Box<dyn ClientTransport>> {
                     .with_target("riscv32imac-unknown-none-elf")
                     .with_profile("release"),
             )
-            .map_err(|e| anyhow::anyhow!("Failed to build fw-emu: {e}"))?;
+            .map_err(|e| connection_failed("emulator", format!("Failed to build fw-emu: {e}")))?;
 
             // Load ELF
-            let elf_data = std::fs::read(&fw_emu_path).context("Failed to read fw-emu ELF")?;
-            let load_info =
-                load_elf(&elf_data).map_err(|e| anyhow::anyhow!("Failed to load ELF: {e}"))?;
+            let elf_data = std::fs::read(&fw_emu_path).map_err(|e| CliError::Io {
+                context: "Failed to read fw-emu ELF".to_string(),
+                source: e,
+            })?;
+            let load_info = load_elf(&elf_data)
+                .map_err(|e| connection_failed("emulator", format!("Failed to load ELF: {e}")))?;
 
             // Create emulator with real time mode
             // Use a higher instruction limit for complex scenes (100M instructions)
             let ram_size = load_info.ram.len();
-            let mut emulator = Riscv32Emulator::new(load_info.code, load_info.ram)
+            let entry_point = load_info.entry_point;
+            let mut emulator = load_info
+                .into_emulator()
                 .with_log_level(LogLevel::None)
                 .with_time_mode(TimeMode::RealTime);
 
@@ -118,21 +134,29 @@ pub fn client_connect(spec: HostSpecifier) -> Result<Box<dyn ClientTransport>> {
             emulator.set_register(Gpr::Sp, sp_value as i32);
 
             // Set PC to entry point
-            emulator.set_pc(load_info.entry_point);
+            emulator.set_pc(entry_point);
 
             // Create shared emulator reference
             let emulator_arc = Arc::new(Mutex::new(emulator));
 
             // Create async serial transport
             let transport = create_emulator_serial_transport_pair(emulator_arc)
-                .map_err(|e| anyhow::anyhow!("Failed to create emulator serial transport: {e}"))?;
+                .map_err(|e| connection_failed("emulator", e))?;
 
             Ok(Box::new(transport))
         }
         #[cfg(not(feature = "serial"))]
-        HostSpecifier::Emulator => {
-            bail!("Emulator transport requires 'serial' feature to be enabled");
-        }
+        HostSpecifier::Emulator => Err(CliError::Unsupported(
+            "Emulator transport requires 'serial' feature to be enabled".to_string(),
+        )),
+    }
+}
+
+/// Wrap a transport setup failure for `target`
+fn connection_failed(target: &str, reason: impl core::fmt::Display) -> CliError {
+    CliError::ConnectionFailed {
+        target: target.to_string(),
+        reason: format!("{reason:#}"),
     }
 }
 
@@ -162,6 +186,19 @@ mod tests {
         let _ = result;
     }
 
+    #[test]
+    fn test_client_connect_websocket_refused() {
+        // Port 1 is privileged and never has a server in test environments
+        let spec = HostSpecifier::parse("ws://127.0.0.1:1/").unwrap();
+        match client_connect(spec) {
+            Err(CliError::ConnectionRefused { target }) => {
+                assert_eq!(target, "ws://127.0.0.1:1/");
+            }
+            Err(other) => panic!("Expected ConnectionRefused, got {other:?}"),
+            Ok(_) => panic!("Expected connection to be refused"),
+        }
+    }
+
     #[test]
     #[cfg(feature = "serial")]
     fn test_client_connect_serial_auto() {
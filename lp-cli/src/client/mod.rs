@@ -4,7 +4,9 @@ pub use lp_client::*;
 // CLI-specific modules
 pub mod client_connect;
 pub mod local_server;
+pub mod resolve_host;
 pub mod serial_port;
 
 // Re-export CLI-specific types
 pub use client_connect::client_connect;
+pub use resolve_host::resolve_host;
@@ -0,0 +1,32 @@
+//! Host specifier resolution for CLI arguments
+//!
+//! Extends `HostSpecifier::parse` with `mdns`, which finds a server on the local
+//! network instead of naming one.
+
+use anyhow::{Result, bail};
+use lp_client::HostSpecifier;
+use lp_client::discovery::{DEFAULT_DISCOVERY_TIMEOUT, discover};
+
+/// Host argument that triggers mDNS discovery
+pub const MDNS_HOST: &str = "mdns";
+
+/// Turn a `--host`/`--push` argument into a host specifier
+///
+/// `mdns` browses the network and picks the first server found (listing any others);
+/// anything else is parsed with `HostSpecifier::parse`.
+pub fn resolve_host(host: &str) -> Result<HostSpecifier> {
+    if host.trim() != MDNS_HOST {
+        return HostSpecifier::parse(host);
+    }
+
+    let mut found = discover(DEFAULT_DISCOVERY_TIMEOUT)?.into_iter();
+    let Some(first) = found.next() else {
+        bail!("No LightPlayer servers found via mDNS. Is the server running on this network?");
+    };
+    // Status goes to stderr so stdout stays clean for --json output
+    eprintln!("Found server via mDNS: {first}");
+    for other in found {
+        eprintln!("  (also found {other})");
+    }
+    Ok(first)
+}
@@ -10,7 +10,8 @@ use lp_model::nodes::{
     NodeSpecifier, fixture::FixtureConfig, output::OutputConfig, shader::ShaderConfig,
     texture::TextureConfig,
 };
-use lp_model::project::config::ProjectConfig;
+use lp_model::project::config::{PROJECT_CONFIG_VERSION, ProjectConfig};
+use lp_model::project::parse_project_config;
 use lp_model::{AsLpPath, AsLpPathBuf};
 use lp_shared::fs::LpFs;
 
@@ -120,8 +121,11 @@ pub fn create_project_structure(dir: &Path, name: Option<&str>, uid: Option<&str
 
     // Write project.json
     let config = ProjectConfig {
+        version: PROJECT_CONFIG_VERSION,
         uid: project_uid.clone(),
         name: project_name.clone(),
+        seed: None,
+        automation: Vec::new(),
     };
     let project_json =
         serde_json::to_string_pretty(&config).context("Failed to serialize project.json")?;
@@ -143,6 +147,10 @@ pub fn create_default_template(fs: &dyn LpFs) -> Result<()> {
     let texture_config = TextureConfig {
         width: 64,
         height: 64,
+        image_path: None,
+        fill_color: None,
+        filter: None,
+        enabled: None,
     };
     let texture_json = serde_json::to_string_pretty(&texture_config)
         .context("Failed to serialize texture config")?;
@@ -157,6 +165,13 @@ pub fn create_default_template(fs: &dyn LpFs) -> Result<()> {
         glsl_path: "main.glsl".as_path_buf(),
         texture_spec: NodeSpecifier::from("/src/main.texture"),
         render_order: 0,
+        uniforms: Vec::new(),
+        viewport: None,
+        loop_fuel: None,
+        backend: None,
+        enabled: None,
+        palette: Vec::new(),
+        error_color: None,
     };
     let shader_json = serde_json::to_string_pretty(&shader_config)
         .context("Failed to serialize shader config")?;
@@ -232,7 +247,12 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
     .map_err(|e| anyhow::anyhow!("Failed to write shader main.glsl: {e}"))?;
 
     // Create output node
-    let output_config = OutputConfig::GpioStrip { pin: 4 };
+    let output_config = OutputConfig::GpioStrip {
+        pin: 4,
+        dither: None,
+        power_limit: None,
+        slew_limit: None,
+    };
     let output_json = serde_json::to_string_pretty(&output_config)
         .context("Failed to serialize output config")?;
     fs.write_file(
@@ -258,6 +278,12 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
         ],
         brightness: None,
         gamma_correction: None,
+        universe: None,
+        channel_offset: None,
+        enabled: None,
+        color_correction: None,
+        mask: None,
+        white_extraction: None,
     };
     let fixture_json = serde_json::to_string_pretty(&fixture_config)
         .context("Failed to serialize fixture config")?;
@@ -273,8 +299,8 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
 /// Print success message with next steps
 pub fn print_success_message(dir: &Path, name: &str) {
     // Read uid from project.json
-    let uid = if let Ok(config) = std::fs::read_to_string(dir.join("project.json")) {
-        match serde_json::from_str::<ProjectConfig>(&config) {
+    let uid = if let Ok(config) = std::fs::read(dir.join("project.json")) {
+        match parse_project_config(&config) {
             Ok(project_config) => project_config.uid,
             Err(_) => "unknown".to_string(),
         }
@@ -353,6 +379,7 @@ mod tests {
         .unwrap();
         assert_eq!(config.name, "Custom Name");
         assert_eq!(config.uid, "custom-uid");
+        assert_eq!(config.version, PROJECT_CONFIG_VERSION);
     }
 
     #[test]
@@ -423,6 +450,10 @@ mod tests {
         let texture_config = TextureConfig {
             width: 64,
             height: 64,
+            image_path: None,
+            fill_color: None,
+            filter: None,
+            enabled: None,
         };
         let texture_json = serde_json::to_string_pretty(&texture_config)
             .context("Failed to serialize texture config")?;
@@ -437,6 +468,13 @@ mod tests {
             glsl_path: "main.glsl".as_path_buf(),
             texture_spec: NodeSpecifier::from("/src/main.texture"),
             render_order: 0,
+            uniforms: Vec::new(),
+            viewport: None,
+            loop_fuel: None,
+            backend: None,
+            enabled: None,
+            palette: Vec::new(),
+            error_color: None,
         };
         let shader_json = serde_json::to_string_pretty(&shader_config)
             .context("Failed to serialize shader config")?;
@@ -512,7 +550,12 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
         .map_err(|e| anyhow::anyhow!("Failed to write shader main.glsl: {e}"))?;
 
         // Create output node
-        let output_config = OutputConfig::GpioStrip { pin: 4 };
+        let output_config = OutputConfig::GpioStrip {
+            pin: 4,
+            dither: None,
+            power_limit: None,
+            slew_limit: None,
+        };
         let output_json = serde_json::to_string_pretty(&output_config)
             .context("Failed to serialize output config")?;
         fs.write_file_mut(
@@ -538,6 +581,12 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
             ],
             brightness: None,
             gamma_correction: None,
+            universe: None,
+            channel_offset: None,
+            enabled: None,
+            color_correction: None,
+            mask: None,
+            white_extraction: None,
         };
         let fixture_json = serde_json::to_string_pretty(&fixture_config)
             .context("Failed to serialize fixture config")?;
@@ -4,15 +4,16 @@
 
 use anyhow::{Context, Result};
 use lp_model::AsLpPath;
-use lp_model::project::ProjectConfig;
+use lp_model::project::parse_project_config;
 use lp_shared::fs::{LpFs, LpFsStd};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::signal;
 
-use crate::client::{LpClient, client_connect};
+use crate::client::{LpClient, client_connect, resolve_host};
 use crate::commands::dev::{fs_loop, push_project_async};
 use crate::debug_ui::DebugUiState;
+use crate::error::CliError;
 use lp_client::HostSpecifier;
 
 use super::args::DevArgs;
@@ -27,26 +28,21 @@ use super::args::DevArgs;
 ///
 /// * `Ok((project_uid, project_name))` if project is valid
 /// * `Err` if project.json is missing or invalid
-fn validate_local_project(project_dir: &PathBuf) -> Result<(String, String)> {
+fn validate_local_project(project_dir: &PathBuf) -> Result<(String, String), CliError> {
     // Create filesystem for reading project.json
     let fs = LpFsStd::new(project_dir.clone());
 
     // Read and parse project.json
-    let data = fs.read_file("/project.json".as_path()).map_err(|e| {
-        anyhow::anyhow!(
-            "Failed to read project.json from: {}\n\
-             Error: {}\n\
-             Make sure you're in a project directory or specify the project directory with --dir",
-            project_dir.display(),
-            e
-        )
-    })?;
+    let data = fs
+        .read_file("/project.json".as_path())
+        .map_err(|e| CliError::ProjectNotFound {
+            path: project_dir.clone(),
+            reason: e.to_string(),
+        })?;
 
-    let config: ProjectConfig = serde_json::from_slice(&data).with_context(|| {
-        format!(
-            "Failed to parse project.json from: {}",
-            project_dir.display()
-        )
+    let config = parse_project_config(&data).map_err(|e| CliError::InvalidProject {
+        path: project_dir.clone(),
+        reason: e.to_string(),
     })?;
 
     Ok((config.uid.clone(), config.name.clone()))
@@ -74,7 +70,7 @@ pub fn handle_dev(mut args: DevArgs) -> Result<()> {
     // Default behavior: push to local server (equivalent to --push without argument)
     let host_spec = if let Some(Some(host)) = &args.push_host {
         // Push to specified host
-        HostSpecifier::parse(host)?
+        resolve_host(host)?
     } else {
         // Default: push to local in-memory server
         // This covers both --push (without argument) and no --push flag
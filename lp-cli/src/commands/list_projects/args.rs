@@ -0,0 +1,4 @@
+pub struct ListProjectsArgs {
+    pub host: Option<String>,
+    pub json: bool,
+}
@@ -0,0 +1,83 @@
+//! List projects command handler
+//!
+//! Queries a server for the projects it has on disk and the ones currently loaded.
+
+use anyhow::{Context, Result};
+use lp_client::HostSpecifier;
+use lp_model::server::{AvailableProject, LoadedProject};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::args::ListProjectsArgs;
+use crate::client::{ClientTransport, LpClient, client_connect, resolve_host};
+use crate::messages::print_json;
+
+/// Projects known to a server, as printed by `list-projects --json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectList {
+    /// Projects in the server's projects directory
+    pub available: Vec<AvailableProject>,
+    /// Projects currently loaded and running
+    pub loaded: Vec<LoadedProject>,
+}
+
+/// Handle the list-projects command
+///
+/// Connects to the host (the in-memory server by default) and prints its projects.
+pub fn handle_list_projects(args: ListProjectsArgs) -> Result<()> {
+    let host_spec = match &args.host {
+        Some(host) => resolve_host(host)?,
+        None => HostSpecifier::Local,
+    };
+
+    // Connect before entering the runtime: websocket connections start their own
+    let transport = client_connect(host_spec).context("Failed to connect to server")?;
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let projects = runtime.block_on(list_projects(transport))?;
+
+    if args.json {
+        return print_json(&projects);
+    }
+
+    if projects.available.is_empty() {
+        println!("No projects available");
+    } else {
+        println!("Available projects:");
+        for project in &projects.available {
+            println!("  {}", project.path.as_str());
+        }
+    }
+    if !projects.loaded.is_empty() {
+        println!("\nLoaded projects:");
+        for project in &projects.loaded {
+            println!("  [{}] {}", project.handle.id(), project.path.as_str());
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch the available and loaded projects from a connected server
+pub async fn list_projects(transport: Box<dyn ClientTransport>) -> Result<ProjectList> {
+    let shared_transport = Arc::new(tokio::sync::Mutex::new(transport));
+    let client = LpClient::new_shared(Arc::clone(&shared_transport));
+
+    let result = async {
+        let available = client
+            .project_list_available()
+            .await
+            .context("Failed to list available projects")?;
+        let loaded = client
+            .project_list_loaded()
+            .await
+            .context("Failed to list loaded projects")?;
+        Ok(ProjectList { available, loaded })
+    }
+    .await;
+
+    // Close even on failure so a local server thread shuts down
+    let _ = shared_transport.lock().await.close().await;
+
+    result
+}
@@ -0,0 +1,5 @@
+pub mod args;
+pub mod handler;
+
+pub use args::ListProjectsArgs;
+pub use handler::{ProjectList, handle_list_projects, list_projects};
@@ -1,3 +1,6 @@
 pub mod create;
 pub mod dev;
+pub mod list_projects;
+pub mod render;
+pub mod repl;
 pub mod serve;
@@ -0,0 +1,11 @@
+use std::path::PathBuf;
+
+pub struct RenderArgs {
+    pub dir: PathBuf,
+    /// Directory the texture images are written to
+    pub out: PathBuf,
+    /// Project time to render at, in milliseconds
+    pub time_ms: u32,
+    /// If true, render again whenever the project's files change
+    pub watch: bool,
+}
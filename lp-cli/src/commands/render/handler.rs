@@ -0,0 +1,35 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+use super::args::RenderArgs;
+use super::render_project::render_project;
+use super::watch::watch_project;
+
+pub fn handle_render(args: RenderArgs) -> Result<()> {
+    if !args.watch {
+        let written = render_project(&args.dir, &args.out, args.time_ms)?;
+        print_written(&written);
+        return Ok(());
+    }
+
+    let runtime = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+    runtime.block_on(watch_project(
+        &args.dir,
+        &args.out,
+        args.time_ms,
+        |result| {
+            match result {
+                Ok(written) => print_written(&written),
+                // Keep watching so the next save can fix it
+                Err(e) => eprintln!("✗ {e:#}"),
+            }
+            true
+        },
+    ))
+}
+
+fn print_written(written: &[PathBuf]) {
+    for path in written {
+        println!("✓ Wrote {}", path.display());
+    }
+}
@@ -0,0 +1,9 @@
+pub mod args;
+pub mod handler;
+pub mod render_project;
+pub mod watch;
+
+pub use args::RenderArgs;
+pub use handler::handle_render;
+pub use render_project::render_project;
+pub use watch::watch_project;
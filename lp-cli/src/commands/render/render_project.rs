@@ -0,0 +1,115 @@
+//! Offline rendering
+//!
+//! Loads a project from disk, renders a single frame without a server or output
+//! hardware, and writes each texture as a PNG image.
+
+use anyhow::{Context, Result};
+use lp_engine::nodes::TextureRuntime;
+use lp_engine::project::NodeStatus;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::NodeKind;
+use lp_shared::Texture;
+use lp_shared::fs::LpFsStd;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Render the project in `project_dir` at `time_ms` and write its textures to `out_dir`
+///
+/// Returns the paths of the written images. Nodes left in an error state, such as a
+/// shader that fails to compile, fail the render with their messages.
+pub fn render_project(project_dir: &Path, out_dir: &Path, time_ms: u32) -> Result<Vec<PathBuf>> {
+    let fs = Rc::new(RefCell::new(LpFsStd::new(project_dir.to_path_buf())));
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs, output_provider)
+        .map_err(|e| anyhow::anyhow!("Failed to load project: {e}"))?;
+    runtime
+        .load_nodes()
+        .map_err(|e| anyhow::anyhow!("Failed to load nodes: {e}"))?;
+    runtime
+        .init_nodes()
+        .map_err(|e| anyhow::anyhow!("Failed to initialize nodes: {e}"))?;
+    runtime
+        .ensure_all_nodes_initialized()
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    runtime
+        .tick(time_ms)
+        .map_err(|e| anyhow::anyhow!("Failed to render frame: {e}"))?;
+
+    let errors: Vec<String> = runtime
+        .nodes
+        .values()
+        .filter_map(|entry| match &entry.status {
+            NodeStatus::Error(msg) | NodeStatus::InitError(msg) => {
+                Some(format!("{}: {msg}", entry.path.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+    if !errors.is_empty() {
+        anyhow::bail!("Some nodes failed to render:\n  {}", errors.join("\n  "));
+    }
+
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create directory: {}", out_dir.display()))?;
+
+    let mut written = Vec::new();
+    for entry in runtime.nodes.values() {
+        if entry.kind != NodeKind::Texture {
+            continue;
+        }
+        let Some(texture) = entry
+            .runtime
+            .as_ref()
+            .and_then(|runtime| runtime.as_any().downcast_ref::<TextureRuntime>())
+            .and_then(TextureRuntime::texture)
+        else {
+            continue;
+        };
+        let path = out_dir.join(image_file_name(entry.path.as_str()));
+        write_png(texture, &path)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// File name for a texture node's image, e.g. `src_main.texture.png` for
+/// `/src/main.texture`
+fn image_file_name(node_path: &str) -> String {
+    format!(
+        "{}.png",
+        node_path.trim_start_matches('/').replace('/', "_")
+    )
+}
+
+/// Write `texture` to `path` as an 8-bit RGBA PNG
+fn write_png(texture: &Texture, path: &Path) -> Result<()> {
+    let (width, height) = (texture.width(), texture.height());
+    let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height {
+        for x in 0..width {
+            rgba.extend_from_slice(&texture.get_pixel(x, y).unwrap_or_default());
+        }
+    }
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&rgba))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_file_name() {
+        assert_eq!(image_file_name("/src/main.texture"), "src_main.texture.png");
+    }
+}
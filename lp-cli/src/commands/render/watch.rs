@@ -0,0 +1,85 @@
+//! Re-rendering on file changes
+//!
+//! Watches the project directory with the same watcher and debouncing as `dev`, and
+//! renders again once a batch of changes settles.
+
+use anyhow::{Context, Result};
+use lp_shared::fs::fs_event::FsChange;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+use crate::commands::dev::fs_loop::{DEBOUNCE_DURATION, add_pending_change};
+use crate::commands::dev::watcher::FileWatcher;
+use crate::commands::render::render_project;
+
+/// Render the project in `project_dir` now and again after every change to its files
+///
+/// Each render's result, failures included, goes to `on_render`, which returns whether
+/// to keep watching. A failed render (e.g. a shader that no longer compiles) does not
+/// stop the watch, so the next save can fix it.
+pub async fn watch_project(
+    project_dir: &Path,
+    out_dir: &Path,
+    time_ms: u32,
+    mut on_render: impl FnMut(Result<Vec<PathBuf>>) -> bool,
+) -> Result<()> {
+    // Watch before the first render so changes made during it are not missed
+    let mut watcher =
+        FileWatcher::new(project_dir.to_path_buf()).context("Failed to create file watcher")?;
+
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create directory: {}", out_dir.display()))?;
+    let ignored_prefix = output_prefix(project_dir, out_dir);
+
+    if !on_render(render_project(project_dir, out_dir, time_ms)) {
+        return Ok(());
+    }
+
+    // Debouncing state
+    let mut pending_changes: HashMap<String, FsChange> = HashMap::new();
+    let mut last_change_time: Option<Instant> = None;
+
+    loop {
+        tokio::select! {
+            change = watcher.next_change() => {
+                let Some(change) = change else {
+                    anyhow::bail!("File watcher channel closed");
+                };
+                // Our own images don't change the project
+                let is_output = ignored_prefix
+                    .as_deref()
+                    .is_some_and(|prefix| change.path.as_str().starts_with(prefix));
+                if !is_output {
+                    add_pending_change(&mut pending_changes, &mut last_change_time, change);
+                }
+            }
+            // Timeout for debounce checking
+            _ = sleep(Duration::from_millis(50)) => {}
+        }
+
+        let should_render = last_change_time
+            .is_some_and(|last_time| last_time.elapsed() >= DEBOUNCE_DURATION)
+            && !pending_changes.is_empty();
+
+        if should_render {
+            pending_changes.clear();
+            last_change_time = None;
+            if !on_render(render_project(project_dir, out_dir, time_ms)) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Project path prefix (e.g. `/render/`) of `out_dir` when it lies inside the project
+fn output_prefix(project_dir: &Path, out_dir: &Path) -> Option<String> {
+    let project_dir = project_dir.canonicalize().ok()?;
+    let out_dir = out_dir.canonicalize().ok()?;
+    let relative = out_dir.strip_prefix(&project_dir).ok()?;
+    Some(format!(
+        "/{}/",
+        relative.to_string_lossy().replace('\\', "/")
+    ))
+}
@@ -0,0 +1,3 @@
+pub struct ReplArgs {
+    pub host: Option<String>,
+}
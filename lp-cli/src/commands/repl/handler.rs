@@ -0,0 +1,209 @@
+//! REPL command handler
+//!
+//! Reads commands line by line, sends the matching requests through `LpClient`, and
+//! prints the responses. Meant for poking at a live server while debugging.
+
+use anyhow::{Context, Result, bail};
+use lp_client::HostSpecifier;
+use lp_model::nodes::shader::{ShaderConfig, UniformConfig};
+use lp_model::project::ProjectHandle;
+use lp_model::project::api::{ApiNodeSpecifier, NodeChange, SerializableProjectResponse};
+use lp_model::{AsLpPath, LpPathBuf, NodeHandle};
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+
+use super::args::ReplArgs;
+use crate::client::{LpClient, client_connect, resolve_host};
+
+const HELP: &str = "\
+Commands:
+  list                              List available and loaded projects
+  load <path>                       Load a project and make it current
+  nodes                             List the current project's nodes
+  node <handle>                     Show detail for a node in the current project
+  set-uniform <shader> <name> <v>   Set a uniform in a shader's config (e.g. /src/main.shader)
+  help                              Show this help
+  quit                              Exit";
+
+/// Handle the repl command
+///
+/// Connects to the host (the in-memory server by default) and reads commands from stdin.
+pub fn handle_repl(args: ReplArgs) -> Result<()> {
+    let host_spec = match &args.host {
+        Some(host) => resolve_host(host)?,
+        None => HostSpecifier::Local,
+    };
+
+    // Connect before entering the runtime: websocket connections start their own
+    let transport = client_connect(host_spec).context("Failed to connect to server")?;
+    let shared_transport = Arc::new(tokio::sync::Mutex::new(transport));
+    let client = LpClient::new_shared(Arc::clone(&shared_transport));
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let result = run_repl(&client, std::io::stdin().lock(), &mut std::io::stdout()).await;
+        let _ = shared_transport.lock().await.close().await;
+        result
+    })
+}
+
+/// Run the REPL until `quit` or end of input
+///
+/// Request failures are printed and the loop continues; only I/O errors on `input` or
+/// `output` end it early.
+pub async fn run_repl(
+    client: &LpClient,
+    input: impl BufRead,
+    output: &mut dyn Write,
+) -> Result<()> {
+    let mut repl = Repl {
+        client,
+        current: None,
+    };
+
+    writeln!(output, "Type 'help' for a list of commands")?;
+    write!(output, "> ")?;
+    output.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["quit"] | ["exit"] => break,
+            ["help"] => writeln!(output, "{HELP}")?,
+            words => {
+                if let Err(e) = repl.execute(words, output).await {
+                    writeln!(output, "Error: {e:#}")?;
+                }
+            }
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+
+    writeln!(output)?;
+    Ok(())
+}
+
+/// REPL session state
+struct Repl<'a> {
+    client: &'a LpClient,
+    /// Project selected by the last `load`
+    current: Option<(ProjectHandle, String)>,
+}
+
+impl Repl<'_> {
+    /// Run one command (other than `help`/`quit`)
+    async fn execute(&mut self, words: &[&str], output: &mut dyn Write) -> Result<()> {
+        match words {
+            ["list"] => {
+                let available = self.client.project_list_available().await?;
+                let loaded = self.client.project_list_loaded().await?;
+                writeln!(output, "Available projects:")?;
+                for project in &available {
+                    writeln!(output, "  {}", project.path.as_str())?;
+                }
+                writeln!(output, "Loaded projects:")?;
+                for project in &loaded {
+                    writeln!(
+                        output,
+                        "  [{}] {}",
+                        project.handle.id(),
+                        project.path.as_str()
+                    )?;
+                }
+            }
+            ["load", path] => {
+                let handle = self.client.project_load(path).await?;
+                writeln!(output, "Loaded project {path} [{}]", handle.id())?;
+                self.current = Some((handle, path.to_string()));
+            }
+            ["nodes"] => {
+                let handle = self.current_handle()?;
+                let response = self
+                    .client
+                    .project_sync_internal(handle, None, ApiNodeSpecifier::None)
+                    .await?;
+                let SerializableProjectResponse::GetChanges { node_changes, .. } = response;
+                for change in node_changes {
+                    if let NodeChange::Created { handle, path, kind } = change {
+                        writeln!(output, "  [{}] {kind:?} {}", handle.as_i32(), path.as_str())?;
+                    }
+                }
+            }
+            ["node", node] => {
+                let handle = self.current_handle()?;
+                let node = NodeHandle::new(
+                    node.parse()
+                        .with_context(|| format!("Invalid node handle: {node}"))?,
+                );
+                let response = self
+                    .client
+                    .project_sync_internal(handle, None, ApiNodeSpecifier::ByHandles(vec![node]))
+                    .await?;
+                let SerializableProjectResponse::GetChanges { node_details, .. } = response;
+                match node_details.iter().find(|(handle, _)| *handle == node) {
+                    Some((_, detail)) => writeln!(output, "{detail:#?}")?,
+                    None => bail!("No node with handle {}", node.as_i32()),
+                }
+            }
+            ["set-uniform", shader, name, value] => {
+                let value: f32 = value
+                    .parse()
+                    .with_context(|| format!("Invalid uniform value: {value}"))?;
+                self.set_uniform(shader, name, value).await?;
+                writeln!(output, "Set {name} = {value} on {shader}")?;
+            }
+            [command, ..] => {
+                writeln!(output, "Unknown command: {command}")?;
+                writeln!(output, "{HELP}")?;
+            }
+            [] => {}
+        }
+        Ok(())
+    }
+
+    /// Handle of the current project, or an error if nothing has been loaded
+    fn current_handle(&self) -> Result<ProjectHandle> {
+        match &self.current {
+            Some((handle, _)) => Ok(*handle),
+            None => bail!("No project loaded; use 'load <path>' first"),
+        }
+    }
+
+    /// Update a uniform in a shader's `node.json` on the server
+    ///
+    /// The server picks up the config change like any other file edit.
+    async fn set_uniform(&self, shader: &str, name: &str, value: f32) -> Result<()> {
+        let Some((_, project_path)) = &self.current else {
+            bail!("No project loaded; use 'load <path>' first");
+        };
+        let config_path = LpPathBuf::from(format!(
+            "/{}/{}/node.json",
+            project_path.trim_matches('/'),
+            shader.trim_matches('/')
+        ));
+
+        let data = self.client.fs_read(config_path.as_path()).await?;
+        let mut config: ShaderConfig = lp_model::json::from_slice(&data)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e}", config_path.as_str()))?;
+        match config
+            .uniforms
+            .iter_mut()
+            .find(|uniform| uniform.name == name)
+        {
+            Some(uniform) => uniform.value = value,
+            None => config.uniforms.push(UniformConfig {
+                name: name.to_string(),
+                value,
+            }),
+        }
+
+        let json = lp_model::json::to_string(&config)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize shader config: {e}"))?;
+        self.client
+            .fs_write(config_path.as_path(), json.into_bytes())
+            .await
+    }
+}
@@ -0,0 +1,5 @@
+pub mod args;
+pub mod handler;
+
+pub use args::ReplArgs;
+pub use handler::{handle_repl, run_repl};
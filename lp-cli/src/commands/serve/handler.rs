@@ -3,6 +3,7 @@
 //! Orchestrates the serve command execution.
 
 use anyhow::Result;
+use lp_client::ServiceAdvertisement;
 use std::path::PathBuf;
 
 use super::args::ServeArgs;
@@ -26,6 +27,18 @@ pub fn handle_serve(args: ServeArgs) -> Result<()> {
         .map_err(|e| anyhow::anyhow!("Failed to start websocket server: {e}"))?;
 
     println!("Server started on ws://localhost:2812/");
+
+    // Let clients find the server with `--host mdns`; serving works without it
+    let _advertisement = match ServiceAdvertisement::new("lightplayer", 2812) {
+        Ok(advertisement) => {
+            println!("Advertising as {}", advertisement.fullname());
+            Some(advertisement)
+        }
+        Err(e) => {
+            eprintln!("Warning: mDNS advertisement unavailable: {e:#}");
+            None
+        }
+    };
     println!("Press Ctrl+C to stop");
 
     // Run server loop (blocks until interrupted)
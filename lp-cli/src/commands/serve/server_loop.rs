@@ -31,10 +31,10 @@ pub fn run_server_loop<T: ServerTransport>(mut server: LpServer, mut transport:
         // Collect incoming messages from all connections (non-blocking)
         let mut incoming_messages = Vec::new();
         loop {
-            match transport.receive() {
-                Ok(Some(client_msg)) => {
+            match transport.receive_from() {
+                Ok(Some((connection, client_msg))) => {
                     // Wrap in Message envelope
-                    incoming_messages.push(Message::Client(client_msg));
+                    incoming_messages.push((connection, Message::Client(client_msg)));
                 }
                 Ok(None) => {
                     // No more messages available
@@ -61,16 +61,21 @@ pub fn run_server_loop<T: ServerTransport>(mut server: LpServer, mut transport:
 
         // Always tick the server to advance frames, even if there are no messages
         // This ensures continuous frame progression at ~60 FPS
-        match server.tick(delta_ms.max(1), incoming_messages) {
+        // Drop the state of clients that went away, so their ids don't linger
+        for connection in transport.take_closed() {
+            server.disconnect(connection);
+        }
+
+        match server.tick_from(delta_ms.max(1), incoming_messages) {
             Ok(responses) => {
                 // Record frame processing time (in microseconds)
                 let frame_time_us = tick_start.elapsed().as_micros() as u64;
                 server.set_last_frame_time(frame_time_us);
 
                 // Send responses back via transport
-                for response in responses {
+                for (connection, response) in responses {
                     if let Message::Server(server_msg) = response {
-                        if let Err(e) = transport.send(server_msg) {
+                        if let Err(e) = transport.send_to(connection, server_msg) {
                             eprintln!("Failed to send response: {e}");
                         }
                     }
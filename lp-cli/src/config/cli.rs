@@ -0,0 +1,125 @@
+//! CLI defaults configuration
+//!
+//! Defaults for command-line flags, read from the user config
+//! (`~/.config/lp/config.toml`) and a project-local `.lp.toml`. Project-local
+//! values override user values, and flags given on the command line override both.
+//!
+//! ```toml
+//! host = "ws://localhost:2812/"
+//! dir = "my-project"
+//! push = true
+//! ```
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Name of the project-local config file
+pub const PROJECT_CONFIG_FILE: &str = ".lp.toml";
+
+/// Defaults for command-line flags; unset fields leave the command's own default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    /// Server host used when `--host`/`--push HOST` is omitted
+    pub host: Option<String>,
+    /// Project directory used when `dev` or `render` is run without one
+    pub dir: Option<PathBuf>,
+    /// Whether `dev` pushes to `host` when `--push` is omitted
+    pub push: Option<bool>,
+}
+
+impl CliConfig {
+    /// Combine with `other`, preferring its values where both are set
+    pub fn merge(self, other: CliConfig) -> CliConfig {
+        CliConfig {
+            host: other.host.or(self.host),
+            dir: other.dir.or(self.dir),
+            push: other.push.or(self.push),
+        }
+    }
+}
+
+/// Path of the user config file (`$XDG_CONFIG_HOME/lp/config.toml`, falling back to
+/// `~/.config/lp/config.toml`)
+pub fn user_config_path() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("lp").join("config.toml"))
+}
+
+/// Load a single config file
+///
+/// # Returns
+///
+/// * `Ok(None)` if the file doesn't exist
+/// * `Err` if the file can't be read or parsed
+pub fn load_cli_config_file(path: &Path) -> Result<Option<CliConfig>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let config =
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(config))
+}
+
+/// Load and merge the user config and the `.lp.toml` in `project_dir`
+///
+/// Directories in a project-local config are resolved relative to `project_dir`.
+pub fn load_cli_config(user_config: Option<&Path>, project_dir: &Path) -> Result<CliConfig> {
+    let user = match user_config {
+        Some(path) => load_cli_config_file(path)?.unwrap_or_default(),
+        None => CliConfig::default(),
+    };
+
+    let mut project =
+        load_cli_config_file(&project_dir.join(PROJECT_CONFIG_FILE))?.unwrap_or_default();
+    project.dir = project.dir.map(|dir| project_dir.join(dir));
+
+    Ok(user.merge(project))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_project_config_overrides_user_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let user_path = temp_dir.path().join("config.toml");
+        fs::write(&user_path, "host = \"ws://user:2812/\"\npush = true\n").unwrap();
+        fs::write(
+            temp_dir.path().join(PROJECT_CONFIG_FILE),
+            "host = \"ws://project:2812/\"\ndir = \"proj\"\n",
+        )
+        .unwrap();
+
+        let config = load_cli_config(Some(&user_path), temp_dir.path()).unwrap();
+        assert_eq!(config.host.as_deref(), Some("ws://project:2812/"));
+        assert_eq!(config.dir, Some(temp_dir.path().join("proj")));
+        assert_eq!(config.push, Some(true));
+    }
+
+    #[test]
+    fn test_missing_config_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let user_path = temp_dir.path().join("missing.toml");
+
+        let config = load_cli_config(Some(&user_path), temp_dir.path()).unwrap();
+        assert_eq!(config, CliConfig::default());
+    }
+
+    #[test]
+    fn test_unknown_key_is_error() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(PROJECT_CONFIG_FILE), "hots = \"x\"\n").unwrap();
+
+        let result = load_cli_config(None, temp_dir.path());
+        assert!(result.unwrap_err().to_string().contains("Failed to parse"));
+    }
+}
@@ -1,3 +1,5 @@
+pub mod cli;
 pub mod server;
 
+pub use cli::{CliConfig, load_cli_config};
 pub use lp_model::server::ServerConfig;
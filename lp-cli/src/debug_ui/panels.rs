@@ -150,6 +150,12 @@ pub fn render_all_nodes_panel(
                     (NodeKind::Output, lp_model::project::api::NodeState::Output(output_state)) => {
                         output::render_output_panel(ui, entry, output_state);
                     }
+                    (NodeKind::Group, lp_model::project::api::NodeState::Group(group_state)) => {
+                        ui.label(format!(
+                            "Layer: {}x{}",
+                            group_state.width, group_state.height
+                        ));
+                    }
                     _ => {
                         // Mismatch between kind and state - shouldn't happen but handle gracefully
                         ui.label(format!("State type mismatch for {:?}", entry.path));
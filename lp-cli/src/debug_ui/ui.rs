@@ -271,7 +271,7 @@ impl eframe::App for DebugUiState {
             if current_frame_id != prev_frame_id {
                 // Server frame advanced - calculate FPS
                 if let Some(prev_time) = self.last_server_frame_time {
-                    let frame_delta = current_frame_id.as_i64() - prev_frame_id.as_i64();
+                    let frame_delta = current_frame_id.diff(prev_frame_id);
                     let time_delta = now.duration_since(prev_time);
                     if frame_delta > 0 && !time_delta.is_zero() {
                         let server_fps = frame_delta as f32 / time_delta.as_secs_f32();
@@ -1,4 +1,84 @@
 //! CLI-specific error types
 //!
-//! For now, we primarily use `anyhow::Result` for error handling.
-//! This module can be extended with CLI-specific error types if needed.
+//! Commands return [`CliError`] internally so callers and tests can tell failure
+//! categories apart. `main` still reports everything through `anyhow`, which wraps
+//! a `CliError` without losing it (`err.downcast_ref::<CliError>()`).
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// Categorized CLI failure
+#[derive(Debug)]
+pub enum CliError {
+    /// Nothing is listening at the server address
+    ConnectionRefused {
+        /// Address that refused the connection
+        target: String,
+    },
+    /// Connecting to the server failed for another reason
+    ConnectionFailed {
+        /// Address or transport that was being connected to
+        target: String,
+        /// What went wrong
+        reason: String,
+    },
+    /// Feature not available in this build
+    Unsupported(String),
+    /// No project at the given directory
+    ProjectNotFound {
+        /// Project directory
+        path: PathBuf,
+        /// What went wrong reading it
+        reason: String,
+    },
+    /// `project.json` exists but couldn't be loaded
+    InvalidProject {
+        /// Project directory
+        path: PathBuf,
+        /// Parse error
+        reason: String,
+    },
+    /// Local filesystem error
+    Io {
+        /// What was being done
+        context: String,
+        /// Underlying error
+        source: std::io::Error,
+    },
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::ConnectionRefused { target } => {
+                write!(f, "Connection refused by {target}; is the server running?")
+            }
+            CliError::ConnectionFailed { target, reason } => {
+                write!(f, "Failed to connect to {target}: {reason}")
+            }
+            CliError::Unsupported(message) => write!(f, "{message}"),
+            CliError::ProjectNotFound { path, reason } => write!(
+                f,
+                "Failed to read project.json from: {}\n\
+                 Error: {reason}\n\
+                 Make sure you're in a project directory or specify the project directory with --dir",
+                path.display()
+            ),
+            CliError::InvalidProject { path, reason } => write!(
+                f,
+                "Failed to parse project.json from: {}\nError: {reason}",
+                path.display()
+            ),
+            CliError::Io { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CliError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
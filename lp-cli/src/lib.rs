@@ -7,6 +7,7 @@
 //! - File watching and synchronization
 //! - Debug UI for development
 
+pub mod cli;
 pub mod client;
 pub mod commands;
 pub mod config;
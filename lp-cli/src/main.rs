@@ -1,6 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
 
+mod cli;
 mod client;
 mod commands;
 mod config;
@@ -9,68 +10,78 @@ mod error;
 mod messages;
 mod server;
 
-use commands::{create, dev, serve};
-
-#[derive(Parser)]
-#[command(name = "lp-cli")]
-#[command(about = "LightPlayer CLI - Server and client modes")]
-enum Cli {
-    /// Run server from a directory
-    Serve {
-        /// Server directory (defaults to current directory)
-        dir: Option<std::path::PathBuf>,
-        /// Initialize server directory (create server.json if missing)
-        #[arg(long)]
-        init: bool,
-        /// Use in-memory filesystem instead of disk
-        #[arg(long)]
-        memory: bool,
-    },
-    /// Connect to server and sync local project
-    Dev {
-        /// Project directory
-        dir: std::path::PathBuf,
-        /// Push local project to server. Optionally specify remote host (e.g., ws://localhost:2812/, serial:auto, or emu).
-        /// If --push is specified without a host, uses in-memory server.
-        #[arg(long, value_name = "HOST")]
-        push: Option<Option<String>>,
-        /// Run without UI (headless mode)
-        #[arg(long)]
-        headless: bool,
-    },
-    /// Create a new project
-    Create {
-        /// Project directory
-        dir: std::path::PathBuf,
-        /// Project name (defaults to directory name)
-        #[arg(long)]
-        name: Option<String>,
-        /// Project UID (auto-generated if not provided)
-        #[arg(long)]
-        uid: Option<String>,
-    },
-}
+use cli::{Cli, Command};
+use commands::{create, dev, list_projects, render, repl, serve};
+use config::cli::{load_cli_config, user_config_path};
 
 fn main() -> Result<()> {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
     let cli = Cli::parse();
+    let config = load_cli_config(
+        user_config_path().as_deref(),
+        &std::env::current_dir().unwrap_or_default(),
+    )?;
 
-    match cli {
-        Cli::Serve { dir, init, memory } => {
+    let result = match cli.command.with_defaults(&config) {
+        Command::Serve { dir, init, memory } => {
             serve::handle_serve(serve::ServeArgs { dir, init, memory })
         }
-        Cli::Dev {
+        Command::Dev {
             dir,
             push,
             headless,
-        } => dev::handle_dev(dev::DevArgs {
+        } => match dir {
+            Some(dir) => dev::handle_dev(dev::DevArgs {
+                dir,
+                push_host: push,
+                headless,
+            }),
+            None => Err(anyhow::anyhow!(
+                "No project directory given; pass one or set `dir` in {}",
+                config::cli::PROJECT_CONFIG_FILE
+            )),
+        },
+        Command::Render {
             dir,
-            push_host: push,
-            headless,
-        }),
-        Cli::Create { dir, name, uid } => {
+            out,
+            time_ms,
+            watch,
+        } => match dir {
+            Some(dir) => render::handle_render(render::RenderArgs {
+                dir,
+                out,
+                time_ms,
+                watch,
+            }),
+            None => Err(anyhow::anyhow!(
+                "No project directory given; pass one or set `dir` in {}",
+                config::cli::PROJECT_CONFIG_FILE
+            )),
+        },
+        Command::Create { dir, name, uid } => {
             create::handle_create(create::CreateArgs { dir, name, uid })
         }
+        Command::ListProjects { host } => {
+            list_projects::handle_list_projects(list_projects::ListProjectsArgs {
+                host,
+                json: cli.json,
+            })
+        }
+        Command::Repl { host } => repl::handle_repl(repl::ReplArgs { host }),
+        Command::Completions { shell } => {
+            cli::write_completions(shell, &mut std::io::stdout());
+            Ok(())
+        }
+    };
+
+    // In JSON mode errors go to stdout as an object too, so scripts only parse one stream
+    if let Err(e) = &result
+        && cli.json
+    {
+        messages::print_json_error(e);
+        std::process::exit(1);
     }
+
+    result
 }
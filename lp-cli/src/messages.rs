@@ -34,6 +34,20 @@ pub fn print_error_and_return(message: &str, suggestions: &[&str]) -> anyhow::Er
     anyhow::anyhow!("{message}")
 }
 
+/// Print a value as pretty JSON on stdout (for `--json` mode)
+pub fn print_json<T: serde::Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Print an error as a JSON object on stdout (for `--json` mode)
+///
+/// The object has a single `error` field holding the full error chain.
+pub fn print_json_error(error: &anyhow::Error) {
+    let value = serde_json::json!({ "error": format!("{error:#}") });
+    println!("{value}");
+}
+
 /// Format command for copy-paste (with proper quoting if needed)
 pub fn format_command(cmd: &str) -> String {
     // Check if command contains spaces and needs quoting
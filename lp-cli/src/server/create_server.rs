@@ -1,8 +1,10 @@
 use crate::commands::serve::init::{create_filesystem, initialize_server};
+use crate::config::ServerConfig;
 use lp_model::AsLpPath;
 use lp_server::LpServer;
 use lp_shared::fs::LpFs;
 use lp_shared::output::MemoryOutputProvider;
+use lp_shared::output::artnet::{ArtNetOutputProvider, StdUdpSink};
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
@@ -31,25 +33,31 @@ pub fn create_server(
     let base_fs = create_filesystem(dir, memory)?;
 
     // Handle server configuration
-    if memory {
+    let config = if memory {
         // For in-memory filesystem, use default config (no file needed)
-        // Config is not actually used, but we need it for LpServer::new
+        ServerConfig::default()
     } else if let Some(init_flag) = init {
         // For disk filesystem, initialize or load config
         let server_dir = dir.unwrap_or_else(|| Path::new("../../.."));
-        initialize_server(server_dir, init_flag)?;
-    }
-    // If init is None, use default config (for backward compatibility)
-
-    // Create output provider
-    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+        initialize_server(server_dir, init_flag)?
+    } else {
+        // If init is None, use default config (for backward compatibility)
+        ServerConfig::default()
+    };
+
+    // Create output provider (Art-Net outputs are sent over UDP, the rest kept in memory)
+    let output_provider = Rc::new(RefCell::new(ArtNetOutputProvider::new(
+        MemoryOutputProvider::new(),
+        StdUdpSink::new(),
+    )));
 
     // Create LpServer (takes ownership of filesystem)
     // We need to clone the filesystem reference before passing it to LpServer
     // Since we can't clone Box<dyn LpFs>, we'll return the filesystem that was passed
     // Note: LpServer takes ownership, so we can't return the same instance
     // For now, return a new filesystem instance (caller may not need it)
-    let server = LpServer::new(output_provider, base_fs, "projects/".as_path());
+    let server =
+        LpServer::new(output_provider, base_fs, "projects/".as_path()).with_config(&config);
 
     // Create a new filesystem instance to return (same type as what was created)
     let returned_fs = create_filesystem(dir, memory)?;
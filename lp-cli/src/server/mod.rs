@@ -5,6 +5,7 @@
 
 pub mod create_server;
 mod run_server_loop_async;
+pub mod transport_udp;
 pub mod transport_ws;
 
 pub use create_server::create_server;
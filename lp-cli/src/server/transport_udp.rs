@@ -0,0 +1,152 @@
+//! UDP server transport
+//!
+//! Implements `ServerTransport` over a non-blocking UDP socket, for low-latency
+//! control on a LAN. Each message is one datagram framed by
+//! `lp_shared::transport::datagram`.
+//!
+//! UDP is unreliable: messages may be dropped or reordered and are never resent.
+//! Gaps in each client's sequence numbers are counted and logged, and late datagrams
+//! are discarded, but nothing is recovered.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+
+use lp_model::{ClientMessage, ServerMessage, TransportError};
+use lp_shared::transport::datagram::{MAX_DATAGRAM_LEN, SequenceEvent, SequenceTracker};
+use lp_shared::transport::{ServerTransport, decode_datagram, encode_datagram};
+
+/// Per-client sequence state
+#[derive(Default)]
+struct Peer {
+    /// Sequence numbers seen from this client
+    tracker: SequenceTracker,
+    /// Sequence number for the next datagram sent to this client
+    next_sequence: u32,
+}
+
+/// UDP server transport
+///
+/// Any address that sends a valid datagram becomes a client. Responses go to the
+/// client that sent the most recent message.
+pub struct UdpServerTransport {
+    /// Bound socket (None once closed)
+    socket: Option<UdpSocket>,
+    /// Clients seen so far
+    peers: HashMap<SocketAddr, Peer>,
+    /// Client that sent the most recent message
+    last_peer: Option<SocketAddr>,
+    /// Receive buffer, large enough for any datagram
+    buffer: Vec<u8>,
+}
+
+impl UdpServerTransport {
+    /// Create a new UDP server transport and bind to the specified port
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - Port to bind to (e.g., 2813), or 0 for any free port
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if binding succeeded
+    /// * `Err(TransportError)` if binding failed
+    pub fn new(port: u16) -> Result<Self, TransportError> {
+        let addr = format!("0.0.0.0:{port}");
+        let socket = UdpSocket::bind(&addr)
+            .map_err(|e| TransportError::Other(format!("Failed to bind to {addr}: {e}")))?;
+        socket
+            .set_nonblocking(true)
+            .map_err(|e| TransportError::Other(format!("Failed to set non-blocking: {e}")))?;
+
+        Ok(Self {
+            socket: Some(socket),
+            peers: HashMap::new(),
+            last_peer: None,
+            buffer: vec![0; MAX_DATAGRAM_LEN],
+        })
+    }
+
+    /// Address the socket is bound to
+    pub fn local_addr(&self) -> Result<SocketAddr, TransportError> {
+        let socket = self.socket.as_ref().ok_or(TransportError::ConnectionLost)?;
+        socket
+            .local_addr()
+            .map_err(|e| TransportError::Other(format!("Failed to get local address: {e}")))
+    }
+
+    /// Number of client messages detected as dropped so far, across all clients
+    pub fn dropped(&self) -> u64 {
+        self.peers.values().map(|peer| peer.tracker.dropped()).sum()
+    }
+}
+
+impl ServerTransport for UdpServerTransport {
+    fn send(&mut self, msg: ServerMessage) -> Result<(), TransportError> {
+        let socket = self.socket.as_ref().ok_or(TransportError::ConnectionLost)?;
+        let Some(addr) = self.last_peer else {
+            return Err(TransportError::Other(
+                "No connected clients to send message to".to_string(),
+            ));
+        };
+        let peer = self.peers.entry(addr).or_default();
+
+        // Serialize ServerMessage to JSON
+        let json = lp_model::json::to_string(&msg).map_err(|e| {
+            TransportError::Serialization(format!("Failed to serialize ServerMessage: {e}"))
+        })?;
+        let datagram = encode_datagram(peer.next_sequence, json.as_bytes())?;
+        peer.next_sequence = peer.next_sequence.wrapping_add(1);
+
+        socket.send_to(&datagram, addr).map_err(|e| {
+            TransportError::Other(format!("Failed to send datagram to {addr}: {e}"))
+        })?;
+        Ok(())
+    }
+
+    fn receive(&mut self) -> Result<Option<ClientMessage>, TransportError> {
+        let socket = self.socket.as_ref().ok_or(TransportError::ConnectionLost)?;
+
+        loop {
+            let (len, addr) = match socket.recv_from(&mut self.buffer) {
+                Ok(received) => received,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(None),
+                // Some platforms report an earlier send to a closed client port here
+                Err(e) if e.kind() == ErrorKind::ConnectionReset => continue,
+                Err(e) => {
+                    return Err(TransportError::Other(format!(
+                        "Failed to receive datagram: {e}"
+                    )));
+                }
+            };
+
+            let (sequence, payload) = decode_datagram(&self.buffer[..len])?;
+            let peer = self.peers.entry(addr).or_default();
+            match peer.tracker.record(sequence) {
+                SequenceEvent::InOrder => {}
+                SequenceEvent::Gap { missed } => {
+                    eprintln!("UDP transport: {missed} message(s) from {addr} dropped");
+                }
+                SequenceEvent::Stale => {
+                    // Overtaken by a later message; delivering it now would reorder them
+                    continue;
+                }
+            }
+
+            // Deserialize ClientMessage from JSON
+            let msg = lp_model::json::from_slice(payload).map_err(|e| {
+                TransportError::Deserialization(format!("Failed to deserialize ClientMessage: {e}"))
+            })?;
+            self.last_peer = Some(addr);
+            return Ok(Some(msg));
+        }
+    }
+
+    fn close(&mut self) -> Result<(), TransportError> {
+        // Connectionless: dropping the socket is all there is to it
+        self.socket = None;
+        self.peers.clear();
+        self.last_peer = None;
+        Ok(())
+    }
+}
@@ -9,14 +9,11 @@ use std::sync::{Arc, Mutex};
 
 use futures_util::SinkExt;
 use futures_util::stream::StreamExt;
-use lp_model::{ClientMessage, ServerMessage, TransportError};
+use lp_model::{ClientMessage, ConnectionId, ServerMessage, TransportError};
 use lp_shared::transport::ServerTransport;
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc;
 
-/// Connection ID for tracking multiple websocket connections
-type ConnectionId = u64;
-
 /// Connection state for a single websocket client
 struct Connection {
     /// Channel sender for sending messages to this connection
@@ -36,6 +33,8 @@ struct SharedState {
     pending_messages: VecDeque<(ConnectionId, ClientMessage)>,
     /// Next connection ID to assign
     next_connection_id: ConnectionId,
+    /// Connections closed since the server last asked
+    closed: Vec<ConnectionId>,
 }
 
 /// WebSocket server transport
@@ -86,6 +85,7 @@ impl WebSocketServerTransport {
             connections: HashMap::new(),
             pending_messages: VecDeque::new(),
             next_connection_id: 0,
+            closed: Vec::new(),
         }));
 
         let runtime_arc = Arc::new(runtime);
@@ -266,6 +266,7 @@ impl WebSocketServerTransport {
             // Cleanup: remove connection on exit
             let mut state = shared_state.lock().unwrap();
             state.connections.remove(&connection_id);
+            state.closed.push(connection_id);
         });
     }
 }
@@ -330,6 +331,36 @@ impl ServerTransport for WebSocketServerTransport {
         Ok(state.pending_messages.pop_front().map(|(_, msg)| msg))
     }
 
+    fn receive_from(&mut self) -> Result<Option<(ConnectionId, ClientMessage)>, TransportError> {
+        let mut state = self.shared_state.lock().unwrap();
+        Ok(state.pending_messages.pop_front())
+    }
+
+    fn send_to(
+        &mut self,
+        connection: ConnectionId,
+        msg: ServerMessage,
+    ) -> Result<(), TransportError> {
+        let mut state = self.shared_state.lock().unwrap();
+        let Some(sender) = state.connections.get(&connection).map(|c| &c.sender) else {
+            return Err(TransportError::Other(format!(
+                "Connection {connection} is closed"
+            )));
+        };
+        if sender.send(msg).is_err() {
+            state.connections.remove(&connection);
+            return Err(TransportError::Other(format!(
+                "Failed to send message to connection {connection}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn take_closed(&mut self) -> Vec<ConnectionId> {
+        let mut state = self.shared_state.lock().unwrap();
+        std::mem::take(&mut state.closed)
+    }
+
     fn receive_all(&mut self) -> Result<Vec<ClientMessage>, TransportError> {
         // Drain all messages from the queue
         let mut state = self.shared_state.lock().unwrap();
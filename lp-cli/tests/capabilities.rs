@@ -0,0 +1,32 @@
+//! Tests for querying server capabilities
+
+use lp_cli::client::{LpClient, client_connect};
+use lp_client::HostSpecifier;
+use lp_model::PROTOCOL_VERSION;
+use lp_model::nodes::NodeKind;
+
+#[tokio::test]
+async fn test_get_capabilities_over_local_transport() {
+    let transport = client_connect(HostSpecifier::Local).unwrap();
+    let client = LpClient::new(transport);
+
+    let capabilities = client.get_capabilities().await.unwrap();
+
+    assert_eq!(capabilities.protocol_version, PROTOCOL_VERSION);
+    for kind in [
+        NodeKind::Texture,
+        NodeKind::Shader,
+        NodeKind::Output,
+        NodeKind::Fixture,
+        NodeKind::Group,
+    ] {
+        assert!(
+            capabilities.node_kinds.contains(&kind),
+            "{kind:?} missing from {:?}",
+            capabilities.node_kinds
+        );
+    }
+    assert!(capabilities.output_drivers.iter().any(|d| d == "ArtNet"));
+    // The in-memory server uses the default config, which has no fixture limit
+    assert_eq!(capabilities.max_fixtures, None);
+}
@@ -0,0 +1,55 @@
+//! Tests for CLI config defaults
+
+use clap::Parser;
+use lp_cli::cli::{Cli, Command};
+use lp_cli::config::cli::PROJECT_CONFIG_FILE;
+use lp_cli::config::load_cli_config;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_config_host_used_when_flag_omitted() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(PROJECT_CONFIG_FILE),
+        "host = \"ws://config-host:2812/\"\n",
+    )
+    .unwrap();
+    let config = load_cli_config(None, temp_dir.path()).unwrap();
+
+    let cli = Cli::try_parse_from(["lp-cli", "list-projects"]).unwrap();
+    match cli.command.with_defaults(&config) {
+        Command::ListProjects { host } => {
+            assert_eq!(host.as_deref(), Some("ws://config-host:2812/"));
+        }
+        _ => panic!("Expected ListProjects"),
+    }
+
+    // An explicit flag wins over the config
+    let cli =
+        Cli::try_parse_from(["lp-cli", "list-projects", "--host", "ws://flag:2812/"]).unwrap();
+    match cli.command.with_defaults(&config) {
+        Command::ListProjects { host } => assert_eq!(host.as_deref(), Some("ws://flag:2812/")),
+        _ => panic!("Expected ListProjects"),
+    }
+}
+
+#[test]
+fn test_config_push_uses_host_for_dev() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(
+        temp_dir.path().join(PROJECT_CONFIG_FILE),
+        "host = \"ws://config-host:2812/\"\ndir = \"my-project\"\npush = true\n",
+    )
+    .unwrap();
+    let config = load_cli_config(None, temp_dir.path()).unwrap();
+
+    let cli = Cli::try_parse_from(["lp-cli", "dev"]).unwrap();
+    match cli.command.with_defaults(&config) {
+        Command::Dev { dir, push, .. } => {
+            assert_eq!(dir, Some(temp_dir.path().join("my-project")));
+            assert_eq!(push, Some(Some("ws://config-host:2812/".to_string())));
+        }
+        _ => panic!("Expected Dev"),
+    }
+}
@@ -0,0 +1,26 @@
+//! Tests for shell completion generation
+
+use clap_complete::Shell;
+use lp_cli::cli::write_completions;
+
+#[test]
+fn test_bash_completions_cover_subcommands() {
+    let mut out = Vec::new();
+    write_completions(Shell::Bash, &mut out);
+    let script = String::from_utf8(out).unwrap();
+
+    for subcommand in [
+        "create",
+        "serve",
+        "dev",
+        "render",
+        "list-projects",
+        "repl",
+        "completions",
+    ] {
+        assert!(
+            script.contains(subcommand),
+            "bash completions missing `{subcommand}`"
+        );
+    }
+}
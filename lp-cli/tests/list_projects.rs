@@ -0,0 +1,39 @@
+//! Tests for the list-projects command
+
+use lp_cli::commands::list_projects::ProjectList;
+use std::process::Command;
+
+#[test]
+fn test_list_projects_json() {
+    let output = Command::new(env!("CARGO_BIN_EXE_lp-cli"))
+        .args(["list-projects", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "list-projects failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // The in-memory server starts with no projects
+    let projects: ProjectList = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(projects.available.is_empty());
+    assert!(projects.loaded.is_empty());
+}
+
+#[test]
+fn test_list_projects_json_error() {
+    let output = Command::new(env!("CARGO_BIN_EXE_lp-cli"))
+        .args(["--json", "list-projects", "--host", "ws://127.0.0.1:1/"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let error: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(
+        error["error"]
+            .as_str()
+            .unwrap()
+            .contains("Failed to connect to server")
+    );
+}
@@ -0,0 +1,104 @@
+//! Tests for the render command
+
+use lp_cli::commands::create::project::create_project_structure;
+use lp_cli::commands::render::{render_project, watch_project};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Shader that fails to compile
+const BROKEN_GLSL: &str =
+    "vec4 main(vec2 fragCoord, vec2 outputSize, float time) { return undefined_fn(); }";
+
+/// Create a project whose shader fills the texture with `color`
+fn create_test_project(temp_dir: &TempDir, color: &str) -> PathBuf {
+    let project_dir = temp_dir.path().join("render-project");
+    create_project_structure(&project_dir, None, Some("render-test")).unwrap();
+    write_shader(&project_dir, color);
+    project_dir
+}
+
+fn write_shader(project_dir: &Path, color: &str) {
+    std::fs::write(
+        project_dir.join("src/rainbow.shader/main.glsl"),
+        format!(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {{ return vec4({color}, 1.0); }}"
+        ),
+    )
+    .unwrap();
+}
+
+/// Top-left pixel of a written image
+fn first_pixel(path: &Path) -> [u8; 4] {
+    let decoder = png::Decoder::new(std::fs::File::open(path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+    let mut buf = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut buf).unwrap();
+    [buf[0], buf[1], buf[2], buf[3]]
+}
+
+#[test]
+fn test_render_writes_texture_images() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "1.0, 0.0, 0.0");
+    let out_dir = temp_dir.path().join("out");
+
+    let written = render_project(&project_dir, &out_dir, 0).unwrap();
+
+    assert_eq!(written, vec![out_dir.join("src_main.texture.png")]);
+    assert_eq!(first_pixel(&written[0]), [255, 0, 0, 255]);
+}
+
+#[test]
+fn test_render_reports_compile_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "1.0, 0.0, 0.0");
+    std::fs::write(
+        project_dir.join("src/rainbow.shader/main.glsl"),
+        BROKEN_GLSL,
+    )
+    .unwrap();
+
+    let error = render_project(&project_dir, &temp_dir.path().join("out"), 0).unwrap_err();
+    assert!(
+        format!("{error:#}").contains("/src/rainbow.shader"),
+        "unexpected error: {error:#}"
+    );
+}
+
+#[tokio::test]
+async fn test_render_watch_rerenders_on_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = create_test_project(&temp_dir, "1.0, 0.0, 0.0");
+    // Inside the project, so the written images must not trigger renders themselves
+    let out_dir = project_dir.join("render");
+
+    let mut renders = Vec::new();
+    let watch = watch_project(&project_dir, &out_dir, 0, |result| {
+        renders.push(
+            result
+                .map(|written| first_pixel(&written[0]))
+                .map_err(|e| format!("{e:#}")),
+        );
+        match renders.len() {
+            // Break the shader; the watch must survive the compile error
+            1 => std::fs::write(
+                project_dir.join("src/rainbow.shader/main.glsl"),
+                BROKEN_GLSL,
+            )
+            .unwrap(),
+            2 => write_shader(&project_dir, "0.0, 1.0, 0.0"),
+            _ => return false,
+        }
+        true
+    });
+    tokio::time::timeout(Duration::from_secs(20), watch)
+        .await
+        .expect("watch did not re-render")
+        .unwrap();
+
+    assert_eq!(renders.len(), 3);
+    assert_eq!(renders[0], Ok([255, 0, 0, 255]));
+    assert!(renders[1].is_err());
+    assert_eq!(renders[2], Ok([0, 255, 0, 255]));
+}
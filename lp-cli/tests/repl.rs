@@ -0,0 +1,62 @@
+//! Tests for the repl command
+
+use lp_cli::client::{LpClient, client_connect};
+use lp_cli::commands::create::project::create_project_structure;
+use lp_cli::commands::dev::push_project_async;
+use lp_cli::commands::repl::run_repl;
+use lp_client::HostSpecifier;
+use lp_shared::fs::LpFsStd;
+use std::io::Cursor;
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_repl_scripted_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("repl-project");
+    create_project_structure(&project_dir, None, Some("repl-test")).unwrap();
+
+    let transport = client_connect(HostSpecifier::Local).unwrap();
+    let client = LpClient::new(transport);
+    push_project_async(&client, &LpFsStd::new(project_dir), "repl-test")
+        .await
+        .unwrap();
+
+    let script = "\
+list
+load projects/repl-test
+nodes
+set-uniform /src/rainbow.shader speed 2.5
+frobnicate
+quit
+list
+";
+    let mut output = Vec::new();
+    run_repl(&client, Cursor::new(script), &mut output)
+        .await
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    assert!(output.contains("Available projects:\n"));
+    assert!(output.contains("projects/repl-test\n"));
+    assert!(output.contains("Loaded project projects/repl-test ["));
+    assert!(output.contains("Shader /src/rainbow.shader"));
+    assert!(output.contains("Set speed = 2.5 on /src/rainbow.shader"));
+    assert!(output.contains("Unknown command: frobnicate\nCommands:"));
+    // Nothing after `quit` runs
+    assert_eq!(output.matches("Available projects:").count(), 1);
+
+    // The uniform change was written to the server's copy of the shader config
+    let data = client
+        .fs_read(lp_model::AsLpPath::as_path(
+            "/projects/repl-test/src/rainbow.shader/node.json",
+        ))
+        .await
+        .unwrap();
+    let config: lp_model::nodes::shader::ShaderConfig = lp_model::json::from_slice(&data).unwrap();
+    assert!(
+        config
+            .uniforms
+            .iter()
+            .any(|uniform| uniform.name == "speed" && uniform.value == 2.5)
+    );
+}
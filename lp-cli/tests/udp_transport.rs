@@ -0,0 +1,128 @@
+//! Tests for the UDP transports over loopback
+
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+use lp_cli::server::transport_udp::UdpServerTransport;
+use lp_client::{ClientTransport, UdpClientTransport};
+use lp_model::server::ServerMsgBody;
+use lp_model::{ClientMessage, ClientRequest, ServerMessage};
+use lp_shared::transport::{ServerTransport, encode_datagram};
+
+/// Poll the server until `count` messages have arrived
+fn receive_messages(server: &mut UdpServerTransport, count: usize) -> Vec<ClientMessage> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut messages = Vec::new();
+    while messages.len() < count {
+        assert!(
+            Instant::now() < deadline,
+            "timed out with {} of {count} messages",
+            messages.len()
+        );
+        match server.receive().unwrap() {
+            Some(msg) => messages.push(msg),
+            None => std::thread::sleep(Duration::from_millis(1)),
+        }
+    }
+    messages
+}
+
+fn raw_datagram(sequence: u32, id: u64) -> Vec<u8> {
+    let msg = ClientMessage {
+        id,
+        msg: ClientRequest::ListLoadedProjects,
+    };
+    let json = lp_model::json::to_string(&msg).unwrap();
+    encode_datagram(sequence, json.as_bytes()).unwrap()
+}
+
+#[tokio::test]
+async fn test_udp_round_trip() {
+    let mut server = UdpServerTransport::new(0).unwrap();
+    let port = server.local_addr().unwrap().port();
+    let mut client = UdpClientTransport::new(&format!("127.0.0.1:{port}"))
+        .await
+        .unwrap();
+
+    for id in 1..=5 {
+        client
+            .send(ClientMessage {
+                id,
+                msg: ClientRequest::LoadProject {
+                    path: format!("projects/test-{id}"),
+                },
+            })
+            .await
+            .unwrap();
+    }
+
+    let messages = receive_messages(&mut server, 5);
+    let ids: Vec<u64> = messages.iter().map(|msg| msg.id).collect();
+    assert_eq!(ids, [1, 2, 3, 4, 5]);
+    assert!(matches!(
+        &messages[2].msg,
+        ClientRequest::LoadProject { path } if path == "projects/test-3"
+    ));
+    assert_eq!(server.dropped(), 0);
+
+    // Responses go back to the client that sent the last message
+    server
+        .send(ServerMessage {
+            id: 5,
+            msg: ServerMsgBody::StopAllProjects,
+        })
+        .unwrap();
+    let response = tokio::time::timeout(Duration::from_secs(5), client.receive())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.id, 5);
+    assert!(matches!(response.msg, ServerMsgBody::StopAllProjects));
+    assert_eq!(client.dropped(), 0);
+
+    client.close().await.unwrap();
+}
+
+#[test]
+fn test_udp_server_reports_gaps() {
+    let mut server = UdpServerTransport::new(0).unwrap();
+    let port = server.local_addr().unwrap().port();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.connect(("127.0.0.1", port)).unwrap();
+
+    // Datagrams 2, 3 and 5 are "lost" on the way
+    for sequence in [0, 1, 4, 6] {
+        socket
+            .send(&raw_datagram(sequence, u64::from(sequence)))
+            .unwrap();
+    }
+    let ids: Vec<u64> = receive_messages(&mut server, 4)
+        .iter()
+        .map(|msg| msg.id)
+        .collect();
+    assert_eq!(ids, [0, 1, 4, 6]);
+    assert_eq!(server.dropped(), 3);
+
+    // A late arrival is discarded rather than delivered out of order
+    socket.send(&raw_datagram(5, 5)).unwrap();
+    socket.send(&raw_datagram(7, 7)).unwrap();
+    let ids: Vec<u64> = receive_messages(&mut server, 1)
+        .iter()
+        .map(|msg| msg.id)
+        .collect();
+    assert_eq!(ids, [7]);
+    assert_eq!(server.dropped(), 3);
+
+    // Malformed datagrams are rejected
+    socket.send(b"not framed").unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        match server.receive() {
+            Err(_) => break,
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            other => panic!("expected an error, got {other:?}"),
+        }
+    }
+}
@@ -4,19 +4,56 @@
 
 use anyhow::{Error, Result};
 use lp_model::{
-    ClientMessage, ClientRequest, LpPath, LpPathBuf, ServerMessage,
+    Capabilities, ClientMessage, ClientRequest, LpPath, LpPathBuf, NodeEdit, PROTOCOL_VERSION,
+    ServerMessage, TestPattern, TestPatternFixture,
     project::{
         FrameId,
         api::{ApiNodeSpecifier, SerializableProjectResponse},
         handle::ProjectHandle,
     },
-    server::{AvailableProject, FsResponse, LoadedProject, ServerMsgBody},
+    server::{AvailableProject, FsResponse, LoadedProject, ServerEvent, ServerMsgBody},
 };
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::fmt;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::transport::ClientTransport;
 
+/// How long a request waits for its response unless configured otherwise
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error returned when the server doesn't answer a request in time
+///
+/// Returned wrapped in `anyhow::Error`; use `downcast_ref::<RequestTimeout>()` to detect it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestTimeout {
+    /// ID of the request that timed out
+    pub id: u64,
+    /// How long the client waited
+    pub timeout: Duration,
+}
+
+impl fmt::Display for RequestTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Request {} timed out after {}ms",
+            self.id,
+            self.timeout.as_millis()
+        )
+    }
+}
+
+impl std::error::Error for RequestTimeout {}
+
+/// Next request ID, shared by every client in the process
+///
+/// IDs never repeat, even across reconnects or between clients sharing a transport, so a
+/// stale response can't be mistaken for the answer to a newer request.
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
 /// Standalone client for communicating with LpServer
 ///
 /// Provides typed async methods for filesystem and project operations.
@@ -24,8 +61,15 @@ use crate::transport::ClientTransport;
 pub struct LpClient {
     /// Transport wrapped in Arc<Mutex> for sharing across async tasks
     transport: Arc<tokio::sync::Mutex<Box<dyn ClientTransport>>>,
-    /// Next request ID to use
-    next_request_id: Arc<AtomicU64>,
+    /// Connection epoch, incremented by each [`LpClient::reconnect`]
+    epoch: Arc<AtomicU64>,
+    /// First request ID sent on the current connection; responses to lower IDs
+    /// belong to an earlier connection and are dropped
+    epoch_first_id: Arc<AtomicU64>,
+    /// How long each request waits for its response
+    request_timeout: Duration,
+    /// IDs of requests that timed out; late responses to them are dropped
+    timed_out: Arc<Mutex<HashSet<u64>>>,
 }
 
 impl LpClient {
@@ -40,10 +84,7 @@ impl LpClient {
     /// * `Self` - The client
     #[allow(dead_code, reason = "Will be used in tests and other contexts")]
     pub fn new(transport: Box<dyn ClientTransport>) -> Self {
-        Self {
-            transport: Arc::new(tokio::sync::Mutex::new(transport)),
-            next_request_id: Arc::new(AtomicU64::new(1)),
-        }
+        Self::new_shared(Arc::new(tokio::sync::Mutex::new(transport)))
     }
 
     /// Create a new LpClient with a shared transport
@@ -58,8 +99,40 @@ impl LpClient {
     pub fn new_shared(transport: Arc<tokio::sync::Mutex<Box<dyn ClientTransport>>>) -> Self {
         Self {
             transport,
-            next_request_id: Arc::new(AtomicU64::new(1)),
+            epoch: Arc::new(AtomicU64::new(0)),
+            epoch_first_id: Arc::new(AtomicU64::new(0)),
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            timed_out: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Set how long each request waits for its response (defaults to [`DEFAULT_REQUEST_TIMEOUT`])
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Replace the transport with a new connection
+    ///
+    /// Closes the old transport and starts a new connection epoch: responses to requests
+    /// sent before the reconnect are ignored if they still arrive. Returns the new epoch.
+    pub async fn reconnect(&self, transport: Box<dyn ClientTransport>) -> u64 {
+        let mut current = self.transport.lock().await;
+        if let Err(e) = current.close().await {
+            log::debug!("Error closing previous connection: {e}");
         }
+        *current = transport;
+
+        self.epoch_first_id
+            .store(NEXT_REQUEST_ID.load(Ordering::SeqCst), Ordering::SeqCst);
+        // Covered by the epoch check from now on
+        self.timed_out.lock().unwrap().clear();
+        self.epoch.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    /// Current connection epoch (0 until the first reconnect)
+    pub fn connection_epoch(&self) -> u64 {
+        self.epoch.load(Ordering::SeqCst)
     }
 
     /// Send a request and wait for the response
@@ -67,10 +140,25 @@ impl LpClient {
     /// Helper method that generates a request ID, sends the request, and waits for the response.
     /// Correlates messages by ID to handle heartbeats and other interstitial messages.
     /// If the server returns an Error response, converts it to an Err.
+    /// Fails with [`RequestTimeout`] if no response arrives within the request timeout.
     async fn send_request(&self, request: ClientRequest) -> Result<ServerMessage> {
-        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::SeqCst);
         let msg = ClientMessage { id, msg: request };
 
+        match tokio::time::timeout(self.request_timeout, self.exchange(id, msg)).await {
+            Ok(result) => result,
+            Err(_) => {
+                self.timed_out.lock().unwrap().insert(id);
+                Err(Error::new(RequestTimeout {
+                    id,
+                    timeout: self.request_timeout,
+                }))
+            }
+        }
+    }
+
+    /// Send a message and wait for the response with the matching ID
+    async fn exchange(&self, id: u64, msg: ClientMessage) -> Result<ServerMessage> {
         // Lock transport and send
         let mut transport = self.transport.lock().await;
         transport
@@ -129,6 +217,24 @@ impl LpClient {
                 continue;
             }
 
+            // Response to a request sent on an earlier connection
+            if response.id < self.epoch_first_id.load(Ordering::SeqCst) {
+                log::debug!(
+                    "Dropping response to request {} from a previous connection",
+                    response.id
+                );
+                continue;
+            }
+
+            // Late response to a request that already timed out
+            if self.timed_out.lock().unwrap().remove(&response.id) {
+                log::debug!(
+                    "Dropping late response to timed-out request {}",
+                    response.id
+                );
+                continue;
+            }
+
             // Non-correlated message (shouldn't happen, but handle gracefully)
             log::warn!(
                 "Received non-correlated message (id: {}, expected: {})",
@@ -419,6 +525,151 @@ impl LpClient {
             ))),
         }
     }
+
+    /// Negotiate the protocol version with the server
+    ///
+    /// Sends this client's [`PROTOCOL_VERSION`]; the server answers with the version
+    /// both sides use from now on, which is lower if the server is older.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u32)` the agreed protocol version
+    /// * `Err` if the server rejected the version or a transport error occurred
+    pub async fn hello(&self) -> Result<u32> {
+        let request = ClientRequest::Hello {
+            protocol_version: PROTOCOL_VERSION,
+        };
+
+        let response = self.send_request(request).await?;
+
+        match response.msg {
+            ServerMsgBody::Hello { protocol_version } => Ok(protocol_version),
+            _ => Err(Error::msg(format!(
+                "Unexpected response type for hello: {:?}",
+                response.msg
+            ))),
+        }
+    }
+
+    /// Write several node files to a project as one transaction
+    ///
+    /// The server reloads the affected nodes once; if any fails to load, nothing is
+    /// changed and an error is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the whole batch was applied
+    /// * `Err` if the batch was rejected or a transport error occurred
+    pub async fn apply_edits(&self, handle: ProjectHandle, edits: Vec<NodeEdit>) -> Result<()> {
+        let request = ClientRequest::ApplyEdits { handle, edits };
+
+        let response = self.send_request(request).await?;
+
+        match response.msg {
+            ServerMsgBody::ApplyEdits => Ok(()),
+            _ => Err(Error::msg(format!(
+                "Unexpected response type for apply_edits: {:?}",
+                response.msg
+            ))),
+        }
+    }
+
+    /// Ask the server what it supports
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Capabilities)` node kinds, output drivers, limits and features of the server
+    /// * `Err` if the request failed or transport error occurred
+    pub async fn get_capabilities(&self) -> Result<Capabilities> {
+        let request = ClientRequest::GetCapabilities;
+
+        let response = self.send_request(request).await?;
+
+        match response.msg {
+            ServerMsgBody::GetCapabilities { capabilities } => Ok(capabilities),
+            _ => Err(Error::msg(format!(
+                "Unexpected response type for get_capabilities: {:?}",
+                response.msg
+            ))),
+        }
+    }
+
+    /// Get the server's log of recent events
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<ServerEvent>)` recent events, oldest first
+    /// * `Err` if the request failed or transport error occurred
+    pub async fn get_events(&self) -> Result<Vec<ServerEvent>> {
+        let request = ClientRequest::GetEvents;
+
+        let response = self.send_request(request).await?;
+
+        match response.msg {
+            ServerMsgBody::GetEvents { events } => Ok(events),
+            _ => Err(Error::msg(format!(
+                "Unexpected response type for get_events: {:?}",
+                response.msg
+            ))),
+        }
+    }
+
+    /// Show a test pattern on a fixture, bypassing any project
+    ///
+    /// # Arguments
+    ///
+    /// * `fixture` - Output and LEDs to show the pattern on
+    /// * `pattern` - Pattern to show, or `None` to clear the output
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` once the pattern is showing (or cleared)
+    /// * `Err` if the output couldn't be opened or transport error occurred
+    pub async fn test_pattern(
+        &self,
+        fixture: TestPatternFixture,
+        pattern: Option<TestPattern>,
+    ) -> Result<()> {
+        let request = ClientRequest::TestPattern { fixture, pattern };
+
+        let response = self.send_request(request).await?;
+
+        match response.msg {
+            ServerMsgBody::TestPattern => Ok(()),
+            _ => Err(Error::msg(format!(
+                "Unexpected response type for test_pattern: {:?}",
+                response.msg
+            ))),
+        }
+    }
+
+    /// Align the server's rendering to the host's clock
+    ///
+    /// # Arguments
+    ///
+    /// * `host_time_ms` - Host clock time in milliseconds, read just before sending
+    /// * `frame_interval_ms` - Length of one frame in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FrameId)` - Frame of the host's clock the server is now on
+    /// * `Err` if transport error occurred
+    pub async fn sync_clock(&self, host_time_ms: u64, frame_interval_ms: u32) -> Result<FrameId> {
+        let request = ClientRequest::SyncClock {
+            host_time_ms,
+            frame_interval_ms,
+        };
+
+        let response = self.send_request(request).await?;
+
+        match response.msg {
+            ServerMsgBody::SyncClock { frame } => Ok(frame),
+            _ => Err(Error::msg(format!(
+                "Unexpected response type for sync_clock: {:?}",
+                response.msg
+            ))),
+        }
+    }
 }
 
 /// Convert SerializableProjectResponse to ProjectResponse
@@ -479,6 +730,15 @@ pub fn serializable_response_to_project_response(
                         config: Box::new(config),
                         state,
                     },
+                    lp_model::project::api::SerializableNodeDetail::Group {
+                        path,
+                        config,
+                        state,
+                    } => NodeDetail {
+                        path,
+                        config: Box::new(config),
+                        state,
+                    },
                 };
                 node_details_map.insert(handle, detail);
             }
@@ -677,4 +937,77 @@ mod tests {
 
         server_task.await.unwrap();
     }
+
+    #[tokio::test]
+    async fn test_send_request_timeout() {
+        let (client_transport, mut server_transport) = create_local_transport_pair();
+        let client = LpClient::new(Box::new(client_transport))
+            .with_request_timeout(Duration::from_millis(50));
+
+        // Server never answers the first request
+        let result = client.stop_all_projects().await;
+        let err = result.unwrap_err();
+        let timeout = err.downcast_ref::<RequestTimeout>().unwrap();
+        let timed_out_id = timeout.id;
+        assert_eq!(timeout.timeout, Duration::from_millis(50));
+
+        // The server eventually answers both the stale request and the next one
+        let server_task = task::spawn(async move {
+            let stale = server_transport.receive().await.unwrap().unwrap();
+            assert_eq!(stale.id, timed_out_id);
+            let next = server_transport.receive().await.unwrap().unwrap();
+            for id in [stale.id, next.id] {
+                server_transport
+                    .send(ServerMessage {
+                        id,
+                        msg: ServerMsgBody::StopAllProjects,
+                    })
+                    .unwrap();
+            }
+        });
+
+        // A later request still works, skipping the late response
+        let result = client.stop_all_projects().await;
+        assert!(result.is_ok());
+        assert!(client.timed_out.lock().unwrap().is_empty());
+
+        server_task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_drops_responses_from_previous_connection() {
+        let (old_client_transport, mut old_server_transport) = create_local_transport_pair();
+        let client = LpClient::new(Box::new(old_client_transport))
+            .with_request_timeout(Duration::from_millis(50));
+        assert_eq!(client.connection_epoch(), 0);
+
+        // The connection drops before the server answers
+        assert!(client.stop_all_projects().await.is_err());
+        let stale_id = old_server_transport.receive().await.unwrap().unwrap().id;
+
+        let (client_transport, mut server_transport) = create_local_transport_pair();
+        assert_eq!(client.reconnect(Box::new(client_transport)).await, 1);
+        assert_eq!(client.connection_epoch(), 1);
+
+        // The late answer to the old request shows up on the new connection first
+        let server_task = task::spawn(async move {
+            let request = server_transport.receive().await.unwrap().unwrap();
+            assert!(
+                request.id > stale_id,
+                "request IDs must not reset on reconnect"
+            );
+            for id in [stale_id, request.id] {
+                server_transport
+                    .send(ServerMessage {
+                        id,
+                        msg: ServerMsgBody::StopAllProjects,
+                    })
+                    .unwrap();
+            }
+        });
+
+        assert!(client.stop_all_projects().await.is_ok());
+
+        server_task.await.unwrap();
+    }
 }
@@ -0,0 +1,166 @@
+//! mDNS service discovery
+//!
+//! Servers advertise their WebSocket endpoint as a `_lightplayer._tcp` service
+//! ([`ServiceAdvertisement`]) and clients browse for it ([`discover`]), so a device can
+//! be found on the LAN without knowing its IP address.
+
+use crate::specifier::HostSpecifier;
+use anyhow::{Context, Result};
+use mdns_sd::{IfKind, ServiceDaemon, ServiceEvent, ServiceInfo};
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+/// mDNS service type for LightPlayer servers
+pub const SERVICE_TYPE: &str = "_lightplayer._tcp.local.";
+
+/// How long [`discover`] listens for servers by default
+pub const DEFAULT_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A server advertised over mDNS
+///
+/// The service is withdrawn when this is dropped.
+pub struct ServiceAdvertisement {
+    daemon: ServiceDaemon,
+    fullname: String,
+}
+
+impl ServiceAdvertisement {
+    /// Advertise a WebSocket server listening on `port` on all of this host's addresses
+    ///
+    /// # Arguments
+    ///
+    /// * `instance_name` - Human-readable service name (e.g., `lightplayer`); renamed
+    ///   automatically if another host on the network already uses it
+    /// * `port` - Port the WebSocket server listens on
+    pub fn new(instance_name: &str, port: u16) -> Result<Self> {
+        Self::start(new_daemon(false)?, instance_name, port, None)
+    }
+
+    /// Advertise on the given daemon, at `addr` or (if None) all host addresses
+    fn start(
+        daemon: ServiceDaemon,
+        instance_name: &str,
+        port: u16,
+        addr: Option<IpAddr>,
+    ) -> Result<Self> {
+        let host_name = format!("{instance_name}.local.");
+        let info = match addr {
+            Some(addr) => {
+                ServiceInfo::new(SERVICE_TYPE, instance_name, &host_name, addr, port, None)
+            }
+            None => ServiceInfo::new(SERVICE_TYPE, instance_name, &host_name, "", port, None)
+                .map(ServiceInfo::enable_addr_auto),
+        }
+        .with_context(|| format!("Invalid mDNS service name '{instance_name}'"))?;
+
+        let fullname = info.get_fullname().to_string();
+        daemon
+            .register(info)
+            .context("Failed to register mDNS service")?;
+        Ok(Self { daemon, fullname })
+    }
+
+    /// Full mDNS name of the advertised service
+    pub fn fullname(&self) -> &str {
+        &self.fullname
+    }
+}
+
+impl Drop for ServiceAdvertisement {
+    fn drop(&mut self) {
+        let _ = self.daemon.unregister(&self.fullname);
+        let _ = self.daemon.shutdown();
+    }
+}
+
+/// Browse the local network for LightPlayer servers
+///
+/// Listens for `timeout` and returns a WebSocket specifier for every server that
+/// answered, sorted and without duplicates. An empty list means nothing was found.
+pub fn discover(timeout: Duration) -> Result<Vec<HostSpecifier>> {
+    browse(&new_daemon(false)?, timeout)
+}
+
+fn browse(daemon: &ServiceDaemon, timeout: Duration) -> Result<Vec<HostSpecifier>> {
+    let events = daemon
+        .browse(SERVICE_TYPE)
+        .context("Failed to browse for mDNS services")?;
+
+    let deadline = Instant::now() + timeout;
+    let mut urls = BTreeSet::new();
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        match events.recv_timeout(remaining) {
+            Ok(ServiceEvent::ServiceResolved(info)) => {
+                if let Some(addr) = preferred_addr(info.get_addresses().iter().copied()) {
+                    urls.insert(websocket_url(addr, info.get_port()));
+                }
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+
+    let _ = daemon.stop_browse(SERVICE_TYPE);
+    let _ = daemon.shutdown();
+    Ok(urls
+        .into_iter()
+        .map(|url| HostSpecifier::WebSocket { url })
+        .collect())
+}
+
+fn new_daemon(loopback: bool) -> Result<ServiceDaemon> {
+    let daemon = ServiceDaemon::new().context("Failed to start mDNS daemon")?;
+    if loopback {
+        daemon
+            .enable_interface(IfKind::LoopbackV4)
+            .context("Failed to enable mDNS on loopback")?;
+    }
+    Ok(daemon)
+}
+
+/// Pick one address to connect to, preferring IPv4 (simpler URLs, no scope issues)
+fn preferred_addr(addrs: impl Iterator<Item = IpAddr>) -> Option<IpAddr> {
+    addrs.min_by_key(|addr| (addr.is_ipv6(), *addr))
+}
+
+fn websocket_url(addr: IpAddr, port: u16) -> String {
+    match addr {
+        IpAddr::V4(addr) => format!("ws://{addr}:{port}/"),
+        IpAddr::V6(addr) => format!("ws://[{addr}]:{port}/"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_websocket_url() {
+        let v4 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 50));
+        let v6 = IpAddr::V6(Ipv6Addr::LOCALHOST);
+        assert_eq!(websocket_url(v4, 2812), "ws://192.168.1.50:2812/");
+        assert_eq!(websocket_url(v6, 2812), "ws://[::1]:2812/");
+        assert_eq!(preferred_addr([v6, v4].into_iter()), Some(v4));
+        assert_eq!(preferred_addr(core::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_discover_advertised_service() {
+        let port = 28_120;
+        let _advertisement = ServiceAdvertisement::start(
+            new_daemon(true).unwrap(),
+            "lightplayer-discovery-test",
+            port,
+            Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+        )
+        .unwrap();
+
+        let found = browse(&new_daemon(true).unwrap(), Duration::from_secs(3)).unwrap();
+        let expected = HostSpecifier::WebSocket {
+            url: format!("ws://127.0.0.1:{port}/"),
+        };
+        assert!(found.contains(&expected), "discovered: {found:?}");
+    }
+}
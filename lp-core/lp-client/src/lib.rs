@@ -4,19 +4,27 @@
 //! Includes transport implementations and the main LpClient struct.
 
 pub mod client;
+#[cfg(feature = "mdns")]
+pub mod discovery;
 pub mod local;
 pub mod specifier;
 pub mod transport;
 pub mod transport_serial;
+pub mod transport_udp;
 #[cfg(feature = "ws")]
 pub mod transport_ws;
 
 // Re-export main types
-pub use client::{LpClient, serializable_response_to_project_response};
+pub use client::{
+    DEFAULT_REQUEST_TIMEOUT, LpClient, RequestTimeout, serializable_response_to_project_response,
+};
 pub use local::{
     AsyncLocalClientTransport, AsyncLocalServerTransport, create_local_transport_pair,
 };
+#[cfg(feature = "mdns")]
+pub use discovery::{ServiceAdvertisement, discover};
 pub use specifier::HostSpecifier;
 pub use transport::ClientTransport;
+pub use transport_udp::UdpClientTransport;
 #[cfg(feature = "ws")]
 pub use transport_ws::WebSocketClientTransport;
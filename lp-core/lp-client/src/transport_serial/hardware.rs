@@ -12,7 +12,7 @@ use tokio::sync::{mpsc, oneshot};
 /// Serial I/O thread loop
 ///
 /// Runs continuously, reading from serial port and writing messages.
-/// Filters for M! prefix, logs non-M! lines, and parses JSON messages.
+/// Filters for M! prefix, logs L! and untagged lines, and parses JSON messages.
 fn serial_thread_loop(
     port_name: String,
     baud_rate: u32,
@@ -137,8 +137,11 @@ fn serial_thread_loop(
                         // Continue - don't crash on parse errors
                     }
                 }
+            } else if let Some(log_line) = line_str.strip_prefix("L!") {
+                // Tagged log frame - strip the tag
+                eprintln!("[serial] {log_line}");
             } else {
-                // Non-M! line - log with prefix
+                // Untagged line (bootloader, panic output) - log with prefix
                 eprintln!("[serial] {line_str}");
             }
         }
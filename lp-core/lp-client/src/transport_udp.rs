@@ -0,0 +1,126 @@
+//! UDP client transport
+//!
+//! Implements `ClientTransport` over UDP using async `tokio`, for low-latency control
+//! on a LAN. Each message is one datagram framed by `lp_shared::transport::datagram`.
+//!
+//! UDP is unreliable: messages may be dropped or reordered and are never resent.
+//! Dropped server messages are counted (see [`UdpClientTransport::dropped`]) and late
+//! ones are discarded, but a request whose response is lost will only time out.
+
+use crate::transport::ClientTransport;
+use lp_model::{ClientMessage, ServerMessage, TransportError};
+use lp_shared::transport::datagram::{MAX_DATAGRAM_LEN, SequenceEvent, SequenceTracker};
+use lp_shared::transport::{decode_datagram, encode_datagram};
+use tokio::net::UdpSocket;
+
+/// UDP client transport
+///
+/// Sends to and receives from a single server address.
+pub struct UdpClientTransport {
+    /// Server address, for error messages
+    addr: String,
+    /// Socket connected to the server (None once closed)
+    socket: Option<UdpSocket>,
+    /// Sequence number for the next outgoing datagram
+    next_sequence: u32,
+    /// Sequence numbers seen from the server
+    tracker: SequenceTracker,
+    /// Receive buffer, large enough for any datagram
+    buffer: Vec<u8>,
+}
+
+impl UdpClientTransport {
+    /// Create a new UDP client transport talking to the server at `addr`
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - Server address (e.g., `192.168.1.50:2813`)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Self)` if a local socket was bound and pointed at `addr`
+    /// * `Err(TransportError)` otherwise
+    ///
+    /// No packets are exchanged, so this succeeds even if no server is listening.
+    pub async fn new(addr: &str) -> Result<Self, TransportError> {
+        let socket = UdpSocket::bind("0.0.0.0:0")
+            .await
+            .map_err(|e| TransportError::Other(format!("Failed to bind UDP socket: {e}")))?;
+        socket.connect(addr).await.map_err(|e| {
+            TransportError::Other(format!("Failed to set UDP peer to '{addr}': {e}"))
+        })?;
+
+        Ok(Self {
+            addr: addr.to_string(),
+            socket: Some(socket),
+            next_sequence: 0,
+            tracker: SequenceTracker::new(),
+            buffer: vec![0; MAX_DATAGRAM_LEN],
+        })
+    }
+
+    /// Number of server messages detected as dropped so far
+    pub fn dropped(&self) -> u64 {
+        self.tracker.dropped()
+    }
+}
+
+#[async_trait::async_trait]
+impl ClientTransport for UdpClientTransport {
+    async fn send(&mut self, msg: ClientMessage) -> Result<(), TransportError> {
+        let socket = self.socket.as_ref().ok_or(TransportError::ConnectionLost)?;
+
+        // Serialize ClientMessage to JSON
+        let json = lp_model::json::to_string(&msg).map_err(|e| {
+            TransportError::Serialization(format!("Failed to serialize ClientMessage: {e}"))
+        })?;
+        let datagram = encode_datagram(self.next_sequence, json.as_bytes())?;
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+
+        socket
+            .send(&datagram)
+            .await
+            .map_err(|e| TransportError::Other(format!("Failed to send datagram: {e}")))?;
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Result<ServerMessage, TransportError> {
+        let socket = self.socket.as_ref().ok_or(TransportError::ConnectionLost)?;
+
+        loop {
+            let len = socket
+                .recv(&mut self.buffer)
+                .await
+                .map_err(|e| match e.kind() {
+                    // Reported by some platforms after an ICMP port unreachable
+                    std::io::ErrorKind::ConnectionRefused => {
+                        TransportError::ConnectionRefused(self.addr.clone())
+                    }
+                    _ => TransportError::Other(format!("Failed to receive datagram: {e}")),
+                })?;
+
+            let (sequence, payload) = decode_datagram(&self.buffer[..len])?;
+            match self.tracker.record(sequence) {
+                SequenceEvent::InOrder => {}
+                SequenceEvent::Gap { missed } => {
+                    log::warn!("UDP transport: {missed} server message(s) dropped");
+                }
+                SequenceEvent::Stale => {
+                    log::debug!("UDP transport: discarding late server message {sequence}");
+                    continue;
+                }
+            }
+
+            // Deserialize ServerMessage from JSON
+            return lp_model::json::from_slice(payload).map_err(|e| {
+                TransportError::Deserialization(format!("Failed to deserialize ServerMessage: {e}"))
+            });
+        }
+    }
+
+    async fn close(&mut self) -> Result<(), TransportError> {
+        // Connectionless: dropping the socket is all there is to it
+        self.socket = None;
+        Ok(())
+    }
+}
@@ -31,10 +31,15 @@ impl WebSocketClientTransport {
     /// * `Err(TransportError)` if connection failed
     pub async fn new(url: &str) -> Result<Self, TransportError> {
         // Connect via tokio-tungstenite
-        let (stream, _) = connect_async(url).await.map_err(|e| {
-            TransportError::Other(format!(
+        let (stream, _) = connect_async(url).await.map_err(|e| match e {
+            tokio_tungstenite::tungstenite::Error::Io(io)
+                if io.kind() == std::io::ErrorKind::ConnectionRefused =>
+            {
+                TransportError::ConnectionRefused(url.to_string())
+            }
+            e => TransportError::Other(format!(
                 "Failed to establish WebSocket connection to '{url}': {e}"
-            ))
+            )),
         })?;
 
         Ok(Self {
@@ -4,7 +4,7 @@ use alloc::format;
 use alloc::string::String;
 use alloc::{vec, vec::Vec};
 use lp_model::{
-    FrameId, LpPathBuf, NodeConfig, NodeHandle, NodeKind,
+    FrameId, LpPath, LpPathBuf, NodeConfig, NodeHandle, NodeKind,
     project::api::{ApiNodeSpecifier, NodeChange, NodeState, NodeStatus},
 };
 
@@ -110,6 +110,10 @@ impl ClientProjectView {
                                     Box::new(lp_model::nodes::texture::TextureConfig {
                                         width: 0,
                                         height: 0,
+                                        image_path: None,
+                                        fill_color: None,
+                                        filter: None,
+                                        enabled: None,
                                     })
                                 }
                                 NodeKind::Shader => {
@@ -118,6 +122,9 @@ impl ClientProjectView {
                                 NodeKind::Output => {
                                     Box::new(lp_model::nodes::output::OutputConfig::GpioStrip {
                                         pin: 0,
+                                        dither: None,
+                                        power_limit: None,
+                                        slew_limit: None,
                                     })
                                 }
                                 NodeKind::Fixture => {
@@ -133,8 +140,17 @@ impl ClientProjectView {
                                         transform: [[0.0; 4]; 4],
                                         brightness: None,
                                         gamma_correction: None,
+                                        universe: None,
+                                        channel_offset: None,
+                                        enabled: None,
+                                        color_correction: None,
+                                        mask: None,
+                                        white_extraction: None,
                                     })
                                 }
+                                NodeKind::Group => {
+                                    Box::new(lp_model::nodes::group::GroupConfig::default())
+                                }
                             };
 
                             let initial_status = NodeStatus::Created;
@@ -208,6 +224,10 @@ impl ClientProjectView {
                                 Box::new(lp_model::nodes::texture::TextureConfig {
                                     width: 0,
                                     height: 0,
+                                    image_path: None,
+                                    fill_color: None,
+                                    filter: None,
+                                    enabled: None,
                                 })
                             }
                             NodeKind::Shader => {
@@ -216,6 +236,9 @@ impl ClientProjectView {
                             NodeKind::Output => {
                                 Box::new(lp_model::nodes::output::OutputConfig::GpioStrip {
                                     pin: 0,
+                                    dither: None,
+                                    power_limit: None,
+                                    slew_limit: None,
                                 })
                             }
                             NodeKind::Fixture => {
@@ -230,8 +253,17 @@ impl ClientProjectView {
                                     transform: [[0.0; 4]; 4],
                                     brightness: None,
                                     gamma_correction: None,
+                                    universe: None,
+                                    channel_offset: None,
+                                    enabled: None,
+                                    color_correction: None,
+                                    mask: None,
+                                    white_extraction: None,
                                 })
                             }
+                            NodeKind::Group => {
+                                Box::new(lp_model::nodes::group::GroupConfig::default())
+                            }
                         };
 
                         entry.config = config;
@@ -245,6 +277,7 @@ impl ClientProjectView {
                             NodeState::Shader(_) => NodeKind::Shader,
                             NodeState::Output(_) => NodeKind::Output,
                             NodeState::Fixture(_) => NodeKind::Fixture,
+                            NodeState::Group(_) => NodeKind::Group,
                         };
 
                         let config: Box<dyn NodeConfig> = match kind {
@@ -252,6 +285,10 @@ impl ClientProjectView {
                                 Box::new(lp_model::nodes::texture::TextureConfig {
                                     width: 0,
                                     height: 0,
+                                    image_path: None,
+                                    fill_color: None,
+                                    filter: None,
+                                    enabled: None,
                                 })
                             }
                             NodeKind::Shader => {
@@ -260,6 +297,9 @@ impl ClientProjectView {
                             NodeKind::Output => {
                                 Box::new(lp_model::nodes::output::OutputConfig::GpioStrip {
                                     pin: 0,
+                                    dither: None,
+                                    power_limit: None,
+                                    slew_limit: None,
                                 })
                             }
                             NodeKind::Fixture => {
@@ -274,8 +314,17 @@ impl ClientProjectView {
                                     transform: [[0.0; 4]; 4],
                                     brightness: None,
                                     gamma_correction: None,
+                                    universe: None,
+                                    channel_offset: None,
+                                    enabled: None,
+                                    color_correction: None,
+                                    mask: None,
+                                    white_extraction: None,
                                 })
                             }
+                            NodeKind::Group => {
+                                Box::new(lp_model::nodes::group::GroupConfig::default())
+                            }
                         };
 
                         self.nodes.insert(
@@ -301,6 +350,24 @@ impl ClientProjectView {
         }
     }
 
+    /// Get all nodes of the given kind, in handle order
+    pub fn nodes_of_kind(&self, kind: NodeKind) -> Vec<&ClientNodeEntry> {
+        self.nodes
+            .values()
+            .filter(|entry| entry.kind == kind)
+            .collect()
+    }
+
+    /// Get all nodes of the given kind at or below `path`, in handle order
+    ///
+    /// Matches whole path components, so `/src/a` does not include `/src/ab.shader`.
+    pub fn nodes_of_kind_under(&self, kind: NodeKind, path: &LpPath) -> Vec<&ClientNodeEntry> {
+        self.nodes
+            .values()
+            .filter(|entry| entry.kind == kind && entry.path.starts_with(path.as_str()))
+            .collect()
+    }
+
     /// Get texture data for a node handle
     ///
     /// Returns the texture data bytes, or an error if:
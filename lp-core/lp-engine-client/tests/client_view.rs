@@ -76,3 +76,61 @@ fn test_sync_with_changes() {
     assert_eq!(view.nodes.len(), 1);
     assert!(view.nodes.contains_key(&handle));
 }
+
+#[test]
+fn test_nodes_of_kind() {
+    use lp_model::{AsLpPath, LpPathBuf, NodeKind, project::api::NodeChange};
+
+    let mut view = ClientProjectView::new();
+    let nodes = [
+        ("/src/tex.texture", NodeKind::Texture),
+        ("/src/a.shader", NodeKind::Shader),
+        ("/src/out.output", NodeKind::Output),
+        ("/src/group/b.shader", NodeKind::Shader),
+        ("/src/fix.fixture", NodeKind::Fixture),
+        ("/src/groupie.shader", NodeKind::Shader),
+    ];
+    let handles: Vec<NodeHandle> = (1..=nodes.len() as i32).map(NodeHandle::new).collect();
+    let response = ProjectResponse::GetChanges {
+        current_frame: FrameId::new(1),
+        node_handles: handles.clone(),
+        node_changes: handles
+            .iter()
+            .zip(nodes)
+            .map(|(handle, (path, kind))| NodeChange::Created {
+                handle: *handle,
+                path: LpPathBuf::from(path),
+                kind,
+            })
+            .collect(),
+        node_details: BTreeMap::new(),
+        theoretical_fps: None,
+    };
+    view.apply_changes(&response).unwrap();
+
+    let shader_paths: Vec<&str> = view
+        .nodes_of_kind(NodeKind::Shader)
+        .iter()
+        .map(|entry| entry.path.as_str())
+        .collect();
+    assert_eq!(
+        shader_paths,
+        [
+            "/src/a.shader",
+            "/src/group/b.shader",
+            "/src/groupie.shader"
+        ]
+    );
+
+    let grouped: Vec<&str> = view
+        .nodes_of_kind_under(NodeKind::Shader, "/src/group".as_path())
+        .iter()
+        .map(|entry| entry.path.as_str())
+        .collect();
+    assert_eq!(grouped, ["/src/group/b.shader"]);
+
+    assert!(
+        view.nodes_of_kind_under(NodeKind::Texture, "/src/group".as_path())
+            .is_empty()
+    );
+}
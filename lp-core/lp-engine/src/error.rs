@@ -106,6 +106,9 @@ impl From<lp_shared::OutputError> for Error {
                     ),
                 }
             }
+            lp_shared::OutputError::TransmitFailed { reason } => Error::Other {
+                message: format!("Transmit failed: {reason}"),
+            },
             lp_shared::OutputError::Other { message } => Error::Other { message },
         }
     }
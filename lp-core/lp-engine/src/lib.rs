@@ -19,6 +19,8 @@ pub mod runtime;
 
 pub use error::Error;
 pub use nodes::{NodeConfig, NodeRuntime};
-pub use output::{MemoryOutputProvider, OutputChannelHandle, OutputFormat, OutputProvider};
+pub use output::{
+    FrameStamp, MemoryOutputProvider, OutputChannelHandle, OutputFormat, OutputProvider,
+};
 pub use project::ProjectRuntime;
-pub use runtime::{NodeInitContext, RenderContext};
+pub use runtime::{FrameBufferPool, FrameBufferPoolStats, NodeInitContext, RenderContext};
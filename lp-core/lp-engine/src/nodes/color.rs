@@ -0,0 +1,81 @@
+//! Conversion of config colors to the engine's RGB representation
+
+use lp_glsl_builtins::builtins::lpfx::color::space::hsv2rgb_q32::lpfx_hsv2rgb_q32;
+use lp_glsl_builtins::glsl::q32::types::q32::{Q32, ToQ32};
+use lp_glsl_builtins::glsl::q32::types::vec3_q32::Vec3Q32;
+use lp_model::nodes::ColorConfig;
+
+/// Convert a config color to Q32 RGB
+///
+/// HSV colors are converted with the `hsv2rgb_q32` builtin so the result matches
+/// what a shader calling `lpfx_hsv2rgb` would produce.
+pub fn color_config_to_rgb(color: &ColorConfig) -> Vec3Q32 {
+    match *color {
+        ColorConfig::Rgb { r, g, b } => Vec3Q32::from_f32(r, g, b),
+        ColorConfig::Hsv { h, s, v } => {
+            // Wrap hue into [0, 1)
+            let h = h - libm::floorf(h);
+            lpfx_hsv2rgb_q32(Vec3Q32::from_f32(h, s, v))
+        }
+    }
+}
+
+/// Convert a config color to 8-bit RGB
+pub fn color_config_to_rgb8(color: &ColorConfig) -> [u8; 3] {
+    let rgb = color_config_to_rgb(color);
+    [q32_to_u8(rgb.x), q32_to_u8(rgb.y), q32_to_u8(rgb.z)]
+}
+
+/// Convert a [0, 1] Q32 component to a rounded, clamped 8-bit value
+fn q32_to_u8(value: Q32) -> u8 {
+    (value * 255.to_q32() + Q32::HALF).to_u8_clamped()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_passthrough() {
+        let color = ColorConfig::Rgb {
+            r: 1.0,
+            g: 0.5,
+            b: 0.0,
+        };
+        assert_eq!(color_config_to_rgb8(&color), [255, 128, 0]);
+    }
+
+    #[test]
+    fn test_hsv_primaries() {
+        let red = ColorConfig::Hsv {
+            h: 0.0,
+            s: 1.0,
+            v: 1.0,
+        };
+        assert_eq!(color_config_to_rgb8(&red), [255, 0, 0]);
+
+        let white = ColorConfig::Hsv {
+            h: 0.25,
+            s: 0.0,
+            v: 1.0,
+        };
+        assert_eq!(color_config_to_rgb8(&white), [255, 255, 255]);
+
+        let black = ColorConfig::Hsv {
+            h: 0.5,
+            s: 1.0,
+            v: 0.0,
+        };
+        assert_eq!(color_config_to_rgb8(&black), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_hsv_hue_wraps() {
+        let red = ColorConfig::Hsv {
+            h: 1.0,
+            s: 1.0,
+            v: 1.0,
+        };
+        assert_eq!(color_config_to_rgb8(&red), [255, 0, 0]);
+    }
+}
@@ -0,0 +1,84 @@
+//! Color correction matrix for matching the white balance of LED strips
+//!
+//! Each output channel is a weighted sum of the input red, green and blue, computed
+//! in fixed point on the 0-255 channel values before they are quantized to 8 bits.
+
+use lp_glsl_builtins::glsl::q32::types::q32::Q32;
+
+/// 3x3 RGB color correction matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorCorrection {
+    /// Rows for output red, green and blue
+    rows: [[Q32; 3]; 3],
+}
+
+impl ColorCorrection {
+    /// Matrix that leaves colors unchanged
+    pub const IDENTITY: Self = Self {
+        rows: [
+            [Q32::ONE, Q32::ZERO, Q32::ZERO],
+            [Q32::ZERO, Q32::ONE, Q32::ZERO],
+            [Q32::ZERO, Q32::ZERO, Q32::ONE],
+        ],
+    };
+
+    /// Matrix from a fixture config, identity if not set
+    pub fn from_config(matrix: Option<&[[f32; 3]; 3]>) -> Self {
+        match matrix {
+            Some(matrix) => Self {
+                rows: matrix.map(|row| row.map(Q32::from_f32)),
+            },
+            None => Self::IDENTITY,
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Multiply an RGB color by the matrix
+    ///
+    /// Results are not clamped; quantizing to 8 bits clamps them to 0-255.
+    pub fn apply(&self, rgb: [Q32; 3]) -> [Q32; 3] {
+        self.rows
+            .map(|row| row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2])
+    }
+}
+
+impl Default for ColorCorrection {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(r: i32, g: i32, b: i32) -> [Q32; 3] {
+        [Q32::from_i32(r), Q32::from_i32(g), Q32::from_i32(b)]
+    }
+
+    #[test]
+    fn test_identity_leaves_colors_unchanged() {
+        let identity = ColorCorrection::from_config(None);
+        assert!(identity.is_identity());
+        for color in [rgb(0, 0, 0), rgb(255, 255, 255), rgb(200, 100, 40)] {
+            assert_eq!(identity.apply(color), color);
+        }
+        let half = [Q32::HALF, Q32::from_f32(127.25), Q32::from_f32(0.75)];
+        assert_eq!(identity.apply(half), half);
+
+        let explicit = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        assert!(ColorCorrection::from_config(Some(&explicit)).is_identity());
+    }
+
+    #[test]
+    fn test_apply_matrix() {
+        // Halve red, and give blue a quarter of red's light
+        let matrix = [[0.5, 0.0, 0.0], [0.0, 1.0, 0.0], [0.25, 0.0, 0.75]];
+        let correction = ColorCorrection::from_config(Some(&matrix));
+        assert!(!correction.is_identity());
+        assert_eq!(correction.apply(rgb(200, 100, 40)), rgb(100, 100, 80));
+    }
+}
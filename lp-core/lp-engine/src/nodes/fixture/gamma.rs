@@ -1,34 +1,62 @@
-//! Gamma correction lookup table for LED brightness correction
+//! Gamma correction for LED brightness
 //!
-//! Provides a standard gamma correction table for LED displays to improve
+//! Provides a standard gamma correction curve for LED displays to improve
 //! perceived brightness linearity.
 
-/// Standard LED gamma correction lookup table (256 entries)
+use lp_glsl_builtins::glsl::q32::types::q32::Q32;
+
+/// Standard LED gamma correction curve (gamma 2.8), in 8.8 fixed point
 ///
-/// This table corrects for the non-linear brightness response of LEDs,
-/// making brightness appear more linear to the human eye.
-pub const GAMMA8: [u8; 256] = [
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1,
-    1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 5, 5, 5,
-    5, 6, 6, 6, 6, 7, 7, 7, 7, 8, 8, 8, 9, 9, 9, 10, 10, 10, 11, 11, 11, 12, 12, 13, 13, 13, 14,
-    14, 15, 15, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22, 22, 23, 24, 24, 25, 25, 26, 27,
-    27, 28, 29, 29, 30, 31, 32, 32, 33, 34, 35, 35, 36, 37, 38, 39, 39, 40, 41, 42, 43, 44, 45, 46,
-    47, 48, 49, 50, 50, 51, 52, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 66, 67, 68, 69, 70, 72,
-    73, 74, 75, 77, 78, 79, 81, 82, 83, 85, 86, 87, 89, 90, 92, 93, 95, 96, 98, 99, 101, 102, 104,
-    105, 107, 109, 110, 112, 114, 115, 117, 119, 120, 122, 124, 126, 127, 129, 131, 133, 135, 137,
-    138, 140, 142, 144, 146, 148, 150, 152, 154, 156, 158, 160, 162, 164, 167, 169, 171, 173, 175,
-    177, 180, 182, 184, 186, 189, 191, 193, 196, 198, 200, 203, 205, 208, 210, 213, 215, 218, 220,
-    223, 225, 228, 231, 233, 236, 239, 241, 244, 247, 249, 252, 255,
+/// Entry `i` is the corrected value of input `i` on the 0-255 scale. The fraction
+/// matters: the curve is flat in 8 bits across the dark range (inputs 0-27 all
+/// round to 0), so correction runs at full precision and dithering does the final
+/// quantization.
+pub const GAMMA16: [u16; 256] = [
+    0, 0, 0, 0, 1, 1, 2, 3, 4, 6, 8, 10, 13, 16, 19, 23, 28, 33, 39, 45, 52, 60, 68, 78, 87, 98,
+    109, 121, 134, 148, 163, 179, 195, 213, 232, 251, 272, 293, 316, 340, 365, 391, 418, 447, 477,
+    508, 540, 573, 608, 644, 682, 721, 761, 802, 846, 890, 936, 984, 1033, 1084, 1136, 1190, 1245,
+    1302, 1361, 1421, 1483, 1547, 1612, 1680, 1749, 1820, 1892, 1967, 2043, 2121, 2202, 2284, 2368,
+    2454, 2542, 2632, 2724, 2818, 2914, 3012, 3112, 3215, 3319, 3426, 3535, 3646, 3759, 3875, 3992,
+    4112, 4235, 4359, 4486, 4616, 4748, 4882, 5018, 5157, 5299, 5442, 5589, 5738, 5889, 6043, 6200,
+    6359, 6520, 6685, 6852, 7021, 7194, 7369, 7546, 7727, 7910, 8096, 8285, 8476, 8671, 8868, 9068,
+    9271, 9477, 9685, 9897, 10112, 10329, 10550, 10774, 11000, 11230, 11463, 11698, 11937, 12179,
+    12425, 12673, 12924, 13179, 13437, 13698, 13962, 14230, 14501, 14775, 15052, 15333, 15617,
+    15905, 16196, 16490, 16788, 17089, 17393, 17701, 18013, 18328, 18646, 18968, 19294, 19623,
+    19956, 20292, 20632, 20976, 21323, 21674, 22029, 22387, 22750, 23115, 23485, 23859, 24236,
+    24617, 25002, 25390, 25783, 26179, 26580, 26984, 27392, 27804, 28220, 28640, 29064, 29492,
+    29925, 30361, 30801, 31245, 31694, 32146, 32603, 33064, 33529, 33998, 34471, 34949, 35431,
+    35917, 36407, 36902, 37400, 37904, 38411, 38923, 39439, 39960, 40485, 41015, 41548, 42087,
+    42630, 43177, 43729, 44285, 44846, 45411, 45981, 46556, 47135, 47718, 48307, 48900, 49497,
+    50100, 50707, 51318, 51935, 52556, 53182, 53812, 54448, 55088, 55733, 56383, 57038, 57698,
+    58362, 59032, 59706, 60385, 61070, 61759, 62453, 63152, 63856, 64566, 65280,
 ];
 
-/// Apply gamma correction to a single color value
-///
-/// # Arguments
-/// * `value` - Input color value (0-255)
+/// Apply gamma correction to a channel value on the 0-255 scale
 ///
-/// # Returns
-/// Gamma-corrected value (0-255)
-#[inline]
-pub fn apply_gamma(value: u8) -> u8 {
-    GAMMA8[value as usize]
+/// Interpolates between table entries, so the result keeps the input's fractional
+/// precision. Values outside 0-255 are clamped.
+pub fn apply_gamma(value: Q32) -> Q32 {
+    let fixed = value.to_fixed().clamp(0, 255 << Q32::SHIFT);
+    let index = (fixed >> Q32::SHIFT) as usize;
+    let frac = i64::from(fixed & ((1 << Q32::SHIFT) - 1));
+    let low = i64::from(GAMMA16[index]);
+    let high = i64::from(GAMMA16[(index + 1).min(255)]);
+    let corrected = low + (((high - low) * frac) >> Q32::SHIFT);
+    Q32::from_fixed((corrected << (Q32::SHIFT - 8)) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gamma_keeps_dark_levels_apart() {
+        let dark = apply_gamma(Q32::from_i32(20));
+        let darker = apply_gamma(Q32::from_i32(19));
+        assert!(darker < dark);
+        assert_eq!(dark.to_i32(), 0);
+        assert_eq!(apply_gamma(Q32::from_i32(255)), Q32::from_i32(255));
+        assert_eq!(apply_gamma(Q32::from_i32(-3)), Q32::from_i32(0));
+        assert_eq!(apply_gamma(Q32::from_i32(300)), Q32::from_i32(255));
+    }
 }
@@ -18,30 +18,48 @@ pub struct ChannelAccumulators {
 /// Finds the maximum channel index from mapping entries and creates Q32 vectors
 /// for R, G, and B channels.
 pub fn initialize_channel_accumulators(entries: &[PixelMappingEntry]) -> ChannelAccumulators {
-    let max_channel = entries
-        .iter()
-        .filter_map(|e| {
-            if !e.is_skip() {
-                Some(e.channel())
-            } else {
-                None
-            }
-        })
-        .max()
-        .unwrap_or(0);
-
-    let mut ch_values_r: Vec<Q32> = Vec::with_capacity((max_channel + 1) as usize);
-    let mut ch_values_g: Vec<Q32> = Vec::with_capacity((max_channel + 1) as usize);
-    let mut ch_values_b: Vec<Q32> = Vec::with_capacity((max_channel + 1) as usize);
-    ch_values_r.resize((max_channel + 1) as usize, Q32::ZERO);
-    ch_values_g.resize((max_channel + 1) as usize, Q32::ZERO);
-    ch_values_b.resize((max_channel + 1) as usize, Q32::ZERO);
-
-    ChannelAccumulators {
-        r: ch_values_r,
-        g: ch_values_g,
-        b: ch_values_b,
-        max_channel,
+    let mut accumulators = ChannelAccumulators::from_buffers(Vec::new(), Vec::new(), Vec::new());
+    accumulators.reset(entries);
+    accumulators
+}
+
+impl ChannelAccumulators {
+    /// Wrap existing buffers (e.g. from a `FrameBufferPool`); contents are discarded
+    pub fn from_buffers(r: Vec<Q32>, g: Vec<Q32>, b: Vec<Q32>) -> Self {
+        Self {
+            r,
+            g,
+            b,
+            max_channel: 0,
+        }
+    }
+
+    /// Give back the R, G and B buffers so they can be recycled
+    pub fn into_buffers(self) -> [Vec<Q32>; 3] {
+        [self.r, self.g, self.b]
+    }
+
+    /// Zero the accumulators and size them for the channels used by `entries`
+    ///
+    /// Only allocates if the buffers are too small.
+    pub fn reset(&mut self, entries: &[PixelMappingEntry]) {
+        self.max_channel = entries
+            .iter()
+            .filter_map(|e| {
+                if !e.is_skip() {
+                    Some(e.channel())
+                } else {
+                    None
+                }
+            })
+            .max()
+            .unwrap_or(0);
+
+        let len = (self.max_channel + 1) as usize;
+        for values in [&mut self.r, &mut self.g, &mut self.b] {
+            values.clear();
+            values.resize(len, Q32::ZERO);
+        }
     }
 }
 
@@ -66,7 +84,30 @@ pub fn accumulate_from_mapping(
     texture_width: u32,
     texture_height: u32,
 ) -> ChannelAccumulators {
-    let mut accumulators = initialize_channel_accumulators(entries);
+    let mut accumulators = ChannelAccumulators::from_buffers(Vec::new(), Vec::new(), Vec::new());
+    accumulate_into(
+        &mut accumulators,
+        entries,
+        texture_data,
+        texture_format,
+        texture_width,
+        texture_height,
+    );
+    accumulators
+}
+
+/// Like [`accumulate_from_mapping`], but accumulating into existing buffers
+///
+/// `accumulators` is reset first, so it can be reused from frame to frame.
+pub fn accumulate_into(
+    accumulators: &mut ChannelAccumulators,
+    entries: &[PixelMappingEntry],
+    texture_data: &[u8],
+    texture_format: &str,
+    texture_width: u32,
+    texture_height: u32,
+) {
+    accumulators.reset(entries);
 
     // Create format-specific sampler
     let sampler = create_sampler(texture_format).expect("Unsupported texture format");
@@ -129,6 +170,4 @@ pub fn accumulate_from_mapping(
             pixel_index += 1;
         }
     }
-
-    accumulators
 }
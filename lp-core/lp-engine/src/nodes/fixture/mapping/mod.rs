@@ -5,14 +5,18 @@ pub mod entry;
 pub mod overlap;
 pub mod points;
 pub mod precompute;
+pub mod preview;
 pub mod sampling;
 pub mod structure;
 
 // Re-export public API
-pub use accumulation::{accumulate_from_mapping, initialize_channel_accumulators};
+pub use accumulation::{
+    ChannelAccumulators, accumulate_from_mapping, accumulate_into, initialize_channel_accumulators,
+};
 pub use entry::{CHANNEL_SKIP, PixelMappingEntry};
 pub use overlap::circle::circle_pixel_overlap;
 pub use points::{MappingPoint, generate_mapping_points};
 pub use precompute::compute_mapping;
+pub use preview::{mapping_csv, render_mapping_preview};
 pub use sampling::{TextureSampler, create_sampler};
 pub use structure::PrecomputedMapping;
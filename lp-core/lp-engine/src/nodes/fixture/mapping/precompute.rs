@@ -5,10 +5,11 @@ use libm;
 use lp_glsl_builtins::glsl::q32::types::q32::Q32;
 use lp_model::FrameId;
 use lp_model::nodes::fixture::mapping::MappingConfig;
+use lp_model::nodes::texture::TextureFilter;
 
 use super::entry::PixelMappingEntry;
 use super::overlap::circle::circle_pixel_overlap;
-use super::points::generate_mapping_points;
+use super::points::{MappingPoint, generate_mapping_points};
 use super::structure::PrecomputedMapping;
 
 /// Compute pre-computed mapping from configuration
@@ -17,6 +18,7 @@ use super::structure::PrecomputedMapping;
 /// * `config` - Mapping configuration
 /// * `texture_width` - Texture width in pixels
 /// * `texture_height` - Texture height in pixels
+/// * `filter` - How lamps sample the texture
 /// * `mapping_data_ver` - FrameId for version tracking
 ///
 /// # Returns
@@ -25,10 +27,9 @@ pub fn compute_mapping(
     config: &MappingConfig,
     texture_width: u32,
     texture_height: u32,
+    filter: TextureFilter,
     mapping_data_ver: FrameId,
 ) -> PrecomputedMapping {
-    let mut mapping = PrecomputedMapping::new(texture_width, texture_height, mapping_data_ver);
-
     match config {
         MappingConfig::PathPoints {
             paths: _,
@@ -36,82 +37,106 @@ pub fn compute_mapping(
         } => {
             // First pass: collect all mapping points (circles)
             let mapping_points = generate_mapping_points(config, texture_width, texture_height);
+            compute_mapping_for_points(
+                &mapping_points,
+                texture_width,
+                texture_height,
+                filter,
+                mapping_data_ver,
+            )
+        }
+    }
+}
 
-            // Second pass: for each pixel, compute contributions from all circles
-            let mut pixel_contributions: Vec<Vec<(u32, f32)>> =
-                Vec::with_capacity((texture_width * texture_height) as usize);
-            pixel_contributions.resize((texture_width * texture_height) as usize, Vec::new());
-
-            // Track total weight per channel for normalization
-            let mut channel_totals: Vec<f32> = Vec::new();
-            let max_channel = mapping_points.iter().map(|p| p.channel).max().unwrap_or(0);
-            channel_totals.resize((max_channel + 1) as usize, 0.0);
-
-            for mapping_point in &mapping_points {
-                // Convert normalized coordinates to pixel coordinates
-                let center_x = mapping_point.center[0] * texture_width as f32;
-                let center_y = mapping_point.center[1] * texture_height as f32;
-                // Convert normalized radius to pixel radius
-                let radius = mapping_point.radius * texture_width.max(texture_height) as f32;
-
-                // Find pixels that might overlap with this circle
-                let min_x = (libm::floorf(center_x - radius) as i32).max(0) as u32;
-                let max_x =
-                    (libm::ceilf(center_x + radius) as i32).min(texture_width as i32 - 1) as u32;
-                let min_y = (libm::floorf(center_y - radius) as i32).max(0) as u32;
-                let max_y =
-                    (libm::ceilf(center_y + radius) as i32).min(texture_height as i32 - 1) as u32;
-
-                for y in min_y..=max_y {
-                    for x in min_x..=max_x {
-                        let weight = circle_pixel_overlap(center_x, center_y, radius, x, y);
-                        if weight > 0.0 {
-                            let pixel_idx = (y * texture_width + x) as usize;
-                            pixel_contributions[pixel_idx].push((mapping_point.channel, weight));
-                            // Accumulate total weight per channel
-                            channel_totals[mapping_point.channel as usize] += weight;
-                        }
-                    }
+/// Compute pre-computed mapping for already generated mapping points
+fn compute_mapping_for_points(
+    mapping_points: &[MappingPoint],
+    texture_width: u32,
+    texture_height: u32,
+    filter: TextureFilter,
+    mapping_data_ver: FrameId,
+) -> PrecomputedMapping {
+    let mut mapping = PrecomputedMapping::new(texture_width, texture_height, mapping_data_ver);
+
+    // Second pass: for each pixel, compute contributions from all circles
+    let mut pixel_contributions: Vec<Vec<(u32, f32)>> =
+        Vec::with_capacity((texture_width * texture_height) as usize);
+    pixel_contributions.resize((texture_width * texture_height) as usize, Vec::new());
+
+    // Track total weight per channel for normalization
+    let mut channel_totals: Vec<f32> = Vec::new();
+    let max_channel = mapping_points.iter().map(|p| p.channel).max().unwrap_or(0);
+    channel_totals.resize((max_channel + 1) as usize, 0.0);
+
+    for mapping_point in mapping_points {
+        let mut add_contribution = |x: u32, y: u32, weight: f32| {
+            if weight > 0.0 {
+                let pixel_idx = (y * texture_width + x) as usize;
+                let contributions = &mut pixel_contributions[pixel_idx];
+                // Clamped bilinear taps can land on the same pixel twice
+                match contributions
+                    .iter_mut()
+                    .find(|(ch, _)| *ch == mapping_point.channel)
+                {
+                    Some((_, w)) => *w += weight,
+                    None => contributions.push((mapping_point.channel, weight)),
                 }
+                // Accumulate total weight per channel
+                channel_totals[mapping_point.channel as usize] += weight;
             }
+        };
 
-            // Third pass: normalize weights per-channel and build entries
-            // Each channel's total contribution from all pixels should sum to 1.0
-            for y in 0..texture_height {
-                for x in 0..texture_width {
-                    let pixel_idx = (y * texture_width + x) as usize;
-                    let contributions = &pixel_contributions[pixel_idx];
-
-                    if contributions.is_empty() {
-                        // No contributions - add SKIP entry
-                        mapping.entries.push(PixelMappingEntry::skip());
-                    } else {
-                        // Normalize weights per-channel: divide by channel total
-                        // This ensures each channel's total contribution from all pixels = 1.0
-                        let normalized: Vec<(u32, f32)> = contributions
-                            .iter()
-                            .map(|(ch, w)| {
-                                let channel_total = channel_totals[*ch as usize];
-                                if channel_total > 0.0 {
-                                    (*ch, *w / channel_total)
-                                } else {
-                                    (*ch, 0.0)
-                                }
-                            })
-                            .collect();
-
-                        // Add entries (last one has has_more = false)
-                        for (idx, (channel, weight)) in normalized.iter().enumerate() {
-                            let has_more = idx < normalized.len() - 1;
-                            let contribution_q32 = Q32::from_f32(*weight);
-
-                            mapping.entries.push(PixelMappingEntry::new(
-                                *channel,
-                                contribution_q32,
-                                has_more,
-                            ));
+        match filter {
+            TextureFilter::Nearest => circle_contributions(
+                mapping_point,
+                texture_width,
+                texture_height,
+                &mut add_contribution,
+            ),
+            TextureFilter::Bilinear => bilinear_contributions(
+                mapping_point,
+                texture_width,
+                texture_height,
+                &mut add_contribution,
+            ),
+        }
+    }
+
+    // Third pass: normalize weights per-channel and build entries
+    // Each channel's total contribution from all pixels should sum to 1.0
+    for y in 0..texture_height {
+        for x in 0..texture_width {
+            let pixel_idx = (y * texture_width + x) as usize;
+            let contributions = &pixel_contributions[pixel_idx];
+
+            if contributions.is_empty() {
+                // No contributions - add SKIP entry
+                mapping.entries.push(PixelMappingEntry::skip());
+            } else {
+                // Normalize weights per-channel: divide by channel total
+                // This ensures each channel's total contribution from all pixels = 1.0
+                let normalized: Vec<(u32, f32)> = contributions
+                    .iter()
+                    .map(|(ch, w)| {
+                        let channel_total = channel_totals[*ch as usize];
+                        if channel_total > 0.0 {
+                            (*ch, *w / channel_total)
+                        } else {
+                            (*ch, 0.0)
                         }
-                    }
+                    })
+                    .collect();
+
+                // Add entries (last one has has_more = false)
+                for (idx, (channel, weight)) in normalized.iter().enumerate() {
+                    let has_more = idx < normalized.len() - 1;
+                    let contribution_q32 = Q32::from_f32(*weight);
+
+                    mapping.entries.push(PixelMappingEntry::new(
+                        *channel,
+                        contribution_q32,
+                        has_more,
+                    ));
                 }
             }
         }
@@ -119,3 +144,123 @@ pub fn compute_mapping(
 
     mapping
 }
+
+/// Weight each pixel by how much of the mapping point's circle covers it
+fn circle_contributions(
+    mapping_point: &MappingPoint,
+    texture_width: u32,
+    texture_height: u32,
+    add_contribution: &mut impl FnMut(u32, u32, f32),
+) {
+    // Convert normalized coordinates to pixel coordinates
+    let center_x = mapping_point.center[0] * texture_width as f32;
+    let center_y = mapping_point.center[1] * texture_height as f32;
+    // Convert normalized radius to pixel radius
+    let radius = mapping_point.radius * texture_width.max(texture_height) as f32;
+
+    // Find pixels that might overlap with this circle
+    let min_x = (libm::floorf(center_x - radius) as i32).max(0) as u32;
+    let max_x = (libm::ceilf(center_x + radius) as i32).min(texture_width as i32 - 1) as u32;
+    let min_y = (libm::floorf(center_y - radius) as i32).max(0) as u32;
+    let max_y = (libm::ceilf(center_y + radius) as i32).min(texture_height as i32 - 1) as u32;
+
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let weight = circle_pixel_overlap(center_x, center_y, radius, x, y);
+            add_contribution(x, y, weight);
+        }
+    }
+}
+
+/// Weight the four pixels around the mapping point's center by bilinear interpolation
+///
+/// Pixel centers sit at half-integer coordinates; points outside the outermost
+/// centers clamp to the edge, so a single-pixel texture always gets full weight.
+fn bilinear_contributions(
+    mapping_point: &MappingPoint,
+    texture_width: u32,
+    texture_height: u32,
+    add_contribution: &mut impl FnMut(u32, u32, f32),
+) {
+    let (x0, x1, fx) = bilinear_axis(mapping_point.center[0], texture_width);
+    let (y0, y1, fy) = bilinear_axis(mapping_point.center[1], texture_height);
+
+    add_contribution(x0, y0, (1.0 - fx) * (1.0 - fy));
+    add_contribution(x1, y0, fx * (1.0 - fy));
+    add_contribution(x0, y1, (1.0 - fx) * fy);
+    add_contribution(x1, y1, fx * fy);
+}
+
+/// Find the two pixels along one axis surrounding a normalized coordinate,
+/// and the fraction of the way from the first to the second
+fn bilinear_axis(coord: f32, size: u32) -> (u32, u32, f32) {
+    let max_index = size.saturating_sub(1);
+    let pos = (coord * size as f32 - 0.5).clamp(0.0, max_index as f32);
+    let i0 = libm::floorf(pos) as u32;
+    let i1 = (i0 + 1).min(max_index);
+    (i0, i1, pos - i0 as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::fixture::mapping::accumulate_from_mapping;
+
+    /// Evenly spaced points across the horizontal center line
+    fn line_points(count: u32) -> Vec<MappingPoint> {
+        (0..count)
+            .map(|i| MappingPoint {
+                channel: i,
+                center: [(i as f32 + 0.5) / count as f32, 0.5],
+                radius: 0.05,
+            })
+            .collect()
+    }
+
+    /// Sample an R8 texture at each point, returning the red channel (0-255)
+    fn sample_red(
+        points: &[MappingPoint],
+        data: &[u8],
+        width: u32,
+        filter: TextureFilter,
+    ) -> Vec<f32> {
+        let mapping = compute_mapping_for_points(points, width, 1, filter, FrameId::new(0));
+        let accumulators = accumulate_from_mapping(&mapping.entries, data, "R8", width, 1);
+        accumulators.r.iter().map(|v| v.to_f32()).collect()
+    }
+
+    #[test]
+    fn test_bilinear_upsamples_gradient() {
+        let points = line_points(8);
+        let red = sample_red(&points, &[0, 255], 2, TextureFilter::Bilinear);
+
+        // The outer quarter on each side clamps to the edge texels, the middle
+        // ramps linearly between them
+        let expected = [0.0, 0.0, 0.125, 0.375, 0.625, 0.875, 1.0, 1.0];
+        for (value, expected) in red.iter().zip(expected) {
+            assert!((value - expected * 255.0).abs() < 1.0, "{red:?}");
+        }
+    }
+
+    #[test]
+    fn test_nearest_is_blocky() {
+        let points = line_points(8);
+        let red = sample_red(&points, &[0, 255], 2, TextureFilter::Nearest);
+
+        for (i, value) in red.iter().enumerate() {
+            let expected = if i < 4 { 0.0 } else { 255.0 };
+            assert!((value - expected).abs() < 1.0, "{red:?}");
+        }
+    }
+
+    #[test]
+    fn test_bilinear_single_texel() {
+        let points = line_points(4);
+        let red = sample_red(&points, &[128], 1, TextureFilter::Bilinear);
+
+        assert_eq!(red.len(), 4);
+        for value in &red {
+            assert!((value - 128.0).abs() < 1.0, "{red:?}");
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Mapping previews for checking a fixture's layout
+//!
+//! Lists the texture pixel each lamp samples, and draws a test pattern that colors lamps
+//! by index (red for the first lamp through blue for the last), so a wrong wiring order
+//! shows up as a broken gradient.
+
+use super::points::MappingPoint;
+use alloc::{format, string::String, string::ToString};
+use lp_shared::Texture;
+
+/// Texture pixel under a mapping point's center
+pub fn pixel_position(point: &MappingPoint, texture_width: u32, texture_height: u32) -> (u32, u32) {
+    let to_pixel = |coord: f32, size: u32| {
+        let pixel = (coord * size as f32) as u32;
+        pixel.min(size.saturating_sub(1))
+    };
+    (
+        to_pixel(point.center[0], texture_width),
+        to_pixel(point.center[1], texture_height),
+    )
+}
+
+/// Gradient color for lamp `index` of `count`: red at the first lamp, blue at the last
+pub fn index_color(index: usize, count: usize) -> [u8; 4] {
+    let last = count.saturating_sub(1).max(1);
+    let t = (index.min(last) * 255 / last) as u8;
+    [255 - t, 0, t, 255]
+}
+
+/// CSV of lamp index to texture pixel, one row per lamp in channel order
+///
+/// Columns are `index,channel,x,y`, with a header row.
+pub fn mapping_csv(points: &[MappingPoint], texture_width: u32, texture_height: u32) -> String {
+    let mut csv = "index,channel,x,y\n".to_string();
+    for (index, point) in points.iter().enumerate() {
+        let (x, y) = pixel_position(point, texture_width, texture_height);
+        csv.push_str(&format!("{index},{},{x},{y}\n", point.channel));
+    }
+    csv
+}
+
+/// Draw the index gradient at each lamp's pixel on a black texture
+pub fn render_mapping_preview(
+    points: &[MappingPoint],
+    texture_width: u32,
+    texture_height: u32,
+) -> Texture {
+    let mut texture = Texture::new(texture_width, texture_height, "RGBA8".to_string())
+        .expect("RGBA8 is a supported format");
+    texture.compute_all(|_, _| [0, 0, 0, 255]);
+    for (index, point) in points.iter().enumerate() {
+        let (x, y) = pixel_position(point, texture_width, texture_height);
+        texture.set_pixel(x, y, index_color(index, points.len()));
+    }
+    texture
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::fixture::mapping::generate_mapping_points;
+    use alloc::vec;
+    use lp_model::nodes::fixture::mapping::{MappingConfig, PathSpec, RingOrder};
+
+    /// A center lamp and a ring of four, over a 9x9 texture so every lamp lands
+    /// mid-pixel
+    fn ring_points() -> alloc::vec::Vec<MappingPoint> {
+        let config = MappingConfig::PathPoints {
+            paths: vec![PathSpec::RingArray {
+                center: (0.5, 0.5),
+                diameter: 1.0,
+                start_ring_inclusive: 0,
+                end_ring_exclusive: 2,
+                ring_lamp_counts: vec![1, 4],
+                offset_angle: 0.0,
+                order: RingOrder::InnerFirst,
+            }],
+            sample_diameter: 1.0,
+        };
+        generate_mapping_points(&config, 9, 9)
+    }
+
+    #[test]
+    fn test_mapping_csv_lists_index_to_pixel() {
+        let csv = mapping_csv(&ring_points(), 9, 9);
+        assert_eq!(
+            csv,
+            "index,channel,x,y\n\
+             0,0,4,4\n\
+             1,1,8,4\n\
+             2,2,4,8\n\
+             3,3,0,4\n\
+             4,4,4,0\n"
+        );
+    }
+
+    #[test]
+    fn test_render_mapping_preview_gradient() {
+        let texture = render_mapping_preview(&ring_points(), 9, 9);
+        assert_eq!(texture.get_pixel(4, 4), Some([255, 0, 0, 255]));
+        assert_eq!(texture.get_pixel(4, 8), Some([128, 0, 127, 255]));
+        assert_eq!(texture.get_pixel(4, 0), Some([0, 0, 255, 255]));
+        // Pixels without a lamp stay black
+        assert_eq!(texture.get_pixel(1, 1), Some([0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_index_color_endpoints() {
+        assert_eq!(index_color(0, 1), [255, 0, 0, 255]);
+        assert_eq!(index_color(0, 3), [255, 0, 0, 255]);
+        assert_eq!(index_color(2, 3), [0, 0, 255, 255]);
+    }
+}
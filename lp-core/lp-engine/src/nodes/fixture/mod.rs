@@ -1,3 +1,4 @@
+pub mod color_correction;
 pub mod gamma;
 pub mod mapping;
 pub mod runtime;
@@ -1,15 +1,19 @@
 use crate::error::Error;
+use crate::nodes::fixture::color_correction::ColorCorrection;
 use crate::nodes::fixture::gamma::apply_gamma;
 use crate::nodes::fixture::mapping::{
-    MappingPoint, PrecomputedMapping, accumulate_from_mapping, compute_mapping,
+    ChannelAccumulators, MappingPoint, PrecomputedMapping, accumulate_into, compute_mapping,
     generate_mapping_points,
 };
+use crate::nodes::output::dither::dither_to_u8;
 use crate::nodes::{NodeConfig, NodeRuntime};
 use crate::runtime::contexts::{NodeInitContext, OutputHandle, RenderContext, TextureHandle};
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 use lp_glsl_builtins::glsl::q32::types::q32::ToQ32;
 use lp_model::FrameId;
-use lp_model::nodes::fixture::{ColorOrder, FixtureConfig};
+use lp_model::nodes::fixture::{ColorOrder, FixtureConfig, MaskConfig, Rgbw, WhiteExtraction};
+use lp_model::nodes::output::OutputConfig;
+use lp_model::nodes::texture::TextureFilter;
 use lp_shared::fs::fs_event::FsChange;
 
 /// Fixture node runtime
@@ -22,6 +26,8 @@ pub struct FixtureRuntime {
     transform: [[f32; 4]; 4],
     texture_width: Option<u32>,
     texture_height: Option<u32>,
+    /// Texture filter the pre-computed mapping was built with
+    texture_filter: TextureFilter,
     /// Pre-computed pixel-to-channel mapping
     precomputed_mapping: Option<PrecomputedMapping>,
     /// Last sampled lamp colors (RGB per lamp, ordered by channel index)
@@ -30,6 +36,20 @@ pub struct FixtureRuntime {
     brightness: u8,
     /// Enable gamma correction, defaults to true
     gamma_correction: bool,
+    /// Color correction applied to output colors, identity by default
+    color_correction: ColorCorrection,
+    /// Per-lamp alpha mask, unmasked if not set
+    mask: Option<MaskConfig>,
+    /// How the white channel is computed for RGBW color orders
+    white_extraction: WhiteExtraction,
+    /// Rendered frame counter, drives temporal dithering (starts at a seeded phase)
+    frame_counter: u32,
+    /// Output universe the fixture's channels start in
+    universe: u32,
+    /// Channel offset within the universe
+    channel_offset: u32,
+    /// Number of lamps the mapping drives
+    lamp_count: u32,
 }
 
 impl FixtureRuntime {
@@ -48,10 +68,18 @@ impl FixtureRuntime {
             ], // Identity matrix
             texture_width: None,
             texture_height: None,
+            texture_filter: TextureFilter::Nearest,
             precomputed_mapping: None,
             lamp_colors: Vec::new(),
             brightness: 64,
             gamma_correction: true,
+            color_correction: ColorCorrection::IDENTITY,
+            mask: None,
+            white_extraction: WhiteExtraction::Min,
+            frame_counter: 0,
+            universe: 0,
+            channel_offset: 0,
+            lamp_count: 0,
         }
     }
 
@@ -90,11 +118,54 @@ impl FixtureRuntime {
         &self.lamp_colors
     }
 
-    /// Regenerate mapping when texture resolution changes or config versions change
+    /// Number of lamps the mapping drives (0 makes the fixture a no-op)
+    pub fn lamp_count(&self) -> u32 {
+        self.lamp_count
+    }
+
+    /// End of this fixture's channels in the buffer of `output`, in bytes
+    ///
+    /// Returns 0 for a fixture without lamps, which writes nothing.
+    pub fn channel_end(&self, output: &OutputConfig) -> u32 {
+        if self.lamp_count == 0 {
+            return 0;
+        }
+        output.channel_start(self.universe, self.channel_offset)
+            + self.lamp_count * self.color_order.bytes_per_pixel() as u32
+    }
+
+    /// Turn off the channels this fixture last wrote, for when it is disabled
+    ///
+    /// Does nothing once the fixture is dark, so a disabled fixture only touches its
+    /// output on the frame it goes dark.
+    pub fn blank(&mut self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        if self.lamp_colors.is_empty() {
+            return Ok(());
+        }
+        let output_handle = self.output_handle.ok_or_else(|| Error::Other {
+            message: String::from("Output handle not resolved"),
+        })?;
+
+        let channel_count = (self.lamp_colors.len() / 3) as u32;
+        let bytes_per_pixel = self.color_order.bytes_per_pixel() as u32;
+        let buffer = ctx.get_output(
+            output_handle,
+            self.universe,
+            self.channel_offset,
+            channel_count * bytes_per_pixel,
+        )?;
+        buffer.fill(0);
+
+        self.lamp_colors.clear();
+        Ok(())
+    }
+
+    /// Regenerate mapping when texture resolution, filter or config versions change
     fn regenerate_mapping_if_needed(
         &mut self,
         texture_width: u32,
         texture_height: u32,
+        texture_filter: TextureFilter,
         our_config_ver: FrameId,
         texture_config_ver: FrameId,
     ) -> Result<(), Error> {
@@ -106,6 +177,7 @@ impl FixtureRuntime {
                 .texture_height
                 .map(|h| h != texture_height)
                 .unwrap_or(true)
+            || self.texture_filter != texture_filter
             || self
                 .precomputed_mapping
                 .as_ref()
@@ -127,6 +199,7 @@ impl FixtureRuntime {
                 &config.mapping,
                 texture_width,
                 texture_height,
+                texture_filter,
                 max_config_ver,
             );
 
@@ -135,6 +208,7 @@ impl FixtureRuntime {
             // Update texture dimensions
             self.texture_width = Some(texture_width);
             self.texture_height = Some(texture_height);
+            self.texture_filter = texture_filter;
 
             // Keep existing mapping points for now (used by state extraction)
             self.mapping = generate_mapping_points(&config.mapping, texture_width, texture_height);
@@ -142,43 +216,21 @@ impl FixtureRuntime {
 
         Ok(())
     }
-}
-
-impl NodeRuntime for FixtureRuntime {
-    fn init(&mut self, ctx: &dyn NodeInitContext) -> Result<(), Error> {
-        // Get config
-        let config = self.config.as_ref().ok_or_else(|| Error::InvalidConfig {
-            node_path: String::from("fixture"),
-            reason: String::from("Config not set"),
-        })?;
-
-        // Resolve texture handle
-        let texture_handle = ctx.resolve_texture(&config.texture_spec)?;
-        self.texture_handle = Some(texture_handle);
-
-        // Resolve output handle
-        let output_handle = ctx.resolve_output(&config.output_spec)?;
-        self.output_handle = Some(output_handle);
-
-        // Store config values
-        self.color_order = config.color_order;
-        self.transform = config.transform;
-        self.brightness = config.brightness.unwrap_or(64);
-        self.gamma_correction = config.gamma_correction.unwrap_or(true);
-
-        // Mapping will be generated in render() when texture is available
-        // Texture dimensions are not available in init() (texture is lazy-loaded)
-        self.mapping = Vec::new();
-
-        Ok(())
-    }
 
-    fn render(&mut self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+    /// Sample the texture and write this fixture's channels, using `accumulators` as
+    /// scratch space
+    fn render_with(
+        &mut self,
+        ctx: &mut dyn RenderContext,
+        accumulators: &mut ChannelAccumulators,
+    ) -> Result<(), Error> {
         // Get texture handle
         let texture_handle = self.texture_handle.ok_or_else(|| Error::Other {
             message: String::from("Texture handle not resolved"),
         })?;
 
+        let texture_filter = ctx.get_texture_filter(texture_handle)?;
+
         // Get texture (triggers lazy rendering if needed)
         let texture = ctx.get_texture(texture_handle)?;
 
@@ -192,6 +244,7 @@ impl NodeRuntime for FixtureRuntime {
         self.regenerate_mapping_if_needed(
             texture_width,
             texture_height,
+            texture_filter,
             our_config_ver,
             texture_config_ver,
         )?;
@@ -207,7 +260,8 @@ impl NodeRuntime for FixtureRuntime {
         // Accumulate channel values using format-specific sampling
         let texture_data = texture.data();
         let texture_format = texture.format();
-        let accumulators = accumulate_from_mapping(
+        accumulate_into(
+            accumulators,
             &mapping.entries,
             texture_data,
             texture_format,
@@ -231,37 +285,126 @@ impl NodeRuntime for FixtureRuntime {
         self.lamp_colors.resize((max_channel as usize + 1) * 3, 0);
 
         let brightness = self.brightness.to_q32() / 255.to_q32();
-
-        // Write sampled values to output buffer
-        // For now, use universe 0 and channel_offset 0 (sequential writing)
-        // TODO: Add universe and channel_offset fields to FixtureConfig when needed
-        let universe = 0u32;
-        let channel_offset = 0u32;
+        let dither = ctx.get_output_dither(output_handle)?;
+        let frame = self.frame_counter;
+        self.frame_counter = self.frame_counter.wrapping_add(1);
+
+        // Write sampled values to output buffer, starting at the configured universe/offset
+        let universe = self.universe;
+        let channel_offset = self.channel_offset;
+        let color_correction =
+            (!self.color_correction.is_identity()).then_some(self.color_correction);
+        let bytes_per_pixel = self.color_order.bytes_per_pixel() as u32;
         for channel in 0..=max_channel as usize {
-            let mut r = (ch_values_r[channel] * brightness).to_u8_clamped();
-            let mut g = (ch_values_g[channel] * brightness).to_u8_clamped();
-            let mut b = (ch_values_b[channel] * brightness).to_u8_clamped();
+            let lamp = channel as u32;
+            let mut rgb = [
+                ch_values_r[channel] * brightness,
+                ch_values_g[channel] * brightness,
+                ch_values_b[channel] * brightness,
+            ];
+            // Masked lamps are dimmed before anything else, so they show as off in
+            // lamp_colors too
+            if let Some(mask) = &self.mask {
+                let alpha = mask.alpha(lamp);
+                if alpha != 255 {
+                    let alpha = alpha.to_q32() / 255.to_q32();
+                    rgb = rgb.map(|value| value * alpha);
+                }
+            }
+            let [r, g, b] = rgb.map(|value| dither_to_u8(value, dither, lamp, frame));
 
             let idx = channel * 3;
             self.lamp_colors[idx] = r;
             self.lamp_colors[idx + 1] = g;
             self.lamp_colors[idx + 2] = b;
 
-            // Apply gamma correction if enabled, _after_ writing to lamp_colors, which should
-            // not be gamma corrected
+            // Color correction and gamma match this strip, so they are left out of
+            // lamp_colors. Both run at full precision, leaving dithering as the final
+            // quantization: gamma on 8-bit values would flatten the dark range, where
+            // dithering matters most.
+            if let Some(correction) = color_correction {
+                rgb = correction.apply(rgb);
+            }
             if self.gamma_correction {
-                r = apply_gamma(r);
-                g = apply_gamma(g);
-                b = apply_gamma(b);
+                rgb = rgb.map(apply_gamma);
             }
+            let [r, g, b] = rgb.map(|value| dither_to_u8(value, dither, lamp, frame));
 
-            let start_ch = channel_offset + (channel as u32) * 3; // 3 bytes per RGB
-            let buffer = ctx.get_output(output_handle, universe, start_ch, 3)?;
-            self.color_order.write_rgb(buffer, 0, r, g, b);
+            // White is extracted last, from the final (gamma corrected) color
+            let color = if self.color_order.has_white() {
+                self.white_extraction.extract(r, g, b)
+            } else {
+                Rgbw::new(r, g, b, 0)
+            };
+            let start_ch = channel_offset + (channel as u32) * bytes_per_pixel;
+            let buffer = ctx.get_output(output_handle, universe, start_ch, bytes_per_pixel)?;
+            self.color_order.write_rgbw(buffer, 0, color);
         }
 
         Ok(())
     }
+}
+
+impl NodeRuntime for FixtureRuntime {
+    fn init(&mut self, ctx: &dyn NodeInitContext) -> Result<(), Error> {
+        // Get config
+        let config = self.config.as_ref().ok_or_else(|| Error::InvalidConfig {
+            node_path: String::from("fixture"),
+            reason: String::from("Config not set"),
+        })?;
+
+        // Resolve texture handle
+        let texture_handle = ctx.resolve_texture(&config.texture_spec)?;
+        self.texture_handle = Some(texture_handle);
+
+        // Resolve output handle
+        let output_handle = ctx.resolve_output(&config.output_spec)?;
+        self.output_handle = Some(output_handle);
+
+        // Store config values
+        self.color_order = config.color_order;
+        self.transform = config.transform;
+        self.brightness = config.brightness.unwrap_or(64);
+        self.gamma_correction = config.gamma_correction.unwrap_or(true);
+        self.color_correction = ColorCorrection::from_config(config.color_correction.as_ref());
+        self.mask = config.mask.clone();
+        self.white_extraction = config.white_extraction.unwrap_or_default();
+        self.universe = config.universe.unwrap_or(0);
+        self.channel_offset = config.channel_offset.unwrap_or(0);
+        self.lamp_count = config.mapping.lamp_count();
+
+        // Start temporal dithering at a seeded phase so fixtures sharing an output
+        // don't flicker in lockstep
+        self.frame_counter = ctx.rng().next_u32();
+
+        // Mapping will be generated in render() when texture is available
+        // Texture dimensions are not available in init() (texture is lazy-loaded)
+        self.mapping = Vec::new();
+
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        // A fixture without lamps has nothing to sample or send (it only darkens
+        // lamps it drove before a config change)
+        if self.lamp_count == 0 {
+            return self.blank(ctx);
+        }
+
+        // Accumulator buffers come from the frame pool so they aren't reallocated
+        // every frame
+        let pool = ctx.frame_pool();
+        let mut accumulators =
+            ChannelAccumulators::from_buffers(pool.take(), pool.take(), pool.take());
+
+        let result = self.render_with(ctx, &mut accumulators);
+
+        let pool = ctx.frame_pool();
+        for buffer in accumulators.into_buffers() {
+            pool.recycle(buffer);
+        }
+        result
+    }
 
     fn as_any(&self) -> &dyn core::any::Any {
         self
@@ -301,6 +444,13 @@ impl NodeRuntime for FixtureRuntime {
         self.transform = fixture_config.transform;
         self.brightness = fixture_config.brightness.unwrap_or(64);
         self.gamma_correction = fixture_config.gamma_correction.unwrap_or(true);
+        self.color_correction =
+            ColorCorrection::from_config(fixture_config.color_correction.as_ref());
+        self.mask = fixture_config.mask.clone();
+        self.white_extraction = fixture_config.white_extraction.unwrap_or_default();
+        self.universe = fixture_config.universe.unwrap_or(0);
+        self.channel_offset = fixture_config.channel_offset.unwrap_or(0);
+        self.lamp_count = fixture_config.mapping.lamp_count();
 
         // Re-resolve handles if they changed
         if texture_changed {
@@ -340,6 +490,19 @@ impl NodeRuntime for FixtureRuntime {
         // This is a no-op for now
         Ok(())
     }
+
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), Error> {
+        match name {
+            // Automated as 0.0-1.0 rather than the config's 0-255
+            "brightness" => {
+                self.brightness = (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+                Ok(())
+            }
+            _ => Err(Error::Other {
+                message: format!("Fixture has no automatable parameter `{name}`"),
+            }),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -438,7 +601,13 @@ mod tests {
         // Build mapping for a small texture
         let texture_width = 32u32;
         let texture_height = 32u32;
-        let mapping = compute_mapping(&config, texture_width, texture_height, FrameId::new(1));
+        let mapping = compute_mapping(
+            &config,
+            texture_width,
+            texture_height,
+            TextureFilter::Nearest,
+            FrameId::new(1),
+        );
 
         // Sum up all contributions to channel 0 from all pixels
         // Decode contributions the same way the runtime does
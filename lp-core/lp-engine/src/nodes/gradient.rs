@@ -0,0 +1,195 @@
+//! Color gradients that shaders sample through `palette(t)`
+//!
+//! A shader node with gradient stops in its config gets a generated
+//! `vec3 palette(float t)` function appended to its source, so every backend runs the
+//! same lookup without needing a host call.
+
+use super::color::color_config_to_rgb;
+use alloc::{format, string::String, vec::Vec};
+use lp_glsl_builtins::glsl::q32::types::q32::Q32;
+use lp_glsl_builtins::glsl::q32::types::vec3_q32::Vec3Q32;
+use lp_model::nodes::GradientStopConfig;
+
+/// Name of the generated GLSL function
+pub const PALETTE_FUNCTION_NAME: &str = "palette";
+
+/// Color at a position along a gradient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GradientStop {
+    /// Position in [0, 1]
+    pub position: Q32,
+    /// RGB color, components in [0, 1]
+    pub color: Vec3Q32,
+}
+
+/// Piecewise-linear RGB gradient
+///
+/// Colors are interpolated between neighbouring stops. Positions before the first
+/// stop take its color, and positions after the last take the last stop's color.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gradient {
+    /// Stops sorted by position
+    stops: Vec<GradientStop>,
+}
+
+impl Gradient {
+    /// Build a gradient from config stops
+    ///
+    /// Positions are clamped to [0, 1] and stops are sorted; stops at the same
+    /// position keep their config order, making a hard edge.
+    pub fn from_config(stops: &[GradientStopConfig]) -> Self {
+        let mut stops: Vec<GradientStop> = stops
+            .iter()
+            .map(|stop| GradientStop {
+                position: Q32::from_f32(stop.position).clamp(Q32::ZERO, Q32::ONE),
+                color: color_config_to_rgb(&stop.color),
+            })
+            .collect();
+        stops.sort_by_key(|stop| stop.position);
+        Self { stops }
+    }
+
+    pub fn stops(&self) -> &[GradientStop] {
+        &self.stops
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Color at `t`, clamped to [0, 1]; black for a gradient with no stops
+    pub fn sample(&self, t: Q32) -> Vec3Q32 {
+        let t = t.clamp(Q32::ZERO, Q32::ONE);
+        let Some(first) = self.stops.first() else {
+            return Vec3Q32::from_i32(0, 0, 0);
+        };
+        if t <= first.position {
+            return first.color;
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            if t <= b.position {
+                if b.position == a.position {
+                    return b.color;
+                }
+                let f = (t - a.position) / (b.position - a.position);
+                return a.color + (b.color - a.color) * f;
+            }
+        }
+        self.stops[self.stops.len() - 1].color
+    }
+
+    /// GLSL source for `vec3 palette(float t)` with the same stops as [`Self::sample`]
+    pub fn to_glsl(&self) -> String {
+        let mut glsl = format!("vec3 {PALETTE_FUNCTION_NAME}(float t) {{\n");
+        glsl.push_str("    float x = clamp(t, 0.0, 1.0);\n");
+        if let Some(first) = self.stops.first() {
+            glsl.push_str(&format!(
+                "    if (x <= {}) {{ return {}; }}\n",
+                glsl_float(first.position),
+                glsl_vec3(first.color)
+            ));
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            // A hard edge is covered by the previous check
+            if b.position == a.position {
+                continue;
+            }
+            glsl.push_str(&format!(
+                "    if (x <= {}) {{ return mix({}, {}, (x - {}) / {}); }}\n",
+                glsl_float(b.position),
+                glsl_vec3(a.color),
+                glsl_vec3(b.color),
+                glsl_float(a.position),
+                glsl_float(b.position - a.position)
+            ));
+        }
+        let last = self
+            .stops
+            .last()
+            .map_or(Vec3Q32::from_i32(0, 0, 0), |stop| stop.color);
+        glsl.push_str(&format!("    return {};\n}}\n", glsl_vec3(last)));
+        glsl
+    }
+}
+
+/// GLSL float literal for a Q32 value
+fn glsl_float(value: Q32) -> String {
+    // Debug formatting always includes a decimal point or exponent
+    format!("{:?}", value.to_f32())
+}
+
+fn glsl_vec3(value: Vec3Q32) -> String {
+    format!(
+        "vec3({}, {}, {})",
+        glsl_float(value.x),
+        glsl_float(value.y),
+        glsl_float(value.z)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lp_model::nodes::ColorConfig;
+
+    fn stop(position: f32, r: f32, g: f32, b: f32) -> GradientStopConfig {
+        GradientStopConfig {
+            position,
+            color: ColorConfig::Rgb { r, g, b },
+        }
+    }
+
+    #[test]
+    fn test_two_stop_midpoint() {
+        let gradient = Gradient::from_config(&[stop(0.0, 1.0, 0.0, 0.0), stop(1.0, 0.0, 0.0, 1.0)]);
+        assert_eq!(gradient.sample(Q32::HALF), Vec3Q32::from_f32(0.5, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_sample_clamps_and_sorts() {
+        // Out of order, and not covering the whole range
+        let gradient =
+            Gradient::from_config(&[stop(0.75, 0.0, 1.0, 0.0), stop(0.25, 1.0, 0.0, 0.0)]);
+        let red = Vec3Q32::from_f32(1.0, 0.0, 0.0);
+        let green = Vec3Q32::from_f32(0.0, 1.0, 0.0);
+        assert_eq!(gradient.sample(Q32::from_f32(-1.0)), red);
+        assert_eq!(gradient.sample(Q32::from_f32(0.1)), red);
+        assert_eq!(gradient.sample(Q32::from_f32(0.9)), green);
+        assert_eq!(gradient.sample(Q32::from_f32(2.0)), green);
+        assert_eq!(gradient.sample(Q32::HALF), Vec3Q32::from_f32(0.5, 0.5, 0.0));
+    }
+
+    #[test]
+    fn test_hard_edge_and_empty() {
+        let gradient = Gradient::from_config(&[stop(0.5, 1.0, 1.0, 1.0), stop(0.5, 0.0, 0.0, 0.0)]);
+        assert_eq!(
+            gradient.sample(Q32::from_f32(0.25)),
+            Vec3Q32::from_f32(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            gradient.sample(Q32::from_f32(0.75)),
+            Vec3Q32::from_f32(0.0, 0.0, 0.0)
+        );
+
+        let empty = Gradient::from_config(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.sample(Q32::HALF), Vec3Q32::from_i32(0, 0, 0));
+    }
+
+    #[test]
+    fn test_to_glsl() {
+        let gradient = Gradient::from_config(&[stop(0.0, 1.0, 0.0, 0.0), stop(1.0, 0.0, 0.0, 1.0)]);
+        assert_eq!(
+            gradient.to_glsl(),
+            "vec3 palette(float t) {
+    float x = clamp(t, 0.0, 1.0);
+    if (x <= 0.0) { return vec3(1.0, 0.0, 0.0); }
+    if (x <= 1.0) { return mix(vec3(1.0, 0.0, 0.0), vec3(0.0, 0.0, 1.0), (x - 0.0) / 1.0); }
+    return vec3(0.0, 0.0, 1.0);
+}
+"
+        );
+    }
+}
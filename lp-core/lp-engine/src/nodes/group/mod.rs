@@ -0,0 +1,3 @@
+pub mod runtime;
+
+pub use runtime::GroupRuntime;
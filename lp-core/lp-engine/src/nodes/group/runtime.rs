@@ -0,0 +1,239 @@
+use crate::error::Error;
+use crate::nodes::{NodeConfig, NodeRuntime};
+use crate::runtime::contexts::{NodeInitContext, RenderContext, TextureHandle};
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+use lp_model::{
+    NodeHandle,
+    nodes::group::{GroupConfig, GroupState},
+};
+use lp_shared::{Texture, fs::fs_event::FsChange};
+
+/// Group node runtime
+///
+/// Owns the group's layer. The project runtime clears the layer and renders the group's
+/// members into it; [`NodeRuntime::render`] then composites the layer onto the target.
+pub struct GroupRuntime {
+    config: Option<GroupConfig>,
+    /// Texture or group the layer is composited onto
+    target: Option<TextureHandle>,
+    /// Intermediate buffer the members render into (sized to the target)
+    layer: Option<Texture>,
+    /// Layer opacity (0.0-1.0), from the config or automation
+    opacity: f32,
+    node_handle: NodeHandle,
+}
+
+impl GroupRuntime {
+    pub fn new(node_handle: NodeHandle) -> Self {
+        Self {
+            config: None,
+            target: None,
+            layer: None,
+            opacity: 1.0,
+            node_handle,
+        }
+    }
+
+    pub fn set_config(&mut self, config: GroupConfig) {
+        self.config = Some(config);
+    }
+
+    /// Get the group config (for state extraction)
+    pub fn get_config(&self) -> Option<&GroupConfig> {
+        self.config.as_ref()
+    }
+
+    /// Get render order from config
+    pub fn render_order(&self) -> i32 {
+        self.config.as_ref().map_or(0, |c| c.render_order)
+    }
+
+    /// Check if this group composites onto the given texture (or group)
+    pub fn targets_texture(&self, texture_handle: TextureHandle) -> bool {
+        self.target == Some(texture_handle)
+    }
+
+    /// Get the resolved target handle
+    pub fn target(&self) -> Option<TextureHandle> {
+        self.target
+    }
+
+    /// Get the layer, if it has been prepared
+    pub fn layer(&self) -> Option<&Texture> {
+        self.layer.as_ref()
+    }
+
+    pub fn layer_mut(&mut self) -> Option<&mut Texture> {
+        self.layer.as_mut()
+    }
+
+    /// Clear the layer to transparent, (re)creating it if the target size changed
+    pub fn prepare_layer(&mut self, width: u32, height: u32) -> Result<(), Error> {
+        if let Some(layer) = &mut self.layer
+            && layer.width() == width
+            && layer.height() == height
+        {
+            layer.data_mut().fill(0);
+            return Ok(());
+        }
+
+        let layer =
+            Texture::new(width, height, "RGBA8".to_string()).map_err(|e| Error::InvalidConfig {
+                node_path: format!("group-{}", self.node_handle.as_i32()),
+                reason: format!("Failed to create layer: {e}"),
+            })?;
+        self.layer = Some(layer);
+        Ok(())
+    }
+
+    pub fn get_state(&self) -> GroupState {
+        match &self.layer {
+            Some(layer) => GroupState {
+                layer_data: layer.data().to_vec(),
+                width: layer.width(),
+                height: layer.height(),
+            },
+            None => GroupState {
+                layer_data: Vec::new(),
+                width: 0,
+                height: 0,
+            },
+        }
+    }
+
+    /// Resolve the target from config, refusing to composite the group onto itself
+    fn resolve_target(
+        &mut self,
+        config: &GroupConfig,
+        ctx: &dyn NodeInitContext,
+    ) -> Result<(), Error> {
+        let target = ctx.resolve_layer_target(&config.texture_spec)?;
+        if target.as_node_handle() == self.node_handle {
+            return Err(Error::InvalidConfig {
+                node_path: format!("group-{}", self.node_handle.as_i32()),
+                reason: "Group cannot target itself".to_string(),
+            });
+        }
+        self.target = Some(target);
+        Ok(())
+    }
+}
+
+impl NodeRuntime for GroupRuntime {
+    fn init(&mut self, ctx: &dyn NodeInitContext) -> Result<(), Error> {
+        let config = self.config.clone().ok_or_else(|| Error::InvalidConfig {
+            node_path: format!("group-{}", self.node_handle.as_i32()),
+            reason: "Config not set".to_string(),
+        })?;
+
+        self.opacity = config.opacity();
+        self.resolve_target(&config, ctx)
+    }
+
+    fn render(&mut self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        let target = self.target.ok_or_else(|| Error::Other {
+            message: "Group target not resolved".to_string(),
+        })?;
+        let Some(layer) = &self.layer else {
+            // Nothing has been rendered into the group yet
+            return Ok(());
+        };
+        let mode = self
+            .config
+            .as_ref()
+            .map(GroupConfig::blend_mode)
+            .unwrap_or_default();
+        let opacity = (self.opacity * 255.0 + 0.5) as u8;
+
+        ctx.get_texture_mut(target)?
+            .composite(layer, opacity, mode)
+            .map_err(|e| Error::Other {
+                message: format!("Failed to composite group layer: {e}"),
+            })
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn core::any::Any {
+        self
+    }
+
+    fn update_config(
+        &mut self,
+        new_config: Box<dyn NodeConfig>,
+        ctx: &dyn NodeInitContext,
+    ) -> Result<(), Error> {
+        let group_config = new_config
+            .as_any()
+            .downcast_ref::<GroupConfig>()
+            .ok_or_else(|| Error::InvalidConfig {
+                node_path: format!("group-{}", self.node_handle.as_i32()),
+                reason: "Config is not a GroupConfig".to_string(),
+            })?;
+
+        let target_changed = self
+            .config
+            .as_ref()
+            .is_none_or(|old| old.texture_spec != group_config.texture_spec);
+
+        self.config = Some(group_config.clone());
+        self.opacity = group_config.opacity();
+
+        if target_changed {
+            self.resolve_target(group_config, ctx)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_fs_change(
+        &mut self,
+        _change: &FsChange,
+        _ctx: &dyn NodeInitContext,
+    ) -> Result<(), Error> {
+        // Groups have no files besides node.json
+        Ok(())
+    }
+
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), Error> {
+        match name {
+            "opacity" => {
+                self.opacity = value.clamp(0.0, 1.0);
+                Ok(())
+            }
+            _ => Err(Error::Other {
+                message: format!("Group has no automatable parameter `{name}`"),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_runtime_creation() {
+        let runtime = GroupRuntime::new(NodeHandle::new(0));
+        let _boxed: alloc::boxed::Box<dyn NodeRuntime> = alloc::boxed::Box::new(runtime);
+    }
+
+    #[test]
+    fn test_prepare_layer_clears_and_resizes() {
+        let mut runtime = GroupRuntime::new(NodeHandle::new(0));
+        runtime.prepare_layer(2, 2).unwrap();
+        runtime
+            .layer_mut()
+            .unwrap()
+            .set_pixel(0, 0, [255, 0, 0, 255]);
+
+        runtime.prepare_layer(2, 2).unwrap();
+        assert_eq!(runtime.layer().unwrap().get_pixel(0, 0), Some([0, 0, 0, 0]));
+
+        runtime.prepare_layer(4, 1).unwrap();
+        let layer = runtime.layer().unwrap();
+        assert_eq!((layer.width(), layer.height()), (4, 1));
+    }
+}
@@ -1,14 +1,18 @@
 use crate::error::Error;
 use crate::runtime::contexts::{NodeInitContext, RenderContext};
-use alloc::boxed::Box;
+use alloc::{boxed::Box, format};
 use lp_shared::fs::fs_event::FsChange;
 
+pub mod color;
 pub mod fixture;
+pub mod gradient;
+pub mod group;
 pub mod output;
 pub mod shader;
 pub mod texture;
 
 pub use fixture::FixtureRuntime;
+pub use group::GroupRuntime;
 pub use output::OutputRuntime;
 pub use shader::ShaderRuntime;
 pub use texture::TextureRuntime;
@@ -25,9 +29,11 @@ pub trait NodeRuntime: Send + Sync {
 
     /// Destroy the node (cleanup)
     ///
-    /// Called when a node is being removed or the runtime is shutting down.
+    /// Called when a node is being removed or the runtime is shutting down. Nodes
+    /// must release what they hold outside themselves here (e.g. output channels),
+    /// as the runtime may be loaded again with the same provider.
     /// Default implementation does nothing - nodes can override if they need cleanup.
-    fn destroy(&mut self) -> Result<(), Error> {
+    fn destroy(&mut self, _ctx: &dyn NodeInitContext) -> Result<(), Error> {
         Ok(())
     }
 
@@ -54,6 +60,15 @@ pub trait NodeRuntime: Send + Sync {
         change: &FsChange,
         ctx: &dyn NodeInitContext,
     ) -> Result<(), Error>;
+
+    /// Set a numeric parameter from a project automation track
+    ///
+    /// Default implementation has no automatable parameters.
+    fn set_param(&mut self, name: &str, _value: f32) -> Result<(), Error> {
+        Err(Error::Other {
+            message: format!("Node has no automatable parameter `{name}`"),
+        })
+    }
 }
 
 // Re-export NodeConfig from lp-model
@@ -0,0 +1,110 @@
+//! Dithering for quantizing fixed-point colors to 8-bit LED values
+//!
+//! Plain truncation maps a smooth gradient onto a few 8-bit codes, which shows as
+//! banding on LEDs. Ordered dithering compares the fractional part of each value
+//! against a per-LED threshold so neighbouring LEDs (or successive frames) round
+//! in different directions, and the average output tracks the input.
+
+use lp_glsl_builtins::glsl::q32::types::q32::Q32;
+use lp_model::nodes::output::DitherMode;
+
+/// 4x4 Bayer matrix, flattened row by row (values 0..16)
+const BAYER4: [u8; 16] = [0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5];
+
+/// Stride between neighbouring LEDs in temporal mode, coprime with 16 so each
+/// LED cycles through every threshold
+const TEMPORAL_LED_STRIDE: u32 = 7;
+
+/// Quantize a channel value (0-255 scale) to 8 bits using the given dither mode
+///
+/// `index` is the LED index along the output and `frame` a counter that advances
+/// once per rendered frame; `frame` only affects [`DitherMode::Temporal`].
+pub fn dither_to_u8(value: Q32, mode: DitherMode, index: u32, frame: u32) -> u8 {
+    let cell = match mode {
+        DitherMode::None => return value.to_u8_clamped(),
+        DitherMode::Bayer => index % 16,
+        DitherMode::Temporal => frame.wrapping_add(index.wrapping_mul(TEMPORAL_LED_STRIDE)) % 16,
+    };
+
+    // Threshold at the center of the cell: (n + 0.5) / 16
+    let threshold = (2 * BAYER4[cell as usize] as i32 + 1) << (Q32::SHIFT - 5);
+    let base = value.to_i32();
+    let rounded = if value.frac().to_fixed() > threshold {
+        base + 1
+    } else {
+        base
+    };
+    rounded.clamp(0, 255) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn ramp_value(i: u32) -> Q32 {
+        // 100.0 .. 101.0 across 16 LEDs
+        Q32::from_i32(100) + Q32::from_fixed((i as i32) << (Q32::SHIFT - 4))
+    }
+
+    fn distinct_adjacent(codes: &[u8]) -> usize {
+        codes.windows(2).filter(|w| w[0] != w[1]).count()
+    }
+
+    #[test]
+    fn test_no_dither_truncates() {
+        let codes: Vec<u8> = (0..16)
+            .map(|i| dither_to_u8(ramp_value(i), DitherMode::None, i, 0))
+            .collect();
+        assert!(codes.iter().all(|&c| c == 100));
+    }
+
+    #[test]
+    fn test_bayer_distributes_quantization() {
+        let plain: Vec<u8> = (0..16)
+            .map(|i| dither_to_u8(ramp_value(i), DitherMode::None, i, 0))
+            .collect();
+        let dithered: Vec<u8> = (0..16)
+            .map(|i| dither_to_u8(ramp_value(i), DitherMode::Bayer, i, 0))
+            .collect();
+
+        assert_eq!(distinct_adjacent(&plain), 0);
+        assert!(distinct_adjacent(&dithered) > 2, "{dithered:?}");
+        assert!(dithered.iter().all(|&c| c == 100 || c == 101));
+    }
+
+    #[test]
+    fn test_bayer_average_tracks_input() {
+        // A constant 10.25 should round up on a quarter of the LEDs
+        let value = Q32::from_f32(10.25);
+        let codes: Vec<u8> = (0..16)
+            .map(|i| dither_to_u8(value, DitherMode::Bayer, i, 0))
+            .collect();
+        let ups = codes.iter().filter(|&&c| c == 11).count();
+        assert_eq!(ups, 4);
+        assert_eq!(
+            codes.len() - ups,
+            codes.iter().filter(|&&c| c == 10).count()
+        );
+    }
+
+    #[test]
+    fn test_temporal_varies_over_frames() {
+        let value = Q32::from_f32(10.5);
+        let codes: Vec<u8> = (0..16)
+            .map(|frame| dither_to_u8(value, DitherMode::Temporal, 3, frame))
+            .collect();
+        assert_eq!(codes.iter().filter(|&&c| c == 11).count(), 8);
+        assert!(distinct_adjacent(&codes) > 0);
+    }
+
+    #[test]
+    fn test_dither_clamps() {
+        let high = Q32::from_f32(255.9);
+        let low = Q32::from_f32(-0.5);
+        for i in 0..16 {
+            assert_eq!(dither_to_u8(high, DitherMode::Bayer, i, 0), 255);
+            assert_eq!(dither_to_u8(low, DitherMode::Bayer, i, 0), 0);
+        }
+    }
+}
@@ -1,3 +1,6 @@
+pub mod dither;
+pub mod power;
 pub mod runtime;
+pub mod slew;
 
-pub use runtime::OutputRuntime;
+pub use runtime::{OutputRuntime, channel_params};
@@ -0,0 +1,113 @@
+//! Power limiting for output frames
+//!
+//! Estimates the current a frame draws from its channel values using a linear
+//! per-channel model, and scales the whole frame down proportionally when it
+//! exceeds the configured budget. Scaling every channel by the same factor
+//! keeps relative colors intact.
+
+use lp_model::nodes::output::PowerLimitConfig;
+
+/// Estimated current for one output frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PowerEstimate {
+    /// Current the frame would draw unscaled, in milliamps
+    pub estimated_ma: u32,
+    /// Current after the limit was applied, in milliamps
+    pub capped_ma: u32,
+}
+
+impl PowerEstimate {
+    /// Whether the frame was scaled down to fit the budget
+    pub fn is_limited(&self) -> bool {
+        self.capped_ma < self.estimated_ma
+    }
+}
+
+/// Estimate the current drawn by a frame, in milliamps
+///
+/// Each channel draws `channel_ma` at 255, scaling linearly with its value.
+pub fn estimate_ma(data: &[u8], channel_ma: u32) -> u32 {
+    let sum: u64 = data.iter().map(|&v| v as u64).sum();
+    (sum * channel_ma as u64 / 255).min(u32::MAX as u64) as u32
+}
+
+/// Scale a frame in place so its estimated current fits within the limit
+pub fn apply_power_limit(data: &mut [u8], limit: &PowerLimitConfig) -> PowerEstimate {
+    let estimated_ma = estimate_ma(data, limit.channel_ma);
+    if estimated_ma <= limit.max_ma {
+        return PowerEstimate {
+            estimated_ma,
+            capped_ma: estimated_ma,
+        };
+    }
+
+    // Scale factor in 16.16 fixed point, rounded down so the result stays within budget
+    let scale = ((limit.max_ma as u64) << 16) / estimated_ma as u64;
+    for value in data.iter_mut() {
+        *value = ((*value as u64 * scale) >> 16) as u8;
+    }
+
+    PowerEstimate {
+        estimated_ma,
+        capped_ma: estimate_ma(data, limit.channel_ma),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn limit(max_ma: u32) -> PowerLimitConfig {
+        PowerLimitConfig {
+            max_ma,
+            channel_ma: 20,
+        }
+    }
+
+    #[test]
+    fn test_estimate_full_white() {
+        // 10 LEDs at full white: 30 channels * 20mA
+        let data = [255u8; 30];
+        assert_eq!(estimate_ma(&data, 20), 600);
+    }
+
+    #[test]
+    fn test_under_budget_unchanged() {
+        let mut data = [255u8, 128, 0, 10, 20, 30];
+        let original = data;
+        let estimate = apply_power_limit(&mut data, &limit(1000));
+        assert_eq!(data, original);
+        assert_eq!(estimate.estimated_ma, estimate.capped_ma);
+        assert!(!estimate.is_limited());
+    }
+
+    #[test]
+    fn test_over_budget_scaled_to_limit() {
+        // 10 LEDs of (255, 128, 0), ~300mA unscaled
+        let mut data: Vec<u8> = (0..10).flat_map(|_| [255u8, 128, 0]).collect();
+        let estimate = apply_power_limit(&mut data, &limit(150));
+
+        assert_eq!(estimate.estimated_ma, 300);
+        assert!(estimate.is_limited());
+        assert!(estimate.capped_ma <= 150);
+        // Rounding down costs at most one step per channel
+        assert!(estimate.capped_ma >= 150 - 2, "{estimate:?}");
+        assert_eq!(estimate.capped_ma, estimate_ma(&data, 20));
+
+        // Relative colors are preserved: red stays ~2x green, blue stays off
+        for led in data.chunks_exact(3) {
+            assert_eq!(led, &data[0..3]);
+            assert!((led[0] as i32 - 2 * led[1] as i32).abs() <= 2, "{led:?}");
+            assert_eq!(led[2], 0);
+        }
+    }
+
+    #[test]
+    fn test_zero_budget_turns_off() {
+        let mut data = [255u8; 6];
+        let estimate = apply_power_limit(&mut data, &limit(0));
+        assert_eq!(data, [0; 6]);
+        assert_eq!(estimate.capped_ma, 0);
+    }
+}
@@ -1,32 +1,54 @@
 use crate::error::Error;
+use crate::nodes::output::power::{PowerEstimate, apply_power_limit};
+use crate::nodes::output::slew::apply_slew_limit;
 use crate::nodes::{NodeConfig, NodeRuntime};
-use crate::output::{OutputChannelHandle, OutputFormat};
+use crate::output::{OutputChannelHandle, OutputFormat, OutputProvider};
 use crate::runtime::contexts::{NodeInitContext, RenderContext};
 use alloc::boxed::Box;
+use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use lp_model::nodes::output::OutputConfig;
+use core::net::{Ipv4Addr, SocketAddrV4};
+use lp_model::nodes::output::{ART_NET_DEFAULT_PORT, DitherMode, OutputConfig};
 use lp_shared::fs::fs_event::FsChange;
 
 /// Output node runtime
 pub struct OutputRuntime {
+    /// Path of the output node, for errors (set in init)
+    node_path: String,
     /// Channel data buffer (DMX-style, sequential bytes)
     channel_data: Vec<u8>,
-    /// Output channel handle from provider (None until initialized)
+    /// Output channel handle from provider (None until a fixture with lamps needs one)
     channel_handle: Option<OutputChannelHandle>,
-    /// GPIO pin number
+    /// Byte count the channel was opened with
+    opened_byte_count: u32,
+    /// GPIO pin number (data pin for clock+data strips)
     pin: u32,
+    /// Output format/protocol
+    format: OutputFormat,
     /// Output config (None until set)
     config: Option<OutputConfig>,
+    /// Power- and slew-limited copy of the channel data sent to the provider
+    limited_data: Vec<u8>,
+    /// Channel data sent for the previous frame, where slew limiting starts from
+    previous_data: Vec<u8>,
+    /// Current estimate for the last rendered frame (None if no power limit)
+    power_estimate: Option<PowerEstimate>,
 }
 
 impl OutputRuntime {
     pub fn new() -> Self {
         Self {
+            node_path: String::from("output"),
             channel_data: Vec::new(),
             channel_handle: None,
+            opened_byte_count: 0,
             pin: 0,
+            format: OutputFormat::Ws2811,
             config: None,
+            limited_data: Vec::new(),
+            previous_data: Vec::new(),
+            power_estimate: None,
         }
     }
 
@@ -44,6 +66,16 @@ impl OutputRuntime {
         &mut self.channel_data[start_ch as usize..end]
     }
 
+    /// Offset of `channel` in `universe` within the channel buffer
+    ///
+    /// Only universe-based outputs (Art-Net) offset by universe; see
+    /// [`OutputConfig::channel_start`].
+    pub fn channel_start(&self, universe: u32, channel: u32) -> u32 {
+        self.config
+            .as_ref()
+            .map_or(channel, |config| config.channel_start(universe, channel))
+    }
+
     /// Get channel data (for state extraction)
     pub fn get_channel_data(&self) -> &[u8] {
         &self.channel_data
@@ -53,51 +85,158 @@ impl OutputRuntime {
     pub fn get_config(&self) -> Option<&OutputConfig> {
         self.config.as_ref()
     }
+
+    /// Get the estimated and capped current for the last rendered frame
+    ///
+    /// Returns None if no power limit is configured.
+    pub fn power_estimate(&self) -> Option<PowerEstimate> {
+        self.power_estimate
+    }
+
+    /// (Re)open the provider channel sized for `byte_count` bytes of channel data
+    ///
+    /// Fails with a descriptive error if the provider's channel can't hold that many
+    /// bytes, rather than letting the hardware truncate. A byte count of 0 (no fixture
+    /// drives any lamps) leaves the channel closed, so nothing is sent.
+    fn open_channel(
+        &mut self,
+        provider: &dyn OutputProvider,
+        byte_count: u32,
+    ) -> Result<(), Error> {
+        if let Some(handle) = self.channel_handle.take() {
+            provider.close(handle)?;
+        }
+        self.opened_byte_count = 0;
+
+        if let Some(max) = provider.max_byte_count(self.pin, self.format)
+            && byte_count > max
+        {
+            return Err(Error::InvalidConfig {
+                node_path: self.node_path.clone(),
+                reason: format!(
+                    "Fixtures need {byte_count} channel bytes ({} LEDs) but the output \
+                     channel on pin {} holds at most {max} bytes ({} LEDs)",
+                    byte_count.div_ceil(3),
+                    self.pin,
+                    max / 3
+                ),
+            });
+        }
+
+        self.channel_data.resize(byte_count as usize, 0);
+        if byte_count > 0 {
+            self.channel_handle = Some(provider.open(self.pin, byte_count, self.format)?);
+            self.opened_byte_count = byte_count;
+        }
+        Ok(())
+    }
+
+    /// Get the dither mode used when quantizing colors for this output
+    pub fn dither_mode(&self) -> DitherMode {
+        self.config
+            .as_ref()
+            .map(OutputConfig::dither)
+            .unwrap_or_default()
+    }
+}
+
+/// Get the GPIO pin and output format for a config
+///
+/// `node_path` names the output node in errors.
+pub fn channel_params(
+    config: &OutputConfig,
+    node_path: &str,
+) -> Result<(u32, OutputFormat), Error> {
+    Ok(match config {
+        OutputConfig::GpioStrip { pin, .. } => (*pin, OutputFormat::Ws2811),
+        OutputConfig::Apa102Strip {
+            data_pin,
+            clock_pin,
+            brightness,
+            ..
+        } => (
+            *data_pin,
+            OutputFormat::Apa102 {
+                clock_pin: *clock_pin,
+                brightness: brightness.unwrap_or(lp_shared::output::apa102::MAX_BRIGHTNESS),
+            },
+        ),
+        OutputConfig::ArtNet {
+            address,
+            port,
+            start_universe,
+            ..
+        } => {
+            let ip = address
+                .parse::<Ipv4Addr>()
+                .map_err(|_| Error::InvalidConfig {
+                    node_path: String::from(node_path),
+                    reason: format!("Invalid Art-Net address: {address}"),
+                })?;
+            let target = SocketAddrV4::new(ip, port.unwrap_or(ART_NET_DEFAULT_PORT));
+            (
+                0,
+                OutputFormat::ArtNet {
+                    target,
+                    start_universe: start_universe.unwrap_or(0),
+                },
+            )
+        }
+    })
 }
 
 impl NodeRuntime for OutputRuntime {
     fn init(&mut self, ctx: &dyn NodeInitContext) -> Result<(), Error> {
+        self.node_path = ctx.node_path().as_str().to_string();
+
         // Get config
         let config = self.config.as_ref().ok_or_else(|| Error::InvalidConfig {
-            node_path: String::from("output"),
+            node_path: self.node_path.clone(),
             reason: "Config not set".to_string(),
         })?;
 
-        // Extract pin from config
-        match config {
-            OutputConfig::GpioStrip { pin } => {
-                self.pin = *pin;
-            }
-        }
-
-        // For now, use a default byte_count (will be calculated properly later from fixtures)
-        // Default: 3 bytes for single RGB pixel
-        let byte_count = 3u32;
-        let format = OutputFormat::Ws2811;
-
-        // Open output channel with provider
-        let handle = ctx.output_provider().open(self.pin, byte_count, format)?;
-        self.channel_handle = Some(handle);
+        // Extract pin and format from config
+        (self.pin, self.format) = channel_params(config, &self.node_path)?;
 
-        // Allocate buffer
-        self.channel_data.resize(byte_count as usize, 0);
-
-        Ok(())
+        // Size the channel for the fixtures that write to this output
+        self.open_channel(ctx.output_provider(), ctx.output_byte_count())
     }
 
     fn render(&mut self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        let power_limit = self.config.as_ref().and_then(OutputConfig::power_limit);
+        let slew_limit = self.config.as_ref().and_then(OutputConfig::slew_limit);
+
+        // Fixtures changed since init: resize the channel (validating it again)
+        if self.channel_data.len() != self.opened_byte_count as usize {
+            self.open_channel(ctx.output_provider(), self.channel_data.len() as u32)?;
+        }
+
         // Flush buffer to provider if handle exists
         if let Some(handle) = self.channel_handle {
-            ctx.output_provider().write(handle, &self.channel_data)?;
+            if power_limit.is_some() || slew_limit.is_some() {
+                // Limit a copy so channels fixtures don't rewrite aren't limited repeatedly
+                self.limited_data.clear();
+                self.limited_data.extend_from_slice(&self.channel_data);
+                self.power_estimate =
+                    power_limit.map(|limit| apply_power_limit(&mut self.limited_data, &limit));
+                if let Some(max_step) = slew_limit {
+                    apply_slew_limit(&mut self.limited_data, &mut self.previous_data, max_step);
+                }
+                ctx.output_provider().write(handle, &self.limited_data)?;
+            } else {
+                self.power_estimate = None;
+                ctx.output_provider().write(handle, &self.channel_data)?;
+            }
         }
         Ok(())
     }
 
-    fn destroy(&mut self) -> Result<(), Error> {
-        // Note: Provider cleanup should happen at a higher level
-        // since destroy() doesn't have access to the provider context
-        // For now, just clear the handle
-        self.channel_handle = None;
+    fn destroy(&mut self, ctx: &dyn NodeInitContext) -> Result<(), Error> {
+        // Close the channel so the pin can be opened again
+        if let Some(handle) = self.channel_handle.take() {
+            ctx.output_provider().close(handle)?;
+        }
+        self.opened_byte_count = 0;
         Ok(())
     }
 
@@ -119,37 +258,27 @@ impl NodeRuntime for OutputRuntime {
             .as_any()
             .downcast_ref::<OutputConfig>()
             .ok_or_else(|| Error::InvalidConfig {
-                node_path: String::from("output"),
+                node_path: self.node_path.clone(),
                 reason: "Config is not an OutputConfig".to_string(),
             })?;
 
-        // Check if pin changed
-        let old_pin = self.pin;
-        match output_config {
-            OutputConfig::GpioStrip { pin } => {
-                if *pin != old_pin {
-                    // Pin changed - need to reinitialize
-                    // Close old channel if exists
-                    if self.channel_handle.is_some() {
-                        // Note: We don't have access to provider here to close
-                        // The old channel will be cleaned up when provider is destroyed
-                        self.channel_handle = None;
-                    }
-
-                    self.pin = *pin;
-                    self.config = Some(output_config.clone());
-
-                    // Reinitialize with new pin
-                    let byte_count = 3u32; // Default for now
-                    let format = OutputFormat::Ws2811;
-                    let handle = ctx.output_provider().open(self.pin, byte_count, format)?;
-                    self.channel_handle = Some(handle);
-                    self.channel_data.resize(byte_count as usize, 0);
-                } else {
-                    // Just update config
-                    self.config = Some(output_config.clone());
-                }
+        // Check if pin or format changed
+        let (pin, format) = channel_params(output_config, &self.node_path)?;
+        if pin != self.pin || format != self.format {
+            // Pin or format changed - close the old channel and reopen on the new one
+            if let Some(handle) = self.channel_handle.take() {
+                ctx.output_provider().close(handle)?;
             }
+
+            self.pin = pin;
+            self.format = format;
+            self.config = Some(output_config.clone());
+
+            let byte_count = ctx.output_byte_count().max(self.channel_data.len() as u32);
+            self.open_channel(ctx.output_provider(), byte_count)?;
+        } else {
+            // Just update config
+            self.config = Some(output_config.clone());
         }
 
         Ok(())
@@ -0,0 +1,66 @@
+//! Slew-rate limiting for output frames
+//!
+//! Fast color changes flicker on some LEDs. With a slew limit, each channel value
+//! moves toward the rendered value by at most a fixed step per frame, so a jump is
+//! spread over several frames. The output remembers the values it last sent, which
+//! are where the next frame starts from.
+
+use alloc::vec::Vec;
+
+/// Limit how far each value in `data` moves from the value last sent
+///
+/// `previous` holds the last frame sent and is updated to the limited frame. Channels
+/// it has no value for (the first frame, or after the output grew) start from 0, as
+/// the LEDs start dark.
+pub fn apply_slew_limit(data: &mut [u8], previous: &mut Vec<u8>, max_step: u8) {
+    previous.resize(data.len(), 0);
+    for (value, last) in data.iter_mut().zip(previous.iter_mut()) {
+        *value = if *value > *last {
+            last.saturating_add(max_step).min(*value)
+        } else {
+            last.saturating_sub(max_step).max(*value)
+        };
+        *last = *value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_step_ramps_over_frames() {
+        let mut previous = Vec::new();
+        let mut sent = Vec::new();
+        for _ in 0..6 {
+            let mut data = [255u8];
+            apply_slew_limit(&mut data, &mut previous, 64);
+            sent.push(data[0]);
+        }
+        assert_eq!(sent, vec![64, 128, 192, 255, 255, 255]);
+
+        // And back down
+        let mut data = [0u8];
+        apply_slew_limit(&mut data, &mut previous, 64);
+        assert_eq!(data, [191]);
+    }
+
+    #[test]
+    fn test_small_changes_pass_through() {
+        let mut previous = vec![100u8, 100, 100];
+        let mut data = [104u8, 100, 90];
+        apply_slew_limit(&mut data, &mut previous, 10);
+        assert_eq!(data, [104, 100, 90]);
+        assert_eq!(previous, vec![104, 100, 90]);
+    }
+
+    #[test]
+    fn test_zero_step_holds_values() {
+        let mut previous = vec![7u8];
+        let mut data = [200u8, 50];
+        apply_slew_limit(&mut data, &mut previous, 0);
+        // The new channel starts dark
+        assert_eq!(data, [7, 0]);
+    }
+}
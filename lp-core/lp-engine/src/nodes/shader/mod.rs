@@ -1,3 +1,4 @@
+mod pixels;
 pub mod runtime;
 
 pub use runtime::ShaderRuntime;
@@ -0,0 +1,166 @@
+//! Pixel loops for the host render path
+//!
+//! Shader results come back as Q32 fixed-point vec4s (16.16). Rather than converting
+//! and writing each pixel as it is produced, [`render_batched`] gathers a row of
+//! results into a buffer reused across rows, then converts and copies the row into the
+//! texture in one pass over contiguous memory.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+use lp_glsl_compiler::DISCARD_ALPHA;
+use lp_shared::Texture;
+
+/// Q32 fixed-point scale factor (2^16 = 65536)
+pub(crate) const Q32_SCALE: i32 = 65536;
+
+/// Alpha returned by `discard`, in Q32 format
+const DISCARD_ALPHA_Q32: i32 = DISCARD_ALPHA as i32 * Q32_SCALE;
+
+/// Convert a Q32 vec4 color to RGBA8, or `None` if the shader discarded the pixel
+///
+/// Channels are clamped to [0, 1] and scaled with integer math: `(q32 * 255) / 65536`.
+pub(crate) fn q32_to_rgba(color: [i32; 4]) -> Option<[u8; 4]> {
+    if color[3] == DISCARD_ALPHA_Q32 {
+        return None;
+    }
+    let to_u8 = |v: i32| ((v.clamp(0, Q32_SCALE) as i64 * 255) / Q32_SCALE as i64) as u8;
+    Some([
+        to_u8(color[0]),
+        to_u8(color[1]),
+        to_u8(color[2]),
+        to_u8(color[3]),
+    ])
+}
+
+/// Run `shade` over the region a row at a time and write the results to `texture`
+///
+/// `shade` gets the Q32 fragment coordinate and returns the Q32 color. The region must
+/// lie within the texture (see `ShaderRuntime::render_region`). Discarded pixels are
+/// left untouched, and only the channels the texture format stores are written, the
+/// same as `Texture::set_pixel`.
+pub(crate) fn render_batched<E>(
+    x_range: Range<u32>,
+    y_range: Range<u32>,
+    texture: &mut Texture,
+    mut shade: impl FnMut([i32; 2]) -> Result<[i32; 4], E>,
+) -> Result<(), E> {
+    let width = texture.width() as usize;
+    let bytes_per_pixel = texture.bytes_per_pixel();
+    let mut row: Vec<[i32; 4]> = Vec::with_capacity(x_range.len());
+
+    for y in y_range {
+        let y_q32 = (y as i32) * Q32_SCALE;
+        row.clear();
+        for x in x_range.clone() {
+            row.push(shade([(x as i32) * Q32_SCALE, y_q32])?);
+        }
+
+        // Formats without RGBA channels are never written, as with `set_pixel`
+        if bytes_per_pixel == 0 || bytes_per_pixel > 4 {
+            continue;
+        }
+        let start = (y as usize * width + x_range.start as usize) * bytes_per_pixel;
+        let end = start + row.len() * bytes_per_pixel;
+        let Some(pixels) = texture.data_mut().get_mut(start..end) else {
+            continue;
+        };
+        for (pixel, color) in pixels.chunks_exact_mut(bytes_per_pixel).zip(&row) {
+            if let Some(rgba) = q32_to_rgba(*color) {
+                pixel.copy_from_slice(&rgba[..bytes_per_pixel]);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use lp_shared::util::formats;
+
+    /// Reference per-pixel loop: shade, convert and `set_pixel` one pixel at a time
+    fn render_scalar(
+        x_range: Range<u32>,
+        y_range: Range<u32>,
+        texture: &mut Texture,
+        mut shade: impl FnMut([i32; 2]) -> [i32; 4],
+    ) {
+        for y in y_range {
+            for x in x_range.clone() {
+                let color = shade([(x as i32) * Q32_SCALE, (y as i32) * Q32_SCALE]);
+                if let Some(rgba) = q32_to_rgba(color) {
+                    texture.set_pixel(x, y, rgba);
+                }
+            }
+        }
+    }
+
+    /// Gradient with out-of-range channels and a discarded checkerboard
+    fn shade([x, y]: [i32; 2]) -> [i32; 4] {
+        let (px, py) = (x / Q32_SCALE, y / Q32_SCALE);
+        if (px / 8 + py / 8) % 2 == 1 {
+            return [0, 0, 0, DISCARD_ALPHA_Q32];
+        }
+        [
+            x / 64,
+            y / 48,
+            Q32_SCALE - x / 32 + y / 16,
+            Q32_SCALE * 2 - x,
+        ]
+    }
+
+    fn texture(format: &str) -> Texture {
+        let mut texture = Texture::new(96, 64, format.to_string()).unwrap();
+        // Non-zero background so skipped (discarded) pixels are distinguishable
+        texture.data_mut().fill(7);
+        texture
+    }
+
+    #[test]
+    fn test_batched_matches_scalar() {
+        for format in [formats::RGBA8, formats::RGB8, formats::R8] {
+            for (x_range, y_range) in [(0..96, 0..64), (13..70, 5..41)] {
+                let mut scalar = texture(format);
+                render_scalar(x_range.clone(), y_range.clone(), &mut scalar, shade);
+
+                let mut batched = texture(format);
+                render_batched::<()>(x_range.clone(), y_range.clone(), &mut batched, |frag| {
+                    Ok(shade(frag))
+                })
+                .unwrap();
+
+                assert_eq!(
+                    scalar.data(),
+                    batched.data(),
+                    "{format} {x_range:?}x{y_range:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_batched_stops_on_error() {
+        let mut texture = texture(formats::RGBA8);
+        let mut calls = 0;
+        let result = render_batched(0..4, 0..4, &mut texture, |_| {
+            calls += 1;
+            if calls == 6 { Err("boom") } else { Ok([0; 4]) }
+        });
+        assert_eq!(result, Err("boom"));
+        // The first row was written, the failing row was not
+        assert_eq!(&texture.data()[..16], &[0; 16]);
+        assert!(texture.data()[16..].iter().all(|&b| b == 7));
+    }
+
+    #[test]
+    fn test_q32_to_rgba() {
+        assert_eq!(
+            q32_to_rgba([0, Q32_SCALE, -5, Q32_SCALE * 3]),
+            Some([0, 255, 0, 255])
+        );
+        assert_eq!(q32_to_rgba([Q32_SCALE / 2; 4]), Some([127; 4]));
+        assert_eq!(q32_to_rgba([0, 0, 0, DISCARD_ALPHA_Q32]), None);
+    }
+}
@@ -1,4 +1,7 @@
+use super::pixels::{self, Q32_SCALE};
 use crate::error::Error;
+use crate::nodes::color::color_config_to_rgb8;
+use crate::nodes::gradient::Gradient;
 use crate::nodes::{NodeConfig, NodeRuntime};
 use crate::runtime::contexts::{NodeInitContext, RenderContext, TextureHandle};
 use alloc::{
@@ -6,16 +9,28 @@ use alloc::{
     format,
     string::{String, ToString},
 };
+use core::ops::Range;
 use log;
-use lp_glsl_compiler::glsl_jit;
-use lp_glsl_compiler::{DecimalFormat, GlslExecutable, GlslOptions, RunMode};
+use lp_glsl_compiler::{
+    DISCARD_ALPHA, DecimalFormat, FRAME_UNIFORM_NAME, GlslError, GlslExecutable, GlslOptions,
+    GlslValue, OptLevel, RunMode, TIME_UNIFORM_NAME,
+};
+use lp_glsl_compiler::{glsl_interpret, glsl_jit};
 use lp_glsl_jit_util::call_structreturn_with_args;
 use lp_model::{
     LpPathBuf, NodeHandle,
-    nodes::shader::{ShaderConfig, ShaderState},
+    nodes::GradientStopConfig,
+    nodes::shader::{ShaderBackend, ShaderConfig, ShaderState},
 };
 use lp_shared::fs::fs_event::FsChange;
 
+/// Loop iterations a shader may run per frame when its config doesn't set a limit
+const DEFAULT_LOOP_FUEL: u32 = 1_000_000;
+
+/// Instructions an emulated shader may run per pixel before it is stopped
+#[cfg(feature = "emulator")]
+const EMULATOR_MAX_INSTRUCTIONS: u64 = 100_000;
+
 /// Wrapper for function pointer that implements Send + Sync
 /// Function pointers are safe to share between threads (they're just addresses)
 /// The compiled code is immutable and stable after compilation
@@ -33,11 +48,19 @@ pub struct ShaderRuntime {
     texture_handle: Option<TextureHandle>,                     // Resolved texture handle
     compilation_error: Option<String>,                         // Compilation error if any
     node_handle: NodeHandle,
+    /// Path of this node, for errors (set in `init`)
+    node_path: String,
     render_order: i32, // Render order (from config)
     // Direct call optimization: cached function pointer and calling convention
     direct_func_ptr: Option<FunctionPtr>,
     direct_call_conv: Option<cranelift_codegen::isa::CallConv>,
     direct_pointer_type: Option<cranelift_codegen::ir::Type>,
+    /// Shader reads time or frame, so its output changes every frame
+    animated: bool,
+    /// Source, config or uniforms changed since the last successful render
+    dirty: bool,
+    /// The texture holds pixels from this shader's last render (cleared by [`Self::blank`])
+    drawn: bool,
 }
 
 impl ShaderRuntime {
@@ -49,16 +72,21 @@ impl ShaderRuntime {
             texture_handle: None,
             compilation_error: None,
             node_handle,
+            node_path: String::from("shader"),
             render_order: 0,
             direct_func_ptr: None,
             direct_call_conv: None,
             direct_pointer_type: None,
+            animated: true,
+            dirty: true,
+            drawn: false,
         }
     }
 
     pub fn set_config(&mut self, config: ShaderConfig) {
         self.render_order = config.render_order;
         self.config = Some(config);
+        self.dirty = true;
     }
 
     pub fn render_order(&self) -> i32 {
@@ -96,12 +124,126 @@ impl ShaderRuntime {
     pub fn compilation_error(&self) -> Option<&str> {
         self.compilation_error.as_deref()
     }
+
+    /// Whether rendering again could produce different pixels than the last render
+    ///
+    /// False for a static shader (one that doesn't read time or frame) whose source,
+    /// config and uniforms are unchanged since it last rendered.
+    pub fn needs_render(&self) -> bool {
+        self.animated || self.dirty
+    }
+
+    /// Whether the texture still holds pixels this shader drew
+    pub fn has_drawn(&self) -> bool {
+        self.drawn
+    }
+
+    /// Blank the pixels this shader drew, for when it is disabled
+    ///
+    /// Does nothing if the shader hasn't drawn since it was last blanked, so a disabled
+    /// shader clears its region once rather than every frame.
+    pub fn blank(&mut self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        if !self.drawn {
+            return Ok(());
+        }
+        let texture_handle = self.texture_handle.ok_or_else(|| Error::Other {
+            message: String::from("Texture handle not resolved"),
+        })?;
+
+        let texture = ctx.get_texture_mut(texture_handle)?;
+        let (x_range, y_range) =
+            Self::render_region(self.config.as_ref(), texture.width(), texture.height());
+        for y in y_range {
+            for x in x_range.clone() {
+                texture.set_pixel(x, y, [0, 0, 0, 255]);
+            }
+        }
+
+        self.drawn = false;
+        // The texture no longer shows the last render
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Whether the shader has an error color to show while it is in an error state
+    pub fn has_error_color(&self) -> bool {
+        self.config
+            .as_ref()
+            .is_some_and(|config| config.error_color.is_some())
+    }
+
+    /// Fill the shader's region with its error color, for when it is in an error state
+    ///
+    /// Does nothing if no error color is configured. The shader stays dirty, so it
+    /// draws over the error color as soon as it renders successfully again.
+    pub fn draw_error_color(&mut self, ctx: &mut dyn RenderContext) -> Result<(), Error> {
+        let Some(color) = self.config.as_ref().and_then(|c| c.error_color) else {
+            return Ok(());
+        };
+        let texture_handle = self.texture_handle.ok_or_else(|| Error::Other {
+            message: String::from("Texture handle not resolved"),
+        })?;
+
+        let [r, g, b] = color_config_to_rgb8(&color);
+        let texture = ctx.get_texture_mut(texture_handle)?;
+        let (x_range, y_range) =
+            Self::render_region(self.config.as_ref(), texture.width(), texture.height());
+        for y in y_range {
+            for x in x_range.clone() {
+                texture.set_pixel(x, y, [r, g, b, 255]);
+            }
+        }
+
+        self.drawn = true;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Loop iterations the shader may run per frame before it is aborted
+    pub fn loop_fuel(&self) -> u32 {
+        self.config
+            .as_ref()
+            .and_then(|c| c.loop_fuel)
+            .unwrap_or(DEFAULT_LOOP_FUEL)
+    }
+
+    /// Set the value of a `uniform float` declared by the shader
+    ///
+    /// Takes effect on the next render without recompiling.
+    pub fn set_uniform(&mut self, name: &str, value: f32) -> Result<(), Error> {
+        let executable = self.executable.as_mut().ok_or_else(|| Error::Other {
+            message: String::from("Shader not compiled"),
+        })?;
+        executable
+            .set_uniform(name, GlslValue::F32(value))
+            .map_err(|e| Error::InvalidConfig {
+                node_path: self.node_path.clone(),
+                reason: format!("Failed to set uniform `{name}`: {e}"),
+            })?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Push the uniform values from the current config into the compiled shader
+    fn apply_config_uniforms(&mut self) {
+        let uniforms = match &self.config {
+            Some(config) => config.uniforms.clone(),
+            None => return,
+        };
+        for uniform in uniforms {
+            // A config value the shader doesn't declare shouldn't stop rendering
+            if let Err(e) = self.set_uniform(&uniform.name, uniform.value) {
+                log::warn!("ShaderRuntime: shader {}: {}", self.node_handle.as_i32(), e);
+            }
+        }
+    }
 }
 
 impl NodeRuntime for ShaderRuntime {
     fn init(&mut self, ctx: &dyn NodeInitContext) -> Result<(), Error> {
+        self.node_path = ctx.node_path().as_str().to_string();
         let config = self.config.clone().ok_or_else(|| Error::InvalidConfig {
-            node_path: format!("shader-{}", self.node_handle.as_i32()),
+            node_path: self.node_path.clone(),
             reason: alloc::string::String::from("Config not set"),
         })?;
 
@@ -120,6 +262,7 @@ impl NodeRuntime for ShaderRuntime {
         let texture_handle = self.texture_handle.ok_or_else(|| Error::Other {
             message: String::from("Texture handle not resolved"),
         })?;
+        let loop_fuel = self.loop_fuel();
 
         let executable = self.executable.as_mut().ok_or_else(|| Error::Other {
             message: String::from("Shader not compiled"),
@@ -127,13 +270,30 @@ impl NodeRuntime for ShaderRuntime {
 
         // Get time before mutable borrow
         let time = ctx.get_time();
+        let frame = ctx.get_frame_id().as_i64() as i32;
+
+        // Update the implicit uniforms. A shader that declares them itself with another
+        // type keeps its own values, so a type mismatch is not an error here.
+        let _ = executable.set_uniform(TIME_UNIFORM_NAME, GlslValue::F32(time));
+        let _ = executable.set_uniform(FRAME_UNIFORM_NAME, GlslValue::I32(frame));
+
+        // Loops share one budget across every pixel of the frame. Emulated shaders are
+        // limited by instruction count instead.
+        if executable.fuel_remaining().is_some() {
+            executable.set_fuel(loop_fuel).map_err(|e| Error::Other {
+                message: format!("Failed to set shader loop fuel: {e}"),
+            })?;
+        }
 
         // Get mutable texture access
         let texture = ctx.get_texture_mut(texture_handle)?;
+        self.drawn = true;
 
         let width = texture.width();
         let height = texture.height();
         let output_size = [width as f32, height as f32];
+        let (x_range, y_range) = Self::render_region(self.config.as_ref(), width, height);
+        let region = (x_range.clone(), y_range.clone());
 
         // Execute shader for each pixel
         // Use direct function pointer call if available (faster), otherwise fall back to trait method
@@ -149,13 +309,15 @@ impl NodeRuntime for ShaderRuntime {
                 pointer_type,
                 width,
                 height,
+                x_range,
+                y_range,
                 time,
                 texture,
             )?;
         } else {
             // Fallback to trait method (slower but always works)
-            for y in 0..height {
-                for x in 0..width {
+            for y in y_range {
+                for x in x_range.clone() {
                     let frag_coord = [x as f32, y as f32];
 
                     // Call shader main function
@@ -164,14 +326,17 @@ impl NodeRuntime for ShaderRuntime {
                         .call_vec(
                             "main",
                             &[
-                                lp_glsl_compiler::GlslValue::Vec2(frag_coord),
-                                lp_glsl_compiler::GlslValue::Vec2(output_size),
-                                lp_glsl_compiler::GlslValue::F32(time),
+                                GlslValue::Vec2(frag_coord),
+                                GlslValue::Vec2(output_size),
+                                GlslValue::F32(time),
                             ],
                             4,
                         )
                         .map_err(|e| Error::Other {
-                            message: format!("Shader execution failed: {e}"),
+                            message: format!(
+                                "Shader execution failed: {}",
+                                describe_execution_error(&e)
+                            ),
                         })?;
 
                     // Extract RGBA from vec4 result
@@ -185,6 +350,11 @@ impl NodeRuntime for ShaderRuntime {
                         });
                     }
 
+                    // `discard` leaves the pixel untouched
+                    if result[3] == DISCARD_ALPHA {
+                        continue;
+                    }
+
                     // Convert from [0, 1] to [0, 255] and clamp
                     let rgba = [
                         (result[0].clamp(0.0, 1.0) * 255.0) as u8,
@@ -199,6 +369,22 @@ impl NodeRuntime for ShaderRuntime {
             }
         }
 
+        // Loops bailed out early, so the pixels can't be trusted: blank them instead
+        if executable.fuel_remaining() == Some(0) {
+            let (x_range, y_range) = region;
+            for y in y_range {
+                for x in x_range.clone() {
+                    texture.set_pixel(x, y, [0, 0, 0, 255]);
+                }
+            }
+            return Err(Error::Other {
+                message: format!(
+                    "Shader execution failed: ran out of loop fuel ({loop_fuel} iterations per frame)"
+                ),
+            });
+        }
+
+        self.dirty = false;
         Ok(())
     }
 
@@ -220,7 +406,7 @@ impl NodeRuntime for ShaderRuntime {
             .as_any()
             .downcast_ref::<ShaderConfig>()
             .ok_or_else(|| Error::InvalidConfig {
-                node_path: format!("shader-{}", self.node_handle.as_i32()),
+                node_path: self.node_path.clone(),
                 reason: "Config is not a ShaderConfig".to_string(),
             })?;
 
@@ -228,6 +414,7 @@ impl NodeRuntime for ShaderRuntime {
         let new_config_clone = shader_config.clone();
         self.config = Some(new_config_clone.clone());
         self.render_order = shader_config.render_order;
+        self.dirty = true;
 
         // If texture_spec changed, re-resolve texture handle
         let texture_changed = old_config
@@ -237,7 +424,7 @@ impl NodeRuntime for ShaderRuntime {
 
         if texture_changed {
             let texture_handle = ctx
-                .resolve_texture(&shader_config.texture_spec)
+                .resolve_layer_target(&shader_config.texture_spec)
                 .map_err(|e| {
                     self.compilation_error = Some(format!("Failed to resolve texture: {e}"));
                     e
@@ -245,14 +432,27 @@ impl NodeRuntime for ShaderRuntime {
             self.texture_handle = Some(texture_handle);
         }
 
-        // If glsl_path changed, reload and recompile
-        let glsl_path_changed = old_config
+        // If glsl_path, backend or palette changed, reload and recompile
+        let needs_recompile = old_config
             .as_ref()
-            .map(|old| old.glsl_path != shader_config.glsl_path)
+            .map(|old| {
+                old.glsl_path != shader_config.glsl_path
+                    || old.backend != shader_config.backend
+                    || old.palette != shader_config.palette
+            })
             .unwrap_or(true);
 
-        if glsl_path_changed {
+        if needs_recompile {
             self.load_and_compile_shader(&new_config_clone, ctx)?;
+        } else {
+            // Uniform changes only need the new values written, not a recompile
+            let uniforms_changed = old_config
+                .as_ref()
+                .map(|old| old.uniforms != shader_config.uniforms)
+                .unwrap_or(true);
+            if uniforms_changed {
+                self.apply_config_uniforms();
+            }
         }
 
         Ok(())
@@ -268,7 +468,7 @@ impl NodeRuntime for ShaderRuntime {
             .as_ref()
             .map(|c| c.glsl_path.clone())
             .ok_or_else(|| Error::InvalidConfig {
-                node_path: format!("shader-{}", self.node_handle.as_i32()),
+                node_path: self.node_path.clone(),
                 reason: "Config not set".to_string(),
             })?;
 
@@ -278,7 +478,7 @@ impl NodeRuntime for ShaderRuntime {
                 lp_shared::fs::fs_event::ChangeType::Create
                 | lp_shared::fs::fs_event::ChangeType::Modify => {
                     let config = self.config.clone().ok_or_else(|| Error::InvalidConfig {
-                        node_path: format!("shader-{}", self.node_handle.as_i32()),
+                        node_path: self.node_path.clone(),
                         reason: "Config not set".to_string(),
                     })?;
                     // Don't fail on compilation errors - store them and return Ok()
@@ -293,15 +493,39 @@ impl NodeRuntime for ShaderRuntime {
                     self.direct_call_conv = None;
                     self.direct_pointer_type = None;
                     self.compilation_error = Some("GLSL file deleted".to_string());
+                    self.dirty = true;
                 }
             }
         }
 
         Ok(())
     }
+
+    fn set_param(&mut self, name: &str, value: f32) -> Result<(), Error> {
+        self.set_uniform(name, value)
+    }
 }
 
 impl ShaderRuntime {
+    /// Pixel ranges to run the shader over: the configured viewport clipped to the
+    /// texture, or the whole texture if no viewport is set
+    fn render_region(
+        config: Option<&ShaderConfig>,
+        width: u32,
+        height: u32,
+    ) -> (Range<u32>, Range<u32>) {
+        match config.and_then(|c| c.viewport) {
+            Some(viewport) => {
+                let x0 = viewport.x.min(width);
+                let y0 = viewport.y.min(height);
+                let x1 = viewport.x.saturating_add(viewport.width).min(width);
+                let y1 = viewport.y.saturating_add(viewport.height).min(height);
+                (x0..x1, y0..y1)
+            }
+            None => (0..width, 0..height),
+        }
+    }
+
     /// Direct call path: bypass GlslValue conversion overhead
     /// Calls the shader function pointer directly with raw arguments
     /// All conversions are done in Q32 fixed-point format (i32) to avoid floating-point overhead
@@ -311,117 +535,61 @@ impl ShaderRuntime {
         pointer_type: &cranelift_codegen::ir::Type,
         width: u32,
         height: u32,
+        x_range: Range<u32>,
+        y_range: Range<u32>,
         time: f32,
         texture: &mut lp_shared::Texture,
     ) -> Result<(), Error> {
-        // Q32 fixed-point scale factor (2^16 = 65536)
-        const Q32_SCALE: i32 = 65536;
-
         // Convert time from f32 to Q32 format once (reused for all pixels)
         let time_q32 = (time * 65536.0) as i32;
 
         // Convert output_size to Q32 format once (reused for all pixels)
         let output_size_q32 = [(width as i32) * Q32_SCALE, (height as i32) * Q32_SCALE];
 
-        // Result buffer for vec4 return (4 i32s = 16 bytes)
-        let mut result_buffer = [0u8; 16];
-
-        // Execute shader for each pixel
-        for y in 0..height {
-            for x in 0..width {
-                // Convert frag_coord to Q32 format
-                let frag_coord_q32 = [(x as i32) * Q32_SCALE, (y as i32) * Q32_SCALE];
-
-                // Prepare JIT call arguments (i32 values as u64)
-                // vec2 expands to 2 i32s each, so we have 5 i32 parameters total
-                let jit_args = [
-                    frag_coord_q32[0] as u64,  // fragCoord.x
-                    frag_coord_q32[1] as u64,  // fragCoord.y
-                    output_size_q32[0] as u64, // outputSize.x
-                    output_size_q32[1] as u64, // outputSize.y
-                    time_q32 as u64,           // time
-                ];
-
-                // Call the shader function with StructReturn
-                unsafe {
-                    call_structreturn_with_args(
-                        func_ptr,
-                        result_buffer.as_mut_ptr(),
-                        16, // 4 i32s = 16 bytes
-                        &jit_args,
-                        *call_conv,
-                        *pointer_type,
-                    )
-                    .map_err(|e| Error::Other {
-                        message: format!("Direct shader call failed: {e:?}"),
-                    })?;
-                }
-
-                // Extract vec4 result (r, g, b, a) as i32 q32 values from buffer
-                let r_q32 = i32::from_le_bytes([
-                    result_buffer[0],
-                    result_buffer[1],
-                    result_buffer[2],
-                    result_buffer[3],
-                ]);
-                let g_q32 = i32::from_le_bytes([
-                    result_buffer[4],
-                    result_buffer[5],
-                    result_buffer[6],
-                    result_buffer[7],
-                ]);
-                let b_q32 = i32::from_le_bytes([
-                    result_buffer[8],
-                    result_buffer[9],
-                    result_buffer[10],
-                    result_buffer[11],
-                ]);
-                let a_q32 = i32::from_le_bytes([
-                    result_buffer[12],
-                    result_buffer[13],
-                    result_buffer[14],
-                    result_buffer[15],
-                ]);
-
-                // Convert from Q32 fixed-point directly to u8 [0, 255] using integer math
-                // Formula: (q32_value * 255) / 65536
-                // Clamp to [0, 65536] range first, then scale
-                let clamp_q32 = |v: i32| -> i32 {
-                    if v < 0 {
-                        0
-                    } else if v > Q32_SCALE {
-                        Q32_SCALE
-                    } else {
-                        v
-                    }
-                };
-
-                // Convert Q32 to u8: (clamped_q32 * 255) / 65536
-                // Use i64 intermediate to avoid overflow
-                let r = ((clamp_q32(r_q32) as i64 * 255) / Q32_SCALE as i64) as u8;
-                let g = ((clamp_q32(g_q32) as i64 * 255) / Q32_SCALE as i64) as u8;
-                let b = ((clamp_q32(b_q32) as i64 * 255) / Q32_SCALE as i64) as u8;
-                let a = ((clamp_q32(a_q32) as i64 * 255) / Q32_SCALE as i64) as u8;
-
-                let rgba = [r, g, b, a];
-
-                // Write to texture
-                texture.set_pixel(x, y, rgba);
+        // Rows of results are gathered and written to the texture in batches
+        pixels::render_batched(x_range, y_range, texture, |frag_coord_q32| {
+            // Prepare JIT call arguments (i32 values as u64)
+            // vec2 expands to 2 i32s each, so we have 5 i32 parameters total
+            let jit_args = [
+                frag_coord_q32[0] as u64,  // fragCoord.x
+                frag_coord_q32[1] as u64,  // fragCoord.y
+                output_size_q32[0] as u64, // outputSize.x
+                output_size_q32[1] as u64, // outputSize.y
+                time_q32 as u64,           // time
+            ];
+
+            // Call the shader function with StructReturn, writing vec4 (r, g, b, a)
+            // straight into the result as Q32 values
+            let mut result = [0i32; 4];
+            unsafe {
+                call_structreturn_with_args(
+                    func_ptr,
+                    result.as_mut_ptr(),
+                    16, // 4 i32s = 16 bytes
+                    &jit_args,
+                    *call_conv,
+                    *pointer_type,
+                )
+                .map_err(|e| Error::Other {
+                    message: format!("Direct shader call failed: {e:?}"),
+                })?;
             }
-        }
-
-        Ok(())
+            Ok(result)
+        })
     }
+
     /// Resolve texture handle from config
     fn resolve_texture_handle(
         &mut self,
         config: &ShaderConfig,
         ctx: &dyn NodeInitContext,
     ) -> Result<(), Error> {
-        let texture_handle = ctx.resolve_texture(&config.texture_spec).map_err(|e| {
-            self.compilation_error = Some(format!("Failed to resolve texture: {e}"));
-            e
-        })?;
+        let texture_handle = ctx
+            .resolve_layer_target(&config.texture_spec)
+            .map_err(|e| {
+                self.compilation_error = Some(format!("Failed to resolve texture: {e}"));
+                e
+            })?;
         self.texture_handle = Some(texture_handle);
         Ok(())
     }
@@ -468,13 +636,46 @@ impl ShaderRuntime {
         );
         log::trace!("ShaderRuntime::compile_shader: GLSL source:\n{glsl_source}");
 
+        let backend = self
+            .config
+            .as_ref()
+            .and_then(|c| c.backend)
+            .unwrap_or_default();
         let options = GlslOptions {
             run_mode: RunMode::HostJit,
             decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: Some(self.loop_fuel()),
+            warnings_as_errors: false,
+        };
+        let compiled = match backend {
+            ShaderBackend::Jit => {
+                glsl_jit(glsl_source, options.clone()).or_else(|jit_error| {
+                    // Keep the JIT error if the interpreter can't run the shader either
+                    match glsl_interpret(glsl_source, options) {
+                        Ok(executable) => {
+                            log::warn!(
+                                "ShaderRuntime::compile_shader: Shader {} falling back to the interpreter: {}",
+                                self.node_handle.as_i32(),
+                                jit_error
+                            );
+                            Ok(executable)
+                        }
+                        Err(_) => Err(jit_error),
+                    }
+                })
+            }
+            ShaderBackend::Emulator => compile_emulated(glsl_source),
+            ShaderBackend::Interpreter => glsl_interpret(glsl_source, options),
         };
 
-        match glsl_jit(glsl_source, options) {
+        match compiled {
             Ok(executable) => {
+                // A `main` that isn't pure only because it reads uniforms needn't render
+                // every frame: setting a uniform marks the shader dirty
+                self.animated = executable.reads_time("main");
+
                 // Extract function pointer and calling convention using trait method
                 // This allows us to make direct calls without the GlslValue conversion overhead
                 let direct_call_info = executable.get_direct_call_info("main");
@@ -494,6 +695,8 @@ impl ShaderRuntime {
                     unsafe { core::mem::transmute(executable) };
                 self.executable = Some(executable_with_bounds);
                 self.compilation_error = None;
+                self.dirty = true;
+                self.apply_config_uniforms();
                 log::debug!(
                     "ShaderRuntime::compile_shader: Shader {} compiled successfully",
                     self.node_handle.as_i32()
@@ -512,7 +715,7 @@ impl ShaderRuntime {
                     e
                 );
                 Err(Error::InvalidConfig {
-                    node_path: format!("shader-{}", self.node_handle.as_i32()),
+                    node_path: self.node_path.clone(),
                     reason: format!("GLSL compilation failed: {e}"),
                 })
             }
@@ -526,14 +729,129 @@ impl ShaderRuntime {
         ctx: &dyn NodeInitContext,
     ) -> Result<(), Error> {
         let glsl_source = self.load_glsl_source(config, ctx)?;
+        let glsl_source = with_palette(glsl_source, &config.palette);
         self.compile_shader(&glsl_source)
     }
 }
 
+/// Append the generated `palette` function if the config has gradient stops
+///
+/// Functions can be called before they are defined, so it goes after the shader's
+/// own code and leaves the line numbers in compile errors unchanged.
+fn with_palette(mut glsl_source: String, stops: &[GradientStopConfig]) -> String {
+    if !stops.is_empty() {
+        glsl_source.push('\n');
+        glsl_source.push_str(&Gradient::from_config(stops).to_glsl());
+    }
+    glsl_source
+}
+
+/// Compile a shader to run in the RISC-V emulator
+#[cfg(feature = "emulator")]
+fn compile_emulated(glsl_source: &str) -> Result<Box<dyn GlslExecutable>, GlslError> {
+    let mut options = GlslOptions::emu_riscv32_imac();
+    if let RunMode::Emulator {
+        max_instructions, ..
+    } = &mut options.run_mode
+    {
+        *max_instructions = EMULATOR_MAX_INSTRUCTIONS;
+    }
+    lp_glsl_compiler::glsl_emu_riscv32(glsl_source, options)
+}
+
+#[cfg(not(feature = "emulator"))]
+fn compile_emulated(_glsl_source: &str) -> Result<Box<dyn GlslExecutable>, GlslError> {
+    Err(GlslError::new(
+        lp_glsl_compiler::ErrorCode::E0400,
+        "shader emulator support is not enabled in this build",
+    ))
+}
+
+/// One-line summary of a failed shader call for the node status
+///
+/// Keeps the reason and source location, plus the faulting PC of an emulator trap,
+/// but leaves out the IR and disassembly dumps attached to the full error.
+fn describe_execution_error(error: &GlslError) -> String {
+    let mut description = error.message.clone();
+    if let Some(location) = error.location.as_ref().filter(|loc| !loc.is_unknown()) {
+        description.push_str(&format!(
+            " at line {}, column {}",
+            location.line, location.column
+        ));
+    }
+    if let Some(pc) = error
+        .notes
+        .iter()
+        .find_map(|note| note.strip_prefix("Trap occurred at PC "))
+    {
+        description.push_str(&format!(" (PC {pc})"));
+    }
+    description
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_describe_execution_error() {
+        use lp_glsl_compiler::GlSourceLoc;
+        use lp_glsl_compiler::frontend::src_loc::GlFileId;
+
+        let error = GlslError::new(
+            lp_glsl_compiler::ErrorCode::E0400,
+            "execution trapped: int_divz",
+        )
+        .with_location(GlSourceLoc::new(GlFileId(1), 3, 17))
+        .with_note("Trap occurred at PC 0x00000010")
+        .with_note("Disassembled:\n...");
+        assert_eq!(
+            describe_execution_error(&error),
+            "execution trapped: int_divz at line 3, column 17 (PC 0x00000010)"
+        );
+
+        let error = GlslError::new(lp_glsl_compiler::ErrorCode::E0400, "bad call");
+        assert_eq!(describe_execution_error(&error), "bad call");
+    }
+
+    #[test]
+    fn test_render_region_clips_viewport_to_texture() {
+        let mut config = ShaderConfig::default();
+        assert_eq!(
+            ShaderRuntime::render_region(Some(&config), 8, 4),
+            (0..8, 0..4)
+        );
+
+        config.viewport = Some(lp_model::nodes::shader::ViewportConfig {
+            x: 6,
+            y: 1,
+            width: 4,
+            height: 2,
+        });
+        assert_eq!(
+            ShaderRuntime::render_region(Some(&config), 8, 4),
+            (6..8, 1..3)
+        );
+    }
+
+    #[test]
+    fn test_with_palette() {
+        let source = String::from("vec4 main(vec2 fragCoord, vec2 outputSize, float time) {}");
+        assert_eq!(with_palette(source.clone(), &[]), source);
+
+        let stops = [GradientStopConfig {
+            position: 0.0,
+            color: lp_model::nodes::ColorConfig::Rgb {
+                r: 1.0,
+                g: 1.0,
+                b: 1.0,
+            },
+        }];
+        let appended = with_palette(source.clone(), &stops);
+        assert!(appended.starts_with(&source));
+        assert!(appended.contains("vec3 palette(float t)"));
+    }
+
     #[test]
     fn test_shader_runtime_creation() {
         let handle = lp_model::NodeHandle::new(0);
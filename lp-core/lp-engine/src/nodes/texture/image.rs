@@ -0,0 +1,215 @@
+//! Image decoding for texture nodes
+//!
+//! Decodes image files into RGBA8 pixels (row-major, top row first) so they can be
+//! copied directly into an RGBA8 [`lp_shared::Texture`].
+//!
+//! Uncompressed 24/32-bit BMP is always supported. PNG requires the `std` feature.
+
+use alloc::{format, string::String, vec::Vec};
+
+/// Decoded image with RGBA8 pixels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedImage {
+    pub width: u32,
+    pub height: u32,
+    /// RGBA8 pixel data, `width * height * 4` bytes
+    pub pixels: Vec<u8>,
+}
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const BMP_SIGNATURE: &[u8] = b"BM";
+
+/// Decode an image, detecting the format from its signature
+pub fn decode_image(data: &[u8]) -> Result<DecodedImage, String> {
+    if data.starts_with(PNG_SIGNATURE) {
+        decode_png(data)
+    } else if data.starts_with(BMP_SIGNATURE) {
+        decode_bmp(data)
+    } else {
+        Err(String::from(
+            "Unsupported image format (expected PNG or BMP)",
+        ))
+    }
+}
+
+#[cfg(feature = "std")]
+fn decode_png(data: &[u8]) -> Result<DecodedImage, String> {
+    let mut decoder = png::Decoder::new(data);
+    // Expand palettes and low bit depths, and reduce 16-bit channels to 8
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| format!("Invalid PNG: {e}"))?;
+
+    let mut buf = alloc::vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buf)
+        .map_err(|e| format!("Invalid PNG: {e}"))?;
+    let src = &buf[..info.buffer_size()];
+
+    let pixel_count = info.width as usize * info.height as usize;
+    let mut pixels = Vec::with_capacity(pixel_count * 4);
+    match info.color_type {
+        png::ColorType::Rgba => pixels.extend_from_slice(src),
+        png::ColorType::Rgb => {
+            for rgb in src.chunks_exact(3) {
+                pixels.extend_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+            }
+        }
+        png::ColorType::GrayscaleAlpha => {
+            for ga in src.chunks_exact(2) {
+                pixels.extend_from_slice(&[ga[0], ga[0], ga[0], ga[1]]);
+            }
+        }
+        png::ColorType::Grayscale => {
+            for &g in src {
+                pixels.extend_from_slice(&[g, g, g, 255]);
+            }
+        }
+        png::ColorType::Indexed => {
+            return Err(String::from("Unsupported PNG color type: indexed"));
+        }
+    }
+
+    Ok(DecodedImage {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn decode_png(_data: &[u8]) -> Result<DecodedImage, String> {
+    Err(String::from("PNG decoding requires the std feature"))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, String> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| String::from("Truncated BMP header"))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, String> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| String::from("Truncated BMP header"))
+}
+
+/// Decode an uncompressed 24-bit or 32-bit BMP
+fn decode_bmp(data: &[u8]) -> Result<DecodedImage, String> {
+    let pixel_offset = read_u32(data, 10)? as usize;
+    let width = read_u32(data, 18)? as i32;
+    let height = read_u32(data, 22)? as i32;
+    let bits_per_pixel = read_u16(data, 28)?;
+    let compression = read_u32(data, 30)?;
+
+    if compression != 0 {
+        return Err(format!("Unsupported BMP compression: {compression}"));
+    }
+    if bits_per_pixel != 24 && bits_per_pixel != 32 {
+        return Err(format!("Unsupported BMP bit depth: {bits_per_pixel}"));
+    }
+    if width <= 0 || height == 0 {
+        return Err(format!("Invalid BMP dimensions: {width}x{height}"));
+    }
+
+    // Positive height means rows are stored bottom-up
+    let bottom_up = height > 0;
+    let width = width as u32;
+    let height = height.unsigned_abs();
+
+    let bytes_per_pixel = bits_per_pixel as usize / 8;
+    // Rows are padded to a multiple of 4 bytes
+    let row_stride = (width as usize * bytes_per_pixel).div_ceil(4) * 4;
+    let needed = pixel_offset + row_stride * height as usize;
+    if data.len() < needed {
+        return Err(format!(
+            "Truncated BMP pixel data: expected {needed} bytes, got {}",
+            data.len()
+        ));
+    }
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for y in 0..height as usize {
+        let src_row = if bottom_up {
+            height as usize - 1 - y
+        } else {
+            y
+        };
+        let row_start = pixel_offset + src_row * row_stride;
+        let row = &data[row_start..row_start + width as usize * bytes_per_pixel];
+        for bgr in row.chunks_exact(bytes_per_pixel) {
+            // Pixels are stored as BGR(X); the 4th byte of BI_RGB 32-bit pixels is unused
+            pixels.extend_from_slice(&[bgr[2], bgr[1], bgr[0], 255]);
+        }
+    }
+
+    Ok(DecodedImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a 24-bit bottom-up BMP from top-down RGB rows
+    fn bmp_24(width: u32, rows: &[&[[u8; 3]]]) -> Vec<u8> {
+        let height = rows.len() as u32;
+        let row_stride = (width as usize * 3).div_ceil(4) * 4;
+        let pixel_offset = 54u32;
+        let file_size = pixel_offset as usize + row_stride * height as usize;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(b"BM");
+        data.extend_from_slice(&(file_size as u32).to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&pixel_offset.to_le_bytes());
+        data.extend_from_slice(&40u32.to_le_bytes());
+        data.extend_from_slice(&(width as i32).to_le_bytes());
+        data.extend_from_slice(&(height as i32).to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&24u16.to_le_bytes());
+        data.extend_from_slice(&[0; 24]);
+        for row in rows.iter().rev() {
+            let start = data.len();
+            for rgb in row.iter() {
+                data.extend_from_slice(&[rgb[2], rgb[1], rgb[0]]);
+            }
+            data.resize(start + row_stride, 0);
+        }
+        data
+    }
+
+    #[test]
+    fn test_decode_bmp_bottom_up() {
+        let data = bmp_24(
+            3,
+            &[
+                &[[255, 0, 0], [0, 255, 0], [0, 0, 255]],
+                &[[10, 20, 30], [40, 50, 60], [70, 80, 90]],
+            ],
+        );
+        let image = decode_image(&data).unwrap();
+        assert_eq!(image.width, 3);
+        assert_eq!(image.height, 2);
+        assert_eq!(&image.pixels[0..4], &[255, 0, 0, 255]);
+        assert_eq!(&image.pixels[8..12], &[0, 0, 255, 255]);
+        assert_eq!(&image.pixels[12..16], &[10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_decode_unsupported_format() {
+        let err = decode_image(b"GIF89a....").unwrap_err();
+        assert!(err.contains("Unsupported image format"), "{err}");
+    }
+
+    #[test]
+    fn test_decode_truncated_bmp() {
+        let mut data = bmp_24(2, &[&[[1, 2, 3], [4, 5, 6]]]);
+        data.truncate(data.len() - 4);
+        assert!(decode_image(&data).is_err());
+    }
+}
@@ -1,17 +1,26 @@
 use crate::error::Error;
+use crate::nodes::color::color_config_to_rgb8;
+use crate::nodes::texture::image::decode_image;
 use crate::nodes::{NodeConfig, NodeRuntime};
 use crate::runtime::contexts::{NodeInitContext, RenderContext};
 use alloc::{boxed::Box, format, string::ToString, vec::Vec};
 use lp_model::{
-    NodeHandle,
-    nodes::texture::{TextureConfig, TextureState},
+    LpPathBuf, NodeHandle,
+    nodes::texture::{TextureConfig, TextureFilter, TextureState},
+};
+use lp_shared::{
+    Texture,
+    fs::{LpFs, fs_event::FsChange},
 };
-use lp_shared::{Texture, fs::fs_event::FsChange};
 
 /// Texture node runtime
 pub struct TextureRuntime {
     config: Option<TextureConfig>,
     texture: Option<Texture>,
+    /// Fill color from the config, converted to RGB at init
+    fill_rgb: Option<[u8; 3]>,
+    /// Pixels of the loaded image, kept so the texture can be reset to them
+    image: Option<Vec<u8>>,
     node_handle: NodeHandle,
 }
 
@@ -20,6 +29,8 @@ impl TextureRuntime {
         Self {
             config: None,
             texture: None,
+            fill_rgb: None,
+            image: None,
             node_handle,
         }
     }
@@ -68,29 +79,109 @@ impl TextureRuntime {
     pub fn get_config(&self) -> Option<&TextureConfig> {
         self.config.as_ref()
     }
-}
 
-impl NodeRuntime for TextureRuntime {
-    fn init(&mut self, _ctx: &dyn NodeInitContext) -> Result<(), Error> {
-        let config = self.config.as_ref().ok_or_else(|| Error::InvalidConfig {
-            node_path: format!("texture-{}", self.node_handle.as_i32()),
-            reason: "Config not set".to_string(),
-        })?;
+    /// Get the filter fixtures use when sampling this texture
+    pub fn filter(&self) -> TextureFilter {
+        self.config
+            .as_ref()
+            .map(TextureConfig::filter)
+            .unwrap_or_default()
+    }
+
+    /// Get the fill color as RGB, if the config has one
+    pub fn fill_rgb(&self) -> Option<[u8; 3]> {
+        self.fill_rgb
+    }
+
+    /// Restore the texture's own contents: its image, its fill color, or black
+    ///
+    /// Used before groups composite onto the texture, since compositing accumulates.
+    pub fn reset(&mut self) {
+        let Some(texture) = self.texture.as_mut() else {
+            return;
+        };
+        if let Some(image) = &self.image
+            && image.len() == texture.data().len()
+        {
+            texture.data_mut().copy_from_slice(image);
+        } else if let Some([r, g, b]) = self.fill_rgb {
+            texture.compute_all(|_, _| [r, g, b, 255]);
+        } else {
+            texture.data_mut().fill(0);
+        }
+    }
+
+    /// Create the texture for a config, loading it from the image file if one is set
+    fn create_texture(&mut self, config: &TextureConfig, fs: &dyn LpFs) -> Result<(), Error> {
+        self.fill_rgb = config.fill_color.as_ref().map(color_config_to_rgb8);
+
+        if let Some(image_path) = &config.image_path {
+            let texture = self.load_image(image_path, fs)?;
+            self.image = Some(texture.data().to_vec());
+            self.texture = Some(texture);
+            return Ok(());
+        }
+        self.image = None;
 
         // Create texture with RGBA8 format (default for now)
         // Format will be added to TextureConfig later
         let format = "RGBA8".to_string();
-        let texture = Texture::new(config.width, config.height, format).map_err(|e| {
+        let mut texture = Texture::new(config.width, config.height, format).map_err(|e| {
             Error::InvalidConfig {
                 node_path: format!("texture-{}", self.node_handle.as_i32()),
                 reason: format!("Failed to create texture: {e}"),
             }
         })?;
+        if let Some([r, g, b]) = self.fill_rgb {
+            texture.compute_all(|_, _| [r, g, b, 255]);
+        }
 
         self.texture = Some(texture);
         Ok(())
     }
 
+    /// Load and decode an image file into an RGBA8 texture
+    fn load_image(&self, image_path: &LpPathBuf, fs: &dyn LpFs) -> Result<Texture, Error> {
+        // Make path absolute if relative (chrooted filesystem requires absolute paths)
+        let image_path_abs = if image_path.is_absolute() {
+            image_path.clone()
+        } else {
+            LpPathBuf::from(format!("/{}", image_path.as_str()))
+        };
+        let data = fs
+            .read_file(image_path_abs.as_path())
+            .map_err(|e| Error::Io {
+                path: image_path.as_str().to_string(),
+                details: format!("Failed to read image file: {e:?}"),
+            })?;
+
+        let image = decode_image(&data).map_err(|reason| Error::InvalidConfig {
+            node_path: format!("texture-{}", self.node_handle.as_i32()),
+            reason: format!("Failed to load image {}: {reason}", image_path.as_str()),
+        })?;
+
+        let mut texture =
+            Texture::new(image.width, image.height, "RGBA8".to_string()).map_err(|e| {
+                Error::InvalidConfig {
+                    node_path: format!("texture-{}", self.node_handle.as_i32()),
+                    reason: format!("Failed to create texture: {e}"),
+                }
+            })?;
+        texture.data_mut().copy_from_slice(&image.pixels);
+        Ok(texture)
+    }
+}
+
+impl NodeRuntime for TextureRuntime {
+    fn init(&mut self, ctx: &dyn NodeInitContext) -> Result<(), Error> {
+        let config = self.config.clone().ok_or_else(|| Error::InvalidConfig {
+            node_path: format!("texture-{}", self.node_handle.as_i32()),
+            reason: "Config not set".to_string(),
+        })?;
+
+        self.create_texture(&config, ctx.get_node_fs())
+    }
+
     fn render(&mut self, _ctx: &mut dyn RenderContext) -> Result<(), Error> {
         // No-op - textures don't render themselves, shaders render to textures
         Ok(())
@@ -107,7 +198,7 @@ impl NodeRuntime for TextureRuntime {
     fn update_config(
         &mut self,
         new_config: Box<dyn NodeConfig>,
-        _ctx: &dyn NodeInitContext,
+        ctx: &dyn NodeInitContext,
     ) -> Result<(), Error> {
         // Downcast to TextureConfig
         let texture_config = new_config
@@ -119,21 +210,20 @@ impl NodeRuntime for TextureRuntime {
             })?;
 
         let old_config = self.config.as_ref();
-        let needs_resize = old_config
-            .map(|old| old.width != texture_config.width || old.height != texture_config.height)
+        let needs_recreate = old_config
+            .map(|old| {
+                old.width != texture_config.width
+                    || old.height != texture_config.height
+                    || old.image_path != texture_config.image_path
+                    || old.fill_color != texture_config.fill_color
+            })
             .unwrap_or(true);
 
         self.config = Some(texture_config.clone());
 
-        // If dimensions changed, resize texture
-        if needs_resize {
-            let format = "RGBA8".to_string();
-            let texture = Texture::new(texture_config.width, texture_config.height, format)
-                .map_err(|e| Error::InvalidConfig {
-                    node_path: format!("texture-{}", self.node_handle.as_i32()),
-                    reason: format!("Failed to resize texture: {e}"),
-                })?;
-            self.texture = Some(texture);
+        // If dimensions, image or fill color changed, recreate texture
+        if needs_recreate {
+            self.create_texture(texture_config, ctx.get_node_fs())?;
         }
 
         Ok(())
@@ -141,11 +231,31 @@ impl NodeRuntime for TextureRuntime {
 
     fn handle_fs_change(
         &mut self,
-        _change: &FsChange,
-        _ctx: &dyn NodeInitContext,
+        change: &FsChange,
+        ctx: &dyn NodeInitContext,
     ) -> Result<(), Error> {
-        // Textures don't currently support loading from files
-        // This is a no-op for now
+        let Some(image_path) = self.config.as_ref().and_then(|c| c.image_path.clone()) else {
+            return Ok(());
+        };
+
+        // Reload when the image file changes; keep the last texture if it is deleted
+        if change.path.as_str() == image_path.as_str() {
+            match change.change_type {
+                lp_shared::fs::fs_event::ChangeType::Create
+                | lp_shared::fs::fs_event::ChangeType::Modify => {
+                    let texture = self.load_image(&image_path, ctx.get_node_fs())?;
+                    self.image = Some(texture.data().to_vec());
+                    self.texture = Some(texture);
+                }
+                lp_shared::fs::fs_event::ChangeType::Delete => {
+                    log::warn!(
+                        "Texture image {} deleted, keeping last loaded image",
+                        image_path.as_str()
+                    );
+                }
+            }
+        }
+
         Ok(())
     }
 }
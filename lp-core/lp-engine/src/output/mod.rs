@@ -1,4 +1,4 @@
 // Re-export from lp-shared for backwards compatibility
 pub use lp_shared::output::{
-    MemoryOutputProvider, OutputChannelHandle, OutputFormat, OutputProvider,
+    Brightness, FrameStamp, MemoryOutputProvider, OutputChannelHandle, OutputFormat, OutputProvider,
 };
@@ -31,6 +31,7 @@ pub(crate) fn node_kind_from_path(path: &LpPathBuf) -> Result<NodeKind, Error> {
         "shader" => Ok(NodeKind::Shader),
         "output" => Ok(NodeKind::Output),
         "fixture" => Ok(NodeKind::Fixture),
+        "group" => Ok(NodeKind::Group),
         _ => Err(Error::InvalidConfig {
             node_path: path_str.to_string(),
             reason: format!("Unknown node kind: {suffix}"),
@@ -45,6 +46,7 @@ pub(crate) fn is_node_directory(path: &LpPathBuf) -> bool {
         || path_str.ends_with(".shader")
         || path_str.ends_with(".output")
         || path_str.ends_with(".fixture")
+        || path_str.ends_with(".group")
 }
 
 /// Load project config from filesystem
@@ -67,7 +69,7 @@ pub fn load_from_filesystem(fs: &dyn LpFs) -> Result<ProjectConfig, Error> {
         format!("{data:02x?}")
     };
 
-    let config: ProjectConfig = lp_model::json::from_slice(&data).map_err(|e| Error::Parse {
+    let config = lp_model::project::parse_project_config(&data).map_err(|e| Error::Parse {
         file: path.to_string(),
         error: format!(
             "{e}\n\nActual project.json content ({} bytes):\n{}\n\nHex dump (first 100 bytes):\n{}",
@@ -146,6 +148,14 @@ pub fn load_node(fs: &dyn LpFs, path: &LpPath) -> Result<(LpPathBuf, Box<dyn Nod
                 })?;
             Box::new(cfg)
         }
+        NodeKind::Group => {
+            let cfg: lp_model::nodes::group::GroupConfig = lp_model::json::from_slice(&data)
+                .map_err(|e| Error::Parse {
+                    file: node_json_path.as_str().to_string(),
+                    error: format!("Failed to parse group config: {e}"),
+                })?;
+            Box::new(cfg)
+        }
     };
 
     Ok((path.to_path_buf(), config))
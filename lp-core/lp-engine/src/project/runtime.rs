@@ -1,7 +1,11 @@
 use crate::error::Error;
-use crate::nodes::{FixtureRuntime, NodeRuntime, OutputRuntime, ShaderRuntime, TextureRuntime};
-use crate::output::OutputProvider;
+use crate::nodes::{
+    FixtureRuntime, GroupRuntime, NodeRuntime, OutputRuntime, ShaderRuntime, TextureRuntime,
+};
+use crate::output::{FrameStamp, OutputProvider};
+use crate::runtime::frame_pool::{FrameBufferPool, FrameBufferPoolStats};
 use crate::runtime::frame_time::FrameTime;
+use crate::runtime::rng::{DEFAULT_PROJECT_SEED, NodeRng};
 use alloc::boxed::Box;
 use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::format;
@@ -10,6 +14,7 @@ use alloc::string::{String, ToString};
 use alloc::{vec, vec::Vec};
 use core::cell::RefCell;
 use log;
+use lp_model::project::AutomationTrack;
 use lp_model::{
     AsLpPath, FrameId, LpPath, LpPathBuf, NodeConfig, NodeHandle, NodeKind,
     project::api::{
@@ -18,6 +23,14 @@ use lp_model::{
     },
 };
 use lp_shared::fs::{LpFs, fs_event::FsChange};
+use lp_shared::time::TimeProvider;
+
+/// Period synced frame times wrap at, in milliseconds
+///
+/// A synced stamp's time is the host's clock, typically wall-clock time since 1970 and
+/// far beyond what shader `time` can represent (Q32 tops out near 32768 seconds). Every
+/// device takes the stamp time modulo this period, so they all agree on the frame time.
+const SYNCED_TIME_PERIOD_MS: u64 = 60 * 60 * 1000;
 
 /// Project runtime - manages nodes and rendering
 pub struct ProjectRuntime {
@@ -33,6 +46,17 @@ pub struct ProjectRuntime {
     pub nodes: BTreeMap<NodeHandle, NodeEntry>,
     /// Next handle to assign
     pub next_handle: i32,
+    /// Project seed for node randomness
+    pub seed: u64,
+    /// Scheduled parameter changes from the project config (keyframes sorted)
+    pub automation: Vec<AutomationTrack>,
+    /// Render buffers recycled across frames
+    pub frame_pool: FrameBufferPool,
+    /// Clock reading at the last [`ProjectRuntime::tick_clock`]
+    last_clock_ms: Option<u64>,
+    /// Stamp of the last frame rendered by [`ProjectRuntime::tick_at`], cleared when
+    /// [`ProjectRuntime::tick`] renders an unsynced frame
+    last_synced: Option<FrameStamp>,
 }
 
 /// Node entry in runtime
@@ -97,7 +121,10 @@ impl ProjectRuntime {
         fs: Rc<RefCell<dyn LpFs>>,
         output_provider: Rc<RefCell<dyn OutputProvider>>,
     ) -> Result<Self, Error> {
-        let _config = crate::project::loader::load_from_filesystem(&*fs.borrow())?;
+        let mut config = crate::project::loader::load_from_filesystem(&*fs.borrow())?;
+        for track in &mut config.automation {
+            track.sort_keyframes();
+        }
 
         Ok(Self {
             frame_id: FrameId::default(),
@@ -106,6 +133,11 @@ impl ProjectRuntime {
             output_provider,
             nodes: BTreeMap::new(),
             next_handle: 1,
+            seed: config.seed.unwrap_or(DEFAULT_PROJECT_SEED),
+            automation: config.automation,
+            frame_pool: FrameBufferPool::new(),
+            last_clock_ms: None,
+            last_synced: None,
         })
     }
 
@@ -150,12 +182,21 @@ impl ProjectRuntime {
                         NodeKind::Texture => Box::new(lp_model::nodes::texture::TextureConfig {
                             width: 0,
                             height: 0,
+                            image_path: None,
+                            fill_color: None,
+                            filter: None,
+                            enabled: None,
                         }),
                         NodeKind::Shader => {
                             Box::new(lp_model::nodes::shader::ShaderConfig::default())
                         }
                         NodeKind::Output => {
-                            Box::new(lp_model::nodes::output::OutputConfig::GpioStrip { pin: 0 })
+                            Box::new(lp_model::nodes::output::OutputConfig::GpioStrip {
+                                pin: 0,
+                                dither: None,
+                                power_limit: None,
+                                slew_limit: None,
+                            })
                         }
                         NodeKind::Fixture => Box::new(lp_model::nodes::fixture::FixtureConfig {
                             output_spec: lp_model::NodeSpecifier::from(""),
@@ -168,7 +209,14 @@ impl ProjectRuntime {
                             transform: [[0.0; 4]; 4],
                             brightness: None,
                             gamma_correction: None,
+                            universe: None,
+                            channel_offset: None,
+                            enabled: None,
+                            color_correction: None,
+                            mask: None,
+                            white_extraction: None,
                         }),
+                        NodeKind::Group => Box::new(lp_model::nodes::group::GroupConfig::default()),
                     };
 
                     let entry = NodeEntry {
@@ -192,9 +240,11 @@ impl ProjectRuntime {
 
     /// Initialize all nodes in dependency order
     pub fn init_nodes(&mut self) -> Result<(), Error> {
-        // Initialize in order: textures → shaders → fixtures → outputs
+        // Initialize in order: textures → groups → shaders → fixtures → outputs
+        // (groups and shaders resolve their targets, so those must exist first)
         let init_order = [
             NodeKind::Texture,
+            NodeKind::Group,
             NodeKind::Shader,
             NodeKind::Fixture,
             NodeKind::Output,
@@ -323,6 +373,31 @@ impl ProjectRuntime {
                     None
                 };
 
+                let group_config = if node_kind == NodeKind::Group {
+                    let entry = self.nodes.get(&handle).ok_or_else(|| Error::Other {
+                        message: format!("Node handle {} not found", handle.as_i32()),
+                    })?;
+                    // Reload config from filesystem (workaround for trait object limitation)
+                    let node_json_path = entry.path.join("node.json");
+                    let data = self
+                        .fs
+                        .borrow()
+                        .read_file(node_json_path.as_path())
+                        .map_err(|e| Error::Io {
+                            path: node_json_path.as_str().to_string(),
+                            details: format!("Failed to read: {e:?}"),
+                        })?;
+                    Some(
+                        lp_model::json::from_slice::<lp_model::nodes::group::GroupConfig>(&data)
+                            .map_err(|e| Error::Parse {
+                                file: node_json_path.as_str().to_string(),
+                                error: format!("Failed to parse group config: {e}"),
+                            })?,
+                    )
+                } else {
+                    None
+                };
+
                 // Create runtime based on kind
                 let mut runtime: Box<dyn NodeRuntime> = match node_kind {
                     NodeKind::Texture => {
@@ -353,6 +428,13 @@ impl ProjectRuntime {
                         }
                         Box::new(fixture_runtime)
                     }
+                    NodeKind::Group => {
+                        let mut group_runtime = GroupRuntime::new(handle);
+                        if let Some(config) = group_config {
+                            group_runtime.set_config(config);
+                        }
+                        Box::new(group_runtime)
+                    }
                 };
 
                 // Create init context and initialize (needs immutable borrow of self)
@@ -446,16 +528,80 @@ impl ProjectRuntime {
         }
     }
 
+    /// Destroy every node, releasing what they hold outside the runtime
+    ///
+    /// Output channels are closed, so the project can be loaded again with the same
+    /// output provider. Also called when the runtime is dropped. Recycled render
+    /// buffers are freed too; `load_nodes` and `init_nodes` start the project over.
+    pub fn unload(&mut self) -> Result<(), Error> {
+        let handles: Vec<NodeHandle> = self.nodes.keys().copied().collect();
+        let mut first_error = None;
+        for handle in handles {
+            // Keep going so one failing node doesn't leave the others open
+            if let Err(e) = self.destroy_node(handle) {
+                first_error.get_or_insert(e);
+            }
+        }
+        self.frame_pool = FrameBufferPool::new();
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Advance to next frame, taking the delta from a clock, and render
+    ///
+    /// The delta is the time since the previous `tick_clock` call (zero on the first),
+    /// so the engine's frame time follows the clock exactly, as with a
+    /// [`lp_shared::time::TestClock`] shared with other consumers.
+    pub fn tick_clock(&mut self, clock: &dyn TimeProvider) -> Result<(), Error> {
+        let now = clock.now_ms();
+        let delta_ms = self.last_clock_ms.map_or(0, |last| {
+            now.saturating_sub(last).min(u32::MAX as u64) as u32
+        });
+        self.last_clock_ms = Some(now);
+        self.tick(delta_ms)
+    }
+
     /// Advance to next frame and render
     ///
     /// Updates frame ID and frame time, then renders the frame.
     /// `delta_ms` is the time elapsed since the last frame in milliseconds.
     pub fn tick(&mut self, delta_ms: u32) -> Result<(), Error> {
+        self.last_synced = None;
+        let total_ms = self.frame_time.total_ms + delta_ms;
+        let stamp = FrameStamp::new(self.frame_id.next(), u64::from(total_ms));
+        self.render_frame(FrameTime::new(delta_ms, total_ms), stamp)
+    }
+
+    /// Render the frame `stamp` of a clock shared with other devices
+    ///
+    /// Devices ticked to the same stamps render the same content: the frame time is
+    /// the stamp's time wrapped to `SYNCED_TIME_PERIOD_MS`, and outputs are written
+    /// under the stamp. The runtime's own `frame_id` still just increments,
+    /// since it versions node changes for clients. Returns `false` without rendering
+    /// when `stamp` is not after the last synced frame, i.e. the clock has not reached
+    /// a new frame yet.
+    pub fn tick_at(&mut self, stamp: FrameStamp) -> Result<bool, Error> {
+        let delta_ms = match self.last_synced {
+            Some(last) if !stamp.frame.is_after(last.frame) => return Ok(false),
+            Some(last) => stamp
+                .time_ms
+                .saturating_sub(last.time_ms)
+                .min(u32::MAX as u64) as u32,
+            None => 0,
+        };
+        self.last_synced = Some(stamp);
+        let total_ms = (stamp.time_ms % SYNCED_TIME_PERIOD_MS) as u32;
+        self.render_frame(FrameTime::new(delta_ms, total_ms), stamp)?;
+        Ok(true)
+    }
+
+    /// Advance to the next frame at `frame_time` and render it, writing outputs under
+    /// `stamp`
+    fn render_frame(&mut self, frame_time: FrameTime, stamp: FrameStamp) -> Result<(), Error> {
         // Update frame ID and time
         let old_frame_id = self.frame_id;
         self.frame_id = self.frame_id.next();
-        self.frame_time.total_ms += delta_ms;
-        self.frame_time.delta_ms = delta_ms;
+        self.frame_time = frame_time;
+        let delta_ms = frame_time.delta_ms;
 
         log::debug!(
             "ProjectRuntime::tick: Frame {} -> {} (time: {}ms total, {}ms delta)",
@@ -465,6 +611,8 @@ impl ProjectRuntime {
             delta_ms
         );
 
+        self.apply_automation();
+
         // Render the frame
         // Render all fixtures
         let fixture_handles: Vec<NodeHandle> = self
@@ -479,6 +627,8 @@ impl ProjectRuntime {
             .collect();
 
         for handle in fixture_handles {
+            let enabled = Self::fixture_enabled(&self.nodes, handle);
+
             // Render fixture - need to handle borrowing carefully
             // The issue: runtime.render() needs &mut runtime and &mut ctx
             // But runtime is inside ctx.nodes, so we can't have both borrows
@@ -490,6 +640,7 @@ impl ProjectRuntime {
                     frame_id: self.frame_id,
                     frame_time: self.frame_time,
                     output_provider: Rc::clone(&self.output_provider),
+                    frame_pool: &mut self.frame_pool,
                 };
 
                 // Get runtime and render in one go
@@ -509,7 +660,17 @@ impl ProjectRuntime {
                         let runtime_ptr: *mut dyn NodeRuntime = runtime.as_mut();
                         // SAFETY: runtime_ptr is valid for the duration of this block
                         // We're not storing it or using it after the block
-                        unsafe { (*runtime_ptr).render(&mut ctx) }
+                        if enabled {
+                            unsafe { (*runtime_ptr).render(&mut ctx) }
+                        } else {
+                            // Disabled fixtures go dark instead of rendering
+                            match unsafe { (*runtime_ptr).as_any_mut() }
+                                .downcast_mut::<FixtureRuntime>()
+                            {
+                                Some(fixture) => fixture.blank(&mut ctx),
+                                None => Ok(()),
+                            }
+                        }
                     } else {
                         Ok(())
                     }
@@ -527,6 +688,8 @@ impl ProjectRuntime {
             }
         }
 
+        self.output_provider.borrow().begin_frame(stamp);
+
         // Flush outputs with state_ver == frame_id (outputs that were written to this frame)
         let output_handles: Vec<NodeHandle> = self
             .nodes
@@ -547,6 +710,7 @@ impl ProjectRuntime {
                     frame_id: self.frame_id,
                     frame_time: self.frame_time,
                     output_provider: Rc::clone(&self.output_provider),
+                    frame_pool: &mut self.frame_pool,
                 };
 
                 if let Some(entry) = ctx.nodes.get_mut(&handle) {
@@ -572,6 +736,59 @@ impl ProjectRuntime {
         Ok(())
     }
 
+    /// Whether a fixture renders: it and the texture it samples must both be enabled
+    ///
+    /// A disabled texture keeps its pixels, so its fixtures are muted here rather than
+    /// left showing a frozen image.
+    fn fixture_enabled(nodes: &BTreeMap<NodeHandle, NodeEntry>, handle: NodeHandle) -> bool {
+        let Some(entry) = nodes.get(&handle) else {
+            return false;
+        };
+        let texture_enabled = entry
+            .runtime
+            .as_ref()
+            .and_then(|runtime| runtime.as_any().downcast_ref::<FixtureRuntime>())
+            .and_then(FixtureRuntime::get_texture_handle)
+            .and_then(|texture| nodes.get(&texture.as_node_handle()))
+            .is_none_or(|texture| texture.config.enabled());
+        entry.config.enabled() && texture_enabled
+    }
+
+    /// Usage of the render buffer pool
+    pub fn frame_pool_stats(&self) -> FrameBufferPoolStats {
+        self.frame_pool.stats()
+    }
+
+    /// Push automated parameter values for the current frame into node runtimes
+    ///
+    /// Failures are logged rather than returned so a bad track doesn't stop rendering.
+    fn apply_automation(&mut self) {
+        for track in &self.automation {
+            let Some(value) = track.value_at(self.frame_id) else {
+                continue;
+            };
+            let path = LpPathBuf::from(track.node.as_str());
+            let runtime = self
+                .nodes
+                .values_mut()
+                .find(|entry| entry.path == path)
+                .and_then(|entry| entry.runtime.as_mut());
+            let result = match runtime {
+                Some(runtime) => runtime.set_param(&track.param, value),
+                None => Err(Error::NotFound {
+                    path: path.as_str().to_string(),
+                }),
+            };
+            if let Err(e) = result {
+                log::warn!(
+                    "Automation for {} `{}` not applied: {e}",
+                    track.node.as_str(),
+                    track.param
+                );
+            }
+        }
+    }
+
     /// Handle filesystem changes
     ///
     /// Processes filesystem change events and updates affected nodes.
@@ -617,28 +834,14 @@ impl ProjectRuntime {
             // Extract node path from file path
             if let Some(node_path) = self.extract_node_path_from_file_path(change.path.as_path()) {
                 if let Ok(handle) = self.handle_for_path(node_path.as_path()) {
-                    // Destroy runtime if it exists
-                    if let Some(entry) = self.nodes.get_mut(&handle) {
-                        if let Some(mut runtime) = entry.runtime.take() {
-                            runtime.destroy()?;
-                        }
-                    }
-                    // Remove node
-                    self.nodes.remove(&handle);
+                    self.destroy_node(handle)?;
                 }
             }
         } else if self.is_node_directory_path(change.path.as_path()) {
             // Node directory was deleted
             if let Some(node_path) = self.extract_node_path_from_file_path(change.path.as_path()) {
                 if let Ok(handle) = self.handle_for_path(node_path.as_path()) {
-                    // Destroy runtime if it exists
-                    if let Some(entry) = self.nodes.get_mut(&handle) {
-                        if let Some(mut runtime) = entry.runtime.take() {
-                            runtime.destroy()?;
-                        }
-                    }
-                    // Remove node
-                    self.nodes.remove(&handle);
+                    self.destroy_node(handle)?;
                 }
             }
         }
@@ -797,6 +1000,19 @@ impl ProjectRuntime {
             || path.has_suffix(".texture")
             || path.has_suffix(".output")
             || path.has_suffix(".fixture")
+            || path.has_suffix(".group")
+    }
+
+    /// Destroy a node's runtime (if initialized) and remove the node
+    fn destroy_node(&mut self, handle: NodeHandle) -> Result<(), Error> {
+        let Some(mut entry) = self.nodes.remove(&handle) else {
+            return Ok(());
+        };
+        if let Some(mut runtime) = entry.runtime.take() {
+            let ctx = InitContext::new(self, &entry.path)?;
+            runtime.destroy(&ctx)?;
+        }
+        Ok(())
     }
 
     /// Load a single node by path
@@ -869,14 +1085,14 @@ impl ProjectRuntime {
         // Collect changes and details
         for (handle, entry) in &self.nodes {
             // Check for changes since since_frame
-            if entry.config_ver.as_i64() > since_frame.as_i64() {
+            if entry.config_ver.is_after(since_frame) {
                 node_changes.push(NodeChange::ConfigUpdated {
                     handle: *handle,
                     config_ver: entry.config_ver,
                 });
             }
 
-            if entry.state_ver.as_i64() > since_frame.as_i64() {
+            if entry.state_ver.is_after(since_frame) {
                 node_changes.push(NodeChange::StateUpdated {
                     handle: *handle,
                     state_ver: entry.state_ver,
@@ -897,8 +1113,7 @@ impl ProjectRuntime {
             // Always include status - if it changed since since_frame, or if this is the first sync (since_frame is default)
             // For first sync (since_frame == 0), we want to send status for all nodes
             // For subsequent syncs, we only send if status changed
-            if entry.status_ver.as_i64() > since_frame.as_i64() || since_frame == FrameId::default()
-            {
+            if entry.status_ver.is_after(since_frame) || since_frame == FrameId::default() {
                 node_changes.push(NodeChange::StatusChanged {
                     handle: *handle,
                     status: api_status,
@@ -906,9 +1121,7 @@ impl ProjectRuntime {
             }
 
             // Check if node was created after since_frame
-            if entry.config_ver.as_i64() > since_frame.as_i64()
-                && entry.config_ver == entry.state_ver
-            {
+            if entry.config_ver.is_after(since_frame) && entry.config_ver == entry.state_ver {
                 node_changes.push(NodeChange::Created {
                     handle: *handle,
                     path: entry.path.clone(),
@@ -1055,6 +1268,21 @@ impl ProjectRuntime {
                             })
                         }
                     }
+                    NodeKind::Group => {
+                        if let Some(group_runtime) = entry
+                            .runtime
+                            .as_ref()
+                            .and_then(|runtime| runtime.as_any().downcast_ref::<GroupRuntime>())
+                        {
+                            NodeState::Group(group_runtime.get_state())
+                        } else {
+                            NodeState::Group(lp_model::nodes::group::GroupState {
+                                layer_data: Vec::new(),
+                                width: 0,
+                                height: 0,
+                            })
+                        }
+                    }
                 };
 
                 // Clone config based on kind - extract from runtime if available
@@ -1070,18 +1298,30 @@ impl ProjectRuntime {
                                     Box::new(lp_model::nodes::texture::TextureConfig {
                                         width: 0,
                                         height: 0,
+                                        image_path: None,
+                                        fill_color: None,
+                                        filter: None,
+                                        enabled: None,
                                     })
                                 }
                             } else {
                                 Box::new(lp_model::nodes::texture::TextureConfig {
                                     width: 0,
                                     height: 0,
+                                    image_path: None,
+                                    fill_color: None,
+                                    filter: None,
+                                    enabled: None,
                                 })
                             }
                         } else {
                             Box::new(lp_model::nodes::texture::TextureConfig {
                                 width: 0,
                                 height: 0,
+                                image_path: None,
+                                fill_color: None,
+                                filter: None,
+                                enabled: None,
                             })
                         }
                     }
@@ -1112,15 +1352,26 @@ impl ProjectRuntime {
                                 } else {
                                     Box::new(lp_model::nodes::output::OutputConfig::GpioStrip {
                                         pin: 0,
+                                        dither: None,
+                                        power_limit: None,
+                                        slew_limit: None,
                                     })
                                 }
                             } else {
                                 Box::new(lp_model::nodes::output::OutputConfig::GpioStrip {
                                     pin: 0,
+                                    dither: None,
+                                    power_limit: None,
+                                    slew_limit: None,
                                 })
                             }
                         } else {
-                            Box::new(lp_model::nodes::output::OutputConfig::GpioStrip { pin: 0 })
+                            Box::new(lp_model::nodes::output::OutputConfig::GpioStrip {
+                                pin: 0,
+                                dither: None,
+                                power_limit: None,
+                                slew_limit: None,
+                            })
                         }
                     }
                     NodeKind::Fixture => {
@@ -1143,6 +1394,12 @@ impl ProjectRuntime {
                                         transform: [[0.0; 4]; 4],
                                         brightness: None,
                                         gamma_correction: None,
+                                        universe: None,
+                                        channel_offset: None,
+                                        enabled: None,
+                                        color_correction: None,
+                                        mask: None,
+                                        white_extraction: None,
                                     })
                                 }
                             } else {
@@ -1157,6 +1414,12 @@ impl ProjectRuntime {
                                     transform: [[0.0; 4]; 4],
                                     brightness: None,
                                     gamma_correction: None,
+                                    universe: None,
+                                    channel_offset: None,
+                                    enabled: None,
+                                    color_correction: None,
+                                    mask: None,
+                                    white_extraction: None,
                                 })
                             }
                         } else {
@@ -1171,9 +1434,24 @@ impl ProjectRuntime {
                                 transform: [[0.0; 4]; 4],
                                 brightness: None,
                                 gamma_correction: None,
+                                universe: None,
+                                channel_offset: None,
+                                enabled: None,
+                                color_correction: None,
+                                mask: None,
+                                white_extraction: None,
                             })
                         }
                     }
+                    NodeKind::Group => Box::new(
+                        entry
+                            .runtime
+                            .as_ref()
+                            .and_then(|runtime| runtime.as_any().downcast_ref::<GroupRuntime>())
+                            .and_then(GroupRuntime::get_config)
+                            .cloned()
+                            .unwrap_or_default(),
+                    ),
                 };
 
                 node_details.insert(
@@ -1197,13 +1475,17 @@ impl ProjectRuntime {
     }
 }
 
+impl Drop for ProjectRuntime {
+    fn drop(&mut self) {
+        if let Err(e) = self.unload() {
+            log::warn!("Failed to unload project: {e}");
+        }
+    }
+}
+
 /// Init context implementation
 struct InitContext<'a> {
     runtime: &'a ProjectRuntime,
-    #[allow(
-        dead_code,
-        reason = "Used for chroot filesystem creation, may be needed for future features"
-    )]
     node_path: &'a LpPathBuf,
     node_fs: alloc::rc::Rc<core::cell::RefCell<dyn LpFs>>,
 }
@@ -1229,6 +1511,10 @@ impl<'a> InitContext<'a> {
 }
 
 impl<'a> crate::runtime::contexts::NodeInitContext for InitContext<'a> {
+    fn node_path(&self) -> &LpPath {
+        self.node_path.as_path()
+    }
+
     fn resolve_node(&self, spec: &lp_model::NodeSpecifier) -> Result<lp_model::NodeHandle, Error> {
         let spec_path = spec.as_str();
         let node_path = if spec_path.starts_with('/') {
@@ -1316,6 +1602,30 @@ impl<'a> crate::runtime::contexts::NodeInitContext for InitContext<'a> {
         Ok(crate::runtime::contexts::TextureHandle::new(handle))
     }
 
+    fn resolve_layer_target(
+        &self,
+        spec: &lp_model::NodeSpecifier,
+    ) -> Result<crate::runtime::contexts::TextureHandle, Error> {
+        let handle = self.resolve_node(spec)?;
+        let entry = self
+            .runtime
+            .nodes
+            .get(&handle)
+            .ok_or_else(|| Error::NotFound {
+                path: spec.as_str().to_string(),
+            })?;
+
+        if !matches!(entry.kind, NodeKind::Texture | NodeKind::Group) {
+            return Err(Error::WrongNodeKind {
+                specifier: spec.as_str().to_string(),
+                expected: lp_model::NodeKind::Texture,
+                actual: entry.kind,
+            });
+        }
+
+        Ok(crate::runtime::contexts::TextureHandle::new(handle))
+    }
+
     fn get_node_fs(&self) -> &dyn lp_shared::fs::LpFs {
         // SAFETY: We're returning a reference from a RefCell borrow, but the trait only allows
         // immutable access and we're not holding the borrow across any potential panics.
@@ -1329,6 +1639,35 @@ impl<'a> crate::runtime::contexts::NodeInitContext for InitContext<'a> {
         // and we're not holding the borrow across any potential panics
         unsafe { &*self.runtime.output_provider.as_ptr() }
     }
+
+    fn output_byte_count(&self) -> u32 {
+        let Ok(own_handle) = self.runtime.handle_for_path(self.node_path.as_path()) else {
+            return 0;
+        };
+        let Some(own_config) = self.runtime.nodes.get(&own_handle).and_then(|entry| {
+            entry
+                .config
+                .as_any()
+                .downcast_ref::<lp_model::nodes::output::OutputConfig>()
+        }) else {
+            return 0;
+        };
+        let own_output = crate::runtime::contexts::OutputHandle::new(own_handle);
+        self.runtime
+            .nodes
+            .values()
+            .filter(|entry| entry.kind == NodeKind::Fixture)
+            .filter_map(|entry| entry.runtime.as_ref())
+            .filter_map(|runtime| runtime.as_any().downcast_ref::<FixtureRuntime>())
+            .filter(|fixture| fixture.get_output_handle() == Some(own_output))
+            .map(|fixture| fixture.channel_end(own_config))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn rng(&self) -> NodeRng {
+        NodeRng::for_node(self.runtime.seed, self.node_path.as_str())
+    }
 }
 
 /// Render context implementation
@@ -1337,6 +1676,7 @@ struct RenderContextImpl<'a> {
     frame_id: FrameId,
     frame_time: FrameTime,
     output_provider: Rc<RefCell<dyn OutputProvider>>,
+    frame_pool: &'a mut FrameBufferPool,
 }
 
 impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
@@ -1351,6 +1691,7 @@ impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
             self.frame_id,
             self.frame_time,
             Rc::clone(&self.output_provider),
+            self.frame_pool,
         )?;
 
         // Get texture runtime
@@ -1362,6 +1703,18 @@ impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
                 path: format!("texture-{}", node_handle.as_i32()),
             })?;
 
+        // A group's layer stands in for a texture while its members render into it
+        if entry.kind == NodeKind::Group {
+            return entry
+                .runtime
+                .as_ref()
+                .and_then(|runtime| runtime.as_any().downcast_ref::<GroupRuntime>())
+                .and_then(GroupRuntime::layer)
+                .ok_or_else(|| Error::Other {
+                    message: "Group layer not initialized".to_string(),
+                });
+        }
+
         // Get texture from runtime
         if let Some(runtime) = &mut entry.runtime {
             if let Some(tex_runtime) = runtime
@@ -1394,6 +1747,7 @@ impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
             self.frame_id,
             self.frame_time,
             Rc::clone(&self.output_provider),
+            self.frame_pool,
         )?;
 
         // Get texture runtime
@@ -1405,6 +1759,18 @@ impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
                 path: format!("texture-{}", node_handle.as_i32()),
             })?;
 
+        // A group's layer stands in for a texture while its members render into it
+        if entry.kind == NodeKind::Group {
+            return entry
+                .runtime
+                .as_mut()
+                .and_then(|runtime| runtime.as_any_mut().downcast_mut::<GroupRuntime>())
+                .and_then(GroupRuntime::layer_mut)
+                .ok_or_else(|| Error::Other {
+                    message: "Group layer not initialized".to_string(),
+                });
+        }
+
         // Get mutable texture from runtime
         if let Some(runtime) = &mut entry.runtime {
             if let Some(tex_runtime) = runtime
@@ -1431,10 +1797,14 @@ impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
         self.frame_time.total_ms as f32 / 1000.0
     }
 
+    fn get_frame_id(&self) -> FrameId {
+        self.frame_id
+    }
+
     fn get_output(
         &mut self,
         handle: crate::runtime::contexts::OutputHandle,
-        _universe: u32,
+        universe: u32,
         start_ch: u32,
         ch_count: u32,
     ) -> Result<&mut [u8], Error> {
@@ -1468,7 +1838,8 @@ impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
                 .as_any_mut()
                 .downcast_mut::<crate::nodes::OutputRuntime>()
             {
-                Ok(output_runtime.get_buffer_mut(start_ch, ch_count))
+                let start = output_runtime.channel_start(universe, start_ch);
+                Ok(output_runtime.get_buffer_mut(start, ch_count))
             } else {
                 Err(Error::Other {
                     message: "Output runtime not found".to_string(),
@@ -1481,12 +1852,64 @@ impl<'a> crate::runtime::contexts::RenderContext for RenderContextImpl<'a> {
         }
     }
 
+    fn get_texture_filter(
+        &self,
+        handle: crate::runtime::contexts::TextureHandle,
+    ) -> Result<lp_model::nodes::texture::TextureFilter, Error> {
+        let node_handle = handle.as_node_handle();
+        let entry = self
+            .nodes
+            .get(&node_handle)
+            .ok_or_else(|| Error::NotFound {
+                path: format!("texture-{}", node_handle.as_i32()),
+            })?;
+
+        entry
+            .runtime
+            .as_ref()
+            .and_then(|runtime| runtime.as_any().downcast_ref::<TextureRuntime>())
+            .map(|texture_runtime| texture_runtime.filter())
+            .ok_or_else(|| Error::Other {
+                message: "Texture runtime not found".to_string(),
+            })
+    }
+
+    fn get_output_dither(
+        &self,
+        handle: crate::runtime::contexts::OutputHandle,
+    ) -> Result<lp_model::nodes::output::DitherMode, Error> {
+        let node_handle = handle.as_node_handle();
+        let entry = self
+            .nodes
+            .get(&node_handle)
+            .ok_or_else(|| Error::NotFound {
+                path: format!("output-{}", node_handle.as_i32()),
+            })?;
+
+        entry
+            .runtime
+            .as_ref()
+            .and_then(|runtime| {
+                runtime
+                    .as_any()
+                    .downcast_ref::<crate::nodes::OutputRuntime>()
+            })
+            .map(|output_runtime| output_runtime.dither_mode())
+            .ok_or_else(|| Error::Other {
+                message: "Output runtime not found".to_string(),
+            })
+    }
+
     fn output_provider(&self) -> &dyn OutputProvider {
         // We can't return a reference from RefCell borrow, so we need to use unsafe
         // SAFETY: This is safe because the trait only allows immutable access
         // and we're not holding the borrow across any potential panics
         unsafe { &*self.output_provider.as_ptr() }
     }
+
+    fn frame_pool(&mut self) -> &mut FrameBufferPool {
+        &mut *self.frame_pool
+    }
 }
 
 impl<'a> RenderContextImpl<'a> {
@@ -1496,12 +1919,70 @@ impl<'a> RenderContextImpl<'a> {
     /// 1. Finds all shader nodes that target this texture
     /// 2. Renders those shaders in render_order (lowest first)
     /// 3. Marks the texture as rendered
+    /// Whether every shader targeting a texture would redraw what it drew last time
+    ///
+    /// True when each shader is static (see [`crate::nodes::ShaderRuntime::needs_render`])
+    /// and neither its config nor the texture's has changed since it last rendered. If
+    /// any shader must render, they all do, since later shaders draw over earlier ones.
+    fn shaders_unchanged(
+        nodes: &BTreeMap<NodeHandle, NodeEntry>,
+        texture: NodeHandle,
+        shaders: &[(NodeHandle, i32)],
+    ) -> bool {
+        let Some(texture_entry) = nodes.get(&texture) else {
+            return false;
+        };
+        !shaders.is_empty()
+            && shaders.iter().all(|(handle, _)| {
+                let Some(entry) = nodes.get(handle) else {
+                    return false;
+                };
+                let rendered = entry.state_ver;
+                rendered != FrameId::default()
+                    && entry.config_ver < rendered
+                    && texture_entry.config_ver < rendered
+                    && entry
+                        .runtime
+                        .as_ref()
+                        .and_then(|runtime| {
+                            runtime
+                                .as_any()
+                                .downcast_ref::<crate::nodes::ShaderRuntime>()
+                        })
+                        .is_some_and(|shader| !shader.needs_render())
+            })
+    }
+
+    /// Size of the texture a group composites onto, following nested groups
+    ///
+    /// An enclosing group's layer is prepared before its members render, so its size
+    /// is current by the time a nested group asks for it.
+    fn group_target_size(
+        nodes: &BTreeMap<NodeHandle, NodeEntry>,
+        group: NodeHandle,
+    ) -> Option<(u32, u32)> {
+        let target = nodes
+            .get(&group)?
+            .runtime
+            .as_ref()?
+            .as_any()
+            .downcast_ref::<GroupRuntime>()?
+            .target()?;
+        let runtime = nodes.get(&target.as_node_handle())?.runtime.as_ref()?;
+        let texture = match runtime.as_any().downcast_ref::<TextureRuntime>() {
+            Some(tex_runtime) => tex_runtime.texture()?,
+            None => runtime.as_any().downcast_ref::<GroupRuntime>()?.layer()?,
+        };
+        Some((texture.width(), texture.height()))
+    }
+
     fn ensure_texture_rendered(
         nodes: &mut BTreeMap<NodeHandle, NodeEntry>,
         handle: crate::runtime::contexts::TextureHandle,
         frame_id: FrameId,
         frame_time: FrameTime,
         output_provider: Rc<RefCell<dyn OutputProvider>>,
+        frame_pool: &mut FrameBufferPool,
     ) -> Result<(), Error> {
         let node_handle = handle.as_node_handle();
 
@@ -1520,15 +2001,46 @@ impl<'a> RenderContextImpl<'a> {
                 );
                 return Ok(());
             }
+
+            // A disabled texture keeps whatever its shaders last drew
+            if !entry.config.enabled() {
+                return Ok(());
+            }
         }
 
+        let is_group = nodes
+            .get(&node_handle)
+            .is_some_and(|entry| entry.kind == NodeKind::Group);
+
         // Find all shader nodes that target this texture
         // Collect (handle, render_order) pairs for shaders targeting this texture
         let mut shader_handles: Vec<(NodeHandle, i32)> = Vec::new();
+        // Disabled shaders whose pixels are still in the texture
+        let mut blank_handles: Vec<NodeHandle> = Vec::new();
+        // Whether any group composites onto this texture, enabled or not
+        let mut has_groups = false;
 
         for (shader_handle, entry) in nodes.iter() {
+            // Groups draw in render_order alongside shaders
+            if entry.kind == NodeKind::Group && entry.status == NodeStatus::Ok {
+                if let Some(group_runtime) = entry
+                    .runtime
+                    .as_ref()
+                    .and_then(|runtime| runtime.as_any().downcast_ref::<GroupRuntime>())
+                    && group_runtime.targets_texture(handle)
+                {
+                    has_groups = true;
+                    if entry.config.enabled() {
+                        shader_handles.push((*shader_handle, group_runtime.render_order()));
+                    }
+                }
+                continue;
+            }
+
+            // Shaders in an error state still draw, but only their error color
+            let failed = matches!(entry.status, NodeStatus::Error(_));
             if entry.kind == NodeKind::Shader
-                && entry.status == NodeStatus::Ok
+                && (entry.status == NodeStatus::Ok || failed)
                 && entry.runtime.is_some()
             {
                 // Check if this shader targets our texture
@@ -1537,11 +2049,21 @@ impl<'a> RenderContextImpl<'a> {
                         .as_any()
                         .downcast_ref::<crate::nodes::ShaderRuntime>()
                     {
-                        if shader_runtime.targets_texture(handle) {
-                            // Get render_order from shader runtime
-                            let render_order = shader_runtime.render_order();
-                            shader_handles.push((*shader_handle, render_order));
+                        if !shader_runtime.targets_texture(handle) {
+                            continue;
+                        }
+                        if failed && !shader_runtime.has_error_color() {
+                            continue;
+                        }
+                        if !entry.config.enabled() {
+                            if shader_runtime.has_drawn() {
+                                blank_handles.push(*shader_handle);
+                            }
+                            continue;
                         }
+                        // Get render_order from shader runtime
+                        let render_order = shader_runtime.render_order();
+                        shader_handles.push((*shader_handle, render_order));
                     }
                 }
             }
@@ -1556,6 +2078,21 @@ impl<'a> RenderContextImpl<'a> {
             node_handle.as_i32()
         );
 
+        // Static shaders with unchanged inputs would draw the same pixels again, so the
+        // texture keeps its previous contents (and its state_ver, since nothing changed).
+        // Group layers are redrawn and composited every frame.
+        if !is_group
+            && !has_groups
+            && blank_handles.is_empty()
+            && Self::shaders_unchanged(nodes, node_handle, &shader_handles)
+        {
+            log::trace!(
+                "RenderContextImpl::ensure_texture_rendered: Texture {} unchanged, skipping its shaders",
+                node_handle.as_i32()
+            );
+            return Ok(());
+        }
+
         // Mark texture as rendering BEFORE calling shader.render() to prevent infinite recursion
         // When shader.render() calls get_texture_mut(), it will see state_ver >= frame_id
         // and skip re-rendering
@@ -1563,6 +2100,56 @@ impl<'a> RenderContextImpl<'a> {
             entry.state_ver = frame_id;
         }
 
+        // Compositing accumulates, so a texture with groups starts each frame from its own
+        // contents and a group's layer starts out transparent, at its target's size
+        if is_group {
+            let size = Self::group_target_size(nodes, node_handle);
+            if let Some(group_runtime) = nodes
+                .get_mut(&node_handle)
+                .and_then(|entry| entry.runtime.as_mut())
+                .and_then(|runtime| runtime.as_any_mut().downcast_mut::<GroupRuntime>())
+            {
+                let (width, height) = size.ok_or_else(|| Error::Other {
+                    message: format!(
+                        "Group {} target has no texture to size its layer",
+                        node_handle.as_i32()
+                    ),
+                })?;
+                group_runtime.prepare_layer(width, height)?;
+            }
+            // Disabled members already left no trace in the cleared layer
+            blank_handles.clear();
+        } else if has_groups
+            && let Some(tex_runtime) = nodes
+                .get_mut(&node_handle)
+                .and_then(|entry| entry.runtime.as_mut())
+                .and_then(|runtime| runtime.as_any_mut().downcast_mut::<TextureRuntime>())
+        {
+            tex_runtime.reset();
+        }
+
+        // Clear newly disabled shaders first, so enabled shaders can draw over the gap
+        for shader_handle in blank_handles {
+            let mut ctx = RenderContextImpl {
+                nodes,
+                frame_id,
+                frame_time,
+                output_provider: Rc::clone(&output_provider),
+                frame_pool,
+            };
+            if let Some(entry) = ctx.nodes.get_mut(&shader_handle) {
+                if let Some(runtime) = entry.runtime.as_mut() {
+                    // Same raw pointer workaround as rendering below
+                    let runtime_ptr: *mut dyn NodeRuntime = runtime.as_mut();
+                    if let Some(shader) =
+                        unsafe { (*runtime_ptr).as_any_mut() }.downcast_mut::<ShaderRuntime>()
+                    {
+                        shader.blank(&mut ctx)?;
+                    }
+                }
+            }
+        }
+
         // Render each shader that targets this texture
         for (shader_handle, _) in shader_handles {
             log::trace!(
@@ -1570,12 +2157,31 @@ impl<'a> RenderContextImpl<'a> {
                 shader_handle.as_i32(),
                 node_handle.as_i32()
             );
+            // A group's members render into its layer before it is composited
+            let member_is_group = nodes
+                .get(&shader_handle)
+                .is_some_and(|entry| entry.kind == NodeKind::Group);
+            let failed = nodes
+                .get(&shader_handle)
+                .is_some_and(|entry| matches!(entry.status, NodeStatus::Error(_)));
+            if member_is_group {
+                Self::ensure_texture_rendered(
+                    nodes,
+                    crate::runtime::contexts::TextureHandle::new(shader_handle),
+                    frame_id,
+                    frame_time,
+                    Rc::clone(&output_provider),
+                    frame_pool,
+                )?;
+            }
+
             // Create RenderContext for each shader render
             let mut ctx = RenderContextImpl {
                 nodes,
                 frame_id,
                 frame_time,
                 output_provider: Rc::clone(&output_provider),
+                frame_pool,
             };
 
             // Get shader runtime and render
@@ -1590,7 +2196,11 @@ impl<'a> RenderContextImpl<'a> {
                         let runtime_ptr: *mut dyn NodeRuntime = runtime.as_mut();
                         // SAFETY: runtime_ptr is valid for the duration of this block
                         // We're not storing it or using it after the block
-                        unsafe { (*runtime_ptr).render(&mut ctx) }
+                        let runtime = unsafe { &mut *runtime_ptr };
+                        match runtime.as_any_mut().downcast_mut::<ShaderRuntime>() {
+                            Some(shader) if failed => shader.draw_error_color(&mut ctx),
+                            _ => runtime.render(&mut ctx),
+                        }
                     } else {
                         Ok(())
                     }
@@ -1610,7 +2220,7 @@ impl<'a> RenderContextImpl<'a> {
                 Err(e) => {
                     // Check if this is a shader execution error
                     let error_msg = format!("{e}");
-                    if error_msg.contains("Shader execution failed") {
+                    if member_is_group || error_msg.contains("Shader execution failed") {
                         // Update shader status to Error
                         if let Some(entry) = nodes.get_mut(&shader_handle) {
                             entry.status = NodeStatus::Error(error_msg.clone());
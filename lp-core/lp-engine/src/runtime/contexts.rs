@@ -1,6 +1,10 @@
 use crate::error::Error;
 use crate::output::OutputProvider;
-use lp_model::{NodeHandle, NodeSpecifier};
+use crate::runtime::frame_pool::FrameBufferPool;
+use crate::runtime::rng::NodeRng;
+use lp_model::nodes::output::DitherMode;
+use lp_model::nodes::texture::TextureFilter;
+use lp_model::{FrameId, LpPath, NodeHandle, NodeSpecifier};
 use lp_shared::fs::LpFs;
 
 /// Handle for resolved texture nodes
@@ -33,6 +37,9 @@ impl OutputHandle {
 
 /// Context for node initialization
 pub trait NodeInitContext {
+    /// Get the path of the node being initialized
+    fn node_path(&self) -> &LpPath;
+
     /// Resolve a node specifier to a node handle (common method)
     fn resolve_node(&self, spec: &NodeSpecifier) -> Result<NodeHandle, Error>;
 
@@ -42,11 +49,26 @@ pub trait NodeInitContext {
     /// Resolve a texture node specifier to a handle
     fn resolve_texture(&self, spec: &NodeSpecifier) -> Result<TextureHandle, Error>;
 
+    /// Resolve what a shader or group draws into: a texture, or a group's layer
+    fn resolve_layer_target(&self, spec: &NodeSpecifier) -> Result<TextureHandle, Error>;
+
     /// Get filesystem for this node
     fn get_node_fs(&self) -> &dyn LpFs;
 
     /// Get output provider
     fn output_provider(&self) -> &dyn OutputProvider;
+
+    /// Bytes the initialized fixtures write to this (output) node's channel buffer
+    ///
+    /// The end of the furthest channel of any fixture targeting the node, or 0 if no
+    /// fixture with lamps targets it.
+    fn output_byte_count(&self) -> u32;
+
+    /// Get a random number generator for this node
+    ///
+    /// Seeded from the project seed and the node path, so the node draws the same
+    /// sequence every time the project is loaded with the same seed.
+    fn rng(&self) -> NodeRng;
 }
 
 use lp_shared::Texture;
@@ -59,9 +81,15 @@ pub trait RenderContext {
     /// Get mutable texture (triggers lazy rendering if needed)
     fn get_texture_mut(&mut self, handle: TextureHandle) -> Result<&mut Texture, Error>;
 
+    /// Get the filter configured on a texture node
+    fn get_texture_filter(&self, handle: TextureHandle) -> Result<TextureFilter, Error>;
+
     /// Get current frame time in seconds
     fn get_time(&self) -> f32;
 
+    /// Get the id of the frame being rendered
+    fn get_frame_id(&self) -> FrameId;
+
     /// Get output buffer slice
     fn get_output(
         &mut self,
@@ -71,6 +99,12 @@ pub trait RenderContext {
         ch_count: u32,
     ) -> Result<&mut [u8], Error>;
 
+    /// Get the dither mode configured on an output node
+    fn get_output_dither(&self, handle: OutputHandle) -> Result<DitherMode, Error>;
+
     /// Get output provider
     fn output_provider(&self) -> &dyn OutputProvider;
+
+    /// Get the pool of reusable per-frame buffers
+    fn frame_pool(&mut self) -> &mut FrameBufferPool;
 }
@@ -0,0 +1,118 @@
+//! Reusable per-frame render buffers
+//!
+//! Nodes that need scratch buffers every frame (e.g. fixture channel accumulators) take
+//! them from the project's [`FrameBufferPool`] and recycle them when done, rather than
+//! allocating and freeing them each frame. On small heaps (ESP32) that churn fragments
+//! memory. Every buffer handed out has room for the largest buffer seen so far, so after
+//! the first frame the pool stops allocating.
+
+use alloc::vec::Vec;
+use lp_glsl_builtins::glsl::q32::types::q32::Q32;
+
+/// Pool of Q32 buffers shared by the nodes of a project
+#[derive(Debug, Default)]
+pub struct FrameBufferPool {
+    /// Buffers waiting to be reused
+    idle: Vec<Vec<Q32>>,
+    /// Largest length any recycled buffer has had
+    buffer_len: usize,
+    /// Allocations made for pooled buffers
+    allocations: u32,
+    /// Buffers handed out from `idle`
+    reuses: u64,
+}
+
+/// Snapshot of a [`FrameBufferPool`]'s usage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameBufferPoolStats {
+    /// Heap allocations made by the pool: new buffers, plus idle buffers grown to
+    /// `buffer_len`. Growth by the node using a buffer isn't counted, but raises
+    /// `buffer_len` so it doesn't happen again.
+    pub allocations: u32,
+    /// Buffers handed out without allocating
+    pub reuses: u64,
+    /// Buffers currently waiting in the pool
+    pub idle: usize,
+    /// Capacity every buffer is sized to (the largest buffer length seen so far)
+    pub buffer_len: usize,
+}
+
+impl FrameBufferPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take an empty buffer with capacity for at least the largest buffer seen so far
+    pub fn take(&mut self) -> Vec<Q32> {
+        match self.idle.pop() {
+            Some(mut buffer) => {
+                buffer.clear();
+                if buffer.capacity() < self.buffer_len {
+                    buffer.reserve_exact(self.buffer_len);
+                    self.allocations += 1;
+                } else {
+                    self.reuses += 1;
+                }
+                buffer
+            }
+            None => {
+                self.allocations += 1;
+                Vec::with_capacity(self.buffer_len)
+            }
+        }
+    }
+
+    /// Return a buffer taken with [`FrameBufferPool::take`]
+    ///
+    /// A buffer longer than any seen before raises the size future buffers are given.
+    pub fn recycle(&mut self, buffer: Vec<Q32>) {
+        self.buffer_len = self.buffer_len.max(buffer.len());
+        self.idle.push(buffer);
+    }
+
+    /// Current usage
+    pub fn stats(&self) -> FrameBufferPoolStats {
+        FrameBufferPoolStats {
+            allocations: self.allocations,
+            reuses: self.reuses,
+            idle: self.idle.len(),
+            buffer_len: self.buffer_len,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_reuses_buffers_sized_to_largest() {
+        let mut pool = FrameBufferPool::new();
+
+        // First frame: a small and a large fixture
+        let mut small = pool.take();
+        small.resize(10, Q32::ZERO);
+        let mut large = pool.take();
+        large.resize(300, Q32::ZERO);
+        pool.recycle(small);
+        pool.recycle(large);
+        let warm = pool.stats();
+        assert_eq!(warm.buffer_len, 300);
+        assert_eq!(warm.idle, 2);
+
+        // Later frames: whichever buffer a fixture gets, it fits without growing.
+        // The small buffer is grown once to the largest size, then never again.
+        for _ in 0..10 {
+            let mut a = pool.take();
+            a.resize(10, Q32::ZERO);
+            let mut b = pool.take();
+            b.resize(300, Q32::ZERO);
+            pool.recycle(a);
+            pool.recycle(b);
+        }
+        let stats = pool.stats();
+        assert_eq!(stats.allocations, warm.allocations + 1);
+        assert_eq!(stats.reuses, 19);
+        assert_eq!(stats.idle, 2);
+    }
+}
@@ -1,4 +1,8 @@
 pub mod contexts;
+pub mod frame_pool;
 pub mod frame_time;
+pub mod rng;
 
 pub use contexts::{NodeInitContext, OutputHandle, RenderContext, TextureHandle};
+pub use frame_pool::{FrameBufferPool, FrameBufferPoolStats};
+pub use rng::NodeRng;
@@ -0,0 +1,92 @@
+//! Seedable random number generation for nodes
+//!
+//! Each node gets its own generator derived from the project seed and the node
+//! path, so a node draws the same sequence on every run regardless of the order
+//! nodes are initialized in.
+
+/// Seed used when the project config doesn't set one
+pub const DEFAULT_PROJECT_SEED: u64 = 0;
+
+/// Small, fast deterministic RNG (SplitMix64)
+///
+/// Not suitable for cryptography; intended for noise and random effects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRng {
+    state: u64,
+}
+
+impl NodeRng {
+    /// Create a generator from a raw seed
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Create the generator for a node from the project seed and the node path
+    pub fn for_node(project_seed: u64, node_path: &str) -> Self {
+        // FNV-1a hash of the path, mixed into the project seed
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in node_path.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        let mut rng = Self::new(project_seed ^ hash);
+        // Discard the first output so nearby seeds diverge immediately
+        rng.next_u64();
+        rng
+    }
+
+    /// Next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Next 32 random bits
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Next random value in `[0.0, 1.0)`
+    pub fn next_f32(&mut self) -> f32 {
+        // 24 bits fill the f32 mantissa exactly
+        (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let a: Vec<u64> = {
+            let mut rng = NodeRng::for_node(42, "/src/noise.shader");
+            (0..8).map(|_| rng.next_u64()).collect()
+        };
+        let b: Vec<u64> = {
+            let mut rng = NodeRng::for_node(42, "/src/noise.shader");
+            (0..8).map(|_| rng.next_u64()).collect()
+        };
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_seed_and_path_change_sequence() {
+        let base = NodeRng::for_node(42, "/src/a.fixture").next_u64();
+        assert_ne!(base, NodeRng::for_node(43, "/src/a.fixture").next_u64());
+        assert_ne!(base, NodeRng::for_node(42, "/src/b.fixture").next_u64());
+    }
+
+    #[test]
+    fn test_next_f32_in_unit_range() {
+        let mut rng = NodeRng::new(7);
+        for _ in 0..1000 {
+            let v = rng.next_f32();
+            assert!((0.0..1.0).contains(&v), "{v}");
+        }
+    }
+}
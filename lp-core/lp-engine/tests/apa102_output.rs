@@ -0,0 +1,73 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+use lp_shared::output::apa102::{Apa102OutputProvider, SpiSink};
+
+/// Fake SPI bus that records every transfer
+#[derive(Default)]
+struct FakeSpi {
+    transfers: Vec<Vec<u8>>,
+}
+
+impl SpiSink for FakeSpi {
+    type Error = ();
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), ()> {
+        self.transfers.push(bytes.to_vec());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_apa102_output_frame() {
+    // ---------------------------------------------------------------------------------------------
+    // Arrange
+    //
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    builder.shader_basic(&texture_path);
+    let output_path = builder.output().gpio_pin(18).apa102(19).add(&mut builder);
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(Apa102OutputProvider::new(
+        MemoryOutputProvider::new(),
+        FakeSpi::default(),
+        18,
+        19,
+    )));
+
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    // ---------------------------------------------------------------------------------------------
+    // Act
+    //
+    // Shader: vec4(mod(time, 1.0), 0.0, 0.0, 1.0); 4ms gives a red value of 1
+    runtime.tick(4).unwrap();
+
+    // ---------------------------------------------------------------------------------------------
+    // Assert
+    //
+    let provider = output_provider.borrow();
+    let driver = provider.driver();
+    let transfers = &driver.sink().transfers;
+
+    // The strip went out over SPI, not through the inner provider
+    assert!(!provider.inner().is_pin_open(18));
+    assert_eq!(transfers.len(), 1);
+
+    // Start frame, then one LED at full 5-bit brightness as blue, green, red
+    let transfer = &transfers[0];
+    assert_eq!(&transfer[0..4], &[0, 0, 0, 0]);
+    assert_eq!(&transfer[4..8], &[0xFF, 0, 0, 1]);
+    assert!(transfer[8..].iter().all(|&b| b == 0));
+}
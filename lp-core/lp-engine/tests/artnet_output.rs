@@ -0,0 +1,119 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use core::net::{Ipv4Addr, SocketAddrV4};
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+use lp_shared::output::artnet::{ArtNetOutputProvider, UdpSink};
+
+/// Fake UDP transport that records every datagram
+#[derive(Default)]
+struct FakeUdp {
+    sent: Vec<(SocketAddrV4, Vec<u8>)>,
+}
+
+impl UdpSink for FakeUdp {
+    type Error = ();
+
+    fn send_to(&mut self, packet: &[u8], target: SocketAddrV4) -> Result<(), ()> {
+        self.sent.push((target, packet.to_vec()));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_artnet_output_frame() {
+    // ---------------------------------------------------------------------------------------------
+    // Arrange
+    //
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    builder.shader_basic(&texture_path);
+    let output_path = builder.output().artnet("10.0.0.5").add(&mut builder);
+    // Place the fixture in the second universe, 10 channels in
+    builder
+        .fixture(&output_path, &texture_path)
+        .universe(1)
+        .channel_offset(10)
+        .add(&mut builder);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(ArtNetOutputProvider::new(
+        MemoryOutputProvider::new(),
+        FakeUdp::default(),
+    )));
+
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    // ---------------------------------------------------------------------------------------------
+    // Act
+    //
+    // Shader: vec4(mod(time, 1.0), 0.0, 0.0, 1.0); 4ms gives a red value of 1
+    runtime.tick(4).unwrap();
+
+    // ---------------------------------------------------------------------------------------------
+    // Assert
+    //
+    let provider = output_provider.borrow();
+    let sink = provider.sink();
+    let target = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 5), 6454);
+
+    // Universe 0 (unused channels) and universe 1 are both sent
+    assert_eq!(sink.sent.len(), 2);
+    for (addr, packet) in &sink.sent {
+        assert_eq!(*addr, target);
+        assert_eq!(&packet[0..8], b"Art-Net\0");
+        assert_eq!(&packet[8..10], &[0x00, 0x50]);
+        assert_eq!(&packet[10..12], &[0, 14]);
+        assert_eq!(packet[12], 1, "first frame has sequence 1");
+    }
+
+    let (_, universe0) = &sink.sent[0];
+    assert_eq!(&universe0[14..16], &[0, 0]);
+    assert_eq!(&universe0[16..18], &512u16.to_be_bytes());
+    assert!(universe0[18..].iter().all(|&v| v == 0));
+
+    // Fixture RGB lands at channels 10..13 of universe 1 (padded to an even length)
+    let (_, universe1) = &sink.sent[1];
+    assert_eq!(&universe1[14..16], &[1, 0]);
+    assert_eq!(&universe1[16..18], &14u16.to_be_bytes());
+    let dmx = &universe1[18..];
+    assert!(dmx[..10].iter().all(|&v| v == 0));
+    assert_eq!(&dmx[10..13], &[1, 0, 0]);
+}
+
+#[test]
+fn test_strip_output_ignores_universe() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    builder.shader_basic(&texture_path);
+    let output_path = builder.output_basic();
+    // Universes only address Art-Net outputs; on a strip the offset alone applies
+    builder
+        .fixture(&output_path, &texture_path)
+        .universe(1)
+        .channel_offset(3)
+        .add(&mut builder);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    runtime.tick(4).unwrap();
+
+    let provider = output_provider.borrow();
+    let handle = provider.get_handle_for_pin(0).unwrap();
+    assert_eq!(provider.get_data(handle), Some(vec![0, 0, 0, 1, 0, 0]));
+}
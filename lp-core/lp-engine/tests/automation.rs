@@ -0,0 +1,68 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_model::project::{AutomationTrack, Keyframe};
+use lp_model::{FrameId, NodeSpecifier};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+mod common;
+use common::{pixel, start_runtime};
+
+#[test]
+fn test_automation_interpolates_shader_uniform() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let shader_path = "/src/shader-1.shader";
+    // Keyframes listed out of order; the runtime sorts them
+    let mut builder = ProjectBuilder::new(fs.clone()).with_automation(AutomationTrack {
+        node: NodeSpecifier::from(shader_path),
+        param: "level".to_string(),
+        keyframes: vec![
+            Keyframe {
+                frame: FrameId::new(5),
+                value: 1.0,
+            },
+            Keyframe {
+                frame: FrameId::new(1),
+                value: 0.0,
+            },
+        ],
+    });
+
+    let texture_path = builder.texture_basic();
+    let added_shader = builder
+        .shader(&texture_path)
+        .glsl(
+            "uniform float level;
+            vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(level, 0.0, 0.0, 1.0);
+            }",
+        )
+        .uniform("level", 0.25)
+        .add(&mut builder);
+    assert_eq!(added_shader.as_str(), shader_path);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let (mut runtime, _) = start_runtime(&fs);
+
+    let texture = runtime.handle_for_path(texture_path.as_path()).unwrap();
+
+    // Frame 1: exactly the first keyframe
+    runtime.tick(16).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([0, 0, 0, 255]));
+
+    // Frame 3: halfway between the keyframes
+    runtime.tick(16).unwrap();
+    runtime.tick(16).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([127, 0, 0, 255]));
+
+    // Frame 5: exactly the last keyframe, and held after it
+    runtime.tick(16).unwrap();
+    runtime.tick(16).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([255, 0, 0, 255]));
+    runtime.tick(16).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([255, 0, 0, 255]));
+}
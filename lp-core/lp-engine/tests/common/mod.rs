@@ -0,0 +1,50 @@
+//! Helpers shared by the engine integration tests
+
+#![allow(dead_code, reason = "each test binary uses a different subset")]
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::{ShaderRuntime, TextureRuntime};
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::NodeHandle;
+use lp_shared::fs::LpFsMemory;
+
+/// Load and initialize the project in `fs`, with its outputs kept in memory
+pub fn start_runtime(
+    fs: &Rc<RefCell<LpFsMemory>>,
+) -> (ProjectRuntime, Rc<RefCell<MemoryOutputProvider>>) {
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+    (runtime, output_provider)
+}
+
+/// Pixel of a texture node, or None if it has no texture yet
+pub fn pixel(runtime: &ProjectRuntime, texture: NodeHandle, x: u32, y: u32) -> Option<[u8; 4]> {
+    runtime.nodes[&texture]
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap()
+        .texture()
+        .unwrap()
+        .get_pixel(x, y)
+}
+
+/// Runtime of a shader node
+pub fn shader_mut(runtime: &mut ProjectRuntime, shader: NodeHandle) -> &mut ShaderRuntime {
+    runtime
+        .nodes
+        .get_mut(&shader)
+        .unwrap()
+        .runtime
+        .as_mut()
+        .unwrap()
+        .as_any_mut()
+        .downcast_mut::<ShaderRuntime>()
+        .unwrap()
+}
@@ -0,0 +1,100 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::nodes::ColorConfig;
+use lp_model::nodes::fixture::{ColorOrder, WhiteExtraction};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+/// Render one frame of a single-lamp fixture over a texture filled with `color`,
+/// and return the bytes sent to the output
+fn render_lamp(color_order: ColorOrder, color: ColorConfig) -> Vec<u8> {
+    render_lamp_with(color_order, WhiteExtraction::default(), color)
+}
+
+/// [`render_lamp`] with a white extraction mode
+fn render_lamp_with(
+    color_order: ColorOrder,
+    white_extraction: WhiteExtraction,
+    color: ColorConfig,
+) -> Vec<u8> {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder
+        .texture()
+        .width(4)
+        .height(4)
+        .fill_color(color)
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder
+        .fixture(&output_path, &texture_path)
+        .color_order(color_order)
+        .white_extraction(white_extraction)
+        .add(&mut builder);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+    runtime.tick(16).unwrap();
+
+    let provider = output_provider.borrow();
+    let handle = provider.get_handle_for_pin(0).unwrap();
+    provider.get_data(handle).unwrap()
+}
+
+#[test]
+fn test_grb_fixture_sends_green_first() {
+    let red = ColorConfig::Rgb {
+        r: 1.0,
+        g: 0.0,
+        b: 0.0,
+    };
+    assert_eq!(render_lamp(ColorOrder::Rgb, red), [255, 0, 0]);
+    assert_eq!(render_lamp(ColorOrder::Grb, red), [0, 255, 0]);
+}
+
+#[test]
+fn test_rgbw_fixture_sends_white_channel() {
+    let white = ColorConfig::Rgb {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+    };
+    // Four bytes per lamp, with the white moved onto the white channel
+    assert_eq!(render_lamp(ColorOrder::Rgbw, white), [0, 0, 0, 255]);
+
+    let red = ColorConfig::Rgb {
+        r: 1.0,
+        g: 0.0,
+        b: 0.0,
+    };
+    assert_eq!(render_lamp(ColorOrder::Grbw, red), [0, 255, 0, 0]);
+}
+
+#[test]
+fn test_white_extraction_only_affects_rgbw_fixtures() {
+    let white = ColorConfig::Rgb {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+    };
+    assert_eq!(
+        render_lamp_with(ColorOrder::Rgbw, WhiteExtraction::Add, white),
+        [255, 255, 255, 255]
+    );
+    assert_eq!(
+        render_lamp_with(ColorOrder::Rgbw, WhiteExtraction::Off, white),
+        [255, 255, 255, 0]
+    );
+    // RGB fixtures have nowhere to put white, so the setting is ignored
+    assert_eq!(
+        render_lamp_with(ColorOrder::Grb, WhiteExtraction::Min, white),
+        [255, 255, 255]
+    );
+}
@@ -0,0 +1,65 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_model::nodes::ColorConfig;
+use lp_model::nodes::output::DitherMode;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+mod common;
+use common::start_runtime;
+
+/// Frames per temporal dither cycle, so every LED sees every threshold
+const DITHER_CYCLE: usize = 16;
+
+#[test]
+fn test_gamma_keeps_dithered_dark_levels_apart() {
+    // Gamma maps both levels (and everything up to 27) to 0 in 8 bits; at full
+    // precision they come out near 0.20 and 0.23, which dithering preserves on average
+    let darker = average_output(20);
+    let dark = average_output(21);
+    assert!(darker > 0.0, "level 20 averaged {darker}");
+    assert!(dark > darker, "level 21 averaged {dark}, level 20 {darker}");
+}
+
+/// Average output byte of a gamma corrected, temporally dithered fixture showing a
+/// solid gray of `level`, over whole dither cycles
+fn average_output(level: u8) -> f64 {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let value = f32::from(level) / 255.0;
+    let texture_path = builder
+        .texture()
+        .fill_color(ColorConfig::Rgb {
+            r: value,
+            g: value,
+            b: value,
+        })
+        .add(&mut builder);
+    let output_path = builder
+        .output()
+        .dither(DitherMode::Temporal)
+        .add(&mut builder);
+    builder
+        .fixture(&output_path, &texture_path)
+        .gamma_correction(true)
+        .add(&mut builder);
+    builder.build();
+
+    let (mut runtime, output_provider) = start_runtime(&fs);
+
+    let mut total = 0u64;
+    let mut count = 0u64;
+    for _ in 0..DITHER_CYCLE * 4 {
+        runtime.tick(16).unwrap();
+
+        let provider = output_provider.borrow();
+        let handle = provider.get_handle_for_pin(0).unwrap();
+        let data = provider.get_data(handle).unwrap();
+        total += data.iter().map(|&byte| u64::from(byte)).sum::<u64>();
+        count += data.len() as u64;
+    }
+    total as f64 / count as f64
+}
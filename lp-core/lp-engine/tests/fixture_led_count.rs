@@ -0,0 +1,123 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::OutputRuntime;
+use lp_engine::project::NodeStatus;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::LpPathBuf;
+use lp_model::nodes::fixture::mapping::{MappingConfig, PathSpec, RingOrder};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+/// One ring of `lamps` lamps
+fn ring_mapping(lamps: u32) -> MappingConfig {
+    MappingConfig::PathPoints {
+        paths: vec![PathSpec::RingArray {
+            center: (0.5, 0.5),
+            diameter: 1.0,
+            start_ring_inclusive: 0,
+            end_ring_exclusive: 1,
+            ring_lamp_counts: vec![lamps],
+            offset_angle: 0.0,
+            order: RingOrder::InnerFirst,
+        }],
+        sample_diameter: 2.0,
+    }
+}
+
+/// A red shader sampled by one fixture of `lamps` lamps
+fn runtime_with_fixture(
+    lamps: u32,
+    output_provider: Rc<RefCell<MemoryOutputProvider>>,
+) -> (ProjectRuntime, LpPathBuf, LpPathBuf) {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder.texture_basic();
+    builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(1.0, 0.0, 0.0, 1.0);
+            }",
+        )
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    let fixture_path = builder
+        .fixture(&output_path, &texture_path)
+        .mapping(ring_mapping(lamps))
+        .add(&mut builder);
+    builder.build();
+
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    (runtime, output_path, fixture_path)
+}
+
+fn status(runtime: &ProjectRuntime, path: &LpPathBuf) -> NodeStatus {
+    let handle = runtime.handle_for_path(path.as_path()).unwrap();
+    runtime.nodes[&handle].status.clone()
+}
+
+#[test]
+fn test_zero_led_fixture_is_a_noop() {
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let (mut runtime, output_path, fixture_path) = runtime_with_fixture(0, output_provider.clone());
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    for _ in 0..3 {
+        runtime.tick(16).unwrap();
+    }
+
+    assert_eq!(status(&runtime, &fixture_path), NodeStatus::Ok);
+    assert_eq!(status(&runtime, &output_path), NodeStatus::Ok);
+    // Nothing to send, so the output never opens a channel
+    assert_eq!(output_provider.borrow().open_channel_count(), 0);
+}
+
+#[test]
+fn test_output_channel_sized_for_fixture() {
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::with_max_byte_count(12)));
+    let (mut runtime, output_path, _) = runtime_with_fixture(4, output_provider.clone());
+    runtime.ensure_all_nodes_initialized().unwrap();
+    runtime.tick(16).unwrap();
+
+    let handle = output_provider.borrow().get_handle_for_pin(0).unwrap();
+    let data = output_provider.borrow().get_data(handle).unwrap();
+    assert_eq!(data.len(), 12);
+    assert!(data.chunks(3).all(|lamp| lamp == [255, 0, 0]), "{data:?}");
+
+    let output = runtime.handle_for_path(output_path.as_path()).unwrap();
+    let output_runtime = runtime.nodes[&output]
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<OutputRuntime>()
+        .unwrap();
+    assert_eq!(output_runtime.get_channel_data().len(), 12);
+}
+
+#[test]
+fn test_oversize_fixture_is_a_descriptive_error() {
+    // Room for 4 LEDs, but the fixture has 8
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::with_max_byte_count(12)));
+    let (mut runtime, output_path, _) = runtime_with_fixture(8, output_provider.clone());
+
+    let NodeStatus::InitError(message) = status(&runtime, &output_path) else {
+        panic!(
+            "expected init error, got {:?}",
+            status(&runtime, &output_path)
+        );
+    };
+    assert!(
+        message.contains("24 channel bytes (8 LEDs)")
+            && message.contains("at most 12 bytes (4 LEDs)"),
+        "{message}"
+    );
+
+    // Nothing is opened or truncated, and rendering carries on
+    runtime.tick(16).unwrap();
+    assert_eq!(output_provider.borrow().open_channel_count(), 0);
+}
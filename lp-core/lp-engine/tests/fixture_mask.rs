@@ -0,0 +1,59 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::nodes::ColorConfig;
+use lp_model::nodes::fixture::MaskConfig;
+use lp_model::nodes::fixture::mapping::{MappingConfig, PathSpec, RingOrder};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+#[test]
+fn test_mask_blanks_half_the_leds() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder
+        .texture()
+        .fill_color(ColorConfig::Rgb {
+            r: 1.0,
+            g: 1.0,
+            b: 1.0,
+        })
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder
+        .fixture(&output_path, &texture_path)
+        .mapping(MappingConfig::PathPoints {
+            paths: vec![PathSpec::RingArray {
+                center: (0.5, 0.5),
+                diameter: 1.0,
+                start_ring_inclusive: 0,
+                end_ring_exclusive: 1,
+                ring_lamp_counts: vec![8],
+                offset_angle: 0.0,
+                order: RingOrder::InnerFirst,
+            }],
+            sample_diameter: 2.0,
+        })
+        .mask(MaskConfig::Blank { start: 0, count: 4 })
+        .add(&mut builder);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+    runtime.tick(16).unwrap();
+
+    let handle = output_provider.borrow().get_handle_for_pin(0).unwrap();
+    let data = output_provider.borrow().get_data(handle).unwrap();
+    assert_eq!(data.len(), 24);
+    let (masked, unmasked) = data.split_at(12);
+    assert!(masked.iter().all(|&value| value == 0), "{data:?}");
+    assert!(
+        unmasked.chunks(3).all(|lamp| lamp == [255, 255, 255]),
+        "{data:?}"
+    );
+}
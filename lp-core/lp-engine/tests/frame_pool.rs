@@ -0,0 +1,65 @@
+extern crate alloc;
+extern crate std;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_glsl_compiler::backend::alloc_tracker::{
+    TrackingAllocator, allocation_count, current_bytes,
+};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+use std::alloc::System;
+
+#[global_allocator]
+static GLOBAL: TrackingAllocator<System> = TrackingAllocator::new(System);
+
+#[test]
+fn test_frame_buffers_are_recycled() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder.texture_basic();
+    builder.shader_basic(&texture_path);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    // Warm up: mappings are computed and the pool fills on the first frames
+    for _ in 0..5 {
+        runtime.tick(4).unwrap();
+    }
+    let warm = runtime.frame_pool_stats();
+    assert!(warm.buffer_len > 0);
+    assert_eq!(warm.idle, 3);
+
+    const FRAMES: usize = 100;
+    let mut per_frame = [0usize; FRAMES];
+    let live_before = current_bytes();
+    for count in per_frame.iter_mut() {
+        let before = allocation_count();
+        runtime.tick(4).unwrap();
+        *count = allocation_count() - before;
+    }
+    let live_after = current_bytes();
+
+    // The pool served every fixture render without allocating
+    let stats = runtime.frame_pool_stats();
+    assert_eq!(stats.allocations, warm.allocations);
+    assert_eq!(stats.reuses, warm.reuses + (FRAMES as u64) * 2 * 3);
+    assert_eq!(stats.idle, 3);
+
+    // Whatever else a frame allocates is freed again, and doesn't grow frame to frame
+    assert_eq!(live_after, live_before);
+    let first = per_frame[0];
+    assert!(
+        per_frame.iter().all(|&count| count <= first),
+        "allocations per frame grew: {per_frame:?}"
+    );
+}
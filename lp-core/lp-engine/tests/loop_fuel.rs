@@ -0,0 +1,67 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::TextureRuntime;
+use lp_engine::project::NodeStatus;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+#[test]
+fn test_infinite_loop_shader_is_aborted() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    let shader_path = builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                float red = 0.0;
+                while (true) {
+                    red = 1.0;
+                }
+                return vec4(red, 0.0, 0.0, 1.0);
+            }",
+        )
+        .loop_fuel(1_000)
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    // Returns instead of hanging the render loop
+    runtime.tick(16).unwrap();
+
+    let shader = runtime.handle_for_path(shader_path.as_path()).unwrap();
+    match &runtime.nodes[&shader].status {
+        NodeStatus::Error(message) => {
+            assert!(
+                message.contains("ran out of loop fuel (1000 iterations per frame)"),
+                "unexpected error: {message}"
+            );
+        }
+        other => panic!("expected Error, got {other:?}"),
+    }
+
+    // The shader's pixels are blanked rather than left half-rendered
+    let texture = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    let texture = runtime.nodes[&texture]
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap()
+        .texture()
+        .unwrap();
+    assert_eq!(texture.get_pixel(0, 0), Some([0, 0, 0, 255]));
+    assert_eq!(texture.get_pixel(15, 15), Some([0, 0, 0, 255]));
+}
@@ -0,0 +1,184 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::nodes::fixture::FixtureConfig;
+use lp_model::nodes::shader::ShaderConfig;
+use lp_model::nodes::texture::TextureConfig;
+use lp_model::{AsLpPath, LpPathBuf, NodeHandle};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+mod common;
+use common::start_runtime;
+
+struct Scene {
+    fs: Rc<RefCell<LpFsMemory>>,
+    output_provider: Rc<RefCell<MemoryOutputProvider>>,
+    runtime: ProjectRuntime,
+    texture_path: LpPathBuf,
+    shader_path: LpPathBuf,
+    fixture_path: LpPathBuf,
+}
+
+/// One red shader filling a texture, sampled by one fixture
+fn red_scene() -> Scene {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    let shader_path = builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(1.0, 0.0, 0.0, 1.0);
+            }",
+        )
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    let fixture_path = builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+    fs.borrow_mut().reset_changes();
+
+    let (runtime, output_provider) = start_runtime(&fs);
+
+    Scene {
+        fs,
+        output_provider,
+        runtime,
+        texture_path,
+        shader_path,
+        fixture_path,
+    }
+}
+
+impl Scene {
+    fn handle(&self, path: &LpPathBuf) -> NodeHandle {
+        self.runtime.handle_for_path(path.as_path()).unwrap()
+    }
+
+    fn config<C: Clone + 'static>(&self, path: &LpPathBuf) -> C {
+        self.runtime.nodes[&self.handle(path)]
+            .config
+            .as_any()
+            .downcast_ref::<C>()
+            .unwrap()
+            .clone()
+    }
+
+    /// Rewrite a node's node.json and let the runtime pick up the change
+    fn write_config(&mut self, path: &LpPathBuf, json: String) {
+        let node_json = format!("{}/node.json", path.as_str());
+        self.fs
+            .borrow_mut()
+            .write_file_mut(node_json.as_path(), json.as_bytes())
+            .unwrap();
+        let changes = self.fs.borrow().get_changes();
+        self.runtime.handle_fs_changes(&changes).unwrap();
+        self.fs.borrow_mut().reset_changes();
+    }
+
+    fn set_shader_enabled(&mut self, enabled: bool) {
+        let config = ShaderConfig {
+            enabled: Some(enabled),
+            ..self.config(&self.shader_path)
+        };
+        let path = self.shader_path.clone();
+        self.write_config(&path, lp_model::json::to_string(&config).unwrap());
+    }
+
+    fn set_fixture_enabled(&mut self, enabled: bool) {
+        let config = FixtureConfig {
+            enabled: Some(enabled),
+            ..self.config(&self.fixture_path)
+        };
+        let path = self.fixture_path.clone();
+        self.write_config(&path, lp_model::json::to_string(&config).unwrap());
+    }
+
+    fn set_texture_enabled(&mut self, enabled: bool) {
+        let config = TextureConfig {
+            enabled: Some(enabled),
+            ..self.config(&self.texture_path)
+        };
+        let path = self.texture_path.clone();
+        self.write_config(&path, lp_model::json::to_string(&config).unwrap());
+    }
+
+    /// RGB of the fixture's single lamp as sent to the output
+    fn lamp(&self) -> [u8; 3] {
+        let provider = self.output_provider.borrow();
+        let handle = provider.get_handle_for_pin(0).unwrap();
+        let data = provider.get_data(handle).unwrap();
+        [data[0], data[1], data[2]]
+    }
+
+    fn lamp_is_red(&self) -> bool {
+        matches!(self.lamp(), [r, 0, 0] if r > 0)
+    }
+
+    fn pixel(&self) -> Option<[u8; 4]> {
+        common::pixel(&self.runtime, self.handle(&self.texture_path), 8, 8)
+    }
+}
+
+#[test]
+fn test_disabled_shader_stops_contributing() {
+    let mut scene = red_scene();
+    scene.runtime.tick(16).unwrap();
+    assert!(scene.lamp_is_red(), "lamp: {:?}", scene.lamp());
+
+    // The shader's region is cleared, so the fixture sees nothing from it
+    scene.set_shader_enabled(false);
+    scene.runtime.tick(16).unwrap();
+    assert_eq!(scene.pixel(), Some([0, 0, 0, 255]));
+    assert_eq!(scene.lamp(), [0, 0, 0]);
+
+    // Still loaded and healthy, ready to come back
+    let shader = scene.handle(&scene.shader_path);
+    assert!(scene.runtime.nodes[&shader].runtime.is_some());
+    assert_eq!(
+        scene.runtime.nodes[&shader].status,
+        lp_engine::project::NodeStatus::Ok
+    );
+
+    scene.set_shader_enabled(true);
+    scene.runtime.tick(16).unwrap();
+    assert_eq!(scene.pixel(), Some([255, 0, 0, 255]));
+    assert!(scene.lamp_is_red(), "lamp: {:?}", scene.lamp());
+}
+
+#[test]
+fn test_disabled_fixture_goes_dark() {
+    let mut scene = red_scene();
+    scene.runtime.tick(16).unwrap();
+    assert!(scene.lamp_is_red(), "lamp: {:?}", scene.lamp());
+
+    scene.set_fixture_enabled(false);
+    scene.runtime.tick(16).unwrap();
+    assert_eq!(scene.lamp(), [0, 0, 0]);
+    // Upstream nodes are unaffected
+    assert_eq!(scene.pixel(), Some([255, 0, 0, 255]));
+
+    scene.set_fixture_enabled(true);
+    scene.runtime.tick(16).unwrap();
+    assert!(scene.lamp_is_red(), "lamp: {:?}", scene.lamp());
+}
+
+#[test]
+fn test_disabled_texture_mutes_downstream_fixtures() {
+    let mut scene = red_scene();
+    scene.runtime.tick(16).unwrap();
+    assert!(scene.lamp_is_red(), "lamp: {:?}", scene.lamp());
+
+    // Fixtures sampling the texture go dark, but the texture keeps its pixels
+    scene.set_texture_enabled(false);
+    scene.runtime.tick(16).unwrap();
+    assert_eq!(scene.lamp(), [0, 0, 0]);
+    assert_eq!(scene.pixel(), Some([255, 0, 0, 255]));
+
+    scene.set_texture_enabled(true);
+    scene.runtime.tick(16).unwrap();
+    assert!(scene.lamp_is_red(), "lamp: {:?}", scene.lamp());
+}
@@ -0,0 +1,93 @@
+//! Test that loading and unloading a project doesn't leak memory
+//!
+//! Every allocation in this test binary goes through a tracking allocator, so
+//! anything a node keeps after the runtime is torn down shows up as growth over
+//! many load/unload cycles.
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_glsl_compiler::backend::alloc_tracker::{TrackingAllocator, current_bytes};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+use std::alloc::System;
+
+#[global_allocator]
+static GLOBAL: TrackingAllocator<System> = TrackingAllocator::new(System);
+
+/// Cycles run before measuring, so one-time allocations (lazy statics, log setup)
+/// are part of the baseline
+const WARMUP_CYCLES: usize = 5;
+const CYCLES: usize = 50;
+/// Growth allowed over all measured cycles, well under one leaked shader or frame
+const MAX_GROWTH_BYTES: usize = 1024;
+
+/// A shader drawing into a texture, sampled by a fixture writing to an output
+fn project() -> Rc<RefCell<LpFsMemory>> {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder.texture_basic();
+    builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(fragCoord / outputSize, sin(time), 1.0);
+            }",
+        )
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+    fs.borrow_mut().reset_changes();
+    fs
+}
+
+/// Load the project, render a few frames and tear it down again
+fn cycle(
+    fs: &Rc<RefCell<LpFsMemory>>,
+    output_provider: &Rc<RefCell<MemoryOutputProvider>>,
+    explicit_unload: bool,
+) {
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+    for _ in 0..3 {
+        runtime.tick(16).unwrap();
+    }
+    assert_eq!(output_provider.borrow().open_channel_count(), 1);
+
+    if explicit_unload {
+        runtime.unload().unwrap();
+        assert!(runtime.nodes.is_empty());
+    }
+    drop(runtime);
+    assert_eq!(
+        output_provider.borrow().open_channel_count(),
+        0,
+        "output channel left open after teardown"
+    );
+}
+
+#[test]
+fn test_load_unload_cycles_return_to_baseline() {
+    let fs = project();
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+
+    for i in 0..WARMUP_CYCLES {
+        cycle(&fs, &output_provider, i % 2 == 0);
+    }
+    let baseline = current_bytes();
+
+    // Alternate between unloading and just dropping the runtime
+    for i in 0..CYCLES {
+        cycle(&fs, &output_provider, i % 2 == 0);
+    }
+    let growth = current_bytes().saturating_sub(baseline);
+    assert!(
+        growth <= MAX_GROWTH_BYTES,
+        "memory grew by {growth} bytes over {CYCLES} load/unload cycles"
+    );
+}
@@ -0,0 +1,77 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::OutputRuntime;
+use lp_engine::{OutputChannelHandle, OutputFormat, OutputProvider, ProjectRuntime};
+use lp_shared::OutputError;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+/// Provider that records where each write's data lives, plus a copy of its contents
+#[derive(Default)]
+struct RecordingProvider {
+    writes: RefCell<Vec<(*const u8, Vec<u8>)>>,
+}
+
+impl OutputProvider for RecordingProvider {
+    fn open(
+        &self,
+        _pin: u32,
+        _byte_count: u32,
+        _format: OutputFormat,
+    ) -> Result<OutputChannelHandle, OutputError> {
+        Ok(OutputChannelHandle::new(0))
+    }
+
+    fn write(&self, _handle: OutputChannelHandle, data: &[u8]) -> Result<(), OutputError> {
+        self.writes
+            .borrow_mut()
+            .push((data.as_ptr(), data.to_vec()));
+        Ok(())
+    }
+
+    fn close(&self, _handle: OutputChannelHandle) -> Result<(), OutputError> {
+        Ok(())
+    }
+
+    fn set_brightness(&self, _brightness: f32) {}
+}
+
+#[test]
+fn test_output_provider_borrows_channel_buffer() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder.texture_basic();
+    builder.shader_basic(&texture_path);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(RecordingProvider::default()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    // Shader: vec4(mod(time, 1.0), 0.0, 0.0, 1.0); 4ms gives a red value of 1
+    runtime.tick(4).unwrap();
+
+    let output = runtime.handle_for_path(output_path.as_path()).unwrap();
+    let channel_data = runtime.nodes[&output]
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<OutputRuntime>()
+        .unwrap()
+        .get_channel_data();
+
+    // The provider was handed the output node's own buffer, not a copy of it
+    let provider = output_provider.borrow();
+    let writes = provider.writes.borrow();
+    let (ptr, data) = writes.last().expect("Output was not written");
+    assert_eq!(*ptr, channel_data.as_ptr());
+    assert_eq!(data.as_slice(), channel_data);
+    assert_eq!(data[0], 1);
+}
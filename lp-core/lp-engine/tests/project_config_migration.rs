@@ -0,0 +1,45 @@
+use lp_engine::Error;
+use lp_engine::project::load_from_filesystem;
+use lp_model::AsLpPath;
+use lp_model::project::{PROJECT_CONFIG_VERSION, ProjectConfig};
+use lp_shared::fs::{LpFs, LpFsMemory};
+
+#[test]
+fn test_load_v1_project_config_migrates() {
+    // Version 1 files had no version field and no seed
+    let fs = LpFsMemory::new();
+    fs.write_file(
+        "/project.json".as_path(),
+        br#"{"uid":"legacy-uid","name":"Legacy Project"}"#,
+    )
+    .unwrap();
+
+    let config = load_from_filesystem(&fs).unwrap();
+    assert_eq!(
+        config,
+        ProjectConfig {
+            version: PROJECT_CONFIG_VERSION,
+            uid: "legacy-uid".to_string(),
+            name: "Legacy Project".to_string(),
+            seed: None,
+            automation: Vec::new(),
+        }
+    );
+}
+
+#[test]
+fn test_load_newer_project_config_fails() {
+    let fs = LpFsMemory::new();
+    fs.write_file(
+        "/project.json".as_path(),
+        br#"{"version":1000,"uid":"future-uid","name":"Future Project"}"#,
+    )
+    .unwrap();
+
+    match load_from_filesystem(&fs) {
+        Err(Error::Parse { error, .. }) => {
+            assert!(error.contains("newer than the latest supported version"));
+        }
+        other => panic!("expected parse error, got {other:?}"),
+    }
+}
@@ -0,0 +1,83 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::ProjectRuntime;
+use lp_model::NodeHandle;
+use lp_model::project::FrameId;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+mod common;
+use common::{pixel, shader_mut, start_runtime};
+
+/// Frame the node last rendered (or, for a texture, last changed)
+fn rendered_at(runtime: &ProjectRuntime, handle: NodeHandle) -> FrameId {
+    runtime.nodes[&handle].state_ver
+}
+
+#[test]
+fn test_static_shader_render_is_skipped() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let static_texture_path = builder.texture_basic();
+    let static_shader_path = builder
+        .shader(&static_texture_path)
+        .glsl(
+            "uniform float level;
+            vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(level, 0.0, 0.0, 1.0);
+            }",
+        )
+        .uniform("level", 0.5)
+        .add(&mut builder);
+    let animated_texture_path = builder.texture_basic();
+    let animated_shader_path = builder.shader_basic(&animated_texture_path);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &static_texture_path);
+    builder.fixture_basic(&output_path, &animated_texture_path);
+    builder.build();
+
+    let (mut runtime, _) = start_runtime(&fs);
+
+    let static_texture = runtime
+        .handle_for_path(static_texture_path.as_path())
+        .unwrap();
+    let static_shader = runtime
+        .handle_for_path(static_shader_path.as_path())
+        .unwrap();
+    let animated_shader = runtime
+        .handle_for_path(animated_shader_path.as_path())
+        .unwrap();
+
+    // Frame 1: everything renders
+    runtime.tick(16).unwrap();
+    assert_eq!(rendered_at(&runtime, static_shader), FrameId::new(1));
+    assert_eq!(rendered_at(&runtime, animated_shader), FrameId::new(1));
+    assert_eq!(
+        pixel(&runtime, static_texture, 0, 0),
+        Some([127, 0, 0, 255])
+    );
+
+    // Frame 2: only the shader reading time renders; the static texture keeps its pixels
+    runtime.tick(16).unwrap();
+    assert_eq!(rendered_at(&runtime, static_shader), FrameId::new(1));
+    assert_eq!(rendered_at(&runtime, static_texture), FrameId::new(1));
+    assert_eq!(rendered_at(&runtime, animated_shader), FrameId::new(2));
+    assert_eq!(
+        pixel(&runtime, static_texture, 0, 0),
+        Some([127, 0, 0, 255])
+    );
+
+    // Frame 3: a uniform change makes the static shader render again
+    shader_mut(&mut runtime, static_shader)
+        .set_uniform("level", 1.0)
+        .unwrap();
+    runtime.tick(16).unwrap();
+    assert_eq!(rendered_at(&runtime, static_shader), FrameId::new(3));
+    assert_eq!(
+        pixel(&runtime, static_texture, 0, 0),
+        Some([255, 0, 0, 255])
+    );
+}
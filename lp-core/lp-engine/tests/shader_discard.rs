@@ -0,0 +1,73 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::TextureRuntime;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::nodes::ColorConfig;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+#[test]
+fn test_shader_discard_keeps_background() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    // Blue background that the shader should only partly cover
+    let texture_path = builder
+        .texture()
+        .width(4)
+        .height(2)
+        .fill_color(ColorConfig::Rgb {
+            r: 0.0,
+            g: 0.0,
+            b: 1.0,
+        })
+        .add(&mut builder);
+
+    // Discard the left half, paint the right half red
+    builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                if (fragCoord.x < 2.0) {
+                    discard;
+                }
+                return vec4(1.0, 0.0, 0.0, 1.0);
+            }",
+        )
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+    runtime.tick(16).unwrap();
+
+    let handle = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    let texture = runtime
+        .nodes
+        .get(&handle)
+        .unwrap()
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap()
+        .texture()
+        .unwrap();
+
+    for y in 0..2 {
+        // Discarded pixels keep the fill color
+        assert_eq!(texture.get_pixel(0, y), Some([0, 0, 255, 255]));
+        assert_eq!(texture.get_pixel(1, y), Some([0, 0, 255, 255]));
+        // Written pixels take the shader color
+        assert_eq!(texture.get_pixel(2, y), Some([255, 0, 0, 255]));
+        assert_eq!(texture.get_pixel(3, y), Some([255, 0, 0, 255]));
+    }
+}
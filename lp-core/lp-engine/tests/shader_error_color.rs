@@ -0,0 +1,71 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::project::NodeStatus;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::AsLpPath;
+use lp_model::nodes::ColorConfig;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+fn output_data(output_provider: &Rc<RefCell<MemoryOutputProvider>>) -> Vec<u8> {
+    let provider = output_provider.borrow();
+    let handle = provider.get_handle_for_pin(0).unwrap();
+    provider.get_data(handle).unwrap().to_vec()
+}
+
+#[test]
+fn test_error_color_shown_until_shader_recovers() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder.texture_basic();
+    let shader_path = builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return undefined_color;
+            }",
+        )
+        .error_color(ColorConfig::Rgb {
+            r: 1.0,
+            g: 0.0,
+            b: 1.0,
+        })
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+    fs.borrow_mut().reset_changes();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider.clone()).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+    runtime.tick(16).unwrap();
+
+    let shader = runtime.handle_for_path(shader_path.as_path()).unwrap();
+    assert!(matches!(
+        runtime.nodes[&shader].status,
+        NodeStatus::Error(_)
+    ));
+    assert_eq!(output_data(&output_provider), [255, 0, 255]);
+
+    // Fix the shader
+    fs.borrow_mut()
+        .write_file_mut(
+            "/src/shader-1.shader/main.glsl".as_path(),
+            b"vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(0.0, 1.0, 0.0, 1.0);
+            }",
+        )
+        .unwrap();
+    let changes = fs.borrow().get_changes();
+    runtime.handle_fs_changes(&changes).unwrap();
+    fs.borrow_mut().reset_changes();
+    runtime.tick(16).unwrap();
+
+    assert_eq!(runtime.nodes[&shader].status, NodeStatus::Ok);
+    assert_eq!(output_data(&output_provider), [0, 255, 0]);
+}
@@ -0,0 +1,51 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_model::nodes::ColorConfig;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+mod common;
+use common::{pixel, start_runtime};
+
+#[test]
+fn test_shader_samples_palette_from_config() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(palette(0.5), 1.0);
+            }",
+        )
+        .palette_stop(
+            0.0,
+            ColorConfig::Rgb {
+                r: 1.0,
+                g: 0.0,
+                b: 0.0,
+            },
+        )
+        .palette_stop(
+            1.0,
+            ColorConfig::Rgb {
+                r: 0.0,
+                g: 0.0,
+                b: 1.0,
+            },
+        )
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let (mut runtime, _) = start_runtime(&fs);
+
+    let texture = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    runtime.tick(16).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([127, 0, 127, 255]));
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "emulator")]
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::project::NodeStatus;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::nodes::shader::ShaderBackend;
+use lp_model::project::FrameId;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+#[test]
+fn test_emulated_shader_trap_is_reported() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let trapping_texture_path = builder.texture_basic();
+    let trapping_shader_path = builder
+        .shader(&trapping_texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {\n\
+             int zero = int(fragCoord.x) * 0;\n\
+             int value = 10 / zero;\n\
+             return vec4(float(value), 0.0, 0.0, 1.0);\n\
+             }\n",
+        )
+        .backend(ShaderBackend::Emulator)
+        .add(&mut builder);
+    let texture_path = builder.texture_basic();
+    let shader_path = builder.shader_basic(&texture_path);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &trapping_texture_path);
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    // The trap doesn't take down the frame
+    runtime.tick(16).unwrap();
+
+    let trapping_shader = runtime
+        .handle_for_path(trapping_shader_path.as_path())
+        .unwrap();
+    match &runtime.nodes[&trapping_shader].status {
+        NodeStatus::Error(message) => {
+            assert!(
+                message.contains("execution trapped"),
+                "unexpected error: {message}"
+            );
+            // The division is on line 3 of the shader
+            assert!(message.contains("at line 3"), "unexpected error: {message}");
+            assert!(message.contains("(PC 0x"), "unexpected error: {message}");
+        }
+        other => panic!("expected Error, got {other:?}"),
+    }
+
+    // Other shaders keep rendering
+    let shader = runtime.handle_for_path(shader_path.as_path()).unwrap();
+    assert_eq!(runtime.nodes[&shader].status, NodeStatus::Ok);
+    assert_eq!(runtime.nodes[&shader].state_ver, FrameId::new(1));
+
+    runtime.tick(16).unwrap();
+    assert_eq!(runtime.nodes[&shader].state_ver, FrameId::new(2));
+}
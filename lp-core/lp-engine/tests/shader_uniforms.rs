@@ -0,0 +1,77 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+mod common;
+use common::{pixel, shader_mut, start_runtime};
+
+#[test]
+fn test_shader_uniform_from_config_and_update() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    let shader_path = builder
+        .shader(&texture_path)
+        .glsl(
+            "uniform float level;
+            vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(level, 0.0, 0.0, 1.0);
+            }",
+        )
+        .uniform("level", 0.5)
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let (mut runtime, _) = start_runtime(&fs);
+
+    let texture = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    let shader = runtime.handle_for_path(shader_path.as_path()).unwrap();
+
+    // Frame 1 uses the value from the node config
+    runtime.tick(16).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([127, 0, 0, 255]));
+
+    // Frame 2 sees the updated value without recompiling
+    shader_mut(&mut runtime, shader)
+        .set_uniform("level", 1.0)
+        .unwrap();
+    runtime.tick(16).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([255, 0, 0, 255]));
+}
+
+#[test]
+fn test_shader_implicit_time_and_frame_uniforms() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    let texture_path = builder.texture_basic();
+    builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(u_time, float(u_frame) * 0.25, 0.0, 1.0);
+            }",
+        )
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let (mut runtime, _) = start_runtime(&fs);
+
+    let texture = runtime.handle_for_path(texture_path.as_path()).unwrap();
+
+    // Frame 1 at 0.25s
+    runtime.tick(250).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([63, 63, 0, 255]));
+
+    // Frame 2 at 0.5s
+    runtime.tick(250).unwrap();
+    assert_eq!(pixel(&runtime, texture, 0, 0), Some([127, 127, 0, 255]));
+}
@@ -0,0 +1,73 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::TextureRuntime;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::nodes::ColorConfig;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+#[test]
+fn test_shader_viewport_renders_sub_rect() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+
+    // Blue background; only the viewport should turn red
+    let texture_path = builder
+        .texture()
+        .width(4)
+        .height(4)
+        .fill_color(ColorConfig::Rgb {
+            r: 0.0,
+            g: 0.0,
+            b: 1.0,
+        })
+        .add(&mut builder);
+
+    builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(1.0, 0.0, 0.0, 1.0);
+            }",
+        )
+        .viewport(1, 1, 2, 2)
+        .add(&mut builder);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+    runtime.tick(16).unwrap();
+
+    let handle = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    let texture = runtime
+        .nodes
+        .get(&handle)
+        .unwrap()
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap()
+        .texture()
+        .unwrap();
+
+    for y in 0..4 {
+        for x in 0..4 {
+            let inside = (1..3).contains(&x) && (1..3).contains(&y);
+            let expected = if inside {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 255, 255]
+            };
+            assert_eq!(texture.get_pixel(x, y), Some(expected), "pixel ({x}, {y})");
+        }
+    }
+}
@@ -0,0 +1,68 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_riscv_emu::{Riscv32Emulator, StepResult};
+use lp_riscv_inst::Gpr;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+use lp_shared::time::TestClock;
+
+/// Reads the time syscall into a0 and halts
+fn time_program() -> Vec<u8> {
+    vec![
+        0x93, 0x08, 0x80, 0x00, // addi a7, zero, 8 (SYSCALL_TIME_MS)
+        0x73, 0x00, 0x00, 0x00, // ecall
+        0x73, 0x00, 0x10, 0x00, // ebreak
+    ]
+}
+
+/// Guest-visible time, as firmware reads it through the syscall
+fn guest_time_ms(emu: &mut Riscv32Emulator) -> u32 {
+    emu.set_pc(0);
+    assert!(matches!(emu.run().unwrap(), StepResult::Halted));
+    emu.get_register(Gpr::A0) as u32
+}
+
+#[test]
+fn test_shared_clock_drives_engine_and_emulator() {
+    let clock = TestClock::starting_at(1000);
+
+    let mut emu = Riscv32Emulator::new(time_program(), vec![0; 64]).with_clock({
+        let clock = clock.clone();
+        move || clock.now_ms_u32()
+    });
+
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder.texture_basic();
+    builder.shader_basic(&texture_path);
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    // First tick only establishes the reference point
+    runtime.tick_clock(&clock).unwrap();
+    assert_eq!(runtime.frame_time.delta_ms, 0);
+    let mut guest_before = guest_time_ms(&mut emu);
+    assert_eq!(guest_before, 1000);
+
+    for advance in [16, 33, 1] {
+        clock.advance(advance);
+        runtime.tick_clock(&clock).unwrap();
+        let guest_now = guest_time_ms(&mut emu);
+
+        // Both sides see exactly the advance
+        assert_eq!(u64::from(runtime.frame_time.delta_ms), advance);
+        assert_eq!(u64::from(guest_now - guest_before), advance);
+        guest_before = guest_now;
+    }
+    assert_eq!(runtime.frame_time.total_ms, 50);
+}
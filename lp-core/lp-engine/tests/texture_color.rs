@@ -0,0 +1,60 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::TextureRuntime;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_model::nodes::ColorConfig;
+use lp_model::nodes::texture::TextureConfig;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::{LpFs, LpFsMemory};
+
+#[test]
+fn test_texture_hsv_fill_color() {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let hsv = ColorConfig::Hsv {
+        h: 2.0 / 3.0,
+        s: 1.0,
+        v: 1.0,
+    };
+    let texture_path = builder
+        .texture()
+        .width(4)
+        .height(2)
+        .fill_color(hsv)
+        .add(&mut builder);
+    builder.build();
+
+    // The stored config keeps the HSV form
+    let json = fs
+        .borrow()
+        .read_file(texture_path.join("node.json").as_path())
+        .unwrap();
+    let stored: TextureConfig = lp_model::json::from_slice(&json).unwrap();
+    assert_eq!(stored.fill_color, Some(hsv));
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    let handle = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    let texture_runtime = runtime
+        .nodes
+        .get(&handle)
+        .unwrap()
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap();
+
+    // HSV(2/3, 1, 1) is pure blue
+    assert_eq!(texture_runtime.fill_rgb(), Some([0, 0, 255]));
+    let texture = texture_runtime.texture().unwrap();
+    assert_eq!(texture.get_pixel(0, 0), Some([0, 0, 255, 255]));
+    assert_eq!(texture.get_pixel(3, 1), Some([0, 0, 255, 255]));
+}
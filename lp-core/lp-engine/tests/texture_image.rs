@@ -0,0 +1,82 @@
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::TextureRuntime;
+use lp_engine::project::NodeStatus;
+use lp_engine::{MemoryOutputProvider, ProjectRuntime};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+/// 2x2 RGBA PNG:
+/// row 0: (255, 0, 0, 255), (0, 128, 255, 255)
+/// row 1: (0, 255, 0, 255), (255, 255, 255, 128)
+const TINY_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+    0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x08, 0x06, 0x00, 0x00, 0x00, 0x72, 0xb6, 0x0d,
+    0x24, 0x00, 0x00, 0x00, 0x15, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0xf8, 0xcf, 0xc0, 0xf0,
+    0x9f, 0xa1, 0xe1, 0x3f, 0x98, 0xfa, 0x0f, 0x04, 0x0d, 0x00, 0x4f, 0x49, 0x09, 0xf8, 0xf9, 0xdc,
+    0xb4, 0x2c, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+];
+
+fn init_runtime(image_name: &str, image_data: &[u8]) -> (ProjectRuntime, lp_model::NodeHandle) {
+    let fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(fs.clone());
+    let texture_path = builder
+        .texture()
+        .image(image_name, image_data)
+        .add(&mut builder);
+    builder.build();
+
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let mut runtime = ProjectRuntime::new(fs.clone(), output_provider).unwrap();
+    runtime.load_nodes().unwrap();
+    runtime.init_nodes().unwrap();
+
+    let handle = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    (runtime, handle)
+}
+
+#[test]
+fn test_texture_loads_png() {
+    let (runtime, handle) = init_runtime("image.png", TINY_PNG);
+    runtime.ensure_all_nodes_initialized().unwrap();
+
+    let entry = runtime.nodes.get(&handle).unwrap();
+    let texture = entry
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap()
+        .texture()
+        .unwrap();
+
+    // Dimensions come from the image, not the builder's 16x16 default
+    assert_eq!(texture.width(), 2);
+    assert_eq!(texture.height(), 2);
+
+    assert_eq!(texture.get_pixel(0, 0), Some([255, 0, 0, 255]));
+    assert_eq!(texture.get_pixel(1, 1), Some([255, 255, 255, 128]));
+    // Sampling at a corner returns the exact pixel
+    assert_eq!(texture.sample(1.0, 0.0), Some([0, 128, 255, 255]));
+    assert_eq!(texture.sample(0.0, 1.0), Some([0, 255, 0, 255]));
+}
+
+#[test]
+fn test_texture_unsupported_image_format() {
+    let (runtime, handle) = init_runtime("image.gif", b"GIF89a\x02\x00\x02\x00");
+
+    let entry = runtime.nodes.get(&handle).unwrap();
+    match &entry.status {
+        NodeStatus::InitError(message) => {
+            assert!(
+                message.contains("Unsupported image format"),
+                "unexpected error: {message}"
+            );
+        }
+        other => panic!("expected InitError, got {other:?}"),
+    }
+    assert!(runtime.ensure_all_nodes_initialized().is_err());
+}
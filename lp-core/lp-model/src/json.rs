@@ -240,8 +240,11 @@ mod tests {
         use alloc::string::ToString;
 
         let original = ProjectConfig {
+            version: crate::project::PROJECT_CONFIG_VERSION,
             uid: "test".to_string(),
             name: "Test Project".to_string(),
+            seed: None,
+            automation: Vec::new(),
         };
         let json = to_string(&original).unwrap();
 
@@ -266,8 +269,11 @@ mod tests {
         use alloc::string::ToString;
 
         let original = ProjectConfig {
+            version: crate::project::PROJECT_CONFIG_VERSION,
             uid: "test".to_string(),
             name: "Test Project".to_string(),
+            seed: None,
+            automation: Vec::new(),
         };
         let json = to_string(&original).unwrap();
         let json_bytes = json.as_bytes();
@@ -19,12 +19,17 @@ pub mod project;
 pub mod serde_base64;
 pub mod serial;
 pub mod server;
+#[cfg(test)]
+mod test_util;
 pub mod transport_error;
 
-pub use message::{ClientMessage, ClientRequest, Message, ServerMessage};
+pub use message::{
+    ClientMessage, ClientRequest, ConnectionId, DEFAULT_CONNECTION, MIN_PROTOCOL_VERSION, Message,
+    NodeEdit, PROTOCOL_VERSION, ServerMessage, TestPattern, TestPatternFixture,
+};
 pub use nodes::{NodeConfig, NodeHandle, NodeKind, NodeSpecifier};
 pub use path::{AsLpPath, AsLpPathBuf, LpPath, LpPathBuf};
 pub use project::{FrameId, ProjectConfig};
 pub use serial::DEFAULT_SERIAL_BAUD_RATE;
-pub use server::{AvailableProject, FsRequest, FsResponse, LoadedProject};
+pub use server::{AvailableProject, Capabilities, FsRequest, FsResponse, LoadedProject};
 pub use transport_error::TransportError;
@@ -2,16 +2,47 @@
 //!
 //! Defines the message envelope and request/response types for client-server communication.
 
+use crate::LpPathBuf;
+use crate::nodes::fixture::ColorOrder;
+use crate::nodes::output::OutputConfig;
 use crate::project::{api::ProjectRequest, handle::ProjectHandle};
+use crate::serde_base64;
 use crate::server::{FsRequest, ServerMsgBody as ServerMessagePayload};
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use serde::{Deserialize, Serialize};
 
+/// Protocol version spoken by this build
+///
+/// Clients announce their version with [`ClientRequest::Hello`] when they connect,
+/// and the server answers with the version both sides will use. Servers accept
+/// `Hello` at any version, so a connection can always renegotiate.
+///
+/// - 1: the protocol before version negotiation
+/// - 2: adds `Undo`, `Redo` and `DuplicateNode`
+/// - 3: adds `GetCapabilities`
+/// - 4: adds `ApplyEdits`
+/// - 5: adds `GetEvents`
+/// - 6: adds `TestPattern`
+/// - 7: adds `SyncClock`
+pub const PROTOCOL_VERSION: u32 = 7;
+
+/// Oldest protocol version a server still accepts
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Identifies one client connection to a server
+///
+/// Assigned by the server transport. The protocol version is agreed per connection,
+/// so clients of different ages can share a server.
+pub type ConnectionId = u64;
+
+/// Connection of transports that serve a single client
+pub const DEFAULT_CONNECTION: ConnectionId = 0;
+
 /// Top-level message envelope
 ///
 /// Messages are wrapped in this enum to distinguish between client and server messages.
 /// Note: Cannot derive Clone because ServerMessage contains non-cloneable types.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Message {
     /// Message from client to server
@@ -23,7 +54,7 @@ pub enum Message {
 /// Client message with request ID
 ///
 /// Wraps a client request with an ID for request/response correlation.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ClientMessage {
     /// Request ID for correlating requests and responses
     pub id: u64,
@@ -37,7 +68,7 @@ pub struct ClientMessage {
 ///
 /// Note: Cannot derive Clone because ServerResponse contains non-cloneable types
 /// (specifically ProjectResponse which contains NodeDetail with trait objects).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ServerMessage {
     /// Request ID matching the original client request
     pub id: u64,
@@ -46,7 +77,7 @@ pub struct ServerMessage {
 }
 
 /// Client request types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ClientRequest {
     /// Filesystem operation request
@@ -66,6 +97,123 @@ pub enum ClientRequest {
     ListLoadedProjects,
     /// Stop all loaded projects
     StopAllProjects,
+    /// Revert the most recent file edit made to a project through the server
+    Undo { handle: ProjectHandle },
+    /// Re-apply the most recently undone edit
+    Redo { handle: ProjectHandle },
+    /// Copy a node directory (and everything under it) to a new path in a project
+    ///
+    /// Paths are relative to the project root (e.g. `/src/rainbow.shader`). Node
+    /// references inside the copy that point into `source` are rewritten to point
+    /// at the copy. If `dest` is taken, a numeric suffix is added.
+    DuplicateNode {
+        handle: ProjectHandle,
+        source: LpPathBuf,
+        dest: LpPathBuf,
+    },
+    /// Announce the newest protocol version the client speaks
+    ///
+    /// Sent once when connecting. The server replies with the version the connection
+    /// will use (the lower of the two), or an error if the client is too old.
+    Hello { protocol_version: u32 },
+    /// Ask what the server supports (node kinds, output drivers, limits, features)
+    GetCapabilities,
+    /// Write several node files to a project as one transaction
+    ///
+    /// The affected nodes are reloaded once, after every file is written. If any of
+    /// them fails to load, all files are restored and an error is returned. The batch
+    /// is a single undo step.
+    ApplyEdits {
+        handle: ProjectHandle,
+        edits: Vec<NodeEdit>,
+    },
+    /// Fetch the server's log of recent events, oldest first
+    GetEvents,
+    /// Show a test pattern on a fixture, or clear it with `pattern: None`
+    ///
+    /// The server writes the pattern straight to the output every tick, bypassing
+    /// the node graph, so it works without a project. The output must not be in use
+    /// by a loaded project.
+    TestPattern {
+        fixture: TestPatternFixture,
+        pattern: Option<TestPattern>,
+    },
+    /// Align rendering to the host's clock, so several devices show the same frame
+    /// at the same time
+    ///
+    /// From the next tick the server renders frame `host_time / frame_interval_ms`
+    /// of the host's clock, stamping output frames with that frame id. Sending the
+    /// request again re-syncs; the host should do so periodically to correct drift.
+    /// `host_time_ms` is read just before sending, so transit delay shows up as an
+    /// offset between devices.
+    SyncClock {
+        /// Host clock time in milliseconds (any epoch shared by the synced devices)
+        host_time_ms: u64,
+        /// Length of one frame in milliseconds
+        frame_interval_ms: u32,
+    },
+}
+
+/// New contents for one file of a node, as part of [`ClientRequest::ApplyEdits`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeEdit {
+    /// File path relative to the project root (e.g. `/src/rainbow.shader/node.json`)
+    pub path: LpPathBuf,
+    /// New file contents
+    #[serde(
+        serialize_with = "serde_base64::serialize_smart",
+        deserialize_with = "serde_base64::deserialize_smart"
+    )]
+    pub data: Vec<u8>,
+}
+
+/// Hardware to show a [`ClientRequest::TestPattern`] on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestPatternFixture {
+    /// Output driver and pins the LEDs are connected to
+    pub output: OutputConfig,
+    /// Number of LEDs on the output
+    pub led_count: u32,
+    /// Color order of the LEDs, defaults to RGB
+    #[serde(default)]
+    pub color_order: Option<ColorOrder>,
+}
+
+/// Pattern shown by [`ClientRequest::TestPattern`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TestPattern {
+    /// Every LED full red
+    Red,
+    /// Every LED full green
+    Green,
+    /// Every LED full blue
+    Blue,
+    /// Every LED full white
+    White,
+    /// A single white LED moving along the strip
+    Chase,
+    /// A rainbow spread over the strip, scrolling slowly
+    Rainbow,
+}
+
+impl ClientRequest {
+    /// Protocol version that introduced this request
+    ///
+    /// The server rejects requests newer than the version agreed for the connection.
+    pub fn protocol_version(&self) -> u32 {
+        match self {
+            ClientRequest::Undo { .. }
+            | ClientRequest::Redo { .. }
+            | ClientRequest::DuplicateNode { .. } => 2,
+            ClientRequest::GetCapabilities => 3,
+            ClientRequest::ApplyEdits { .. } => 4,
+            ClientRequest::GetEvents => 5,
+            ClientRequest::TestPattern { .. } => 6,
+            ClientRequest::SyncClock { .. } => 7,
+            _ => 1,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -73,7 +221,10 @@ mod tests {
     use super::*;
     use crate::AsLpPathBuf;
     use crate::server::FsResponse;
+    use crate::test_util::{assert_all_variants, assert_round_trip};
     use alloc::string::ToString;
+    use alloc::vec;
+    use alloc::vec::Vec;
 
     #[test]
     fn test_message_serialization() {
@@ -242,4 +393,160 @@ mod tests {
             _ => panic!("Wrong request type"),
         }
     }
+
+    #[test]
+    fn test_undo_redo_requests() {
+        let req = ClientRequest::Undo {
+            handle: ProjectHandle::new(3),
+        };
+        let json = crate::json::to_string(&req).unwrap();
+        let deserialized: ClientRequest = crate::json::from_str(&json).unwrap();
+        match deserialized {
+            ClientRequest::Undo { handle } => assert_eq!(handle, ProjectHandle::new(3)),
+            _ => panic!("Wrong request type"),
+        }
+
+        let req = ClientRequest::Redo {
+            handle: ProjectHandle::new(3),
+        };
+        let json = crate::json::to_string(&req).unwrap();
+        let deserialized: ClientRequest = crate::json::from_str(&json).unwrap();
+        assert!(matches!(deserialized, ClientRequest::Redo { .. }));
+    }
+
+    #[test]
+    fn test_duplicate_node_request() {
+        let req = ClientRequest::DuplicateNode {
+            handle: ProjectHandle::new(2),
+            source: "/src/rainbow.shader".as_path_buf(),
+            dest: "/src/rainbow-copy.shader".as_path_buf(),
+        };
+        let json = crate::json::to_string(&req).unwrap();
+        let deserialized: ClientRequest = crate::json::from_str(&json).unwrap();
+        match deserialized {
+            ClientRequest::DuplicateNode {
+                handle,
+                source,
+                dest,
+            } => {
+                assert_eq!(handle, ProjectHandle::new(2));
+                assert_eq!(source.as_str(), "/src/rainbow.shader");
+                assert_eq!(dest.as_str(), "/src/rainbow-copy.shader");
+            }
+            _ => panic!("Wrong request type"),
+        }
+    }
+
+    #[test]
+    fn test_client_request_round_trip() {
+        use crate::project::FrameId;
+        use crate::project::api::ApiNodeSpecifier;
+
+        let handle = ProjectHandle::new(4);
+        let requests = vec![
+            ClientRequest::Filesystem(FsRequest::ListDir {
+                path: "/".as_path_buf(),
+                recursive: true,
+            }),
+            ClientRequest::LoadProject {
+                path: "projects/my project".to_string(),
+            },
+            ClientRequest::UnloadProject { handle },
+            ClientRequest::ProjectRequest {
+                handle,
+                request: ProjectRequest::GetChanges {
+                    since_frame: FrameId::new(3),
+                    detail_specifier: ApiNodeSpecifier::None,
+                },
+            },
+            ClientRequest::ListAvailableProjects,
+            ClientRequest::ListLoadedProjects,
+            ClientRequest::StopAllProjects,
+            ClientRequest::Undo { handle },
+            ClientRequest::Redo { handle },
+            ClientRequest::DuplicateNode {
+                handle,
+                source: "/src/rainbow.shader".as_path_buf(),
+                dest: "/src/rainbow-copy.shader".as_path_buf(),
+            },
+            ClientRequest::Hello {
+                protocol_version: PROTOCOL_VERSION,
+            },
+            ClientRequest::GetCapabilities,
+            ClientRequest::ApplyEdits {
+                handle,
+                edits: vec![NodeEdit {
+                    path: "/src/main.texture/node.json".as_path_buf(),
+                    data: b"{\"width\":16}".to_vec(),
+                }],
+            },
+            ClientRequest::GetEvents,
+            ClientRequest::TestPattern {
+                fixture: TestPatternFixture {
+                    output: OutputConfig::GpioStrip {
+                        pin: 4,
+                        dither: None,
+                        power_limit: None,
+                        slew_limit: None,
+                    },
+                    led_count: 60,
+                    color_order: Some(ColorOrder::Grb),
+                },
+                pattern: Some(TestPattern::Rainbow),
+            },
+            ClientRequest::SyncClock {
+                host_time_ms: 1_700_000_000_000,
+                frame_interval_ms: 16,
+            },
+        ];
+
+        assert_all_variants(&requests, 16, |request| match request {
+            ClientRequest::Filesystem(_) => 0,
+            ClientRequest::LoadProject { .. } => 1,
+            ClientRequest::UnloadProject { .. } => 2,
+            ClientRequest::ProjectRequest { .. } => 3,
+            ClientRequest::ListAvailableProjects => 4,
+            ClientRequest::ListLoadedProjects => 5,
+            ClientRequest::StopAllProjects => 6,
+            ClientRequest::Undo { .. } => 7,
+            ClientRequest::Redo { .. } => 8,
+            ClientRequest::DuplicateNode { .. } => 9,
+            ClientRequest::Hello { .. } => 10,
+            ClientRequest::GetCapabilities => 11,
+            ClientRequest::ApplyEdits { .. } => 12,
+            ClientRequest::GetEvents => 13,
+            ClientRequest::TestPattern { .. } => 14,
+            ClientRequest::SyncClock { .. } => 15,
+        });
+        for (id, msg) in requests.into_iter().enumerate() {
+            assert_round_trip(&Message::Client(ClientMessage { id: id as u64, msg }));
+        }
+    }
+
+    #[test]
+    fn test_server_message_round_trip() {
+        use crate::server::ServerMsgBody as ServerMessagePayload;
+
+        // Heartbeats are unsolicited and use id 0
+        for (id, msg) in [
+            (
+                0,
+                ServerMessagePayload::Heartbeat {
+                    fps: 30,
+                    frame_count: 900,
+                    loaded_projects: Vec::new(),
+                    uptime_ms: 30_000,
+                },
+            ),
+            (
+                u64::MAX,
+                ServerMessagePayload::Filesystem(FsResponse::Write {
+                    path: "/test".as_path_buf(),
+                    error: None,
+                }),
+            ),
+        ] {
+            assert_round_trip(&Message::Server(ServerMessage { id, msg }));
+        }
+    }
 }
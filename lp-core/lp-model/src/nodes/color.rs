@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+/// Color value in a node config
+///
+/// Colors can be given as RGB or HSV. The engine works in RGB, so HSV colors are
+/// converted when the node is initialized; the config keeps the original form.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ColorConfig {
+    /// RGB color, components in [0, 1]
+    Rgb { r: f32, g: f32, b: f32 },
+    /// HSV color, components in [0, 1] (hue wraps around)
+    Hsv { h: f32, s: f32, v: f32 },
+}
+
+/// Stop in a color gradient
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GradientStopConfig {
+    /// Position along the gradient in [0, 1]
+    pub position: f32,
+    /// Color at this position
+    pub color: ColorConfig,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_config_hsv_round_trip() {
+        let color = ColorConfig::Hsv {
+            h: 0.5,
+            s: 0.25,
+            v: 1.0,
+        };
+        let json = crate::json::to_string(&color).unwrap();
+        assert!(json.contains("Hsv"), "HSV form should be preserved: {json}");
+        let parsed: ColorConfig = crate::json::from_str(&json).unwrap();
+        assert_eq!(parsed, color);
+    }
+}
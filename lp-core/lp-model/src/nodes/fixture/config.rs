@@ -1,5 +1,7 @@
 use crate::nodes::fixture::mapping::MappingConfig;
+use crate::nodes::fixture::white::{Rgbw, WhiteExtraction};
 use crate::nodes::{NodeConfig, NodeKind, NodeSpecifier};
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
 /// Fixture node configuration
@@ -21,6 +23,31 @@ pub struct FixtureConfig {
     /// Enable gamma correction, defaults to true if not specified
     #[serde(default = "default_gamma_correction")]
     pub gamma_correction: Option<bool>,
+    /// Output universe the fixture's channels start in, defaults to 0
+    ///
+    /// Only meaningful for universe-based outputs such as Art-Net; strips ignore it.
+    #[serde(default)]
+    pub universe: Option<u32>,
+    /// Channel offset within the universe (0-based), defaults to 0
+    #[serde(default)]
+    pub channel_offset: Option<u32>,
+    /// Whether the node renders, defaults to true (see [`NodeConfig::enabled`])
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// 3x3 matrix applied to each lamp's RGB before output, defaults to identity
+    ///
+    /// Rows give the output red, green and blue as weighted sums of the input
+    /// channels, to match the white balance of different LED strips.
+    #[serde(default)]
+    pub color_correction: Option<[[f32; 3]; 3]>,
+    /// Per-lamp alpha multiplied into the sampled colors, unmasked if not set
+    #[serde(default)]
+    pub mask: Option<MaskConfig>,
+    /// How the white channel is computed, defaults to [`WhiteExtraction::Min`]
+    ///
+    /// Only meaningful for RGBW color orders.
+    #[serde(default)]
+    pub white_extraction: Option<WhiteExtraction>,
 }
 
 fn default_brightness() -> Option<u8> {
@@ -36,12 +63,51 @@ impl NodeConfig for FixtureConfig {
         NodeKind::Fixture
     }
 
+    fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
     fn as_any(&self) -> &dyn core::any::Any {
         self
     }
 }
 
+/// Per-lamp alpha mask for a fixture
+///
+/// Alpha 0 turns a lamp off and 255 leaves it unchanged; values in between dim it.
+/// Lamps are numbered in channel order, starting at 0.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MaskConfig {
+    /// Alpha for each lamp; lamps past the end of the list are unmasked
+    Alpha { values: Vec<u8> },
+    /// Alpha values repeated along the lamps, e.g. `[255, 0]` for every other lamp
+    Pattern { values: Vec<u8> },
+    /// Turn off `count` lamps starting at `start`
+    Blank { start: u32, count: u32 },
+}
+
+impl MaskConfig {
+    /// Alpha for a lamp
+    pub fn alpha(&self, lamp: u32) -> u8 {
+        match self {
+            MaskConfig::Alpha { values } => values.get(lamp as usize).copied().unwrap_or(255),
+            MaskConfig::Pattern { values } if values.is_empty() => 255,
+            MaskConfig::Pattern { values } => values[lamp as usize % values.len()],
+            MaskConfig::Blank { start, count } => {
+                if lamp >= *start && lamp - start < *count {
+                    0
+                } else {
+                    255
+                }
+            }
+        }
+    }
+}
+
 /// Color order for RGB channels
+///
+/// The RGBW orders add a white channel (see [`WhiteExtraction`]), so each LED takes 4
+/// bytes instead of 3.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColorOrder {
     /// Red, Green, Blue
@@ -56,6 +122,10 @@ pub enum ColorOrder {
     Brg,
     /// Blue, Green, Red
     Bgr,
+    /// Red, Green, Blue, White
+    Rgbw,
+    /// Green, Red, Blue, White (SK6812 RGBW strips)
+    Grbw,
 }
 
 impl ColorOrder {
@@ -68,19 +138,44 @@ impl ColorOrder {
             ColorOrder::Gbr => "gbr",
             ColorOrder::Brg => "brg",
             ColorOrder::Bgr => "bgr",
+            ColorOrder::Rgbw => "rgbw",
+            ColorOrder::Grbw => "grbw",
         }
     }
 
-    /// Get bytes per pixel (always 3 for RGB variants)
+    /// Get bytes per pixel (3 for RGB variants, 4 for RGBW)
     pub fn bytes_per_pixel(&self) -> usize {
-        3
+        match self {
+            ColorOrder::Rgbw | ColorOrder::Grbw => 4,
+            _ => 3,
+        }
+    }
+
+    /// Check whether LEDs in this order have a white channel
+    pub fn has_white(&self) -> bool {
+        self.bytes_per_pixel() == 4
     }
 
     /// Write RGB values to buffer in the correct order
+    ///
+    /// RGBW orders take the white out of the color with [`WhiteExtraction::Min`].
     pub fn write_rgb(&self, buffer: &mut [u8], offset: usize, r: u8, g: u8, b: u8) {
-        if offset + 3 > buffer.len() {
+        let color = if self.has_white() {
+            WhiteExtraction::Min.extract(r, g, b)
+        } else {
+            Rgbw::new(r, g, b, 0)
+        };
+        self.write_rgbw(buffer, offset, color);
+    }
+
+    /// Write an RGBW color to buffer in the correct order
+    ///
+    /// Orders without a white channel drop `w`.
+    pub fn write_rgbw(&self, buffer: &mut [u8], offset: usize, color: Rgbw) {
+        if offset + self.bytes_per_pixel() > buffer.len() {
             return;
         }
+        let Rgbw { r, g, b, w } = color;
         match self {
             ColorOrder::Rgb => {
                 buffer[offset] = r;
@@ -112,6 +207,18 @@ impl ColorOrder {
                 buffer[offset + 1] = g;
                 buffer[offset + 2] = r;
             }
+            ColorOrder::Rgbw => {
+                buffer[offset] = r;
+                buffer[offset + 1] = g;
+                buffer[offset + 2] = b;
+                buffer[offset + 3] = w;
+            }
+            ColorOrder::Grbw => {
+                buffer[offset] = g;
+                buffer[offset + 1] = r;
+                buffer[offset + 2] = b;
+                buffer[offset + 3] = w;
+            }
         }
     }
 }
@@ -143,21 +250,51 @@ mod tests {
             transform: [[1.0; 4]; 4],
             brightness: None,
             gamma_correction: None,
+            universe: None,
+            channel_offset: None,
+            enabled: None,
+            color_correction: None,
+            mask: None,
+            white_extraction: None,
         };
         assert_eq!(config.kind(), NodeKind::Fixture);
     }
 
+    #[test]
+    fn test_mask_alpha() {
+        let alpha = MaskConfig::Alpha {
+            values: vec![0, 128],
+        };
+        assert_eq!([0, 1, 2].map(|lamp| alpha.alpha(lamp)), [0, 128, 255]);
+
+        let pattern = MaskConfig::Pattern {
+            values: vec![255, 0],
+        };
+        assert_eq!([0, 1, 2, 3].map(|lamp| pattern.alpha(lamp)), [255, 0, 255, 0]);
+        let empty = MaskConfig::Pattern { values: vec![] };
+        assert_eq!(empty.alpha(3), 255);
+
+        let blank = MaskConfig::Blank { start: 2, count: 2 };
+        assert_eq!(
+            [1, 2, 3, 4].map(|lamp| blank.alpha(lamp)),
+            [255, 0, 0, 255]
+        );
+    }
+
     #[test]
     fn test_color_order_as_str() {
         assert_eq!(ColorOrder::Rgb.as_str(), "rgb");
         assert_eq!(ColorOrder::Grb.as_str(), "grb");
         assert_eq!(ColorOrder::Bgr.as_str(), "bgr");
+        assert_eq!(ColorOrder::Rgbw.as_str(), "rgbw");
     }
 
     #[test]
     fn test_color_order_bytes_per_pixel() {
         assert_eq!(ColorOrder::Rgb.bytes_per_pixel(), 3);
         assert_eq!(ColorOrder::Grb.bytes_per_pixel(), 3);
+        assert_eq!(ColorOrder::Rgbw.bytes_per_pixel(), 4);
+        assert_eq!(ColorOrder::Grbw.bytes_per_pixel(), 4);
     }
 
     #[test]
@@ -180,6 +317,26 @@ mod tests {
         assert_eq!(buffer[8], 100); // R third
     }
 
+    #[test]
+    fn test_color_order_write_rgbw() {
+        // Orange with some white in it: the common 50 moves to the white channel
+        let mut buffer = [0u8; 8];
+        ColorOrder::Rgbw.write_rgb(&mut buffer, 0, 250, 150, 50);
+        assert_eq!(buffer[..4], [200, 100, 0, 50]);
+
+        ColorOrder::Grbw.write_rgb(&mut buffer, 4, 250, 150, 50);
+        assert_eq!(buffer[4..], [100, 200, 0, 50]);
+
+        // A pure color has no white
+        ColorOrder::Rgbw.write_rgb(&mut buffer, 0, 0, 255, 0);
+        assert_eq!(buffer[..4], [0, 255, 0, 0]);
+
+        // Too little room for the white channel: nothing is written
+        let mut buffer = [9u8; 3];
+        ColorOrder::Rgbw.write_rgb(&mut buffer, 0, 1, 2, 3);
+        assert_eq!(buffer, [9, 9, 9]);
+    }
+
     #[test]
     fn test_color_order_write_rgb_bounds_check() {
         let mut buffer = [0u8; 2]; // Too small
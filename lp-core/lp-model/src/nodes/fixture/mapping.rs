@@ -46,3 +46,66 @@ pub enum RingOrder {
     OuterFirst,
     // Could do a custom order later.
 }
+
+impl MappingConfig {
+    /// Total number of lamps (output channels) the mapping drives
+    pub fn lamp_count(&self) -> u32 {
+        match self {
+            MappingConfig::PathPoints { paths, .. } => paths.iter().map(PathSpec::lamp_count).sum(),
+        }
+    }
+}
+
+impl PathSpec {
+    /// Number of lamps on this path
+    pub fn lamp_count(&self) -> u32 {
+        match self {
+            PathSpec::RingArray {
+                start_ring_inclusive,
+                end_ring_exclusive,
+                ring_lamp_counts,
+                ..
+            } => (*start_ring_inclusive..*end_ring_exclusive)
+                .map(|ring| ring_lamp_counts.get(ring as usize).copied().unwrap_or(0))
+                .sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn rings(start: u32, end: u32, counts: Vec<u32>) -> PathSpec {
+        PathSpec::RingArray {
+            center: (0.5, 0.5),
+            diameter: 1.0,
+            start_ring_inclusive: start,
+            end_ring_exclusive: end,
+            ring_lamp_counts: counts,
+            offset_angle: 0.0,
+            order: RingOrder::InnerFirst,
+        }
+    }
+
+    #[test]
+    fn test_lamp_count_sums_selected_rings() {
+        let config = MappingConfig::PathPoints {
+            paths: vec![rings(1, 3, vec![1, 8, 12, 16]), rings(0, 1, vec![1])],
+            sample_diameter: 1.0,
+        };
+        assert_eq!(config.lamp_count(), 21);
+    }
+
+    #[test]
+    fn test_lamp_count_zero() {
+        let empty = MappingConfig::PathPoints {
+            paths: vec![],
+            sample_diameter: 1.0,
+        };
+        assert_eq!(empty.lamp_count(), 0);
+        // Rings past the end of the counts have no lamps
+        assert_eq!(rings(2, 4, vec![1, 8]).lamp_count(), 0);
+    }
+}
@@ -1,7 +1,9 @@
 pub mod config;
 pub mod mapping;
 pub mod state;
+pub mod white;
 
-pub use config::{ColorOrder, FixtureConfig};
+pub use config::{ColorOrder, FixtureConfig, MaskConfig};
 pub use mapping::{MappingConfig, PathSpec, RingOrder};
 pub use state::{FixtureState, MappingCell};
+pub use white::{Rgbw, WhiteExtraction};
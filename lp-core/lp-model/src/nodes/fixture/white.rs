@@ -0,0 +1,92 @@
+//! White channel extraction for RGBW fixtures
+//!
+//! An RGBW LED can show the white part of a color on its dedicated white emitter,
+//! which is brighter and better balanced than mixing red, green and blue.
+
+use serde::{Deserialize, Serialize};
+
+/// 8-bit color with a white channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgbw {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub w: u8,
+}
+
+impl Rgbw {
+    pub const fn new(r: u8, g: u8, b: u8, w: u8) -> Self {
+        Self { r, g, b, w }
+    }
+}
+
+/// How the white channel of an RGBW fixture is computed from RGB
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WhiteExtraction {
+    /// Move the white common to all three channels (their minimum) onto the white
+    /// channel, keeping the color the same
+    #[default]
+    Min,
+    /// Drive the white channel with the common white but leave RGB unchanged, for
+    /// extra brightness at the cost of washed-out colors
+    Add,
+    /// Leave the white channel off
+    Off,
+}
+
+impl WhiteExtraction {
+    /// Split an RGB color into RGBW
+    pub fn extract(self, r: u8, g: u8, b: u8) -> Rgbw {
+        let white = r.min(g).min(b);
+        match self {
+            WhiteExtraction::Min => Rgbw::new(r - white, g - white, b - white, white),
+            WhiteExtraction::Add => Rgbw::new(r, g, b, white),
+            WhiteExtraction::Off => Rgbw::new(r, g, b, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gray_is_all_white() {
+        assert_eq!(
+            WhiteExtraction::Min.extract(255, 255, 255),
+            Rgbw::new(0, 0, 0, 255)
+        );
+        assert_eq!(
+            WhiteExtraction::Min.extract(128, 128, 128),
+            Rgbw::new(0, 0, 0, 128)
+        );
+    }
+
+    #[test]
+    fn test_saturated_color_has_no_white() {
+        assert_eq!(
+            WhiteExtraction::Min.extract(255, 0, 0),
+            Rgbw::new(255, 0, 0, 0)
+        );
+        assert_eq!(
+            WhiteExtraction::Min.extract(0, 200, 100),
+            Rgbw::new(0, 200, 100, 0)
+        );
+    }
+
+    #[test]
+    fn test_extraction_modes() {
+        assert_eq!(
+            WhiteExtraction::Min.extract(250, 150, 50),
+            Rgbw::new(200, 100, 0, 50)
+        );
+        assert_eq!(
+            WhiteExtraction::Add.extract(250, 150, 50),
+            Rgbw::new(250, 150, 50, 50)
+        );
+        assert_eq!(
+            WhiteExtraction::Off.extract(250, 150, 50),
+            Rgbw::new(250, 150, 50, 0)
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::nodes::{NodeConfig, NodeKind, NodeSpecifier};
+use serde::{Deserialize, Serialize};
+
+/// Group node configuration
+///
+/// A group is a layer. Shaders (and other groups) render into it by naming the group as
+/// their `texture_spec`, the same way they would name a texture. Each frame the group
+/// clears its layer, lets its members render into it, and composites the result onto its
+/// own target with the group's opacity and blend mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupConfig {
+    /// Texture (or group) the layer is composited onto; the layer has the same size
+    pub texture_spec: NodeSpecifier,
+    /// Order among the shaders and groups drawing into the same target - lower numbers
+    /// render first (default 0)
+    #[serde(default)]
+    pub render_order: i32,
+    /// Opacity of the whole layer (0.0-1.0), defaults to 1.0
+    #[serde(default)]
+    pub opacity: Option<f32>,
+    /// How the layer combines with what is beneath it, defaults to [`BlendMode::Normal`]
+    #[serde(default)]
+    pub blend_mode: Option<BlendMode>,
+    /// Whether the node renders, defaults to true (see [`NodeConfig::enabled`])
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+impl GroupConfig {
+    /// Get the opacity, clamped to 0.0-1.0 and defaulting to fully opaque
+    pub fn opacity(&self) -> f32 {
+        self.opacity.unwrap_or(1.0).clamp(0.0, 1.0)
+    }
+
+    /// Get the blend mode, defaulting to [`BlendMode::Normal`]
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode.unwrap_or_default()
+    }
+}
+
+impl Default for GroupConfig {
+    fn default() -> Self {
+        Self {
+            texture_spec: NodeSpecifier::from(""),
+            render_order: 0,
+            opacity: None,
+            blend_mode: None,
+            enabled: None,
+        }
+    }
+}
+
+/// How a layer's colors combine with the colors beneath it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlendMode {
+    /// Layer covers what is beneath it according to its alpha
+    #[default]
+    Normal,
+    /// Colors are summed (brightens; good for light effects)
+    Add,
+    /// Colors are multiplied (darkens)
+    Multiply,
+    /// Inverted colors are multiplied (brightens, never past white)
+    Screen,
+}
+
+impl NodeConfig for GroupConfig {
+    fn kind(&self) -> NodeKind {
+        NodeKind::Group
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    fn as_any(&self) -> &dyn core::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_config_defaults() {
+        let config: GroupConfig =
+            crate::json::from_str(r#"{"texture_spec":"/src/main.texture"}"#).unwrap();
+        assert_eq!(config.kind(), NodeKind::Group);
+        assert_eq!(config.render_order, 0);
+        assert_eq!(config.opacity(), 1.0);
+        assert_eq!(config.blend_mode(), BlendMode::Normal);
+        assert!(config.enabled());
+    }
+
+    #[test]
+    fn test_group_config_opacity_clamped() {
+        let config = GroupConfig {
+            opacity: Some(1.5),
+            ..Default::default()
+        };
+        assert_eq!(config.opacity(), 1.0);
+        let config = GroupConfig {
+            opacity: Some(-0.5),
+            ..Default::default()
+        };
+        assert_eq!(config.opacity(), 0.0);
+    }
+}
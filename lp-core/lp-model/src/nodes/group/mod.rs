@@ -0,0 +1,5 @@
+pub mod config;
+pub mod state;
+
+pub use config::{BlendMode, GroupConfig};
+pub use state::GroupState;
@@ -0,0 +1,18 @@
+use crate::serde_base64;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// Group node state - runtime values
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupState {
+    /// Layer pixel data (RGBA8), as rendered by the group's members before compositing
+    #[serde(
+        serialize_with = "serde_base64::serialize",
+        deserialize_with = "serde_base64::deserialize"
+    )]
+    pub layer_data: Vec<u8>,
+    /// Layer width in pixels
+    pub width: u32,
+    /// Layer height in pixels
+    pub height: u32,
+}
@@ -5,4 +5,16 @@ pub enum NodeKind {
     Shader,
     Output,
     Fixture,
+    Group,
+}
+
+impl NodeKind {
+    /// Every node kind
+    pub const ALL: [NodeKind; 5] = [
+        NodeKind::Texture,
+        NodeKind::Shader,
+        NodeKind::Output,
+        NodeKind::Fixture,
+        NodeKind::Group,
+    ];
 }
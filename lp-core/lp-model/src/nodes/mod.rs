@@ -1,13 +1,16 @@
+pub mod color;
 pub mod handle;
 pub mod kind;
 pub mod specifier;
 
 // Node type modules
 pub mod fixture;
+pub mod group;
 pub mod output;
 pub mod shader;
 pub mod texture;
 
+pub use color::{ColorConfig, GradientStopConfig};
 pub use handle::NodeHandle;
 pub use kind::NodeKind;
 pub use specifier::NodeSpecifier;
@@ -18,6 +21,15 @@ use core::any::Any;
 pub trait NodeConfig: core::fmt::Debug {
     fn kind(&self) -> NodeKind;
 
+    /// Whether the node takes part in rendering
+    ///
+    /// A disabled node keeps its config and runtime state but is skipped at render
+    /// time, and so is everything that renders through it. Nodes are enabled unless
+    /// their config says otherwise.
+    fn enabled(&self) -> bool {
+        true
+    }
+
     /// Get reference to Any for downcasting
     fn as_any(&self) -> &dyn Any;
 }
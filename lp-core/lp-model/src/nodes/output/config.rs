@@ -1,14 +1,147 @@
 use crate::nodes::{NodeConfig, NodeKind};
+use alloc::string::String;
 use serde::{Deserialize, Serialize};
 
+/// Number of channels in a DMX universe
+///
+/// Universe-based outputs address channels as `universe * DMX_UNIVERSE_SIZE + channel`,
+/// so a fixture whose channels run past the end of its universe continues in the next
+/// one (see [`OutputConfig::channel_start`]).
+pub const DMX_UNIVERSE_SIZE: u32 = 512;
+
+/// Default Art-Net UDP port
+pub const ART_NET_DEFAULT_PORT: u16 = 6454;
+
+/// Names of the output drivers, one per [`OutputConfig`] variant as serialized
+pub const OUTPUT_DRIVERS: [&str; 3] = ["GpioStrip", "Apa102Strip", "ArtNet"];
+
 /// Output node configuration
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OutputConfig {
     /// GPIO strip output
     GpioStrip {
         pin: u32,
+        /// Dithering applied when quantizing colors to 8-bit LED values
+        #[serde(default)]
+        dither: Option<DitherMode>,
+        /// Current budget; frames estimated above it are scaled down
+        #[serde(default)]
+        power_limit: Option<PowerLimitConfig>,
+        /// Largest change per frame in any channel value, to smooth fast transitions
+        #[serde(default)]
+        slew_limit: Option<u8>,
         // channel_count: todo!(), // Will add later
     },
+    /// APA102/SK9822 clock+data strip output
+    Apa102Strip {
+        data_pin: u32,
+        clock_pin: u32,
+        /// 5-bit global brightness sent with every LED (0-31), defaults to 31
+        #[serde(default)]
+        brightness: Option<u8>,
+        /// Dithering applied when quantizing colors to 8-bit LED values
+        #[serde(default)]
+        dither: Option<DitherMode>,
+        /// Current budget; frames estimated above it are scaled down
+        #[serde(default)]
+        power_limit: Option<PowerLimitConfig>,
+        /// Largest change per frame in any channel value, to smooth fast transitions
+        #[serde(default)]
+        slew_limit: Option<u8>,
+    },
+    /// DMX over Art-Net (UDP), for non-addressable fixtures
+    ArtNet {
+        /// IPv4 address of the Art-Net node, e.g. "192.168.1.50"
+        address: String,
+        /// UDP port, defaults to [`ART_NET_DEFAULT_PORT`]
+        #[serde(default)]
+        port: Option<u16>,
+        /// Art-Net universe that output universe 0 is sent to, defaults to 0
+        #[serde(default)]
+        start_universe: Option<u16>,
+        /// Dithering applied when quantizing colors to 8-bit DMX values
+        #[serde(default)]
+        dither: Option<DitherMode>,
+        /// Current budget; frames estimated above it are scaled down
+        #[serde(default)]
+        power_limit: Option<PowerLimitConfig>,
+        /// Largest change per frame in any channel value, to smooth fast transitions
+        #[serde(default)]
+        slew_limit: Option<u8>,
+    },
+}
+
+impl OutputConfig {
+    /// Get the dither mode, defaulting to [`DitherMode::None`]
+    pub fn dither(&self) -> DitherMode {
+        match self {
+            OutputConfig::GpioStrip { dither, .. }
+            | OutputConfig::Apa102Strip { dither, .. }
+            | OutputConfig::ArtNet { dither, .. } => dither.unwrap_or_default(),
+        }
+    }
+
+    /// Get the power limit, if one is configured
+    pub fn power_limit(&self) -> Option<PowerLimitConfig> {
+        match self {
+            OutputConfig::GpioStrip { power_limit, .. }
+            | OutputConfig::Apa102Strip { power_limit, .. }
+            | OutputConfig::ArtNet { power_limit, .. } => *power_limit,
+        }
+    }
+
+    /// Get the slew limit, if one is configured
+    pub fn slew_limit(&self) -> Option<u8> {
+        match self {
+            OutputConfig::GpioStrip { slew_limit, .. }
+            | OutputConfig::Apa102Strip { slew_limit, .. }
+            | OutputConfig::ArtNet { slew_limit, .. } => *slew_limit,
+        }
+    }
+
+    /// Whether fixtures address this output by DMX universe
+    pub fn is_universe_based(&self) -> bool {
+        matches!(self, OutputConfig::ArtNet { .. })
+    }
+
+    /// Offset of `channel` in `universe` within the output's channel buffer
+    ///
+    /// Universe-based outputs lay universes out consecutively; strips are a single run
+    /// of channels, so `universe` is ignored.
+    pub fn channel_start(&self, universe: u32, channel: u32) -> u32 {
+        if self.is_universe_based() {
+            universe * DMX_UNIVERSE_SIZE + channel
+        } else {
+            channel
+        }
+    }
+}
+
+/// Power limit for an output, based on a per-channel current model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerLimitConfig {
+    /// Maximum total current for the output, in milliamps
+    pub max_ma: u32,
+    /// Current drawn by one color channel at full value (255), in milliamps
+    #[serde(default = "default_channel_ma")]
+    pub channel_ma: u32,
+}
+
+fn default_channel_ma() -> u32 {
+    // Typical WS2812: ~20mA per channel, 60mA per LED at full white
+    20
+}
+
+/// Dithering mode for converting colors to 8-bit LED values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DitherMode {
+    /// Truncate to 8 bits
+    #[default]
+    None,
+    /// Ordered dithering with a 4x4 Bayer matrix over the LED index
+    Bayer,
+    /// Ordered dithering that also varies the threshold from frame to frame
+    Temporal,
 }
 
 impl NodeConfig for OutputConfig {
@@ -27,7 +160,97 @@ mod tests {
 
     #[test]
     fn test_output_config_kind() {
-        let config = OutputConfig::GpioStrip { pin: 18 };
+        let config = OutputConfig::GpioStrip {
+            pin: 18,
+            dither: None,
+            power_limit: None,
+            slew_limit: None,
+        };
         assert_eq!(config.kind(), NodeKind::Output);
     }
+
+    #[test]
+    fn test_output_config_dither_default() {
+        let config: OutputConfig = crate::json::from_str(r#"{"GpioStrip":{"pin":18}}"#).unwrap();
+        assert_eq!(config.dither(), DitherMode::None);
+
+        let config: OutputConfig =
+            crate::json::from_str(r#"{"GpioStrip":{"pin":18,"dither":"Bayer"}}"#).unwrap();
+        assert_eq!(config.dither(), DitherMode::Bayer);
+    }
+
+    #[test]
+    fn test_output_config_apa102() {
+        let config: OutputConfig =
+            crate::json::from_str(r#"{"Apa102Strip":{"data_pin":5,"clock_pin":6}}"#).unwrap();
+        assert_eq!(
+            config,
+            OutputConfig::Apa102Strip {
+                data_pin: 5,
+                clock_pin: 6,
+                brightness: None,
+                dither: None,
+                power_limit: None,
+                slew_limit: None,
+            }
+        );
+        assert_eq!(config.kind(), NodeKind::Output);
+    }
+
+    #[test]
+    fn test_output_config_artnet() {
+        let config: OutputConfig =
+            crate::json::from_str(r#"{"ArtNet":{"address":"10.0.0.5","start_universe":3}}"#)
+                .unwrap();
+        match config {
+            OutputConfig::ArtNet {
+                address,
+                port,
+                start_universe,
+                ..
+            } => {
+                assert_eq!(address, "10.0.0.5");
+                assert_eq!(port, None);
+                assert_eq!(start_universe, Some(3));
+            }
+            other => panic!("expected ArtNet, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_output_config_channel_start() {
+        let artnet: OutputConfig =
+            crate::json::from_str(r#"{"ArtNet":{"address":"10.0.0.5"}}"#).unwrap();
+        assert_eq!(artnet.channel_start(2, 10), 2 * DMX_UNIVERSE_SIZE + 10);
+
+        let strip: OutputConfig = crate::json::from_str(r#"{"GpioStrip":{"pin":18}}"#).unwrap();
+        assert_eq!(strip.channel_start(2, 10), 10);
+    }
+
+    #[test]
+    fn test_output_config_power_limit() {
+        let config: OutputConfig = crate::json::from_str(r#"{"GpioStrip":{"pin":18}}"#).unwrap();
+        assert_eq!(config.power_limit(), None);
+
+        let config: OutputConfig =
+            crate::json::from_str(r#"{"GpioStrip":{"pin":18,"power_limit":{"max_ma":2000}}}"#)
+                .unwrap();
+        assert_eq!(
+            config.power_limit(),
+            Some(PowerLimitConfig {
+                max_ma: 2000,
+                channel_ma: 20,
+            })
+        );
+    }
+
+    #[test]
+    fn test_output_config_slew_limit() {
+        let config: OutputConfig = crate::json::from_str(r#"{"GpioStrip":{"pin":18}}"#).unwrap();
+        assert_eq!(config.slew_limit(), None);
+
+        let config: OutputConfig =
+            crate::json::from_str(r#"{"ArtNet":{"address":"10.0.0.5","slew_limit":8}}"#).unwrap();
+        assert_eq!(config.slew_limit(), Some(8));
+    }
 }
@@ -1,5 +1,8 @@
 pub mod config;
 pub mod state;
 
-pub use config::OutputConfig;
+pub use config::{
+    ART_NET_DEFAULT_PORT, DMX_UNIVERSE_SIZE, DitherMode, OUTPUT_DRIVERS, OutputConfig,
+    PowerLimitConfig,
+};
 pub use state::OutputState;
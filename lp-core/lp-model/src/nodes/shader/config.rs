@@ -1,9 +1,10 @@
-use crate::nodes::{NodeConfig, NodeKind, NodeSpecifier};
+use crate::nodes::{ColorConfig, GradientStopConfig, NodeConfig, NodeKind, NodeSpecifier};
 use crate::{AsLpPathBuf, LpPathBuf};
+use alloc::{string::String, vec::Vec};
 use serde::{Deserialize, Serialize};
 
 /// Shader node configuration
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ShaderConfig {
     /// Path to GLSL file (relative to node directory)
     pub glsl_path: LpPathBuf,
@@ -11,6 +12,76 @@ pub struct ShaderConfig {
     pub texture_spec: NodeSpecifier,
     /// Render order - lower numbers render first (default 0)
     pub render_order: i32,
+    /// Values for `uniform float` inputs declared by the shader
+    ///
+    /// Stored as a list rather than a map for `serde-json-core` compatibility.
+    #[serde(default)]
+    pub uniforms: Vec<UniformConfig>,
+    /// Sub-rectangle of the texture to render into (whole texture if not set)
+    #[serde(default)]
+    pub viewport: Option<ViewportConfig>,
+    /// Loop iterations the shader may run per frame, summed over all pixels, before
+    /// it is aborted (engine default if not set)
+    #[serde(default)]
+    pub loop_fuel: Option<u32>,
+    /// How the compiled shader is run (JIT if not set)
+    #[serde(default)]
+    pub backend: Option<ShaderBackend>,
+    /// Whether the node renders, defaults to true (see [`NodeConfig::enabled`])
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Gradient stops sampled by the shader's `palette(t)` function
+    ///
+    /// When set, the shader can call `vec3 palette(float t)` to get the gradient color
+    /// at `t`, which is clamped to [0, 1]. Stops need not be sorted.
+    #[serde(default)]
+    pub palette: Vec<GradientStopConfig>,
+    /// Solid color drawn over the shader's region while it fails to compile or run
+    ///
+    /// Makes a broken shader obvious on the LEDs; if not set, the region keeps its
+    /// last pixels. The shader draws normally again once it recovers.
+    #[serde(default)]
+    pub error_color: Option<ColorConfig>,
+}
+
+/// Execution backend for a shader
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShaderBackend {
+    /// Native code compiled for the host, falling back to the interpreter if the
+    /// shader can't be compiled to native code
+    #[default]
+    Jit,
+    /// RISC-V code run in an emulator; much slower, but faults such as division by
+    /// zero are reported with their source location instead of crashing
+    Emulator,
+    /// Tree-walking interpreter over the checked AST; slowest, but needs no code
+    /// generation, so it works where native code can't be produced or loaded
+    Interpreter,
+}
+
+/// Rectangle of texture pixels a shader renders into
+///
+/// Pixels outside the viewport are left untouched. The shader still sees
+/// `fragCoord` and `outputSize` in whole-texture coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewportConfig {
+    /// Left edge in pixels
+    pub x: u32,
+    /// Top edge in pixels
+    pub y: u32,
+    /// Width in pixels
+    pub width: u32,
+    /// Height in pixels
+    pub height: u32,
+}
+
+/// Value for a single shader uniform
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniformConfig {
+    /// Uniform name as declared in the GLSL source
+    pub name: String,
+    /// Uniform value
+    pub value: f32,
 }
 
 impl Default for ShaderConfig {
@@ -19,6 +90,13 @@ impl Default for ShaderConfig {
             glsl_path: "main.glsl".as_path_buf(),
             texture_spec: NodeSpecifier::from(""),
             render_order: 0,
+            uniforms: Vec::new(),
+            viewport: None,
+            loop_fuel: None,
+            backend: None,
+            enabled: None,
+            palette: Vec::new(),
+            error_color: None,
         }
     }
 }
@@ -28,6 +106,10 @@ impl NodeConfig for ShaderConfig {
         NodeKind::Shader
     }
 
+    fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
     fn as_any(&self) -> &dyn core::any::Any {
         self
     }
@@ -43,6 +125,13 @@ mod tests {
             glsl_path: "main.glsl".as_path_buf(),
             texture_spec: NodeSpecifier::from("/src/tex.texture"),
             render_order: 0,
+            uniforms: Vec::new(),
+            viewport: None,
+            loop_fuel: None,
+            backend: None,
+            enabled: None,
+            palette: Vec::new(),
+            error_color: None,
         };
         assert_eq!(config.kind(), NodeKind::Shader);
     }
@@ -52,5 +141,86 @@ mod tests {
         let config = ShaderConfig::default();
         assert_eq!(config.glsl_path.as_str(), "main.glsl");
         assert_eq!(config.render_order, 0);
+        assert!(config.uniforms.is_empty());
+        assert!(config.viewport.is_none());
+        assert!(config.loop_fuel.is_none());
+        assert!(config.backend.is_none());
+        assert!(config.palette.is_empty());
+        assert!(config.error_color.is_none());
+    }
+
+    #[test]
+    fn test_shader_config_viewport_roundtrip() {
+        let config = ShaderConfig {
+            viewport: Some(ViewportConfig {
+                x: 1,
+                y: 2,
+                width: 3,
+                height: 4,
+            }),
+            ..ShaderConfig::default()
+        };
+        let json = crate::json::to_string(&config).unwrap();
+        let parsed: ShaderConfig = crate::json::from_str(&json).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_shader_config_backend_roundtrip() {
+        let config = ShaderConfig {
+            backend: Some(ShaderBackend::Emulator),
+            ..ShaderConfig::default()
+        };
+        let json = crate::json::to_string(&config).unwrap();
+        assert!(json.contains(r#""backend":"Emulator""#), "json: {json}");
+        let parsed: ShaderConfig = crate::json::from_str(&json).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_shader_config_uniforms_default_when_missing() {
+        let json =
+            r#"{"glsl_path":"main.glsl","texture_spec":"/src/tex.texture","render_order":0}"#;
+        let config: ShaderConfig = crate::json::from_str(json).unwrap();
+        assert!(config.uniforms.is_empty());
+
+        let config = ShaderConfig {
+            uniforms: alloc::vec![UniformConfig {
+                name: String::from("speed"),
+                value: 2.5,
+            }],
+            ..config
+        };
+        let json = crate::json::to_string(&config).unwrap();
+        let parsed: ShaderConfig = crate::json::from_str(&json).unwrap();
+        assert_eq!(parsed, config);
+    }
+
+    #[test]
+    fn test_shader_config_palette_roundtrip() {
+        let config = ShaderConfig {
+            palette: alloc::vec![
+                GradientStopConfig {
+                    position: 0.0,
+                    color: ColorConfig::Rgb {
+                        r: 1.0,
+                        g: 0.0,
+                        b: 0.0,
+                    },
+                },
+                GradientStopConfig {
+                    position: 1.0,
+                    color: ColorConfig::Hsv {
+                        h: 0.5,
+                        s: 1.0,
+                        v: 1.0,
+                    },
+                },
+            ],
+            ..ShaderConfig::default()
+        };
+        let json = crate::json::to_string(&config).unwrap();
+        let parsed: ShaderConfig = crate::json::from_str(&json).unwrap();
+        assert_eq!(parsed, config);
     }
 }
@@ -1,5 +1,5 @@
 pub mod config;
 pub mod state;
 
-pub use config::ShaderConfig;
+pub use config::{ShaderBackend, ShaderConfig, UniformConfig, ViewportConfig};
 pub use state::ShaderState;
@@ -1,19 +1,54 @@
-use crate::nodes::{NodeConfig, NodeKind};
+use crate::LpPathBuf;
+use crate::nodes::{ColorConfig, NodeConfig, NodeKind};
 use serde::{Deserialize, Serialize};
 
 /// Texture node configuration
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TextureConfig {
     pub width: u32,
     pub height: u32,
+    /// Image file (PNG or BMP) to load into the texture, relative to the node directory.
+    /// When set, the texture takes its dimensions from the image.
+    #[serde(default)]
+    pub image_path: Option<LpPathBuf>,
+    /// Solid color to fill the texture with at init (ignored when `image_path` is set)
+    #[serde(default)]
+    pub fill_color: Option<ColorConfig>,
+    /// How fixtures sample the texture, defaults to [`TextureFilter::Nearest`]
+    #[serde(default)]
+    pub filter: Option<TextureFilter>,
+    /// Whether the node renders, defaults to true (see [`NodeConfig::enabled`])
+    #[serde(default)]
+    pub enabled: Option<bool>,
     // format: todo!() - will be added later
 }
 
+impl TextureConfig {
+    /// Get the filter mode, defaulting to [`TextureFilter::Nearest`]
+    pub fn filter(&self) -> TextureFilter {
+        self.filter.unwrap_or_default()
+    }
+}
+
+/// Filtering used when sampling a texture at fixture lamp positions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextureFilter {
+    /// Average the texels under each lamp's sample area (blocky when upscaling)
+    #[default]
+    Nearest,
+    /// Interpolate between the four texels nearest each lamp
+    Bilinear,
+}
+
 impl NodeConfig for TextureConfig {
     fn kind(&self) -> NodeKind {
         NodeKind::Texture
     }
 
+    fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
     fn as_any(&self) -> &dyn core::any::Any {
         self
     }
@@ -28,7 +63,27 @@ mod tests {
         let config = TextureConfig {
             width: 100,
             height: 200,
+            image_path: None,
+            fill_color: None,
+            filter: None,
+            enabled: None,
         };
         assert_eq!(config.kind(), NodeKind::Texture);
+        assert_eq!(config.filter(), TextureFilter::Nearest);
+    }
+
+    #[test]
+    fn test_texture_config_filter_roundtrip() {
+        let config = TextureConfig {
+            width: 2,
+            height: 1,
+            image_path: None,
+            fill_color: None,
+            filter: Some(TextureFilter::Bilinear),
+            enabled: None,
+        };
+        let json = crate::json::to_string(&config).unwrap();
+        let parsed: TextureConfig = crate::json::from_str(&json).unwrap();
+        assert_eq!(parsed.filter(), TextureFilter::Bilinear);
     }
 }
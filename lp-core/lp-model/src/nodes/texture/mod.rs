@@ -1,5 +1,5 @@
 pub mod config;
 pub mod state;
 
-pub use config::TextureConfig;
+pub use config::{TextureConfig, TextureFilter};
 pub use state::TextureState;
@@ -1,6 +1,7 @@
 use crate::nodes::{NodeConfig, NodeHandle, NodeKind};
 use crate::nodes::{
-    fixture::FixtureConfig, output::OutputConfig, shader::ShaderConfig, texture::TextureConfig,
+    fixture::FixtureConfig, group::GroupConfig, output::OutputConfig, shader::ShaderConfig,
+    texture::TextureConfig,
 };
 use crate::path::LpPathBuf;
 use crate::project::FrameId;
@@ -130,6 +131,7 @@ pub enum NodeState {
     Shader(crate::nodes::shader::ShaderState),
     Output(crate::nodes::output::OutputState),
     Fixture(crate::nodes::fixture::FixtureState),
+    Group(crate::nodes::group::GroupState),
 }
 
 /// Serializable wrapper for NodeDetail
@@ -162,6 +164,12 @@ pub enum SerializableNodeDetail {
         config: FixtureConfig,
         state: NodeState,
     },
+    /// Group node detail
+    Group {
+        path: LpPathBuf,
+        config: GroupConfig,
+        state: NodeState,
+    },
 }
 
 /// Serializable wrapper for ProjectResponse
@@ -244,6 +252,18 @@ impl NodeDetail {
                     state: self.state.clone(),
                 })
             }
+            NodeKind::Group => {
+                let config = self
+                    .config
+                    .as_any()
+                    .downcast_ref::<GroupConfig>()
+                    .ok_or_else(|| format!("Failed to downcast to GroupConfig"))?;
+                Ok(SerializableNodeDetail::Group {
+                    path: self.path.clone(),
+                    config: config.clone(),
+                    state: self.state.clone(),
+                })
+            }
         }
     }
 }
@@ -281,6 +301,7 @@ impl ProjectResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{assert_all_variants, assert_round_trip};
     use alloc::{string::ToString, vec};
 
     #[test]
@@ -353,6 +374,10 @@ mod tests {
             config: Box::new(TextureConfig {
                 width: 100,
                 height: 200,
+                image_path: None,
+                fill_color: None,
+                filter: None,
+                enabled: None,
             }),
             state: NodeState::Texture(crate::nodes::texture::TextureState {
                 texture_data: vec![0, 1, 2, 3],
@@ -413,6 +438,10 @@ mod tests {
                 config: Box::new(TextureConfig {
                     width: 100,
                     height: 200,
+                    image_path: None,
+                    fill_color: None,
+                    filter: None,
+                    enabled: None,
                 }),
                 state: NodeState::Texture(crate::nodes::texture::TextureState {
                     texture_data: vec![0, 1, 2, 3],
@@ -461,6 +490,10 @@ mod tests {
             config: TextureConfig {
                 width: 100,
                 height: 200,
+                image_path: None,
+                fill_color: None,
+                filter: None,
+                enabled: None,
             },
             state: NodeState::Texture(crate::nodes::texture::TextureState {
                 texture_data: vec![0, 1, 2, 3],
@@ -496,6 +529,10 @@ mod tests {
                 config: TextureConfig {
                     width: 100,
                     height: 200,
+                    image_path: None,
+                    fill_color: None,
+                    filter: None,
+                    enabled: None,
                 },
                 state: NodeState::Texture(crate::nodes::texture::TextureState {
                     texture_data: vec![0, 1, 2, 3],
@@ -531,4 +568,224 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_project_request_round_trip() {
+        let requests = vec![
+            ProjectRequest::GetChanges {
+                since_frame: FrameId::default(),
+                detail_specifier: ApiNodeSpecifier::None,
+            },
+            ProjectRequest::GetChanges {
+                since_frame: FrameId::new(42),
+                detail_specifier: ApiNodeSpecifier::All,
+            },
+            ProjectRequest::GetChanges {
+                since_frame: FrameId::new(-1),
+                detail_specifier: ApiNodeSpecifier::ByHandles(vec![
+                    NodeHandle::new(1),
+                    NodeHandle::new(7),
+                ]),
+            },
+        ];
+        for request in &requests {
+            assert_round_trip(request);
+        }
+    }
+
+    #[test]
+    fn test_node_change_round_trip() {
+        let statuses = vec![
+            NodeStatus::Created,
+            NodeStatus::InitError("missing texture \"/src/a.texture\"".to_string()),
+            NodeStatus::Ok,
+            NodeStatus::Warn(String::new()),
+            NodeStatus::Error("line 3:\n\tdivision by zero".to_string()),
+        ];
+        assert_all_variants(&statuses, 5, |status| match status {
+            NodeStatus::Created => 0,
+            NodeStatus::InitError(_) => 1,
+            NodeStatus::Ok => 2,
+            NodeStatus::Warn(_) => 3,
+            NodeStatus::Error(_) => 4,
+        });
+
+        let handle = NodeHandle::new(3);
+        let mut changes = vec![
+            NodeChange::Created {
+                handle,
+                path: LpPathBuf::from("/src/rainbow.shader"),
+                kind: NodeKind::Shader,
+            },
+            NodeChange::ConfigUpdated {
+                handle,
+                config_ver: FrameId::new(10),
+            },
+            NodeChange::StateUpdated {
+                handle,
+                state_ver: FrameId::new(11),
+            },
+            NodeChange::Removed { handle },
+        ];
+        for status in statuses {
+            changes.push(NodeChange::StatusChanged { handle, status });
+        }
+
+        assert_all_variants(&changes, 5, |change| match change {
+            NodeChange::Created { .. } => 0,
+            NodeChange::ConfigUpdated { .. } => 1,
+            NodeChange::StateUpdated { .. } => 2,
+            NodeChange::StatusChanged { .. } => 3,
+            NodeChange::Removed { .. } => 4,
+        });
+        for change in &changes {
+            assert_round_trip(change);
+        }
+    }
+
+    #[test]
+    fn test_serializable_project_response_round_trip() {
+        use crate::nodes::fixture::{
+            ColorOrder, FixtureState, MappingCell, MappingConfig, PathSpec, RingOrder,
+        };
+        use crate::nodes::group::{BlendMode, GroupState};
+        use crate::nodes::output::{OutputState, PowerLimitConfig};
+        use crate::nodes::shader::ShaderState;
+        use crate::nodes::texture::TextureState;
+
+        let details = vec![
+            SerializableNodeDetail::Texture {
+                path: LpPathBuf::from("/src/main.texture"),
+                config: TextureConfig {
+                    width: 16,
+                    height: 16,
+                    image_path: None,
+                    fill_color: None,
+                    filter: None,
+                    enabled: None,
+                },
+                state: NodeState::Texture(TextureState {
+                    texture_data: vec![0, 127, 255, 1],
+                    width: 1,
+                    height: 1,
+                    format: "RGBA8".to_string(),
+                }),
+            },
+            SerializableNodeDetail::Shader {
+                path: LpPathBuf::from("/src/rainbow.shader"),
+                config: ShaderConfig::default(),
+                state: NodeState::Shader(ShaderState {
+                    glsl_code: "vec4 main() {\n    return vec4(\"\\\");\n}".to_string(),
+                    error: Some("unexpected token".to_string()),
+                }),
+            },
+            SerializableNodeDetail::Output {
+                path: LpPathBuf::from("/src/strip.output"),
+                config: OutputConfig::GpioStrip {
+                    pin: 4,
+                    dither: None,
+                    power_limit: Some(PowerLimitConfig {
+                        max_ma: 2000,
+                        channel_ma: 20,
+                    }),
+                    slew_limit: Some(16),
+                },
+                state: NodeState::Output(OutputState {
+                    channel_data: Vec::new(),
+                }),
+            },
+            SerializableNodeDetail::Fixture {
+                path: LpPathBuf::from("/src/ring.fixture"),
+                config: FixtureConfig {
+                    output_spec: "/src/strip.output".into(),
+                    texture_spec: "/src/main.texture".into(),
+                    mapping: MappingConfig::PathPoints {
+                        paths: vec![PathSpec::RingArray {
+                            center: (0.5, 0.5),
+                            diameter: 1.0,
+                            start_ring_inclusive: 0,
+                            end_ring_exclusive: 2,
+                            ring_lamp_counts: vec![1, 8],
+                            offset_angle: 0.25,
+                            order: RingOrder::OuterFirst,
+                        }],
+                        sample_diameter: 2.0,
+                    },
+                    color_order: ColorOrder::Grb,
+                    transform: [
+                        [1.0, 0.0, 0.0, 0.0],
+                        [0.0, 1.0, 0.0, 0.0],
+                        [0.0, 0.0, 1.0, 0.0],
+                        [0.0, 0.0, 0.0, 1.0],
+                    ],
+                    brightness: Some(64),
+                    gamma_correction: Some(false),
+                    universe: None,
+                    channel_offset: Some(3),
+                    enabled: None,
+                    color_correction: None,
+                    mask: None,
+                    white_extraction: None,
+                },
+                state: NodeState::Fixture(FixtureState {
+                    lamp_colors: vec![255, 0, 0],
+                    mapping_cells: vec![MappingCell {
+                        channel: 0,
+                        center: [0.5, 0.5],
+                        radius: 0.125,
+                    }],
+                    texture_handle: Some(NodeHandle::new(1)),
+                    output_handle: None,
+                }),
+            },
+            SerializableNodeDetail::Group {
+                path: LpPathBuf::from("/src/layer.group"),
+                config: GroupConfig {
+                    texture_spec: "/src/main.texture".into(),
+                    render_order: -2,
+                    opacity: Some(0.5),
+                    blend_mode: Some(BlendMode::Screen),
+                    enabled: Some(false),
+                },
+                state: NodeState::Group(GroupState {
+                    layer_data: vec![255, 0, 0, 128],
+                    width: 1,
+                    height: 1,
+                }),
+            },
+        ];
+        assert_all_variants(&details, 5, |detail| match detail {
+            SerializableNodeDetail::Texture { .. } => 0,
+            SerializableNodeDetail::Shader { .. } => 1,
+            SerializableNodeDetail::Output { .. } => 2,
+            SerializableNodeDetail::Fixture { .. } => 3,
+            SerializableNodeDetail::Group { .. } => 4,
+        });
+        for detail in &details {
+            assert_round_trip(detail);
+        }
+
+        let responses = vec![
+            SerializableProjectResponse::GetChanges {
+                current_frame: FrameId::default(),
+                node_handles: Vec::new(),
+                node_changes: Vec::new(),
+                node_details: Vec::new(),
+                theoretical_fps: None,
+            },
+            SerializableProjectResponse::GetChanges {
+                current_frame: FrameId::new(120),
+                node_handles: (1..=4).map(NodeHandle::new).collect(),
+                node_changes: vec![NodeChange::StatusChanged {
+                    handle: NodeHandle::new(2),
+                    status: NodeStatus::Ok,
+                }],
+                node_details: (1..=4).map(NodeHandle::new).zip(details).collect(),
+                theoretical_fps: Some(59.5),
+            },
+        ];
+        for response in &responses {
+            assert_round_trip(response);
+        }
+    }
 }
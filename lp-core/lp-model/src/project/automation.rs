@@ -0,0 +1,105 @@
+use crate::nodes::NodeSpecifier;
+use crate::project::FrameId;
+use alloc::{string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+/// Scheduled changes to one numeric node parameter
+///
+/// Before the first keyframe the parameter is left alone; between keyframes the value
+/// is linearly interpolated, and after the last keyframe it holds the last value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationTrack {
+    /// Node to automate (absolute path within the project, e.g. `/src/main.fixture`)
+    pub node: NodeSpecifier,
+    /// Parameter name (a shader uniform, or `brightness` on a fixture)
+    pub param: String,
+    /// Keyframes; may be listed in any order, see [`AutomationTrack::sort_keyframes`]
+    pub keyframes: Vec<Keyframe>,
+}
+
+/// Parameter value at a specific frame
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    /// Frame the value is reached at
+    pub frame: FrameId,
+    /// Parameter value
+    pub value: f32,
+}
+
+impl AutomationTrack {
+    /// Sort keyframes by frame, as [`AutomationTrack::value_at`] expects
+    pub fn sort_keyframes(&mut self) {
+        self.keyframes.sort_by_key(|keyframe| keyframe.frame);
+    }
+
+    /// Parameter value at `frame`, or `None` before the first keyframe
+    ///
+    /// Keyframes must be sorted.
+    pub fn value_at(&self, frame: FrameId) -> Option<f32> {
+        let first = self.keyframes.first()?;
+        if frame < first.frame {
+            return None;
+        }
+
+        for pair in self.keyframes.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            if frame < to.frame {
+                let t = frame.diff(from.frame) as f32 / to.frame.diff(from.frame) as f32;
+                return Some(from.value + (to.value - from.value) * t);
+            }
+        }
+
+        self.keyframes.last().map(|keyframe| keyframe.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+
+    fn track(keyframes: Vec<Keyframe>) -> AutomationTrack {
+        let mut track = AutomationTrack {
+            node: NodeSpecifier::from("/src/main.fixture"),
+            param: "brightness".to_string(),
+            keyframes,
+        };
+        track.sort_keyframes();
+        track
+    }
+
+    fn keyframe(frame: i64, value: f32) -> Keyframe {
+        Keyframe {
+            frame: FrameId::new(frame),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_value_at_interpolates_between_keyframes() {
+        // Listed out of order on purpose
+        let track = track(vec![keyframe(300, 1.0), keyframe(100, 0.0)]);
+
+        assert_eq!(track.value_at(FrameId::new(50)), None);
+        assert_eq!(track.value_at(FrameId::new(100)), Some(0.0));
+        assert_eq!(track.value_at(FrameId::new(150)), Some(0.25));
+        assert_eq!(track.value_at(FrameId::new(300)), Some(1.0));
+        assert_eq!(track.value_at(FrameId::new(1000)), Some(1.0));
+    }
+
+    #[test]
+    fn test_value_at_steps_on_repeated_frame() {
+        let track = track(vec![keyframe(0, 0.0), keyframe(10, 0.5), keyframe(10, 1.0)]);
+
+        assert_eq!(track.value_at(FrameId::new(5)), Some(0.25));
+        assert_eq!(track.value_at(FrameId::new(10)), Some(1.0));
+    }
+
+    #[test]
+    fn test_track_round_trip() {
+        let track = track(vec![keyframe(0, 0.0), keyframe(60, 0.75)]);
+        let json = crate::json::to_string(&track).unwrap();
+        let deserialized: AutomationTrack = crate::json::from_str(&json).unwrap();
+        assert_eq!(deserialized, track);
+    }
+}
@@ -1,13 +1,33 @@
-use alloc::string::String;
+use super::automation::AutomationTrack;
+use alloc::{string::String, vec::Vec};
 use serde::{Deserialize, Serialize};
 
+/// Current `project.json` schema version
+///
+/// - 1: `uid` and `name` only (files without a `version` field)
+/// - 2: adds `version` and `seed`
+/// - 3: adds `automation`
+///
+/// Bump this and add a migration in [`super::migration`] when the schema changes.
+pub const PROJECT_CONFIG_VERSION: u32 = 3;
+
 /// Project configuration - minimal, no nodes field
 ///
 /// Nodes are discovered from filesystem, not stored in config.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Load it with [`super::parse_project_config`] so older files are migrated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProjectConfig {
+    /// Schema version, see [`PROJECT_CONFIG_VERSION`]
+    pub version: u32,
     pub uid: String,
     pub name: String,
+    /// Seed for node randomness; renders are reproducible for a given seed
+    /// (defaults to 0)
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Parameter changes scheduled at specific frames
+    #[serde(default)]
+    pub automation: Vec<AutomationTrack>,
 }
 
 #[cfg(test)]
@@ -18,10 +38,26 @@ mod tests {
     #[test]
     fn test_project_config_creation() {
         let config = ProjectConfig {
+            version: PROJECT_CONFIG_VERSION,
             uid: "test-uid".to_string(),
             name: "Test Project".to_string(),
+            seed: None,
+            automation: Vec::new(),
         };
         assert_eq!(config.uid, "test-uid");
         assert_eq!(config.name, "Test Project");
     }
+
+    #[test]
+    fn test_project_config_seed_optional() {
+        let config: ProjectConfig =
+            crate::json::from_str(r#"{"version":3,"uid":"test","name":"Test Project"}"#).unwrap();
+        assert_eq!(config.seed, None);
+        assert!(config.automation.is_empty());
+
+        let config: ProjectConfig =
+            crate::json::from_str(r#"{"version":3,"uid":"test","name":"Test Project","seed":42}"#)
+                .unwrap();
+        assert_eq!(config.seed, Some(42));
+    }
 }
@@ -1,4 +1,9 @@
 /// Frame identifier - increments each render frame
+///
+/// Arithmetic saturates rather than wraps: a frame id stuck at `i64::MAX` keeps every
+/// comparison meaningful, where a wrapped one would look older than every frame before
+/// it. (At 1000 fps the counter takes about 290 million years to fill, so in practice
+/// this only guards against corrupt or hostile values.)
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
 )]
@@ -14,8 +19,26 @@ impl FrameId {
         self.0
     }
 
+    /// The following frame (saturating at `i64::MAX`)
     pub fn next(self) -> Self {
-        Self(self.0 + 1)
+        self.saturating_add(1)
+    }
+
+    /// Advance by `frames` (negative to go back), saturating at the ends of the range
+    pub fn saturating_add(self, frames: i64) -> Self {
+        Self(self.0.saturating_add(frames))
+    }
+
+    /// Frames from `earlier` to this frame, negative if `earlier` is actually later
+    ///
+    /// Saturates at the ends of the `i64` range instead of overflowing.
+    pub fn diff(self, earlier: FrameId) -> i64 {
+        self.0.saturating_sub(earlier.0)
+    }
+
+    /// Whether this frame comes after `other`
+    pub fn is_after(self, other: FrameId) -> bool {
+        self.0 > other.0
     }
 }
 
@@ -42,6 +65,40 @@ mod tests {
         assert_eq!(next.as_i64(), 11);
     }
 
+    #[test]
+    fn test_frame_id_saturates_at_max() {
+        let last = FrameId::new(i64::MAX);
+        assert_eq!(last.next(), last);
+        assert_eq!(FrameId::new(i64::MAX - 1).next(), last);
+        assert_eq!(FrameId::new(i64::MAX - 5).saturating_add(10), last);
+        assert_eq!(
+            FrameId::new(i64::MIN + 5).saturating_add(-10).as_i64(),
+            i64::MIN
+        );
+        assert_eq!(FrameId::new(10).saturating_add(-3).as_i64(), 7);
+    }
+
+    #[test]
+    fn test_frame_id_diff() {
+        assert_eq!(FrameId::new(15).diff(FrameId::new(10)), 5);
+        assert_eq!(FrameId::new(10).diff(FrameId::new(15)), -5);
+        assert_eq!(FrameId::new(i64::MAX).diff(FrameId::new(i64::MAX - 3)), 3);
+        assert_eq!(FrameId::new(i64::MAX).diff(FrameId::new(-1)), i64::MAX);
+        assert_eq!(FrameId::new(i64::MIN).diff(FrameId::new(1)), i64::MIN);
+    }
+
+    #[test]
+    fn test_frame_id_is_after() {
+        let last = FrameId::new(i64::MAX);
+        let before = FrameId::new(i64::MAX - 1);
+        assert!(last.is_after(before));
+        assert!(!before.is_after(last));
+        assert!(!last.is_after(last));
+        // Saturated ids never appear to go backwards
+        assert!(!last.next().is_after(last));
+        assert!(!before.next().next().is_after(last));
+    }
+
     #[test]
     fn test_frame_id_default() {
         let id = FrameId::default();
@@ -0,0 +1,164 @@
+//! Loading `project.json` files written with older schema versions
+//!
+//! Files are parsed with the struct matching their declared `version` and then
+//! upgraded one version at a time to the current [`ProjectConfig`].
+
+use super::config::{PROJECT_CONFIG_VERSION, ProjectConfig};
+use crate::json;
+use alloc::{string::String, vec::Vec};
+use serde::Deserialize;
+
+/// Error loading a project config
+#[derive(Debug)]
+pub enum ProjectConfigError {
+    /// The file is not valid JSON for its declared version
+    Parse(json::Error),
+    /// The file declares a version this build doesn't know how to load
+    UnsupportedVersion {
+        /// Version found in the file
+        version: u32,
+        /// Newest version this build supports
+        supported: u32,
+    },
+}
+
+impl core::fmt::Display for ProjectConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProjectConfigError::Parse(e) => write!(f, "{e}"),
+            ProjectConfigError::UnsupportedVersion { version, supported }
+                if version > supported =>
+            {
+                write!(
+                    f,
+                    "project.json version {version} is newer than the latest supported version {supported}; update the firmware or CLI"
+                )
+            }
+            ProjectConfigError::UnsupportedVersion { version, .. } => {
+                write!(f, "project.json version {version} is not a valid version")
+            }
+        }
+    }
+}
+
+impl From<json::Error> for ProjectConfigError {
+    fn from(e: json::Error) -> Self {
+        ProjectConfigError::Parse(e)
+    }
+}
+
+/// Just the version field, read before choosing how to parse the rest
+#[derive(Deserialize)]
+struct VersionProbe {
+    #[serde(default)]
+    version: Option<u32>,
+}
+
+/// Version 1: no `version` field, no seed
+#[derive(Deserialize)]
+struct ProjectConfigV1 {
+    uid: String,
+    name: String,
+}
+
+impl From<ProjectConfigV1> for ProjectConfigV2 {
+    fn from(v1: ProjectConfigV1) -> Self {
+        ProjectConfigV2 {
+            uid: v1.uid,
+            name: v1.name,
+            seed: None,
+        }
+    }
+}
+
+/// Version 2: adds `seed`, no automation
+#[derive(Deserialize)]
+struct ProjectConfigV2 {
+    uid: String,
+    name: String,
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+impl From<ProjectConfigV2> for ProjectConfig {
+    fn from(v2: ProjectConfigV2) -> Self {
+        ProjectConfig {
+            version: PROJECT_CONFIG_VERSION,
+            uid: v2.uid,
+            name: v2.name,
+            seed: v2.seed,
+            automation: Vec::new(),
+        }
+    }
+}
+
+/// Parse a `project.json`, migrating older versions to the current schema
+pub fn parse_project_config(data: &[u8]) -> Result<ProjectConfig, ProjectConfigError> {
+    // Files written before versioning was added have no version field
+    let probe: VersionProbe = json::from_slice(data)?;
+    let version = probe.version.unwrap_or(1);
+
+    match version {
+        1 => Ok(ProjectConfigV2::from(json::from_slice::<ProjectConfigV1>(data)?).into()),
+        2 => Ok(json::from_slice::<ProjectConfigV2>(data)?.into()),
+        PROJECT_CONFIG_VERSION => Ok(json::from_slice(data)?),
+        _ => Err(ProjectConfigError::UnsupportedVersion {
+            version,
+            supported: PROJECT_CONFIG_VERSION,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_migrate_v1() {
+        let config = parse_project_config(br#"{"uid":"abc","name":"Old Project"}"#).unwrap();
+        assert_eq!(
+            config,
+            ProjectConfig {
+                version: PROJECT_CONFIG_VERSION,
+                uid: "abc".to_string(),
+                name: "Old Project".to_string(),
+                seed: None,
+                automation: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_migrate_v2() {
+        let config =
+            parse_project_config(br#"{"version":2,"uid":"abc","name":"Seeded","seed":9}"#).unwrap();
+        assert_eq!(config.version, PROJECT_CONFIG_VERSION);
+        assert_eq!(config.seed, Some(9));
+        assert!(config.automation.is_empty());
+    }
+
+    #[test]
+    fn test_parse_current_version() {
+        let config = ProjectConfig {
+            version: PROJECT_CONFIG_VERSION,
+            uid: "abc".to_string(),
+            name: "Project".to_string(),
+            seed: Some(7),
+            automation: Vec::new(),
+        };
+        let json = json::to_string(&config).unwrap();
+        assert_eq!(parse_project_config(json.as_bytes()).unwrap(), config);
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let err =
+            parse_project_config(br#"{"version":99,"uid":"abc","name":"Future"}"#).unwrap_err();
+        assert!(matches!(
+            err,
+            ProjectConfigError::UnsupportedVersion { version: 99, .. }
+        ));
+        assert!(err.to_string().contains("newer"));
+    }
+}
@@ -1,12 +1,16 @@
 pub mod api;
+pub mod automation;
 pub mod config;
 pub mod frame_id;
 pub mod handle;
+pub mod migration;
 
 pub use api::{
     ApiNodeSpecifier, NodeChange, NodeDetail, NodeState, NodeStatus, ProjectRequest,
     ProjectResponse, SerializableNodeDetail, SerializableProjectResponse,
 };
-pub use config::ProjectConfig;
+pub use automation::{AutomationTrack, Keyframe};
+pub use config::{PROJECT_CONFIG_VERSION, ProjectConfig};
 pub use frame_id::FrameId;
 pub use handle::ProjectHandle;
+pub use migration::{ProjectConfigError, parse_project_config};
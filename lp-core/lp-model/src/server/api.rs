@@ -1,11 +1,12 @@
 use crate::LpPathBuf;
-use crate::project::{ProjectHandle, ProjectRequest, api::SerializableProjectResponse};
+use crate::nodes::NodeKind;
+use crate::project::{FrameId, ProjectHandle, ProjectRequest, api::SerializableProjectResponse};
 use crate::server::fs_api::{FsRequest, FsResponse};
 use alloc::string::String;
 use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ClientMsgBody {
     /// Filesystem operation request
@@ -25,7 +26,7 @@ pub enum ClientMsgBody {
     ListLoadedProjects,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ServerMsgBody {
     /// Filesystem operation response
@@ -53,6 +54,38 @@ pub enum ServerMsgBody {
     },
     /// Response to StopAllProjects
     StopAllProjects,
+    /// Response to Undo; `applied` is false when there was nothing to undo
+    Undo {
+        applied: bool,
+    },
+    /// Response to Redo; `applied` is false when there was nothing to redo
+    Redo {
+        applied: bool,
+    },
+    /// Response to DuplicateNode with the path the copy was written to
+    DuplicateNode {
+        path: LpPathBuf,
+    },
+    /// Response to Hello with the protocol version agreed for the connection
+    Hello {
+        protocol_version: u32,
+    },
+    /// Response to GetCapabilities
+    GetCapabilities {
+        capabilities: Capabilities,
+    },
+    /// Response to ApplyEdits once the whole batch is applied
+    ApplyEdits,
+    /// Response to GetEvents
+    GetEvents {
+        events: Vec<ServerEvent>,
+    },
+    /// Response to TestPattern once the pattern is showing (or cleared)
+    TestPattern,
+    /// Response to SyncClock with the frame of the host's clock the server is now on
+    SyncClock {
+        frame: FrameId,
+    },
 
     Log {
         level: LogLevel,
@@ -103,13 +136,234 @@ pub enum LogLevel {
     Error,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AvailableProject {
     pub path: LpPathBuf,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LoadedProject {
     pub handle: ProjectHandle,
     pub path: LpPathBuf,
 }
+
+/// A significant event on the server, kept in its event log for debugging
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerEvent {
+    /// Server uptime in milliseconds when the event happened
+    pub uptime_ms: u64,
+    pub kind: ServerEventKind,
+}
+
+/// What happened in a [`ServerEvent`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ServerEventKind {
+    /// A project was loaded
+    ProjectLoaded { path: LpPathBuf },
+    /// A project was unloaded
+    ProjectUnloaded { path: LpPathBuf },
+    /// A node went into an error state (logged once until it recovers)
+    NodeError {
+        project: LpPathBuf,
+        node: LpPathBuf,
+        error: String,
+    },
+    /// A project file was written or deleted through the server
+    FileEdited { path: LpPathBuf },
+}
+
+/// What a server supports, so clients can adapt instead of assuming
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// Newest protocol version the server speaks
+    pub protocol_version: u32,
+    /// Node kinds projects can contain
+    pub node_kinds: Vec<NodeKind>,
+    /// Output drivers, named like the `OutputConfig` variants (e.g. `"ArtNet"`)
+    pub output_drivers: Vec<String>,
+    /// Most fixture nodes a project may have, if the server sets a limit
+    pub max_fixtures: Option<u32>,
+    /// Optional features compiled into the server (e.g. `"cranelift-optimizer"`)
+    pub features: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AsLpPathBuf;
+    use crate::nodes::NodeHandle;
+    use crate::project::FrameId;
+    use crate::project::api::{ApiNodeSpecifier, NodeChange, NodeStatus};
+    use crate::test_util::{assert_all_variants, assert_round_trip};
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn test_client_msg_body_round_trip() {
+        let handle = ProjectHandle::new(1);
+        let bodies = vec![
+            ClientMsgBody::Filesystem(FsRequest::Write {
+                path: "/src/rainbow.shader/main.glsl".as_path_buf(),
+                data: b"vec4 main() {}\n".to_vec(),
+            }),
+            ClientMsgBody::LoadProject {
+                path: "/projects/my-project".as_path_buf(),
+            },
+            ClientMsgBody::UnloadProject { handle },
+            ClientMsgBody::ProjectRequest {
+                handle,
+                request: ProjectRequest::GetChanges {
+                    since_frame: FrameId::new(5),
+                    detail_specifier: ApiNodeSpecifier::ByHandles(vec![NodeHandle::new(2)]),
+                },
+            },
+            ClientMsgBody::ListAvailableProjects,
+            ClientMsgBody::ListLoadedProjects,
+        ];
+
+        assert_all_variants(&bodies, 6, |body| match body {
+            ClientMsgBody::Filesystem(_) => 0,
+            ClientMsgBody::LoadProject { .. } => 1,
+            ClientMsgBody::UnloadProject { .. } => 2,
+            ClientMsgBody::ProjectRequest { .. } => 3,
+            ClientMsgBody::ListAvailableProjects => 4,
+            ClientMsgBody::ListLoadedProjects => 5,
+        });
+        for body in &bodies {
+            assert_round_trip(body);
+        }
+    }
+
+    #[test]
+    fn test_server_msg_body_round_trip() {
+        let handle = ProjectHandle::new(1);
+        let loaded = LoadedProject {
+            handle,
+            path: "/projects/my-project".as_path_buf(),
+        };
+        let mut bodies = vec![
+            ServerMsgBody::Filesystem(FsResponse::Read {
+                path: "/logo.png".as_path_buf(),
+                data: Some(vec![0x89, 0x50, 0x4E, 0x47]),
+                error: None,
+            }),
+            ServerMsgBody::LoadProject { handle },
+            ServerMsgBody::UnloadProject,
+            ServerMsgBody::ProjectRequest {
+                response: SerializableProjectResponse::GetChanges {
+                    current_frame: FrameId::new(9),
+                    node_handles: vec![NodeHandle::new(2)],
+                    node_changes: vec![NodeChange::StatusChanged {
+                        handle: NodeHandle::new(2),
+                        status: NodeStatus::Warn("slow".to_string()),
+                    }],
+                    node_details: Vec::new(),
+                    theoretical_fps: Some(240.0),
+                },
+            },
+            ServerMsgBody::ListAvailableProjects {
+                projects: Vec::new(),
+            },
+            ServerMsgBody::ListAvailableProjects {
+                projects: vec![
+                    AvailableProject {
+                        path: "/projects/a".as_path_buf(),
+                    },
+                    AvailableProject {
+                        path: "/projects/b c".as_path_buf(),
+                    },
+                ],
+            },
+            ServerMsgBody::ListLoadedProjects {
+                projects: vec![loaded.clone()],
+            },
+            ServerMsgBody::StopAllProjects,
+            ServerMsgBody::Undo { applied: true },
+            ServerMsgBody::Redo { applied: false },
+            ServerMsgBody::DuplicateNode {
+                path: "/src/rainbow-2.shader".as_path_buf(),
+            },
+            ServerMsgBody::Hello {
+                protocol_version: 1,
+            },
+            ServerMsgBody::GetCapabilities {
+                capabilities: Capabilities {
+                    protocol_version: 3,
+                    node_kinds: NodeKind::ALL.to_vec(),
+                    output_drivers: vec!["ArtNet".to_string()],
+                    max_fixtures: Some(16),
+                    features: Vec::new(),
+                },
+            },
+            ServerMsgBody::ApplyEdits,
+            ServerMsgBody::GetEvents {
+                events: vec![
+                    ServerEvent {
+                        uptime_ms: 16,
+                        kind: ServerEventKind::ProjectLoaded {
+                            path: "/projects/a".as_path_buf(),
+                        },
+                    },
+                    ServerEvent {
+                        uptime_ms: 32,
+                        kind: ServerEventKind::NodeError {
+                            project: "/projects/a".as_path_buf(),
+                            node: "/src/rainbow.shader".as_path_buf(),
+                            error: "undefined variable".to_string(),
+                        },
+                    },
+                ],
+            },
+            ServerMsgBody::TestPattern,
+            ServerMsgBody::SyncClock {
+                frame: FrameId::new(106_250_000_000),
+            },
+            ServerMsgBody::Heartbeat {
+                fps: 60,
+                frame_count: u64::MAX,
+                loaded_projects: vec![loaded],
+                uptime_ms: 0,
+            },
+            ServerMsgBody::Error {
+                error: "Project not found: \"/projects/missing\"".to_string(),
+            },
+        ];
+        for level in [
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+        ] {
+            bodies.push(ServerMsgBody::Log {
+                level,
+                message: "frame took 41ms\n".to_string(),
+            });
+        }
+
+        assert_all_variants(&bodies, 19, |body| match body {
+            ServerMsgBody::Filesystem(_) => 0,
+            ServerMsgBody::LoadProject { .. } => 1,
+            ServerMsgBody::UnloadProject => 2,
+            ServerMsgBody::ProjectRequest { .. } => 3,
+            ServerMsgBody::ListAvailableProjects { .. } => 4,
+            ServerMsgBody::ListLoadedProjects { .. } => 5,
+            ServerMsgBody::StopAllProjects => 6,
+            ServerMsgBody::Undo { .. } => 7,
+            ServerMsgBody::Redo { .. } => 8,
+            ServerMsgBody::DuplicateNode { .. } => 9,
+            ServerMsgBody::Hello { .. } => 10,
+            ServerMsgBody::GetCapabilities { .. } => 11,
+            ServerMsgBody::ApplyEdits => 12,
+            ServerMsgBody::GetEvents { .. } => 13,
+            ServerMsgBody::TestPattern => 14,
+            ServerMsgBody::SyncClock { .. } => 15,
+            ServerMsgBody::Log { .. } => 16,
+            ServerMsgBody::Heartbeat { .. } => 17,
+            ServerMsgBody::Error { .. } => 18,
+        });
+        for body in &bodies {
+            assert_round_trip(body);
+        }
+    }
+}
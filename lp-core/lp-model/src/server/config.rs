@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 /// This struct represents the configuration for a LightPlayer server instance.
 /// It is stored in `server.json` at the root of the server directory.
 ///
-/// Currently empty, but will be extended with fields such as:
+/// Will be extended with fields such as:
 /// - `memory_limits`: Memory usage limits for projects
 /// - `security_rules`: Security and access control rules
 /// - `projects_dir`: Custom projects directory path (defaults to "projects/")
@@ -18,12 +18,14 @@ use serde::{Deserialize, Serialize};
 /// - `port`: Custom websocket port (defaults to 2812)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ServerConfig {
-    // Future fields will be added here as needed
+    /// Most fixture nodes a project may have; projects with more fail to load
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fixtures: Option<u32>,
 }
 
 impl Default for ServerConfig {
     fn default() -> Self {
-        Self {}
+        Self { max_fixtures: None }
     }
 }
 
@@ -48,6 +50,14 @@ mod tests {
     #[test]
     fn test_server_config_default() {
         let config = ServerConfig::default();
-        assert_eq!(config, ServerConfig {});
+        assert_eq!(config, ServerConfig { max_fixtures: None });
+    }
+
+    #[test]
+    fn test_server_config_max_fixtures() {
+        let json = r#"{"max_fixtures":8}"#;
+        let config: ServerConfig = crate::json::from_str(json).unwrap();
+        assert_eq!(config.max_fixtures, Some(8));
+        assert_eq!(crate::json::to_string(&config).unwrap(), json);
     }
 }
@@ -8,7 +8,7 @@ use alloc::{string::String, vec::Vec};
 use serde::{Deserialize, Serialize};
 
 /// Filesystem operation request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum FsRequest {
     /// Read a file
@@ -34,7 +34,7 @@ pub enum FsRequest {
 ///
 /// All response variants include an optional error field.
 /// If `error` is `Some`, the operation failed and other fields may be empty/default.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum FsResponse {
     /// Response to Read request
@@ -74,6 +74,7 @@ pub enum FsResponse {
 mod tests {
     use super::*;
     use crate::AsLpPathBuf;
+    use crate::test_util::{assert_all_variants, assert_round_trip};
     use alloc::{string::ToString, vec};
 
     #[test]
@@ -275,8 +276,11 @@ mod tests {
         use alloc::string::ToString;
 
         let config = ProjectConfig {
+            version: crate::project::PROJECT_CONFIG_VERSION,
             uid: "test".to_string(),
             name: "Test Project".to_string(),
+            seed: None,
+            automation: Vec::new(),
         };
         let project_json =
             crate::json::to_string(&config).expect("Failed to serialize project config");
@@ -332,14 +336,20 @@ mod tests {
         use alloc::string::ToString;
 
         let config = ProjectConfig {
+            version: crate::project::PROJECT_CONFIG_VERSION,
             uid: "test".to_string(),
             name: "Test Project".to_string(),
+            seed: None,
+            automation: Vec::new(),
         };
         let project_json =
             crate::json::to_string(&config).expect("Failed to serialize project config");
 
-        // project_json is: {"uid":"test","name":"Test Project"}
-        assert_eq!(project_json, r#"{"uid":"test","name":"Test Project"}"#);
+        // project_json is: {"version":3,"uid":"test","name":"Test Project","seed":null,"automation":[]}
+        assert_eq!(
+            project_json,
+            r#"{"version":3,"uid":"test","name":"Test Project","seed":null,"automation":[]}"#
+        );
 
         // Convert to bytes and serialize in FsRequest
         let project_json_bytes = project_json.as_bytes().to_vec();
@@ -369,7 +379,7 @@ mod tests {
 
         // Verify the JSON contains escaped quotes (this is correct JSON!)
         assert!(
-            request_json.contains(r#""data":"{\"uid\""#),
+            request_json.contains(r#""data":"{\"version\""#),
             "JSON should contain escaped quotes in the data field. Actual JSON: {}",
             request_json
         );
@@ -438,4 +448,116 @@ mod tests {
             core::str::from_utf8(&deserialized.data).unwrap()
         );
     }
+
+    fn sample_data() -> Vec<Vec<u8>> {
+        vec![
+            Vec::new(),
+            b"hello world".to_vec(),
+            "line\n\ttab \"quoted\" back\\slash \u{1} caf\u{e9} \u{1f308}"
+                .as_bytes()
+                .to_vec(),
+            b"{\"uid\":\"test\"}".to_vec(),
+            vec![0xFF, 0xFE, 0xFD, 0x00, 0x01, 0x02],
+            vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A],
+        ]
+    }
+
+    #[test]
+    fn test_fs_request_round_trip() {
+        let mut requests = vec![
+            FsRequest::Read {
+                path: "/project.json".as_path_buf(),
+            },
+            FsRequest::DeleteFile {
+                path: "/src/old.shader/main.glsl".as_path_buf(),
+            },
+            FsRequest::DeleteDir {
+                path: "/src/old.shader".as_path_buf(),
+            },
+            FsRequest::ListDir {
+                path: "/".as_path_buf(),
+                recursive: true,
+            },
+            FsRequest::ListDir {
+                path: "/src".as_path_buf(),
+                recursive: false,
+            },
+        ];
+        for data in sample_data() {
+            requests.push(FsRequest::Write {
+                path: "/data.bin".as_path_buf(),
+                data,
+            });
+        }
+
+        assert_all_variants(&requests, 5, |request| match request {
+            FsRequest::Read { .. } => 0,
+            FsRequest::Write { .. } => 1,
+            FsRequest::DeleteFile { .. } => 2,
+            FsRequest::DeleteDir { .. } => 3,
+            FsRequest::ListDir { .. } => 4,
+        });
+        for request in &requests {
+            assert_round_trip(request);
+        }
+    }
+
+    #[test]
+    fn test_fs_response_round_trip() {
+        let error = Some("Permission denied: \"/data.bin\"".to_string());
+        let mut responses = vec![
+            FsResponse::Read {
+                path: "/missing.txt".as_path_buf(),
+                data: None,
+                error: Some("File not found".to_string()),
+            },
+            FsResponse::Write {
+                path: "/data.bin".as_path_buf(),
+                error: None,
+            },
+            FsResponse::Write {
+                path: "/data.bin".as_path_buf(),
+                error: error.clone(),
+            },
+            FsResponse::DeleteFile {
+                path: "/data.bin".as_path_buf(),
+                error: None,
+            },
+            FsResponse::DeleteDir {
+                path: "/src".as_path_buf(),
+                error: error.clone(),
+            },
+            FsResponse::ListDir {
+                path: "/src".as_path_buf(),
+                entries: Vec::new(),
+                error: None,
+            },
+            FsResponse::ListDir {
+                path: "/src".as_path_buf(),
+                entries: vec![
+                    "/src/rainbow.shader".as_path_buf(),
+                    "/src/rainbow.shader/main.glsl".as_path_buf(),
+                ],
+                error: None,
+            },
+        ];
+        for data in sample_data() {
+            responses.push(FsResponse::Read {
+                path: "/data.bin".as_path_buf(),
+                data: Some(data),
+                error: None,
+            });
+        }
+
+        assert_all_variants(&responses, 5, |response| match response {
+            FsResponse::Read { .. } => 0,
+            FsResponse::Write { .. } => 1,
+            FsResponse::DeleteFile { .. } => 2,
+            FsResponse::DeleteDir { .. } => 3,
+            FsResponse::ListDir { .. } => 4,
+        });
+        for response in &responses {
+            assert_round_trip(response);
+        }
+    }
 }
@@ -2,6 +2,9 @@ pub mod api;
 pub mod config;
 pub mod fs_api;
 
-pub use api::{AvailableProject, ClientMsgBody, LoadedProject, ServerMsgBody};
+pub use api::{
+    AvailableProject, Capabilities, ClientMsgBody, LoadedProject, ServerEvent, ServerEventKind,
+    ServerMsgBody,
+};
 pub use config::ServerConfig;
 pub use fs_api::{FsRequest, FsResponse};
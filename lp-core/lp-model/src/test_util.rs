@@ -0,0 +1,45 @@
+//! Shared helpers for unit tests
+
+use alloc::string::String;
+use core::fmt::Debug;
+use serde::{Deserialize, Serialize};
+
+/// Serialize `value` to JSON and back, asserting nothing was lost on the way
+///
+/// Checks both that the decoded value equals the original and that encoding it again
+/// produces the same JSON, so asymmetric `serialize_with`/`deserialize_with` pairs are
+/// caught even when they happen to decode to an equal value. Returns the JSON for
+/// further assertions on the wire format.
+pub fn assert_round_trip<T>(value: &T) -> String
+where
+    T: Serialize + for<'de> Deserialize<'de> + PartialEq + Debug,
+{
+    let json = crate::json::to_string(value)
+        .unwrap_or_else(|e| panic!("failed to serialize {value:?}: {e}"));
+    let decoded: T = crate::json::from_str(&json)
+        .unwrap_or_else(|e| panic!("failed to deserialize {json}: {e}"));
+    assert_eq!(
+        &decoded, value,
+        "value changed in round trip through {json}"
+    );
+
+    let reencoded = crate::json::to_string(&decoded).unwrap();
+    assert_eq!(
+        reencoded, json,
+        "re-serializing {decoded:?} changed the JSON"
+    );
+    json
+}
+
+/// Assert that `values` include every variant of an enum with `count` variants
+///
+/// `variant` numbers the variants with an exhaustive `match`, so adding a variant
+/// breaks the build of the calling test until it is given a sample and `count` is bumped.
+pub fn assert_all_variants<T>(values: &[T], count: usize, variant: impl Fn(&T) -> usize) {
+    for index in 0..count {
+        assert!(
+            values.iter().any(|value| variant(value) == index),
+            "no sample for variant {index}"
+        );
+    }
+}
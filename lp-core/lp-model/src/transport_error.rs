@@ -15,6 +15,8 @@ pub enum TransportError {
     Deserialization(String),
     /// Connection lost
     ConnectionLost,
+    /// Nothing accepted the connection at the target address
+    ConnectionRefused(String),
     /// Other transport error
     Other(String),
 }
@@ -27,6 +29,9 @@ impl fmt::Display for TransportError {
                 write!(f, "Deserialization error: {msg}")
             }
             TransportError::ConnectionLost => write!(f, "Connection lost"),
+            TransportError::ConnectionRefused(target) => {
+                write!(f, "Connection refused: {target}")
+            }
             TransportError::Other(msg) => write!(f, "Transport error: {msg}"),
         }
     }
@@ -0,0 +1,198 @@
+//! Duplicating node subtrees within a project
+//!
+//! Copies every file under a source directory to a new location. Node specifiers in
+//! the copied configs that point into the source subtree are rewritten to point at the
+//! copy, so the duplicate drives its own nodes rather than aliasing the original's.
+
+extern crate alloc;
+
+use crate::error::ServerError;
+use crate::history::FileEdit;
+use alloc::{format, string::String, vec::Vec};
+use lp_model::nodes::fixture::FixtureConfig;
+use lp_model::nodes::shader::ShaderConfig;
+use lp_model::{LpPath, LpPathBuf, NodeSpecifier};
+use lp_shared::fs::LpFs;
+
+/// Copy `source` to `dest` inside the project at `project_path`
+///
+/// `source` and `dest` are relative to the project root. Returns the path the copy was
+/// written to (which gets a numeric suffix if `dest` is taken) and the files created,
+/// so the caller can record them for undo.
+pub fn duplicate_node(
+    fs: &dyn LpFs,
+    project_path: &LpPath,
+    source: &LpPath,
+    dest: &LpPath,
+) -> Result<(LpPathBuf, Vec<FileEdit>), ServerError> {
+    let to_fs_path = |path: &LpPath| {
+        project_path
+            .to_path_buf()
+            .join(path.as_str().trim_start_matches('/'))
+    };
+
+    if !source.is_absolute() || !dest.is_absolute() {
+        return Err(ServerError::Filesystem(format!(
+            "Duplicate paths must be absolute within the project: {} -> {}",
+            source.as_str(),
+            dest.as_str()
+        )));
+    }
+
+    let source_fs = to_fs_path(source);
+    if !is_dir(fs, source_fs.as_path()) {
+        return Err(ServerError::Filesystem(format!(
+            "Node directory not found: {}",
+            source.as_str()
+        )));
+    }
+
+    let dest = available_path(fs, &to_fs_path, dest)?;
+    if dest.starts_with(source.as_str()) {
+        return Err(ServerError::Filesystem(format!(
+            "Cannot duplicate {} into itself",
+            source.as_str()
+        )));
+    }
+
+    let entries = fs
+        .list_dir(source_fs.as_path(), true)
+        .map_err(|e| ServerError::Filesystem(format!("{e}")))?;
+
+    let mut edits = Vec::new();
+    for entry in entries {
+        // Directories can't be read; they are created along with their files
+        let Ok(data) = fs.read_file(entry.as_path()) else {
+            continue;
+        };
+        let Some(relative) = entry.strip_prefix(source_fs.as_str()) else {
+            continue;
+        };
+        let relative = relative.as_str().trim_start_matches('/');
+        let original_path = source.to_path_buf().join(relative);
+
+        let data = match original_path.parent() {
+            Some(node_path) if original_path.file_name() == Some("node.json") => {
+                remap_node_config(node_path, &data, source, dest.as_path())?
+            }
+            _ => data,
+        };
+
+        let path = to_fs_path(dest.as_path()).join(relative);
+        fs.write_file_atomic(path.as_path(), &data)
+            .map_err(|e| ServerError::Filesystem(format!("{e}")))?;
+        edits.push(FileEdit {
+            path,
+            before: None,
+            after: Some(data),
+        });
+    }
+
+    Ok((dest, edits))
+}
+
+/// First free path of the form `dest`, `dest-1`, `dest-2`, ... (suffix goes before the node kind)
+fn available_path(
+    fs: &dyn LpFs,
+    to_fs_path: &dyn Fn(&LpPath) -> LpPathBuf,
+    dest: &LpPath,
+) -> Result<LpPathBuf, ServerError> {
+    let parent = dest.parent().unwrap_or(LpPath::new("/"));
+    let (stem, extension) = match (dest.file_stem(), dest.extension()) {
+        (Some(stem), Some(extension)) => (stem, format!(".{extension}")),
+        _ => (dest.file_name().unwrap_or_default(), String::new()),
+    };
+
+    let mut candidate = dest.to_path_buf();
+    let mut suffix = 1;
+    loop {
+        let fs_path = to_fs_path(candidate.as_path());
+        let taken = fs
+            .file_exists(fs_path.as_path())
+            .map_err(|e| ServerError::Filesystem(format!("{e}")))?
+            || is_dir(fs, fs_path.as_path());
+        if !taken {
+            return Ok(candidate);
+        }
+        candidate = parent
+            .to_path_buf()
+            .join(format!("{stem}-{suffix}{extension}"));
+        suffix += 1;
+    }
+}
+
+/// Whether `path` is an existing directory (missing paths are not an error)
+fn is_dir(fs: &dyn LpFs, path: &LpPath) -> bool {
+    fs.is_dir(path).unwrap_or(false)
+}
+
+/// Rewrite the node specifiers in a copied `node.json`
+///
+/// Configs without specifiers, and files outside node directories, are copied as-is.
+fn remap_node_config(
+    node_path: &LpPath,
+    data: &[u8],
+    source: &LpPath,
+    dest: &LpPath,
+) -> Result<Vec<u8>, ServerError> {
+    let remap = |spec: &mut NodeSpecifier| remap_specifier(spec, node_path, source, dest);
+    let parse_error = |e: lp_model::json::Error| {
+        ServerError::Core(format!(
+            "Failed to parse {}/node.json: {e}",
+            node_path.as_str()
+        ))
+    };
+    let serialize_error = |e: lp_model::json::Error| {
+        ServerError::Core(format!("Failed to serialize node config: {e}"))
+    };
+
+    let json = match node_path.extension() {
+        Some("shader") => {
+            let mut config: ShaderConfig = lp_model::json::from_slice(data).map_err(parse_error)?;
+            remap(&mut config.texture_spec);
+            lp_model::json::to_string(&config).map_err(serialize_error)?
+        }
+        Some("fixture") => {
+            let mut config: FixtureConfig =
+                lp_model::json::from_slice(data).map_err(parse_error)?;
+            remap(&mut config.output_spec);
+            remap(&mut config.texture_spec);
+            lp_model::json::to_string(&config).map_err(serialize_error)?
+        }
+        _ => return Ok(data.to_vec()),
+    };
+    Ok(json.into_bytes())
+}
+
+/// Point a specifier at the copy if it refers to a node inside the duplicated subtree
+///
+/// Relative specifiers that point outside the subtree are made absolute, since the copy
+/// may not sit next to the same nodes as the original.
+fn remap_specifier(spec: &mut NodeSpecifier, node_path: &LpPath, source: &LpPath, dest: &LpPath) {
+    let spec_str = spec.as_str();
+    if spec_str.is_empty() {
+        return;
+    }
+    // Resolved the same way the engine resolves specifiers
+    let target = if spec_str.starts_with('/') {
+        LpPathBuf::from(spec_str)
+    } else {
+        let parent = node_path.parent().unwrap_or(LpPath::new("/"));
+        match parent.to_path_buf().join_relative(spec_str) {
+            Some(target) => target,
+            None => return,
+        }
+    };
+
+    if let Some(rest) = target.strip_prefix(source.as_str()) {
+        let rest = rest.as_str().trim_start_matches('/');
+        let remapped = if rest.is_empty() {
+            dest.to_path_buf()
+        } else {
+            dest.to_path_buf().join(rest)
+        };
+        *spec = NodeSpecifier::from(remapped.as_str());
+    } else if !spec_str.starts_with('/') {
+        *spec = NodeSpecifier::from(target.as_str());
+    }
+}
@@ -0,0 +1,106 @@
+//! Applying a batch of node edits to a project as one transaction
+//!
+//! Every file is written before the runtime sees any of them, so the affected nodes
+//! are reloaded once and never run with half of a change applied. If a node fails to
+//! reload, the old files are put back and reloaded, leaving the project as it was.
+
+extern crate alloc;
+
+use crate::error::ServerError;
+use crate::history::{FileEdit, restore};
+use crate::project::Project;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use lp_engine::project::NodeStatus;
+use lp_model::{LpPathBuf, NodeEdit};
+use lp_shared::fs::LpFs;
+
+/// Write `edits` into `project` and reload the affected nodes
+///
+/// Returns the files changed, so the caller can record them as one undo step. On
+/// error nothing is changed.
+pub fn apply_edits(
+    fs: &dyn LpFs,
+    project: &mut Project,
+    edits: &[NodeEdit],
+) -> Result<Vec<FileEdit>, ServerError> {
+    for edit in edits {
+        if !edit.path.is_absolute() || edit.path.components().any(|c| c == "..") {
+            return Err(ServerError::Filesystem(format!(
+                "Edit paths must be absolute within the project: {}",
+                edit.path.as_str()
+            )));
+        }
+    }
+
+    let mut step = Vec::new();
+    for edit in edits {
+        let path = project
+            .path()
+            .to_path_buf()
+            .join(edit.path.as_str().trim_start_matches('/'));
+        let before = fs.read_file(path.as_path()).ok();
+        if let Err(e) = fs.write_file_atomic(path.as_path(), &edit.data) {
+            let _ = restore_all(fs, &step);
+            return Err(ServerError::Filesystem(format!(
+                "Failed to write {}: {e}",
+                edit.path.as_str()
+            )));
+        }
+        step.push(FileEdit {
+            path,
+            before,
+            after: Some(edit.data.clone()),
+        });
+    }
+
+    let node_paths: Vec<LpPathBuf> = edits
+        .iter()
+        .filter_map(|edit| edit.path.parent().map(|p| p.to_path_buf()))
+        .collect();
+    if let Err(reason) = reload(fs, project, &node_paths) {
+        restore_all(fs, &step)?;
+        // The old files loaded before, so a failure here only means the project was
+        // already broken; report the original problem
+        let _ = reload(fs, project, &[]);
+        return Err(ServerError::Core(format!(
+            "Edits rejected, nothing was changed: {reason}"
+        )));
+    }
+
+    Ok(step)
+}
+
+/// Put every file of `step` back the way it was, newest edit first
+fn restore_all(fs: &dyn LpFs, step: &[FileEdit]) -> Result<(), ServerError> {
+    for edit in step.iter().rev() {
+        restore(fs, edit.path.as_path(), edit.before.as_deref())?;
+    }
+    Ok(())
+}
+
+/// Hand the project its pending file changes and check the given nodes are healthy
+fn reload(fs: &dyn LpFs, project: &mut Project, node_paths: &[LpPathBuf]) -> Result<(), String> {
+    let changes = project.pending_changes(fs);
+    let current_version = fs.current_version();
+    let result = project.runtime_mut().handle_fs_changes(&changes);
+    // Mark the changes processed so the next tick doesn't reload the nodes again
+    project.update_fs_version(current_version.next());
+    result.map_err(|e| e.to_string())?;
+
+    let runtime = project.runtime();
+    for node_path in node_paths {
+        let Ok(handle) = runtime.handle_for_path(node_path.as_path()) else {
+            continue;
+        };
+        if let Some(entry) = runtime.nodes.get(&handle)
+            && let NodeStatus::InitError(error) | NodeStatus::Error(error) = &entry.status
+        {
+            return Err(format!("{}: {error}", node_path.as_str()));
+        }
+    }
+    Ok(())
+}
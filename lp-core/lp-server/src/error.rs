@@ -18,6 +18,10 @@ pub enum ServerError {
     Core(String),
     /// Serialization error
     Serialization(String),
+    /// Protocol version mismatch between client and server
+    Protocol(String),
+    /// Output channel error (from the output provider)
+    Output(String),
 }
 
 impl fmt::Display for ServerError {
@@ -32,6 +36,8 @@ impl fmt::Display for ServerError {
             ServerError::Filesystem(msg) => write!(f, "Filesystem error: {msg}"),
             ServerError::Core(msg) => write!(f, "Core error: {msg}"),
             ServerError::Serialization(msg) => write!(f, "Serialization error: {msg}"),
+            ServerError::Protocol(msg) => write!(f, "Protocol error: {msg}"),
+            ServerError::Output(msg) => write!(f, "Output error: {msg}"),
         }
     }
 }
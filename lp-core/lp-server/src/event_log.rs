@@ -0,0 +1,91 @@
+//! Log of recent significant server events, for debugging a misbehaving show
+//!
+//! The log is a bounded ring buffer: once full, the oldest event is dropped for each
+//! new one. Events are stamped with the server's uptime, which advances with the
+//! tick deltas.
+
+extern crate alloc;
+
+use crate::project_manager::ProjectManager;
+use alloc::{collections::VecDeque, vec::Vec};
+use hashbrown::HashSet;
+use lp_engine::project::NodeStatus;
+use lp_model::NodeHandle;
+use lp_model::project::ProjectHandle;
+use lp_model::server::{ServerEvent, ServerEventKind};
+
+/// Maximum number of events kept (older events are dropped)
+pub const MAX_EVENTS: usize = 64;
+
+/// Bounded log of recent server events
+#[derive(Debug, Default)]
+pub struct EventLog {
+    /// Recent events, oldest first
+    events: VecDeque<ServerEvent>,
+    /// Server uptime in milliseconds
+    uptime_ms: u64,
+    /// Nodes in an error state at the last check, so each error is logged once
+    errored_nodes: HashSet<(ProjectHandle, NodeHandle)>,
+}
+
+impl EventLog {
+    /// Create an empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advance the uptime used to stamp new events
+    pub fn advance(&mut self, delta_ms: u32) {
+        self.uptime_ms += u64::from(delta_ms);
+    }
+
+    /// Server uptime in milliseconds (the sum of the tick deltas so far)
+    pub fn uptime_ms(&self) -> u64 {
+        self.uptime_ms
+    }
+
+    /// Record an event at the current uptime
+    pub fn record(&mut self, kind: ServerEventKind) {
+        if self.events.len() == MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(ServerEvent {
+            uptime_ms: self.uptime_ms,
+            kind,
+        });
+    }
+
+    /// Recent events, oldest first
+    pub fn events(&self) -> Vec<ServerEvent> {
+        self.events.iter().cloned().collect()
+    }
+
+    /// Record an event for every node that has gone into an error state since the
+    /// last check
+    ///
+    /// A node that recovers and fails again is logged again.
+    pub fn record_node_errors(&mut self, project_manager: &ProjectManager) {
+        let mut errored_nodes = HashSet::new();
+        for loaded in project_manager.list_loaded_projects() {
+            let Some(project) = project_manager.get_project(loaded.handle) else {
+                continue;
+            };
+            for (node_handle, entry) in &project.runtime().nodes {
+                let (NodeStatus::InitError(error) | NodeStatus::Error(error)) = &entry.status
+                else {
+                    continue;
+                };
+                let key = (loaded.handle, *node_handle);
+                if !self.errored_nodes.contains(&key) {
+                    self.record(ServerEventKind::NodeError {
+                        project: loaded.path.clone(),
+                        node: entry.path.clone(),
+                        error: error.clone(),
+                    });
+                }
+                errored_nodes.insert(key);
+            }
+        }
+        self.errored_nodes = errored_nodes;
+    }
+}
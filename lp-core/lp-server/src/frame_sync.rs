@@ -0,0 +1,45 @@
+//! Frame alignment across devices
+//!
+//! Each controller in an installation ticks on its own clock, so left alone their
+//! frames drift apart. A host keeps them together by sending every server its clock
+//! with `SyncClock`: the server remembers the offset between the host's clock and its
+//! own uptime, and from then on renders frame `host_time / frame_interval` of the
+//! host's clock each tick. Devices synced to the same host therefore show the same
+//! frame id at the same wall time, to within one tick.
+
+use lp_engine::FrameStamp;
+use lp_model::FrameId;
+
+/// A host clock the server renders in step with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameSync {
+    /// Host clock time minus server uptime, in milliseconds
+    offset_ms: i64,
+    /// Length of one frame in milliseconds (never zero)
+    frame_interval_ms: u32,
+}
+
+impl FrameSync {
+    /// Sync to a host clock that read `host_time_ms` at server uptime `uptime_ms`
+    ///
+    /// A `frame_interval_ms` of zero is treated as one.
+    pub fn new(host_time_ms: u64, uptime_ms: u64, frame_interval_ms: u32) -> Self {
+        Self {
+            offset_ms: (host_time_ms as i64).saturating_sub(uptime_ms as i64),
+            frame_interval_ms: frame_interval_ms.max(1),
+        }
+    }
+
+    /// Host clock time at server uptime `uptime_ms`
+    pub fn host_time_ms(&self, uptime_ms: u64) -> u64 {
+        (uptime_ms as i64).saturating_add(self.offset_ms).max(0) as u64
+    }
+
+    /// Frame of the host's clock at server uptime `uptime_ms`, stamped with the host
+    /// time the frame started
+    pub fn frame_at(&self, uptime_ms: u64) -> FrameStamp {
+        let interval = u64::from(self.frame_interval_ms);
+        let frame = self.host_time_ms(uptime_ms) / interval;
+        FrameStamp::new(FrameId::new(frame as i64), frame * interval)
+    }
+}
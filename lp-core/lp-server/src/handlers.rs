@@ -2,39 +2,81 @@
 
 extern crate alloc;
 
+use crate::duplicate::duplicate_node;
+use crate::edits::apply_edits;
 use crate::error::ServerError;
+use crate::event_log::EventLog;
+use crate::frame_sync::FrameSync;
+use crate::history::FileEdit;
 use crate::project_manager::ProjectManager;
-use alloc::{format, rc::Rc, vec::Vec};
+use crate::test_pattern::TestPatterns;
+use alloc::{format, rc::Rc, string::String, vec, vec::Vec};
 use core::cell::RefCell;
 use lp_model::{
-    AsLpPath, ClientMessage, LpPath, LpPathBuf, ServerMessage,
-    server::{AvailableProject, FsRequest, FsResponse, ServerMsgBody as ServerMessagePayload},
+    AsLpPath, Capabilities, ClientMessage, LpPath, LpPathBuf, MIN_PROTOCOL_VERSION, NodeEdit,
+    PROTOCOL_VERSION, ServerMessage,
+    nodes::{NodeKind, output::OUTPUT_DRIVERS},
+    server::{
+        AvailableProject, FsRequest, FsResponse, ServerEventKind,
+        ServerMsgBody as ServerMessagePayload,
+    },
 };
 use lp_shared::fs::LpFs;
 use lp_shared::output::OutputProvider;
 
+/// State a request can read and update besides the projects and filesystem
+///
+/// The server builds one per request from its own fields, keeping the borrows disjoint
+/// from the project manager and filesystem.
+pub struct RequestContext<'a> {
+    /// Protocol version agreed for the connection; `Hello` requests update it, and
+    /// requests newer than it are rejected
+    pub protocol_version: &'a mut u32,
+    /// Significant changes (projects loaded or unloaded, files edited) are recorded here
+    pub events: &'a mut EventLog,
+    /// Set by `TestPattern` requests; the server renders them every tick
+    pub test_patterns: &'a mut TestPatterns,
+    /// Set by `SyncClock` requests; the server ticks projects by it
+    pub frame_sync: &'a mut Option<FrameSync>,
+    /// Theoretical FPS reported with project changes
+    pub theoretical_fps: Option<f32>,
+}
+
 /// Handle a client message and generate a server response
 pub fn handle_client_message(
     project_manager: &mut ProjectManager,
     base_fs: &mut dyn LpFs,
     output_provider: &Rc<RefCell<dyn OutputProvider>>,
+    ctx: &mut RequestContext<'_>,
     client_msg: ClientMessage,
-    theoretical_fps: Option<f32>,
 ) -> Result<ServerMessage, ServerError> {
     let ClientMessage { id, msg } = client_msg;
+    let events = &mut *ctx.events;
+
+    if msg.protocol_version() > *ctx.protocol_version {
+        return Err(ServerError::Protocol(format!(
+            "request needs protocol version {}, but the connection uses version {}",
+            msg.protocol_version(),
+            *ctx.protocol_version
+        )));
+    }
 
     let response = match msg {
-        lp_model::ClientRequest::Filesystem(fs_request) => {
-            ServerMessagePayload::Filesystem(handle_fs_request(base_fs, fs_request)?)
-        }
-        lp_model::ClientRequest::LoadProject { path } => {
-            handle_load_project(project_manager, base_fs, output_provider, path.as_path())?
-        }
+        lp_model::ClientRequest::Filesystem(fs_request) => ServerMessagePayload::Filesystem(
+            handle_fs_request(project_manager, base_fs, events, fs_request)?,
+        ),
+        lp_model::ClientRequest::LoadProject { path } => handle_load_project(
+            project_manager,
+            base_fs,
+            output_provider,
+            events,
+            path.as_path(),
+        )?,
         lp_model::ClientRequest::UnloadProject { handle } => {
-            handle_unload_project(project_manager, handle)?
+            handle_unload_project(project_manager, events, handle)?
         }
         lp_model::ClientRequest::ProjectRequest { handle, request } => {
-            handle_project_request(project_manager, handle, request, theoretical_fps)?
+            handle_project_request(project_manager, handle, request, ctx.theoretical_fps)?
         }
         lp_model::ClientRequest::ListAvailableProjects => {
             handle_list_available_projects(project_manager, base_fs)?
@@ -42,14 +84,127 @@ pub fn handle_client_message(
         lp_model::ClientRequest::ListLoadedProjects => {
             handle_list_loaded_projects(project_manager)?
         }
-        lp_model::ClientRequest::StopAllProjects => handle_stop_all_projects(project_manager)?,
+        lp_model::ClientRequest::StopAllProjects => {
+            handle_stop_all_projects(project_manager, events)?
+        }
+        lp_model::ClientRequest::Undo { handle } => handle_undo(project_manager, base_fs, handle)?,
+        lp_model::ClientRequest::Redo { handle } => handle_redo(project_manager, base_fs, handle)?,
+        lp_model::ClientRequest::DuplicateNode {
+            handle,
+            source,
+            dest,
+        } => handle_duplicate_node(project_manager, base_fs, events, handle, &source, &dest)?,
+        lp_model::ClientRequest::Hello {
+            protocol_version: client_version,
+        } => handle_hello(ctx.protocol_version, client_version)?,
+        lp_model::ClientRequest::GetCapabilities => handle_get_capabilities(project_manager),
+        lp_model::ClientRequest::ApplyEdits { handle, edits } => {
+            handle_apply_edits(project_manager, base_fs, events, handle, &edits)?
+        }
+        lp_model::ClientRequest::GetEvents => ServerMessagePayload::GetEvents {
+            events: events.events(),
+        },
+        lp_model::ClientRequest::TestPattern { fixture, pattern } => {
+            ctx.test_patterns
+                .set(&*output_provider.borrow(), &fixture, pattern)?;
+            ServerMessagePayload::TestPattern
+        }
+        lp_model::ClientRequest::SyncClock {
+            host_time_ms,
+            frame_interval_ms,
+        } => {
+            let sync = FrameSync::new(host_time_ms, events.uptime_ms(), frame_interval_ms);
+            *ctx.frame_sync = Some(sync);
+            ServerMessagePayload::SyncClock {
+                frame: sync.frame_at(events.uptime_ms()).frame,
+            }
+        }
     };
 
     Ok(ServerMessage { id, msg: response })
 }
 
 /// Handle a filesystem request
-fn handle_fs_request(fs: &mut dyn LpFs, request: FsRequest) -> Result<FsResponse, ServerError> {
+///
+/// Writes and deletes inside a loaded project are recorded in its undo history and
+/// the event log.
+fn handle_fs_request(
+    project_manager: &mut ProjectManager,
+    fs: &mut dyn LpFs,
+    events: &mut EventLog,
+    request: FsRequest,
+) -> Result<FsResponse, ServerError> {
+    // Only pay for capturing the old contents when the edit lands in a loaded project
+    let tracked = mutated_path(&request).is_some_and(|path| {
+        project_manager
+            .project_for_path_mut(path.as_path())
+            .is_some()
+    });
+    let (step, edited_path) = if tracked {
+        (
+            record_fs_request(fs, &request),
+            mutated_path(&request).cloned(),
+        )
+    } else {
+        (Vec::new(), None)
+    };
+
+    let response = apply_fs_request(fs, request)?;
+
+    let succeeded = match &response {
+        FsResponse::Write { error, .. }
+        | FsResponse::DeleteFile { error, .. }
+        | FsResponse::DeleteDir { error, .. } => error.is_none(),
+        FsResponse::Read { .. } | FsResponse::ListDir { .. } => false,
+    };
+    if succeeded
+        && let Some(first) = step.first()
+        && let Some(project) = project_manager.project_for_path_mut(first.path.as_path())
+    {
+        project.history_mut().record(step);
+    }
+    if succeeded && let Some(path) = edited_path {
+        events.record(ServerEventKind::FileEdited { path });
+    }
+
+    Ok(response)
+}
+
+/// Path changed by a mutating request, if any
+fn mutated_path(request: &FsRequest) -> Option<&LpPathBuf> {
+    match request {
+        FsRequest::Write { path, .. }
+        | FsRequest::DeleteFile { path }
+        | FsRequest::DeleteDir { path } => Some(path),
+        FsRequest::Read { .. } | FsRequest::ListDir { .. } => None,
+    }
+}
+
+/// Capture the files a mutating request is about to change, for undo
+fn record_fs_request(fs: &dyn LpFs, request: &FsRequest) -> Vec<FileEdit> {
+    let file_edit = |path: &LpPathBuf, after: Option<Vec<u8>>| FileEdit {
+        path: path.clone(),
+        before: fs.read_file(path.as_path()).ok(),
+        after,
+    };
+
+    match request {
+        FsRequest::Write { path, data } => vec![file_edit(path, Some(data.clone()))],
+        FsRequest::DeleteFile { path } => vec![file_edit(path, None)],
+        FsRequest::DeleteDir { path } => fs
+            .list_dir(path.as_path(), true)
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| file_edit(entry, None))
+            // Directories can't be read; they are recreated along with their files
+            .filter(|edit| edit.before.is_some())
+            .collect(),
+        FsRequest::Read { .. } | FsRequest::ListDir { .. } => Vec::new(),
+    }
+}
+
+/// Apply a filesystem request
+fn apply_fs_request(fs: &mut dyn LpFs, request: FsRequest) -> Result<FsResponse, ServerError> {
     match request {
         FsRequest::Read { path } => match fs.read_file(path.as_path()) {
             Ok(data) => Ok(FsResponse::Read {
@@ -63,7 +218,7 @@ fn handle_fs_request(fs: &mut dyn LpFs, request: FsRequest) -> Result<FsResponse
                 error: Some(format!("{e}")),
             }),
         },
-        FsRequest::Write { path, data } => match fs.write_file(path.as_path(), &data) {
+        FsRequest::Write { path, data } => match fs.write_file_atomic(path.as_path(), &data) {
             Ok(()) => Ok(FsResponse::Write { path, error: None }),
             Err(e) => Ok(FsResponse::Write {
                 path,
@@ -104,18 +259,35 @@ fn handle_load_project(
     project_manager: &mut ProjectManager,
     base_fs: &mut dyn LpFs,
     output_provider: &Rc<RefCell<dyn OutputProvider>>,
+    events: &mut EventLog,
     path: &LpPath,
 ) -> Result<ServerMessagePayload, ServerError> {
+    let already_loaded = project_manager.list_loaded_projects().len();
     let handle = project_manager.load_project(path, base_fs, output_provider.clone())?;
+    // Loading a project that is already loaded just returns its handle
+    if project_manager.list_loaded_projects().len() > already_loaded
+        && let Some(project) = project_manager.get_project(handle)
+    {
+        events.record(ServerEventKind::ProjectLoaded {
+            path: project.path().to_path_buf(),
+        });
+    }
     Ok(ServerMessagePayload::LoadProject { handle })
 }
 
 /// Handle an UnloadProject request
 fn handle_unload_project(
     project_manager: &mut ProjectManager,
+    events: &mut EventLog,
     handle: lp_model::project::ProjectHandle,
 ) -> Result<ServerMessagePayload, ServerError> {
+    let path = project_manager
+        .get_project(handle)
+        .map(|project| project.path().to_path_buf());
     project_manager.unload_project(handle)?;
+    if let Some(path) = path {
+        events.record(ServerEventKind::ProjectUnloaded { path });
+    }
     Ok(ServerMessagePayload::UnloadProject)
 }
 
@@ -193,10 +365,133 @@ fn handle_list_loaded_projects(
     Ok(ServerMessagePayload::ListLoadedProjects { projects })
 }
 
+/// Handle an Undo request
+fn handle_undo(
+    project_manager: &mut ProjectManager,
+    base_fs: &dyn LpFs,
+    handle: lp_model::project::ProjectHandle,
+) -> Result<ServerMessagePayload, ServerError> {
+    let project = project_manager
+        .get_project_mut(handle)
+        .ok_or_else(|| ServerError::ProjectNotFound(format!("handle {}", handle.id())))?;
+    let applied = project.history_mut().undo(base_fs)?;
+    Ok(ServerMessagePayload::Undo { applied })
+}
+
+/// Handle a Redo request
+fn handle_redo(
+    project_manager: &mut ProjectManager,
+    base_fs: &dyn LpFs,
+    handle: lp_model::project::ProjectHandle,
+) -> Result<ServerMessagePayload, ServerError> {
+    let project = project_manager
+        .get_project_mut(handle)
+        .ok_or_else(|| ServerError::ProjectNotFound(format!("handle {}", handle.id())))?;
+    let applied = project.history_mut().redo(base_fs)?;
+    Ok(ServerMessagePayload::Redo { applied })
+}
+
+/// Handle a DuplicateNode request
+///
+/// The copy is recorded as a single undo step; the project picks up the new files on the
+/// next tick like any other edit.
+fn handle_duplicate_node(
+    project_manager: &mut ProjectManager,
+    base_fs: &dyn LpFs,
+    events: &mut EventLog,
+    handle: lp_model::project::ProjectHandle,
+    source: &LpPathBuf,
+    dest: &LpPathBuf,
+) -> Result<ServerMessagePayload, ServerError> {
+    let project = project_manager
+        .get_project_mut(handle)
+        .ok_or_else(|| ServerError::ProjectNotFound(format!("handle {}", handle.id())))?;
+    let (path, step) = duplicate_node(base_fs, project.path(), source.as_path(), dest.as_path())?;
+    record_file_edits(events, &step);
+    project.history_mut().record(step);
+    Ok(ServerMessagePayload::DuplicateNode { path })
+}
+
+/// Handle an ApplyEdits request
+///
+/// The batch is recorded as a single undo step.
+fn handle_apply_edits(
+    project_manager: &mut ProjectManager,
+    base_fs: &dyn LpFs,
+    events: &mut EventLog,
+    handle: lp_model::project::ProjectHandle,
+    edits: &[NodeEdit],
+) -> Result<ServerMessagePayload, ServerError> {
+    let project = project_manager
+        .get_project_mut(handle)
+        .ok_or_else(|| ServerError::ProjectNotFound(format!("handle {}", handle.id())))?;
+    let step = apply_edits(base_fs, project, edits)?;
+    record_file_edits(events, &step);
+    project.history_mut().record(step);
+    Ok(ServerMessagePayload::ApplyEdits)
+}
+
+/// Log each file of an edit step
+fn record_file_edits(events: &mut EventLog, step: &[FileEdit]) {
+    for edit in step {
+        events.record(ServerEventKind::FileEdited {
+            path: edit.path.clone(),
+        });
+    }
+}
+
+/// Handle a Hello request, agreeing on the protocol version for the connection
+///
+/// A newer client is answered with this server's version, which it must fall back
+/// to. A client older than [`MIN_PROTOCOL_VERSION`] is rejected and the connection
+/// keeps its current version.
+fn handle_hello(
+    protocol_version: &mut u32,
+    client_version: u32,
+) -> Result<ServerMessagePayload, ServerError> {
+    if client_version < MIN_PROTOCOL_VERSION {
+        return Err(ServerError::Protocol(format!(
+            "client protocol version {client_version} is not supported \
+             (oldest supported version is {MIN_PROTOCOL_VERSION})"
+        )));
+    }
+    *protocol_version = client_version.min(PROTOCOL_VERSION);
+    Ok(ServerMessagePayload::Hello {
+        protocol_version: *protocol_version,
+    })
+}
+
+/// Handle a GetCapabilities request
+fn handle_get_capabilities(project_manager: &ProjectManager) -> ServerMessagePayload {
+    let mut features = Vec::new();
+    if cfg!(feature = "cranelift-optimizer") {
+        features.push(String::from("cranelift-optimizer"));
+    }
+    if cfg!(feature = "cranelift-verifier") {
+        features.push(String::from("cranelift-verifier"));
+    }
+    ServerMessagePayload::GetCapabilities {
+        capabilities: Capabilities {
+            protocol_version: PROTOCOL_VERSION,
+            node_kinds: NodeKind::ALL.to_vec(),
+            output_drivers: OUTPUT_DRIVERS
+                .iter()
+                .map(|&name| String::from(name))
+                .collect(),
+            max_fixtures: project_manager.max_fixtures(),
+            features,
+        },
+    }
+}
+
 /// Handle a StopAllProjects request
 fn handle_stop_all_projects(
     project_manager: &mut ProjectManager,
+    events: &mut EventLog,
 ) -> Result<ServerMessagePayload, ServerError> {
+    for project in project_manager.list_loaded_projects() {
+        events.record(ServerEventKind::ProjectUnloaded { path: project.path });
+    }
     project_manager.unload_all_projects();
     Ok(ServerMessagePayload::StopAllProjects)
 }
@@ -0,0 +1,111 @@
+//! Undo/redo history for file edits made to a project through the server
+//!
+//! Each step records the contents of the files it touched before and after the edit.
+//! Undoing or redoing writes those contents back to the filesystem; the project then
+//! picks up the change like any other edit and hot-reloads the affected nodes.
+
+extern crate alloc;
+
+use crate::error::ServerError;
+use alloc::{collections::VecDeque, format, vec::Vec};
+use lp_model::{LpPath, LpPathBuf};
+use lp_shared::fs::LpFs;
+
+/// Maximum number of steps kept for undo (older steps are dropped)
+pub const MAX_HISTORY_LEN: usize = 16;
+
+/// A single file changed by an edit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEdit {
+    /// Path in the server's base filesystem
+    pub path: LpPathBuf,
+    /// Contents before the edit (`None` if the file didn't exist)
+    pub before: Option<Vec<u8>>,
+    /// Contents after the edit (`None` if the file was deleted)
+    pub after: Option<Vec<u8>>,
+}
+
+/// Bounded undo/redo history of a project's file edits
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    /// Steps that can be undone, oldest first
+    undo: VecDeque<Vec<FileEdit>>,
+    /// Steps that can be redone, most recently undone last
+    redo: Vec<Vec<FileEdit>>,
+}
+
+impl EditHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a step made up of one or more file edits
+    ///
+    /// Clears the redo history, since it no longer applies on top of the new edit.
+    pub fn record(&mut self, step: Vec<FileEdit>) {
+        if step.is_empty() {
+            return;
+        }
+        self.redo.clear();
+        self.undo.push_back(step);
+        if self.undo.len() > MAX_HISTORY_LEN {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Whether there is a step to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is a step to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Revert the most recent step, returning false if there was nothing to undo
+    pub fn undo(&mut self, fs: &dyn LpFs) -> Result<bool, ServerError> {
+        let Some(step) = self.undo.pop_back() else {
+            return Ok(false);
+        };
+        // Restore in reverse order in case the step touched the same file twice
+        for edit in step.iter().rev() {
+            restore(fs, edit.path.as_path(), edit.before.as_deref())?;
+        }
+        self.redo.push(step);
+        Ok(true)
+    }
+
+    /// Re-apply the most recently undone step, returning false if there was nothing to redo
+    pub fn redo(&mut self, fs: &dyn LpFs) -> Result<bool, ServerError> {
+        let Some(step) = self.redo.pop() else {
+            return Ok(false);
+        };
+        for edit in &step {
+            restore(fs, edit.path.as_path(), edit.after.as_deref())?;
+        }
+        self.undo.push_back(step);
+        Ok(true)
+    }
+
+    /// Forget all history
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+/// Put a file back to the given contents, deleting it if it shouldn't exist
+pub(crate) fn restore(
+    fs: &dyn LpFs,
+    path: &LpPath,
+    contents: Option<&[u8]>,
+) -> Result<(), ServerError> {
+    let result = match contents {
+        Some(data) => fs.write_file_atomic(path, data),
+        None if fs.file_exists(path).unwrap_or(false) => fs.delete_file(path),
+        None => Ok(()),
+    };
+    result.map_err(|e| ServerError::Filesystem(format!("Failed to restore {}: {e}", path.as_str())))
+}
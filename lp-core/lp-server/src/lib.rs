@@ -10,14 +10,26 @@
 
 #![no_std]
 
+pub mod duplicate;
+pub mod edits;
 pub mod error;
+pub mod event_log;
+pub mod frame_sync;
 pub mod handlers;
+pub mod history;
 pub mod project;
 pub mod project_manager;
 pub mod server;
 pub mod template;
+pub mod test_pattern;
 
+pub use duplicate::duplicate_node;
+pub use edits::apply_edits;
 pub use error::ServerError;
+pub use event_log::EventLog;
+pub use frame_sync::FrameSync;
+pub use history::{EditHistory, FileEdit};
 pub use project::Project;
 pub use project_manager::ProjectManager;
 pub use server::LpServer;
+pub use test_pattern::TestPatterns;
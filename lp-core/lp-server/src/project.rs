@@ -3,11 +3,12 @@
 extern crate alloc;
 
 use crate::error::ServerError;
-use alloc::{format, rc::Rc, string::String};
+use crate::history::EditHistory;
+use alloc::{format, rc::Rc, string::String, vec::Vec};
 use core::cell::RefCell;
 use lp_engine::ProjectRuntime;
 use lp_model::{LpPath, LpPathBuf};
-use lp_shared::fs::{FsVersion, LpFs};
+use lp_shared::fs::{FsChange, FsVersion, LpFs};
 use lp_shared::output::OutputProvider;
 
 /// A project instance wrapping a ProjectRuntime
@@ -20,6 +21,8 @@ pub struct Project {
     runtime: ProjectRuntime,
     /// Last filesystem version processed by this project
     last_fs_version: FsVersion,
+    /// Undo/redo history of edits made through the server
+    history: EditHistory,
 }
 
 impl Project {
@@ -41,6 +44,7 @@ impl Project {
             path: path.to_path_buf(),
             runtime,
             last_fs_version: FsVersion::default(),
+            history: EditHistory::new(),
         })
     }
 
@@ -65,11 +69,24 @@ impl Project {
     }
 
     /// Reload the project from the filesystem
+    ///
+    /// Clears the undo/redo history, since a full reload starts from what's on disk.
     pub fn reload(&mut self) -> Result<(), ServerError> {
         // todo!("Implement project reload")
+        self.history.clear();
         Ok(())
     }
 
+    /// Get the undo/redo history
+    pub fn history(&self) -> &EditHistory {
+        &self.history
+    }
+
+    /// Get mutable access to the undo/redo history
+    pub fn history_mut(&mut self) -> &mut EditHistory {
+        &mut self.history
+    }
+
     /// Get the last filesystem version processed by this project
     pub fn last_fs_version(&self) -> FsVersion {
         self.last_fs_version
@@ -79,4 +96,23 @@ impl Project {
     pub fn update_fs_version(&mut self, version: FsVersion) {
         self.last_fs_version = version;
     }
+
+    /// Changes to the project's files since the last processed filesystem version
+    ///
+    /// Paths are relative to the project root, as the runtime expects them.
+    pub fn pending_changes(&self, base_fs: &dyn LpFs) -> Vec<FsChange> {
+        // Build project prefix path using join - ensure it ends with /
+        let project_prefix = LpPathBuf::from("/").join(self.path.as_str()).join("");
+        base_fs
+            .get_changes_since(self.last_fs_version)
+            .into_iter()
+            .filter_map(|change| {
+                let stripped = change.path.strip_prefix(project_prefix.as_str())?;
+                Some(FsChange {
+                    path: stripped.to_path_buf(),
+                    change_type: change.change_type,
+                })
+            })
+            .collect()
+    }
 }
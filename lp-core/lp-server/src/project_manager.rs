@@ -13,6 +13,7 @@ use alloc::{
 };
 use core::cell::RefCell;
 use hashbrown::HashMap;
+use lp_model::nodes::NodeKind;
 use lp_model::project::ProjectHandle;
 use lp_model::{LpPath, LpPathBuf};
 use lp_shared::fs::LpFs;
@@ -28,6 +29,8 @@ pub struct ProjectManager {
     next_handle_id: u32,
     /// Base directory where projects are stored (relative path)
     projects_base_dir: LpPathBuf,
+    /// Most fixture nodes a project may have (None for no limit)
+    max_fixtures: Option<u32>,
 }
 
 impl ProjectManager {
@@ -41,9 +44,20 @@ impl ProjectManager {
             name_to_handle: HashMap::new(),
             next_handle_id: 1,
             projects_base_dir: projects_base_dir.to_path_buf(),
+            max_fixtures: None,
         }
     }
 
+    /// Most fixture nodes a project may have (None for no limit)
+    pub fn max_fixtures(&self) -> Option<u32> {
+        self.max_fixtures
+    }
+
+    /// Limit the number of fixture nodes in projects loaded from now on
+    pub fn set_max_fixtures(&mut self, max_fixtures: Option<u32>) {
+        self.max_fixtures = max_fixtures;
+    }
+
     /// Create a new project
     ///
     /// Creates the project directory structure using the provided filesystem.
@@ -104,6 +118,19 @@ impl ProjectManager {
         project.runtime_mut().load_nodes().map_err(|e| {
             ServerError::Core(format!("Failed to load nodes for project {name}: {e}"))
         })?;
+        if let Some(max_fixtures) = self.max_fixtures {
+            let fixture_count = project
+                .runtime()
+                .nodes
+                .values()
+                .filter(|entry| entry.kind == NodeKind::Fixture)
+                .count();
+            if fixture_count > max_fixtures as usize {
+                return Err(ServerError::Core(format!(
+                    "Project {name} has {fixture_count} fixtures, more than the server limit of {max_fixtures}"
+                )));
+            }
+        }
         project.runtime_mut().init_nodes().map_err(|e| {
             ServerError::Core(format!(
                 "Failed to initialize nodes for project {name}: {e}"
@@ -186,6 +213,15 @@ impl ProjectManager {
         self.projects.get_mut(&handle)
     }
 
+    /// Get the loaded project containing a path in the base filesystem
+    pub fn project_for_path_mut(&mut self, path: &LpPath) -> Option<&mut Project> {
+        self.projects.values_mut().find(|project| {
+            path.as_str()
+                .strip_prefix(project.path().as_str())
+                .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+
     /// Get handle by project name
     pub fn get_handle_by_name(&self, name: &str) -> Option<ProjectHandle> {
         self.name_to_handle.get(name).copied()
@@ -3,13 +3,17 @@
 extern crate alloc;
 
 use crate::error::ServerError;
-use crate::handlers;
+use crate::event_log::EventLog;
+use crate::frame_sync::FrameSync;
+use crate::handlers::{self, RequestContext};
 use crate::project_manager::ProjectManager;
+use crate::test_pattern::TestPatterns;
 use alloc::{boxed::Box, format, rc::Rc, string::ToString, vec::Vec};
 use core::cell::RefCell;
 use hashbrown::HashMap;
 use log;
-use lp_model::{LpPath, LpPathBuf, Message};
+use lp_model::server::ServerConfig;
+use lp_model::{ConnectionId, DEFAULT_CONNECTION, LpPath, Message, PROTOCOL_VERSION};
 use lp_shared::fs::{FsChange, LpFs};
 use lp_shared::output::OutputProvider;
 
@@ -27,6 +31,15 @@ pub struct LpServer {
     base_fs: Box<dyn LpFs>,
     /// Last frame processing time in microseconds (for theoretical FPS calculation)
     last_frame_time_us: RefCell<Option<u64>>,
+    /// Protocol version agreed with each connection by its `Hello` (the current
+    /// version until one is received)
+    protocol_versions: HashMap<ConnectionId, u32>,
+    /// Recent significant events, queryable by clients
+    events: EventLog,
+    /// Test patterns written straight to outputs, bypassing projects
+    test_patterns: TestPatterns,
+    /// Host clock projects render in step with, once a client sends `SyncClock`
+    frame_sync: Option<FrameSync>,
 }
 
 impl LpServer {
@@ -64,9 +77,19 @@ impl LpServer {
             project_manager,
             base_fs,
             last_frame_time_us: RefCell::new(None),
+            protocol_versions: HashMap::new(),
+            events: EventLog::new(),
+            test_patterns: TestPatterns::new(),
+            frame_sync: None,
         }
     }
 
+    /// Apply a server configuration (from `server.json`)
+    pub fn with_config(mut self, config: &ServerConfig) -> Self {
+        self.project_manager.set_max_fixtures(config.max_fixtures);
+        self
+    }
+
     /// Process incoming messages and return responses
     ///
     /// This is the main entry point for processing client messages. It handles
@@ -104,6 +127,27 @@ impl LpServer {
         delta_ms: u32,
         incoming: Vec<Message>,
     ) -> Result<Vec<Message>, ServerError> {
+        let incoming = incoming
+            .into_iter()
+            .map(|message| (DEFAULT_CONNECTION, message))
+            .collect();
+        let responses = self.tick_from(delta_ms, incoming)?;
+        Ok(responses.into_iter().map(|(_, message)| message).collect())
+    }
+
+    /// Process incoming messages from several connections and return responses
+    ///
+    /// Like [`LpServer::tick`], but each message is paired with the connection it
+    /// arrived on, and each response with the connection to send it to. The protocol
+    /// version is agreed per connection, so one client's `Hello` doesn't change what
+    /// the others may send.
+    pub fn tick_from(
+        &mut self,
+        delta_ms: u32,
+        incoming: Vec<(ConnectionId, Message)>,
+    ) -> Result<Vec<(ConnectionId, Message)>, ServerError> {
+        self.events.advance(delta_ms);
+
         // Process filesystem changes for all loaded projects
         // Collect project info first to avoid borrowing issues
         let project_info: Vec<_> = self
@@ -121,37 +165,9 @@ impl LpServer {
         // Collect changes per project
         let mut project_changes_map: HashMap<_, Vec<FsChange>> = HashMap::new();
 
-        for (handle, project_path) in &project_info {
+        for (handle, _) in &project_info {
             if let Some(project) = self.project_manager.get_project(*handle) {
-                let last_version = project.last_fs_version();
-
-                // Query changes from base_fs
-                let base_changes = self.base_fs().get_changes_since(last_version);
-
-                // If no changes, skip this project
-                if base_changes.is_empty() {
-                    continue;
-                }
-
-                // Filter changes for this project
-                // Build project prefix path using join - ensure it ends with /
-                let project_prefix_buf = LpPathBuf::from("/").join(project_path.as_str()).join("");
-                let project_prefix = project_prefix_buf.as_str();
-                let project_changes: Vec<FsChange> = base_changes
-                    .into_iter()
-                    .filter_map(|change| {
-                        // Use LpPath to strip prefix and normalize
-                        if let Some(stripped) = change.path.strip_prefix(project_prefix) {
-                            Some(FsChange {
-                                path: stripped.to_path_buf(),
-                                change_type: change.change_type,
-                            })
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-
+                let project_changes = project.pending_changes(self.base_fs());
                 if !project_changes.is_empty() {
                     project_changes_map.insert(*handle, project_changes);
                 }
@@ -194,7 +210,16 @@ impl LpServer {
                 );
                 // Ignore errors and continue with other projects
                 // Errors will be visible when clients sync or query project state
-                match project.runtime_mut().tick(delta_ms) {
+                // A synced project renders the host clock's current frame, skipping
+                // ticks that land within the frame it already rendered
+                let result = match self.frame_sync {
+                    Some(sync) => project
+                        .runtime_mut()
+                        .tick_at(sync.frame_at(self.events.uptime_ms()))
+                        .map(|_| ()),
+                    None => project.runtime_mut().tick(delta_ms),
+                };
+                match result {
                     Ok(()) => {
                         log::trace!("LpServer::tick: Project {} tick succeeded", project.name());
                     }
@@ -228,30 +253,43 @@ impl LpServer {
         // Process incoming messages AFTER ticking projects
         // This ensures GetChanges requests see the current frame's data
         let mut responses = Vec::new();
-        for message in incoming {
+        for (connection, message) in incoming {
             match message {
                 Message::Client(client_msg) => {
                     // Process client message and generate response
                     let theoretical_fps = self.theoretical_fps();
                     let msg_id = client_msg.id;
+                    let mut ctx = RequestContext {
+                        protocol_version: self
+                            .protocol_versions
+                            .entry(connection)
+                            .or_insert(PROTOCOL_VERSION),
+                        events: &mut self.events,
+                        test_patterns: &mut self.test_patterns,
+                        frame_sync: &mut self.frame_sync,
+                        theoretical_fps,
+                    };
                     match handlers::handle_client_message(
                         &mut self.project_manager,
                         &mut *self.base_fs,
                         &self.output_provider,
+                        &mut ctx,
                         client_msg,
-                        theoretical_fps,
                     ) {
                         Ok(response) => {
-                            responses.push(Message::Server(response));
+                            responses.push((connection, Message::Server(response)));
                         }
                         Err(e) => {
                             // Send error response for this message
-                            responses.push(Message::Server(lp_model::ServerMessage {
-                                id: msg_id,
-                                msg: lp_model::server::ServerMsgBody::Error {
-                                    error: format!("{e}"),
-                                },
-                            }));
+                            responses.push((
+                                connection,
+                                Message::Server(lp_model::ServerMessage {
+                                    id: msg_id,
+                                    msg: lp_model::server::ServerMsgBody::Error {
+                                        error: format!("{e}"),
+                                    },
+                                }),
+                            ));
                         }
                     }
                 }
@@ -265,6 +303,15 @@ impl LpServer {
             }
         }
 
+        // Log nodes that went into an error state during this tick or its requests
+        self.events.record_node_errors(&self.project_manager);
+
+        // Test patterns go out after the requests, so a new pattern shows this tick
+        if !self.test_patterns.is_empty() {
+            self.test_patterns
+                .render(&*self.output_provider.borrow(), delta_ms);
+        }
+
         Ok(responses)
     }
 
@@ -291,6 +338,38 @@ impl LpServer {
         &mut *self.base_fs
     }
 
+    /// Protocol version used with `connection`
+    ///
+    /// Message handling branches on this: requests newer than it are rejected.
+    pub fn protocol_version(&self, connection: ConnectionId) -> u32 {
+        self.protocol_versions
+            .get(&connection)
+            .copied()
+            .unwrap_or(PROTOCOL_VERSION)
+    }
+
+    /// Forget the state kept for a connection that has closed
+    ///
+    /// A new connection starts again at the current protocol version.
+    pub fn disconnect(&mut self, connection: ConnectionId) {
+        self.protocol_versions.remove(&connection);
+    }
+
+    /// Get the log of recent server events
+    pub fn events(&self) -> &EventLog {
+        &self.events
+    }
+
+    /// Get the test patterns currently shown on outputs
+    pub fn test_patterns(&self) -> &TestPatterns {
+        &self.test_patterns
+    }
+
+    /// Get the host clock projects render in step with, if synced
+    pub fn frame_sync(&self) -> Option<&FrameSync> {
+        self.frame_sync.as_ref()
+    }
+
     /// Set the last frame processing time (called by server loop)
     ///
     /// # Arguments
@@ -0,0 +1,168 @@
+//! Test patterns written straight to an output, for hardware bring-up
+//!
+//! A test pattern bypasses the node graph: the server opens the output channel itself
+//! and renders the pattern into it every tick until the pattern is cleared. This lets
+//! a fixture's wiring, LED count and color order be checked without a project.
+
+extern crate alloc;
+
+use crate::error::ServerError;
+use alloc::{format, string::ToString, vec, vec::Vec};
+use lp_engine::nodes::output::channel_params;
+use lp_model::nodes::fixture::ColorOrder;
+use lp_model::{TestPattern, TestPatternFixture};
+use lp_shared::output::{OutputChannelHandle, OutputFormat, OutputProvider};
+
+/// Time the lit LED of [`TestPattern::Chase`] stays on each position
+const CHASE_STEP_MS: u64 = 50;
+
+/// Time for [`TestPattern::Rainbow`] to scroll by one step of its 256-step hue wheel
+const RAINBOW_STEP_MS: u64 = 20;
+
+/// A test pattern being shown on one output channel
+struct ActivePattern {
+    pin: u32,
+    format: OutputFormat,
+    handle: OutputChannelHandle,
+    pattern: TestPattern,
+    color_order: ColorOrder,
+    /// Channel data, rewritten every frame
+    data: Vec<u8>,
+}
+
+/// Test patterns currently shown, one per output channel
+#[derive(Default)]
+pub struct TestPatterns {
+    active: Vec<ActivePattern>,
+    /// Time since the first pattern was shown, which animated patterns run from
+    elapsed_ms: u64,
+}
+
+impl TestPatterns {
+    /// Create with no patterns showing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check whether any pattern is showing
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+
+    /// Show `pattern` on `fixture`, replacing any pattern already on its output, or
+    /// clear the output when `pattern` is `None`
+    ///
+    /// Clearing turns the LEDs off and closes the channel.
+    pub fn set(
+        &mut self,
+        provider: &dyn OutputProvider,
+        fixture: &TestPatternFixture,
+        pattern: Option<TestPattern>,
+    ) -> Result<(), ServerError> {
+        // The fixture isn't a project node, so name the request in errors
+        let (pin, format) = channel_params(&fixture.output, "test-pattern")
+            .map_err(|e| ServerError::Output(e.to_string()))?;
+
+        if let Some(index) = self
+            .active
+            .iter()
+            .position(|active| active.pin == pin && active.format == format)
+        {
+            let mut active = self.active.remove(index);
+            active.data.fill(0);
+            let _ = provider.write(active.handle, &active.data);
+            provider
+                .close(active.handle)
+                .map_err(|e| ServerError::Output(e.to_string()))?;
+        }
+
+        let Some(pattern) = pattern else {
+            return Ok(());
+        };
+        let color_order = fixture.color_order.unwrap_or(ColorOrder::Rgb);
+        let byte_count = fixture
+            .led_count
+            .saturating_mul(color_order.bytes_per_pixel() as u32);
+        if let Some(max) = provider.max_byte_count(pin, format)
+            && byte_count > max
+        {
+            return Err(ServerError::Output(format!(
+                "{} LEDs need {byte_count} bytes, but the output carries at most {max}",
+                fixture.led_count
+            )));
+        }
+        let handle = provider
+            .open(pin, byte_count, format)
+            .map_err(|e| ServerError::Output(e.to_string()))?;
+        self.active.push(ActivePattern {
+            pin,
+            format,
+            handle,
+            pattern,
+            color_order,
+            data: vec![0; byte_count as usize],
+        });
+        Ok(())
+    }
+
+    /// Advance animated patterns by `delta_ms` and write every pattern to its output
+    pub fn render(&mut self, provider: &dyn OutputProvider, delta_ms: u32) {
+        if self.active.is_empty() {
+            self.elapsed_ms = 0;
+            return;
+        }
+        self.elapsed_ms += u64::from(delta_ms);
+
+        for active in &mut self.active {
+            let bytes_per_pixel = active.color_order.bytes_per_pixel();
+            let led_count = active.data.len() / bytes_per_pixel;
+            for index in 0..led_count {
+                let (r, g, b) = pattern_color(active.pattern, index, led_count, self.elapsed_ms);
+                active
+                    .color_order
+                    .write_rgb(&mut active.data, index * bytes_per_pixel, r, g, b);
+            }
+            if let Err(e) = provider.write(active.handle, &active.data) {
+                log::warn!("Test pattern write to pin {} failed: {e}", active.pin);
+            }
+        }
+    }
+}
+
+/// Color of LED `index` of `led_count` for `pattern`, `elapsed_ms` into the pattern
+fn pattern_color(
+    pattern: TestPattern,
+    index: usize,
+    led_count: usize,
+    elapsed_ms: u64,
+) -> (u8, u8, u8) {
+    match pattern {
+        TestPattern::Red => (255, 0, 0),
+        TestPattern::Green => (0, 255, 0),
+        TestPattern::Blue => (0, 0, 255),
+        TestPattern::White => (255, 255, 255),
+        TestPattern::Chase => {
+            if (elapsed_ms / CHASE_STEP_MS) % led_count as u64 == index as u64 {
+                (255, 255, 255)
+            } else {
+                (0, 0, 0)
+            }
+        }
+        TestPattern::Rainbow => {
+            let offset = (index * 256 / led_count) as u64;
+            hue_wheel(((offset + elapsed_ms / RAINBOW_STEP_MS) % 256) as u8)
+        }
+    }
+}
+
+/// Fully saturated color for a hue on a 0-255 wheel (red, through green and blue,
+/// back to red)
+fn hue_wheel(hue: u8) -> (u8, u8, u8) {
+    let segment = hue / 86;
+    let ramp = (hue % 86) * 3;
+    match segment {
+        0 => (255 - ramp, ramp, 0),
+        1 => (0, 255 - ramp, ramp),
+        _ => (ramp, 0, 255 - ramp),
+    }
+}
@@ -0,0 +1,125 @@
+extern crate alloc;
+
+mod common;
+use common::{PROJECT_PATH, load_project, new_server, request, server_fs};
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::TextureRuntime;
+use lp_model::nodes::texture::TextureConfig;
+use lp_model::project::FrameId;
+use lp_model::server::ServerMsgBody;
+use lp_model::{AsLpPathBuf, ClientRequest, LpPathBuf, NodeEdit};
+use lp_server::LpServer;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+/// Width of a texture node as currently configured in the running project, and the
+/// frame its config was last loaded at
+fn texture_width(server: &LpServer, texture_path: &LpPathBuf) -> (u32, FrameId) {
+    let project = server
+        .project_manager()
+        .get_project(server.project_manager().list_loaded_projects()[0].handle)
+        .unwrap();
+    let runtime = project.runtime();
+    let handle = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    let entry = runtime.nodes.get(&handle).unwrap();
+    let width = entry
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap()
+        .get_config()
+        .unwrap()
+        .width;
+    (width, entry.config_ver)
+}
+
+/// Edit replacing a texture's node.json with one of the given width
+fn width_edit(server: &LpServer, texture_path: &LpPathBuf, width: u32) -> NodeEdit {
+    let path = texture_path.join("node.json");
+    let original = server
+        .base_fs()
+        .read_file(
+            PROJECT_PATH
+                .as_path_buf()
+                .join(&path.as_str()[1..])
+                .as_path(),
+        )
+        .unwrap();
+    let mut config: TextureConfig = lp_model::json::from_slice(&original).unwrap();
+    config.width = width;
+    NodeEdit {
+        path,
+        data: lp_model::json::to_string(&config).unwrap().into_bytes(),
+    }
+}
+
+#[test]
+fn test_apply_edits_batch() {
+    // Build a project with two textures and copy it into the server filesystem
+    let temp_fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(temp_fs.clone());
+    let first = builder.texture().width(8).height(4).add(&mut builder);
+    let second = builder.texture().width(8).height(4).add(&mut builder);
+    builder.build();
+
+    let project_prefix = PROJECT_PATH.as_path_buf();
+    let (mut server, _) = new_server(server_fs(&temp_fs));
+    let handle = load_project(&mut server);
+
+    // A valid batch lands in one reload: both nodes change together and the next
+    // tick doesn't load them again
+    let edits = vec![
+        width_edit(&server, &first, 16),
+        width_edit(&server, &second, 32),
+    ];
+    let response = request(&mut server, ClientRequest::ApplyEdits { handle, edits });
+    assert_eq!(response, ServerMsgBody::ApplyEdits);
+    let (first_width, first_ver) = texture_width(&server, &first);
+    let (second_width, second_ver) = texture_width(&server, &second);
+    assert_eq!((first_width, second_width), (16, 32));
+    assert_eq!(first_ver, second_ver);
+    server.tick(16, vec![]).unwrap();
+    assert_eq!(texture_width(&server, &first), (16, first_ver));
+    assert_eq!(texture_width(&server, &second), (32, second_ver));
+
+    // An invalid batch is rolled back entirely, including its valid edit
+    let second_json = project_prefix.join(&second.as_str()[1..]).join("node.json");
+    let second_before = server.base_fs().read_file(second_json.as_path()).unwrap();
+    let edits = vec![
+        width_edit(&server, &first, 64),
+        NodeEdit {
+            path: second.join("node.json"),
+            data: b"{ not json".to_vec(),
+        },
+    ];
+    let response = request(&mut server, ClientRequest::ApplyEdits { handle, edits });
+    match response {
+        ServerMsgBody::Error { error } => {
+            assert!(
+                error.contains("nothing was changed"),
+                "Unexpected error: {error}"
+            )
+        }
+        other => panic!("Expected an error, got {other:?}"),
+    }
+    server.tick(16, vec![]).unwrap();
+    assert_eq!(texture_width(&server, &first).0, 16);
+    assert_eq!(texture_width(&server, &second).0, 32);
+    assert_eq!(
+        server.base_fs().read_file(second_json.as_path()).unwrap(),
+        second_before
+    );
+
+    // The valid batch is a single undo step
+    assert!(matches!(
+        request(&mut server, ClientRequest::Undo { handle }),
+        ServerMsgBody::Undo { applied: true }
+    ));
+    server.tick(16, vec![]).unwrap();
+    assert_eq!(texture_width(&server, &first).0, 8);
+    assert_eq!(texture_width(&server, &second).0, 8);
+}
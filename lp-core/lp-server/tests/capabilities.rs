@@ -0,0 +1,50 @@
+extern crate alloc;
+
+mod common;
+use common::{PROJECT_PATH, new_server, request, server_fs};
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_model::ClientRequest;
+use lp_model::server::{ServerConfig, ServerMsgBody};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+#[test]
+fn test_max_fixtures_is_reported_and_enforced() {
+    // Build a project with two fixtures and copy it into the server filesystem
+    let temp_fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(temp_fs.clone());
+    let texture_path = builder.texture_basic();
+    let output_path = builder.output_basic();
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.fixture_basic(&output_path, &texture_path);
+    builder.build();
+
+    let (server, _) = new_server(server_fs(&temp_fs));
+    let mut server = server.with_config(&ServerConfig {
+        max_fixtures: Some(1),
+    });
+
+    match request(&mut server, ClientRequest::GetCapabilities) {
+        ServerMsgBody::GetCapabilities { capabilities } => {
+            assert_eq!(capabilities.max_fixtures, Some(1));
+        }
+        other => panic!("Expected capabilities, got {other:?}"),
+    }
+
+    let response = request(
+        &mut server,
+        ClientRequest::LoadProject {
+            path: PROJECT_PATH.into(),
+        },
+    );
+    match response {
+        ServerMsgBody::Error { error } => assert!(
+            error.contains("more than the server limit of 1"),
+            "Unexpected error: {error}"
+        ),
+        other => panic!("Expected an error, got {other:?}"),
+    }
+    assert!(server.project_manager().list_loaded_projects().is_empty());
+}
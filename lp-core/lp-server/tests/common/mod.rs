@@ -0,0 +1,65 @@
+//! Helpers shared by the server integration tests
+
+#![allow(dead_code, reason = "each test binary uses a different subset")]
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::MemoryOutputProvider;
+use lp_model::project::ProjectHandle;
+use lp_model::server::ServerMsgBody;
+use lp_model::{AsLpPath, AsLpPathBuf, ClientMessage, ClientRequest, Message};
+use lp_server::LpServer;
+use lp_shared::fs::{LpFs, LpFsMemory};
+
+/// Where [`server_fs`] puts the test project on the server
+pub const PROJECT_PATH: &str = "/projects/test-project";
+
+/// Server filesystem holding the project built in `project_fs` at [`PROJECT_PATH`]
+pub fn server_fs(project_fs: &Rc<RefCell<LpFsMemory>>) -> LpFsMemory {
+    let base_fs = LpFsMemory::new();
+    let project_prefix = PROJECT_PATH.as_path_buf();
+    for path in project_fs.borrow().list_dir("/".as_path(), true).unwrap() {
+        if let Ok(data) = project_fs.borrow().read_file(path.as_path()) {
+            let relative = path.as_str().trim_start_matches('/');
+            base_fs
+                .write_file(project_prefix.join(relative).as_path(), &data)
+                .unwrap();
+        }
+    }
+    base_fs
+}
+
+/// Server on `base_fs`, with its outputs kept in memory
+pub fn new_server(base_fs: LpFsMemory) -> (LpServer, Rc<RefCell<MemoryOutputProvider>>) {
+    let output_provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+    let server = LpServer::new(
+        output_provider.clone(),
+        Box::new(base_fs),
+        "projects/".as_path(),
+    );
+    (server, output_provider)
+}
+
+/// Send one request through the server and return its response
+pub fn request(server: &mut LpServer, msg: ClientRequest) -> ServerMsgBody {
+    let mut responses = server
+        .tick(16, vec![Message::Client(ClientMessage { id: 1, msg })])
+        .unwrap();
+    match responses.pop() {
+        Some(Message::Server(response)) => response.msg,
+        other => panic!("Expected a server response, got {other:?}"),
+    }
+}
+
+/// Load the project at [`PROJECT_PATH`], returning its handle
+pub fn load_project(server: &mut LpServer) -> ProjectHandle {
+    match request(
+        server,
+        ClientRequest::LoadProject {
+            path: PROJECT_PATH.into(),
+        },
+    ) {
+        ServerMsgBody::LoadProject { handle } => handle,
+        other => panic!("Expected LoadProject response, got {other:?}"),
+    }
+}
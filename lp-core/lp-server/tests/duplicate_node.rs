@@ -0,0 +1,161 @@
+extern crate alloc;
+
+mod common;
+use common::{PROJECT_PATH, load_project, new_server, request, server_fs};
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_model::nodes::shader::ShaderConfig;
+use lp_model::nodes::texture::TextureConfig;
+use lp_model::project::ProjectHandle;
+use lp_model::server::ServerMsgBody;
+use lp_model::{AsLpPath, AsLpPathBuf, ClientRequest, LpPathBuf, NodeSpecifier};
+use lp_server::LpServer;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::{LpFs, LpFsMemory};
+
+/// Read and parse the shader config at `node_path` (relative to the project)
+fn shader_config(server: &LpServer, node_path: &str) -> ShaderConfig {
+    let path = format!("{PROJECT_PATH}{node_path}/node.json");
+    let data = server.base_fs().read_file(path.as_path()).unwrap();
+    lp_model::json::from_slice(&data).unwrap()
+}
+
+/// Load a project with a top-level texture and shader, plus a `/src/group` directory
+/// holding a texture and a shader that renders into it
+fn setup() -> (LpServer, ProjectHandle, LpPathBuf, LpPathBuf) {
+    let temp_fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(temp_fs.clone());
+    let texture_path = builder.texture_basic();
+    let shader_path = builder.shader_basic(&texture_path);
+    builder.build();
+
+    let base_fs = server_fs(&temp_fs);
+
+    let group_texture = TextureConfig {
+        width: 4,
+        height: 4,
+        image_path: None,
+        fill_color: None,
+        filter: None,
+        enabled: None,
+    };
+    let group_shader = ShaderConfig {
+        texture_spec: NodeSpecifier::from("/src/group/tex.texture"),
+        ..Default::default()
+    };
+    base_fs
+        .write_file(
+            "/projects/test-project/src/group/tex.texture/node.json".as_path(),
+            lp_model::json::to_string(&group_texture)
+                .unwrap()
+                .as_bytes(),
+        )
+        .unwrap();
+    base_fs
+        .write_file(
+            "/projects/test-project/src/group/main.shader/node.json".as_path(),
+            lp_model::json::to_string(&group_shader).unwrap().as_bytes(),
+        )
+        .unwrap();
+    base_fs
+        .write_file(
+            "/projects/test-project/src/group/main.shader/main.glsl".as_path(),
+            b"vec4 main(vec2 fragCoord, vec2 outputSize, float time) { return vec4(1.0); }",
+        )
+        .unwrap();
+
+    let (mut server, _) = new_server(base_fs);
+    let handle = load_project(&mut server);
+
+    (server, handle, texture_path, shader_path)
+}
+
+#[test]
+fn test_duplicate_subtree_remaps_internal_references() {
+    let (mut server, handle, _, _) = setup();
+
+    let path = match request(
+        &mut server,
+        ClientRequest::DuplicateNode {
+            handle,
+            source: "/src/group".as_path_buf(),
+            dest: "/src/group-copy".as_path_buf(),
+        },
+    ) {
+        ServerMsgBody::DuplicateNode { path } => path,
+        other => panic!("Expected DuplicateNode response, got {other:?}"),
+    };
+    assert_eq!(path.as_str(), "/src/group-copy");
+
+    // The copied shader renders into the copied texture, not the original
+    assert_eq!(
+        shader_config(&server, "/src/group-copy/main.shader")
+            .texture_spec
+            .as_str(),
+        "/src/group-copy/tex.texture"
+    );
+    assert_eq!(
+        shader_config(&server, "/src/group/main.shader")
+            .texture_spec
+            .as_str(),
+        "/src/group/tex.texture"
+    );
+
+    // Non-config files are copied unchanged
+    let fs = server.base_fs();
+    assert_eq!(
+        fs.read_file("/projects/test-project/src/group-copy/main.shader/main.glsl".as_path())
+            .unwrap(),
+        fs.read_file("/projects/test-project/src/group/main.shader/main.glsl".as_path())
+            .unwrap()
+    );
+    assert!(
+        fs.file_exists("/projects/test-project/src/group-copy/tex.texture/node.json".as_path())
+            .unwrap()
+    );
+
+    // The whole copy is undone in one step
+    assert!(matches!(
+        request(&mut server, ClientRequest::Undo { handle }),
+        ServerMsgBody::Undo { applied: true }
+    ));
+    assert!(
+        !server
+            .base_fs()
+            .file_exists("/projects/test-project/src/group-copy/main.shader/node.json".as_path())
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_duplicate_node_name_collision() {
+    let (mut server, handle, texture_path, shader_path) = setup();
+
+    // Duplicating onto an existing path gets a numeric suffix, twice in a row
+    let mut copies = Vec::new();
+    for _ in 0..2 {
+        match request(
+            &mut server,
+            ClientRequest::DuplicateNode {
+                handle,
+                source: shader_path.clone(),
+                dest: shader_path.clone(),
+            },
+        ) {
+            ServerMsgBody::DuplicateNode { path } => copies.push(path),
+            other => panic!("Expected DuplicateNode response, got {other:?}"),
+        }
+    }
+    let stem = shader_path.file_stem().unwrap();
+    assert_eq!(copies[0].as_str(), format!("/src/{stem}-1.shader"));
+    assert_eq!(copies[1].as_str(), format!("/src/{stem}-2.shader"));
+
+    // References outside the duplicated node are left pointing at the same node
+    assert_eq!(
+        shader_config(&server, copies[0].as_str())
+            .texture_spec
+            .as_str(),
+        texture_path.as_str()
+    );
+}
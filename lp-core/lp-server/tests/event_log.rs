@@ -0,0 +1,103 @@
+extern crate alloc;
+
+mod common;
+use common::{PROJECT_PATH, load_project, new_server, request, server_fs};
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_model::server::{FsRequest, ServerEventKind, ServerMsgBody};
+use lp_model::{AsLpPathBuf, ClientRequest};
+use lp_server::event_log::{EventLog, MAX_EVENTS};
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+#[test]
+fn test_node_error_is_logged() {
+    // Build a project with a working shader and copy it into the server filesystem
+    let temp_fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(temp_fs.clone());
+    let texture_path = builder.texture_basic();
+    let shader_path = builder
+        .shader(&texture_path)
+        .glsl(
+            "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return vec4(0.0, 1.0, 0.0, 1.0);
+            }",
+        )
+        .add(&mut builder);
+    builder.build();
+
+    let project_prefix = PROJECT_PATH.as_path_buf();
+    let (mut server, _) = new_server(server_fs(&temp_fs));
+    load_project(&mut server);
+
+    // Break the shader through the server
+    let glsl_path = project_prefix
+        .join(&shader_path.as_str()[1..])
+        .join("main.glsl");
+    request(
+        &mut server,
+        ClientRequest::Filesystem(FsRequest::Write {
+            path: glsl_path.clone(),
+            data: b"vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+                return undefined_color;
+            }"
+            .to_vec(),
+        }),
+    );
+    server.tick(16, vec![]).unwrap();
+    server.tick(16, vec![]).unwrap();
+
+    let events = match request(&mut server, ClientRequest::GetEvents) {
+        ServerMsgBody::GetEvents { events } => events,
+        other => panic!("Expected GetEvents response, got {other:?}"),
+    };
+    let kinds: Vec<&ServerEventKind> = events.iter().map(|event| &event.kind).collect();
+    assert!(matches!(
+        kinds[0],
+        ServerEventKind::ProjectLoaded { path } if path.as_str() == PROJECT_PATH
+    ));
+    assert!(
+        kinds
+            .iter()
+            .any(|kind| matches!(kind, ServerEventKind::FileEdited { path } if *path == glsl_path))
+    );
+
+    // The error is logged once, naming the node, even though it persists across ticks
+    let node_errors: Vec<_> = events
+        .iter()
+        .filter(|event| matches!(event.kind, ServerEventKind::NodeError { .. }))
+        .collect();
+    assert_eq!(node_errors.len(), 1, "Unexpected events: {events:?}");
+    match &node_errors[0].kind {
+        ServerEventKind::NodeError { node, .. } => assert_eq!(*node, shader_path),
+        _ => unreachable!(),
+    }
+
+    // Events are stamped with the server uptime, in order
+    assert!(node_errors[0].uptime_ms > events[0].uptime_ms);
+    assert!(
+        events
+            .windows(2)
+            .all(|pair| pair[0].uptime_ms <= pair[1].uptime_ms)
+    );
+}
+
+#[test]
+fn test_event_log_drops_oldest() {
+    let mut log = EventLog::new();
+    for i in 0..MAX_EVENTS + 3 {
+        log.advance(1);
+        log.record(ServerEventKind::FileEdited {
+            path: alloc::format!("/file-{i}").as_path_buf(),
+        });
+    }
+
+    let events = log.events();
+    assert_eq!(events.len(), MAX_EVENTS);
+    assert!(matches!(
+        &events[0].kind,
+        ServerEventKind::FileEdited { path } if path.as_str() == "/file-3"
+    ));
+    assert_eq!(events[0].uptime_ms, 4);
+}
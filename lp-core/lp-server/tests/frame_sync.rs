@@ -0,0 +1,240 @@
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::MemoryOutputProvider;
+use lp_model::server::ServerMsgBody;
+use lp_model::{AsLpPath, AsLpPathBuf, ClientMessage, ClientRequest, FrameId, Message};
+use lp_server::LpServer;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::{LpFs, LpFsMemory};
+use lp_shared::time::{TestClock, TimeProvider};
+
+/// Host clock reading when the shared wall clock reads zero
+const HOST_EPOCH_MS: u64 = 1_700_000_000_000;
+
+/// Frame length the host syncs the devices to
+const FRAME_INTERVAL_MS: u32 = 20;
+
+/// A controller: a server running one project, ticked on its own schedule
+struct Device {
+    server: LpServer,
+    provider: Rc<RefCell<MemoryOutputProvider>>,
+    clock: TestClock,
+    /// Time between ticks
+    tick_period_ms: u64,
+    /// Wall clock time of the last tick
+    last_tick_ms: u64,
+    /// Wall clock time each synced frame first reached the output
+    shown_at: BTreeMap<FrameId, u64>,
+    /// Bytes each synced frame wrote to the output
+    outputs: BTreeMap<FrameId, Vec<u8>>,
+}
+
+impl Device {
+    /// Boot a device at the clock's current time and load a project on it
+    fn boot(clock: &TestClock, tick_period_ms: u64) -> Self {
+        let temp_fs = Rc::new(RefCell::new(LpFsMemory::new()));
+        let mut builder = ProjectBuilder::new(temp_fs.clone());
+        let texture_path = builder.texture_basic();
+        builder.shader_basic(&texture_path);
+        let output_path = builder.output_basic();
+        builder.fixture_basic(&output_path, &texture_path);
+        builder.build();
+
+        let project_prefix = "/projects/test-project".as_path_buf();
+        let base_fs = Box::new(LpFsMemory::new());
+        for path in temp_fs.borrow().list_dir("/".as_path(), true).unwrap() {
+            if let Ok(data) = temp_fs.borrow().read_file(path.as_path()) {
+                let relative = path.as_str().trim_start_matches('/');
+                base_fs
+                    .write_file(project_prefix.join(relative).as_path(), &data)
+                    .unwrap();
+            }
+        }
+
+        let provider = Rc::new(RefCell::new(MemoryOutputProvider::new()));
+        let mut device = Self {
+            server: LpServer::new(provider.clone(), base_fs, "projects/".as_path()),
+            provider,
+            clock: clock.clone(),
+            tick_period_ms,
+            last_tick_ms: clock.now_ms(),
+            shown_at: BTreeMap::new(),
+            outputs: BTreeMap::new(),
+        };
+        device.tick(vec![ClientRequest::LoadProject {
+            path: "/projects/test-project".into(),
+        }]);
+        device
+    }
+
+    /// Tick if the device's next tick is due
+    fn poll(&mut self) {
+        if self.clock.now_ms() >= self.last_tick_ms + self.tick_period_ms {
+            self.tick(vec![]);
+        }
+    }
+
+    /// Tick now with `requests`, returning the responses
+    fn tick(&mut self, requests: Vec<ClientRequest>) -> Vec<ServerMsgBody> {
+        let now = self.clock.now_ms();
+        let incoming = requests
+            .into_iter()
+            .map(|msg| Message::Client(ClientMessage { id: 1, msg }))
+            .collect();
+        let responses = self
+            .server
+            .tick((now - self.last_tick_ms) as u32, incoming)
+            .unwrap();
+        self.last_tick_ms = now;
+
+        if let Some(stamp) = self.frame() {
+            self.shown_at.entry(stamp).or_insert(now);
+            if let Some(data) = self.data() {
+                self.outputs.entry(stamp).or_insert(data);
+            }
+        }
+        responses
+            .into_iter()
+            .map(|response| match response {
+                Message::Server(response) => response.msg,
+                other => panic!("Expected a server response, got {other:?}"),
+            })
+            .collect()
+    }
+
+    /// Frame last written to the fixture's output
+    fn frame(&self) -> Option<FrameId> {
+        let provider = self.provider.borrow();
+        let handle = provider.get_handle_for_pin(0)?;
+        provider.get_frame(handle).map(|stamp| stamp.frame)
+    }
+
+    /// Bytes last written to the fixture's output
+    fn data(&self) -> Option<Vec<u8>> {
+        let provider = self.provider.borrow();
+        provider.get_data(provider.get_handle_for_pin(0)?)
+    }
+
+    /// Sync to the host clock, forgetting the frames shown before
+    fn sync(&mut self) {
+        let host_time_ms = HOST_EPOCH_MS + self.clock.now_ms();
+        let responses = self.tick(vec![ClientRequest::SyncClock {
+            host_time_ms,
+            frame_interval_ms: FRAME_INTERVAL_MS,
+        }]);
+        assert!(matches!(responses[..], [ServerMsgBody::SyncClock { .. }]));
+        self.shown_at.clear();
+        self.outputs.clear();
+    }
+}
+
+#[test]
+fn test_synced_servers_render_frames_together() {
+    let clock = TestClock::new();
+
+    // Two devices booted at different times, ticking at different rates
+    let mut a = Device::boot(&clock, 7);
+    clock.advance(333);
+    let mut b = Device::boot(&clock, 11);
+    for _ in 0..500 {
+        clock.advance(1);
+        a.poll();
+        b.poll();
+    }
+    // Unsynced, each stamps frames with its own count
+    assert_ne!(a.frame(), b.frame());
+
+    // The host sends both its clock
+    let sync = ClientRequest::SyncClock {
+        host_time_ms: HOST_EPOCH_MS + clock.now_ms(),
+        frame_interval_ms: FRAME_INTERVAL_MS,
+    };
+    let expected_frame =
+        FrameId::new(((HOST_EPOCH_MS + clock.now_ms()) / u64::from(FRAME_INTERVAL_MS)) as i64);
+    for device in [&mut a, &mut b] {
+        assert_eq!(
+            device.tick(vec![sync.clone()]),
+            [ServerMsgBody::SyncClock {
+                frame: expected_frame
+            }]
+        );
+        device.shown_at.clear();
+        device.outputs.clear();
+    }
+
+    let mut synced_outputs = BTreeSet::new();
+    for _ in 0..2000 {
+        clock.advance(1);
+        a.poll();
+        b.poll();
+        synced_outputs.extend(a.data());
+
+        // Once synced, each device shows the host clock's frame as of its last tick
+        for device in [&a, &b]
+            .into_iter()
+            .filter(|device| !device.shown_at.is_empty())
+        {
+            let host_time = HOST_EPOCH_MS + device.last_tick_ms;
+            let frame = (host_time / u64::from(FRAME_INTERVAL_MS)) as i64;
+            assert_eq!(device.frame(), Some(FrameId::new(frame)));
+        }
+    }
+
+    // Shader time keeps running after the sync, so the animated output keeps changing
+    assert!(
+        synced_outputs.len() > 10,
+        "only {} distinct outputs after sync",
+        synced_outputs.len()
+    );
+
+    // The same frames went out at the same wall time, give or take the slower tick
+    let common: Vec<_> = a
+        .shown_at
+        .iter()
+        .filter_map(|(frame, at_a)| b.shown_at.get(frame).map(|at_b| (*at_a, *at_b)))
+        .collect();
+    assert!(common.len() > 50, "only {} frames in common", common.len());
+    for (at_a, at_b) in common {
+        assert!(
+            at_a.abs_diff(at_b) <= b.tick_period_ms,
+            "frame shown at {at_a}ms on one device and {at_b}ms on the other"
+        );
+    }
+}
+
+#[test]
+fn test_devices_synced_at_different_times_render_the_same_content() {
+    let clock = TestClock::new();
+    let mut a = Device::boot(&clock, 7);
+    let mut b = Device::boot(&clock, 11);
+
+    // A joins the host clock well before B
+    a.sync();
+    for _ in 0..1500 {
+        clock.advance(1);
+        a.poll();
+        b.poll();
+    }
+    b.sync();
+    for _ in 0..1500 {
+        clock.advance(1);
+        a.poll();
+        b.poll();
+    }
+
+    // Frames both devices rendered carry the same bytes, however long each was synced
+    let common: Vec<_> = b
+        .outputs
+        .iter()
+        .filter_map(|(frame, at_b)| a.outputs.get(frame).map(|at_a| (frame, at_a, at_b)))
+        .collect();
+    assert!(common.len() > 10, "only {} frames in common", common.len());
+    for (frame, at_a, at_b) in &common {
+        assert_eq!(at_a, at_b, "devices rendered {frame:?} differently");
+    }
+    let distinct: BTreeSet<_> = common.iter().map(|(_, data, _)| data).collect();
+    assert!(distinct.len() > 1, "output never changed while synced");
+}
@@ -0,0 +1,181 @@
+extern crate alloc;
+
+mod common;
+use common::{new_server, request};
+
+use lp_model::project::handle::ProjectHandle;
+use lp_model::server::ServerMsgBody;
+use lp_model::{
+    ClientMessage, ClientRequest, ConnectionId, DEFAULT_CONNECTION, MIN_PROTOCOL_VERSION, Message,
+    PROTOCOL_VERSION,
+};
+use lp_server::LpServer;
+use lp_shared::fs::LpFsMemory;
+
+/// Send one request on `connection` through the server and return its response
+fn request_from(
+    server: &mut LpServer,
+    connection: ConnectionId,
+    msg: ClientRequest,
+) -> ServerMsgBody {
+    let incoming = vec![(connection, Message::Client(ClientMessage { id: 1, msg }))];
+    let mut responses = server.tick_from(16, incoming).unwrap();
+    match responses.pop() {
+        Some((to, Message::Server(response))) => {
+            assert_eq!(to, connection, "response sent to the wrong connection");
+            response.msg
+        }
+        other => panic!("Expected a server response, got {other:?}"),
+    }
+}
+
+/// Whether `response` refuses a request for needing a newer protocol version
+fn is_protocol_error(response: &ServerMsgBody) -> bool {
+    matches!(response, ServerMsgBody::Error { error } if error.contains("protocol version"))
+}
+
+#[test]
+fn test_older_client_is_downgraded() {
+    let (mut server, _) = new_server(LpFsMemory::new());
+
+    let response = request(
+        &mut server,
+        ClientRequest::Hello {
+            protocol_version: 1,
+        },
+    );
+    assert_eq!(
+        response,
+        ServerMsgBody::Hello {
+            protocol_version: 1
+        }
+    );
+    assert_eq!(server.protocol_version(DEFAULT_CONNECTION), 1);
+
+    // Version 1 requests still work
+    let response = request(&mut server, ClientRequest::ListLoadedProjects);
+    assert!(
+        matches!(response, ServerMsgBody::ListLoadedProjects { .. }),
+        "Expected a project list, got {response:?}"
+    );
+
+    // Requests added in version 2 are refused
+    let response = request(
+        &mut server,
+        ClientRequest::Undo {
+            handle: ProjectHandle::new(1),
+        },
+    );
+    match response {
+        ServerMsgBody::Error { error } => assert!(
+            error.contains("protocol version 2"),
+            "Unexpected error: {error}"
+        ),
+        other => panic!("Expected an error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_newer_client_gets_server_version() {
+    let (mut server, _) = new_server(LpFsMemory::new());
+
+    let response = request(
+        &mut server,
+        ClientRequest::Hello {
+            protocol_version: PROTOCOL_VERSION + 1,
+        },
+    );
+    assert_eq!(
+        response,
+        ServerMsgBody::Hello {
+            protocol_version: PROTOCOL_VERSION
+        }
+    );
+    assert_eq!(
+        server.protocol_version(DEFAULT_CONNECTION),
+        PROTOCOL_VERSION
+    );
+}
+
+#[test]
+fn test_unsupported_version_is_rejected() {
+    let (mut server, _) = new_server(LpFsMemory::new());
+
+    let response = request(
+        &mut server,
+        ClientRequest::Hello {
+            protocol_version: MIN_PROTOCOL_VERSION - 1,
+        },
+    );
+    match response {
+        ServerMsgBody::Error { error } => {
+            assert!(error.contains("not supported"), "Unexpected error: {error}")
+        }
+        other => panic!("Expected an error, got {other:?}"),
+    }
+    assert_eq!(
+        server.protocol_version(DEFAULT_CONNECTION),
+        PROTOCOL_VERSION
+    );
+
+    // The connection keeps working and can negotiate again
+    let response = request(
+        &mut server,
+        ClientRequest::Hello {
+            protocol_version: PROTOCOL_VERSION,
+        },
+    );
+    assert_eq!(
+        response,
+        ServerMsgBody::Hello {
+            protocol_version: PROTOCOL_VERSION
+        }
+    );
+}
+
+#[test]
+fn test_version_is_agreed_per_connection() {
+    let (mut server, _) = new_server(LpFsMemory::new());
+    let (old, new) = (1, 2);
+    let undo = ClientRequest::Undo {
+        handle: ProjectHandle::new(1),
+    };
+
+    request_from(
+        &mut server,
+        old,
+        ClientRequest::Hello {
+            protocol_version: 1,
+        },
+    );
+    request_from(
+        &mut server,
+        new,
+        ClientRequest::Hello {
+            protocol_version: PROTOCOL_VERSION,
+        },
+    );
+    assert_eq!(server.protocol_version(old), 1);
+    assert_eq!(server.protocol_version(new), PROTOCOL_VERSION);
+
+    // The old client's downgrade only limits its own connection
+    assert!(is_protocol_error(&request_from(
+        &mut server,
+        old,
+        undo.clone()
+    )));
+    let response = request_from(&mut server, new, undo.clone());
+    assert!(
+        !is_protocol_error(&response),
+        "Unexpected error: {response:?}"
+    );
+
+    // Once the old client disconnects, its connection id starts over
+    server.disconnect(old);
+    assert_eq!(server.protocol_version(old), PROTOCOL_VERSION);
+    let response = request_from(&mut server, old, undo);
+    assert!(
+        !is_protocol_error(&response),
+        "Unexpected error: {response:?}"
+    );
+}
@@ -3,8 +3,9 @@ extern crate alloc;
 use alloc::rc::Rc;
 use core::cell::RefCell;
 use lp_engine::MemoryOutputProvider;
-use lp_model::{AsLpPath, AsLpPathBuf, ClientMessage, ClientRequest};
-use lp_server::{LpServer, handlers::handle_client_message};
+use lp_model::{AsLpPath, AsLpPathBuf, ClientMessage, ClientRequest, PROTOCOL_VERSION};
+use lp_server::handlers::{RequestContext, handle_client_message};
+use lp_server::{EventLog, LpServer, TestPatterns};
 use lp_shared::ProjectBuilder;
 use lp_shared::fs::{LpFs, LpFsMemory};
 
@@ -114,7 +115,15 @@ fn test_stop_all_projects() {
     let response = unsafe {
         let pm = (*server_ptr).project_manager_mut();
         let fs = (*server_ptr).base_fs_mut();
-        handle_client_message(pm, fs, &output_provider, request, None).unwrap()
+        let mut protocol_version = PROTOCOL_VERSION;
+        let mut ctx = RequestContext {
+            protocol_version: &mut protocol_version,
+            events: &mut EventLog::new(),
+            test_patterns: &mut TestPatterns::new(),
+            frame_sync: &mut None,
+            theoretical_fps: None,
+        };
+        handle_client_message(pm, fs, &output_provider, &mut ctx, request).unwrap()
     };
 
     // Verify response is StopAllProjects
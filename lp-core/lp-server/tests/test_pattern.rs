@@ -0,0 +1,108 @@
+extern crate alloc;
+
+mod common;
+use common::{new_server, request};
+
+use lp_engine::MemoryOutputProvider;
+use lp_model::nodes::fixture::ColorOrder;
+use lp_model::nodes::output::OutputConfig;
+use lp_model::server::ServerMsgBody;
+use lp_model::{ClientRequest, TestPattern, TestPatternFixture};
+use lp_shared::fs::LpFsMemory;
+
+/// A strip of `led_count` LEDs on GPIO pin 4
+fn strip(led_count: u32, color_order: Option<ColorOrder>) -> TestPatternFixture {
+    TestPatternFixture {
+        output: OutputConfig::GpioStrip {
+            pin: 4,
+            dither: None,
+            power_limit: None,
+            slew_limit: None,
+        },
+        led_count,
+        color_order,
+    }
+}
+
+/// Data last written to pin 4
+fn pin_data(provider: &MemoryOutputProvider) -> Vec<u8> {
+    let handle = provider.get_handle_for_pin(4).expect("pin 4 is not open");
+    provider.get_data(handle).unwrap()
+}
+
+#[test]
+fn test_all_red_pattern() {
+    // No project is loaded: the pattern goes straight to the output
+    let (mut server, provider) = new_server(LpFsMemory::new());
+
+    let response = request(
+        &mut server,
+        ClientRequest::TestPattern {
+            fixture: strip(10, None),
+            pattern: Some(TestPattern::Red),
+        },
+    );
+    assert_eq!(response, ServerMsgBody::TestPattern);
+    let data = pin_data(&provider.borrow());
+    assert_eq!(data.len(), 30);
+    for led in data.chunks(3) {
+        assert_eq!(led, [255, 0, 0]);
+    }
+
+    // The pattern keeps showing until cleared
+    server.tick(16, vec![]).unwrap();
+    assert!(
+        pin_data(&provider.borrow())
+            .chunks(3)
+            .all(|led| led == [255, 0, 0])
+    );
+
+    // Clearing turns the LEDs off and releases the output
+    let response = request(
+        &mut server,
+        ClientRequest::TestPattern {
+            fixture: strip(10, None),
+            pattern: None,
+        },
+    );
+    assert_eq!(response, ServerMsgBody::TestPattern);
+    assert!(!provider.borrow().is_pin_open(4));
+    assert!(server.test_patterns().is_empty());
+}
+
+#[test]
+fn test_chase_pattern_moves_in_color_order() {
+    let (mut server, provider) = new_server(LpFsMemory::new());
+
+    // Green on a GRB strip is sent as its first byte
+    request(
+        &mut server,
+        ClientRequest::TestPattern {
+            fixture: strip(4, Some(ColorOrder::Grb)),
+            pattern: Some(TestPattern::Green),
+        },
+    );
+    assert_eq!(&pin_data(&provider.borrow())[..3], [255, 0, 0]);
+
+    // Replacing the pattern reuses the output; one LED is lit and it moves along
+    request(
+        &mut server,
+        ClientRequest::TestPattern {
+            fixture: strip(4, None),
+            pattern: Some(TestPattern::Chase),
+        },
+    );
+    let lit_led = |data: &[u8]| -> Vec<usize> {
+        data.chunks(3)
+            .enumerate()
+            .filter(|(_, led)| led.iter().any(|&value| value > 0))
+            .map(|(index, _)| index)
+            .collect()
+    };
+    let first = lit_led(&pin_data(&provider.borrow()));
+    assert_eq!(first.len(), 1);
+    server.tick(100, vec![]).unwrap();
+    let second = lit_led(&pin_data(&provider.borrow()));
+    assert_eq!(second.len(), 1);
+    assert_ne!(first, second);
+}
@@ -0,0 +1,108 @@
+extern crate alloc;
+
+mod common;
+use common::{PROJECT_PATH, load_project, new_server, request, server_fs};
+
+use alloc::rc::Rc;
+use core::cell::RefCell;
+use lp_engine::nodes::TextureRuntime;
+use lp_model::nodes::texture::TextureConfig;
+use lp_model::server::{FsRequest, ServerMsgBody};
+use lp_model::{AsLpPathBuf, ClientRequest, LpPathBuf};
+use lp_server::LpServer;
+use lp_shared::ProjectBuilder;
+use lp_shared::fs::LpFsMemory;
+
+/// Width of the texture node as currently configured in the running project
+fn texture_width(server: &LpServer, texture_path: &LpPathBuf) -> u32 {
+    let project = server
+        .project_manager()
+        .get_project(server.project_manager().list_loaded_projects()[0].handle)
+        .unwrap();
+    let runtime = project.runtime();
+    let handle = runtime.handle_for_path(texture_path.as_path()).unwrap();
+    runtime
+        .nodes
+        .get(&handle)
+        .unwrap()
+        .runtime
+        .as_ref()
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TextureRuntime>()
+        .unwrap()
+        .get_config()
+        .unwrap()
+        .width
+}
+
+#[test]
+fn test_undo_redo_node_edit() {
+    // Build a project and copy it into the server filesystem
+    let temp_fs = Rc::new(RefCell::new(LpFsMemory::new()));
+    let mut builder = ProjectBuilder::new(temp_fs.clone());
+    let texture_path = builder.texture().width(8).height(4).add(&mut builder);
+    builder.build();
+
+    let project_prefix = PROJECT_PATH.as_path_buf();
+    let (mut server, _) = new_server(server_fs(&temp_fs));
+    let handle = load_project(&mut server);
+    assert_eq!(texture_width(&server, &texture_path), 8);
+
+    // Edit the texture through the server
+    let node_json_path = project_prefix
+        .join(&texture_path.as_str()[1..])
+        .join("node.json");
+    let original = server
+        .base_fs()
+        .read_file(node_json_path.as_path())
+        .unwrap();
+    let mut config: TextureConfig = lp_model::json::from_slice(&original).unwrap();
+    config.width = 16;
+    let edited = lp_model::json::to_string(&config).unwrap().into_bytes();
+    request(
+        &mut server,
+        ClientRequest::Filesystem(FsRequest::Write {
+            path: node_json_path.clone(),
+            data: edited.clone(),
+        }),
+    );
+    server.tick(16, vec![]).unwrap();
+    assert_eq!(texture_width(&server, &texture_path), 16);
+
+    // Undo restores the file and the running node picks it up
+    assert!(matches!(
+        request(&mut server, ClientRequest::Undo { handle }),
+        ServerMsgBody::Undo { applied: true }
+    ));
+    server.tick(16, vec![]).unwrap();
+    assert_eq!(
+        server
+            .base_fs()
+            .read_file(node_json_path.as_path())
+            .unwrap(),
+        original
+    );
+    assert_eq!(texture_width(&server, &texture_path), 8);
+
+    // Nothing further to undo
+    assert!(matches!(
+        request(&mut server, ClientRequest::Undo { handle }),
+        ServerMsgBody::Undo { applied: false }
+    ));
+
+    // Redo re-applies the edit
+    assert!(matches!(
+        request(&mut server, ClientRequest::Redo { handle }),
+        ServerMsgBody::Redo { applied: true }
+    ));
+    server.tick(16, vec![]).unwrap();
+    assert_eq!(
+        server
+            .base_fs()
+            .read_file(node_json_path.as_path())
+            .unwrap(),
+        edited
+    );
+    assert_eq!(texture_width(&server, &texture_path), 16);
+}
@@ -31,6 +31,11 @@ pub enum TextureError {
     InvalidFormat(String),
     /// Texture dimensions too large
     DimensionsTooLarge { width: u32, height: u32 },
+    /// Texture dimensions do not match
+    DimensionMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
 }
 
 impl fmt::Display for TextureError {
@@ -42,6 +47,13 @@ impl fmt::Display for TextureError {
             TextureError::DimensionsTooLarge { width, height } => {
                 write!(f, "Texture dimensions too large: {width}x{height}")
             }
+            TextureError::DimensionMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Texture dimensions mismatch: expected {}x{}, got {}x{}",
+                    expected.0, expected.1, actual.0, actual.1
+                )
+            }
         }
     }
 }
@@ -57,6 +69,8 @@ pub enum OutputError {
     InvalidConfig { reason: String },
     /// Data length mismatch
     DataLengthMismatch { expected: u32, actual: usize },
+    /// Transmitting a frame failed, even after a retry
+    TransmitFailed { reason: String },
     /// Other error
     Other { message: String },
 }
@@ -79,6 +93,9 @@ impl fmt::Display for OutputError {
                     "Data length {actual} doesn't match expected byte_count {expected}"
                 )
             }
+            OutputError::TransmitFailed { reason } => {
+                write!(f, "Transmit failed: {reason}")
+            }
             OutputError::Other { message } => {
                 write!(f, "Error: {message}")
             }
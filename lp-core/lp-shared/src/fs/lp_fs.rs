@@ -73,6 +73,30 @@ pub trait LpFs {
     /// Returns an error if the path is "/" (root), would escape the root directory, or the directory doesn't exist.
     fn delete_dir(&self, path: &LpPath) -> Result<(), FsError>;
 
+    /// Rename a file, replacing the destination if it exists
+    ///
+    /// Paths are relative to project root.
+    ///
+    /// Replacing the destination is atomic where the backing store supports it, so readers
+    /// see either the old or the new file, never a mix. Returns an error if `from` doesn't exist.
+    fn rename(&self, from: &LpPath, to: &LpPath) -> Result<(), FsError>;
+
+    /// Write data to a file atomically
+    ///
+    /// Path is relative to project root.
+    ///
+    /// Writes to a temporary file next to `path` and renames it into place, so a write that
+    /// is interrupted (e.g. by power loss) never leaves a partially written file at `path`.
+    fn write_file_atomic(&self, path: &LpPath, data: &[u8]) -> Result<(), FsError> {
+        let temp_path = atomic_temp_path(path);
+        if let Err(e) = self.write_file(temp_path.as_path(), data) {
+            // Don't leave a partial temp file behind
+            let _ = self.delete_file(temp_path.as_path());
+            return Err(e);
+        }
+        self.rename(temp_path.as_path(), path)
+    }
+
     /// Create a new filesystem view rooted at a subdirectory
     ///
     /// Returns a new `LpFs` instance where all paths are relative to the specified subdirectory.
@@ -115,3 +139,8 @@ pub trait LpFs {
     /// Each change is assigned the next version number.
     fn record_changes(&mut self, changes: alloc::vec::Vec<FsChange>);
 }
+
+/// Path of the temporary file used by [`LpFs::write_file_atomic`] for `path`
+pub fn atomic_temp_path(path: &LpPath) -> LpPathBuf {
+    LpPathBuf::from(alloc::format!("{}.tmp", path.as_str()))
+}
@@ -367,6 +367,35 @@ impl LpFs for LpFsMemory {
         Ok(())
     }
 
+    fn rename(&self, from: &LpPath, to: &LpPath) -> Result<(), FsError> {
+        self.validate_path(from)?;
+        self.validate_path(to)?;
+        let from_normalized = from.to_path_buf();
+        let to_normalized = to.to_path_buf();
+        if from_normalized == to_normalized {
+            return Ok(());
+        }
+
+        // Swap the contents in a single step so the destination is never partially written
+        let mut files = self.files.borrow_mut();
+        let data = files
+            .remove(&from_normalized)
+            .ok_or_else(|| FsError::NotFound(from_normalized.as_str().to_string()))?;
+        let existed = files.insert(to_normalized.clone(), data).is_some();
+        drop(files); // Release borrow before recording changes
+
+        // Record changes
+        self.record_change(from_normalized.as_path(), ChangeType::Delete);
+        let change_type = if existed {
+            ChangeType::Modify
+        } else {
+            ChangeType::Create
+        };
+        self.record_change(to_normalized.as_path(), change_type);
+
+        Ok(())
+    }
+
     fn chroot(
         &self,
         subdir: &LpPath,
@@ -504,6 +533,40 @@ mod tests {
         assert!(entries.contains(&LpPathBuf::from("/src/nested/deep/file3.txt")));
     }
 
+    #[test]
+    fn test_rename_replaces_destination() {
+        let mut fs = LpFsMemory::new();
+        fs.write_file_mut("/a.txt".as_path(), b"new").unwrap();
+        fs.write_file_mut("/b.txt".as_path(), b"old").unwrap();
+
+        fs.rename("/a.txt".as_path(), "/b.txt".as_path()).unwrap();
+        assert!(!fs.file_exists("/a.txt".as_path()).unwrap());
+        assert_eq!(fs.read_file("/b.txt".as_path()).unwrap(), b"new");
+
+        assert!(matches!(
+            fs.rename("/missing.txt".as_path(), "/b.txt".as_path()),
+            Err(FsError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_file_atomic() {
+        let fs = LpFsMemory::new();
+        let path = "/project.json".as_path();
+        fs.write_file(path, b"{\"name\":\"good\"}").unwrap();
+
+        // Power lost partway through a save: only the temp file was (partially) written
+        let temp_path = crate::fs::atomic_temp_path(path);
+        fs.write_file(temp_path.as_path(), b"{\"na").unwrap();
+        assert_eq!(fs.read_file(path).unwrap(), b"{\"name\":\"good\"}");
+
+        // The next save completes and replaces the file, cleaning up the temp file
+        fs.write_file_atomic(path, b"{\"name\":\"better\"}")
+            .unwrap();
+        assert_eq!(fs.read_file(path).unwrap(), b"{\"name\":\"better\"}");
+        assert!(!fs.file_exists(temp_path.as_path()).unwrap());
+    }
+
     #[test]
     fn test_delete_file() {
         let mut fs = LpFsMemory::new();
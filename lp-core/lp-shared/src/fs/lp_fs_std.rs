@@ -360,6 +360,31 @@ impl LpFs for LpFsStd {
         })
     }
 
+    fn rename(&self, from: &LpPath, to: &LpPath) -> Result<(), FsError> {
+        let from_path = self.resolve_and_validate(from)?;
+        let to_path = self.get_path(to)?;
+        if from_path.is_dir() {
+            return Err(FsError::Filesystem(format!(
+                "Path {:?} is a directory, only files can be renamed",
+                from.as_str()
+            )));
+        }
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = to_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                return Err(FsError::Filesystem(format!(
+                    "Failed to create directory {parent:?}: {e}"
+                )));
+            }
+        }
+        // fs::rename replaces the destination atomically on the same filesystem
+        fs::rename(&from_path, &to_path).map_err(|e| {
+            FsError::Filesystem(format!(
+                "Failed to rename {from_path:?} to {to_path:?}: {e}"
+            ))
+        })
+    }
+
     fn chroot(
         &self,
         subdir: &LpPath,
@@ -466,6 +491,23 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_write_file_atomic() {
+        let temp_dir = TempDir::new().unwrap();
+        let lp_fs = LpFsStd::new(temp_dir.path().to_path_buf());
+
+        lp_fs.write_file("/project.json".as_path(), b"old").unwrap();
+        lp_fs
+            .write_file_atomic("/project.json".as_path(), b"new")
+            .unwrap();
+
+        assert_eq!(
+            fs::read(temp_dir.path().join("project.json")).unwrap(),
+            b"new"
+        );
+        assert!(!temp_dir.path().join("project.json.tmp").exists());
+    }
+
     #[test]
     fn test_path_validation_within_root() {
         let temp_dir = TempDir::new().unwrap();
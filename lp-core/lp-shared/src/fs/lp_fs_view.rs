@@ -203,6 +203,18 @@ impl LpFs for LpFsView {
         self.parent.borrow().delete_dir(parent_lp_path)
     }
 
+    fn rename(&self, from: &LpPath, to: &LpPath) -> Result<(), FsError> {
+        // Validate input is absolute (contract: LpFs only accepts absolute paths)
+        self.validate_path(from)?;
+        self.validate_path(to)?;
+        let parent_from = self.parent_path(from.to_path_buf().as_str());
+        let parent_to = self.parent_path(to.to_path_buf().as_str());
+        self.parent.borrow().rename(
+            LpPath::new(parent_from.as_str()),
+            LpPath::new(parent_to.as_str()),
+        )
+    }
+
     fn chroot(&self, subdir: &LpPath) -> Result<Rc<RefCell<dyn LpFs>>, FsError> {
         // Validate input is absolute (contract: LpFs only accepts absolute paths)
         self.validate_path(subdir)?;
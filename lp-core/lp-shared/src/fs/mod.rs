@@ -8,7 +8,7 @@ pub mod lp_fs_std;
 pub mod lp_fs_view;
 
 pub use fs_event::{ChangeType, FsChange, FsVersion};
-pub use lp_fs::LpFs;
+pub use lp_fs::{LpFs, atomic_temp_path};
 pub use lp_fs_mem::LpFsMemory;
 
 #[cfg(feature = "std")]
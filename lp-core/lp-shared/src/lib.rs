@@ -26,4 +26,5 @@ pub use error::{FsError, OutputError, TextureError};
 // Re-export TransportError from lp-model for convenience
 pub use lp_model::TransportError;
 pub use project::ProjectBuilder;
+pub use util::rgba::Rgba;
 pub use util::texture::Texture;
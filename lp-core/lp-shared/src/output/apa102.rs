@@ -0,0 +1,387 @@
+//! APA102/SK9822 clock+data LED protocol
+//!
+//! Unlike WS2811, these LEDs are driven over an SPI-style clock and data pair.
+//! A transfer is framed as:
+//! - Start frame: 32 zero bits
+//! - One 32-bit frame per LED: `0b111` + 5-bit global brightness, then blue, green, red
+//! - End frame: 32 zero bits (the SK9822 latch frame), then `n / 2` more zero bits
+//!   so the data propagates through every LED of an APA102 strip
+//!
+//! The framing is independent of the hardware so it can be tested on the host;
+//! firmware supplies an [`SpiSink`] for its SPI peripheral and wraps its provider
+//! in an [`Apa102OutputProvider`].
+
+use crate::error::OutputError;
+use crate::output::provider::{
+    Brightness, FrameStamp, OutputChannelHandle, OutputFormat, OutputProvider,
+};
+use alloc::format;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+/// Maximum value of the 5-bit global brightness field
+pub const MAX_BRIGHTNESS: u8 = 31;
+
+/// Handles for APA102 channels start here, clear of the inner provider's handles
+const HANDLE_BASE: i32 = 0x5000_0000;
+
+/// Byte sink for a clock+data (SPI) bus
+pub trait SpiSink {
+    type Error: core::fmt::Debug;
+
+    /// Write bytes to the bus, most significant bit first
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Encode RGB channel data into a complete APA102 transfer
+///
+/// # Arguments
+/// * `rgb` - RGB bytes, 3 per LED (a trailing partial LED is ignored)
+/// * `brightness` - 5-bit global brightness, clamped to [`MAX_BRIGHTNESS`]
+/// * `out` - Buffer to write the framed bytes into (cleared first)
+pub fn encode_frame(rgb: &[u8], brightness: u8, out: &mut Vec<u8>) {
+    let led_count = rgb.len() / 3;
+    let header = 0b1110_0000 | brightness.min(MAX_BRIGHTNESS);
+
+    out.clear();
+    out.reserve(4 + led_count * 4 + end_frame_len(led_count));
+
+    // Start frame
+    out.extend_from_slice(&[0; 4]);
+
+    // LED frames, sent as blue, green, red
+    for led in rgb.chunks_exact(3) {
+        out.extend_from_slice(&[header, led[2], led[1], led[0]]);
+    }
+
+    // End frame
+    out.resize(out.len() + end_frame_len(led_count), 0);
+}
+
+/// Length of the end frame in bytes for a strip of `led_count` LEDs
+fn end_frame_len(led_count: usize) -> usize {
+    // 32 bits for SK9822, plus one clock per two LEDs for APA102
+    4 + led_count.div_ceil(16)
+}
+
+/// APA102/SK9822 driver writing framed data to an [`SpiSink`]
+pub struct Apa102Driver<S: SpiSink> {
+    sink: S,
+    brightness: u8,
+    buffer: Vec<u8>,
+}
+
+impl<S: SpiSink> Apa102Driver<S> {
+    /// Create a new driver with the given 5-bit global brightness
+    pub fn new(sink: S, brightness: u8) -> Self {
+        Self {
+            sink,
+            brightness: brightness.min(MAX_BRIGHTNESS),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Set the 5-bit global brightness, clamped to [`MAX_BRIGHTNESS`]
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness.min(MAX_BRIGHTNESS);
+    }
+
+    /// Get the 5-bit global brightness
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// Frame RGB channel data and write it to the sink
+    pub fn write_rgb(&mut self, rgb: &[u8]) -> Result<(), S::Error> {
+        encode_frame(rgb, self.brightness, &mut self.buffer);
+        self.sink.write(&self.buffer)
+    }
+
+    /// Get the underlying sink
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+}
+
+/// Output provider that drives an [`OutputFormat::Apa102`] strip over one SPI bus
+///
+/// The bus is wired to fixed data and clock pins, so a single strip can be open at a
+/// time. Channels in any other format, or APA102 strips on other pins, are passed
+/// through to the inner provider.
+pub struct Apa102OutputProvider<P: OutputProvider, S: SpiSink> {
+    inner: P,
+    driver: RefCell<Apa102Driver<S>>,
+    data_pin: u32,
+    clock_pin: u32,
+    /// Handle and byte count of the open strip
+    open: RefCell<Option<(OutputChannelHandle, u32)>>,
+    next_handle: RefCell<i32>,
+    brightness: RefCell<Brightness>,
+    /// Scratch buffer for brightness-scaled data
+    scaled: RefCell<Vec<u8>>,
+}
+
+impl<P: OutputProvider, S: SpiSink> Apa102OutputProvider<P, S> {
+    /// Create a provider that drives the SPI bus on `data_pin`/`clock_pin` through `sink`
+    /// and delegates the rest to `inner`
+    pub fn new(inner: P, sink: S, data_pin: u32, clock_pin: u32) -> Self {
+        Self {
+            inner,
+            driver: RefCell::new(Apa102Driver::new(sink, MAX_BRIGHTNESS)),
+            data_pin,
+            clock_pin,
+            open: RefCell::new(None),
+            next_handle: RefCell::new(HANDLE_BASE),
+            brightness: RefCell::new(Brightness::FULL),
+            scaled: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Get the inner provider
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Get the driver (for testing)
+    pub fn driver(&self) -> core::cell::Ref<'_, Apa102Driver<S>> {
+        self.driver.borrow()
+    }
+
+    fn is_open(&self, handle: OutputChannelHandle) -> bool {
+        matches!(*self.open.borrow(), Some((open, _)) if open == handle)
+    }
+}
+
+impl<P: OutputProvider, S: SpiSink> OutputProvider for Apa102OutputProvider<P, S> {
+    fn open(
+        &self,
+        pin: u32,
+        byte_count: u32,
+        format: OutputFormat,
+    ) -> Result<OutputChannelHandle, OutputError> {
+        let brightness = match format {
+            OutputFormat::Apa102 {
+                clock_pin,
+                brightness,
+            } if pin == self.data_pin && clock_pin == self.clock_pin => brightness,
+            _ => return self.inner.open(pin, byte_count, format),
+        };
+
+        if byte_count == 0 {
+            return Err(OutputError::InvalidConfig {
+                reason: "byte_count must be greater than 0".into(),
+            });
+        }
+
+        let mut open = self.open.borrow_mut();
+        if open.is_some() {
+            return Err(OutputError::PinAlreadyOpen { pin });
+        }
+
+        let mut next_handle = self.next_handle.borrow_mut();
+        let handle = OutputChannelHandle::new(*next_handle);
+        *next_handle += 1;
+
+        self.driver.borrow_mut().set_brightness(brightness);
+        *open = Some((handle, byte_count));
+        Ok(handle)
+    }
+
+    fn write(&self, handle: OutputChannelHandle, data: &[u8]) -> Result<(), OutputError> {
+        let byte_count = match *self.open.borrow() {
+            Some((open, byte_count)) if open == handle => byte_count,
+            _ => return self.inner.write(handle, data),
+        };
+        if data.len() != byte_count as usize {
+            return Err(OutputError::DataLengthMismatch {
+                expected: byte_count,
+                actual: data.len(),
+            });
+        }
+
+        let brightness = *self.brightness.borrow();
+        let mut scaled = self.scaled.borrow_mut();
+        let data = if brightness.is_full() {
+            data
+        } else {
+            scaled.clear();
+            scaled.extend_from_slice(data);
+            brightness.apply(&mut scaled);
+            &scaled[..]
+        };
+
+        self.driver
+            .borrow_mut()
+            .write_rgb(data)
+            .map_err(|e| OutputError::Other {
+                message: format!("APA102 SPI write failed: {e:?}"),
+            })
+    }
+
+    fn close(&self, handle: OutputChannelHandle) -> Result<(), OutputError> {
+        if self.is_open(handle) {
+            *self.open.borrow_mut() = None;
+            Ok(())
+        } else {
+            self.inner.close(handle)
+        }
+    }
+
+    fn set_brightness(&self, brightness: f32) {
+        *self.brightness.borrow_mut() = Brightness::from_f32(brightness);
+        self.inner.set_brightness(brightness);
+    }
+
+    fn max_byte_count(&self, pin: u32, format: OutputFormat) -> Option<u32> {
+        match format {
+            // SPI has no frame-size limit beyond memory
+            OutputFormat::Apa102 { .. } => None,
+            _ => self.inner.max_byte_count(pin, format),
+        }
+    }
+
+    fn begin_frame(&self, stamp: FrameStamp) {
+        self.inner.begin_frame(stamp);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::MemoryOutputProvider;
+    use alloc::vec;
+
+    /// Fake SPI bus that records everything written to it
+    #[derive(Default)]
+    struct FakeSpi {
+        written: Vec<u8>,
+    }
+
+    impl SpiSink for FakeSpi {
+        type Error = ();
+
+        fn write(&mut self, bytes: &[u8]) -> Result<(), ()> {
+            self.written.extend_from_slice(bytes);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_apa102_frame_layout() {
+        let mut driver = Apa102Driver::new(FakeSpi::default(), 16);
+        // Red, green, blue
+        driver
+            .write_rgb(&[255, 0, 0, 0, 255, 0, 0, 0, 255])
+            .unwrap();
+
+        let expected = vec![
+            // Start frame
+            0x00, 0x00, 0x00, 0x00, //
+            // LED frames: 0b111 + brightness, B, G, R
+            0xF0, 0x00, 0x00, 0xFF, //
+            0xF0, 0x00, 0xFF, 0x00, //
+            0xF0, 0xFF, 0x00, 0x00, //
+            // End frame: 32 bits + one byte for up to 16 LEDs
+            0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(driver.sink().written, expected);
+    }
+
+    #[test]
+    fn test_apa102_brightness_clamped() {
+        let mut out = Vec::new();
+        encode_frame(&[1, 2, 3], 200, &mut out);
+        assert_eq!(&out[4..8], &[0xFF, 3, 2, 1]);
+
+        encode_frame(&[1, 2, 3], 0, &mut out);
+        assert_eq!(out[4], 0xE0);
+    }
+
+    #[test]
+    fn test_apa102_end_frame_grows_with_strip() {
+        let mut out = Vec::new();
+        let rgb = [0u8; 3 * 40];
+        encode_frame(&rgb, MAX_BRIGHTNESS, &mut out);
+        // 40 LEDs need 20 extra clocks: 3 bytes after the 32-bit end frame
+        assert_eq!(out.len(), 4 + 40 * 4 + 4 + 3);
+    }
+
+    #[test]
+    fn test_apa102_ignores_partial_led() {
+        let mut out = Vec::new();
+        encode_frame(&[9, 8, 7, 6], MAX_BRIGHTNESS, &mut out);
+        assert_eq!(out.len(), 4 + 4 + 5);
+        assert_eq!(&out[4..8], &[0xFF, 7, 8, 9]);
+    }
+
+    fn apa102(brightness: u8) -> OutputFormat {
+        OutputFormat::Apa102 {
+            clock_pin: 19,
+            brightness,
+        }
+    }
+
+    #[test]
+    fn test_provider_writes_strip_to_spi() {
+        let provider =
+            Apa102OutputProvider::new(MemoryOutputProvider::new(), FakeSpi::default(), 18, 19);
+        let handle = provider.open(18, 3, apa102(8)).unwrap();
+        provider.write(handle, &[10, 20, 30]).unwrap();
+
+        let driver = provider.driver();
+        assert_eq!(driver.brightness(), 8);
+        assert_eq!(&driver.sink().written[4..8], &[0xE8, 30, 20, 10]);
+        assert!(!provider.inner().is_pin_open(18));
+    }
+
+    #[test]
+    fn test_provider_applies_master_brightness() {
+        let provider =
+            Apa102OutputProvider::new(MemoryOutputProvider::new(), FakeSpi::default(), 18, 19);
+        let handle = provider.open(18, 3, apa102(MAX_BRIGHTNESS)).unwrap();
+        provider.set_brightness(0.0);
+        provider.write(handle, &[10, 20, 30]).unwrap();
+
+        assert_eq!(&provider.driver().sink().written[4..8], &[0xFF, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_provider_rejects_second_strip() {
+        let provider =
+            Apa102OutputProvider::new(MemoryOutputProvider::new(), FakeSpi::default(), 18, 19);
+        let handle = provider.open(18, 3, apa102(MAX_BRIGHTNESS)).unwrap();
+        assert!(matches!(
+            provider.open(18, 3, apa102(MAX_BRIGHTNESS)),
+            Err(OutputError::PinAlreadyOpen { pin: 18 })
+        ));
+        assert!(matches!(
+            provider.write(handle, &[1, 2]),
+            Err(OutputError::DataLengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+
+        provider.close(handle).unwrap();
+        provider.open(18, 3, apa102(MAX_BRIGHTNESS)).unwrap();
+    }
+
+    #[test]
+    fn test_provider_delegates_other_formats() {
+        let provider =
+            Apa102OutputProvider::new(MemoryOutputProvider::new(), FakeSpi::default(), 18, 19);
+        let handle = provider.open(5, 3, OutputFormat::Ws2811).unwrap();
+        provider.write(handle, &[1, 2, 3]).unwrap();
+
+        assert!(provider.driver().sink().written.is_empty());
+        assert_eq!(provider.inner().get_data(handle), Some(vec![1, 2, 3]));
+        provider.close(handle).unwrap();
+        assert!(!provider.inner().is_pin_open(5));
+
+        // An APA102 strip off the bus pins is the inner provider's to handle
+        let handle = provider.open(4, 3, apa102(MAX_BRIGHTNESS)).unwrap();
+        provider.write(handle, &[1, 2, 3]).unwrap();
+        assert!(provider.driver().sink().written.is_empty());
+        assert!(provider.inner().is_pin_open(4));
+    }
+}
@@ -0,0 +1,418 @@
+//! DMX over Art-Net output
+//!
+//! Output channel data is laid out as consecutive DMX universes of
+//! [`DMX_UNIVERSE_SIZE`] channels. Each universe is sent as one ArtDmx packet, so a
+//! frame longer than 512 channels is split across universes.
+//!
+//! The UDP transport is abstracted behind [`UdpSink`] so packets can be checked on
+//! the host without a network; with the `std` feature, [`StdUdpSink`] sends them
+//! over a real socket.
+
+use crate::error::OutputError;
+use crate::output::provider::{Brightness, OutputChannelHandle, OutputFormat, OutputProvider};
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::net::SocketAddrV4;
+use lp_model::nodes::output::DMX_UNIVERSE_SIZE;
+
+/// Art-Net packet ID, including the trailing NUL
+pub const ART_NET_ID: &[u8; 8] = b"Art-Net\0";
+
+/// ArtDmx opcode
+pub const OP_DMX: u16 = 0x5000;
+
+/// Art-Net protocol version
+pub const PROTOCOL_VERSION: u16 = 14;
+
+/// Size of the ArtDmx header before the channel data
+pub const ART_DMX_HEADER_LEN: usize = 18;
+
+/// Highest 15-bit Port-Address
+const MAX_PORT_ADDRESS: u32 = 0x7FFF;
+
+/// Handles for Art-Net channels start here, clear of the inner provider's handles
+const HANDLE_BASE: i32 = 0x4000_0000;
+
+/// Sink for UDP datagrams
+pub trait UdpSink {
+    type Error: core::fmt::Debug;
+
+    /// Send one datagram to `target`
+    fn send_to(&mut self, packet: &[u8], target: SocketAddrV4) -> Result<(), Self::Error>;
+}
+
+/// Encode an ArtDmx packet
+///
+/// # Arguments
+/// * `universe` - 15-bit Port-Address (net, sub-net and universe)
+/// * `sequence` - Sequence number (1-255, 0 disables sequencing)
+/// * `data` - Up to 512 DMX channel values
+/// * `out` - Buffer to write the packet into (cleared first)
+///
+/// DMX data length must be even, so odd-length data is padded with a zero channel.
+pub fn encode_art_dmx(universe: u16, sequence: u8, data: &[u8], out: &mut Vec<u8>) {
+    let data = &data[..data.len().min(DMX_UNIVERSE_SIZE as usize)];
+    let length = data.len() + data.len() % 2;
+
+    out.clear();
+    out.reserve(ART_DMX_HEADER_LEN + length);
+    out.extend_from_slice(ART_NET_ID);
+    // OpCode is little-endian, everything else big-endian
+    out.extend_from_slice(&OP_DMX.to_le_bytes());
+    out.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+    out.push(sequence);
+    // Physical input port (informational only)
+    out.push(0);
+    // SubUni (low byte) then Net (high 7 bits)
+    out.push((universe & 0xFF) as u8);
+    out.push(((universe >> 8) & 0x7F) as u8);
+    out.extend_from_slice(&(length as u16).to_be_bytes());
+    out.extend_from_slice(data);
+    out.resize(ART_DMX_HEADER_LEN + length, 0);
+}
+
+/// Sends output frames as ArtDmx packets, one per universe
+pub struct ArtNetSender {
+    target: SocketAddrV4,
+    start_universe: u16,
+    sequence: u8,
+    packet: Vec<u8>,
+}
+
+impl ArtNetSender {
+    /// Create a sender for the Art-Net node at `target`
+    ///
+    /// Output universe 0 is sent as Art-Net universe `start_universe`.
+    pub fn new(target: SocketAddrV4, start_universe: u16) -> Self {
+        Self {
+            target,
+            start_universe,
+            sequence: 0,
+            packet: Vec::new(),
+        }
+    }
+
+    /// Send a frame of channel data, splitting it into 512-channel universes
+    ///
+    /// Fails without sending anything if the frame runs past the last Port-Address.
+    pub fn send_frame<S: UdpSink>(&mut self, sink: &mut S, data: &[u8]) -> Result<(), OutputError> {
+        check_port_addresses(self.start_universe, data.len() as u32)?;
+
+        // Sequence runs 1..=255; 0 means "sequencing disabled"
+        self.sequence = if self.sequence == 255 {
+            1
+        } else {
+            self.sequence + 1
+        };
+
+        for (index, universe_data) in data.chunks(DMX_UNIVERSE_SIZE as usize).enumerate() {
+            let universe = self.start_universe + index as u16;
+            encode_art_dmx(universe, self.sequence, universe_data, &mut self.packet);
+            sink.send_to(&self.packet, self.target)
+                .map_err(|e| OutputError::Other {
+                    message: format!("Art-Net send failed: {e:?}"),
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// Check that `byte_count` channels starting at `start_universe` fit in the 15-bit
+/// Port-Address space
+fn check_port_addresses(start_universe: u16, byte_count: u32) -> Result<(), OutputError> {
+    let universes = byte_count.div_ceil(DMX_UNIVERSE_SIZE).max(1);
+    let last = start_universe as u32 + universes - 1;
+    if last > MAX_PORT_ADDRESS {
+        return Err(OutputError::InvalidConfig {
+            reason: format!(
+                "Art-Net universes {start_universe}-{last} run past the last Port-Address \
+                 ({MAX_PORT_ADDRESS})"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Output provider that sends [`OutputFormat::ArtNet`] channels over UDP
+///
+/// Channels in any other format are passed through to the inner provider.
+pub struct ArtNetOutputProvider<P: OutputProvider, S: UdpSink> {
+    inner: P,
+    sink: RefCell<S>,
+    senders: RefCell<BTreeMap<OutputChannelHandle, ArtNetSender>>,
+    next_handle: RefCell<i32>,
+    brightness: RefCell<Brightness>,
+    /// Scratch buffer for brightness-scaled data
+    scaled: RefCell<Vec<u8>>,
+}
+
+impl<P: OutputProvider, S: UdpSink> ArtNetOutputProvider<P, S> {
+    /// Create a provider that sends Art-Net through `sink` and delegates the rest to `inner`
+    pub fn new(inner: P, sink: S) -> Self {
+        Self {
+            inner,
+            sink: RefCell::new(sink),
+            senders: RefCell::new(BTreeMap::new()),
+            next_handle: RefCell::new(HANDLE_BASE),
+            brightness: RefCell::new(Brightness::FULL),
+            scaled: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Get the inner provider
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Get the UDP sink (for testing)
+    pub fn sink(&self) -> core::cell::Ref<'_, S> {
+        self.sink.borrow()
+    }
+}
+
+impl<P: OutputProvider, S: UdpSink> OutputProvider for ArtNetOutputProvider<P, S> {
+    fn open(
+        &self,
+        pin: u32,
+        byte_count: u32,
+        format: OutputFormat,
+    ) -> Result<OutputChannelHandle, OutputError> {
+        let OutputFormat::ArtNet {
+            target,
+            start_universe,
+        } = format
+        else {
+            return self.inner.open(pin, byte_count, format);
+        };
+        check_port_addresses(start_universe, byte_count)?;
+
+        let mut next_handle = self.next_handle.borrow_mut();
+        let handle = OutputChannelHandle::new(*next_handle);
+        *next_handle += 1;
+
+        self.senders
+            .borrow_mut()
+            .insert(handle, ArtNetSender::new(target, start_universe));
+        Ok(handle)
+    }
+
+    fn write(&self, handle: OutputChannelHandle, data: &[u8]) -> Result<(), OutputError> {
+        let mut senders = self.senders.borrow_mut();
+        let Some(sender) = senders.get_mut(&handle) else {
+            return self.inner.write(handle, data);
+        };
+
+        let brightness = *self.brightness.borrow();
+        let mut scaled = self.scaled.borrow_mut();
+        let data = if brightness.is_full() {
+            data
+        } else {
+            scaled.clear();
+            scaled.extend_from_slice(data);
+            brightness.apply(&mut scaled);
+            &scaled[..]
+        };
+
+        sender.send_frame(&mut *self.sink.borrow_mut(), data)
+    }
+
+    fn close(&self, handle: OutputChannelHandle) -> Result<(), OutputError> {
+        if self.senders.borrow_mut().remove(&handle).is_some() {
+            Ok(())
+        } else {
+            self.inner.close(handle)
+        }
+    }
+
+    fn set_brightness(&self, brightness: f32) {
+        *self.brightness.borrow_mut() = Brightness::from_f32(brightness);
+        self.inner.set_brightness(brightness);
+    }
+
+    fn max_byte_count(&self, pin: u32, format: OutputFormat) -> Option<u32> {
+        match format {
+            // Universes are sent one packet each, so only the port address space limits them
+            OutputFormat::ArtNet { .. } => None,
+            _ => self.inner.max_byte_count(pin, format),
+        }
+    }
+}
+
+/// [`UdpSink`] backed by a std UDP socket, bound on first send
+#[cfg(feature = "std")]
+#[derive(Default)]
+pub struct StdUdpSink {
+    socket: Option<std::net::UdpSocket>,
+}
+
+#[cfg(feature = "std")]
+impl StdUdpSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl UdpSink for StdUdpSink {
+    type Error = std::io::Error;
+
+    fn send_to(&mut self, packet: &[u8], target: SocketAddrV4) -> Result<(), Self::Error> {
+        let socket = match &mut self.socket {
+            Some(socket) => socket,
+            socket => {
+                let bound = std::net::UdpSocket::bind("0.0.0.0:0")?;
+                bound.set_broadcast(true)?;
+                socket.insert(bound)
+            }
+        };
+        socket.send_to(packet, target)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::MemoryOutputProvider;
+    use alloc::vec;
+    use core::net::Ipv4Addr;
+
+    /// Fake UDP transport that records every datagram
+    #[derive(Default)]
+    struct FakeUdp {
+        sent: Vec<(SocketAddrV4, Vec<u8>)>,
+    }
+
+    impl UdpSink for FakeUdp {
+        type Error = ();
+
+        fn send_to(&mut self, packet: &[u8], target: SocketAddrV4) -> Result<(), ()> {
+            self.sent.push((target, packet.to_vec()));
+            Ok(())
+        }
+    }
+
+    fn target() -> SocketAddrV4 {
+        SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 5), 6454)
+    }
+
+    #[test]
+    fn test_art_dmx_header() {
+        let mut packet = Vec::new();
+        encode_art_dmx(0x0123, 7, &[10, 20, 30, 40], &mut packet);
+
+        assert_eq!(&packet[0..8], b"Art-Net\0");
+        assert_eq!(&packet[8..10], &[0x00, 0x50]); // OpDmx, little-endian
+        assert_eq!(&packet[10..12], &[0, 14]); // Protocol version 14
+        assert_eq!(packet[12], 7); // Sequence
+        assert_eq!(packet[13], 0); // Physical
+        assert_eq!(packet[14], 0x23); // SubUni
+        assert_eq!(packet[15], 0x01); // Net
+        assert_eq!(&packet[16..18], &[0, 4]); // Length, big-endian
+        assert_eq!(&packet[18..], &[10, 20, 30, 40]);
+    }
+
+    #[test]
+    fn test_art_dmx_pads_odd_length() {
+        let mut packet = Vec::new();
+        encode_art_dmx(0, 1, &[1, 2, 3], &mut packet);
+        assert_eq!(&packet[16..18], &[0, 4]);
+        assert_eq!(&packet[18..], &[1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_provider_splits_universes() {
+        let provider = ArtNetOutputProvider::new(MemoryOutputProvider::new(), FakeUdp::default());
+        let handle = provider
+            .open(
+                0,
+                600,
+                OutputFormat::ArtNet {
+                    target: target(),
+                    start_universe: 2,
+                },
+            )
+            .unwrap();
+
+        let data: Vec<u8> = (0..600).map(|i| (i % 251) as u8).collect();
+        provider.write(handle, &data).unwrap();
+
+        let sink = provider.sink();
+        assert_eq!(sink.sent.len(), 2);
+
+        let (addr, first) = &sink.sent[0];
+        assert_eq!(*addr, target());
+        assert_eq!(first[12], 1); // First frame has sequence 1
+        assert_eq!(&first[14..16], &[2, 0]); // Universe 2
+        assert_eq!(&first[16..18], &512u16.to_be_bytes());
+        assert_eq!(&first[18..], &data[..512]);
+
+        let (_, second) = &sink.sent[1];
+        assert_eq!(&second[14..16], &[3, 0]); // Universe 3
+        assert_eq!(&second[16..18], &88u16.to_be_bytes());
+        assert_eq!(&second[18..], &data[512..]);
+    }
+
+    #[test]
+    fn test_provider_sequence_increments_per_frame() {
+        let provider = ArtNetOutputProvider::new(MemoryOutputProvider::new(), FakeUdp::default());
+        let handle = provider
+            .open(
+                0,
+                2,
+                OutputFormat::ArtNet {
+                    target: target(),
+                    start_universe: 0,
+                },
+            )
+            .unwrap();
+
+        provider.write(handle, &[1, 2]).unwrap();
+        provider.write(handle, &[3, 4]).unwrap();
+
+        let sink = provider.sink();
+        assert_eq!(sink.sent[0].1[12], 1);
+        assert_eq!(sink.sent[1].1[12], 2);
+    }
+
+    #[test]
+    fn test_provider_rejects_universes_past_last_port_address() {
+        let provider = ArtNetOutputProvider::new(MemoryOutputProvider::new(), FakeUdp::default());
+        let format = OutputFormat::ArtNet {
+            target: target(),
+            start_universe: 0x7FFF,
+        };
+
+        // One universe at the last Port-Address fits, two don't
+        assert!(provider.open(0, 512, format).is_ok());
+        assert!(matches!(
+            provider.open(0, 513, format),
+            Err(OutputError::InvalidConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sender_rejects_frame_past_last_port_address() {
+        let mut sink = FakeUdp::default();
+        let mut sender = ArtNetSender::new(target(), 0x7FFF);
+        assert!(matches!(
+            sender.send_frame(&mut sink, &[0; 600]),
+            Err(OutputError::InvalidConfig { .. })
+        ));
+        assert!(sink.sent.is_empty());
+    }
+
+    #[test]
+    fn test_provider_delegates_other_formats() {
+        let provider = ArtNetOutputProvider::new(MemoryOutputProvider::new(), FakeUdp::default());
+        let handle = provider.open(18, 3, OutputFormat::Ws2811).unwrap();
+        provider.write(handle, &[1, 2, 3]).unwrap();
+
+        assert!(provider.sink().sent.is_empty());
+        assert_eq!(provider.inner().get_data(handle), Some(vec![1, 2, 3]));
+        provider.close(handle).unwrap();
+        assert!(!provider.inner().is_pin_open(18));
+    }
+}
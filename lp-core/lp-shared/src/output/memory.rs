@@ -1,5 +1,7 @@
 use crate::error::OutputError;
-use crate::output::provider::{OutputChannelHandle, OutputFormat, OutputProvider};
+use crate::output::provider::{
+    Brightness, FrameStamp, OutputChannelHandle, OutputFormat, OutputProvider,
+};
 use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::format;
 use alloc::vec;
@@ -13,6 +15,8 @@ struct ChannelState {
     #[allow(dead_code, reason = "Stored for future protocol-specific handling")]
     format: OutputFormat,
     data: Vec<u8>,
+    /// Frame the data was last written for
+    frame: Option<FrameStamp>,
 }
 
 /// Internal state for memory provider (wrapped in RefCell for interior mutability)
@@ -20,6 +24,11 @@ struct MemoryOutputProviderState {
     channels: BTreeMap<OutputChannelHandle, ChannelState>,
     next_handle: i32,
     open_pins: BTreeSet<u32>,
+    brightness: Brightness,
+    /// Channel capacity in bytes (None for unlimited)
+    max_byte_count: Option<u32>,
+    /// Frame begun by the last `begin_frame`
+    frame: Option<FrameStamp>,
 }
 
 /// In-memory output provider for testing
@@ -38,10 +47,21 @@ impl MemoryOutputProvider {
                 channels: BTreeMap::new(),
                 next_handle: 0,
                 open_pins: BTreeSet::new(),
+                brightness: Brightness::FULL,
+                max_byte_count: None,
+                frame: None,
             }),
         }
     }
 
+    /// Create a provider whose channels hold at most `max_byte_count` bytes, like a
+    /// hardware driver with a fixed LED buffer
+    pub fn with_max_byte_count(max_byte_count: u32) -> Self {
+        let provider = Self::new();
+        provider.state.borrow_mut().max_byte_count = Some(max_byte_count);
+        provider
+    }
+
     /// Get the data written to a channel (for testing)
     pub fn get_data(&self, handle: OutputChannelHandle) -> Option<Vec<u8>> {
         self.state
@@ -51,6 +71,17 @@ impl MemoryOutputProvider {
             .map(|state| state.data.clone())
     }
 
+    /// Get the frame a channel's data was last written for (for testing)
+    ///
+    /// `None` if the channel was never written inside a `begin_frame`.
+    pub fn get_frame(&self, handle: OutputChannelHandle) -> Option<FrameStamp> {
+        self.state
+            .borrow()
+            .channels
+            .get(&handle)
+            .and_then(|state| state.frame)
+    }
+
     /// Get the number of open channels
     pub fn open_channel_count(&self) -> usize {
         self.state.borrow().channels.len()
@@ -72,6 +103,11 @@ impl MemoryOutputProvider {
         None
     }
 
+    /// Get the current master brightness
+    pub fn brightness(&self) -> Brightness {
+        self.state.borrow().brightness
+    }
+
     /// Get all open handles (for testing)
     pub fn get_all_handles(&self) -> Vec<OutputChannelHandle> {
         self.state.borrow().channels.keys().copied().collect()
@@ -98,6 +134,13 @@ impl OutputProvider for MemoryOutputProvider {
                 reason: format!("byte_count must be > 0, got {byte_count}"),
             });
         }
+        if let Some(max) = state.max_byte_count
+            && byte_count > max
+        {
+            return Err(OutputError::InvalidConfig {
+                reason: format!("byte_count {byte_count} exceeds channel capacity of {max}"),
+            });
+        }
 
         // Create handle
         let handle = OutputChannelHandle::new(state.next_handle);
@@ -109,6 +152,7 @@ impl OutputProvider for MemoryOutputProvider {
             byte_count,
             format,
             data: vec![0u8; byte_count as usize],
+            frame: None,
         };
 
         // Store state
@@ -120,6 +164,8 @@ impl OutputProvider for MemoryOutputProvider {
 
     fn write(&self, handle: OutputChannelHandle, data: &[u8]) -> Result<(), OutputError> {
         let mut state = self.state.borrow_mut();
+        let brightness = state.brightness;
+        let frame = state.frame;
 
         // Check if handle exists and get mutable reference
         let channel_state =
@@ -138,8 +184,10 @@ impl OutputProvider for MemoryOutputProvider {
             });
         }
 
-        // Store data
+        // Store data, scaled by master brightness
         channel_state.data.copy_from_slice(data);
+        brightness.apply(&mut channel_state.data);
+        channel_state.frame = frame;
 
         Ok(())
     }
@@ -164,15 +212,84 @@ impl OutputProvider for MemoryOutputProvider {
 
         Ok(())
     }
+
+    fn set_brightness(&self, brightness: f32) {
+        self.state.borrow_mut().brightness = Brightness::from_f32(brightness);
+    }
+
+    fn max_byte_count(&self, _pin: u32, _format: OutputFormat) -> Option<u32> {
+        self.state.borrow().max_byte_count
+    }
+
+    fn begin_frame(&self, stamp: FrameStamp) {
+        self.state.borrow_mut().frame = Some(stamp);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use lp_model::FrameId;
 
     #[test]
     fn test_memory_provider_creation() {
         let provider = MemoryOutputProvider::new();
         assert_eq!(provider.open_channel_count(), 0);
     }
+
+    #[test]
+    fn test_memory_provider_brightness_half() {
+        let provider = MemoryOutputProvider::new();
+        let handle = provider.open(18, 6, OutputFormat::Ws2811).unwrap();
+        let data = [0, 1, 64, 100, 200, 255];
+
+        provider.set_brightness(0.5);
+        provider.write(handle, &data).unwrap();
+
+        let written = provider.get_data(handle).unwrap();
+        for (out, input) in written.iter().zip(data.iter()) {
+            let expected = *input as i32 / 2;
+            assert!(
+                (*out as i32 - expected).abs() <= 1,
+                "{input} scaled to {out}, expected ~{expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_memory_provider_brightness_zero_still_writes() {
+        let provider = MemoryOutputProvider::new();
+        let handle = provider.open(18, 3, OutputFormat::Ws2811).unwrap();
+        provider.write(handle, &[10, 20, 30]).unwrap();
+
+        provider.set_brightness(0.0);
+        provider.write(handle, &[255, 255, 255]).unwrap();
+        assert_eq!(provider.get_data(handle), Some(vec![0, 0, 0]));
+
+        // Data written at full brightness is unchanged
+        provider.set_brightness(1.0);
+        provider.write(handle, &[255, 128, 1]).unwrap();
+        assert_eq!(provider.get_data(handle), Some(vec![255, 128, 1]));
+    }
+
+    #[test]
+    fn test_memory_provider_stamps_writes_with_frame() {
+        let provider = MemoryOutputProvider::new();
+        let handle = provider.open(18, 3, OutputFormat::Ws2811).unwrap();
+        provider.write(handle, &[1, 2, 3]).unwrap();
+        assert_eq!(provider.get_frame(handle), None);
+
+        let stamp = FrameStamp::new(FrameId::new(42), 672);
+        provider.begin_frame(stamp);
+        provider.write(handle, &[4, 5, 6]).unwrap();
+        assert_eq!(provider.get_frame(handle), Some(stamp));
+    }
+
+    #[test]
+    fn test_brightness_from_f32_clamps() {
+        assert_eq!(Brightness::from_f32(-1.0), Brightness::OFF);
+        assert_eq!(Brightness::from_f32(f32::NAN), Brightness::OFF);
+        assert_eq!(Brightness::from_f32(2.0), Brightness::FULL);
+        assert_eq!(Brightness::from_f32(0.5).scale(200), 100);
+    }
 }
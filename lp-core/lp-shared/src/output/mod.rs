@@ -1,5 +1,9 @@
+pub mod apa102;
+pub mod artnet;
 pub mod memory;
 pub mod provider;
+pub mod transmit;
 
 pub use memory::MemoryOutputProvider;
-pub use provider::{OutputChannelHandle, OutputFormat, OutputProvider};
+pub use provider::{Brightness, FrameStamp, OutputChannelHandle, OutputFormat, OutputProvider};
+pub use transmit::{FrameTransmitter, TransmitGuard};
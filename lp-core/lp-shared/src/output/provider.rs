@@ -1,4 +1,5 @@
 use crate::error::OutputError;
+use lp_model::FrameId;
 
 /// Handle for an opened output channel
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -26,6 +27,100 @@ impl OutputChannelHandle {
 pub enum OutputFormat {
     /// WS2811/WS2812 RGB LED protocol
     Ws2811,
+    /// APA102/SK9822 clock+data LED protocol (see [`crate::output::apa102`])
+    ///
+    /// The channel's pin is the data pin.
+    Apa102 {
+        /// Clock pin
+        clock_pin: u32,
+        /// 5-bit global brightness (0-31)
+        brightness: u8,
+    },
+    /// DMX over Art-Net (see [`crate::output::artnet`])
+    ///
+    /// The channel's pin is unused.
+    ArtNet {
+        /// Art-Net node to send to
+        target: core::net::SocketAddrV4,
+        /// Art-Net universe for the first 512 channels
+        start_universe: u16,
+    },
+}
+
+/// Identifies the frame an output write belongs to
+///
+/// When the server is synced to a host clock (see `ClientRequest::SyncClock`),
+/// `frame` and `time_ms` are on that shared clock, so devices showing the same frame
+/// carry the same stamp. Otherwise they are the engine's own frame id and time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStamp {
+    /// Frame being written
+    pub frame: FrameId,
+    /// Time the frame is meant to be shown, in milliseconds
+    pub time_ms: u64,
+}
+
+impl FrameStamp {
+    /// Create a stamp for `frame`, shown at `time_ms`
+    pub fn new(frame: FrameId, time_ms: u64) -> Self {
+        Self { frame, time_ms }
+    }
+}
+
+/// Master brightness scale in 8.8 fixed point (256 = full brightness)
+///
+/// Applied to every output byte in the final stage before transmit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Brightness(u16);
+
+impl Brightness {
+    /// Full brightness (output unchanged)
+    pub const FULL: Brightness = Brightness(256);
+    /// All channels off
+    pub const OFF: Brightness = Brightness(0);
+
+    /// Create from a 0.0..=1.0 scalar, clamping out-of-range values (NaN is off)
+    pub fn from_f32(brightness: f32) -> Self {
+        if brightness.is_nan() || brightness <= 0.0 {
+            Self::OFF
+        } else if brightness >= 1.0 {
+            Self::FULL
+        } else {
+            Self((brightness * 256.0 + 0.5) as u16)
+        }
+    }
+
+    /// Get the brightness as a 0.0..=1.0 scalar
+    pub fn to_f32(self) -> f32 {
+        self.0 as f32 / 256.0
+    }
+
+    /// Check if this is full brightness (scaling is a no-op)
+    pub fn is_full(self) -> bool {
+        self.0 >= 256
+    }
+
+    /// Scale a single channel value, rounding to nearest
+    #[inline]
+    pub fn scale(self, value: u8) -> u8 {
+        ((value as u32 * self.0 as u32 + 128) >> 8) as u8
+    }
+
+    /// Scale every byte of an output buffer in place
+    pub fn apply(self, data: &mut [u8]) {
+        if self.is_full() {
+            return;
+        }
+        for value in data.iter_mut() {
+            *value = self.scale(*value);
+        }
+    }
+}
+
+impl Default for Brightness {
+    fn default() -> Self {
+        Self::FULL
+    }
 }
 
 /// Trait for output providers (hardware drivers, test implementations, etc.)
@@ -51,6 +146,12 @@ pub trait OutputProvider {
 
     /// Write data to an output channel
     ///
+    /// `data` borrows the output node's channel buffer directly: the engine does not
+    /// copy rendered pixels before handing them over (the one exception is a
+    /// power-limited output, which passes its scaled copy). The borrow only lasts for
+    /// the call, and fixtures rewrite the buffer in place next frame, so a provider
+    /// must either transmit before returning or copy what it needs to keep.
+    ///
     /// # Arguments
     /// * `handle` - Output channel handle from `open()`
     /// * `data` - Data to write (must match `byte_count` from `open()`)
@@ -70,4 +171,29 @@ pub trait OutputProvider {
     /// # Returns
     /// Returns `Ok(())` on success, or `OutputError` if handle is invalid
     fn close(&self, handle: OutputChannelHandle) -> Result<(), OutputError>;
+
+    /// Set the master brightness applied to all channels
+    ///
+    /// # Arguments
+    /// * `brightness` - Scalar in `0.0..=1.0`; out-of-range values are clamped
+    ///
+    /// Every channel's data is scaled on `write()`. A brightness of 0 still
+    /// transmits, with all channels off.
+    fn set_brightness(&self, brightness: f32);
+
+    /// Largest `byte_count` a channel on `pin` with `format` can carry
+    ///
+    /// Returns `None` if the provider has no fixed limit. The engine checks fixture
+    /// LED counts against this before opening, so an oversize configuration fails with
+    /// an error instead of being truncated by the hardware.
+    fn max_byte_count(&self, _pin: u32, _format: OutputFormat) -> Option<u32> {
+        None
+    }
+
+    /// Start a frame: the channel writes that follow belong to `stamp`
+    ///
+    /// Called once per rendered frame, before any of its channels are written.
+    /// Providers that tag or time their transmits (e.g. for devices in sync) keep the
+    /// stamp; the default ignores it.
+    fn begin_frame(&self, _stamp: FrameStamp) {}
 }
@@ -0,0 +1,162 @@
+//! Recovery from failed LED frame transmits
+//!
+//! A clocked-out LED strip latches whatever data reached it, so a transmit that
+//! fails mid-frame leaves the strip half-updated. [`TransmitGuard`] retries a failed
+//! frame once and, if that fails too, re-sends the last frame that went out in full,
+//! so the LEDs show a whole frame (even if an old one) before the error is reported.
+//!
+//! The hardware is abstracted behind [`FrameTransmitter`] so the recovery can be
+//! checked on the host.
+
+use crate::error::OutputError;
+use alloc::format;
+use alloc::vec::Vec;
+
+/// Sends one complete frame of channel data to the LEDs
+pub trait FrameTransmitter {
+    type Error: core::fmt::Debug;
+
+    /// Transmit `frame`, returning once it has been sent (or has failed)
+    fn transmit(&mut self, frame: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Retries failed frames and falls back to the last good one
+#[derive(Debug, Default)]
+pub struct TransmitGuard {
+    /// Last frame that was transmitted without error
+    last_good: Vec<u8>,
+}
+
+impl TransmitGuard {
+    /// Create a guard with no good frame yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transmit `frame`, retrying once if it fails
+    ///
+    /// If the retry fails too, the last good frame is sent again (when there is one)
+    /// and [`OutputError::TransmitFailed`] is returned.
+    pub fn transmit<T: FrameTransmitter>(
+        &mut self,
+        transmitter: &mut T,
+        frame: &[u8],
+    ) -> Result<(), OutputError> {
+        let error = match transmitter.transmit(frame) {
+            Ok(()) => return self.sent(frame),
+            Err(e) => e,
+        };
+        log::warn!("Frame transmit failed, retrying: {error:?}");
+
+        let error = match transmitter.transmit(frame) {
+            Ok(()) => return self.sent(frame),
+            Err(e) => e,
+        };
+
+        if !self.last_good.is_empty()
+            && let Err(e) = transmitter.transmit(&self.last_good)
+        {
+            log::warn!("Restoring the last good frame failed: {e:?}");
+        }
+        Err(OutputError::TransmitFailed {
+            reason: format!("{error:?}"),
+        })
+    }
+
+    /// Remember `frame` as the last good one
+    fn sent(&mut self, frame: &[u8]) -> Result<(), OutputError> {
+        self.last_good.clear();
+        self.last_good.extend_from_slice(frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Fake LED hardware that records each transmit and fails the ones it is told to
+    #[derive(Default)]
+    struct FakeStrip {
+        /// Every frame transmitted, with whether it succeeded
+        attempts: Vec<(Vec<u8>, bool)>,
+        /// Numbers of the transmits (counting from 0) that fail
+        failing: Vec<usize>,
+    }
+
+    impl FakeStrip {
+        /// Frame the LEDs show: the last one that went out in full
+        fn showing(&self) -> Option<&[u8]> {
+            self.attempts
+                .iter()
+                .rev()
+                .find(|(_, ok)| *ok)
+                .map(|(frame, _)| frame.as_slice())
+        }
+    }
+
+    impl FrameTransmitter for FakeStrip {
+        type Error = &'static str;
+
+        fn transmit(&mut self, frame: &[u8]) -> Result<(), &'static str> {
+            let ok = !self.failing.contains(&self.attempts.len());
+            self.attempts.push((frame.to_vec(), ok));
+            if ok { Ok(()) } else { Err("RMT error") }
+        }
+    }
+
+    #[test]
+    fn test_failed_transmit_is_retried() {
+        let mut strip = FakeStrip {
+            failing: vec![0],
+            ..Default::default()
+        };
+        let mut guard = TransmitGuard::new();
+
+        guard.transmit(&mut strip, &[1, 2, 3]).unwrap();
+        assert_eq!(
+            strip.attempts,
+            [(vec![1, 2, 3], false), (vec![1, 2, 3], true)]
+        );
+        assert_eq!(strip.showing(), Some(&[1, 2, 3][..]));
+    }
+
+    #[test]
+    fn test_repeated_failure_restores_last_good_frame() {
+        let mut strip = FakeStrip {
+            failing: vec![1, 2],
+            ..Default::default()
+        };
+        let mut guard = TransmitGuard::new();
+        guard.transmit(&mut strip, &[1, 2, 3]).unwrap();
+
+        let error = guard.transmit(&mut strip, &[4, 5, 6]).unwrap_err();
+        assert!(matches!(
+            &error,
+            OutputError::TransmitFailed { reason } if reason.contains("RMT error")
+        ));
+        // Tried twice, then went back to the previous frame
+        assert_eq!(strip.attempts.len(), 4);
+        assert_eq!(strip.attempts[3], (vec![1, 2, 3], true));
+        assert_eq!(strip.showing(), Some(&[1, 2, 3][..]));
+
+        // The failed frame never became the fallback
+        strip.failing = vec![4, 5];
+        guard.transmit(&mut strip, &[7, 8, 9]).unwrap_err();
+        assert_eq!(strip.attempts[6], (vec![1, 2, 3], true));
+    }
+
+    #[test]
+    fn test_repeated_failure_without_good_frame() {
+        let mut strip = FakeStrip {
+            failing: vec![0, 1],
+            ..Default::default()
+        };
+        let mut guard = TransmitGuard::new();
+
+        assert!(guard.transmit(&mut strip, &[1, 2, 3]).is_err());
+        assert_eq!(strip.attempts.len(), 2);
+        assert_eq!(strip.showing(), None);
+    }
+}
@@ -1,15 +1,20 @@
 //! Project builder for creating test projects with a fluent API
 
 use crate::fs::LpFs;
-use alloc::{format, rc::Rc, string::String, vec};
+use alloc::{format, rc::Rc, string::String, vec, vec::Vec};
 use core::cell::RefCell;
-use lp_model::nodes::fixture::ColorOrder;
+use lp_model::nodes::fixture::{ColorOrder, MaskConfig, WhiteExtraction};
 use lp_model::nodes::fixture::{MappingConfig, PathSpec, RingOrder};
 use lp_model::nodes::{
-    NodeSpecifier, fixture::FixtureConfig, output::OutputConfig, shader::ShaderConfig,
-    texture::TextureConfig,
+    ColorConfig, GradientStopConfig, NodeSpecifier,
+    fixture::FixtureConfig,
+    group::{BlendMode, GroupConfig},
+    output::{DitherMode, OutputConfig, PowerLimitConfig},
+    shader::{ShaderBackend, ShaderConfig, UniformConfig, ViewportConfig},
+    texture::{TextureConfig, TextureFilter},
 };
 use lp_model::path::LpPathBuf;
+use lp_model::project::AutomationTrack;
 use lp_model::{AsLpPath, AsLpPathBuf};
 
 /// Builder for creating test projects
@@ -17,16 +22,23 @@ pub struct ProjectBuilder {
     fs: Rc<RefCell<dyn LpFs>>,
     uid: String,
     name: String,
+    seed: Option<u64>,
+    automation: Vec<AutomationTrack>,
     texture_id: u32,
     shader_id: u32,
     output_id: u32,
     fixture_id: u32,
+    group_id: u32,
 }
 
 /// Builder for texture nodes
 pub struct TextureBuilder {
     width: u32,
     height: u32,
+    image: Option<(String, Vec<u8>)>,
+    fill_color: Option<ColorConfig>,
+    filter: Option<TextureFilter>,
+    enabled: Option<bool>,
 }
 
 impl TextureBuilder {
@@ -41,6 +53,30 @@ impl TextureBuilder {
         self.height = height;
         self
     }
+
+    /// Load the texture from an image file, written into the node directory as `file_name`
+    pub fn image(mut self, file_name: &str, data: &[u8]) -> Self {
+        self.image = Some((String::from(file_name), data.to_vec()));
+        self
+    }
+
+    /// Fill the texture with a solid color at init
+    pub fn fill_color(mut self, color: ColorConfig) -> Self {
+        self.fill_color = Some(color);
+        self
+    }
+
+    /// Set how fixtures sample the texture
+    pub fn filter(mut self, filter: TextureFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Enable or disable the texture
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
 }
 
 /// Builder for shader nodes
@@ -48,11 +84,34 @@ pub struct ShaderBuilder {
     texture_path: LpPathBuf,
     glsl_source: String,
     render_order: i32,
+    uniforms: Vec<UniformConfig>,
+    viewport: Option<ViewportConfig>,
+    loop_fuel: Option<u32>,
+    backend: Option<ShaderBackend>,
+    enabled: Option<bool>,
+    palette: Vec<GradientStopConfig>,
+    error_color: Option<ColorConfig>,
+}
+
+/// Builder for group nodes
+pub struct GroupBuilder {
+    texture_path: LpPathBuf,
+    render_order: i32,
+    opacity: Option<f32>,
+    blend_mode: Option<BlendMode>,
+    enabled: Option<bool>,
 }
 
 /// Builder for output nodes
 pub struct OutputBuilder {
     pin: u32,
+    /// Clock pin; set for APA102 clock+data strips
+    clock_pin: Option<u32>,
+    /// Art-Net node address; set for DMX over Art-Net outputs
+    artnet_address: Option<String>,
+    dither: Option<DitherMode>,
+    power_limit: Option<PowerLimitConfig>,
+    slew_limit: Option<u8>,
 }
 
 /// Builder for fixture nodes
@@ -64,6 +123,12 @@ pub struct FixtureBuilder {
     transform: [[f32; 4]; 4],
     brightness: Option<u8>,
     gamma_correction: Option<bool>,
+    universe: Option<u32>,
+    channel_offset: Option<u32>,
+    enabled: Option<bool>,
+    color_correction: Option<[[f32; 3]; 3]>,
+    mask: Option<MaskConfig>,
+    white_extraction: Option<WhiteExtraction>,
 }
 
 impl ProjectBuilder {
@@ -73,10 +138,13 @@ impl ProjectBuilder {
             fs,
             uid: String::from("test"),
             name: String::from("Test Project"),
+            seed: None,
+            automation: Vec::new(),
             texture_id: 1,
             shader_id: 1,
             output_id: 1,
             fixture_id: 1,
+            group_id: 1,
         }
     }
 
@@ -92,6 +160,18 @@ impl ProjectBuilder {
         self
     }
 
+    /// Set project random seed (defaults to unset, which uses seed 0)
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Add an automation track to the project config
+    pub fn with_automation(mut self, track: AutomationTrack) -> Self {
+        self.automation.push(track);
+        self
+    }
+
     /// Helper to write files
     fn write_file_helper(&self, path: &str, data: &[u8]) -> Result<(), crate::error::FsError> {
         self.fs.borrow().write_file(path.as_path(), data)
@@ -102,6 +182,10 @@ impl ProjectBuilder {
         TextureBuilder {
             width: 16,
             height: 16,
+            image: None,
+            fill_color: None,
+            filter: None,
+            enabled: None,
         }
     }
 
@@ -113,12 +197,39 @@ impl ProjectBuilder {
                 "vec4 main(vec2 fragCoord, vec2 outputSize, float time) { return vec4(mod(time, 1.0), 0.0, 0.0, 1.0); }",
             ),
             render_order: 0,
+            uniforms: Vec::new(),
+            viewport: None,
+            loop_fuel: None,
+            backend: None,
+            enabled: None,
+            palette: Vec::new(),
+            error_color: None,
+        }
+    }
+
+    /// Start building a group node that composites onto a texture (or another group)
+    ///
+    /// Shaders join the group by passing the returned path to [`ProjectBuilder::shader`].
+    pub fn group(&mut self, texture_path: &LpPathBuf) -> GroupBuilder {
+        GroupBuilder {
+            texture_path: texture_path.clone(),
+            render_order: 0,
+            opacity: None,
+            blend_mode: None,
+            enabled: None,
         }
     }
 
     /// Start building an output node (defaults to GPIO pin 0)
     pub fn output(&mut self) -> OutputBuilder {
-        OutputBuilder { pin: 0 }
+        OutputBuilder {
+            pin: 0,
+            clock_pin: None,
+            artnet_address: None,
+            dither: None,
+            power_limit: None,
+            slew_limit: None,
+        }
     }
 
     /// Start building a fixture node
@@ -147,6 +258,12 @@ impl ProjectBuilder {
             ],
             brightness: Some(255),
             gamma_correction: Some(false),
+            universe: None,
+            channel_offset: None,
+            enabled: None,
+            color_correction: None,
+            mask: None,
+            white_extraction: None,
         }
     }
 
@@ -178,8 +295,11 @@ impl ProjectBuilder {
     pub fn build(self) {
         // Write project.json using proper JSON serialization
         let config = lp_model::ProjectConfig {
+            version: lp_model::project::PROJECT_CONFIG_VERSION,
             uid: self.uid.clone(),
             name: self.name.clone(),
+            seed: self.seed,
+            automation: self.automation.clone(),
         };
         let project_json =
             lp_model::json::to_string(&config).expect("Failed to serialize project config");
@@ -201,10 +321,23 @@ impl TextureBuilder {
         let config = TextureConfig {
             width: self.width,
             height: self.height,
+            image_path: self
+                .image
+                .as_ref()
+                .map(|(file_name, _)| file_name.as_path_buf()),
+            fill_color: self.fill_color,
+            filter: self.filter,
+            enabled: self.enabled,
         };
 
         let json = lp_model::json::to_string(&config).expect("Failed to serialize texture config");
 
+        if let Some((file_name, data)) = &self.image {
+            builder
+                .write_file_helper(&format!("{path_str}/{file_name}"), data)
+                .expect("Failed to write texture image");
+        }
+
         builder
             .write_file_helper(&node_path, json.as_bytes())
             .expect("Failed to write texture node.json");
@@ -226,6 +359,56 @@ impl ShaderBuilder {
         self
     }
 
+    /// Set the value of a `uniform float` declared by the shader
+    pub fn uniform(mut self, name: &str, value: f32) -> Self {
+        self.uniforms.push(UniformConfig {
+            name: String::from(name),
+            value,
+        });
+        self
+    }
+
+    /// Only render into the given sub-rectangle of the texture
+    pub fn viewport(mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        self.viewport = Some(ViewportConfig {
+            x,
+            y,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Abort the shader after this many loop iterations in a frame
+    pub fn loop_fuel(mut self, fuel: u32) -> Self {
+        self.loop_fuel = Some(fuel);
+        self
+    }
+
+    /// Run the shader on the given backend instead of the JIT
+    pub fn backend(mut self, backend: ShaderBackend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Enable or disable the shader
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Add a stop to the gradient sampled by the shader's `palette(t)`
+    pub fn palette_stop(mut self, position: f32, color: ColorConfig) -> Self {
+        self.palette.push(GradientStopConfig { position, color });
+        self
+    }
+
+    /// Fill the shader's region with a solid color while it is in an error state
+    pub fn error_color(mut self, color: ColorConfig) -> Self {
+        self.error_color = Some(color);
+        self
+    }
+
     /// Add the shader node to the project
     pub fn add(self, builder: &mut ProjectBuilder) -> LpPathBuf {
         let id = builder.shader_id;
@@ -239,6 +422,13 @@ impl ShaderBuilder {
             glsl_path: "main.glsl".as_path_buf(),
             texture_spec: NodeSpecifier::from(self.texture_path.as_str()),
             render_order: self.render_order,
+            uniforms: self.uniforms,
+            viewport: self.viewport,
+            loop_fuel: self.loop_fuel,
+            backend: self.backend,
+            enabled: self.enabled,
+            palette: self.palette,
+            error_color: self.error_color,
         };
 
         let json = lp_model::json::to_string(&config).expect("Failed to serialize shader config");
@@ -255,6 +445,57 @@ impl ShaderBuilder {
     }
 }
 
+impl GroupBuilder {
+    /// Set render order among the shaders and groups sharing the target
+    pub fn render_order(mut self, order: i32) -> Self {
+        self.render_order = order;
+        self
+    }
+
+    /// Set the layer opacity (0.0-1.0)
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// Set how the layer blends onto its target
+    pub fn blend_mode(mut self, mode: BlendMode) -> Self {
+        self.blend_mode = Some(mode);
+        self
+    }
+
+    /// Enable or disable the group
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Add the group node to the project
+    pub fn add(self, builder: &mut ProjectBuilder) -> LpPathBuf {
+        let id = builder.group_id;
+        builder.group_id += 1;
+
+        let path_str = format!("/src/group-{id}.group");
+        let node_path = format!("{path_str}/node.json");
+
+        let config = GroupConfig {
+            texture_spec: NodeSpecifier::from(self.texture_path.as_str()),
+            render_order: self.render_order,
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
+            enabled: self.enabled,
+        };
+
+        let json = lp_model::json::to_string(&config).expect("Failed to serialize group config");
+
+        builder
+            .write_file_helper(&node_path, json.as_bytes())
+            .expect("Failed to write group node.json");
+
+        LpPathBuf::from(path_str)
+    }
+}
+
 impl OutputBuilder {
     /// Set the GPIO pin
     pub fn gpio_pin(mut self, pin: u32) -> Self {
@@ -262,6 +503,36 @@ impl OutputBuilder {
         self
     }
 
+    /// Drive an APA102/SK9822 strip, using the GPIO pin as data and `clock_pin` as clock
+    pub fn apa102(mut self, clock_pin: u32) -> Self {
+        self.clock_pin = Some(clock_pin);
+        self
+    }
+
+    /// Send DMX over Art-Net to the node at `address` (IPv4) instead of driving a GPIO strip
+    pub fn artnet(mut self, address: &str) -> Self {
+        self.artnet_address = Some(String::from(address));
+        self
+    }
+
+    /// Set the dither mode
+    pub fn dither(mut self, dither: DitherMode) -> Self {
+        self.dither = Some(dither);
+        self
+    }
+
+    /// Set the power limit
+    pub fn power_limit(mut self, power_limit: PowerLimitConfig) -> Self {
+        self.power_limit = Some(power_limit);
+        self
+    }
+
+    /// Set the slew limit (largest change per frame in a channel value)
+    pub fn slew_limit(mut self, slew_limit: u8) -> Self {
+        self.slew_limit = Some(slew_limit);
+        self
+    }
+
     /// Add the output node to the project
     pub fn add(self, builder: &mut ProjectBuilder) -> LpPathBuf {
         let id = builder.output_id;
@@ -270,7 +541,30 @@ impl OutputBuilder {
         let path_str = format!("/src/output-{id}.output");
         let node_path = format!("{path_str}/node.json");
 
-        let config = OutputConfig::GpioStrip { pin: self.pin };
+        let config = match (self.artnet_address, self.clock_pin) {
+            (Some(address), _) => OutputConfig::ArtNet {
+                address,
+                port: None,
+                start_universe: None,
+                dither: self.dither,
+                power_limit: self.power_limit,
+                slew_limit: self.slew_limit,
+            },
+            (None, Some(clock_pin)) => OutputConfig::Apa102Strip {
+                data_pin: self.pin,
+                clock_pin,
+                brightness: None,
+                dither: self.dither,
+                power_limit: self.power_limit,
+                slew_limit: self.slew_limit,
+            },
+            (None, None) => OutputConfig::GpioStrip {
+                pin: self.pin,
+                dither: self.dither,
+                power_limit: self.power_limit,
+                slew_limit: self.slew_limit,
+            },
+        };
 
         let json = lp_model::json::to_string(&config).expect("Failed to serialize output config");
 
@@ -313,6 +607,42 @@ impl FixtureBuilder {
         self
     }
 
+    /// Set the output universe the fixture's channels start in
+    pub fn universe(mut self, universe: u32) -> Self {
+        self.universe = Some(universe);
+        self
+    }
+
+    /// Set the channel offset within the universe
+    pub fn channel_offset(mut self, channel_offset: u32) -> Self {
+        self.channel_offset = Some(channel_offset);
+        self
+    }
+
+    /// Enable or disable the fixture
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = Some(enabled);
+        self
+    }
+
+    /// Set the 3x3 color correction matrix (rows are output red, green, blue)
+    pub fn color_correction(mut self, matrix: [[f32; 3]; 3]) -> Self {
+        self.color_correction = Some(matrix);
+        self
+    }
+
+    /// Set the per-lamp alpha mask
+    pub fn mask(mut self, mask: MaskConfig) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Set how the white channel of an RGBW fixture is computed
+    pub fn white_extraction(mut self, extraction: WhiteExtraction) -> Self {
+        self.white_extraction = Some(extraction);
+        self
+    }
+
     /// Add the fixture node to the project
     pub fn add(self, builder: &mut ProjectBuilder) -> LpPathBuf {
         let id = builder.fixture_id;
@@ -329,6 +659,12 @@ impl FixtureBuilder {
             transform: self.transform,
             brightness: self.brightness,
             gamma_correction: self.gamma_correction,
+            universe: self.universe,
+            channel_offset: self.channel_offset,
+            enabled: self.enabled,
+            color_correction: self.color_correction,
+            mask: self.mask,
+            white_extraction: self.white_extraction,
         };
 
         let json = lp_model::json::to_string(&config).expect("Failed to serialize fixture config");
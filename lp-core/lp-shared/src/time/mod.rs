@@ -1,3 +1,9 @@
+pub mod pacer;
 pub mod provider;
+#[cfg(feature = "std")]
+pub mod test_clock;
 
+pub use pacer::FramePacer;
 pub use provider::TimeProvider;
+#[cfg(feature = "std")]
+pub use test_clock::TestClock;
@@ -0,0 +1,199 @@
+//! Frame pacing for fixed-rate render loops
+//!
+//! [`FramePacer`] tells a loop how long to sleep after each frame so frames start
+//! on a fixed interval. It only does the arithmetic; the caller reads the clock
+//! and sleeps, so it works with any [`TimeProvider`](super::TimeProvider) or
+//! async timer.
+
+/// Window over which the actual frame rate is measured
+const FPS_WINDOW_MS: u64 = 1000;
+
+/// Paces frames to a target interval and measures the actual frame rate
+///
+/// Call [`begin_frame`](Self::begin_frame) when a frame starts and
+/// [`end_frame`](Self::end_frame) when it finishes, then sleep for the returned
+/// duration.
+///
+/// A frame that overruns its slot resets the schedule instead of accumulating
+/// sleep debt: following frames are paced from the end of the late frame rather
+/// than rushed to catch up.
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    /// Target interval between frame starts in milliseconds (0 = unpaced)
+    target_interval_ms: u64,
+    /// Scheduled start of the current frame
+    frame_deadline: Option<u64>,
+    /// Start of the current FPS measurement window
+    window_start: Option<u64>,
+    /// Frames started in the current window
+    window_frames: u32,
+    /// Frame rate measured over the last complete window
+    actual_fps: u32,
+    /// Frames that took longer than the target interval
+    overruns: u32,
+}
+
+impl FramePacer {
+    /// Create a pacer with the given target interval in milliseconds
+    ///
+    /// An interval of 0 disables pacing; frame rate is still measured.
+    pub fn new(target_interval_ms: u64) -> Self {
+        Self {
+            target_interval_ms,
+            frame_deadline: None,
+            window_start: None,
+            window_frames: 0,
+            actual_fps: 0,
+            overruns: 0,
+        }
+    }
+
+    /// Create a pacer targeting the given frame rate (0 = unpaced)
+    pub fn from_fps(fps: u32) -> Self {
+        Self::new(Self::interval_for_fps(fps))
+    }
+
+    /// Target interval in milliseconds for a frame rate (0 = unpaced)
+    pub fn interval_for_fps(fps: u32) -> u64 {
+        if fps == 0 { 0 } else { 1000 / fps as u64 }
+    }
+
+    /// Change the target interval, restarting the schedule
+    pub fn set_target_interval_ms(&mut self, target_interval_ms: u64) {
+        self.target_interval_ms = target_interval_ms;
+        self.frame_deadline = None;
+    }
+
+    /// Target interval between frame starts in milliseconds
+    pub fn target_interval_ms(&self) -> u64 {
+        self.target_interval_ms
+    }
+
+    /// Target frame rate (0 if unpaced)
+    pub fn target_fps(&self) -> u32 {
+        if self.target_interval_ms == 0 {
+            0
+        } else {
+            (1000 / self.target_interval_ms) as u32
+        }
+    }
+
+    /// Frame rate measured over the last complete one-second window
+    pub fn actual_fps(&self) -> u32 {
+        self.actual_fps
+    }
+
+    /// Number of frames that overran the target interval
+    pub fn overruns(&self) -> u32 {
+        self.overruns
+    }
+
+    /// Record the start of a frame
+    pub fn begin_frame(&mut self, now_ms: u64) {
+        if self.frame_deadline.is_none() {
+            self.frame_deadline = Some(now_ms);
+        }
+
+        let window_start = *self.window_start.get_or_insert(now_ms);
+        let elapsed = now_ms.saturating_sub(window_start);
+        if elapsed >= FPS_WINDOW_MS {
+            self.actual_fps = ((self.window_frames as u64 * 1000) / elapsed) as u32;
+            self.window_start = Some(now_ms);
+            self.window_frames = 0;
+        }
+        self.window_frames += 1;
+    }
+
+    /// Record the end of a frame
+    ///
+    /// # Returns
+    /// Milliseconds to sleep before starting the next frame (0 if the frame overran
+    /// or pacing is disabled)
+    pub fn end_frame(&mut self, now_ms: u64) -> u64 {
+        if self.target_interval_ms == 0 {
+            return 0;
+        }
+
+        let frame_start = self.frame_deadline.unwrap_or(now_ms);
+        let next_start = frame_start + self.target_interval_ms;
+        if now_ms > next_start {
+            // Overran: start the next frame now and pace from there
+            self.overruns = self.overruns.saturating_add(1);
+            self.frame_deadline = Some(now_ms);
+            0
+        } else {
+            self.frame_deadline = Some(next_start);
+            next_start - now_ms
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Manually advanced clock
+    struct ManualClock {
+        now_ms: u64,
+    }
+
+    /// Run frames with the given work durations, returning each frame's start time
+    fn run_frames(pacer: &mut FramePacer, clock: &mut ManualClock, work_ms: &[u64]) -> Vec<u64> {
+        let mut starts = Vec::new();
+        for &work in work_ms {
+            starts.push(clock.now_ms);
+            pacer.begin_frame(clock.now_ms);
+            clock.now_ms += work;
+            clock.now_ms += pacer.end_frame(clock.now_ms);
+        }
+        starts
+    }
+
+    #[test]
+    fn test_frames_paced_to_target_interval() {
+        let mut clock = ManualClock { now_ms: 100 };
+        let mut pacer = FramePacer::new(20);
+
+        let starts = run_frames(&mut pacer, &mut clock, &[5, 12, 1, 19, 20, 7]);
+        assert_eq!(starts, [100, 120, 140, 160, 180, 200]);
+        assert_eq!(pacer.overruns(), 0);
+    }
+
+    #[test]
+    fn test_overrun_does_not_accumulate_debt() {
+        let mut clock = ManualClock { now_ms: 0 };
+        let mut pacer = FramePacer::new(20);
+
+        // The second frame takes 70ms; later frames are paced from its end
+        // instead of running back-to-back to catch up
+        let starts = run_frames(&mut pacer, &mut clock, &[5, 70, 5, 5, 5]);
+        assert_eq!(starts, [0, 20, 90, 110, 130]);
+        assert_eq!(pacer.overruns(), 1);
+    }
+
+    #[test]
+    fn test_actual_fps_measured() {
+        let mut clock = ManualClock { now_ms: 0 };
+        let mut pacer = FramePacer::from_fps(50);
+        assert_eq!(pacer.target_interval_ms(), 20);
+        assert_eq!(pacer.target_fps(), 50);
+
+        let work = [3u64; 60];
+        run_frames(&mut pacer, &mut clock, &work);
+        assert_eq!(pacer.actual_fps(), 50);
+
+        // Slow frames drop the measured rate below target
+        let work = [40u64; 60];
+        run_frames(&mut pacer, &mut clock, &work);
+        assert_eq!(pacer.actual_fps(), 25);
+    }
+
+    #[test]
+    fn test_unpaced() {
+        let mut pacer = FramePacer::from_fps(0);
+        pacer.begin_frame(0);
+        assert_eq!(pacer.end_frame(3), 0);
+        assert_eq!(pacer.target_fps(), 0);
+    }
+}
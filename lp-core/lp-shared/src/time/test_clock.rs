@@ -0,0 +1,78 @@
+//! Deterministic clock for tests
+//!
+//! A [`TestClock`] only moves when the test advances it. Clones share the same time, so
+//! one clock can drive several consumers (the engine's frame ticks, the emulator's time
+//! syscall) and they all see the same advance.
+
+use super::TimeProvider;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Manually advanced clock, shared between clones
+#[derive(Debug, Clone, Default)]
+pub struct TestClock {
+    now_ms: Arc<AtomicU64>,
+}
+
+impl TestClock {
+    /// Create a clock starting at zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a clock starting at `ms`
+    pub fn starting_at(ms: u64) -> Self {
+        let clock = Self::new();
+        clock.set(ms);
+        clock
+    }
+
+    /// Move the clock forward by `ms`
+    pub fn advance(&self, ms: u64) {
+        // fetch_update never fails when the closure always returns Some
+        let _ = self
+            .now_ms
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |now| {
+                Some(now.saturating_add(ms))
+            });
+    }
+
+    /// Jump the clock to `ms`
+    pub fn set(&self, ms: u64) {
+        self.now_ms.store(ms, Ordering::SeqCst);
+    }
+
+    /// Current time, truncated to the 32-bit milliseconds the firmware and emulator use
+    pub fn now_ms_u32(&self) -> u32 {
+        self.now_ms() as u32
+    }
+}
+
+impl TimeProvider for TestClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_time() {
+        let clock = TestClock::new();
+        let other = clock.clone();
+        clock.advance(16);
+        assert_eq!(other.now_ms(), 16);
+        other.advance(4);
+        assert_eq!(clock.now_ms(), 20);
+    }
+
+    #[test]
+    fn test_set_and_elapsed() {
+        let clock = TestClock::starting_at(1000);
+        let start = clock.now_ms();
+        clock.set(1250);
+        assert_eq!(clock.elapsed_ms(start), 250);
+    }
+}
@@ -0,0 +1,166 @@
+//! Datagram framing for unreliable transports (UDP)
+//!
+//! Each message is sent as one datagram with an 8-byte header in front of the
+//! serialized message:
+//!
+//! ```text
+//! [sequence: u32 LE][payload length: u32 LE][payload: JSON bytes]
+//! ```
+//!
+//! The sender numbers its datagrams 0, 1, 2, ... (wrapping). The receiver uses the
+//! sequence number to notice datagrams that were dropped or arrived out of order, and
+//! the length to reject truncated or padded datagrams.
+//!
+//! Framing only detects loss; it does not retransmit. Transports built on it are
+//! best-effort.
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::vec::Vec;
+use lp_model::TransportError;
+
+/// Size of the header in front of every datagram payload
+pub const DATAGRAM_HEADER_LEN: usize = 8;
+
+/// Largest datagram that fits in a single IPv4 UDP packet
+pub const MAX_DATAGRAM_LEN: usize = 65_507;
+
+/// Largest payload that fits in one datagram
+pub const MAX_DATAGRAM_PAYLOAD_LEN: usize = MAX_DATAGRAM_LEN - DATAGRAM_HEADER_LEN;
+
+/// Frame `payload` as a datagram with the given sequence number
+pub fn encode_datagram(sequence: u32, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+    if payload.len() > MAX_DATAGRAM_PAYLOAD_LEN {
+        return Err(TransportError::Serialization(format!(
+            "Message of {} bytes exceeds the {MAX_DATAGRAM_PAYLOAD_LEN} byte datagram limit",
+            payload.len()
+        )));
+    }
+
+    let mut datagram = Vec::with_capacity(DATAGRAM_HEADER_LEN + payload.len());
+    datagram.extend_from_slice(&sequence.to_le_bytes());
+    datagram.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    datagram.extend_from_slice(payload);
+    Ok(datagram)
+}
+
+/// Split a datagram into its sequence number and payload
+pub fn decode_datagram(datagram: &[u8]) -> Result<(u32, &[u8]), TransportError> {
+    let Some((header, payload)) = datagram.split_first_chunk::<DATAGRAM_HEADER_LEN>() else {
+        return Err(TransportError::Deserialization(format!(
+            "Datagram of {} bytes is shorter than its header",
+            datagram.len()
+        )));
+    };
+
+    let sequence = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    if len != payload.len() {
+        return Err(TransportError::Deserialization(format!(
+            "Datagram header announces {len} payload bytes but {} arrived",
+            payload.len()
+        )));
+    }
+    Ok((sequence, payload))
+}
+
+/// What a received sequence number says about the datagrams before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// The next datagram in order
+    InOrder,
+    /// `missed` datagrams before this one never arrived
+    Gap { missed: u32 },
+    /// A duplicate, or a datagram overtaken by a later one; should be discarded
+    Stale,
+}
+
+/// Tracks sequence numbers from one sender to detect dropped datagrams
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    /// Sequence number expected next (None until the first datagram)
+    expected: Option<u32>,
+    /// Total datagrams detected as missing
+    dropped: u64,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a received sequence number
+    ///
+    /// The first datagram seen is always in order, so a receiver that starts late
+    /// doesn't report everything sent before it as dropped.
+    pub fn record(&mut self, sequence: u32) -> SequenceEvent {
+        let Some(expected) = self.expected else {
+            self.expected = Some(sequence.wrapping_add(1));
+            return SequenceEvent::InOrder;
+        };
+
+        // Distances in the upper half of the range are behind us (with wrapping)
+        let ahead = sequence.wrapping_sub(expected);
+        if ahead >= 1 << 31 {
+            return SequenceEvent::Stale;
+        }
+
+        self.expected = Some(sequence.wrapping_add(1));
+        if ahead == 0 {
+            SequenceEvent::InOrder
+        } else {
+            self.dropped += u64::from(ahead);
+            SequenceEvent::Gap { missed: ahead }
+        }
+    }
+
+    /// Total datagrams detected as missing so far
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_datagram_layout() {
+        let datagram = encode_datagram(0x0102_0304, b"{}").unwrap();
+        assert_eq!(datagram, [4, 3, 2, 1, 2, 0, 0, 0, b'{', b'}']);
+        assert_eq!(decode_datagram(&datagram).unwrap(), (0x0102_0304, &b"{}"[..]));
+    }
+
+    #[test]
+    fn test_malformed_datagrams_rejected() {
+        assert!(decode_datagram(&[0, 0, 0]).is_err());
+
+        let datagram = encode_datagram(1, b"hello").unwrap();
+        assert!(decode_datagram(&datagram[..datagram.len() - 1]).is_err());
+
+        let mut padded = datagram.clone();
+        padded.push(0);
+        assert!(decode_datagram(&padded).is_err());
+
+        assert!(encode_datagram(0, &[0; MAX_DATAGRAM_PAYLOAD_LEN + 1]).is_err());
+    }
+
+    #[test]
+    fn test_sequence_tracker() {
+        let mut tracker = SequenceTracker::new();
+        assert_eq!(tracker.record(5), SequenceEvent::InOrder);
+        assert_eq!(tracker.record(6), SequenceEvent::InOrder);
+        assert_eq!(tracker.record(9), SequenceEvent::Gap { missed: 2 });
+        assert_eq!(tracker.record(8), SequenceEvent::Stale);
+        assert_eq!(tracker.record(9), SequenceEvent::Stale);
+        assert_eq!(tracker.record(10), SequenceEvent::InOrder);
+        assert_eq!(tracker.dropped(), 2);
+
+        // Wraps around u32::MAX
+        let mut tracker = SequenceTracker::new();
+        tracker.record(u32::MAX - 1);
+        assert_eq!(tracker.record(u32::MAX), SequenceEvent::InOrder);
+        assert_eq!(tracker.record(1), SequenceEvent::Gap { missed: 1 });
+    }
+}
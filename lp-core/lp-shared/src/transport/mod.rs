@@ -6,8 +6,10 @@
 //! Transports handle serialization/deserialization internally, working directly
 //! with `ClientMessage` and `ServerMessage` types from `lp-model`.
 
+pub mod datagram;
 pub mod server;
 
 // Re-export TransportError from lp-model for convenience
 pub use lp_model::TransportError;
+pub use datagram::{SequenceEvent, SequenceTracker, decode_datagram, encode_datagram};
 pub use server::ServerTransport;
@@ -8,7 +8,7 @@
 extern crate alloc;
 
 use alloc::vec::Vec;
-use lp_model::{ClientMessage, ServerMessage, TransportError};
+use lp_model::{ClientMessage, ConnectionId, DEFAULT_CONNECTION, ServerMessage, TransportError};
 
 /// Trait for server-side transport implementations
 ///
@@ -93,6 +93,36 @@ pub trait ServerTransport {
         Ok(messages)
     }
 
+    /// Receive a client message along with the connection it arrived on (non-blocking)
+    ///
+    /// Transports serving several clients override this, together with
+    /// [`ServerTransport::send_to`]; by default every message comes from
+    /// [`DEFAULT_CONNECTION`].
+    fn receive_from(&mut self) -> Result<Option<(ConnectionId, ClientMessage)>, TransportError> {
+        Ok(self.receive()?.map(|msg| (DEFAULT_CONNECTION, msg)))
+    }
+
+    /// Send a server message to one connection (consumes the message)
+    ///
+    /// By default the connection is ignored and the message goes out with
+    /// [`ServerTransport::send`].
+    fn send_to(
+        &mut self,
+        connection: ConnectionId,
+        msg: ServerMessage,
+    ) -> Result<(), TransportError> {
+        let _ = connection;
+        self.send(msg)
+    }
+
+    /// Take the connections closed since the last call
+    ///
+    /// The server drops its per-connection state for them. Transports serving a single
+    /// client have nothing to report.
+    fn take_closed(&mut self) -> Vec<ConnectionId> {
+        Vec::new()
+    }
+
     /// Close the transport connection
     ///
     /// Explicitly closes the transport connection. This method is idempotent -
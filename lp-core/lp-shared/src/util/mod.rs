@@ -1,2 +1,3 @@
 pub mod formats;
+pub mod rgba;
 pub mod texture;
@@ -0,0 +1,217 @@
+//! RGBA color with alpha premultiplication helpers
+//!
+//! Blend math (e.g. source-over) is only correct on premultiplied colors, where the
+//! color channels are already scaled by alpha. Straight (non-premultiplied) colors
+//! must be premultiplied before compositing and unpremultiplied afterwards.
+
+use lp_model::nodes::group::BlendMode;
+
+/// 8-bit RGBA color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// Multiply two 8-bit values, treating 255 as 1.0, with rounding
+fn mul_u8(x: u8, y: u8) -> u8 {
+    ((x as u32 * y as u32 + 127) / 255) as u8
+}
+
+impl Rgba {
+    /// Fully transparent black
+    pub const TRANSPARENT: Rgba = Rgba::new(0, 0, 0, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Convert a straight color to premultiplied alpha
+    pub fn premultiply(self) -> Self {
+        Self {
+            r: mul_u8(self.r, self.a),
+            g: mul_u8(self.g, self.a),
+            b: mul_u8(self.b, self.a),
+            a: self.a,
+        }
+    }
+
+    /// Convert a premultiplied color back to straight alpha
+    ///
+    /// A color with zero alpha carries no color information and becomes transparent black.
+    pub fn unpremultiply(self) -> Self {
+        if self.a == 0 {
+            return Self::TRANSPARENT;
+        }
+
+        let a = self.a as u32;
+        let div = |c: u8| ((c as u32 * 255 + a / 2) / a).min(255) as u8;
+        Self {
+            r: div(self.r),
+            g: div(self.g),
+            b: div(self.b),
+            a: self.a,
+        }
+    }
+
+    /// Composite this color over `dst` (source-over)
+    ///
+    /// Both colors must be premultiplied; the result is premultiplied.
+    pub fn over(self, dst: Rgba) -> Self {
+        let inv_a = 255 - self.a;
+        Self {
+            r: self.r.saturating_add(mul_u8(dst.r, inv_a)),
+            g: self.g.saturating_add(mul_u8(dst.g, inv_a)),
+            b: self.b.saturating_add(mul_u8(dst.b, inv_a)),
+            a: self.a.saturating_add(mul_u8(dst.a, inv_a)),
+        }
+    }
+
+    /// Scale every channel, alpha included, by `factor` (255 leaves the color unchanged)
+    ///
+    /// On a premultiplied color this fades it towards transparent, e.g. for layer opacity.
+    pub fn scale(self, factor: u8) -> Self {
+        Self {
+            r: mul_u8(self.r, factor),
+            g: mul_u8(self.g, factor),
+            b: mul_u8(self.b, factor),
+            a: mul_u8(self.a, factor),
+        }
+    }
+
+    /// Composite this color onto `dst` using `mode` to mix the colors
+    ///
+    /// Both colors must be premultiplied; the result is premultiplied. Alpha combines
+    /// as in [`Rgba::over`] for every mode.
+    pub fn blend(self, dst: Rgba, mode: BlendMode) -> Self {
+        let channel = |s: u8, d: u8| match mode {
+            BlendMode::Normal => s.saturating_add(mul_u8(d, 255 - self.a)),
+            BlendMode::Add => s.saturating_add(d),
+            BlendMode::Multiply => mul_u8(s, 255 - dst.a)
+                .saturating_add(mul_u8(d, 255 - self.a))
+                .saturating_add(mul_u8(s, d)),
+            BlendMode::Screen => s.saturating_add(mul_u8(d, 255 - s)),
+        };
+        Self {
+            r: channel(self.r, dst.r),
+            g: channel(self.g, dst.g),
+            b: channel(self.b, dst.b),
+            a: self.a.saturating_add(mul_u8(dst.a, 255 - self.a)),
+        }
+    }
+}
+
+impl From<[u8; 4]> for Rgba {
+    fn from([r, g, b, a]: [u8; 4]) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl From<Rgba> for [u8; 4] {
+    fn from(color: Rgba) -> Self {
+        [color.r, color.g, color.b, color.a]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Rgba, expected: Rgba) {
+        let a: [u8; 4] = actual.into();
+        let e: [u8; 4] = expected.into();
+        for (x, y) in a.iter().zip(e.iter()) {
+            assert!(x.abs_diff(*y) <= 1, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_premultiply_opaque_is_identity() {
+        let color = Rgba::new(10, 128, 255, 255);
+        assert_eq!(color.premultiply(), color);
+        assert_eq!(color.unpremultiply(), color);
+    }
+
+    #[test]
+    fn test_premultiply_half_alpha() {
+        let color = Rgba::new(255, 128, 0, 128);
+        assert_eq!(color.premultiply(), Rgba::new(128, 64, 0, 128));
+    }
+
+    #[test]
+    fn test_premultiply_round_trip() {
+        for color in [
+            Rgba::new(255, 128, 0, 128),
+            Rgba::new(200, 100, 50, 200),
+            Rgba::new(17, 34, 51, 255),
+            Rgba::new(255, 255, 255, 64),
+        ] {
+            assert_close(color.premultiply().unpremultiply(), color);
+        }
+    }
+
+    #[test]
+    fn test_unpremultiply_zero_alpha() {
+        assert_eq!(Rgba::new(10, 20, 30, 0).unpremultiply(), Rgba::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_over_transparent_background() {
+        let src = Rgba::new(255, 64, 0, 128).premultiply();
+        assert_eq!(src.over(Rgba::TRANSPARENT), src);
+        assert_close(
+            src.over(Rgba::TRANSPARENT).unpremultiply(),
+            Rgba::new(255, 64, 0, 128),
+        );
+    }
+
+    #[test]
+    fn test_over_opaque_background() {
+        let src = Rgba::new(255, 0, 0, 128).premultiply();
+        let dst = Rgba::new(0, 0, 255, 255);
+        assert_eq!(src.over(dst), Rgba::new(128, 0, 127, 255));
+    }
+
+    #[test]
+    fn test_scale() {
+        let color = Rgba::new(255, 128, 0, 255);
+        assert_eq!(color.scale(255), color);
+        assert_eq!(color.scale(128), Rgba::new(128, 64, 0, 128));
+        assert_eq!(color.scale(0), Rgba::TRANSPARENT);
+    }
+
+    #[test]
+    fn test_blend_modes() {
+        let src = Rgba::new(128, 255, 0, 255);
+        let dst = Rgba::new(128, 128, 255, 255);
+
+        assert_eq!(src.blend(dst, BlendMode::Normal), src.over(dst));
+        assert_eq!(
+            src.blend(dst, BlendMode::Add),
+            Rgba::new(255, 255, 255, 255)
+        );
+        assert_eq!(
+            src.blend(dst, BlendMode::Multiply),
+            Rgba::new(64, 128, 0, 255)
+        );
+        assert_eq!(
+            src.blend(dst, BlendMode::Screen),
+            Rgba::new(192, 255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_blend_transparent_source_leaves_destination() {
+        let dst = Rgba::new(10, 20, 30, 255);
+        for mode in [
+            BlendMode::Normal,
+            BlendMode::Add,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+        ] {
+            assert_eq!(Rgba::TRANSPARENT.blend(dst, mode), dst, "{mode:?}");
+        }
+    }
+}
@@ -2,6 +2,8 @@
 
 use crate::error::TextureError;
 use crate::util::formats;
+use crate::util::rgba::Rgba;
+use lp_model::nodes::group::BlendMode;
 
 /// Texture structure for managing pixel buffers
 #[derive(Debug, Clone)]
@@ -10,6 +12,8 @@ pub struct Texture {
     height: u32,
     format: alloc::string::String,
     data: alloc::vec::Vec<u8>,
+    /// Whether pixel colors are stored with premultiplied alpha
+    premultiplied: bool,
 }
 
 impl Texture {
@@ -44,6 +48,7 @@ impl Texture {
             height,
             format,
             data,
+            premultiplied: false,
         })
     }
 
@@ -67,6 +72,67 @@ impl Texture {
         self.height
     }
 
+    /// Whether pixel colors are stored with premultiplied alpha
+    pub fn is_premultiplied(&self) -> bool {
+        self.premultiplied
+    }
+
+    /// Mark the pixel data as premultiplied or straight alpha
+    ///
+    /// This only changes how the data is interpreted, not the data itself.
+    pub fn set_premultiplied(&mut self, premultiplied: bool) {
+        self.premultiplied = premultiplied;
+    }
+
+    /// Composite `src` over this texture (source-over)
+    ///
+    /// Both textures are converted to premultiplied alpha for the blend as needed, and
+    /// the result is stored in this texture's alpha mode.
+    pub fn composite_over(&mut self, src: &Texture) -> Result<(), TextureError> {
+        self.composite(src, 255, BlendMode::Normal)
+    }
+
+    /// Composite `src` onto this texture with a blend mode, faded by `opacity`
+    ///
+    /// `opacity` scales the whole of `src` (255 = as is, 0 = no effect). Alpha modes are
+    /// handled as in [`Texture::composite_over`].
+    pub fn composite(
+        &mut self,
+        src: &Texture,
+        opacity: u8,
+        mode: BlendMode,
+    ) -> Result<(), TextureError> {
+        if src.width != self.width || src.height != self.height {
+            return Err(TextureError::DimensionMismatch {
+                expected: (self.width, self.height),
+                actual: (src.width, src.height),
+            });
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (Some(s), Some(d)) = (src.get_pixel(x, y), self.get_pixel(x, y)) else {
+                    continue;
+                };
+                let mut s = Rgba::from(s);
+                let mut d = Rgba::from(d);
+                if !src.premultiplied {
+                    s = s.premultiply();
+                }
+                if !self.premultiplied {
+                    d = d.premultiply();
+                }
+                let mut out = s.scale(opacity).blend(d, mode);
+                if !self.premultiplied {
+                    out = out.unpremultiply();
+                }
+                self.set_pixel(x, y, out.into());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get a pixel value at the given coordinates
     ///
     /// Returns RGBA values as [u8; 4], with missing channels set to 0.
@@ -323,4 +389,58 @@ mod tests {
         assert_eq!(pixel[1], 30);
         assert_eq!(pixel[2], 128);
     }
+
+    #[test]
+    fn test_composite_over_transparent_background() {
+        let mut dst = Texture::new(2, 1, formats::RGBA8.to_string()).unwrap();
+        let mut src = Texture::new(2, 1, formats::RGBA8.to_string()).unwrap();
+        src.set_pixel(0, 0, [255, 64, 0, 128]);
+        src.set_pixel(1, 0, [10, 20, 30, 0]);
+
+        dst.composite_over(&src).unwrap();
+
+        // Straight alpha is preserved through the premultiplied blend
+        let pixel = dst.get_pixel(0, 0).unwrap();
+        assert_eq!(pixel[3], 128);
+        assert!(pixel[0] >= 254 && pixel[1].abs_diff(64) <= 1 && pixel[2] == 0);
+        // Fully transparent source leaves the background untouched
+        assert_eq!(dst.get_pixel(1, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_composite_over_premultiplied() {
+        let mut dst = Texture::new(1, 1, formats::RGBA8.to_string()).unwrap();
+        dst.set_premultiplied(true);
+        dst.set_pixel(0, 0, [0, 0, 255, 255]);
+        let mut src = Texture::new(1, 1, formats::RGBA8.to_string()).unwrap();
+        src.set_premultiplied(true);
+        src.set_pixel(0, 0, [128, 0, 0, 128]);
+
+        dst.composite_over(&src).unwrap();
+        assert_eq!(dst.get_pixel(0, 0), Some([128, 0, 127, 255]));
+    }
+
+    #[test]
+    fn test_composite_with_opacity_and_mode() {
+        let mut dst = Texture::new(2, 1, formats::RGBA8.to_string()).unwrap();
+        dst.compute_all(|_, _| [0, 0, 255, 255]);
+        let mut src = Texture::new(2, 1, formats::RGBA8.to_string()).unwrap();
+        src.compute_all(|_, _| [255, 0, 0, 255]);
+
+        // Half opacity mixes an opaque layer halfway into the background
+        let mut faded = dst.clone();
+        faded.composite(&src, 128, BlendMode::Normal).unwrap();
+        assert_eq!(faded.get_pixel(0, 0), Some([128, 0, 127, 255]));
+
+        // Adding keeps the background's blue
+        dst.composite(&src, 128, BlendMode::Add).unwrap();
+        assert_eq!(dst.get_pixel(1, 0), Some([128, 0, 255, 255]));
+    }
+
+    #[test]
+    fn test_composite_over_dimension_mismatch() {
+        let mut dst = Texture::new(2, 2, formats::RGBA8.to_string()).unwrap();
+        let src = Texture::new(1, 1, formats::RGBA8.to_string()).unwrap();
+        assert!(dst.composite_over(&src).is_err());
+    }
 }
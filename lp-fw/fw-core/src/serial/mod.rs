@@ -1,3 +1,7 @@
 pub mod io;
+pub mod mux;
+pub mod write_queue;
 
 pub use io::{SerialError, SerialIo};
+pub use mux::{SerialDemux, SerialFrame, SerialMux};
+pub use write_queue::SerialWriteQueue;
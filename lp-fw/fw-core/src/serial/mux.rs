@@ -0,0 +1,356 @@
+//! Log/protocol multiplexing over a single serial port
+//!
+//! Frames are newline-terminated lines tagged by a two-byte prefix:
+//! - `M!{json}\n` - protocol message, routed to the [`MessageRouter`]
+//! - `L!{text}\n` - log line, printed
+//!
+//! Untagged lines are treated as log output, so raw prints (e.g. from a panic
+//! handler or the bootloader) still reach the host console.
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::message_router::MessageRouter;
+use crate::serial::write_queue::SerialWriteQueue;
+use crate::serial::{SerialError, SerialIo};
+
+/// Prefix for protocol frames
+pub const PROTOCOL_TAG: &str = "M!";
+
+/// Prefix for log frames
+pub const LOG_TAG: &str = "L!";
+
+/// A demultiplexed serial frame (tag and newline stripped)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerialFrame {
+    /// Log line
+    Log(String),
+    /// Protocol message payload (JSON)
+    Protocol(String),
+}
+
+impl SerialFrame {
+    /// Classify a line (without its trailing newline) by its tag
+    pub fn from_line(line: &str) -> Self {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if let Some(payload) = line.strip_prefix(PROTOCOL_TAG) {
+            SerialFrame::Protocol(payload.to_string())
+        } else if let Some(text) = line.strip_prefix(LOG_TAG) {
+            SerialFrame::Log(text.to_string())
+        } else {
+            SerialFrame::Log(line.to_string())
+        }
+    }
+}
+
+/// Encode a log message as one log frame per line
+pub fn encode_log(message: &str, out: &mut Vec<u8>) {
+    for line in message.lines() {
+        out.extend_from_slice(LOG_TAG.as_bytes());
+        out.extend_from_slice(line.as_bytes());
+        out.push(b'\n');
+    }
+}
+
+/// Encode a protocol payload as a protocol frame
+///
+/// The payload must be a single line (compact JSON).
+pub fn encode_protocol(payload: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(PROTOCOL_TAG.as_bytes());
+    out.extend_from_slice(payload.as_bytes());
+    out.push(b'\n');
+}
+
+/// Splits a byte stream into tagged frames
+#[derive(Default)]
+pub struct SerialDemux {
+    buffer: Vec<u8>,
+}
+
+impl SerialDemux {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append received bytes
+    pub fn push(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Take the next complete frame, if one has been received
+    ///
+    /// Lines that are not valid UTF-8 are skipped.
+    pub fn next_frame(&mut self) -> Option<SerialFrame> {
+        while let Some(newline_pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline_pos).collect();
+            match core::str::from_utf8(&line[..line.len() - 1]) {
+                Ok(line) => return Some(SerialFrame::from_line(line)),
+                Err(_) => log::warn!("SerialDemux: Skipping line with invalid UTF-8"),
+            }
+        }
+        None
+    }
+
+    /// Number of buffered bytes not yet part of a complete frame
+    pub fn pending(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+/// Carries both log and protocol frames over one [`SerialIo`]
+pub struct SerialMux<Io: SerialIo> {
+    io: Io,
+    demux: SerialDemux,
+    write_buffer: Vec<u8>,
+    queue: SerialWriteQueue,
+}
+
+impl<Io: SerialIo> SerialMux<Io> {
+    /// Create a multiplexer over the given serial port
+    pub fn new(io: Io) -> Self {
+        Self {
+            io,
+            demux: SerialDemux::new(),
+            write_buffer: Vec::new(),
+            queue: SerialWriteQueue::default(),
+        }
+    }
+
+    /// Create a multiplexer with an outbound queue of `capacity` bytes
+    pub fn with_queue_capacity(io: Io, capacity: usize) -> Self {
+        Self {
+            queue: SerialWriteQueue::new(capacity),
+            ..Self::new(io)
+        }
+    }
+
+    /// Write a log message (multi-line messages become several log frames)
+    pub fn write_log(&mut self, message: &str) -> Result<(), SerialError> {
+        self.write_buffer.clear();
+        encode_log(message, &mut self.write_buffer);
+        self.io.write(&self.write_buffer)
+    }
+
+    /// Write a protocol payload
+    pub fn write_protocol(&mut self, payload: &str) -> Result<(), SerialError> {
+        self.write_buffer.clear();
+        encode_protocol(payload, &mut self.write_buffer);
+        self.io.write(&self.write_buffer)
+    }
+
+    /// Queue a log message without blocking
+    ///
+    /// The message is dropped if the queue is full; see [`SerialWriteQueue`].
+    pub fn queue_log(&mut self, message: &str) -> bool {
+        self.queue.push_log(message)
+    }
+
+    /// Queue a protocol payload without blocking (never dropped)
+    pub fn queue_protocol(&mut self, payload: &str) {
+        self.queue.push_protocol(payload);
+    }
+
+    /// Write up to about `max_bytes` of queued frames
+    ///
+    /// # Returns
+    /// Number of bytes written
+    pub fn flush_queued(&mut self, max_bytes: usize) -> Result<usize, SerialError> {
+        self.queue.flush(&mut self.io, max_bytes)
+    }
+
+    /// Number of log frames dropped because the outbound queue was full
+    pub fn dropped_logs(&self) -> u32 {
+        self.queue.dropped_logs()
+    }
+
+    /// Read available bytes and return the next complete frame, if any
+    pub fn poll(&mut self) -> Result<Option<SerialFrame>, SerialError> {
+        let mut temp_buf = [0u8; 256];
+        loop {
+            if let Some(frame) = self.demux.next_frame() {
+                return Ok(Some(frame));
+            }
+            let n = self.io.read_available(&mut temp_buf)?;
+            if n == 0 {
+                return Ok(None);
+            }
+            self.demux.push(&temp_buf[..n]);
+        }
+    }
+
+    /// Read all available frames, routing protocol frames and printing log frames
+    ///
+    /// Protocol frames are pushed to the router's incoming channel as `M!{json}`
+    /// lines, the format `MessageRouterTransport` expects. Frames are dropped with a
+    /// warning if the channel is full.
+    ///
+    /// # Returns
+    /// Number of protocol frames routed
+    pub fn dispatch(
+        &mut self,
+        router: &MessageRouter,
+        mut print_log: impl FnMut(&str),
+    ) -> Result<usize, SerialError> {
+        let mut routed = 0;
+        while let Some(frame) = self.poll()? {
+            match frame {
+                SerialFrame::Log(text) => print_log(&text),
+                SerialFrame::Protocol(payload) => {
+                    let mut line = String::with_capacity(PROTOCOL_TAG.len() + payload.len());
+                    line.push_str(PROTOCOL_TAG);
+                    line.push_str(&payload);
+                    if router.send_incoming(line).is_err() {
+                        log::warn!("SerialMux: Incoming queue full, dropping message");
+                    } else {
+                        routed += 1;
+                    }
+                }
+            }
+        }
+        Ok(routed)
+    }
+
+    /// Get the underlying serial port
+    pub fn io(&self) -> &Io {
+        &self.io
+    }
+
+    /// Get the underlying serial port mutably
+    pub fn io_mut(&mut self) -> &mut Io {
+        &mut self.io
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+    use alloc::{boxed::Box, vec};
+    use core::cell::RefCell;
+    use embassy_sync::channel::Channel;
+
+    // Mock SerialIo that delivers reads in small chunks
+    struct MockSerialIo {
+        read_data: RefCell<Vec<u8>>,
+        write_data: RefCell<Vec<u8>>,
+        chunk: usize,
+    }
+
+    impl MockSerialIo {
+        fn new(chunk: usize) -> Self {
+            Self {
+                read_data: RefCell::new(Vec::new()),
+                write_data: RefCell::new(Vec::new()),
+                chunk,
+            }
+        }
+
+        fn push_read(&self, data: &[u8]) {
+            self.read_data.borrow_mut().extend_from_slice(data);
+        }
+    }
+
+    impl SerialIo for MockSerialIo {
+        fn write(&mut self, data: &[u8]) -> Result<(), SerialError> {
+            self.write_data.borrow_mut().extend_from_slice(data);
+            Ok(())
+        }
+
+        fn read_available(&mut self, buf: &mut [u8]) -> Result<usize, SerialError> {
+            let mut read_buf = self.read_data.borrow_mut();
+            let to_read = read_buf.len().min(buf.len()).min(self.chunk);
+            buf[..to_read].copy_from_slice(&read_buf[..to_read]);
+            read_buf.drain(..to_read);
+            Ok(to_read)
+        }
+    }
+
+    #[test]
+    fn test_encode_frames() {
+        let mut out = Vec::new();
+        encode_log("first\nsecond", &mut out);
+        encode_protocol("{\"id\":1}", &mut out);
+        assert_eq!(out, b"L!first\nL!second\nM!{\"id\":1}\n");
+    }
+
+    #[test]
+    fn test_frame_from_line() {
+        assert_eq!(
+            SerialFrame::from_line("M!{}"),
+            SerialFrame::Protocol("{}".to_string())
+        );
+        assert_eq!(
+            SerialFrame::from_line("L!hello\r"),
+            SerialFrame::Log("hello".to_string())
+        );
+        assert_eq!(
+            SerialFrame::from_line("ESP-ROM:esp32c6"),
+            SerialFrame::Log("ESP-ROM:esp32c6".to_string())
+        );
+    }
+
+    #[test]
+    fn test_demux_interleaved_frames() {
+        // Write through one mux, read back through another over the same bytes
+        let mut writer = SerialMux::new(MockSerialIo::new(usize::MAX));
+        writer.write_log("[INFO] booting").unwrap();
+        writer.write_protocol("{\"id\":1}").unwrap();
+        writer
+            .write_log("[WARN] low memory\n[INFO] retrying")
+            .unwrap();
+        writer.write_protocol("{\"id\":2}").unwrap();
+        let wire = writer.io().write_data.borrow().clone();
+
+        // Deliver in 5-byte chunks so frames are split across reads
+        let reader_io = MockSerialIo::new(5);
+        reader_io.push_read(&wire);
+        reader_io.push_read(b"raw print\n");
+        let mut reader = SerialMux::new(reader_io);
+
+        let mut frames = Vec::new();
+        while let Some(frame) = reader.poll().unwrap() {
+            frames.push(frame);
+        }
+
+        assert_eq!(
+            frames,
+            vec![
+                SerialFrame::Log("[INFO] booting".to_string()),
+                SerialFrame::Protocol("{\"id\":1}".to_string()),
+                SerialFrame::Log("[WARN] low memory".to_string()),
+                SerialFrame::Log("[INFO] retrying".to_string()),
+                SerialFrame::Protocol("{\"id\":2}".to_string()),
+                SerialFrame::Log("raw print".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dispatch_routes_protocol_and_prints_logs() {
+        let incoming = Box::leak(Box::new(Channel::new()));
+        let outgoing = Box::leak(Box::new(Channel::new()));
+        let router = MessageRouter::new(incoming, outgoing);
+
+        let io = MockSerialIo::new(7);
+        io.push_read(b"L!log one\nM!{\"id\":1}\nL!log two\nM!{\"id\":2}\nM!{\"id\":");
+        let mut mux = SerialMux::new(io);
+
+        let mut printed = Vec::new();
+        let routed = mux
+            .dispatch(&router, |line| printed.push(line.to_string()))
+            .unwrap();
+
+        assert_eq!(routed, 2);
+        assert_eq!(printed, vec!["log one", "log two"]);
+        assert_eq!(router.receive_all(), vec!["M!{\"id\":1}", "M!{\"id\":2}"]);
+
+        // The partial frame completes on the next read
+        mux.io_mut().push_read(b"3}\n");
+        let routed = mux.dispatch(&router, |_| {}).unwrap();
+        assert_eq!(routed, 1);
+        assert_eq!(router.receive_all(), vec!["M!{\"id\":3}"]);
+    }
+}
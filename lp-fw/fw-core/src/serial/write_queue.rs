@@ -0,0 +1,319 @@
+//! Bounded outbound queue for multiplexed serial frames
+//!
+//! Frames are queued instead of written directly so a slow serial consumer
+//! can't stall the render loop. The loop flushes a bounded number of bytes per
+//! iteration. When the queue is full, log frames are dropped (oldest queued log
+//! first); protocol frames are never dropped, so the queue may exceed its
+//! capacity while protocol frames are pending.
+
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::serial::mux::{encode_log, encode_protocol};
+use crate::serial::{SerialError, SerialIo};
+
+/// Default queue capacity in bytes
+pub const DEFAULT_WRITE_QUEUE_CAPACITY: usize = 4096;
+
+/// An encoded frame waiting to be written
+struct QueuedFrame {
+    bytes: Vec<u8>,
+    is_log: bool,
+}
+
+/// Bounded queue of encoded serial frames
+pub struct SerialWriteQueue {
+    frames: VecDeque<QueuedFrame>,
+    queued_bytes: usize,
+    capacity: usize,
+    dropped_logs: u32,
+}
+
+impl SerialWriteQueue {
+    /// Create a queue holding up to `capacity` bytes of frames
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::new(),
+            queued_bytes: 0,
+            capacity,
+            dropped_logs: 0,
+        }
+    }
+
+    /// Queue a log message
+    ///
+    /// Older queued log frames are evicted to make room. If the message still
+    /// doesn't fit, it is dropped.
+    ///
+    /// # Returns
+    /// `true` if the message was queued, `false` if it was dropped
+    pub fn push_log(&mut self, message: &str) -> bool {
+        let mut bytes = Vec::new();
+        encode_log(message, &mut bytes);
+        if bytes.is_empty() {
+            return true;
+        }
+
+        if !self.make_room(bytes.len()) {
+            self.dropped_logs = self.dropped_logs.saturating_add(1);
+            return false;
+        }
+        self.push_frame(bytes, true);
+        true
+    }
+
+    /// Queue a protocol payload
+    ///
+    /// Protocol frames are always queued; queued log frames are evicted to make
+    /// room if needed.
+    pub fn push_protocol(&mut self, payload: &str) {
+        let mut bytes = Vec::new();
+        encode_protocol(payload, &mut bytes);
+        self.make_room(bytes.len());
+        self.push_frame(bytes, false);
+    }
+
+    /// Write queued frames to the serial port
+    ///
+    /// Writes whole frames until at least `max_bytes` have been written or the
+    /// queue is empty. At least one frame is written per call (if any are
+    /// queued), so a frame larger than `max_bytes` still makes progress.
+    ///
+    /// # Returns
+    /// Number of bytes written
+    pub fn flush<Io: SerialIo>(
+        &mut self,
+        io: &mut Io,
+        max_bytes: usize,
+    ) -> Result<usize, SerialError> {
+        let mut written = 0;
+        while written < max_bytes {
+            let Some(frame) = self.frames.front() else {
+                break;
+            };
+            io.write(&frame.bytes)?;
+            written += frame.bytes.len();
+            self.queued_bytes -= frame.bytes.len();
+            self.frames.pop_front();
+        }
+        Ok(written)
+    }
+
+    /// Next queued frame, left in the queue
+    ///
+    /// For writers that can't go through [`SerialIo`], such as async serial drivers:
+    /// write the frame, then [`Self::pop_frame`] it once the write succeeded, so a
+    /// failed write leaves it queued. The caller bounds how much it writes per
+    /// iteration.
+    pub fn front_frame(&self) -> Option<&[u8]> {
+        self.frames.front().map(|frame| frame.bytes.as_slice())
+    }
+
+    /// Take the next queued frame
+    pub fn pop_frame(&mut self) -> Option<Vec<u8>> {
+        let frame = self.frames.pop_front()?;
+        self.queued_bytes -= frame.bytes.len();
+        Some(frame.bytes)
+    }
+
+    /// Number of log frames dropped because the queue was full
+    pub fn dropped_logs(&self) -> u32 {
+        self.dropped_logs
+    }
+
+    /// Number of bytes currently queued
+    pub fn queued_bytes(&self) -> usize {
+        self.queued_bytes
+    }
+
+    /// Number of frames currently queued
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frames are queued
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Queue capacity in bytes
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn push_frame(&mut self, bytes: Vec<u8>, is_log: bool) {
+        self.queued_bytes += bytes.len();
+        self.frames.push_back(QueuedFrame { bytes, is_log });
+    }
+
+    /// Evict queued log frames, oldest first, until `len` more bytes fit
+    ///
+    /// Returns whether the bytes fit.
+    fn make_room(&mut self, len: usize) -> bool {
+        while self.queued_bytes + len > self.capacity {
+            let Some(index) = self.frames.iter().position(|frame| frame.is_log) else {
+                return false;
+            };
+            if let Some(frame) = self.frames.remove(index) {
+                self.queued_bytes -= frame.bytes.len();
+                self.dropped_logs = self.dropped_logs.saturating_add(1);
+            }
+        }
+        true
+    }
+}
+
+impl Default for SerialWriteQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_WRITE_QUEUE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+    use crate::serial::mux::{SerialDemux, SerialFrame};
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[derive(Default)]
+    struct MockSerialIo {
+        written: Vec<u8>,
+        /// Fail every write, as a disconnected port does
+        fail_writes: bool,
+    }
+
+    impl SerialIo for MockSerialIo {
+        fn write(&mut self, data: &[u8]) -> Result<(), SerialError> {
+            if self.fail_writes {
+                return Err(SerialError::WriteFailed("disconnected".to_string()));
+            }
+            self.written.extend_from_slice(data);
+            Ok(())
+        }
+
+        fn read_available(&mut self, _buf: &mut [u8]) -> Result<usize, SerialError> {
+            Ok(0)
+        }
+    }
+
+    fn decode(bytes: &[u8]) -> Vec<SerialFrame> {
+        let mut demux = SerialDemux::new();
+        demux.push(bytes);
+        let mut frames = Vec::new();
+        while let Some(frame) = demux.next_frame() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn test_full_queue_drops_logs_keeps_protocol() {
+        // "L!log N\n" is 8 bytes, so 32 bytes holds four log frames
+        let mut queue = SerialWriteQueue::new(32);
+        for i in 0..4 {
+            assert!(queue.push_log(&alloc::format!("log {i}")));
+        }
+        assert_eq!(queue.queued_bytes(), 32);
+
+        // Full: a new log evicts the oldest queued log
+        assert!(queue.push_log("log 4"));
+        assert_eq!(queue.dropped_logs(), 1);
+
+        // Protocol frames evict logs and are never dropped, even over capacity
+        queue.push_protocol("{\"id\":1}");
+        queue.push_protocol("{\"id\":2}");
+        queue.push_protocol("{\"id\":3}");
+        queue.push_protocol("{\"id\":4}");
+        assert!(queue.queued_bytes() > queue.capacity());
+        assert_eq!(queue.dropped_logs(), 5);
+
+        // No logs left to evict: new logs are dropped outright
+        assert!(!queue.push_log("log 5"));
+        assert_eq!(queue.dropped_logs(), 6);
+
+        let mut io = MockSerialIo::default();
+        queue.flush(&mut io, usize::MAX).unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(
+            decode(&io.written),
+            vec![
+                SerialFrame::Protocol("{\"id\":1}".to_string()),
+                SerialFrame::Protocol("{\"id\":2}".to_string()),
+                SerialFrame::Protocol("{\"id\":3}".to_string()),
+                SerialFrame::Protocol("{\"id\":4}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flush_respects_byte_budget() {
+        let mut queue = SerialWriteQueue::new(1024);
+        queue.push_log("first");
+        queue.push_protocol("{}");
+        queue.push_log("second");
+
+        let mut io = MockSerialIo::default();
+        // Budget smaller than one frame still writes one frame
+        assert_eq!(queue.flush(&mut io, 1).unwrap(), 8);
+        assert_eq!(queue.len(), 2);
+
+        queue.flush(&mut io, usize::MAX).unwrap();
+        assert_eq!(
+            decode(&io.written),
+            vec![
+                SerialFrame::Log("first".to_string()),
+                SerialFrame::Protocol("{}".to_string()),
+                SerialFrame::Log("second".to_string()),
+            ]
+        );
+        assert_eq!(queue.queued_bytes(), 0);
+        assert_eq!(queue.dropped_logs(), 0);
+    }
+
+    #[test]
+    fn test_pop_frame_in_order() {
+        let mut queue = SerialWriteQueue::new(1024);
+        queue.push_log("first");
+        queue.push_protocol("{}");
+
+        assert_eq!(queue.pop_frame(), Some(b"L!first\n".to_vec()));
+        assert_eq!(queue.queued_bytes(), 5);
+        assert_eq!(queue.pop_frame(), Some(b"M!{}\n".to_vec()));
+        assert_eq!(queue.pop_frame(), None);
+        assert_eq!(queue.queued_bytes(), 0);
+    }
+
+    #[test]
+    fn test_failed_write_keeps_frame_queued() {
+        let mut queue = SerialWriteQueue::new(1024);
+        queue.push_protocol("{\"id\":1}");
+        queue.push_log("after");
+
+        let mut io = MockSerialIo {
+            fail_writes: true,
+            ..Default::default()
+        };
+        assert!(queue.flush(&mut io, usize::MAX).is_err());
+        assert_eq!(queue.len(), 2);
+
+        // Async writers peek and only pop once the write succeeded
+        assert_eq!(queue.front_frame(), Some(&b"M!{\"id\":1}\n"[..]));
+        assert_eq!(queue.len(), 2);
+
+        io.fail_writes = false;
+        queue.flush(&mut io, usize::MAX).unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(
+            decode(&io.written),
+            vec![
+                SerialFrame::Protocol("{\"id\":1}".to_string()),
+                SerialFrame::Log("after".to_string()),
+            ]
+        );
+    }
+}
@@ -2,6 +2,15 @@
 //!
 //! Defines command and response types for test protocol using external
 //! discriminators compatible with serde-json-core.
+//!
+//! # Byte order
+//!
+//! Messages are UTF-8 JSON text, and multi-byte numbers such as `frame_count` are
+//! written as decimal digits, most significant first. The wire format therefore has
+//! no byte order of its own: firmware and host agree on every field regardless of
+//! their native endianness. Fields must never be sent as raw native-endian memory
+//! (e.g. `to_ne_bytes`); binary payloads go through [`lp_model::serde_base64`], which
+//! encodes a byte sequence rather than a machine word.
 
 extern crate alloc;
 
@@ -30,8 +39,8 @@ pub enum TestCommand {
 /// Test response (external discriminator format)
 ///
 /// Responses match command structure:
-/// - `M!{"frame_count":12345}\n`
-/// - `M!{"echo":"test"}\n`
+/// - `M!{"frame_count":{"frame_count":12345}}\n`
+/// - `M!{"Echo":{"echo":"test"}}\n`
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TestResponse {
     /// Frame count response
@@ -214,6 +223,30 @@ mod tests {
         assert!(msg.starts_with("M!"));
         assert!(msg.ends_with('\n'));
         assert!(msg.contains("test"));
+        assert_eq!(msg, "M!{\"Echo\":{\"echo\":\"test\"}}\n");
+    }
+
+    #[test]
+    fn test_multi_byte_field_layout() {
+        // 0x01020304 would be 04 03 02 01 in little-endian memory and 01 02 03 04 in
+        // big-endian; on the wire it is the same decimal text on both
+        let resp = TestResponse::FrameCount {
+            frame_count: 0x0102_0304,
+        };
+        let msg = serialize_response(&resp).unwrap();
+        assert_eq!(
+            msg.as_bytes(),
+            b"M!{\"frame_count\":{\"frame_count\":16909060}}\n"
+        );
+
+        let resp = TestResponse::FrameCount {
+            frame_count: u32::MAX,
+        };
+        let msg = serialize_response(&resp).unwrap();
+        assert_eq!(
+            msg.as_bytes(),
+            b"M!{\"frame_count\":{\"frame_count\":4294967295}}\n"
+        );
     }
 
     #[test]
@@ -9,7 +9,7 @@ use core::cell::RefCell;
 
 use lp_riscv_emu_guest::println;
 use lp_shared::OutputError;
-use lp_shared::output::{OutputChannelHandle, OutputFormat, OutputProvider};
+use lp_shared::output::{Brightness, OutputChannelHandle, OutputFormat, OutputProvider};
 
 /// Syscall-based OutputProvider implementation
 ///
@@ -18,6 +18,7 @@ use lp_shared::output::{OutputChannelHandle, OutputFormat, OutputProvider};
 pub struct SyscallOutputProvider {
     handles: RefCell<Vec<OutputChannelHandle>>,
     next_handle: RefCell<u32>,
+    brightness: RefCell<Brightness>,
 }
 
 impl SyscallOutputProvider {
@@ -26,6 +27,7 @@ impl SyscallOutputProvider {
         Self {
             handles: RefCell::new(Vec::new()),
             next_handle: RefCell::new(1),
+            brightness: RefCell::new(Brightness::FULL),
         }
     }
 }
@@ -51,7 +53,12 @@ impl OutputProvider for SyscallOutputProvider {
     }
 
     fn write(&self, handle: OutputChannelHandle, data: &[u8]) -> Result<(), OutputError> {
-        println!("[output] write: handle={:?}, len={}", handle, data.len());
+        println!(
+            "[output] write: handle={:?}, len={}, brightness={}",
+            handle,
+            data.len(),
+            self.brightness.borrow().to_f32()
+        );
         // TODO: Implement syscall for writing LED data to host
         // For now, just succeed
         Ok(())
@@ -63,4 +70,8 @@ impl OutputProvider for SyscallOutputProvider {
         // For now, just succeed
         Ok(())
     }
+
+    fn set_brightness(&self, brightness: f32) {
+        *self.brightness.borrow_mut() = Brightness::from_f32(brightness);
+    }
 }
@@ -20,6 +20,9 @@ pub fn init_board() -> (
     esp_hal::peripherals::RMT<'static>,
     esp_hal::peripherals::USB_DEVICE<'static>,
     esp_hal::peripherals::GPIO18<'static>,
+    esp_hal::peripherals::SPI2<'static>,
+    esp_hal::peripherals::GPIO20<'static>,
+    esp_hal::peripherals::GPIO21<'static>,
 ) {
     // Configure CPU clock to maximum speed (160MHz for ESP32-C6)
     let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
@@ -32,12 +35,15 @@ pub fn init_board() -> (
     let rmt = peripherals.RMT;
     let usb_device = peripherals.USB_DEVICE;
     let gpio18 = peripherals.GPIO18;
+    let spi2 = peripherals.SPI2;
+    let gpio20 = peripherals.GPIO20;
+    let gpio21 = peripherals.GPIO21;
 
     // Set up software interrupt and timer for Embassy runtime
     let sw_int = SoftwareInterruptControl::new(peripherals.SW_INTERRUPT);
     let timg0 = TimerGroup::new(peripherals.TIMG0);
 
-    (sw_int, timg0, rmt, usb_device, gpio18)
+    (sw_int, timg0, rmt, usb_device, gpio18, spi2, gpio20, gpio21)
 }
 
 /// Start Embassy runtime
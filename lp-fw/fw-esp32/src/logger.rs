@@ -1,7 +1,8 @@
 //! ESP32 logging module
 //!
-//! Provides logging functionality using USB serial directly. Uses our own USB serial instance
-//! that's shared with the transport layer.
+//! Log lines are handed to a write function; the firmware passes
+//! [`queue_log`](crate::serial::queue_log), so they share the I/O task's serial
+//! write queue with the transport and are dropped under back-pressure.
 
 extern crate alloc;
 
@@ -10,20 +11,19 @@ use core::sync::atomic::{AtomicPtr, Ordering};
 use fw_core::serial::SerialIo;
 use log::{Level, LevelFilter, Log, Metadata, Record};
 
-#[allow(dead_code, reason = "used in init function")]
-const LOG_LEVEL: LevelFilter = LevelFilter::Debug;
+/// Per-frame debug logs would crowd out protocol traffic, so serial gets info and up
+const LOG_LEVEL: LevelFilter = LevelFilter::Info;
 
 /// Initialize the ESP32 logger with a write function
 ///
 /// Call this once at startup after USB serial is initialized.
 /// The write function should write to your USB serial instance.
-#[allow(dead_code, reason = "public API reserved for future use")]
 pub fn init(write_fn: LogWriteFn) {
     unsafe {
         set_log_write_fn(write_fn);
     }
 
-    let logger = alloc::boxed::Box::new(Esp32Logger::new(LevelFilter::Debug));
+    let logger = alloc::boxed::Box::new(Esp32Logger::new(LOG_LEVEL));
     log::set_logger(alloc::boxed::Box::leak(logger))
         .map(|()| log::set_max_level(LOG_LEVEL))
         .expect("Failed to set ESP32 logger");
@@ -48,20 +48,17 @@ static ESP_PRINTLN_SERIAL: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut())
 ///
 /// # Safety
 /// The function pointer must remain valid for the lifetime of the program
-#[allow(dead_code, reason = "used internally by init function")]
 pub unsafe fn set_log_write_fn(write_fn: LogWriteFn) {
     LOG_WRITE_FN.store(write_fn as *mut (), Ordering::Release);
 }
 
-/// ESP32 logger that uses USB serial directly
-#[allow(dead_code, reason = "used internally by init function")]
+/// ESP32 logger that writes through the log write function
 pub struct Esp32Logger {
     max_level: LevelFilter,
 }
 
 impl Esp32Logger {
     /// Create a new ESP32 logger with the given max level
-    #[allow(dead_code, reason = "used internally by init function")]
     pub fn new(max_level: LevelFilter) -> Self {
         Self { max_level }
     }
@@ -31,9 +31,9 @@ use lp_server::LpServer;
 use lp_shared::fs::LpFsMemory;
 use lp_shared::output::OutputProvider;
 
-use output::Esp32OutputProvider;
-use serial::{get_message_channels, io_task};
-use server_loop::run_server_loop;
+use output::{Esp32OutputProvider, EspSpiSink, with_apa102};
+use serial::{get_message_channels, io_task, queue_log};
+use server_loop::{DEFAULT_FRAME_INTERVAL_MS, run_server_loop};
 use time::Esp32TimeProvider;
 
 #[cfg(feature = "test_rmt")]
@@ -77,7 +77,8 @@ async fn main(spawner: embassy_executor::Spawner) {
     {
         // Initialize board (clock, heap, runtime) and get hardware peripherals
         esp_println::println!("[INIT] Initializing board...");
-        let (sw_int, timg0, rmt_peripheral, usb_device, gpio18) = init_board();
+        let (sw_int, timg0, rmt_peripheral, usb_device, gpio18, spi2, gpio20, gpio21) =
+            init_board();
         esp_println::println!("[INIT] Board initialized, starting runtime...");
         start_runtime(timg0, sw_int);
         esp_println::println!("[INIT] Runtime started");
@@ -98,6 +99,9 @@ async fn main(spawner: embassy_executor::Spawner) {
         spawner.spawn(io_task(usb_device)).ok();
         esp_println::println!("[INIT] I/O task spawned");
 
+        // Route `log` output through the I/O task's write queue
+        logger::init(queue_log);
+
         // Queue LoadProject message to auto-load the demo project
         // This simulates what FakeTransport used to do
         // Send to incoming channel so server loop can receive it
@@ -140,6 +144,12 @@ async fn main(spawner: embassy_executor::Spawner) {
             .expect("Failed to initialize RMT channel");
         esp_println::println!("[INIT] RMT channel initialized");
 
+        // APA102 strips go out over SPI2, data on GPIO20 and clock on GPIO21
+        esp_println::println!("[INIT] Initializing SPI bus for APA102 on GPIO20/GPIO21...");
+        let spi_sink = EspSpiSink::new(spi2, gpio20, gpio21).expect("Failed to initialize SPI bus");
+        let output_provider = with_apa102(output_provider, spi_sink);
+        esp_println::println!("[INIT] SPI bus initialized");
+
         let output_provider: Rc<RefCell<dyn OutputProvider>> =
             Rc::new(RefCell::new(output_provider));
         esp_println::println!("[INIT] Output provider created");
@@ -170,6 +180,6 @@ async fn main(spawner: embassy_executor::Spawner) {
         esp_println::println!("[INIT] fw-esp32 initialized, starting server loop...");
 
         // Run server loop (never returns)
-        run_server_loop(server, transport, time_provider).await;
+        run_server_loop(server, transport, time_provider, DEFAULT_FRAME_INTERVAL_MS).await;
     }
 }
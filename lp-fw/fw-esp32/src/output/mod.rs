@@ -1,7 +1,9 @@
 mod provider;
 mod rmt;
+mod spi;
 
 pub use provider::Esp32OutputProvider;
+pub use spi::{EspSpiSink, with_apa102};
 // Public API - will be used when provider is updated
 #[allow(unused_imports, reason = "public API reserved for future use")]
 pub use rmt::{LedChannel, LedTransaction};
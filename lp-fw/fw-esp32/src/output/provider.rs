@@ -1,14 +1,21 @@
 //! ESP32 OutputProvider implementation
 //!
-//! Uses RMT driver for WS2811/WS2812 LED output.
+//! Uses RMT driver for WS2811/WS2812 LED output. Frames that fail to transmit are
+//! retried once, then the last good frame is restored (see [`TransmitGuard`]).
+//!
+//! APA102 strips are driven over SPI by the wrapping provider (see
+//! [`with_apa102`](crate::output::with_apa102)).
 
 extern crate alloc;
 
-use alloc::{collections::BTreeMap, format};
+use alloc::{collections::BTreeMap, format, vec::Vec};
 use core::cell::RefCell;
+use core::sync::atomic::{AtomicU32, Ordering};
 
 use lp_shared::OutputError;
-use lp_shared::output::{OutputChannelHandle, OutputFormat, OutputProvider};
+use lp_shared::output::{
+    Brightness, FrameTransmitter, OutputChannelHandle, OutputFormat, OutputProvider, TransmitGuard,
+};
 
 use crate::output::{LedChannel, LedTransaction};
 use esp_hal::Blocking;
@@ -30,6 +37,8 @@ struct ChannelState {
 // TODO: Refactor to support multiple channels and proper lifetime management
 static mut LED_CHANNEL: Option<LedChannel<'static>> = None;
 static mut CURRENT_TRANSACTION: Option<LedTransaction<'static>> = None;
+/// LEDs the RMT channel was sized for in `init_rmt` (data past this is dropped)
+static LED_CAPACITY: AtomicU32 = AtomicU32::new(0);
 
 /// ESP32 OutputProvider implementation using RMT driver
 pub struct Esp32OutputProvider {
@@ -39,6 +48,57 @@ pub struct Esp32OutputProvider {
     open_pins: RefCell<alloc::collections::BTreeSet<u32>>,
     /// Next handle ID to assign
     next_handle: RefCell<i32>,
+    /// Master brightness applied to all output
+    brightness: RefCell<Brightness>,
+    /// Scratch buffer for brightness-scaled data
+    scaled: RefCell<Vec<u8>>,
+    /// Retries failed frames and restores the last good one
+    transmit_guard: RefCell<TransmitGuard>,
+}
+
+/// Transmits frames on the shared RMT channel
+struct RmtTransmitter;
+
+/// A frame ended with an RMT error
+#[derive(Debug)]
+struct RmtTransmitError;
+
+impl FrameTransmitter for RmtTransmitter {
+    type Error = RmtTransmitError;
+
+    fn transmit(&mut self, frame: &[u8]) -> Result<(), RmtTransmitError> {
+        unsafe {
+            let tx_ptr = core::ptr::addr_of_mut!(CURRENT_TRANSACTION);
+            let channel_ptr = core::ptr::addr_of_mut!(LED_CHANNEL);
+
+            // Wait for any previous transaction to complete
+            if let Some(tx) = (*tx_ptr).take() {
+                log::debug!("RmtTransmitter::transmit: Waiting for previous transaction");
+                let channel = tx.wait_complete();
+                (*channel_ptr) = Some(channel);
+            }
+
+            // The provider checks the channel is initialized before transmitting
+            let Some(channel) = (*channel_ptr).take() else {
+                return Err(RmtTransmitError);
+            };
+            log::debug!(
+                "RmtTransmitter::transmit: Starting transmission, {} bytes",
+                frame.len()
+            );
+            let tx = channel.start_transmission(frame);
+            // Wait for transmission to complete (write() is synchronous)
+            let channel = tx.wait_complete();
+            let failed = channel.transmit_failed();
+            (*channel_ptr) = Some(channel);
+            if failed {
+                Err(RmtTransmitError)
+            } else {
+                log::debug!("RmtTransmitter::transmit: Transmission complete");
+                Ok(())
+            }
+        }
+    }
 }
 
 impl Esp32OutputProvider {
@@ -56,6 +116,9 @@ impl Esp32OutputProvider {
             channels: RefCell::new(BTreeMap::new()),
             open_pins: RefCell::new(alloc::collections::BTreeSet::new()),
             next_handle: RefCell::new(1),
+            brightness: RefCell::new(Brightness::FULL),
+            scaled: RefCell::new(Vec::new()),
+            transmit_guard: RefCell::new(TransmitGuard::new()),
         }
     }
 
@@ -78,6 +141,7 @@ impl Esp32OutputProvider {
             let channel = LedChannel::new(rmt, pin, num_leds)?;
             (*channel_ptr) = Some(core::mem::transmute(channel));
         }
+        LED_CAPACITY.store(num_leds as u32, Ordering::Release);
         Ok(())
     }
 }
@@ -100,11 +164,28 @@ impl OutputProvider for Esp32OutputProvider {
         }
 
         // Validate format
-        if format != OutputFormat::Ws2811 {
-            log::warn!("Esp32OutputProvider::open: Unsupported format: {format:?}");
-            return Err(OutputError::InvalidConfig {
-                reason: format!("Unsupported format: {format:?}"),
-            });
+        match format {
+            OutputFormat::Ws2811 => {}
+            // The SPI wrapper takes APA102 strips on its bus pins; anything that
+            // reaches here asked for pins the bus isn't wired to
+            OutputFormat::Apa102 { clock_pin, .. } => {
+                log::warn!(
+                    "Esp32OutputProvider::open: APA102 on data pin {pin}, clock pin {clock_pin} is not on the SPI bus"
+                );
+                return Err(OutputError::InvalidConfig {
+                    reason: format!(
+                        "APA102 strips must use data pin {} and clock pin {}",
+                        crate::output::spi::APA102_DATA_PIN,
+                        crate::output::spi::APA102_CLOCK_PIN
+                    ),
+                });
+            }
+            OutputFormat::ArtNet { .. } => {
+                log::warn!("Esp32OutputProvider::open: Unsupported format: {format:?}");
+                return Err(OutputError::InvalidConfig {
+                    reason: format!("Unsupported format: {format:?}"),
+                });
+            }
         }
 
         // Calculate number of LEDs (WS2811 = 3 bytes per LED)
@@ -204,38 +285,35 @@ impl OutputProvider for Esp32OutputProvider {
             });
         }
 
-        // Use LedChannel to send data
-        unsafe {
-            let tx_ptr = core::ptr::addr_of_mut!(CURRENT_TRANSACTION);
-            let channel_ptr = core::ptr::addr_of_mut!(LED_CHANNEL);
-
-            // Wait for any previous transaction to complete
-            if let Some(tx) = (*tx_ptr).take() {
-                log::debug!("Esp32OutputProvider::write: Waiting for previous transaction");
-                let channel = tx.wait_complete();
-                (*channel_ptr) = Some(channel);
-            }
+        // Apply master brightness (brightness 0 still transmits, all off)
+        let brightness = *self.brightness.borrow();
+        let mut scaled = self.scaled.borrow_mut();
+        let data = if brightness.is_full() {
+            data
+        } else {
+            scaled.clear();
+            scaled.extend_from_slice(data);
+            brightness.apply(&mut scaled);
+            &scaled[..]
+        };
 
-            // Get the channel and start transmission
-            if let Some(channel) = (*channel_ptr).take() {
-                log::debug!(
-                    "Esp32OutputProvider::write: Starting transmission, {} bytes",
-                    data.len()
-                );
-                let tx = channel.start_transmission(data);
-                // Wait for transmission to complete (write() is synchronous)
-                log::debug!("Esp32OutputProvider::write: Waiting for transmission to complete");
-                let channel = tx.wait_complete();
-                (*channel_ptr) = Some(channel);
-                log::debug!("Esp32OutputProvider::write: Transmission complete");
-                Ok(())
-            } else {
+        unsafe {
+            let channel_ptr = core::ptr::addr_of!(LED_CHANNEL);
+            let tx_ptr = core::ptr::addr_of!(CURRENT_TRANSACTION);
+            if (*channel_ptr).is_none() && (*tx_ptr).is_none() {
                 log::error!("Esp32OutputProvider::write: RMT channel not initialized");
-                Err(OutputError::InvalidConfig {
+                return Err(OutputError::InvalidConfig {
                     reason: "RMT channel not initialized".into(),
-                })
+                });
             }
         }
+
+        // Send the frame, retrying once; a repeated failure leaves the last good
+        // frame on the strip and is reported to the output node
+        self.transmit_guard
+            .borrow_mut()
+            .transmit(&mut RmtTransmitter, data)
+            .inspect_err(|e| log::error!("Esp32OutputProvider::write: {e}"))
     }
 
     fn close(&self, handle: OutputChannelHandle) -> Result<(), OutputError> {
@@ -255,4 +333,16 @@ impl OutputProvider for Esp32OutputProvider {
 
         Ok(())
     }
+
+    fn set_brightness(&self, brightness: f32) {
+        *self.brightness.borrow_mut() = Brightness::from_f32(brightness);
+    }
+
+    fn max_byte_count(&self, _pin: u32, _format: OutputFormat) -> Option<u32> {
+        // WS2811 = 3 bytes per LED
+        match LED_CAPACITY.load(Ordering::Acquire) {
+            0 => None,
+            num_leds => Some(num_leds * 3),
+        }
+    }
 }
@@ -210,6 +210,15 @@ impl<'ch> LedChannel<'ch> {
 
         LedTransaction { channel: self }
     }
+
+    /// Check whether the last transmission ended with an RMT error
+    ///
+    /// A failed frame may have reached only part of the strip.
+    pub fn transmit_failed(&self) -> bool {
+        CHANNEL_STATE[self.channel_idx as usize]
+            .tx_error
+            .load(Ordering::Acquire)
+    }
 }
 
 impl<'ch> LedTransaction<'ch> {
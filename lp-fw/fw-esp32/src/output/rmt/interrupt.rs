@@ -45,6 +45,9 @@ pub(crate) unsafe fn start_transmission_with_state(
     CHANNEL_STATE[ch_idx]
         .frame_complete
         .store(false, Ordering::Release);
+    CHANNEL_STATE[ch_idx]
+        .tx_error
+        .store(false, Ordering::Release);
     CHANNEL_STATE[ch_idx]
         .led_counter
         .store(0, Ordering::Relaxed);
@@ -124,7 +127,7 @@ pub(crate) unsafe fn start_transmission_with_state(
 /// This handler manages double-buffered transmission by:
 /// 1. Handling threshold interrupts to write the next half-buffer
 /// 2. Handling end interrupts to signal frame completion
-/// 3. Handling error interrupts (currently just logged)
+/// 3. Handling error interrupts by stopping the frame and flagging it as failed
 #[unsafe(no_mangle)]
 pub(crate) extern "C" fn rmt_interrupt_handler() {
     unsafe {
@@ -144,7 +147,15 @@ pub(crate) extern "C" fn rmt_interrupt_handler() {
         });
 
         if is_err_int {
-            // Error interrupt - could log here if needed
+            // Error interrupt - stop the frame and flag it, so the waiter doesn't hang
+            // and the provider can retry it
+            rmt.ch_tx_conf0(0).modify(|_, w| w.tx_stop().set_bit());
+            CHANNEL_STATE[RMT_CH_IDX]
+                .tx_error
+                .store(true, Ordering::Release);
+            CHANNEL_STATE[RMT_CH_IDX]
+                .frame_complete
+                .store(true, Ordering::Release);
         }
         // End interrupt
         else if is_end_int {
@@ -11,6 +11,9 @@ use smart_leds::RGB8;
 pub(crate) struct ChannelState {
     /// Flag indicating if the current frame transmission is complete
     pub(crate) frame_complete: AtomicBool,
+    /// Flag set when the current frame ended with an RMT error (the strip may only
+    /// have received part of it)
+    pub(crate) tx_error: AtomicBool,
     /// Current LED position in the transmission
     pub(crate) led_counter: AtomicUsize,
     /// Counter for completed frames
@@ -29,6 +32,7 @@ impl ChannelState {
     const fn new() -> Self {
         Self {
             frame_complete: AtomicBool::new(true),
+            tx_error: AtomicBool::new(false),
             led_counter: AtomicUsize::new(0),
             frame_counter: AtomicUsize::new(0),
             stats_count: AtomicI32::new(0),
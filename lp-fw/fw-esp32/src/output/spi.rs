@@ -0,0 +1,58 @@
+//! SPI bus for APA102/SK9822 LED output
+//!
+//! The shared [`Apa102OutputProvider`] frames the data; this module supplies the
+//! [`SpiSink`] that writes it out over the SPI2 peripheral.
+
+use esp_hal::Blocking;
+use esp_hal::gpio::interconnect::PeripheralOutput;
+use esp_hal::spi::Mode;
+use esp_hal::spi::master::{Config, ConfigError, Spi};
+use esp_hal::time::Rate;
+use lp_shared::output::apa102::{Apa102OutputProvider, SpiSink};
+
+use crate::output::Esp32OutputProvider;
+
+/// GPIO the APA102 data line is wired to
+pub const APA102_DATA_PIN: u32 = 20;
+
+/// GPIO the APA102 clock line is wired to
+pub const APA102_CLOCK_PIN: u32 = 21;
+
+/// SPI clock rate (APA102 strips are reliable up to several MHz over short runs)
+const SPI_FREQUENCY_MHZ: u32 = 4;
+
+/// [`SpiSink`] backed by a blocking esp-hal SPI master
+pub struct EspSpiSink {
+    spi: Spi<'static, Blocking>,
+}
+
+impl EspSpiSink {
+    /// Configure the SPI2 peripheral to drive an APA102 strip on `data` and `clock`
+    pub fn new(
+        spi: esp_hal::peripherals::SPI2<'static>,
+        data: impl PeripheralOutput<'static>,
+        clock: impl PeripheralOutput<'static>,
+    ) -> Result<Self, ConfigError> {
+        let config = Config::default()
+            .with_frequency(Rate::from_mhz(SPI_FREQUENCY_MHZ))
+            .with_mode(Mode::_0);
+        let spi = Spi::new(spi, config)?.with_mosi(data).with_sck(clock);
+        Ok(Self { spi })
+    }
+}
+
+impl SpiSink for EspSpiSink {
+    type Error = esp_hal::spi::Error;
+
+    fn write(&mut self, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.spi.write(bytes)
+    }
+}
+
+/// ESP32 output: APA102 strips over SPI, everything else through the RMT provider
+pub type Esp32LedOutputProvider = Apa102OutputProvider<Esp32OutputProvider, EspSpiSink>;
+
+/// Wrap `inner` so APA102 channels on the SPI bus are driven by `sink`
+pub fn with_apa102(inner: Esp32OutputProvider, sink: EspSpiSink) -> Esp32LedOutputProvider {
+    Apa102OutputProvider::new(inner, sink, APA102_DATA_PIN, APA102_CLOCK_PIN)
+}
@@ -1,7 +1,8 @@
 //! I/O task for handling serial communication
 //!
 //! Responsibilities:
-//! - Drain outgoing queue and send via serial (with M! prefix)
+//! - Drain outgoing messages and logs into a [`SerialWriteQueue`], then write a
+//!   bounded number of bytes per iteration (logs are dropped under back-pressure)
 //! - Read from serial and push to incoming queue (filter M! prefix)
 //! - Handle serial state (Ready/Disconnected/Error)
 //! - Retry serial initialization if disconnected
@@ -15,12 +16,26 @@ use embassy_time::{Duration, Timer};
 use embedded_io_async::{Read, Write};
 use esp_hal::usb_serial_jtag::UsbSerialJtag;
 use fw_core::message_router::MessageRouter;
+use fw_core::serial::SerialWriteQueue;
 use log;
 
 /// Static message channels for MessageRouter
 static INCOMING_MSG: Channel<CriticalSectionRawMutex, String, 32> = Channel::new();
 static OUTGOING_MSG: Channel<CriticalSectionRawMutex, String, 32> = Channel::new();
 
+/// Log messages waiting for the I/O task
+static OUTGOING_LOG: Channel<CriticalSectionRawMutex, String, 32> = Channel::new();
+
+/// Bytes written per I/O loop iteration, so a slow host can't starve reads
+const MAX_WRITE_BYTES_PER_ITERATION: usize = 512;
+
+/// Queue a log message for the I/O task (a [`LogWriteFn`](crate::logger::LogWriteFn))
+///
+/// Never blocks: the message is dropped if the log channel is full.
+pub fn queue_log(message: &str) {
+    let _ = OUTGOING_LOG.try_send(String::from(message));
+}
+
 /// I/O task for handling serial communication
 ///
 /// This task runs independently of the main loop and handles all serial I/O.
@@ -43,30 +58,37 @@ pub async fn io_task(usb_device: esp_hal::peripherals::USB_DEVICE<'static>) {
     Timer::after(Duration::from_millis(100)).await;
 
     let mut read_buffer = Vec::new();
+    let mut write_queue = SerialWriteQueue::default();
 
     // Main I/O loop
     loop {
-        // Drain outgoing queue and send via serial
-        let outgoing = router.outgoing();
-        let receiver = outgoing.receiver();
-
-        loop {
-            match receiver.try_receive() {
-                Ok(msg) => {
-                    // Message already has M! prefix from MessageRouterTransport
-                    // Send via serial (handle errors gracefully)
-                    if Write::write(&mut tx, msg.as_bytes()).await.is_err() {
-                        // Write error - USB may be disconnected, continue
-                        break;
-                    }
-                    // Flush after each message
-                    let _ = Write::flush(&mut tx).await;
-                }
-                Err(_) => {
-                    // No messages - break to read
-                    break;
-                }
+        // Move outgoing messages and logs into the write queue
+        let receiver = router.outgoing().receiver();
+        while let Ok(msg) = receiver.try_receive() {
+            // Messages arrive framed as `M!<json>\n` from MessageRouterTransport
+            let payload = msg.strip_prefix("M!").unwrap_or(&msg).trim_end();
+            write_queue.push_protocol(payload);
+        }
+        while let Ok(msg) = OUTGOING_LOG.try_receive() {
+            write_queue.push_log(&msg);
+        }
+
+        // Write a bounded amount of the queue
+        let mut written = 0;
+        while written < MAX_WRITE_BYTES_PER_ITERATION {
+            let Some(frame) = write_queue.front_frame() else {
+                break;
+            };
+            if Write::write_all(&mut tx, frame).await.is_err() {
+                // Write error - USB may be disconnected; the frame stays queued for
+                // the next iteration
+                break;
             }
+            written += frame.len();
+            write_queue.pop_frame();
+        }
+        if written > 0 {
+            let _ = Write::flush(&mut tx).await;
         }
 
         // Read from serial (non-blocking with timeout)
@@ -27,7 +27,7 @@ extern crate alloc;
 use alloc::vec::Vec;
 use lp_model::Message;
 use lp_server::LpServer;
-use lp_shared::time::TimeProvider;
+use lp_shared::time::{FramePacer, TimeProvider};
 use lp_shared::transport::ServerTransport;
 
 use crate::time::Esp32TimeProvider;
@@ -35,6 +35,9 @@ use crate::time::Esp32TimeProvider;
 /// FPS logging interval (log every N frames)
 const FPS_LOG_INTERVAL: u32 = 60;
 
+/// Default target frame interval in milliseconds (~60 FPS)
+pub const DEFAULT_FRAME_INTERVAL_MS: u64 = 16;
+
 /// Heartbeat message interval (send every N milliseconds)
 const HEARTBEAT_INTERVAL_MS: u64 = 1000; // Send every second
 
@@ -47,13 +50,15 @@ const HEARTBEAT_MESSAGE_ID: u64 = 0;
 /// Run the server loop
 ///
 /// This is the main async loop that processes incoming messages and sends responses.
-/// Runs at ~60 FPS to maintain consistent frame timing.
-/// Yields control back to Embassy runtime between iterations.
+/// Frames are paced to `target_frame_interval_ms` (0 runs flat out); the loop sleeps
+/// between frames, yielding control back to the Embassy runtime.
 pub async fn run_server_loop<T: ServerTransport>(
     mut server: LpServer,
     mut transport: T,
     time_provider: Esp32TimeProvider,
+    target_frame_interval_ms: u64,
 ) -> ! {
+    let mut pacer = FramePacer::new(target_frame_interval_ms);
     let mut last_tick = time_provider.now_ms();
     let mut frame_count = 0u32;
     let mut fps_last_log_time = time_provider.now_ms();
@@ -62,6 +67,7 @@ pub async fn run_server_loop<T: ServerTransport>(
 
     loop {
         let frame_start = time_provider.now_ms();
+        pacer.begin_frame(frame_start);
 
         // Collect incoming messages (non-blocking)
         let mut incoming_messages = Vec::new();
@@ -114,7 +120,11 @@ pub async fn run_server_loop<T: ServerTransport>(
             let elapsed_ms = current_time.saturating_sub(fps_last_log_time);
             if elapsed_ms > 0 {
                 let fps = (FPS_LOG_INTERVAL as u64 * 1000) / elapsed_ms;
-                log::info!("FPS: {fps} (frame_count: {frame_count}, elapsed: {elapsed_ms}ms)");
+                log::info!(
+                    "FPS: {fps} (target: {}, overruns: {}, frame_count: {frame_count}, elapsed: {elapsed_ms}ms)",
+                    pacer.target_fps(),
+                    pacer.overruns()
+                );
                 fps_last_log_time = current_time;
             }
         }
@@ -163,8 +173,9 @@ pub async fn run_server_loop<T: ServerTransport>(
             heartbeat_last_sent = current_time;
         }
 
-        // Yield to Embassy runtime (allows other tasks to run)
-        // Use embassy_time::Timer to delay slightly
-        embassy_time::Timer::after(embassy_time::Duration::from_millis(1)).await;
+        // Sleep until the next frame is due, yielding to the Embassy runtime
+        // (allows other tasks to run). Always yield at least briefly.
+        let sleep_ms = pacer.end_frame(time_provider.now_ms()).max(1);
+        embassy_time::Timer::after(embassy_time::Duration::from_millis(sleep_ms)).await;
     }
 }
@@ -21,7 +21,7 @@ use fw_core::serial::SerialIo;
 /// Displays simple patterns on LEDs to verify RMT driver works.
 pub async fn run_rmt_test() -> ! {
     // Initialize board (clock, heap, runtime) and get hardware peripherals
-    let (sw_int, timg0, rmt_peripheral, usb_device, gpio18) = init_board();
+    let (sw_int, timg0, rmt_peripheral, usb_device, gpio18, ..) = init_board();
     start_runtime(timg0, sw_int);
 
     // Initialize USB-serial for logging (synchronous mode)
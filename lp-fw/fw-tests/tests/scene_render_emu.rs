@@ -14,7 +14,7 @@ use lp_engine_client::ClientProjectView;
 use lp_model::{AsLpPath, FrameId};
 use lp_riscv_elf::load_elf;
 use lp_riscv_emu::{
-    LogLevel, Riscv32Emulator, TimeMode,
+    LogLevel, TimeMode,
     test_util::{BinaryBuildConfig, ensure_binary_built},
 };
 use lp_riscv_inst::Gpr;
@@ -44,7 +44,9 @@ async fn test_scene_render_fw_emu() {
 
     // Create emulator with simulated time mode
     let ram_size = load_info.ram.len();
-    let mut emulator = Riscv32Emulator::new(load_info.code, load_info.ram)
+    let entry_point = load_info.entry_point;
+    let mut emulator = load_info
+        .into_emulator()
         .with_log_level(LogLevel::None)
         .with_max_instructions(10_000_000)
         .with_time_mode(TimeMode::Simulated(0));
@@ -54,7 +56,7 @@ async fn test_scene_render_fw_emu() {
     emulator.set_register(Gpr::Sp, sp_value as i32);
 
     // Set PC to entry point
-    emulator.set_pc(load_info.entry_point);
+    emulator.set_pc(entry_point);
 
     // Create shared emulator reference
     let emulator_arc = Arc::new(Mutex::new(emulator));
@@ -23,6 +23,7 @@ use cranelift_codegen::isa::riscv32::isa_builder;
 use cranelift_codegen::settings::{self, Configurable};
 use lp_glsl_builtins::glsl::q32::types::q32::Q32;
 use lp_glsl_compiler::Compiler;
+use lp_glsl_compiler::OptLevel;
 use lp_glsl_compiler::backend::transform::q32::{FixedPointFormat, Q32Transform};
 use target_lexicon::Triple;
 
@@ -31,6 +32,10 @@ use lp_glsl_compiler::backend::codegen::jit::build_jit_executable_memory_optimiz
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
+/// Cranelift optimization level for shader compilation.
+/// Higher levels produce faster code at the cost of compile time and heap usage.
+const OPT_LEVEL: OptLevel = OptLevel::None;
+
 /// Print memory usage statistics with a descriptive label
 fn print_memory_stats(label: &str) {
     println!("\n=== Memory Stats: {} ===", label);
@@ -175,7 +180,7 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
     // Create RISC-V32 ISA
     println!("Step 1: Creating RISC-V32 ISA...");
     let mut flag_builder = settings::builder();
-    flag_builder.set("opt_level", "none").unwrap();
+    flag_builder.set("opt_level", OPT_LEVEL.as_str()).unwrap();
     flag_builder.set("is_pic", "false").unwrap();
     flag_builder.set("enable_verifier", "false").unwrap();
     flag_builder
@@ -215,6 +220,7 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
         arch: None, // Auto-detect from host (ESP32 = RISC-V32)
         flags,
         isa: None, // Will be created from target
+        schedule: false,
     };
 
     // Compile to JIT module
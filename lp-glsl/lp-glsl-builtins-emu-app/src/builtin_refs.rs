@@ -60,6 +60,8 @@ use lp_glsl_builtins::builtins::lpfx::generative::srandom::srandom3_tile_f32::__
 use lp_glsl_builtins::builtins::lpfx::generative::srandom::srandom3_tile_q32::__lpfx_srandom3_tile_q32;
 use lp_glsl_builtins::builtins::lpfx::generative::srandom::srandom3_vec_f32::__lpfx_srandom3_vec_f32;
 use lp_glsl_builtins::builtins::lpfx::generative::srandom::srandom3_vec_q32::__lpfx_srandom3_vec_q32;
+use lp_glsl_builtins::builtins::lpfx::generative::vnoise::vnoise2_f32::__lpfx_vnoise2_f32;
+use lp_glsl_builtins::builtins::lpfx::generative::vnoise::vnoise2_q32::__lpfx_vnoise2_q32;
 use lp_glsl_builtins::builtins::lpfx::generative::worley::worley2_f32::__lpfx_worley2_f32;
 use lp_glsl_builtins::builtins::lpfx::generative::worley::worley2_q32::__lpfx_worley2_q32;
 use lp_glsl_builtins::builtins::lpfx::generative::worley::worley2_value_f32::__lpfx_worley2_value_f32;
@@ -245,6 +247,8 @@ pub fn ensure_builtins_referenced() {
             __lpfx_srandom3_vec_f32;
         let __lpfx_srandom3_vec_q32_fn: extern "C" fn(*mut i32, i32, i32, i32, u32) -> () =
             __lpfx_srandom3_vec_q32;
+        let __lpfx_vnoise2_f32_fn: extern "C" fn(f32, f32, u32) -> f32 = __lpfx_vnoise2_f32;
+        let __lpfx_vnoise2_q32_fn: extern "C" fn(i32, i32, u32) -> i32 = __lpfx_vnoise2_q32;
         let __lpfx_worley2_f32_fn: extern "C" fn(f32, f32, u32) -> f32 = __lpfx_worley2_f32;
         let __lpfx_worley2_q32_fn: extern "C" fn(i32, i32, u32) -> i32 = __lpfx_worley2_q32;
         let __lpfx_worley2_value_f32_fn: extern "C" fn(f32, f32, u32) -> f32 =
@@ -348,6 +352,8 @@ pub fn ensure_builtins_referenced() {
         let _ = core::ptr::read_volatile(&__lpfx_srandom3_tile_q32_fn as *const _);
         let _ = core::ptr::read_volatile(&__lpfx_srandom3_vec_f32_fn as *const _);
         let _ = core::ptr::read_volatile(&__lpfx_srandom3_vec_q32_fn as *const _);
+        let _ = core::ptr::read_volatile(&__lpfx_vnoise2_f32_fn as *const _);
+        let _ = core::ptr::read_volatile(&__lpfx_vnoise2_q32_fn as *const _);
         let _ = core::ptr::read_volatile(&__lpfx_worley2_f32_fn as *const _);
         let _ = core::ptr::read_volatile(&__lpfx_worley2_q32_fn as *const _);
         let _ = core::ptr::read_volatile(&__lpfx_worley2_value_f32_fn as *const _);
@@ -4,4 +4,5 @@ pub mod psrdnoise;
 pub mod random;
 pub mod snoise;
 pub mod srandom;
+pub mod vnoise;
 pub mod worley;
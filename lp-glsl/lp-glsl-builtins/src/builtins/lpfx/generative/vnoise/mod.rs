@@ -0,0 +1,2 @@
+pub mod vnoise2_f32;
+pub mod vnoise2_q32;
@@ -0,0 +1,123 @@
+//! 2D Value Noise function.
+//!
+//! Hashes the integer lattice coordinates of each grid cell corner to a value in [0, 1) and
+//! interpolates between them with quintic smoothing. Unlike `lpfx_gnoise`, the corner values
+//! come from the integer hash rather than a sin-based random, so results are exact and
+//! identical on every target, and the quintic fade keeps the noise smooth across cell edges.
+
+use crate::builtins::lpfx::hash::lpfx_hash2;
+use crate::glsl::q32::fns::{mix_q32, quintic_vec2};
+use crate::glsl::q32::types::q32::Q32;
+use crate::glsl::q32::types::vec2_q32::Vec2Q32;
+
+/// 2D Value Noise function
+///
+/// # Arguments
+/// * `p` - Input coordinates as Vec2Q32
+/// * `seed` - Seed value for randomization
+///
+/// # Returns
+/// Noise value in [0, 1] range as Q32
+#[inline(always)]
+pub fn lpfx_vnoise2(p: Vec2Q32, seed: u32) -> Q32 {
+    // i = floor(p), f = fract(p)
+    let i = p.floor();
+    let f = p.fract();
+    let ix = i.x.to_i32() as u32;
+    let iy = i.y.to_i32() as u32;
+
+    // Sample corners
+    let a = lattice_value(ix, iy, seed);
+    let b = lattice_value(ix.wrapping_add(1), iy, seed);
+    let c = lattice_value(ix, iy.wrapping_add(1), seed);
+    let d = lattice_value(ix.wrapping_add(1), iy.wrapping_add(1), seed);
+
+    // Interpolate using quintic smoothing
+    let u = quintic_vec2(f);
+    mix_q32(mix_q32(a, b, u.x), mix_q32(c, d, u.x), u.y)
+}
+
+/// Value at a lattice point, in [0, 1)
+///
+/// The top 16 bits of the hash are used directly as the Q16.16 fraction.
+#[inline(always)]
+fn lattice_value(x: u32, y: u32, seed: u32) -> Q32 {
+    Q32::from_fixed((lpfx_hash2(x, y, seed) >> 16) as i32)
+}
+
+/// 2D Value Noise function (extern C wrapper for compiler).
+///
+/// # Arguments
+/// * `x` - X coordinate as i32 (Q32 fixed-point)
+/// * `y` - Y coordinate as i32 (Q32 fixed-point)
+/// * `seed` - Seed value for randomization
+///
+/// # Returns
+/// Noise value in [0, 1] range as i32 (Q32 fixed-point format)
+#[lpfx_impl_macro::lpfx_impl(q32, "float lpfx_vnoise(vec2 p, uint seed)")]
+#[unsafe(no_mangle)]
+pub extern "C" fn __lpfx_vnoise2_q32(x: i32, y: i32, seed: u32) -> i32 {
+    lpfx_vnoise2(Vec2Q32::new(Q32::from_fixed(x), Q32::from_fixed(y)), seed).to_fixed()
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(test)]
+    extern crate std;
+    use super::*;
+
+    fn vnoise(x: f32, y: f32, seed: u32) -> f32 {
+        let result = __lpfx_vnoise2_q32(
+            Q32::from_f32(x).to_fixed(),
+            Q32::from_f32(y).to_fixed(),
+            seed,
+        );
+        Q32::from_fixed(result).to_f32()
+    }
+
+    #[test]
+    fn test_vnoise2_range() {
+        for i in 0..200 {
+            let x = i as f32 * 0.37 - 30.0;
+            let y = i as f32 * 0.21 - 15.0;
+            let val = vnoise(x, y, 123);
+            assert!(
+                (0.0..=1.0).contains(&val),
+                "Vnoise should be in [0, 1] range, got {val} at ({x}, {y})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_vnoise2_deterministic() {
+        for i in 0..50 {
+            let x = i as f32 * 1.13 - 20.0;
+            let y = i as f32 * 0.77 + 3.0;
+            assert_eq!(vnoise(x, y, 7), vnoise(x, y, 7));
+        }
+        // Seeds select different noise fields
+        let differs =
+            (0..10).any(|i| vnoise(i as f32 + 0.5, 0.5, 0) != vnoise(i as f32 + 0.5, 0.5, 1));
+        assert!(differs, "Different seeds should produce different noise");
+    }
+
+    #[test]
+    fn test_vnoise2_smooth() {
+        // The quintic fade has a maximum slope of 15/8, so a step of 1/256 changes the
+        // output by at most ~0.0073, including across cell boundaries
+        let step = 1.0 / 256.0;
+        for row in 0..8 {
+            let y = row as f32 * 0.61 - 2.0;
+            let mut prev = vnoise(-3.0, y, 42);
+            for i in 1..(6 * 256) {
+                let x = -3.0 + i as f32 * step;
+                let val = vnoise(x, y, 42);
+                assert!(
+                    (val - prev).abs() < 0.01,
+                    "Vnoise jumped from {prev} to {val} at ({x}, {y})"
+                );
+                prev = val;
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Fixed-point 16.16 cosine function.
 
-use super::sin::__lp_q32_sin;
+use super::sin::{__lp_q32_sin, reduce_angle};
 
 /// Fixed-point value of π (Q16.16 format)
 const FIX16_PI: i32 = 205887;
@@ -8,12 +8,12 @@ const FIX16_PI: i32 = 205887;
 /// Compute cosine using sine: cos(x) = sin(x + π/2)
 ///
 /// Algorithm ported from libfixmath.
-/// Accuracy: ~2.1% (same as sin)
+/// Accuracy: absolute error below 0.0001 (same as sin)
 #[unsafe(no_mangle)]
 pub extern "C" fn __lp_q32_cos(x: i32) -> i32 {
-    // cos(x) = sin(x + π/2)
+    // cos(x) = sin(x + π/2), reducing first so the shift cannot overflow
     let half_pi = FIX16_PI >> 1;
-    __lp_q32_sin(x + half_pi)
+    __lp_q32_sin(reduce_angle(x) + half_pi)
 }
 
 #[cfg(test)]
@@ -57,4 +57,31 @@ mod tests {
 
         test_q32_function_relative(|x| __lp_q32_cos(x), &tests, 0.03, 0.01);
     }
+
+    #[test]
+    fn test_cos_full_period() {
+        let steps = 10_000;
+        for i in 0..=steps {
+            let x = (core::f64::consts::TAU * i as f64 / steps as f64 * 65536.0) as i32;
+            let expected = (x as f64 / 65536.0).cos();
+            let actual = __lp_q32_cos(x) as f64 / 65536.0;
+            assert!(
+                (actual - expected).abs() < 0.0001,
+                "cos({x}): expected {expected}, got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cos_extreme_inputs() {
+        // Must not overflow when shifting by π/2
+        for x in [i32::MAX, i32::MAX - 1000, i32::MIN] {
+            let expected = (x as f64 / 65536.0).cos();
+            let actual = __lp_q32_cos(x) as f64 / 65536.0;
+            assert!(
+                (actual - expected).abs() < 0.0001,
+                "cos({x}): expected {expected}, got {actual}"
+            );
+        }
+    }
 }
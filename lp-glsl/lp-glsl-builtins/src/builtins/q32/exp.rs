@@ -45,7 +45,9 @@ pub extern "C" fn __lp_q32_exp(x: i32) -> i32 {
         // Convert i to fixed point for division
         let i_fixed = i << 16;
         term = __lp_q32_mul(term, __lp_q32_div(in_value, i_fixed));
-        result += term;
+        // Saturate: near the limits (and for |x| of large negative inputs) the sum can
+        // exceed the Q16.16 range
+        result = result.saturating_add(term);
 
         // Early termination if term becomes small enough
         if (term < 500) && ((i > 15) || (term < 20)) {
@@ -81,4 +83,30 @@ mod tests {
         // Use 3% tolerance for exponential functions
         test_q32_function_relative(|x| __lp_q32_exp(x), &tests, 0.03, 0.01);
     }
+
+    #[test]
+    fn test_exp_against_float() {
+        // exp(x) for x in [-10.7, 10.39], up to the edge of the Q16.16 range
+        for i in 0..20_000 {
+            let x = -700_000 + i * 68;
+            let expected = (x as f64 / 65536.0).exp();
+            let actual = __lp_q32_exp(x) as f64 / 65536.0;
+            assert!(
+                (actual - expected).abs() < 0.0001 * expected.max(1.0),
+                "exp({x}): expected {expected}, got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_exp_edge_cases() {
+        // Overflow saturates, underflow goes to zero
+        assert_eq!(__lp_q32_exp(FIX16_MAX_EXP), i32::MAX);
+        assert_eq!(__lp_q32_exp(i32::MAX), i32::MAX);
+        assert_eq!(__lp_q32_exp(FIX16_MIN_EXP), 0);
+        assert_eq!(__lp_q32_exp(i32::MIN), 0);
+        // Just inside the limits (previously overflowed while summing the series)
+        assert!(__lp_q32_exp(FIX16_MAX_EXP - 1) > 32_000 << 16);
+        assert!(__lp_q32_exp(FIX16_MIN_EXP + 1) >= 0);
+    }
 }
@@ -2,10 +2,11 @@
 
 use super::exp::__lp_q32_exp;
 use crate::builtins::q32::div::__lp_q32_div;
-use crate::builtins::q32::mul::__lp_q32_mul;
 
 /// Fixed-point value of 1.0 (Q16.16 format)
 const FIX16_ONE: i32 = 0x00010000; // 65536
+/// ln(2) with 32 fractional bits
+const LN_2_Q32: i64 = 2_977_044_472;
 /// Minimum representable value (used for log(0) or negative)
 const FIX16_MINIMUM: i32 = i32::MIN;
 
@@ -13,6 +14,9 @@ const FIX16_MINIMUM: i32 = i32::MIN;
 ///
 /// Algorithm ported from libfixmath.
 /// Uses iterative refinement: solving e(guess) = x using Newton's method.
+/// Accuracy: absolute error below 0.0001
+///
+/// Returns `i32::MIN` (standing in for -infinity) for zero and negative inputs.
 #[unsafe(no_mangle)]
 pub extern "C" fn __lp_q32_log(x: i32) -> i32 {
     if x <= 0 {
@@ -37,24 +41,12 @@ pub extern "C" fn __lp_q32_log(x: i32) -> i32 {
         scaling += 4;
     }
 
+    // Small values: shift up (exact, unlike multiplying by e^4) and subtract
+    // shift * ln(2) at the end
+    let mut shift = 0i64;
     while in_value < FIX16_ONE {
-        let prev_value = in_value;
-        in_value = __lp_q32_mul(in_value, E_TO_FOURTH);
-        scaling -= 4;
-
-        // Safety check: if multiplication didn't change the value, we're stuck
-        // This can happen if the value underflows to 0 or saturates incorrectly
-        if in_value == prev_value {
-            // Value didn't change - break to avoid infinite loop
-            // This indicates underflow or saturation issue
-            break;
-        }
-
-        // Additional safety: if scaling becomes very negative, we've scaled too far
-        // This shouldn't happen in normal cases, but protects against edge cases
-        if scaling < -100 {
-            break;
-        }
+        in_value <<= 1;
+        shift += 1;
     }
 
     // Newton-Raphson iteration: solving e(guess) = in_value
@@ -88,7 +80,8 @@ pub extern "C" fn __lp_q32_log(x: i32) -> i32 {
 
     // Add scaling factor: log(x * e^n) = log(x) + n
     // libfixmath uses fix16_from_int(scaling) which is scaling << 16
-    guess + (scaling << 16)
+    let shift_correction = ((shift * LN_2_Q32 + (1 << 15)) >> 16) as i32;
+    guess + (scaling << 16) - shift_correction
 }
 
 #[cfg(test)]
@@ -111,4 +104,37 @@ mod tests {
         // Use 5% tolerance for log functions (Newton-Raphson can have some error)
         test_q32_function_relative(|x| __lp_q32_log(x), &tests, 0.05, 0.01);
     }
+
+    #[test]
+    fn test_log_against_float() {
+        // log(x) for x in [0.015, 305]
+        for i in 1..20_000 {
+            let x = i * 1000;
+            let expected = (x as f64 / 65536.0).ln();
+            let actual = __lp_q32_log(x) as f64 / 65536.0;
+            assert!(
+                (actual - expected).abs() < 0.0001,
+                "log({x}): expected {expected}, got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_log_edge_cases() {
+        // log of zero or a negative number: most negative value (-infinity)
+        assert_eq!(__lp_q32_log(0), i32::MIN);
+        assert_eq!(__lp_q32_log(-FIX16_ONE), i32::MIN);
+        assert_eq!(__lp_q32_log(i32::MIN), i32::MIN);
+        // Smallest and largest positive inputs
+        let smallest = __lp_q32_log(1) as f64 / 65536.0;
+        assert!(
+            (smallest - (1.0f64 / 65536.0).ln()).abs() < 0.01,
+            "log(1 ulp) = {smallest}"
+        );
+        let largest = __lp_q32_log(i32::MAX) as f64 / 65536.0;
+        assert!(
+            (largest - 32768.0f64.ln()).abs() < 0.001,
+            "log(max) = {largest}"
+        );
+    }
 }
@@ -15,10 +15,15 @@ const FIX16_ZERO: i32 = 0;
 /// Algorithm ported from fpm library.
 /// For fractional exponents: pow(x, y) = exp2(log2(x) * y)
 /// For integer exponents: uses iterative multiplication for efficiency
+/// Accuracy: relative error below 0.5% for results in [0.01, 1000]
+///
 /// Special cases:
 /// - pow(x, 0) = 1
-/// - pow(0, y) = 0 for y > 0
+/// - pow(0, y) = 0 (for y < 0 the result is undefined in GLSL)
 /// - pow(x, -y) = 1 / pow(x, y)
+/// - negative x with an integer y: computed by repeated multiplication (negative for odd y)
+/// - negative x with a fractional y: 0 (undefined in GLSL)
+/// - results out of range saturate
 #[unsafe(no_mangle)]
 pub extern "C" fn __lp_q32_pow(x: i32, y: i32) -> i32 {
     // Special case: pow(x, 0) = 1
@@ -32,9 +37,14 @@ pub extern "C" fn __lp_q32_pow(x: i32, y: i32) -> i32 {
         return FIX16_ZERO;
     }
 
-    // Handle negative exponent: pow(x, -y) = 1 / pow(x, y)
+    // Handle negative exponent: pow(x, -y) = 1 / pow(x, y) = pow(1 / x, y).
+    // Invert whichever side is >= 1 so small intermediate results don't lose precision.
     if y < 0 {
-        let result = __lp_q32_pow(x, -y);
+        let y = y.saturating_neg();
+        if x.unsigned_abs() < FIX16_ONE as u32 {
+            return __lp_q32_pow(__lp_q32_div(FIX16_ONE, x), y);
+        }
+        let result = __lp_q32_pow(x, y);
         return __lp_q32_div(FIX16_ONE, result);
     }
 
@@ -145,4 +155,39 @@ mod tests {
 
         test_pow_helper(&tests, 0.05, 0.01);
     }
+
+    #[test]
+    fn test_pow_against_float() {
+        for i in 1..200 {
+            for j in -40..40 {
+                let (x, y) = (i * 3000, j * 9000); // x in (0, 9], y in [-5.5, 5.5)
+                let (xf, yf) = (x as f64 / 65536.0, y as f64 / 65536.0);
+                let expected = xf.powf(yf);
+                if !(0.01..=1000.0).contains(&expected) {
+                    continue;
+                }
+                let actual = __lp_q32_pow(x, y) as f64 / 65536.0;
+                assert!(
+                    (actual - expected).abs() < 0.005 * expected,
+                    "pow({xf}, {yf}): expected {expected}, got {actual}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_edge_cases() {
+        let one = 1 << 16;
+        // Negative base with integer exponent
+        assert_eq!(__lp_q32_pow(-2 << 16, 3 << 16), -8 << 16);
+        assert_eq!(__lp_q32_pow(-2 << 16, 2 << 16), 4 << 16);
+        // Negative base with fractional exponent is undefined: 0
+        assert_eq!(__lp_q32_pow(-2 << 16, one / 2), 0);
+        // Zero base with negative exponent is undefined: 0
+        assert_eq!(__lp_q32_pow(0, -one), 0);
+        // Overflow saturates
+        assert_eq!(__lp_q32_pow(100 << 16, 3 << 16), 0x7FFF_FFFF);
+        // Most negative exponent doesn't overflow on negation
+        assert!(__lp_q32_pow(2 << 16, i32::MIN) <= 2);
+    }
 }
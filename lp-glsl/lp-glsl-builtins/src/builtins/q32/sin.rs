@@ -5,10 +5,22 @@ use crate::builtins::q32::mul::__lp_q32_mul;
 /// Fixed-point value of π (Q16.16 format)
 const FIX16_PI: i32 = 205887;
 
+/// 2π with 32 fractional bits, so range reduction of large angles stays exact to
+/// within half a Q16.16 ulp
+const TWO_PI_Q32: i64 = 26_986_075_409;
+
+/// Reduce an angle to [-π, π) by subtracting the nearest multiple of 2π.
+pub(crate) fn reduce_angle(x: i32) -> i32 {
+    let x_q32 = (x as i64) << 16;
+    let k = (x_q32 + TWO_PI_Q32 / 2).div_euclid(TWO_PI_Q32);
+    ((x_q32 - k * TWO_PI_Q32) >> 16) as i32
+}
+
 /// Compute sine using Taylor series approximation.
 ///
-/// Algorithm ported from libfixmath's accurate Taylor series implementation.
-/// Accuracy: ~2.1%
+/// The angle is reduced to [-π, π] (valid for the whole Q16.16 range) and folded to
+/// [-π/2, π/2] using sin(π - x) = sin(x), where the series converges quickly.
+/// Accuracy: absolute error below 0.0001 (about 6 ulp)
 ///
 /// Formula: sin(x) ≈ x - x³/6 + x⁵/120 - x⁷/5040 + x⁹/362880 - x¹¹/39916800
 #[unsafe(no_mangle)]
@@ -18,15 +30,15 @@ pub extern "C" fn __lp_q32_sin(x: i32) -> i32 {
         return 0;
     }
 
-    // Range reduction: reduce to [-2π, 2π]
-    let two_pi = FIX16_PI << 1;
-    let mut temp_angle = x % two_pi;
+    // Range reduction: reduce to [-π, π]
+    let mut temp_angle = reduce_angle(x);
 
-    // Further reduce to [-π, π]
-    if temp_angle > FIX16_PI {
-        temp_angle -= two_pi;
-    } else if temp_angle < -FIX16_PI {
-        temp_angle += two_pi;
+    // Fold to [-π/2, π/2]
+    let half_pi = FIX16_PI >> 1;
+    if temp_angle > half_pi {
+        temp_angle = FIX16_PI - temp_angle;
+    } else if temp_angle < -half_pi {
+        temp_angle = -FIX16_PI - temp_angle;
     }
 
     // Compute temp_angle² for Taylor series
@@ -99,4 +111,40 @@ mod tests {
 
         test_q32_function_relative(|x| __lp_q32_sin(x), &tests, 0.03, 0.01);
     }
+
+    /// Max absolute error of `f` against `reference` at `steps` points in [start, end]
+    fn max_error(
+        f: extern "C" fn(i32) -> i32,
+        reference: fn(f64) -> f64,
+        start: f64,
+        end: f64,
+    ) -> f64 {
+        let steps = 10_000;
+        (0..=steps)
+            .map(|i| {
+                let x = ((start + (end - start) * i as f64 / steps as f64) * 65536.0) as i32;
+                let expected = reference(x as f64 / 65536.0);
+                (f(x) as f64 / 65536.0 - expected).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_sin_full_period() {
+        let error = max_error(
+            __lp_q32_sin,
+            f64::sin,
+            -core::f64::consts::PI,
+            core::f64::consts::PI,
+        );
+        assert!(error < 0.0001, "max error {error}");
+    }
+
+    #[test]
+    fn test_sin_large_inputs() {
+        for start in [1000.0, -20000.0, 32000.0] {
+            let error = max_error(__lp_q32_sin, f64::sin, start, start + 7.0);
+            assert!(error < 0.0001, "max error {error} near {start}");
+        }
+    }
 }
@@ -7,7 +7,8 @@ use crate::builtins::q32::div::__lp_q32_div;
 /// Compute tangent using sine and cosine: tan(x) = sin(x) / cos(x)
 ///
 /// Algorithm ported from libfixmath.
-/// Accuracy: ~2.1% (same as sin/cos)
+/// Accuracy: relative error below 0.1% (absolute below 0.001 for |tan(x)| < 1) where
+/// |cos(x)| > 0.1; grows near the poles at π/2 + kπ as cos(x) loses precision
 #[unsafe(no_mangle)]
 pub extern "C" fn __lp_q32_tan(x: i32) -> i32 {
     let sin_val = __lp_q32_sin(x);
@@ -55,4 +56,20 @@ mod tests {
         // Use larger tolerance for values near zero
         test_q32_function_relative(|x| __lp_q32_tan(x), &tests, 0.05, 0.01);
     }
+
+    #[test]
+    fn test_tan_full_period() {
+        // One period (-π/2, π/2), staying away from the poles where |cos(x)| < 0.1
+        let steps = 10_000;
+        let limit = 1.47;
+        for i in 0..=steps {
+            let x = ((-limit + 2.0 * limit * i as f64 / steps as f64) * 65536.0) as i32;
+            let expected = (x as f64 / 65536.0).tan();
+            let actual = __lp_q32_tan(x) as f64 / 65536.0;
+            assert!(
+                (actual - expected).abs() < 0.001 * expected.abs().max(1.0),
+                "tan({x}): expected {expected}, got {actual}"
+            );
+        }
+    }
 }
@@ -0,0 +1,29 @@
+//! Compile-time benchmark for the shaders in `lp_glsl_compiler::bench::SHADERS`
+//!
+//! Usage: `cargo bench -p lp-glsl-compiler --features bench [-- <iterations>]`
+
+use lp_glsl_compiler::OptLevel;
+use lp_glsl_compiler::bench;
+
+fn main() {
+    // `cargo bench` passes `--bench`; the first numeric argument is the iteration count
+    let iterations = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(20);
+
+    for opt_level in [OptLevel::None, OptLevel::Speed] {
+        println!("opt level {opt_level:?}:");
+        match bench::run(iterations, opt_level) {
+            Ok(results) => {
+                for result in results {
+                    println!("  {result}");
+                }
+            }
+            Err(e) => {
+                eprintln!("benchmark failed: {e}");
+                std::process::exit(1);
+            }
+        }
+    }
+}
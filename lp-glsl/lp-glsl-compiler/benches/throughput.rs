@@ -0,0 +1,25 @@
+//! Emulator vs host JIT throughput for the shaders in `lp_glsl_compiler::bench::SHADERS`
+//!
+//! Usage: `cargo bench -p lp-glsl-compiler --features bench --bench throughput [-- <width> <height>]`
+
+use lp_glsl_compiler::bench;
+
+fn main() {
+    // `cargo bench` passes `--bench`; numeric arguments are the frame size
+    let mut size = std::env::args().skip(1).filter_map(|arg| arg.parse().ok());
+    let width = size.next().unwrap_or(64);
+    let height = size.next().unwrap_or(16);
+
+    println!("{width}x{height} pixels:");
+    match bench::run_throughput(width, height) {
+        Ok(results) => {
+            for result in results {
+                println!("  {result}");
+            }
+        }
+        Err(e) => {
+            eprintln!("benchmark failed: {e:#}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,11 +1,15 @@
 //! Simple example of compiling and executing GLSL code
 
-use lp_glsl_compiler::{DecimalFormat, GlslOptions, RunMode, glsl_jit};
+use lp_glsl_compiler::{DecimalFormat, GlslOptions, OptLevel, RunMode, glsl_jit};
 
 fn main() {
     let options = GlslOptions {
         run_mode: RunMode::HostJit,
         decimal_format: DecimalFormat::Float,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
     };
 
     // Example 1: Integer arithmetic
@@ -0,0 +1,154 @@
+//! Heap usage tracking for compilation.
+//!
+//! Cranelift's heap usage during compilation depends on shader complexity, which makes
+//! sizing the heap on embedded targets guesswork. [`TrackingAllocator`] wraps the real
+//! allocator and keeps current/peak byte counters that the compiler reads to fill in
+//! [`CompileStats`]. It also counts allocations, which tests use to check that hot
+//! paths don't allocate.
+//!
+//! The tracker only sees allocations when the application installs it:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: TrackingAllocator<std::alloc::System> =
+//!     TrackingAllocator::new(std::alloc::System);
+//! ```
+//!
+//! Without it, all reported heap numbers are zero.
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator wrapper that tracks current and peak heap usage and counts
+/// allocations
+pub struct TrackingAllocator<A> {
+    inner: A,
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Wrap an allocator
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.inner.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { self.inner.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+}
+
+/// Bytes currently allocated through the tracking allocator
+pub fn current_bytes() -> usize {
+    CURRENT_BYTES.load(Ordering::Relaxed)
+}
+
+/// Successful allocations (including reallocations) made through the tracking allocator
+pub fn allocation_count() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+/// Highest value of [`current_bytes`] since the last [`reset_peak`]
+pub fn peak_bytes() -> usize {
+    PEAK_BYTES.load(Ordering::Relaxed)
+}
+
+/// Reset the peak to the current usage and return the current usage
+pub fn reset_peak() -> usize {
+    let current = current_bytes();
+    PEAK_BYTES.store(current, Ordering::Relaxed);
+    current
+}
+
+/// Statistics collected while compiling a shader
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompileStats {
+    /// Peak heap usage during compilation, relative to usage when compilation started.
+    /// Zero unless [`TrackingAllocator`] is installed as the global allocator.
+    pub peak_heap_bytes: usize,
+    /// Size of the emitted machine code in bytes
+    pub code_size: usize,
+    /// Registers stored to the stack by the register allocator, across all functions.
+    /// Heavy spilling means the shader keeps more values live than there are registers.
+    pub spills: usize,
+    /// Registers loaded back from spill slots, across all functions
+    pub reloads: usize,
+}
+
+/// Measures peak heap usage over a span of work
+///
+/// Useful for measuring compilation paths not covered by [`CompileStats`], such as
+/// building a JIT executable on the device.
+pub struct HeapSpan {
+    baseline: usize,
+}
+
+impl HeapSpan {
+    /// Start measuring (resets the global peak)
+    pub fn start() -> Self {
+        Self {
+            baseline: reset_peak(),
+        }
+    }
+
+    /// Peak usage above the baseline since the span started
+    pub fn peak(&self) -> usize {
+        peak_bytes().saturating_sub(self.baseline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_alloc_updates_peak() {
+        let start = reset_peak();
+        let allocations = allocation_count();
+        record_alloc(1000);
+        assert!(peak_bytes() >= start + 1000);
+        assert!(allocation_count() > allocations);
+        record_dealloc(1000);
+        assert!(peak_bytes() >= start + 1000);
+    }
+}
@@ -110,6 +110,8 @@ pub enum BuiltinId {
     LpfxSrandom3TileQ32,
     LpfxSrandom3VecF32,
     LpfxSrandom3VecQ32,
+    LpfxVnoise2F32,
+    LpfxVnoise2Q32,
     LpfxWorley2F32,
     LpfxWorley2Q32,
     LpfxWorley2ValueF32,
@@ -212,6 +214,8 @@ impl BuiltinId {
             BuiltinId::LpfxSrandom3TileQ32 => "__lpfx_srandom3_tile_q32",
             BuiltinId::LpfxSrandom3VecF32 => "__lpfx_srandom3_vec_f32",
             BuiltinId::LpfxSrandom3VecQ32 => "__lpfx_srandom3_vec_q32",
+            BuiltinId::LpfxVnoise2F32 => "__lpfx_vnoise2_f32",
+            BuiltinId::LpfxVnoise2Q32 => "__lpfx_vnoise2_q32",
             BuiltinId::LpfxWorley2F32 => "__lpfx_worley2_f32",
             BuiltinId::LpfxWorley2Q32 => "__lpfx_worley2_q32",
             BuiltinId::LpfxWorley2ValueF32 => "__lpfx_worley2_value_f32",
@@ -316,6 +320,8 @@ impl BuiltinId {
             "__lpfx_srandom3_tile_q32" => Some(BuiltinId::LpfxSrandom3TileQ32),
             "__lpfx_srandom3_vec_f32" => Some(BuiltinId::LpfxSrandom3VecF32),
             "__lpfx_srandom3_vec_q32" => Some(BuiltinId::LpfxSrandom3VecQ32),
+            "__lpfx_vnoise2_f32" => Some(BuiltinId::LpfxVnoise2F32),
+            "__lpfx_vnoise2_q32" => Some(BuiltinId::LpfxVnoise2Q32),
             "__lpfx_worley2_f32" => Some(BuiltinId::LpfxWorley2F32),
             "__lpfx_worley2_q32" => Some(BuiltinId::LpfxWorley2Q32),
             "__lpfx_worley2_value_f32" => Some(BuiltinId::LpfxWorley2ValueF32),
@@ -457,6 +463,8 @@ impl BuiltinId {
             | BuiltinId::LpfxSnoise2Q32
             | BuiltinId::LpfxSrandom2F32
             | BuiltinId::LpfxSrandom2Q32
+            | BuiltinId::LpfxVnoise2F32
+            | BuiltinId::LpfxVnoise2Q32
             | BuiltinId::LpfxWorley2F32
             | BuiltinId::LpfxWorley2Q32
             | BuiltinId::LpfxWorley2ValueF32
@@ -610,6 +618,8 @@ impl BuiltinId {
             BuiltinId::LpfxSrandom3TileQ32,
             BuiltinId::LpfxSrandom3VecF32,
             BuiltinId::LpfxSrandom3VecQ32,
+            BuiltinId::LpfxVnoise2F32,
+            BuiltinId::LpfxVnoise2Q32,
             BuiltinId::LpfxWorley2F32,
             BuiltinId::LpfxWorley2Q32,
             BuiltinId::LpfxWorley2ValueF32,
@@ -796,6 +806,12 @@ pub fn get_function_pointer(builtin: BuiltinId) -> *const u8 {
         BuiltinId::LpfxSrandom3VecQ32 => {
             generative::srandom::srandom3_vec_q32::__lpfx_srandom3_vec_q32 as *const u8
         }
+        BuiltinId::LpfxVnoise2F32 => {
+            generative::vnoise::vnoise2_f32::__lpfx_vnoise2_f32 as *const u8
+        }
+        BuiltinId::LpfxVnoise2Q32 => {
+            generative::vnoise::vnoise2_q32::__lpfx_vnoise2_q32 as *const u8
+        }
         BuiltinId::LpfxWorley2F32 => {
             generative::worley::worley2_f32::__lpfx_worley2_f32 as *const u8
         }
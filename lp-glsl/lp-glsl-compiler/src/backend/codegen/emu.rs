@@ -38,6 +38,140 @@ pub struct EmulatorOptions {
     pub log_level: lp_riscv_emu::LogLevel,
 }
 
+/// Spill slot traffic inserted by register allocation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpillCounts {
+    /// Stores of a register to a spill slot
+    pub spills: usize,
+    /// Loads of a register back from a spill slot
+    pub reloads: usize,
+}
+
+/// Compile all functions in a GlModule<ObjectModule> and return the emitted `.text` bytes
+///
+/// Relocations (e.g. calls to builtins) are not applied.
+#[cfg(feature = "emulator")]
+pub fn emit_text_section(gl_module: GlModule<ObjectModule>) -> Result<Vec<u8>, GlslError> {
+    emit_text_section_impl(gl_module, false).map(|(code, _)| code)
+}
+
+/// Like [`emit_text_section`], also counting spills and reloads across all functions
+///
+/// The counts come from each function's post-regalloc VCode, so this compiles with
+/// disassembly enabled, which costs extra time and memory.
+#[cfg(feature = "emulator")]
+pub fn emit_text_section_with_spills(
+    gl_module: GlModule<ObjectModule>,
+) -> Result<(Vec<u8>, SpillCounts), GlslError> {
+    emit_text_section_impl(gl_module, true)
+}
+
+#[cfg(feature = "emulator")]
+fn emit_text_section_impl(
+    mut gl_module: GlModule<ObjectModule>,
+    count_spills: bool,
+) -> Result<(Vec<u8>, SpillCounts), GlslError> {
+    use object::{Object, ObjectSection};
+
+    // Define in sorted order, matching declaration order (see build_emu_executable)
+    let mut funcs: Vec<(
+        String,
+        cranelift_codegen::ir::Function,
+        cranelift_module::FuncId,
+    )> = gl_module
+        .fns
+        .iter()
+        .map(|(name, gl_func)| (name.clone(), gl_func.function.clone(), gl_func.func_id))
+        .collect();
+    funcs.sort_by_key(|(name, _, _)| name.clone());
+
+    let mut spill_counts = SpillCounts::default();
+    for (name, func, func_id) in funcs {
+        let mut ctx = gl_module.module_internal().make_context();
+        ctx.func = func;
+        ctx.set_disasm(count_spills);
+        gl_module
+            .module_mut_internal()
+            .define_function(func_id, &mut ctx)
+            .map_err(|e| {
+                GlslError::new(
+                    ErrorCode::E0400,
+                    format!("Failed to define function '{name}': {e}"),
+                )
+            })?;
+        if count_spills {
+            count_function_spills(&ctx, &mut spill_counts);
+        }
+        gl_module.module_internal().clear_context(&mut ctx);
+    }
+
+    let elf_bytes = gl_module
+        .into_module()
+        .finish()
+        .emit()
+        .map_err(|e| GlslError::new(ErrorCode::E0400, format!("Failed to emit ELF: {e}")))?;
+    let obj = object::File::parse(&elf_bytes[..])
+        .map_err(|e| GlslError::new(ErrorCode::E0400, format!("Failed to parse ELF: {e}")))?;
+    let text = obj
+        .section_by_name(".text")
+        .ok_or_else(|| GlslError::new(ErrorCode::E0400, "Emitted ELF has no .text section"))?;
+    let data = text
+        .data()
+        .map_err(|e| GlslError::new(ErrorCode::E0400, format!("Failed to read .text: {e}")))?;
+    Ok((data.to_vec(), spill_counts))
+}
+
+/// Add the spills and reloads in a compiled function to `counts`
+#[cfg(feature = "emulator")]
+fn count_function_spills(ctx: &cranelift_codegen::Context, counts: &mut SpillCounts) {
+    let Some(compiled_code) = ctx.compiled_code() else {
+        return;
+    };
+    let Some(vcode) = &compiled_code.vcode else {
+        return;
+    };
+    // Spill slots are laid out after the function's explicit stack slots
+    let spill_area_start = ctx
+        .func
+        .sized_stack_slots
+        .iter()
+        .map(|(slot, data)| compiled_code.sized_stackslot_offsets[slot] + data.size)
+        .max()
+        .unwrap_or(0);
+    count_spill_slot_accesses(vcode, spill_area_start, counts);
+}
+
+/// Count loads and stores addressing spill slots in a function's VCode
+///
+/// Stack slot operands print as `<offset>(slot)`; those at or past
+/// `spill_area_start` are spill slots rather than the function's own stack slots.
+#[cfg(feature = "emulator")]
+fn count_spill_slot_accesses(vcode: &str, spill_area_start: u32, counts: &mut SpillCounts) {
+    for line in vcode.lines() {
+        let Some((mnemonic, operands)) = line.trim().split_once(' ') else {
+            continue;
+        };
+        let Some(address) = operands.rsplit(',').next() else {
+            continue;
+        };
+        let Some(Ok(offset)) = address
+            .trim()
+            .strip_suffix("(slot)")
+            .map(|offset| offset.parse::<u32>())
+        else {
+            continue;
+        };
+        if offset < spill_area_start {
+            continue;
+        }
+        if mnemonic.starts_with('s') {
+            counts.spills += 1;
+        } else if mnemonic.starts_with('l') {
+            counts.reloads += 1;
+        }
+    }
+}
+
 /// Build emulator executable from GlModule<ObjectModule>
 /// Called by GlModule<ObjectModule>::build_executable()
 #[cfg(feature = "emulator")]
@@ -268,6 +402,7 @@ pub fn build_emu_executable(
 
     // 2. Build signatures and extract metadata before moving gl_module
     let signatures = gl_module.glsl_signatures.clone();
+    let purity = gl_module.purity.clone();
     let mut cranelift_signatures = HashMap::new();
     for (name, gl_func) in &gl_module.fns {
         cranelift_signatures.insert(name.clone(), gl_func.clif_sig.clone());
@@ -277,6 +412,8 @@ pub fn build_emu_executable(
     let source_loc_manager =
         core::mem::replace(&mut gl_module.source_loc_manager, SourceLocManager::new());
     let source_map = core::mem::replace(&mut gl_module.source_map, GlSourceMap::new());
+    // The uniform block's values are copied into guest RAM whenever they change
+    let uniforms = core::mem::take(&mut gl_module.uniforms);
 
     // 3. Finish module and get object file
     let product = gl_module.into_module().finish();
@@ -483,6 +620,7 @@ pub fn build_emu_executable(
     Ok(GlslEmulatorModule {
         emulator,
         signatures,
+        purity,
         cranelift_signatures,
         binary,
         function_addresses, // Populated from merged symbol map after object file loading
@@ -493,6 +631,7 @@ pub fn build_emu_executable(
         trap_source_info,
         source_loc_manager,
         source_map,
+        uniforms,
         next_buffer_addr: 0x80000000, // Default RAM start
     })
 }
@@ -542,4 +681,28 @@ mod tests {
         let result = executable.call_i32("main", &[]).unwrap();
         assert_eq!(result, 42);
     }
+
+    #[test]
+    #[cfg(feature = "emulator")]
+    fn test_count_spill_slot_accesses() {
+        let vcode = "block0:
+  sw a0,0(slot)
+  sw a1,16(slot)
+  lw a2,16(slot)
+  lw a3,20(slot)
+  sw ra,12(sp)
+  add a0,a2,a3
+  ret
+";
+        // Offsets below 16 belong to the function's own stack slots
+        let mut counts = SpillCounts::default();
+        count_spill_slot_accesses(vcode, 16, &mut counts);
+        assert_eq!(
+            counts,
+            SpillCounts {
+                spills: 1,
+                reloads: 2
+            }
+        );
+    }
 }
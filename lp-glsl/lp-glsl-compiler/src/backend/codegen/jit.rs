@@ -4,6 +4,7 @@ use crate::backend::module::gl_module::GlModule;
 use crate::error::{ErrorCode, GlslError};
 use crate::exec::jit::GlslJitModule;
 use alloc::{format, string::String, vec::Vec};
+use core::mem::ManuallyDrop;
 use cranelift_jit::JITModule;
 use cranelift_module::Module;
 use hashbrown::HashMap;
@@ -112,6 +113,7 @@ pub fn build_jit_executable(
 
     // 3. Build signatures map from GlModule metadata
     let signatures = gl_module.glsl_signatures.clone();
+    let purity = gl_module.purity.clone();
     let mut cranelift_signatures = HashMap::new();
     for (name, gl_func) in &gl_module.fns {
         cranelift_signatures.insert(name.clone(), gl_func.clif_sig.clone());
@@ -126,14 +128,21 @@ pub fn build_jit_executable(
         GlslError::new(ErrorCode::E0400, format!("Failed to get pointer type: {e}"))
     })?;
 
-    // 5. Create GlslJitModule
+    // 5. Take the uniform block and loop fuel (their data stays at the addresses baked into the code)
+    let uniforms = core::mem::take(&mut gl_module.uniforms);
+    let fuel = gl_module.fuel.take();
+
+    // 6. Create GlslJitModule
     Ok(GlslJitModule {
-        jit_module: gl_module.into_module(),
+        jit_module: ManuallyDrop::new(gl_module.into_module()),
         function_ptrs,
         signatures,
+        purity,
         cranelift_signatures,
         call_conv,
         pointer_type,
+        uniforms,
+        fuel,
     })
 }
 
@@ -149,6 +158,7 @@ pub fn build_jit_executable_memory_optimized(
 
     // 1. Extract signatures and target info early (before compilation)
     let signatures = gl_module.glsl_signatures.clone();
+    let purity = gl_module.purity.clone();
     let call_conv = gl_module
         .target
         .default_call_conv()
@@ -274,16 +284,22 @@ pub fn build_jit_executable_memory_optimized(
 
     // 7. Extract JITModule and drop the rest of GlModule
     // This frees: function_registry, source_text, source_loc_manager, source_map, and the now-empty fns HashMap
+    // The uniform block and loop fuel are kept since compiled code uses their addresses
+    let uniforms = core::mem::take(&mut gl_module.uniforms);
+    let fuel = gl_module.fuel.take();
     let jit_module = gl_module.into_module();
 
     // 8. Create GlslJitModule
     Ok(GlslJitModule {
-        jit_module,
+        jit_module: ManuallyDrop::new(jit_module),
         function_ptrs,
         signatures,
+        purity,
         cranelift_signatures,
         call_conv,
         pointer_type,
+        uniforms,
+        fuel,
     })
 }
 
@@ -0,0 +1,58 @@
+//! Loop fuel shared between the host and compiled shader code
+//!
+//! When a module is compiled with loop fuel, every loop back-edge loads a counter
+//! from a fixed address, leaves the loop if it is zero, and otherwise stores it back
+//! decremented. Once the counter runs dry all loops exit after at most one more check,
+//! so a shader with an unbounded loop returns instead of hanging its caller.
+
+use alloc::boxed::Box;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// Host-owned loop iteration counter
+///
+/// The counter is boxed so its address stays valid for as long as the fuel exists
+/// (including after it is moved), like the uniform block.
+pub struct LoopFuel {
+    remaining: Box<AtomicU32>,
+}
+
+impl LoopFuel {
+    /// Create a counter allowing `fuel` loop iterations
+    pub fn new(fuel: u32) -> Self {
+        Self {
+            remaining: Box::new(AtomicU32::new(fuel)),
+        }
+    }
+
+    /// Address of the counter, embedded into compiled code
+    pub fn as_ptr(&self) -> *const u8 {
+        self.remaining.as_ptr() as *const u8
+    }
+
+    /// Refill the counter
+    pub fn set(&self, fuel: u32) {
+        self.remaining.store(fuel, Ordering::Relaxed);
+    }
+
+    /// Iterations left before loops start bailing out
+    pub fn remaining(&self) -> u32 {
+        self.remaining.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuel_address_is_stable() {
+        let fuel = LoopFuel::new(10);
+        let ptr = fuel.as_ptr();
+        let moved = fuel;
+        assert_eq!(moved.as_ptr(), ptr);
+
+        moved.set(3);
+        assert_eq!(moved.remaining(), 3);
+        assert_eq!(unsafe { *(ptr as *const u32) }, 3);
+    }
+}
@@ -1,9 +1,12 @@
 //! GLSL Module - owns the actual Cranelift Module
 
+use crate::backend::module::fuel::LoopFuel;
 use crate::backend::module::gl_func::GlFunc;
+use crate::backend::module::uniforms::UniformBlock;
 use crate::backend::target::Target;
 use crate::error::{ErrorCode, GlslError};
 use crate::frontend::semantic::functions::{FunctionRegistry, FunctionSignature};
+use crate::frontend::semantic::passes::purity::Purity;
 use crate::frontend::src_loc::GlSourceMap;
 use crate::frontend::src_loc_manager::SourceLocManager;
 use alloc::boxed::Box;
@@ -23,9 +26,14 @@ pub struct GlModule<M: Module> {
     // Metadata fields
     pub function_registry: FunctionRegistry,
     pub glsl_signatures: HashMap<String, FunctionSignature>,
+    /// Which functions are pure or read time
+    pub purity: Purity,
     pub source_text: String,
     pub source_loc_manager: SourceLocManager,
     pub source_map: GlSourceMap,
+    pub uniforms: UniformBlock,
+    /// Loop iteration counter, if loops are compiled with fuel checks
+    pub fuel: Option<LoopFuel>,
 }
 
 // Separate constructors for each Module type (Rust needs concrete types)
@@ -102,9 +110,12 @@ impl GlModule<JITModule> {
                     module,
                     function_registry: FunctionRegistry::new(),
                     glsl_signatures: HashMap::new(),
+                    purity: Purity::default(),
                     source_text: String::new(),
                     source_loc_manager: SourceLocManager::new(),
                     source_map: GlSourceMap::new(),
+                    uniforms: UniformBlock::default(),
+                    fuel: None,
                 })
             }
             _ => Err(GlslError::new(
@@ -159,9 +170,12 @@ impl GlModule<ObjectModule> {
                     module,
                     function_registry: FunctionRegistry::new(),
                     glsl_signatures: HashMap::new(),
+                    purity: Purity::default(),
                     source_text: String::new(),
                     source_loc_manager: SourceLocManager::new(),
                     source_map: GlSourceMap::new(),
+                    uniforms: UniformBlock::default(),
+                    fuel: None,
                 })
             }
             _ => Err(GlslError::new(
@@ -348,6 +362,18 @@ impl<M: Module> GlModule<M> {
         );
     }
 
+    /// Reorder instructions to hide latency, if the target's opt level asks for it
+    ///
+    /// Runs after transforms, on the functions as they will be compiled. See
+    /// [`crate::backend::schedule`].
+    pub fn schedule_functions(&mut self) {
+        if self.target.opt_level() == crate::backend::target::OptLevel::Scheduled {
+            for gl_func in self.fns.values_mut() {
+                crate::backend::schedule::schedule_function(&mut gl_func.function);
+            }
+        }
+    }
+
     /// Internal: Get mutable access to Module
     ///
     /// **WARNING**: This is internal-only. Do not use outside of GlModule implementation.
@@ -521,17 +547,23 @@ impl GlModule<JITModule> {
         let target = self.target.clone();
         let function_registry = self.function_registry;
         let glsl_signatures = self.glsl_signatures;
+        let purity = self.purity;
         let source_text = self.source_text;
         let source_loc_manager = self.source_loc_manager;
         let source_map = self.source_map;
+        let uniforms = self.uniforms;
+        let fuel = self.fuel;
         let fns = self.fns;
         let mut new_module = Self::new_with_target(target)?;
         // Preserve metadata
         new_module.function_registry = function_registry;
         new_module.glsl_signatures = glsl_signatures;
+        new_module.purity = purity;
         new_module.source_text = source_text;
         new_module.source_loc_manager = source_loc_manager;
         new_module.source_map = source_map;
+        new_module.uniforms = uniforms;
+        new_module.fuel = fuel;
         Self::apply_transform_impl(&old_module_builtins, fns, transform, new_module)
     }
 }
@@ -591,17 +623,23 @@ impl GlModule<ObjectModule> {
         let target = self.target.clone();
         let function_registry = self.function_registry;
         let glsl_signatures = self.glsl_signatures;
+        let purity = self.purity;
         let source_text = self.source_text;
         let source_loc_manager = self.source_loc_manager;
         let source_map = self.source_map;
+        let uniforms = self.uniforms;
+        let fuel = self.fuel;
         let fns = self.fns;
         let mut new_module = Self::new_with_target(target)?;
         // Preserve metadata
         new_module.function_registry = function_registry;
         new_module.glsl_signatures = glsl_signatures;
+        new_module.purity = purity;
         new_module.source_text = source_text;
         new_module.source_loc_manager = source_loc_manager;
         new_module.source_map = source_map;
+        new_module.uniforms = uniforms;
+        new_module.fuel = fuel;
         Self::apply_transform_impl(&old_module_builtins, fns, transform, new_module)
     }
 
@@ -1,7 +1,9 @@
 //! GLSL Module (wraps Cranelift Module)
 
 pub mod builder;
+pub mod fuel;
 pub mod gl_func;
 pub mod gl_module;
 #[cfg(test)]
 pub mod test_helpers;
+pub mod uniforms;
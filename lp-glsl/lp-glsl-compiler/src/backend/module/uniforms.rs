@@ -0,0 +1,235 @@
+//! Uniform block shared between the host and compiled shader code
+//!
+//! Every uniform declared in a shader gets a slot in a single heap-allocated block
+//! of 32-bit components. Compiled functions load uniforms from the block's address
+//! on entry, so updating a value only writes to the block and never recompiles.
+//!
+//! JIT code reads the block from host memory. Emulated code can't reach host memory,
+//! so the emulator copies the block into guest RAM at [`EMU_UNIFORM_BLOCK_ADDR`].
+
+use crate::error::{ErrorCode, GlslError};
+use crate::exec::executable::DecimalFormat;
+use crate::exec::glsl_value::GlslValue;
+use crate::frontend::codegen::constants::FIXED16X16_SCALE;
+use crate::frontend::semantic::UniformDecl;
+use crate::frontend::semantic::types::Type;
+
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+
+/// Size of one uniform component in bytes
+pub const UNIFORM_COMPONENT_BYTES: usize = 4;
+
+/// Guest address of the uniform block in emulated code
+///
+/// The builtins app's linker script reserves `.uniforms` at the start of RAM for it.
+pub const EMU_UNIFORM_BLOCK_ADDR: u32 = 0x8000_0000;
+
+/// Bytes reserved for the uniform block in emulator RAM
+pub const EMU_UNIFORM_BLOCK_SIZE: usize = 4096;
+
+/// Location of a single uniform within the block
+#[derive(Debug, Clone)]
+pub struct UniformEntry {
+    pub name: String,
+    pub ty: Type,
+    /// Byte offset of the first component
+    pub offset: usize,
+}
+
+impl UniformEntry {
+    /// Number of 32-bit components this uniform occupies
+    pub fn component_count(&self) -> usize {
+        self.ty.component_count().unwrap_or(1)
+    }
+}
+
+/// Host-owned storage for uniform values
+///
+/// The data lives in a boxed slice that is never reallocated, so its address stays
+/// valid for as long as the block exists (including after the block is moved).
+pub struct UniformBlock {
+    entries: Vec<UniformEntry>,
+    data: Box<[u32]>,
+    decimal_format: DecimalFormat,
+    /// Address compiled code reads the block from, if not the host data itself
+    guest_address: Option<u32>,
+}
+
+impl UniformBlock {
+    /// Lay out the given uniforms back to back, with every value initialized to zero
+    pub fn new(decls: &[UniformDecl]) -> Self {
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        for decl in decls {
+            let entry = UniformEntry {
+                name: decl.name.clone(),
+                ty: decl.ty.clone(),
+                offset,
+            };
+            offset += entry.component_count() * UNIFORM_COMPONENT_BYTES;
+            entries.push(entry);
+        }
+
+        Self {
+            entries,
+            data: vec![0u32; offset / UNIFORM_COMPONENT_BYTES].into_boxed_slice(),
+            decimal_format: DecimalFormat::Float,
+            guest_address: None,
+        }
+    }
+
+    /// Lay out the uniforms for emulated code, which reads them at [`EMU_UNIFORM_BLOCK_ADDR`]
+    pub fn new_emulated(decls: &[UniformDecl]) -> Result<Self, GlslError> {
+        let mut block = Self::new(decls);
+        let size = block.data.len() * UNIFORM_COMPONENT_BYTES;
+        if size > EMU_UNIFORM_BLOCK_SIZE {
+            return Err(GlslError::new(
+                ErrorCode::E0400,
+                format!(
+                    "uniforms take {size} bytes, more than the {EMU_UNIFORM_BLOCK_SIZE} bytes the emulator reserves"
+                ),
+            ));
+        }
+        block.guest_address = Some(EMU_UNIFORM_BLOCK_ADDR);
+        Ok(block)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[UniformEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, name: &str) -> Option<&UniformEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    /// Address of the first component, embedded into compiled code
+    pub fn base_address(&self) -> usize {
+        match self.guest_address {
+            Some(address) => address as usize,
+            None => self.data.as_ptr() as usize,
+        }
+    }
+
+    /// Current values as little-endian bytes, for copying into guest memory
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        self.data
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+
+    /// Set how float components are encoded (must match the compiled code)
+    pub fn set_decimal_format(&mut self, format: DecimalFormat) {
+        self.decimal_format = format;
+    }
+
+    /// Write a new value for a uniform
+    pub fn set(&mut self, name: &str, value: &GlslValue) -> Result<(), GlslError> {
+        let entry = self.get(name).ok_or_else(|| {
+            GlslError::new(ErrorCode::E0100, format!("uniform `{name}` not found"))
+        })?;
+
+        let words = self.encode(&entry.ty, value).ok_or_else(|| {
+            GlslError::new(
+                ErrorCode::E0102,
+                format!(
+                    "uniform `{}` has type {:?}, got {:?}",
+                    entry.name, entry.ty, value
+                ),
+            )
+        })?;
+
+        let start = entry.offset / UNIFORM_COMPONENT_BYTES;
+        self.data[start..start + words.len()].copy_from_slice(&words);
+        Ok(())
+    }
+
+    /// Convert a value into raw components, or None if it doesn't match `ty`
+    fn encode(&self, ty: &Type, value: &GlslValue) -> Option<Vec<u32>> {
+        let float = |v: f32| match self.decimal_format {
+            DecimalFormat::Float => v.to_bits(),
+            DecimalFormat::Q32 => (v * FIXED16X16_SCALE) as i32 as u32,
+        };
+
+        let words = match (ty, value) {
+            (Type::Float, GlslValue::F32(v)) => vec![float(*v)],
+            (Type::Int, GlslValue::I32(v)) => vec![*v as u32],
+            (Type::UInt, GlslValue::U32(v)) => vec![*v],
+            (Type::Vec2, GlslValue::Vec2(v)) => v.iter().map(|c| float(*c)).collect(),
+            (Type::Vec3, GlslValue::Vec3(v)) => v.iter().map(|c| float(*c)).collect(),
+            (Type::Vec4, GlslValue::Vec4(v)) => v.iter().map(|c| float(*c)).collect(),
+            (Type::IVec2, GlslValue::IVec2(v)) => v.iter().map(|c| *c as u32).collect(),
+            (Type::IVec3, GlslValue::IVec3(v)) => v.iter().map(|c| *c as u32).collect(),
+            (Type::IVec4, GlslValue::IVec4(v)) => v.iter().map(|c| *c as u32).collect(),
+            (Type::UVec2, GlslValue::UVec2(v)) => v.to_vec(),
+            (Type::UVec3, GlslValue::UVec3(v)) => v.to_vec(),
+            (Type::UVec4, GlslValue::UVec4(v)) => v.to_vec(),
+            _ => return None,
+        };
+        Some(words)
+    }
+}
+
+impl Default for UniformBlock {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decl(name: &str, ty: Type) -> UniformDecl {
+        UniformDecl {
+            name: String::from(name),
+            ty,
+            implicit: false,
+        }
+    }
+
+    #[test]
+    fn test_layout_is_packed() {
+        let block = UniformBlock::new(&[decl("speed", Type::Float), decl("tint", Type::Vec3)]);
+        assert_eq!(block.get("speed").unwrap().offset, 0);
+        assert_eq!(block.get("tint").unwrap().offset, 4);
+        assert_eq!(block.data.len(), 4);
+    }
+
+    #[test]
+    fn test_set_q32_float() {
+        let mut block = UniformBlock::new(&[decl("speed", Type::Float)]);
+        block.set_decimal_format(DecimalFormat::Q32);
+        block.set("speed", &GlslValue::F32(1.5)).unwrap();
+        assert_eq!(block.data[0], 98304);
+    }
+
+    #[test]
+    fn test_set_rejects_wrong_type_and_unknown_name() {
+        let mut block = UniformBlock::new(&[decl("count", Type::Int)]);
+        assert!(block.set("count", &GlslValue::F32(1.0)).is_err());
+        assert!(block.set("missing", &GlslValue::I32(1)).is_err());
+        block.set("count", &GlslValue::I32(-2)).unwrap();
+        assert_eq!(block.data[0], (-2i32) as u32);
+    }
+
+    #[test]
+    fn test_emulated_block_reads_from_guest_address() {
+        let mut block = UniformBlock::new_emulated(&[decl("count", Type::Int)]).unwrap();
+        assert_eq!(block.base_address(), EMU_UNIFORM_BLOCK_ADDR as usize);
+        block.set("count", &GlslValue::I32(3)).unwrap();
+        assert_eq!(block.to_le_bytes(), [3, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_emulated_block_must_fit_reserved_space() {
+        let decls: Vec<_> = (0..EMU_UNIFORM_BLOCK_SIZE / 16 + 1)
+            .map(|i| decl(&format!("color{i}"), Type::Vec4))
+            .collect();
+        assert!(UniformBlock::new_emulated(&decls).is_err());
+    }
+}
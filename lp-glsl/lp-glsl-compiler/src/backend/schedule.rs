@@ -0,0 +1,248 @@
+//! Latency-aware instruction scheduling
+//!
+//! On an in-order core, an instruction that reads the result of a multiply, divide
+//! or load issued just before it stalls until that result is ready. This pass
+//! reorders the instructions within each block so independent work fills those
+//! gaps, using list scheduling with the critical path length as priority.
+//!
+//! Only the order changes: data dependencies are kept, instructions with side
+//! effects (memory access, calls, traps) keep their relative order, and each
+//! block's terminator stays last. Interleaving independent chains keeps more values
+//! live at once, so the pass trades some register pressure for fewer stalls.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use cranelift_codegen::ir::{Block, Function, Inst, Opcode, ValueDef};
+use hashbrown::HashMap;
+
+// The latencies mirror the emulator's in-order pipeline model
+// (`lp_riscv_emu::emu::cycle_model`), which is the source of truth; the compiler
+// can't depend on the emulator outside the `emulator` feature. `test_schedule`
+// checks that they match.
+
+/// Cycles until a multiply result can be used
+pub const MUL_LATENCY: u32 = 3;
+/// Cycles until a divide or remainder result can be used
+pub const DIV_LATENCY: u32 = 8;
+/// Cycles until loaded data can be used
+pub const LOAD_LATENCY: u32 = 2;
+
+/// Schedule every block of `func` in place
+pub fn schedule_function(func: &mut Function) {
+    let blocks: Vec<Block> = func.layout.blocks().collect();
+    for block in blocks {
+        schedule_block(func, block);
+    }
+}
+
+/// Cycles from issuing an instruction until its result can be used
+fn latency(opcode: Opcode) -> u32 {
+    match opcode {
+        Opcode::Imul | Opcode::Smulhi | Opcode::Umulhi => MUL_LATENCY,
+        Opcode::Sdiv | Opcode::Udiv | Opcode::Srem | Opcode::Urem => DIV_LATENCY,
+        _ if opcode.can_load() => LOAD_LATENCY,
+        _ => 1,
+    }
+}
+
+/// Whether an instruction must stay in order with other such instructions
+fn has_side_effects(opcode: Opcode) -> bool {
+    opcode.can_load()
+        || opcode.can_store()
+        || opcode.is_call()
+        || opcode.can_trap()
+        || opcode.other_side_effects()
+}
+
+fn schedule_block(func: &mut Function, block: Block) {
+    let mut insts: Vec<Inst> = func.layout.block_insts(block).collect();
+    let Some(&last) = insts.last() else {
+        return;
+    };
+    let terminator = func.dfg.insts[last]
+        .opcode()
+        .is_terminator()
+        .then_some(last);
+    if terminator.is_some() {
+        insts.pop();
+    }
+    if insts.len() < 3 {
+        return;
+    }
+
+    let order = schedule_order(func, &insts);
+    if order.iter().enumerate().all(|(i, &index)| i == index) {
+        return;
+    }
+    for &inst in &insts {
+        func.layout.remove_inst(inst);
+    }
+    for index in order {
+        match terminator {
+            Some(terminator) => func.layout.insert_inst(insts[index], terminator),
+            None => func.layout.append_inst(insts[index], block),
+        }
+    }
+}
+
+/// New order of `insts` (a block without its terminator), as indices into `insts`
+fn schedule_order(func: &Function, insts: &[Inst]) -> Vec<usize> {
+    let n = insts.len();
+    let index: HashMap<Inst, usize> = insts
+        .iter()
+        .enumerate()
+        .map(|(i, &inst)| (inst, i))
+        .collect();
+    let latencies: Vec<u32> = insts
+        .iter()
+        .map(|&inst| latency(func.dfg.insts[inst].opcode()))
+        .collect();
+
+    // Edges to later instructions, with the cycles the later one has to wait
+    let mut succs: Vec<Vec<(usize, u32)>> = vec![Vec::new(); n];
+    let mut pending = vec![0usize; n];
+    let mut last_effect: Option<usize> = None;
+    for (i, &inst) in insts.iter().enumerate() {
+        let defs =
+            func.dfg
+                .inst_args(inst)
+                .iter()
+                .filter_map(|&arg| match func.dfg.value_def(arg) {
+                    ValueDef::Result(def, _) => index.get(&def).copied(),
+                    _ => None,
+                });
+        for d in defs {
+            succs[d].push((i, latencies[d]));
+            pending[i] += 1;
+        }
+        if has_side_effects(func.dfg.insts[inst].opcode()) {
+            if let Some(prev) = last_effect {
+                succs[prev].push((i, 1));
+                pending[i] += 1;
+            }
+            last_effect = Some(i);
+        }
+    }
+
+    // Length of the longest path from each instruction to the end of the block.
+    // Edges only point forward, so a reverse walk sees successors first.
+    let mut height = vec![0u32; n];
+    for i in (0..n).rev() {
+        height[i] = succs[i]
+            .iter()
+            .map(|&(s, wait)| wait + height[s])
+            .max()
+            .unwrap_or(latencies[i]);
+    }
+
+    let mut ready_at = vec![0u32; n];
+    let mut ready: Vec<usize> = (0..n).filter(|&i| pending[i] == 0).collect();
+    let mut order = Vec::with_capacity(n);
+    let mut cycle = 0u32;
+    while !ready.is_empty() {
+        // Prefer instructions that would not stall, then the longest remaining path,
+        // then the original order
+        let (pos, _) = ready
+            .iter()
+            .enumerate()
+            .max_by_key(|&(_, &i)| (ready_at[i] <= cycle, height[i], core::cmp::Reverse(i)))
+            .unwrap();
+        let i = ready.swap_remove(pos);
+        let issue = cycle.max(ready_at[i]);
+        cycle = issue + 1;
+        order.push(i);
+
+        for &(s, wait) in &succs[i] {
+            ready_at[s] = ready_at[s].max(issue + wait);
+            pending[s] -= 1;
+            if pending[s] == 0 {
+                ready.push(s);
+            }
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cranelift_codegen::ir::{AbiParam, InstBuilder, Signature, Value, types};
+    use cranelift_codegen::isa::CallConv;
+    use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext};
+
+    /// `(a * a * a) + (b * b * b)`, written one chain after the other
+    fn two_chains() -> (Function, [Value; 4]) {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I32));
+        sig.params.push(AbiParam::new(types::I32));
+        sig.returns.push(AbiParam::new(types::I32));
+        let mut func = Function::with_name_signature(Default::default(), sig);
+        let mut ctx = FunctionBuilderContext::new();
+        let mut builder = FunctionBuilder::new(&mut func, &mut ctx);
+
+        let block = builder.create_block();
+        builder.append_block_params_for_function_params(block);
+        builder.switch_to_block(block);
+        let a = builder.block_params(block)[0];
+        let b = builder.block_params(block)[1];
+        let a2 = builder.ins().imul(a, a);
+        let a3 = builder.ins().imul(a2, a);
+        let b2 = builder.ins().imul(b, b);
+        let b3 = builder.ins().imul(b2, b);
+        let sum = builder.ins().iadd(a3, b3);
+        builder.ins().return_(&[sum]);
+        builder.seal_all_blocks();
+        builder.finalize();
+        (func, [a2, a3, b2, b3])
+    }
+
+    fn def(func: &Function, value: Value) -> Inst {
+        func.dfg.value_def(value).unwrap_inst()
+    }
+
+    #[test]
+    fn test_interleaves_independent_multiplies() {
+        let (mut func, [a2, a3, b2, b3]) = two_chains();
+        schedule_function(&mut func);
+
+        let block = func.layout.entry_block().unwrap();
+        let insts: Vec<Inst> = func.layout.block_insts(block).collect();
+        assert_eq!(
+            insts[..4],
+            [
+                def(&func, a2),
+                def(&func, b2),
+                def(&func, a3),
+                def(&func, b3)
+            ]
+        );
+        assert_eq!(func.dfg.insts[insts[5]].opcode(), Opcode::Return);
+    }
+
+    #[test]
+    fn test_keeps_side_effect_order() {
+        let mut sig = Signature::new(CallConv::SystemV);
+        sig.params.push(AbiParam::new(types::I32));
+        let mut func = Function::with_name_signature(Default::default(), sig);
+        let mut ctx = FunctionBuilderContext::new();
+        let mut builder = FunctionBuilder::new(&mut func, &mut ctx);
+
+        let block = builder.create_block();
+        builder.append_block_params_for_function_params(block);
+        builder.switch_to_block(block);
+        let ptr = builder.block_params(block)[0];
+        let flags = cranelift_codegen::ir::MemFlags::trusted();
+        let x = builder.ins().load(types::I32, flags, ptr, 0);
+        builder.ins().store(flags, x, ptr, 4);
+        let y = builder.ins().load(types::I32, flags, ptr, 8);
+        builder.ins().store(flags, y, ptr, 12);
+        builder.ins().return_(&[]);
+        builder.seal_all_blocks();
+        builder.finalize();
+
+        let before: Vec<Inst> = func.layout.block_insts(block).collect();
+        schedule_function(&mut func);
+        let after: Vec<Inst> = func.layout.block_insts(block).collect();
+        assert_eq!(before, after);
+    }
+}
@@ -3,4 +3,4 @@
 pub mod builder;
 pub mod target;
 
-pub use target::Target;
+pub use target::{OptLevel, Target};
@@ -9,6 +9,37 @@ use cranelift_codegen::isa::OwnedTargetIsa;
 use cranelift_codegen::settings::{self, Configurable, Flags};
 use target_lexicon::Architecture;
 
+/// Cranelift optimization level
+///
+/// Trades compile time (and compiler memory) for runtime speed of the generated code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OptLevel {
+    /// No optimizations (Cranelift default, fastest compile)
+    #[default]
+    None,
+    /// Optimize for runtime speed
+    Speed,
+    /// Optimize for runtime speed and code size
+    SpeedAndSize,
+    /// Reorder instructions within each block to hide multiply, divide and load
+    /// latency on in-order cores (see [`crate::backend::schedule`])
+    ///
+    /// Cranelift's own optimizer stays off: it re-places pure instructions next to
+    /// their first use, which would undo the schedule.
+    Scheduled,
+}
+
+impl OptLevel {
+    /// Value of Cranelift's `opt_level` setting for this level
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OptLevel::None | OptLevel::Scheduled => "none",
+            OptLevel::Speed => "speed",
+            OptLevel::SpeedAndSize => "speed_and_size",
+        }
+    }
+}
+
 /// Semantic target enum - caller doesn't need to know implementation details
 #[derive(Clone)]
 pub enum Target {
@@ -17,6 +48,8 @@ pub enum Target {
         flags: Flags,
         /// Cached ISA (created lazily)
         isa: Option<OwnedTargetIsa>,
+        /// Schedule instructions for latency before codegen ([`OptLevel::Scheduled`])
+        schedule: bool,
     },
     /// Host JIT target (runs on current machine)
     HostJit {
@@ -26,6 +59,8 @@ pub enum Target {
         flags: Flags,
         /// Cached ISA (created lazily)
         isa: Option<OwnedTargetIsa>,
+        /// Schedule instructions for latency before codegen ([`OptLevel::Scheduled`])
+        schedule: bool,
     },
 }
 
@@ -35,6 +70,7 @@ impl Target {
         Ok(Self::Rv32Emu {
             flags: default_riscv32_flags()?,
             isa: None,
+            schedule: false,
         })
     }
 
@@ -45,6 +81,7 @@ impl Target {
             arch: None, // Auto-detect
             flags: default_host_flags()?,
             isa: None,
+            schedule: false,
         })
     }
 
@@ -56,9 +93,51 @@ impl Target {
             arch: Some(arch),
             flags: default_host_flags()?,
             isa: None,
+            schedule: false,
         })
     }
 
+    /// Return this target with Cranelift's `opt_level` replaced
+    ///
+    /// All other flags are preserved. The cached ISA is dropped so it gets rebuilt
+    /// with the new flags.
+    pub fn with_opt_level(self, opt_level: OptLevel) -> Result<Self, GlslError> {
+        let schedule = opt_level == OptLevel::Scheduled;
+        match self {
+            Target::Rv32Emu { flags, .. } => Ok(Target::Rv32Emu {
+                flags: flags_with_opt_level(&flags, opt_level)?,
+                isa: None,
+                schedule,
+            }),
+            Target::HostJit { arch, flags, .. } => Ok(Target::HostJit {
+                arch,
+                flags: flags_with_opt_level(&flags, opt_level)?,
+                isa: None,
+                schedule,
+            }),
+        }
+    }
+
+    /// Get the optimization level configured in this target
+    pub fn opt_level(&self) -> OptLevel {
+        let (flags, schedule) = match self {
+            Target::Rv32Emu {
+                flags, schedule, ..
+            }
+            | Target::HostJit {
+                flags, schedule, ..
+            } => (flags, *schedule),
+        };
+        if schedule {
+            return OptLevel::Scheduled;
+        }
+        match flags.opt_level() {
+            settings::OptLevel::None => OptLevel::None,
+            settings::OptLevel::Speed => OptLevel::Speed,
+            settings::OptLevel::SpeedAndSize => OptLevel::SpeedAndSize,
+        }
+    }
+
     /// Create or get cached ISA for this target
     pub fn create_isa(&mut self) -> Result<&OwnedTargetIsa, GlslError> {
         match self {
@@ -66,7 +145,7 @@ impl Target {
                 unused_variables,
                 reason = "Flags may be used in future ISA configuration"
             )]
-            Target::Rv32Emu { flags, isa } => {
+            Target::Rv32Emu { flags, isa, .. } => {
                 if isa.is_none() {
                     #[cfg(feature = "emulator")]
                     {
@@ -90,6 +169,7 @@ impl Target {
                 arch: _,
                 flags: _flags,
                 isa,
+                ..
             } => {
                 if isa.is_none() {
                     *isa = Some(create_host_isa(_flags.clone())?);
@@ -112,6 +192,26 @@ impl Target {
     }
 }
 
+/// Helper: Copy flags, overriding `opt_level`
+fn flags_with_opt_level(flags: &Flags, opt_level: OptLevel) -> Result<Flags, GlslError> {
+    let mut flag_builder = settings::builder();
+    for value in flags.iter() {
+        flag_builder
+            .set(value.name, &value.value_string())
+            .map_err(|e| {
+                GlslError::new(
+                    ErrorCode::E0400,
+                    format!("failed to copy flag {}: {e}", value.name),
+                )
+            })?;
+    }
+    flag_builder
+        .set("opt_level", opt_level.as_str())
+        .map_err(|e| GlslError::new(ErrorCode::E0400, format!("failed to set opt_level: {e}")))?;
+
+    Ok(settings::Flags::new(flag_builder))
+}
+
 /// Helper: Create default flags for RISC-V 32-bit target
 fn default_riscv32_flags() -> Result<Flags, GlslError> {
     let mut flag_builder = settings::builder();
@@ -271,6 +371,33 @@ mod tests {
         assert!(target.is_ok());
     }
 
+    #[test]
+    fn test_with_opt_level() {
+        let target = Target::riscv32_emulator().unwrap();
+        assert_eq!(target.opt_level(), OptLevel::None);
+
+        let target = target.with_opt_level(OptLevel::Speed).unwrap();
+        assert_eq!(target.opt_level(), OptLevel::Speed);
+
+        // Scheduling keeps Cranelift's optimizer off
+        let scheduled = target.clone().with_opt_level(OptLevel::Scheduled).unwrap();
+        assert_eq!(scheduled.opt_level(), OptLevel::Scheduled);
+        match &scheduled {
+            Target::Rv32Emu { flags, .. } | Target::HostJit { flags, .. } => {
+                assert_eq!(flags.opt_level(), settings::OptLevel::None);
+            }
+        }
+
+        // Other flags are preserved
+        match target {
+            Target::Rv32Emu { flags, .. } => {
+                assert!(flags.is_pic());
+                assert!(!flags.use_colocated_libcalls());
+            }
+            Target::HostJit { .. } => panic!("Expected Rv32Emu target"),
+        }
+    }
+
     #[test]
     #[cfg(feature = "emulator")]
     fn test_isa_creation() {
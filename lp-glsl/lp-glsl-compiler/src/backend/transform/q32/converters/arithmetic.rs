@@ -4,9 +4,9 @@ use crate::backend::transform::q32::converters::{
     create_zero_const, extract_binary_operands, extract_unary_operand, get_first_result,
     map_operand,
 };
-use crate::backend::transform::q32::types::FixedPointFormat;
+use crate::backend::transform::q32::types::{FixedPointFormat, NonFinitePolicy};
 use crate::error::GlslError;
-use cranelift_codegen::ir::{Function, Inst, InstBuilder, condcodes::IntCC, types};
+use cranelift_codegen::ir::{Function, Inst, InstBuilder, TrapCode, condcodes::IntCC, types};
 use cranelift_frontend::FunctionBuilder;
 use hashbrown::HashMap;
 
@@ -196,12 +196,18 @@ pub(crate) fn convert_fmul(
 }
 
 /// Convert Fdiv to fixed-point division by calling __lp_q32_div builtin.
+///
+/// Dividing by zero is where a float division produces a non-finite value (±Inf, or
+/// NaN for 0/0), so the quotient is handled according to `non_finite`. The builtin
+/// saturates to ±max, which [`NonFinitePolicy::Clamp`] keeps except for 0/0 (zero).
+/// With [`NonFinitePolicy::Trap`], a zero divisor traps with `TrapCode::user(2)`.
 pub(crate) fn convert_fdiv(
     old_func: &Function,
     old_inst: Inst,
     builder: &mut FunctionBuilder,
     value_map: &mut HashMap<cranelift_codegen::ir::Value, cranelift_codegen::ir::Value>,
-    _format: FixedPointFormat,
+    format: FixedPointFormat,
+    non_finite: NonFinitePolicy,
     func_id_map: &HashMap<alloc::string::String, cranelift_module::FuncId>,
 ) -> Result<(), GlslError> {
     use cranelift_codegen::ir::{AbiParam, ExtFuncData, ExternalName, Signature, UserExternalName};
@@ -246,9 +252,31 @@ pub(crate) fn convert_fdiv(
     };
     let div_func_ref = builder.func.import_function(ext_func);
 
+    let divisor_zero = builder.ins().icmp_imm(IntCC::Equal, arg2, 0);
+    if non_finite == NonFinitePolicy::Trap {
+        builder
+            .ins()
+            .trapnz(divisor_zero, TrapCode::user(2).unwrap());
+    }
+
     // Call __lp_q32_div with the mapped arguments
     let call_result = builder.ins().call(div_func_ref, &[arg1, arg2]);
-    let result = builder.inst_results(call_result)[0];
+    let quotient = builder.inst_results(call_result)[0];
+
+    let result = match non_finite {
+        NonFinitePolicy::Clamp => {
+            // 0/0 is NaN, which clamps to zero rather than saturating
+            let dividend_zero = builder.ins().icmp_imm(IntCC::Equal, arg1, 0);
+            let nan = builder.ins().band(divisor_zero, dividend_zero);
+            let zero = create_zero_const(builder, format);
+            builder.ins().select(nan, zero, quotient)
+        }
+        NonFinitePolicy::Zero => {
+            let zero = create_zero_const(builder, format);
+            builder.ins().select(divisor_zero, zero, quotient)
+        }
+        NonFinitePolicy::Trap => quotient,
+    };
 
     let old_result = get_first_result(old_func, old_inst);
     value_map.insert(old_result, result);
@@ -1,18 +1,25 @@
 //! Constant conversion functions.
 
-use crate::backend::transform::q32::types::{FixedPointFormat, float_to_fixed16x16};
+use crate::backend::transform::q32::types::{
+    FixedPointFormat, NonFinitePolicy, float_to_fixed16x16, non_finite_to_fixed16x16,
+};
 use crate::error::{ErrorCode, GlslError};
-use cranelift_codegen::ir::{Function, Inst, InstBuilder, InstructionData};
+use cranelift_codegen::ir::{Function, Inst, InstBuilder, InstructionData, TrapCode, types};
 use cranelift_frontend::FunctionBuilder;
 use hashbrown::HashMap;
 
 /// Convert F32const to iconst with fixed-point value.
+///
+/// NaN and infinite constants are handled according to `non_finite`. With
+/// [`NonFinitePolicy::Trap`], a `trapnz` with `TrapCode::user(2)` is emitted and
+/// the constant becomes zero.
 pub(crate) fn convert_f32const(
     old_func: &Function,
     old_inst: Inst,
     builder: &mut FunctionBuilder,
     value_map: &mut HashMap<cranelift_codegen::ir::Value, cranelift_codegen::ir::Value>,
     format: FixedPointFormat,
+    non_finite: NonFinitePolicy,
 ) -> Result<(), GlslError> {
     // Get the float constant value
     let inst_data = &old_func.dfg.insts[old_inst];
@@ -33,7 +40,17 @@ pub(crate) fn convert_f32const(
     // Convert to fixed-point
     let target_type = format.cranelift_type();
     let fixed_value = match format {
-        FixedPointFormat::Fixed16x16 => float_to_fixed16x16(f32_value) as i64,
+        FixedPointFormat::Fixed16x16 if f32_value.is_finite() => {
+            float_to_fixed16x16(f32_value) as i64
+        }
+        FixedPointFormat::Fixed16x16 => match non_finite_to_fixed16x16(f32_value, non_finite) {
+            Some(value) => value as i64,
+            None => {
+                let one = builder.ins().iconst(types::I8, 1);
+                builder.ins().trapnz(one, TrapCode::user(2).unwrap());
+                0
+            }
+        },
         FixedPointFormat::Q32x32 => {
             return Err(GlslError::new(
                 ErrorCode::E0301,
@@ -57,6 +74,132 @@ pub(crate) fn convert_f32const(
 mod tests {
     #[cfg(feature = "emulator")]
     use crate::backend::transform::q32::q32_test_util;
+    use crate::backend::transform::q32::{FixedPointFormat, NonFinitePolicy, Q32Transform};
+    use crate::backend::transform::shared::transform_test_util;
+    use cranelift_codegen::ir::{Function, InstructionData, Opcode};
+
+    const NAN_CLIF: &str = r#"
+function %main() -> f32 system_v {
+block0:
+    v0 = f32const +NaN
+    return v0
+}
+"#;
+
+    const INF_CLIF: &str = r#"
+function %main() -> f32 system_v {
+block0:
+    v0 = f32const +Inf
+    return v0
+}
+"#;
+
+    fn transform_with_policy(clif: &str, policy: NonFinitePolicy) -> Function {
+        let transform =
+            Q32Transform::new(FixedPointFormat::Fixed16x16).with_non_finite_policy(policy);
+        transform_test_util::transform_function(clif, "main", transform)
+    }
+
+    /// Fixed-point constant returned by a single-block function
+    fn returned_const(func: &Function) -> i32 {
+        let block = func.layout.last_block().unwrap();
+        let ret = func.layout.last_inst(block).unwrap();
+        let value = func.dfg.resolve_aliases(func.dfg.inst_args(ret)[0]);
+        let def = func.dfg.value_def(value).unwrap_inst();
+        match func.dfg.insts[def] {
+            InstructionData::UnaryImm {
+                opcode: Opcode::Iconst,
+                imm,
+            } => imm.bits() as i32,
+            ref other => panic!("expected iconst, got {other:?}"),
+        }
+    }
+
+    fn has_trap(func: &Function) -> bool {
+        func.layout
+            .blocks()
+            .flat_map(|block| func.layout.block_insts(block))
+            .any(|inst| func.dfg.insts[inst].opcode() == Opcode::Trapnz)
+    }
+
+    fn has_select(func: &Function) -> bool {
+        func.layout
+            .blocks()
+            .flat_map(|block| func.layout.block_insts(block))
+            .any(|inst| func.dfg.insts[inst].opcode() == Opcode::Select)
+    }
+
+    const FDIV_CLIF: &str = r#"
+function %main(f32, f32) -> f32 system_v {
+block0(v0: f32, v1: f32):
+    v2 = fdiv v0, v1
+    return v2
+}
+"#;
+
+    #[test]
+    fn test_runtime_division_by_zero_follows_policy() {
+        for policy in [NonFinitePolicy::Clamp, NonFinitePolicy::Zero] {
+            let func = transform_with_policy(FDIV_CLIF, policy);
+            assert!(
+                has_select(&func),
+                "{policy:?} should select on a zero divisor"
+            );
+            assert!(!has_trap(&func));
+        }
+
+        let func = transform_with_policy(FDIV_CLIF, NonFinitePolicy::Trap);
+        assert!(has_trap(&func));
+        assert!(!has_select(&func));
+    }
+
+    #[test]
+    fn test_non_finite_clamp() {
+        let func = transform_with_policy(INF_CLIF, NonFinitePolicy::Clamp);
+        assert_eq!(returned_const(&func), i32::MAX);
+        assert!(!has_trap(&func));
+
+        let func = transform_with_policy(NAN_CLIF, NonFinitePolicy::Clamp);
+        assert_eq!(returned_const(&func), 0);
+        assert!(!has_trap(&func));
+    }
+
+    #[test]
+    fn test_non_finite_zero() {
+        for clif in [NAN_CLIF, INF_CLIF] {
+            let func = transform_with_policy(clif, NonFinitePolicy::Zero);
+            assert_eq!(returned_const(&func), 0);
+            assert!(!has_trap(&func));
+        }
+    }
+
+    #[test]
+    fn test_non_finite_trap() {
+        for clif in [NAN_CLIF, INF_CLIF] {
+            let func = transform_with_policy(clif, NonFinitePolicy::Trap);
+            assert!(has_trap(&func));
+        }
+    }
+
+    #[test]
+    fn test_finite_constant_unaffected_by_policy() {
+        let clif = r#"
+function %main() -> f32 system_v {
+block0:
+    v0 = f32const 0x1.0p1
+    return v0
+}
+"#;
+        for policy in [
+            NonFinitePolicy::Clamp,
+            NonFinitePolicy::Zero,
+            NonFinitePolicy::Trap,
+        ] {
+            let func = transform_with_policy(clif, policy);
+            assert_eq!(returned_const(&func), 2 << 16);
+            assert!(!has_trap(&func));
+        }
+    }
 
     /// Test fconst: constant conversion
     #[test]
@@ -1,7 +1,7 @@
 //! Instruction conversion routing for fixed-point transformation.
 
 use crate::backend::transform::q32::converters;
-use crate::backend::transform::q32::types::FixedPointFormat;
+use crate::backend::transform::q32::types::{FixedPointFormat, NonFinitePolicy};
 use crate::backend::transform::shared::copy_instruction;
 use crate::error::GlslError;
 use alloc::string::String;
@@ -34,6 +34,7 @@ pub(crate) fn convert_all_instructions(
     builder: &mut FunctionBuilder,
     value_map: &mut HashMap<Value, Value>,
     format: FixedPointFormat,
+    non_finite: NonFinitePolicy,
     block_map: &HashMap<Block, Block>,
     stack_slot_map: Option<&HashMap<StackSlot, StackSlot>>,
     call_state: &mut CallConversionState,
@@ -47,6 +48,7 @@ pub(crate) fn convert_all_instructions(
         builder,
         value_map,
         format,
+        non_finite,
         block_map,
         stack_slot_map,
         call_state,
@@ -63,6 +65,7 @@ fn convert_instruction(
     builder: &mut FunctionBuilder,
     value_map: &mut HashMap<Value, Value>,
     format: FixedPointFormat,
+    non_finite: NonFinitePolicy,
     block_map: &HashMap<Block, Block>,
     stack_slot_map: Option<&HashMap<StackSlot, StackSlot>>,
     call_state: &mut CallConversionState,
@@ -82,7 +85,7 @@ fn convert_instruction(
     match opcode {
         Opcode::F32const => {
             converters::constants::convert_f32const(
-                old_func, old_inst, builder, value_map, format,
+                old_func, old_inst, builder, value_map, format, non_finite,
             )?;
         }
         Opcode::Fadd => {
@@ -122,6 +125,7 @@ fn convert_instruction(
                 builder,
                 value_map,
                 format,
+                non_finite,
                 func_id_map,
             )?;
         }
@@ -14,4 +14,4 @@ mod types;
 mod q32_test_util;
 
 pub use transform::Q32Transform;
-pub use types::FixedPointFormat;
+pub use types::{FixedPointFormat, NonFinitePolicy};
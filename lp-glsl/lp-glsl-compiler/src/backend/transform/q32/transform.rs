@@ -3,7 +3,7 @@
 use crate::backend::transform::pipeline::{Transform, TransformContext};
 use crate::backend::transform::q32::instructions::convert_all_instructions;
 use crate::backend::transform::q32::signature::convert_signature;
-use crate::backend::transform::q32::types::FixedPointFormat;
+use crate::backend::transform::q32::types::{FixedPointFormat, NonFinitePolicy};
 use crate::backend::transform::shared::transform_function_body;
 use crate::error::GlslError;
 use cranelift_codegen::ir::{Function, Signature};
@@ -12,12 +12,22 @@ use cranelift_module::Module;
 /// Q32 transform - converts F32 to fixed-point representation
 pub struct Q32Transform {
     format: FixedPointFormat,
+    non_finite: NonFinitePolicy,
 }
 
 impl Q32Transform {
     /// Create a new Q32 transform with the specified format
     pub fn new(format: FixedPointFormat) -> Self {
-        Self { format }
+        Self {
+            format,
+            non_finite: NonFinitePolicy::default(),
+        }
+    }
+
+    /// Set how NaN and infinite float values (constants and division by zero) are converted
+    pub fn with_non_finite_policy(mut self, policy: NonFinitePolicy) -> Self {
+        self.non_finite = policy;
+        self
     }
 
     /// Create a Q32 transform with default format (Fixed16x16)
@@ -39,6 +49,7 @@ impl Transform for Q32Transform {
         // 1. Convert signature (happens before transform_function_body)
         let new_sig = convert_signature(&old_func.signature, self.format);
         let format = self.format;
+        let non_finite = self.non_finite;
 
         // 2. Get pointer type from module ISA (needed for builtin signatures)
         let pointer_type = ctx.module.module_internal().isa().pointer_type();
@@ -67,6 +78,7 @@ impl Transform for Q32Transform {
                     builder,
                     value_map,
                     format,
+                    non_finite,
                     block_map,
                     stack_slot_map,
                     &mut *call_state.borrow_mut(),
@@ -33,6 +33,35 @@ impl FixedPointFormat {
     }
 }
 
+/// How the transform handles non-finite (NaN or infinite) float values
+///
+/// Fixed-point values have no encoding for NaN or infinity. They appear as float
+/// constants in the input IR, and at runtime as the result of dividing by zero
+/// (±Inf, or NaN for 0/0). The policy decides what they become in both cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonFinitePolicy {
+    /// +Inf becomes the maximum representable value, -Inf the minimum, NaN zero
+    #[default]
+    Clamp,
+    /// NaN and infinities become zero
+    Zero,
+    /// Trap at runtime when the value is produced
+    Trap,
+}
+
+/// Convert a non-finite float32 value to fixed16x16 according to `policy`.
+///
+/// Returns `None` for [`NonFinitePolicy::Trap`], where there is no value to produce.
+pub fn non_finite_to_fixed16x16(f: f32, policy: NonFinitePolicy) -> Option<i32> {
+    match policy {
+        NonFinitePolicy::Clamp if f.is_nan() => Some(0),
+        NonFinitePolicy::Clamp if f.is_sign_positive() => Some(i32::MAX),
+        NonFinitePolicy::Clamp => Some(i32::MIN),
+        NonFinitePolicy::Zero => Some(0),
+        NonFinitePolicy::Trap => None,
+    }
+}
+
 /// Convert a float32 value to fixed16x16 representation.
 ///
 /// Fixed16x16 format uses 16 integer bits and 16 fractional bits.
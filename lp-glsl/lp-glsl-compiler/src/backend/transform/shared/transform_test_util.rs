@@ -94,6 +94,37 @@ fn parse_and_transform<T: Transform>(clif_input: &str, transform: T) -> (String,
     (parsed_buf, transformed_buf)
 }
 
+/// Parse CLIF module input, transform it, and return the transformed function `name`
+pub fn transform_function<T: Transform>(
+    clif_input: &str,
+    name: &str,
+    transform: T,
+) -> cranelift_codegen::ir::Function {
+    let test_file =
+        parse_test(clif_input, ParseOptions::default()).expect("Failed to parse CLIF module");
+
+    let target = crate::backend::target::Target::host_jit().unwrap();
+    let mut module =
+        crate::backend::module::gl_module::GlModule::<cranelift_jit::JITModule>::new_jit(target)
+            .unwrap();
+    for (func, _) in test_file.functions {
+        let func_name = format!("{}", func.name);
+        let func_name = func_name.strip_prefix('%').unwrap_or(&func_name);
+        module
+            .add_function(func_name, Linkage::Local, func.signature.clone(), func)
+            .expect("Failed to add function to module");
+    }
+
+    let transformed_module = module
+        .apply_transform(transform)
+        .expect("Failed to apply transform");
+    transformed_module
+        .get_func(name)
+        .expect("Function not found in transformed module")
+        .function
+        .clone()
+}
+
 /// Assert that identity transform produces identical CLIF output
 pub fn assert_identity_transform(message: &str, clif_input: &str) {
     let (parsed_buf, transformed_buf) = parse_and_transform(clif_input, IdentityTransform);
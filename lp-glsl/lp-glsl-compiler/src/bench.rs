@@ -0,0 +1,273 @@
+//! Compile-time and throughput benchmark harness
+//!
+//! Compiles a fixed set of representative shaders to RISC-V code with [`Compiler`] and
+//! reports how long each took and how much code it produced, so compile-time and
+//! code-size regressions show up when comparing runs. Run it with
+//! `cargo bench -p lp-glsl-compiler --features bench --bench compile`.
+//!
+//! The throughput benchmark renders the same shaders over a fixed number of pixels in
+//! the RISC-V emulator and with the host JIT, and reports pixels per second for each,
+//! to show when emulation is fast enough. Run it with
+//! `cargo bench -p lp-glsl-compiler --features bench --bench throughput`.
+
+use crate::Compiler;
+use crate::backend::target::OptLevel;
+use crate::error::GlslError;
+use crate::exec::test_util::compile_shader;
+use crate::exec::{DecimalFormat, GlslExecutable, GlslOptions, GlslValue};
+use crate::frontend::glsl_jit;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use anyhow::Result;
+use core::fmt;
+use std::time::{Duration, Instant};
+
+/// A shader in the benchmark set
+#[derive(Debug, Clone, Copy)]
+pub struct BenchShader {
+    pub name: &'static str,
+    pub source: &'static str,
+}
+
+/// Representative shaders, from a trivial gradient to loops and helper functions
+pub const SHADERS: &[BenchShader] = &[
+    BenchShader {
+        name: "gradient",
+        source: r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    return vec4(uv, 0.5, 1.0);
+}
+"#,
+    },
+    BenchShader {
+        name: "trig",
+        source: r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    float r = sin(uv.x * 6.0 + time) * 0.5 + 0.5;
+    float g = cos(uv.y * 4.0 - time) * 0.5 + 0.5;
+    float b = sin(length(uv - vec2(0.5)) * 10.0 - time * 2.0) * 0.5 + 0.5;
+    return vec4(r, g, b, 1.0);
+}
+"#,
+    },
+    BenchShader {
+        name: "loops",
+        source: r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    float sum = 0.0;
+    for (int i = 0; i < 8; i++) {
+        for (int j = 0; j < 4; j++) {
+            sum += sin(uv.x * float(i) + uv.y * float(j) + time) * 0.03;
+        }
+    }
+    return vec4(sum, sum * 0.5, 1.0 - sum, 1.0);
+}
+"#,
+    },
+    BenchShader {
+        name: "functions",
+        source: r#"
+float wave(float x, float speed, float time) {
+    return sin(x * speed + time) * 0.5 + 0.5;
+}
+
+vec3 palette(float t) {
+    return vec3(wave(t, 1.0, 0.0), wave(t, 2.0, 1.0), wave(t, 3.0, 2.0));
+}
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    float t = wave(uv.x + uv.y, 4.0, time);
+    return vec4(palette(t), 1.0);
+}
+"#,
+    },
+];
+
+/// Measurements for one shader
+#[derive(Debug, Clone, Copy)]
+pub struct BenchResult {
+    pub name: &'static str,
+    /// Times the shader was compiled
+    pub iterations: u32,
+    /// Fastest compile
+    pub min_time: Duration,
+    /// Average compile
+    pub mean_time: Duration,
+    /// Size of the emitted machine code in bytes
+    pub code_size: usize,
+}
+
+impl fmt::Display for BenchResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<12} min {:>9.3} ms  mean {:>9.3} ms  code {:>6} bytes  ({} runs)",
+            self.name,
+            self.min_time.as_secs_f64() * 1000.0,
+            self.mean_time.as_secs_f64() * 1000.0,
+            self.code_size,
+            self.iterations
+        )
+    }
+}
+
+/// Compile `shader` `iterations` times (at least once) and measure it
+pub fn bench_shader(
+    compiler: &mut Compiler,
+    shader: &BenchShader,
+    iterations: u32,
+    opt_level: OptLevel,
+) -> Result<BenchResult, GlslError> {
+    let iterations = iterations.max(1);
+    let mut min_time = Duration::MAX;
+    let mut total_time = Duration::ZERO;
+    let mut code_size = 0;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let code = compiler.compile_to_code_with_opts(shader.source, opt_level)?;
+        let elapsed = start.elapsed();
+
+        min_time = min_time.min(elapsed);
+        total_time += elapsed;
+        code_size = code.len();
+    }
+
+    Ok(BenchResult {
+        name: shader.name,
+        iterations,
+        min_time,
+        mean_time: total_time / iterations,
+        code_size,
+    })
+}
+
+/// Measure every shader in [`SHADERS`]
+pub fn run(iterations: u32, opt_level: OptLevel) -> Result<Vec<BenchResult>, GlslError> {
+    let mut compiler = Compiler::new();
+    SHADERS
+        .iter()
+        .map(|shader| bench_shader(&mut compiler, shader, iterations, opt_level))
+        .collect()
+}
+
+/// Where a shader runs in the throughput benchmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// RISC-V code in the emulator, as on devices without native execution
+    Emulator,
+    /// Native code from the host JIT
+    HostJit,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Emulator => f.pad("emulator"),
+            Backend::HostJit => f.pad("host jit"),
+        }
+    }
+}
+
+/// Time taken to render one shader over a fixed pixel count
+#[derive(Debug, Clone)]
+pub struct ThroughputResult {
+    pub name: &'static str,
+    pub backend: Backend,
+    /// Pixels rendered
+    pub pixels: u32,
+    /// Time spent running the shader, not counting compilation
+    pub elapsed: Duration,
+    /// Rendered pixels as RGBA8, row by row
+    pub output: Vec<[u8; 4]>,
+}
+
+impl ThroughputResult {
+    pub fn pixels_per_sec(&self) -> f64 {
+        // A tiny workload can finish within the clock's resolution
+        let elapsed = self.elapsed.max(Duration::from_nanos(1));
+        self.pixels as f64 / elapsed.as_secs_f64()
+    }
+}
+
+impl fmt::Display for ThroughputResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<12} {:<9} {:>14.0} pixels/s  ({} pixels in {:.3} ms)",
+            self.name,
+            self.backend,
+            self.pixels_per_sec(),
+            self.pixels,
+            self.elapsed.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/// Render `shader` over a `width` x `height` frame on `backend` and measure it
+///
+/// Both backends run the same Q32 code, so they produce the same pixels.
+pub fn bench_throughput(
+    shader: &BenchShader,
+    backend: Backend,
+    width: u32,
+    height: u32,
+) -> Result<ThroughputResult> {
+    let mut executable: Box<dyn GlslExecutable> = match backend {
+        Backend::Emulator => compile_shader(shader.source)?,
+        Backend::HostJit => {
+            let options = GlslOptions {
+                decimal_format: DecimalFormat::Q32,
+                ..GlslOptions::jit()
+            };
+            glsl_jit(shader.source, options).map_err(|e| anyhow::anyhow!("{e:#}"))?
+        }
+    };
+
+    let output_size = GlslValue::Vec2([width as f32, height as f32]);
+    let time = GlslValue::F32(1.0);
+    let mut output = Vec::with_capacity((width * height) as usize);
+
+    let start = Instant::now();
+    for y in 0..height {
+        for x in 0..width {
+            let args = [
+                GlslValue::Vec2([x as f32, y as f32]),
+                output_size.clone(),
+                time.clone(),
+            ];
+            let rgba = executable
+                .call_vec("main", &args, 4)
+                .map_err(|e| anyhow::anyhow!("{e:#}"))?;
+            if rgba.len() != 4 {
+                anyhow::bail!("main() must return vec4, got {} components", rgba.len());
+            }
+            output.push([0, 1, 2, 3].map(|i| (rgba[i].clamp(0.0, 1.0) * 255.0) as u8));
+        }
+    }
+    let elapsed = start.elapsed();
+
+    Ok(ThroughputResult {
+        name: shader.name,
+        backend,
+        pixels: width * height,
+        elapsed,
+        output,
+    })
+}
+
+/// Measure every shader in [`SHADERS`] on both backends
+pub fn run_throughput(width: u32, height: u32) -> Result<Vec<ThroughputResult>> {
+    let mut results = Vec::new();
+    for shader in SHADERS {
+        for backend in [Backend::Emulator, Backend::HostJit] {
+            results.push(bench_throughput(shader, backend, width, height)?);
+        }
+    }
+    Ok(results)
+}
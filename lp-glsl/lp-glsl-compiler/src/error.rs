@@ -19,6 +19,7 @@ use core::fmt;
 /// - E0100-E0299: Semantic/type errors
 /// - E0300-E0399: Transform errors
 /// - E0400-E0499: Codegen errors
+/// - W0001-W0099: Warnings
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ErrorCode {
     // Parse errors (E0001-E0099)
@@ -60,6 +61,10 @@ pub enum ErrorCode {
     E0115,
     /// Return type mismatch
     E0116,
+    /// Shader exceeds the complexity budget
+    E0117,
+    /// Invalid constant expression
+    E0118,
 
     // Transform errors (E0300-E0399)
     /// Fixed-point transformation error
@@ -72,6 +77,10 @@ pub enum ErrorCode {
     E0400,
     /// Verification error
     E0401,
+
+    // Warnings (W0001-W0099)
+    /// Variable is declared but never used
+    W0001,
 }
 
 impl ErrorCode {
@@ -96,10 +105,13 @@ impl ErrorCode {
             ErrorCode::E0114 => "E0114",
             ErrorCode::E0115 => "E0115",
             ErrorCode::E0116 => "E0116",
+            ErrorCode::E0117 => "E0117",
+            ErrorCode::E0118 => "E0118",
             ErrorCode::E0300 => "E0300",
             ErrorCode::E0301 => "E0301",
             ErrorCode::E0400 => "E0400",
             ErrorCode::E0401 => "E0401",
+            ErrorCode::W0001 => "W0001",
         }
     }
 
@@ -124,10 +136,13 @@ impl ErrorCode {
             ErrorCode::E0114 => "no matching function",
             ErrorCode::E0115 => "cannot assign",
             ErrorCode::E0116 => "return type mismatch",
+            ErrorCode::E0117 => "complexity budget exceeded",
+            ErrorCode::E0118 => "invalid constant expression",
             ErrorCode::E0300 => "transformation error",
             ErrorCode::E0301 => "verification failed",
             ErrorCode::E0400 => "codegen error",
             ErrorCode::E0401 => "verification error",
+            ErrorCode::W0001 => "unused variable",
         }
     }
 }
@@ -212,8 +227,14 @@ impl GlslError {
 
 impl fmt::Display for GlslError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_with_severity(f, Severity::Error)
+    }
+}
+
+impl GlslError {
+    fn fmt_with_severity(&self, f: &mut fmt::Formatter, severity: Severity) -> fmt::Result {
         // Format: error[E0100]: message
-        write!(f, "error[{}]: {}", self.code, self.message)?;
+        write!(f, "{}[{}]: {}", severity, self.code, self.message)?;
 
         // Add location if available
         if let Some(ref loc) = self.location {
@@ -256,6 +277,77 @@ impl fmt::Display for GlslError {
 #[cfg(feature = "std")]
 impl std::error::Error for GlslError {}
 
+/// How serious a [`Diagnostic`] is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Suspicious code that still compiles
+    Warning,
+    /// Code that can't be compiled
+    Error,
+}
+
+impl Severity {
+    /// Get the severity as a string (e.g., "warning").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A compiler message with a severity.
+///
+/// Warnings are reported alongside a successful compile. Strict mode (see
+/// `GlslOptions::warnings_as_errors`) turns them into errors with [`Diagnostic::into_error`].
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Code, message, location and notes
+    pub details: GlslError,
+}
+
+impl Diagnostic {
+    /// Create a warning from its details.
+    pub fn warning(details: GlslError) -> Self {
+        Self {
+            severity: Severity::Warning,
+            details,
+        }
+    }
+
+    /// Whether this diagnostic stops compilation.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// Escalate to an error, regardless of severity.
+    pub fn into_error(self) -> GlslError {
+        self.details
+    }
+}
+
+impl From<GlslError> for Diagnostic {
+    fn from(details: GlslError) -> Self {
+        Self {
+            severity: Severity::Error,
+            details,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.details.fmt_with_severity(f, self.severity)
+    }
+}
+
 // Convenience constructors for common errors
 
 impl GlslError {
@@ -513,6 +605,21 @@ mod tests {
         assert!(display.contains("5:10"));
     }
 
+    #[test]
+    fn test_diagnostic_severity() {
+        let warning = Diagnostic::warning(GlslError::new(ErrorCode::W0001, "unused variable `x`"));
+        assert!(!warning.is_error());
+        assert!(
+            warning
+                .to_string()
+                .starts_with("warning[W0001]: unused variable `x`")
+        );
+
+        let error = warning.into_error();
+        assert!(error.to_string().starts_with("error[W0001]"));
+        assert!(Diagnostic::from(error).is_error());
+    }
+
     #[test]
     fn test_glsl_error_with_note() {
         let err = GlslError::undefined_variable("foo").with_note("did you mean `bar`?");
@@ -7,6 +7,7 @@ use crate::error::GlslError;
 use crate::exec::executable::GlslExecutable;
 use crate::exec::glsl_value::GlslValue;
 use crate::frontend::semantic::functions::FunctionSignature;
+use crate::frontend::semantic::passes::purity::Purity;
 use crate::frontend::src_loc::GlSourceMap;
 use hashbrown::HashMap;
 use lp_riscv_emu::{EmulatorError, trap_code_to_string};
@@ -21,6 +22,8 @@ use alloc::{format, string::String, vec::Vec};
 pub struct GlslEmulatorModule {
     pub(crate) emulator: lp_riscv_emu::Riscv32Emulator,
     pub(crate) signatures: HashMap<String, FunctionSignature>,
+    // Which functions are pure or read time
+    pub(crate) purity: Purity,
     // Store Cranelift signatures for proper function calling with arguments
     pub(crate) cranelift_signatures: HashMap<String, cranelift_codegen::ir::Signature>,
     pub(crate) binary: Vec<u8>,
@@ -45,6 +48,8 @@ pub struct GlslEmulatorModule {
     pub(crate) source_loc_manager: crate::frontend::src_loc_manager::SourceLocManager,
     // Source map for managing file locations
     pub(crate) source_map: GlSourceMap,
+    // Uniform values, mirrored into guest RAM at the block's guest address
+    pub(crate) uniforms: crate::backend::module::uniforms::UniformBlock,
     // Track next buffer allocation address (allocated from start of RAM, growing upward)
     #[allow(
         dead_code,
@@ -1829,6 +1834,43 @@ impl GlslExecutable for GlslEmulatorModule {
         self.signatures.keys().cloned().collect()
     }
 
+    fn is_pure(&self, name: &str) -> bool {
+        self.purity.is_pure(name)
+    }
+
+    fn reads_time(&self, name: &str) -> bool {
+        self.purity.reads_time(name)
+    }
+
+    fn set_uniform(&mut self, name: &str, value: GlslValue) -> Result<(), GlslError> {
+        use crate::error::ErrorCode;
+
+        self.uniforms.set(name, &value)?;
+
+        // Compiled code reads the block from guest RAM, so copy the new values there
+        const DEFAULT_RAM_START: usize = 0x80000000;
+        let bytes = self.uniforms.to_le_bytes();
+        let offset = self.uniforms.base_address() - DEFAULT_RAM_START;
+        let ram = self.emulator.memory_mut().ram_mut();
+        let ram_size = ram.len();
+        let target = ram.get_mut(offset..offset + bytes.len()).ok_or_else(|| {
+            GlslError::new(
+                ErrorCode::E0400,
+                format!("uniform block does not fit in {ram_size} bytes of RAM"),
+            )
+        })?;
+        target.copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    fn list_uniforms(&self) -> Vec<String> {
+        self.uniforms
+            .entries()
+            .iter()
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
     #[cfg(feature = "std")]
     fn format_emulator_state(&self) -> Option<String> {
         let state_dump = self.emulator.dump_state();
@@ -3,14 +3,17 @@
 //! This module provides a trait-based API for executing GLSL functions that
 //! abstracts away JIT vs Emulator implementations.
 
+use crate::backend::target::OptLevel;
 use crate::error::GlslError;
 use crate::exec::glsl_value::GlslValue;
+use crate::frontend::semantic::complexity::ComplexityBudget;
 use crate::frontend::semantic::functions::FunctionSignature;
 
 use alloc::{format, string::String, vec::Vec};
 
 /// Information needed for direct function pointer calls
-/// Contains the function pointer and calling convention details
+/// Contains the function pointer and calling convention details.
+/// The pointer is only valid while the executable it came from is alive.
 #[derive(Clone, Copy)]
 pub struct DirectCallInfo {
     /// Raw function pointer
@@ -24,9 +27,9 @@ pub struct DirectCallInfo {
 /// Trait for executing GLSL functions with various return types
 /// Abstracts away JIT vs Emulator implementations
 ///
-/// **Current State**: Supports basic function calling with in-parameters only.
+/// **Current State**: Supports basic function calling with in-parameters only,
+/// plus uniform variables on JIT modules.
 /// Future extensions will add:
-/// - Uniform variables (`set_uniform`, `get_uniform`, `list_uniforms`)
 /// - Texture/sampler binding (`bind_texture`, `bind_sampler`)
 /// - Built-in variables (`set_builtin`, e.g., `gl_Position`, `gl_FragCoord`)
 /// - `out` and `inout` parameters
@@ -99,6 +102,21 @@ pub trait GlslExecutable {
     /// List all available function names
     fn list_functions(&self) -> Vec<String>;
 
+    /// Whether a function's result depends only on its arguments (it reads no
+    /// uniforms, including `u_time`, nor `main`'s time argument), so its output can
+    /// be cached.
+    /// False for unknown functions and executables that don't track purity.
+    fn is_pure(&self, _name: &str) -> bool {
+        false
+    }
+
+    /// Whether a function reads `main`'s time argument, `u_time` or `u_frame`, so its
+    /// output changes from frame to frame even when no uniform is set.
+    /// True for unknown functions and executables that don't track purity.
+    fn reads_time(&self, _name: &str) -> bool {
+        true
+    }
+
     /// Get emulator state as a formatted string, if this is an emulator module.
     /// Returns None for non-emulator implementations (e.g., JIT).
     #[cfg(feature = "std")]
@@ -132,10 +150,37 @@ pub trait GlslExecutable {
         None
     }
 
+    /// Set the value of a `uniform` variable.
+    /// The new value is seen by all subsequent calls without recompiling.
+    fn set_uniform(&mut self, name: &str, _value: GlslValue) -> Result<(), GlslError> {
+        Err(GlslError::new(
+            crate::error::ErrorCode::E0400,
+            format!("uniform `{name}` cannot be set on this executable"),
+        ))
+    }
+
+    /// List all uniform variable names
+    fn list_uniforms(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Set how many loop iterations the compiled code may run before its loops bail out.
+    /// The budget is shared by all subsequent calls until it is set again.
+    fn set_fuel(&mut self, _fuel: u32) -> Result<(), GlslError> {
+        Err(GlslError::new(
+            crate::error::ErrorCode::E0400,
+            "loop fuel cannot be set on this executable",
+        ))
+    }
+
+    /// Loop iterations left, or None if the module was compiled without loop fuel.
+    /// Zero means loops were cut short, so results of the calls since the last
+    /// `set_fuel` can't be trusted (this includes a budget spent exactly).
+    fn fuel_remaining(&self) -> Option<u32> {
+        None
+    }
+
     // TODO: Future extensions:
-    // fn set_uniform(&mut self, name: &str, value: GlslValue) -> Result<(), GlslError>;
-    // fn get_uniform(&self, name: &str) -> Option<&GlslValue>;
-    // fn list_uniforms(&self) -> Vec<String>;
     // fn bind_texture(&mut self, unit: u32, texture: Texture) -> Result<(), GlslError>;
     // fn bind_sampler(&mut self, unit: u32, sampler: Sampler) -> Result<(), GlslError>;
     // fn set_builtin(&mut self, name: &str, value: GlslValue) -> Result<(), GlslError>;
@@ -175,6 +220,15 @@ pub enum DecimalFormat {
 pub struct GlslOptions {
     pub run_mode: RunMode,
     pub decimal_format: DecimalFormat,
+    /// Cranelift optimization level
+    pub opt_level: OptLevel,
+    /// Reject shaders over this budget before codegen (no limit if None)
+    pub complexity_budget: Option<ComplexityBudget>,
+    /// Check a fuel counter on every loop back-edge, starting with this many
+    /// iterations (JIT and interpreter only; loops are unchecked if None)
+    pub loop_fuel: Option<u32>,
+    /// Fail compilation on warnings (strict mode)
+    pub warnings_as_errors: bool,
 }
 
 impl GlslOptions {
@@ -182,6 +236,13 @@ impl GlslOptions {
         use crate::error::{ErrorCode, GlslError};
         use target_lexicon::Triple;
 
+        if self.loop_fuel.is_some() && matches!(self.run_mode, RunMode::Emulator { .. }) {
+            return Err(GlslError::new(
+                ErrorCode::E0400,
+                "Loop fuel is only supported for JIT execution (the emulator limits instructions instead)",
+            ));
+        }
+
         // Validate option combinations
         match (&self.run_mode, self.decimal_format) {
             (RunMode::Emulator { .. }, DecimalFormat::Float) => {
@@ -213,6 +274,10 @@ impl GlslOptions {
         Self {
             run_mode: RunMode::HostJit,
             decimal_format: DecimalFormat::Float,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         }
     }
 
@@ -227,9 +292,37 @@ impl GlslOptions {
                 log_level: None,
             },
             decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         }
     }
 
+    /// Set the Cranelift optimization level
+    pub fn with_opt_level(mut self, opt_level: OptLevel) -> Self {
+        self.opt_level = opt_level;
+        self
+    }
+
+    /// Reject shaders that exceed `budget` before attempting codegen
+    pub fn with_complexity_budget(mut self, budget: ComplexityBudget) -> Self {
+        self.complexity_budget = Some(budget);
+        self
+    }
+
+    /// Make loops bail out after `fuel` iterations (see [`GlslExecutable::set_fuel`])
+    pub fn with_loop_fuel(mut self, fuel: u32) -> Self {
+        self.loop_fuel = Some(fuel);
+        self
+    }
+
+    /// Fail compilation on warnings instead of just reporting them
+    pub fn with_warnings_as_errors(mut self) -> Self {
+        self.warnings_as_errors = true;
+        self
+    }
+
     /// Convenience constructor for RISC-V 32-bit IMA(C) emulator
     /// Uses 1MB RAM, 64KB stack, and Q32 format
     #[cfg(feature = "emulator")]
@@ -242,6 +335,10 @@ impl GlslOptions {
                 max_instructions: 10_000,
             },
             decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         }
     }
 }
@@ -0,0 +1,278 @@
+//! Built-in function evaluation for the AST interpreter
+//!
+//! Argument types are checked against the same signature table the compiler
+//! uses, so evaluation only has to handle combinations that table accepts.
+
+use super::eval::{scalar_cmp, scalar_eq};
+use super::value::{Scalar, Value, base_type, component_len, unsupported};
+use crate::error::{ErrorCode, GlslError};
+use crate::frontend::semantic::builtins::check_builtin_call;
+use crate::frontend::semantic::types::Type;
+use core::cmp::Ordering;
+
+use alloc::{format, vec::Vec};
+
+/// Call built-in function `name`
+pub(crate) fn call(name: &str, args: &[Value]) -> Result<Value, GlslError> {
+    let arg_types: Vec<Type> = args.iter().map(|a| a.ty.clone()).collect();
+    let ty = check_builtin_call(name, &arg_types)
+        .map_err(|message| GlslError::new(ErrorCode::E0114, message))?;
+
+    let value = match name {
+        // Geometric functions
+        "dot" => Value::scalar(Scalar::Float(dot(&args[0], &args[1]))),
+        "length" => Value::scalar(Scalar::Float(libm::sqrtf(dot(&args[0], &args[0])))),
+        "distance" => {
+            let diff = zip_map(&args[0].ty, args, |c| Scalar::Float(f(c[0]) - f(c[1])))?;
+            Value::scalar(Scalar::Float(libm::sqrtf(dot(&diff, &diff))))
+        }
+        "normalize" => {
+            let len = libm::sqrtf(dot(&args[0], &args[0]));
+            zip_map(&ty, args, |c| Scalar::Float(f(c[0]) / len))?
+        }
+        "cross" => {
+            let a = |i: usize| f(args[0].comps[i]);
+            let b = |i: usize| f(args[1].comps[i]);
+            Value::new(
+                ty,
+                [
+                    a(1) * b(2) - a(2) * b(1),
+                    a(2) * b(0) - a(0) * b(2),
+                    a(0) * b(1) - a(1) * b(0),
+                ]
+                .map(Scalar::Float)
+                .to_vec(),
+            )
+        }
+
+        // Matrix functions
+        "matrixCompMult" => zip_map(&ty, args, |c| Scalar::Float(f(c[0]) * f(c[1])))?,
+        "outerProduct" => {
+            let (rows, cols) = dims(&ty)?;
+            let comps = (0..rows * cols)
+                .map(|i| Scalar::Float(f(args[0].comps[i % rows]) * f(args[1].comps[i / rows])))
+                .collect();
+            Value::new(ty, comps)
+        }
+        "transpose" => {
+            let (n, _) = dims(&ty)?;
+            let comps = (0..n * n)
+                .map(|i| args[0].comps[(i % n) * n + i / n])
+                .collect();
+            Value::new(ty, comps)
+        }
+        "determinant" => {
+            let m = matrix(&args[0])?;
+            Value::scalar(Scalar::Float(determinant(&m)))
+        }
+        "inverse" => {
+            let m = matrix(&args[0])?;
+            let n = m.len();
+            let det = determinant(&m);
+            // Transposed cofactors over the determinant, in column-major order
+            let comps = (0..n * n)
+                .map(|i| {
+                    let (row, col) = (i % n, i / n);
+                    let sign = if (row + col) % 2 == 0 { 1.0 } else { -1.0 };
+                    Scalar::Float(sign * determinant(&minor(&m, col, row)) / det)
+                })
+                .collect();
+            Value::new(ty, comps)
+        }
+
+        // Vector relational functions
+        "all" => Value::scalar(Scalar::Bool(args[0].comps.iter().all(|c| c.as_bool()))),
+        "any" => Value::scalar(Scalar::Bool(args[0].comps.iter().any(|c| c.as_bool()))),
+        "not" => zip_map(&ty, args, |c| Scalar::Bool(!c[0].as_bool()))?,
+        "equal" => zip_map(&ty, args, |c| Scalar::Bool(scalar_eq(c[0], c[1])))?,
+        "notEqual" => zip_map(&ty, args, |c| Scalar::Bool(!scalar_eq(c[0], c[1])))?,
+        "lessThan" | "lessThanEqual" | "greaterThan" | "greaterThanEqual" => {
+            zip_map(&ty, args, |c| {
+                let ordering = scalar_cmp(c[0], c[1]);
+                Scalar::Bool(match name {
+                    "lessThan" => ordering == Some(Ordering::Less),
+                    "lessThanEqual" => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+                    "greaterThan" => ordering == Some(Ordering::Greater),
+                    _ => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+                })
+            })?
+        }
+
+        // Everything else works component by component
+        _ => zip_map(&ty, args, |c| componentwise(name, c))?,
+    };
+    Ok(value)
+}
+
+fn f(c: Scalar) -> f32 {
+    c.as_f32()
+}
+
+/// Apply `op` to matching components of `args`, giving a value of type `ty`
+///
+/// Scalar arguments are applied to every component.
+fn zip_map(
+    ty: &Type,
+    args: &[Value],
+    op: impl Fn(&[Scalar]) -> Scalar,
+) -> Result<Value, GlslError> {
+    let base = base_type(ty);
+    let comps = (0..component_len(ty)?)
+        .map(|i| {
+            let comps: Vec<Scalar> = args
+                .iter()
+                .map(|arg| {
+                    if arg.comps.len() == 1 {
+                        arg.comps[0]
+                    } else {
+                        arg.comps[i]
+                    }
+                })
+                .collect();
+            op(&comps).convert(&base)
+        })
+        .collect();
+    Ok(Value::new(ty.clone(), comps))
+}
+
+fn componentwise(name: &str, c: &[Scalar]) -> Scalar {
+    // Integer overloads of min, max, clamp and abs
+    if let Scalar::Int(x) = c[0] {
+        let i = |n: usize| c[n].as_i32();
+        return Scalar::Int(match name {
+            "min" => x.min(i(1)),
+            "max" => x.max(i(1)),
+            "clamp" => x.max(i(1)).min(i(2)),
+            "sign" => x.signum(),
+            _ => x.wrapping_abs(),
+        });
+    }
+    if let Scalar::UInt(x) = c[0] {
+        let u = |n: usize| c[n].as_u32();
+        return Scalar::UInt(match name {
+            "min" => x.min(u(1)),
+            "max" => x.max(u(1)),
+            _ => x.max(u(1)).min(u(2)),
+        });
+    }
+    if let Scalar::Bool(x) = c[0] {
+        // mix with a bool selector picks between x and y
+        return if c[2].as_bool() {
+            c[1]
+        } else {
+            Scalar::Bool(x)
+        };
+    }
+
+    let x = f(c[0]);
+    let y = || f(c[1]);
+    let z = || f(c[2]);
+    Scalar::Float(match name {
+        "abs" => libm::fabsf(x),
+        "sign" => {
+            if x > 0.0 {
+                1.0
+            } else if x < 0.0 {
+                -1.0
+            } else {
+                0.0
+            }
+        }
+        "floor" => libm::floorf(x),
+        "ceil" => libm::ceilf(x),
+        "round" => libm::roundf(x),
+        "roundEven" => libm::rintf(x),
+        "fract" => x - libm::floorf(x),
+        "mod" => x - y() * libm::floorf(x / y()),
+        "min" => libm::fminf(x, y()),
+        "max" => libm::fmaxf(x, y()),
+        "clamp" => libm::fminf(libm::fmaxf(x, y()), z()),
+        "mix" => x + (y() - x) * z(),
+        "step" => {
+            if y() < x {
+                0.0
+            } else {
+                1.0
+            }
+        }
+        "smoothstep" => {
+            let t = ((z() - x) / (y() - x)).clamp(0.0, 1.0);
+            t * t * (3.0 - 2.0 * t)
+        }
+        "sqrt" => libm::sqrtf(x),
+        "inversesqrt" => 1.0 / libm::sqrtf(x),
+        "pow" => libm::powf(x, y()),
+        "exp" => libm::expf(x),
+        "log" => libm::logf(x),
+        "radians" => x.to_radians(),
+        "degrees" => x.to_degrees(),
+        "sin" => libm::sinf(x),
+        "cos" => libm::cosf(x),
+        "tan" => libm::tanf(x),
+        "asin" => libm::asinf(x),
+        "acos" => libm::acosf(x),
+        "atan" if c.len() == 2 => libm::atan2f(x, y()),
+        "atan" => libm::atanf(x),
+        "sinh" => libm::sinhf(x),
+        "cosh" => libm::coshf(x),
+        "tanh" => libm::tanhf(x),
+        "asinh" => libm::asinhf(x),
+        "acosh" => libm::acoshf(x),
+        "atanh" => libm::atanhf(x),
+        "isinf" => return Scalar::Bool(x.is_infinite()),
+        "isnan" => return Scalar::Bool(x.is_nan()),
+        _ => f32::NAN,
+    })
+}
+
+fn dot(a: &Value, b: &Value) -> f32 {
+    a.comps
+        .iter()
+        .zip(&b.comps)
+        .map(|(a, b)| f(*a) * f(*b))
+        .sum()
+}
+
+fn dims(ty: &Type) -> Result<(usize, usize), GlslError> {
+    ty.matrix_dims()
+        .ok_or_else(|| unsupported(format!("matrix function on `{ty:?}`")))
+}
+
+/// Square matrix as rows of columns (`m[col][row]`)
+fn matrix(value: &Value) -> Result<Vec<Vec<f32>>, GlslError> {
+    let (n, _) = dims(&value.ty)?;
+    Ok(value
+        .comps
+        .chunks(n)
+        .map(|col| col.iter().map(|c| f(*c)).collect())
+        .collect())
+}
+
+/// `m` without column `col` and row `row`
+fn minor(m: &[Vec<f32>], row: usize, col: usize) -> Vec<Vec<f32>> {
+    m.iter()
+        .enumerate()
+        .filter(|(c, _)| *c != col)
+        .map(|(_, column)| {
+            column
+                .iter()
+                .enumerate()
+                .filter(|(r, _)| *r != row)
+                .map(|(_, v)| *v)
+                .collect()
+        })
+        .collect()
+}
+
+fn determinant(m: &[Vec<f32>]) -> f32 {
+    match m.len() {
+        0 => 1.0,
+        1 => m[0][0],
+        n => (0..n)
+            .map(|col| {
+                let sign = if col % 2 == 0 { 1.0 } else { -1.0 };
+                sign * m[col][0] * determinant(&minor(m, 0, col))
+            })
+            .sum(),
+    }
+}
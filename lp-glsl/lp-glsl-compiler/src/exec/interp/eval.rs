@@ -0,0 +1,1010 @@
+//! Statement and expression evaluation for the AST interpreter
+
+use super::builtins;
+use super::value::{Scalar, Value, base_type, component_len, runtime_error, unsupported};
+use crate::error::{ErrorCode, GlslError, extract_span_from_expr, source_span_to_location};
+use crate::frontend::codegen::constants::DISCARD_ALPHA;
+use crate::frontend::semantic::functions::ParamQualifier;
+use crate::frontend::semantic::lpfx::lpfx_fn_registry::is_lpfx_fn;
+use crate::frontend::semantic::type_check::constructors::check_scalar_constructor;
+use crate::frontend::semantic::type_check::{
+    check_matrix_constructor, check_vector_constructor, infer_binary_result_type,
+    infer_unary_result_type, is_matrix_type_name, is_scalar_type_name, is_vector_type_name,
+};
+use crate::frontend::semantic::types::Type;
+use crate::frontend::semantic::{TypedFunction, TypedShader, type_resolver};
+use glsl::syntax::{
+    ArraySpecifier, ArraySpecifierDimension, AssignmentOp, BinaryOp, Condition, Declaration, Expr,
+    ForInitStatement, FunIdentifier, Initializer, IterationStatement, JumpStatement,
+    SelectionRestStatement, SimpleStatement, SourceSpan, Statement, UnaryOp,
+};
+use hashbrown::HashMap;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Calls nested deeper than this are reported instead of overflowing the host stack
+const MAX_CALL_DEPTH: usize = 64;
+
+/// How control leaves a statement
+enum Flow {
+    Next,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+/// Storage location of an assignable expression
+///
+/// `indices` are positions in the flat components of variable `name`.
+struct Place {
+    name: String,
+    ty: Type,
+    indices: Vec<usize>,
+}
+
+/// Evaluates functions of a typed shader by walking their AST
+pub(crate) struct Interpreter<'a> {
+    shader: &'a TypedShader,
+    uniforms: &'a HashMap<String, Value>,
+    /// Loop iterations left (loops are unchecked if None)
+    pub fuel: Option<u32>,
+    /// Variable scopes of the active call, innermost last
+    scopes: Vec<HashMap<String, Value>>,
+    depth: usize,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(
+        shader: &'a TypedShader,
+        uniforms: &'a HashMap<String, Value>,
+        fuel: Option<u32>,
+    ) -> Self {
+        Self {
+            shader,
+            uniforms,
+            fuel,
+            scopes: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    /// Call function `name` with `args` converted to its parameter types
+    pub fn call(&mut self, name: &str, args: Vec<Value>) -> Result<Value, GlslError> {
+        let func = self.function(name)?;
+        if args.len() != func.parameters.len() {
+            return Err(GlslError::new(
+                ErrorCode::E0104,
+                format!(
+                    "function `{name}` takes {} arguments, got {}",
+                    func.parameters.len(),
+                    args.len()
+                ),
+            ));
+        }
+        let (result, _outs) = self.call_function(func, args)?;
+        Ok(result)
+    }
+
+    fn function(&self, name: &str) -> Result<&'a TypedFunction, GlslError> {
+        let shader = self.shader;
+        shader
+            .main_function
+            .iter()
+            .chain(&shader.user_functions)
+            .find(|func| func.name == name)
+            .ok_or_else(|| GlslError::new(ErrorCode::E0101, format!("undefined function `{name}`")))
+    }
+
+    /// Run a function body, returning its result and final parameter values
+    fn call_function(
+        &mut self,
+        func: &'a TypedFunction,
+        args: Vec<Value>,
+    ) -> Result<(Value, Vec<Value>), GlslError> {
+        if self.depth >= MAX_CALL_DEPTH {
+            return Err(runtime_error(format!(
+                "calls nested more than {MAX_CALL_DEPTH} deep (in `{}`)",
+                func.name
+            )));
+        }
+
+        let mut params = HashMap::new();
+        for (param, arg) in func.parameters.iter().zip(args) {
+            let value = match param.qualifier {
+                ParamQualifier::Out => Value::zero(&param.ty)?,
+                ParamQualifier::In | ParamQualifier::InOut => arg.convert_to(&param.ty)?,
+            };
+            params.insert(param.name.clone(), value);
+        }
+
+        // Parameters share the outermost scope of the body
+        let caller_scopes = core::mem::replace(&mut self.scopes, vec![params]);
+        self.depth += 1;
+        let flow = self.exec_stmts(&func.body);
+        self.depth -= 1;
+        let mut scopes = core::mem::replace(&mut self.scopes, caller_scopes);
+        let flow = flow?;
+
+        let result = match (flow, &func.return_type) {
+            (_, Type::Void) => Value::void(),
+            (Flow::Return(value), ty) => value.convert_to(ty)?,
+            (_, ty) => Value::zero(ty)?,
+        };
+        let outs = func
+            .parameters
+            .iter()
+            .map(|param| scopes[0].remove(&param.name).unwrap_or_else(Value::void))
+            .collect();
+        Ok((result, outs))
+    }
+
+    // Statements
+
+    fn exec_stmts(&mut self, stmts: &[Statement]) -> Result<Flow, GlslError> {
+        for stmt in stmts {
+            match self.exec_stmt(stmt)? {
+                Flow::Next => {}
+                flow => return Ok(flow),
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    fn exec_stmt(&mut self, stmt: &Statement) -> Result<Flow, GlslError> {
+        match stmt {
+            Statement::Compound(compound) => {
+                self.scoped(|this| this.exec_stmts(&compound.statement_list))
+            }
+            Statement::Simple(simple) => self.exec_simple(simple),
+        }
+    }
+
+    /// Run `f` in a fresh variable scope
+    fn scoped<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, GlslError>,
+    ) -> Result<T, GlslError> {
+        self.scopes.push(HashMap::new());
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn exec_simple(&mut self, stmt: &SimpleStatement) -> Result<Flow, GlslError> {
+        match stmt {
+            SimpleStatement::Declaration(decl) => {
+                self.declare(decl)?;
+                Ok(Flow::Next)
+            }
+            SimpleStatement::Expression(Some(expr)) => {
+                self.eval(expr)?;
+                Ok(Flow::Next)
+            }
+            SimpleStatement::Expression(None) => Ok(Flow::Next),
+            SimpleStatement::Selection(selection) => {
+                let cond = self.eval_bool(&selection.cond)?;
+                match &selection.rest {
+                    SelectionRestStatement::Statement(then_stmt) => {
+                        if cond {
+                            self.scoped(|this| this.exec_stmt(then_stmt))
+                        } else {
+                            Ok(Flow::Next)
+                        }
+                    }
+                    SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                        let branch = if cond { then_stmt } else { else_stmt };
+                        self.scoped(|this| this.exec_stmt(branch))
+                    }
+                }
+            }
+            SimpleStatement::Iteration(iteration) => self.scoped(|this| this.exec_loop(iteration)),
+            SimpleStatement::Jump(jump) => match jump {
+                JumpStatement::Break => Ok(Flow::Break),
+                JumpStatement::Continue => Ok(Flow::Continue),
+                JumpStatement::Return(Some(expr)) => Ok(Flow::Return(self.eval(expr)?)),
+                JumpStatement::Return(None) => Ok(Flow::Return(Value::void())),
+                JumpStatement::Discard => {
+                    let mut color = vec![Scalar::Float(0.0); 3];
+                    color.push(Scalar::Float(DISCARD_ALPHA));
+                    Ok(Flow::Return(Value::new(Type::Vec4, color)))
+                }
+            },
+            _ => Err(unsupported(format!("statement `{stmt:?}`"))),
+        }
+    }
+
+    /// Spend fuel at a loop header, returning false once it has run out
+    ///
+    /// Mirrors the check the JIT emits, so both backends use the same amount.
+    fn take_fuel(&mut self) -> bool {
+        match &mut self.fuel {
+            None => true,
+            Some(0) => false,
+            Some(fuel) => {
+                *fuel -= 1;
+                true
+            }
+        }
+    }
+
+    fn exec_loop(&mut self, iteration: &IterationStatement) -> Result<Flow, GlslError> {
+        match iteration {
+            IterationStatement::While(condition, body) => {
+                while self.take_fuel() && self.eval_condition(condition)? {
+                    match self.exec_stmt(body)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Next | Flow::Continue => {}
+                    }
+                }
+            }
+            IterationStatement::DoWhile(body, cond) => loop {
+                // The body always runs once; fuel is spent before each condition check
+                match self.exec_stmt(body)? {
+                    Flow::Break => break,
+                    Flow::Return(value) => return Ok(Flow::Return(value)),
+                    Flow::Next | Flow::Continue => {}
+                }
+                if !self.take_fuel() || !self.eval_bool(cond)? {
+                    break;
+                }
+            },
+            IterationStatement::For(init, rest, body) => {
+                match init {
+                    ForInitStatement::Expression(Some(expr)) => {
+                        self.eval(expr)?;
+                    }
+                    ForInitStatement::Expression(None) => {}
+                    ForInitStatement::Declaration(decl) => self.declare(decl)?,
+                }
+                while self.take_fuel() {
+                    if let Some(condition) = &rest.condition
+                        && !self.eval_condition(condition)?
+                    {
+                        break;
+                    }
+                    match self.exec_stmt(body)? {
+                        Flow::Break => break,
+                        Flow::Return(value) => return Ok(Flow::Return(value)),
+                        Flow::Next | Flow::Continue => {}
+                    }
+                    if let Some(post_expr) = &rest.post_expr {
+                        self.eval(post_expr)?;
+                    }
+                }
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    fn eval_condition(&mut self, condition: &Condition) -> Result<bool, GlslError> {
+        match condition {
+            Condition::Expr(expr) => self.eval_bool(expr),
+            Condition::Assignment(ty, ident, init) => {
+                let ty = type_resolver::parse_type_specifier(&ty.ty, None)?;
+                let value = self.declare_var(&ident.name, ty, Some(init))?;
+                Ok(value.first()?.as_bool())
+            }
+        }
+    }
+
+    fn declare(&mut self, decl: &Declaration) -> Result<(), GlslError> {
+        let Declaration::InitDeclaratorList(list) = decl else {
+            return Err(unsupported("declarations other than variables"));
+        };
+        if let Some(name) = &list.head.name {
+            let ty = type_resolver::parse_head_declarator_type(list, &name.span)?;
+            self.declare_var(&name.name, ty, list.head.initializer.as_ref())?;
+        }
+        let base_ty = type_resolver::parse_return_type(&list.head.ty, None)?;
+        for declarator in &list.tail {
+            let ty = type_resolver::parse_tail_declarator_type(&base_ty, declarator)?;
+            self.declare_var(
+                &declarator.ident.ident.name,
+                ty,
+                declarator.initializer.as_ref(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Declare a variable in the innermost scope, returning its initial value
+    fn declare_var(
+        &mut self,
+        name: &str,
+        ty: Type,
+        init: Option<&Initializer>,
+    ) -> Result<Value, GlslError> {
+        let value = match init {
+            None => Value::zero(&ty)?,
+            Some(init) => {
+                let init = self.eval_initializer(init)?;
+                match ty {
+                    Type::Array(element, size) => {
+                        // Unsized arrays take their size from the initializer, and
+                        // missing elements are zero
+                        let element_len = component_len(&element)?.max(1);
+                        let size = if size == 0 {
+                            init.comps.len() / element_len
+                        } else {
+                            size
+                        };
+                        let ty = Type::Array(element, size);
+                        let mut value = Value::zero(&ty)?;
+                        if init.comps.len() > value.comps.len() {
+                            return Err(runtime_error(format!(
+                                "array initializer has {} components, but `{ty:?}` holds {}",
+                                init.comps.len(),
+                                value.comps.len()
+                            )));
+                        }
+                        let base = base_type(&ty);
+                        for (slot, comp) in value.comps.iter_mut().zip(&init.comps) {
+                            *slot = comp.convert(&base);
+                        }
+                        value
+                    }
+                    ty => init.convert_to(&ty)?,
+                }
+            }
+        };
+        self.scopes
+            .last_mut()
+            .expect("interpreter always has a scope while running a function")
+            .insert(name.to_string(), value.clone());
+        Ok(value)
+    }
+
+    fn eval_initializer(&mut self, init: &Initializer) -> Result<Value, GlslError> {
+        match init {
+            Initializer::Simple(expr) => self.eval(expr),
+            Initializer::List(list) => {
+                let mut element_ty = Type::Void;
+                let mut comps = Vec::new();
+                for item in list.0.iter() {
+                    let item = self.eval_initializer(item)?;
+                    element_ty = item.ty;
+                    comps.extend(item.comps);
+                }
+                let size = comps.len() / component_len(&element_ty)?.max(1);
+                Ok(Value::new(
+                    Type::Array(alloc::boxed::Box::new(element_ty), size),
+                    comps,
+                ))
+            }
+        }
+    }
+
+    // Expressions
+
+    /// Evaluate an expression, attaching its location to errors that lack one
+    pub fn eval(&mut self, expr: &Expr) -> Result<Value, GlslError> {
+        self.eval_expr(expr).map_err(|error| {
+            if error.location.is_some() {
+                error
+            } else {
+                error.with_location(source_span_to_location(&extract_span_from_expr(expr)))
+            }
+        })
+    }
+
+    fn eval_bool(&mut self, expr: &Expr) -> Result<bool, GlslError> {
+        Ok(self.eval(expr)?.first()?.as_bool())
+    }
+
+    fn eval_expr(&mut self, expr: &Expr) -> Result<Value, GlslError> {
+        match expr {
+            Expr::IntConst(n, _) => Ok(Value::scalar(Scalar::Int(*n))),
+            Expr::UIntConst(n, _) => Ok(Value::scalar(Scalar::UInt(*n))),
+            Expr::FloatConst(f, _) => Ok(Value::scalar(Scalar::Float(*f))),
+            Expr::DoubleConst(d, _) => Ok(Value::scalar(Scalar::Float(*d as f32))),
+            Expr::BoolConst(b, _) => Ok(Value::scalar(Scalar::Bool(*b))),
+            Expr::Variable(ident, _) => self.lookup(&ident.name).cloned(),
+            Expr::Unary(op, operand, span) => match op {
+                UnaryOp::Inc | UnaryOp::Dec => {
+                    let (_, new) = self.increment(operand, matches!(op, UnaryOp::Inc))?;
+                    Ok(new)
+                }
+                _ => {
+                    let value = self.eval(operand)?;
+                    let ty = infer_unary_result_type(op, &value.ty, span.clone())?;
+                    let comps = value
+                        .comps
+                        .iter()
+                        .map(|c| unary(op, *c))
+                        .collect::<Result<_, _>>()?;
+                    Ok(Value::new(ty, comps))
+                }
+            },
+            Expr::Binary(op, lhs, rhs, span) => {
+                // `&&` and `||` only evaluate the right side when it matters
+                if matches!(op, BinaryOp::And | BinaryOp::Or) {
+                    let lhs = self.eval_bool(lhs)?;
+                    let result = match (op, lhs) {
+                        (BinaryOp::And, false) => false,
+                        (BinaryOp::Or, true) => true,
+                        _ => self.eval_bool(rhs)?,
+                    };
+                    return Ok(Value::scalar(Scalar::Bool(result)));
+                }
+                let lhs = self.eval(lhs)?;
+                let rhs = self.eval(rhs)?;
+                binary(op, &lhs, &rhs, span.clone())
+            }
+            Expr::Ternary(cond, then_expr, else_expr, _) => {
+                if self.eval_bool(cond)? {
+                    self.eval(then_expr)
+                } else {
+                    self.eval(else_expr)
+                }
+            }
+            Expr::Assignment(lhs, op, rhs, span) => {
+                let rhs = self.eval(rhs)?;
+                let place = self.place(lhs)?;
+                let value = match op {
+                    AssignmentOp::Equal => rhs,
+                    op => {
+                        let op = match op {
+                            AssignmentOp::Add => BinaryOp::Add,
+                            AssignmentOp::Sub => BinaryOp::Sub,
+                            AssignmentOp::Mult => BinaryOp::Mult,
+                            AssignmentOp::Div => BinaryOp::Div,
+                            AssignmentOp::Mod => BinaryOp::Mod,
+                            AssignmentOp::LShift => BinaryOp::LShift,
+                            AssignmentOp::RShift => BinaryOp::RShift,
+                            AssignmentOp::And => BinaryOp::BitAnd,
+                            AssignmentOp::Xor => BinaryOp::BitXor,
+                            AssignmentOp::Or => BinaryOp::BitOr,
+                            op => return Err(unsupported(format!("assignment operator `{op:?}`"))),
+                        };
+                        binary(&op, &self.read(&place)?, &rhs, span.clone())?
+                    }
+                };
+                let value = value.convert_to(&place.ty)?;
+                self.write(&place, &value)?;
+                Ok(value)
+            }
+            Expr::FunCall(func_ident, args, _) => self.eval_call(func_ident, args),
+            Expr::Dot(base, field, _) => {
+                let base = self.eval(base)?;
+                let (ty, indices) = select_field(&base.ty, &field.name)?;
+                Ok(pick(&base, ty, &indices))
+            }
+            Expr::Bracket(base, array_spec, _) => {
+                let base = self.eval(base)?;
+                let index = self.eval_index(array_spec)?;
+                let (ty, indices) = select_index(&base.ty, index)?;
+                Ok(pick(&base, ty, &indices))
+            }
+            Expr::PostInc(operand, _) => Ok(self.increment(operand, true)?.0),
+            Expr::PostDec(operand, _) => Ok(self.increment(operand, false)?.0),
+            Expr::Comma(lhs, rhs, _) => {
+                self.eval(lhs)?;
+                self.eval(rhs)
+            }
+            _ => Err(unsupported(format!("expression `{expr:?}`"))),
+        }
+    }
+
+    /// Add or subtract one, returning the old and new values
+    fn increment(&mut self, operand: &Expr, up: bool) -> Result<(Value, Value), GlslError> {
+        let place = self.place(operand)?;
+        let old = self.read(&place)?;
+        let one = Value::scalar(Scalar::Int(1).convert(&base_type(&old.ty)));
+        let op = if up { BinaryOp::Add } else { BinaryOp::Sub };
+        let new = binary(&op, &old, &one, SourceSpan::unknown())?.convert_to(&old.ty)?;
+        self.write(&place, &new)?;
+        Ok((old, new))
+    }
+
+    fn eval_index(&mut self, array_spec: &ArraySpecifier) -> Result<i64, GlslError> {
+        match &array_spec.dimensions.0[0] {
+            ArraySpecifierDimension::ExplicitlySized(expr) => {
+                Ok(match self.eval(expr)?.first()? {
+                    Scalar::UInt(i) => i as i64,
+                    index => index.as_i32() as i64,
+                })
+            }
+            ArraySpecifierDimension::Unsized => Err(runtime_error("indexing requires an index")),
+        }
+    }
+
+    fn eval_call(&mut self, func_ident: &FunIdentifier, args: &[Expr]) -> Result<Value, GlslError> {
+        let FunIdentifier::Identifier(ident) = func_ident else {
+            return Err(unsupported("complex function identifiers"));
+        };
+        let name = ident.name.as_str();
+
+        if is_vector_type_name(name) || is_matrix_type_name(name) || is_scalar_type_name(name) {
+            let args = self.eval_args(args)?;
+            return construct(name, &args);
+        }
+        if crate::frontend::semantic::builtins::is_builtin_function(name) {
+            let args = self.eval_args(args)?;
+            return builtins::call(name, &args);
+        }
+        if is_lpfx_fn(name) {
+            return Err(unsupported(format!("LPFX function `{name}`")));
+        }
+
+        let func = self.function(name)?;
+        if args.len() != func.parameters.len() {
+            return Err(GlslError::new(
+                ErrorCode::E0104,
+                format!(
+                    "function `{name}` takes {} arguments, got {}",
+                    func.parameters.len(),
+                    args.len()
+                ),
+            ));
+        }
+        let mut values = Vec::new();
+        for (arg, param) in args.iter().zip(&func.parameters) {
+            values.push(match param.qualifier {
+                ParamQualifier::Out => Value::void(),
+                ParamQualifier::In | ParamQualifier::InOut => self.eval(arg)?,
+            });
+        }
+        let (result, outs) = self.call_function(func, values)?;
+
+        // Copy `out` and `inout` parameters back to the caller's variables
+        for ((arg, param), out) in args.iter().zip(&func.parameters).zip(outs) {
+            if param.qualifier != ParamQualifier::In {
+                let place = self.place(arg)?;
+                self.write(&place, &out.convert_to(&place.ty)?)?;
+            }
+        }
+        Ok(result)
+    }
+
+    fn eval_args(&mut self, args: &[Expr]) -> Result<Vec<Value>, GlslError> {
+        args.iter().map(|arg| self.eval(arg)).collect()
+    }
+
+    // Variables
+
+    fn lookup(&self, name: &str) -> Result<&Value, GlslError> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.uniforms.get(name))
+            .ok_or_else(|| GlslError::new(ErrorCode::E0100, format!("undefined variable `{name}`")))
+    }
+
+    fn lookup_mut(&mut self, name: &str) -> Result<&mut Value, GlslError> {
+        let is_uniform = self.uniforms.contains_key(name);
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(name))
+            .ok_or_else(|| {
+                if is_uniform {
+                    GlslError::new(
+                        ErrorCode::E0115,
+                        format!("cannot assign to uniform `{name}`"),
+                    )
+                } else {
+                    GlslError::new(ErrorCode::E0100, format!("undefined variable `{name}`"))
+                }
+            })
+    }
+
+    /// Resolve an assignable expression to the components it names
+    fn place(&mut self, expr: &Expr) -> Result<Place, GlslError> {
+        match expr {
+            Expr::Variable(ident, _) => {
+                let value = self.lookup_mut(&ident.name)?;
+                Ok(Place {
+                    name: ident.name.clone(),
+                    ty: value.ty.clone(),
+                    indices: (0..value.comps.len()).collect(),
+                })
+            }
+            Expr::Dot(base, field, _) => {
+                let base = self.place(base)?;
+                let (ty, indices) = select_field(&base.ty, &field.name)?;
+                Ok(Place {
+                    ty,
+                    indices: indices.iter().map(|i| base.indices[*i]).collect(),
+                    name: base.name,
+                })
+            }
+            Expr::Bracket(base, array_spec, _) => {
+                let base = self.place(base)?;
+                let index = self.eval_index(array_spec)?;
+                let (ty, indices) = select_index(&base.ty, index)?;
+                Ok(Place {
+                    ty,
+                    indices: indices.iter().map(|i| base.indices[*i]).collect(),
+                    name: base.name,
+                })
+            }
+            _ => Err(GlslError::new(
+                ErrorCode::E0115,
+                "cannot assign to expression",
+            )),
+        }
+    }
+
+    fn read(&self, place: &Place) -> Result<Value, GlslError> {
+        let value = self.lookup(&place.name)?;
+        Ok(pick(value, place.ty.clone(), &place.indices))
+    }
+
+    fn write(&mut self, place: &Place, value: &Value) -> Result<(), GlslError> {
+        if value.comps.len() != place.indices.len() {
+            return Err(runtime_error(format!(
+                "cannot store `{:?}` into `{:?}`",
+                value.ty, place.ty
+            )));
+        }
+        let target = self.lookup_mut(&place.name)?;
+        for (index, comp) in place.indices.iter().zip(&value.comps) {
+            target.comps[*index] = *comp;
+        }
+        Ok(())
+    }
+}
+
+/// Components at `indices` of `value`, as a value of type `ty`
+fn pick(value: &Value, ty: Type, indices: &[usize]) -> Value {
+    Value::new(ty, indices.iter().map(|i| value.comps[*i]).collect())
+}
+
+/// Type and component positions selected by a swizzle such as `.xy`
+fn select_field(ty: &Type, field: &str) -> Result<(Type, Vec<usize>), GlslError> {
+    let count = ty.component_count().ok_or_else(|| {
+        GlslError::new(
+            ErrorCode::E0112,
+            format!("cannot access `.{field}` on `{ty:?}`"),
+        )
+    })?;
+    let indices = field
+        .chars()
+        .map(|c| {
+            ["xyzw", "rgba", "stpq"]
+                .iter()
+                .find_map(|set| set.find(c))
+                .filter(|i| *i < count)
+                .ok_or_else(|| {
+                    GlslError::new(
+                        ErrorCode::E0113,
+                        format!("invalid swizzle `.{field}` on `{ty:?}`"),
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let base = base_type(ty);
+    let result_ty = match indices.len() {
+        1 => base,
+        n => Type::vector_type(&base, n).ok_or_else(|| {
+            GlslError::new(
+                ErrorCode::E0113,
+                format!("invalid swizzle `.{field}` on `{ty:?}`"),
+            )
+        })?,
+    };
+    Ok((result_ty, indices))
+}
+
+/// Type and component positions selected by `[index]`
+fn select_index(ty: &Type, index: i64) -> Result<(Type, Vec<usize>), GlslError> {
+    let (element_ty, count) = match ty {
+        Type::Array(element, size) => ((**element).clone(), *size),
+        _ if ty.is_matrix() => {
+            let (_, cols) = ty.matrix_dims().unwrap();
+            (ty.matrix_column_type().unwrap(), cols)
+        }
+        _ if ty.is_vector() => (base_type(ty), ty.component_count().unwrap()),
+        _ => {
+            return Err(GlslError::new(
+                ErrorCode::E0112,
+                format!("cannot index into `{ty:?}`"),
+            ));
+        }
+    };
+    if index < 0 || index as usize >= count {
+        return Err(GlslError::new(
+            ErrorCode::E0111,
+            format!("index {index} out of range for `{ty:?}`"),
+        ));
+    }
+    let len = component_len(&element_ty)?;
+    let start = index as usize * len;
+    Ok((element_ty, (start..start + len).collect()))
+}
+
+/// Evaluate a constructor such as `vec3(...)`, `mat2(...)` or `float(...)`
+fn construct(name: &str, args: &[Value]) -> Result<Value, GlslError> {
+    let arg_types: Vec<Type> = args.iter().map(|a| a.ty.clone()).collect();
+
+    if is_scalar_type_name(name) {
+        let ty = check_scalar_constructor(name, &arg_types)?;
+        let value = args
+            .first()
+            .ok_or_else(|| runtime_error(format!("`{name}` needs an argument")))?
+            .first()?;
+        return Ok(Value::scalar(value.convert(&ty)));
+    }
+
+    let ty = if is_vector_type_name(name) {
+        check_vector_constructor(name, &arg_types)?
+    } else {
+        check_matrix_constructor(name, &arg_types)?
+    };
+    let base = base_type(&ty);
+    let len = component_len(&ty)?;
+
+    if let [arg] = args {
+        // A single scalar fills a vector, or the diagonal of a matrix
+        if arg.ty.is_scalar() {
+            let value = arg.comps[0].convert(&base);
+            return Ok(match ty.matrix_dims() {
+                Some((rows, _)) => {
+                    let mut value_matrix = Value::zero(&ty)?;
+                    for i in 0..rows {
+                        value_matrix.comps[i * rows + i] = value;
+                    }
+                    value_matrix
+                }
+                None => Value::new(ty, vec![value; len]),
+            });
+        }
+        // A matrix built from a matrix keeps the overlap and is identity elsewhere
+        if let (Some((rows, cols)), Some((arg_rows, arg_cols))) =
+            (ty.matrix_dims(), arg.ty.matrix_dims())
+        {
+            let mut comps = Vec::with_capacity(len);
+            for c in 0..cols {
+                for r in 0..rows {
+                    comps.push(if c < arg_cols && r < arg_rows {
+                        arg.comps[c * arg_rows + r]
+                    } else {
+                        Scalar::Float(if c == r { 1.0 } else { 0.0 })
+                    });
+                }
+            }
+            return Ok(Value::new(ty, comps));
+        }
+    }
+
+    // Otherwise components are consumed in order
+    let comps: Vec<Scalar> = args
+        .iter()
+        .flat_map(|arg| arg.comps.iter())
+        .take(len)
+        .map(|comp| comp.convert(&base))
+        .collect();
+    if comps.len() < len {
+        return Err(GlslError::new(
+            ErrorCode::E0110,
+            format!("not enough components to construct `{name}`"),
+        ));
+    }
+    Ok(Value::new(ty, comps))
+}
+
+fn unary(op: &UnaryOp, value: Scalar) -> Result<Scalar, GlslError> {
+    Ok(match (op, value) {
+        (UnaryOp::Minus, Scalar::Float(v)) => Scalar::Float(-v),
+        (UnaryOp::Minus, Scalar::Int(v)) => Scalar::Int(v.wrapping_neg()),
+        (UnaryOp::Minus, Scalar::UInt(v)) => Scalar::UInt(v.wrapping_neg()),
+        (UnaryOp::Not, Scalar::Bool(v)) => Scalar::Bool(!v),
+        (UnaryOp::Complement, Scalar::Int(v)) => Scalar::Int(!v),
+        (UnaryOp::Complement, Scalar::UInt(v)) => Scalar::UInt(!v),
+        (op, value) => {
+            return Err(unsupported(format!("unary `{op:?}` on `{:?}`", value.ty())));
+        }
+    })
+}
+
+/// Evaluate a binary operator on two evaluated operands
+pub(crate) fn binary(
+    op: &BinaryOp,
+    lhs: &Value,
+    rhs: &Value,
+    span: SourceSpan,
+) -> Result<Value, GlslError> {
+    let ty = infer_binary_result_type(op, &lhs.ty, &rhs.ty, span)?;
+    match op {
+        BinaryOp::Equal | BinaryOp::NonEqual => {
+            let equal = lhs.comps.len() == rhs.comps.len()
+                && lhs
+                    .comps
+                    .iter()
+                    .zip(&rhs.comps)
+                    .all(|(a, b)| scalar_eq(*a, *b));
+            Ok(Value::scalar(Scalar::Bool(
+                equal == matches!(op, BinaryOp::Equal),
+            )))
+        }
+        BinaryOp::LT | BinaryOp::GT | BinaryOp::LTE | BinaryOp::GTE => {
+            let ordering = scalar_cmp(lhs.first()?, rhs.first()?);
+            let result = match op {
+                BinaryOp::LT => ordering == Some(core::cmp::Ordering::Less),
+                BinaryOp::GT => ordering == Some(core::cmp::Ordering::Greater),
+                BinaryOp::LTE => matches!(
+                    ordering,
+                    Some(core::cmp::Ordering::Less | core::cmp::Ordering::Equal)
+                ),
+                _ => matches!(
+                    ordering,
+                    Some(core::cmp::Ordering::Greater | core::cmp::Ordering::Equal)
+                ),
+            };
+            Ok(Value::scalar(Scalar::Bool(result)))
+        }
+        BinaryOp::And | BinaryOp::Or | BinaryOp::Xor => {
+            let (a, b) = (lhs.first()?.as_bool(), rhs.first()?.as_bool());
+            let result = match op {
+                BinaryOp::And => a && b,
+                BinaryOp::Or => a || b,
+                _ => a != b,
+            };
+            Ok(Value::scalar(Scalar::Bool(result)))
+        }
+        BinaryOp::Mult
+            if (lhs.ty.is_matrix() || rhs.ty.is_matrix())
+                && !lhs.ty.is_scalar()
+                && !rhs.ty.is_scalar() =>
+        {
+            Ok(Value::new(ty, matrix_product(lhs, rhs)?))
+        }
+        BinaryOp::LShift | BinaryOp::RShift => {
+            // The left operand's type decides arithmetic or logical shift
+            let comps = lhs
+                .comps
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    let amount = if rhs.comps.len() == 1 {
+                        rhs.comps[0]
+                    } else {
+                        rhs.comps[i]
+                    };
+                    shift(op, *value, amount.as_u32())
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Value::new(ty, comps))
+        }
+        BinaryOp::Add
+        | BinaryOp::Sub
+        | BinaryOp::Mult
+        | BinaryOp::Div
+        | BinaryOp::Mod
+        | BinaryOp::BitAnd
+        | BinaryOp::BitOr
+        | BinaryOp::BitXor => {
+            // Component-wise, with scalars applied to every component
+            let base = base_type(&ty);
+            let comp = |value: &Value, i: usize| {
+                let comp = if value.comps.len() == 1 {
+                    value.comps[0]
+                } else {
+                    value.comps[i]
+                };
+                comp.convert(&base)
+            };
+            let comps = (0..component_len(&ty)?)
+                .map(|i| arithmetic(op, comp(lhs, i), comp(rhs, i)))
+                .collect::<Result<_, _>>()?;
+            Ok(Value::new(ty, comps))
+        }
+        op => Err(unsupported(format!("binary operator `{op:?}`"))),
+    }
+}
+
+/// Linear algebra product of matrix * matrix, matrix * vector or vector * matrix
+fn matrix_product(lhs: &Value, rhs: &Value) -> Result<Vec<Scalar>, GlslError> {
+    let f = |value: &Value, i: usize| value.comps[i].as_f32();
+    let dims = |value: &Value| value.ty.matrix_dims().unwrap_or((value.comps.len(), 1));
+    let (lhs_rows, lhs_cols) = dims(lhs);
+    let (rhs_rows, rhs_cols) = if rhs.ty.is_vector() {
+        (rhs.comps.len(), 1)
+    } else {
+        dims(rhs)
+    };
+    if lhs.ty.is_vector() {
+        // Row vector times matrix
+        let mut comps = Vec::with_capacity(rhs_cols);
+        for c in 0..rhs_cols {
+            let sum = (0..rhs_rows)
+                .map(|k| f(lhs, k) * f(rhs, c * rhs_rows + k))
+                .sum();
+            comps.push(Scalar::Float(sum));
+        }
+        return Ok(comps);
+    }
+    if lhs_cols != rhs_rows {
+        return Err(runtime_error(format!(
+            "cannot multiply `{:?}` by `{:?}`",
+            lhs.ty, rhs.ty
+        )));
+    }
+    let mut comps = Vec::with_capacity(lhs_rows * rhs_cols);
+    for c in 0..rhs_cols {
+        for r in 0..lhs_rows {
+            let sum = (0..lhs_cols)
+                .map(|k| f(lhs, k * lhs_rows + r) * f(rhs, c * rhs_rows + k))
+                .sum();
+            comps.push(Scalar::Float(sum));
+        }
+    }
+    Ok(comps)
+}
+
+fn arithmetic(op: &BinaryOp, a: Scalar, b: Scalar) -> Result<Scalar, GlslError> {
+    let division_by_zero = || runtime_error("integer division by zero");
+    Ok(match (a, b) {
+        (Scalar::Float(a), Scalar::Float(b)) => Scalar::Float(match op {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mult => a * b,
+            BinaryOp::Div => a / b,
+            _ => return Err(unsupported("`%` on floats")),
+        }),
+        (Scalar::Int(a), Scalar::Int(b)) => Scalar::Int(match op {
+            BinaryOp::Add => a.wrapping_add(b),
+            BinaryOp::Sub => a.wrapping_sub(b),
+            BinaryOp::Mult => a.wrapping_mul(b),
+            BinaryOp::BitAnd => a & b,
+            BinaryOp::BitOr => a | b,
+            BinaryOp::BitXor => a ^ b,
+            _ if b == 0 => return Err(division_by_zero()),
+            BinaryOp::Div => a.wrapping_div(b),
+            _ => a.wrapping_rem(b),
+        }),
+        (Scalar::UInt(a), Scalar::UInt(b)) => Scalar::UInt(match op {
+            BinaryOp::Add => a.wrapping_add(b),
+            BinaryOp::Sub => a.wrapping_sub(b),
+            BinaryOp::Mult => a.wrapping_mul(b),
+            BinaryOp::BitAnd => a & b,
+            BinaryOp::BitOr => a | b,
+            BinaryOp::BitXor => a ^ b,
+            _ if b == 0 => return Err(division_by_zero()),
+            BinaryOp::Div => a / b,
+            _ => a % b,
+        }),
+        (a, b) => {
+            return Err(unsupported(format!(
+                "`{op:?}` on `{:?}` and `{:?}`",
+                a.ty(),
+                b.ty()
+            )));
+        }
+    })
+}
+
+/// Shift like RV32 `sll`/`sra`/`srl`, which only use the low 5 bits of the amount
+fn shift(op: &BinaryOp, value: Scalar, amount: u32) -> Result<Scalar, GlslError> {
+    let left = matches!(op, BinaryOp::LShift);
+    Ok(match value {
+        Scalar::Int(v) if left => Scalar::Int(v.wrapping_shl(amount)),
+        Scalar::Int(v) => Scalar::Int(v.wrapping_shr(amount)),
+        Scalar::UInt(v) if left => Scalar::UInt(v.wrapping_shl(amount)),
+        Scalar::UInt(v) => Scalar::UInt(v.wrapping_shr(amount)),
+        value => return Err(unsupported(format!("`{op:?}` on `{:?}`", value.ty()))),
+    })
+}
+
+pub(super) fn scalar_eq(a: Scalar, b: Scalar) -> bool {
+    match (a, b) {
+        (Scalar::Float(_), _) | (_, Scalar::Float(_)) => a.as_f32() == b.as_f32(),
+        (Scalar::Bool(a), Scalar::Bool(b)) => a == b,
+        _ => a.as_i32() == b.as_i32(),
+    }
+}
+
+pub(super) fn scalar_cmp(a: Scalar, b: Scalar) -> Option<core::cmp::Ordering> {
+    match (a, b) {
+        (Scalar::Float(_), _) | (_, Scalar::Float(_)) => a.as_f32().partial_cmp(&b.as_f32()),
+        (Scalar::UInt(_), _) | (_, Scalar::UInt(_)) => Some(a.as_u32().cmp(&b.as_u32())),
+        _ => Some(a.as_i32().cmp(&b.as_i32())),
+    }
+}
@@ -0,0 +1,242 @@
+//! Interpreted GLSL module implementation
+//!
+//! Runs shaders by walking the type-checked AST instead of generating code. It
+//! is much slower than the JIT, but works on targets Cranelift can't generate
+//! code for and on shaders that fail in codegen. Floats are evaluated as native
+//! `f32`, so results may differ slightly from Q32 builds.
+
+mod builtins;
+mod eval;
+mod value;
+
+use crate::error::{ErrorCode, GlslError};
+use crate::exec::executable::GlslExecutable;
+use crate::exec::glsl_value::GlslValue;
+use crate::frontend::semantic::TypedShader;
+use crate::frontend::semantic::functions::FunctionSignature;
+use crate::frontend::semantic::types::Type;
+use eval::Interpreter;
+use hashbrown::HashMap;
+use value::Value;
+
+use alloc::{format, string::String, vec::Vec};
+
+/// GLSL module executed by the AST interpreter
+pub struct GlslInterpreterModule {
+    shader: TypedShader,
+    signatures: HashMap<String, FunctionSignature>,
+    uniforms: HashMap<String, Value>,
+    // Loop iterations left, shared by all calls (loops are unchecked if None)
+    fuel: Option<u32>,
+}
+
+impl GlslInterpreterModule {
+    /// Wrap an analyzed shader, optionally limiting loops to `loop_fuel` iterations
+    pub fn new(shader: TypedShader, loop_fuel: Option<u32>) -> Result<Self, GlslError> {
+        let signatures = shader
+            .main_function
+            .iter()
+            .chain(&shader.user_functions)
+            .map(|func| {
+                let sig = FunctionSignature {
+                    name: func.name.clone(),
+                    return_type: func.return_type.clone(),
+                    parameters: func.parameters.clone(),
+                };
+                (func.name.clone(), sig)
+            })
+            .collect();
+        let uniforms = zeroed_uniforms(&shader)?;
+        Ok(Self {
+            shader,
+            signatures,
+            uniforms,
+            fuel: loop_fuel,
+        })
+    }
+
+    fn run(&mut self, name: &str, args: &[GlslValue]) -> Result<Value, GlslError> {
+        let mut interpreter = Interpreter::new(&self.shader, &self.uniforms, self.fuel);
+        let result = interpreter.call(name, args.iter().map(Value::from_glsl_value).collect());
+        self.fuel = interpreter.fuel;
+        result
+    }
+
+    /// Call `name`, checking that it returns `ty`
+    fn run_returning(
+        &mut self,
+        name: &str,
+        args: &[GlslValue],
+        ty: Option<Type>,
+    ) -> Result<Value, GlslError> {
+        let ty = ty.ok_or_else(|| {
+            GlslError::new(ErrorCode::E0109, "unsupported return type dimensions")
+        })?;
+        let value = self.run(name, args)?;
+        if value.ty != ty {
+            return Err(GlslError::new(
+                ErrorCode::E0116,
+                format!("function `{name}` returns {:?}, not {ty:?}", value.ty),
+            ));
+        }
+        Ok(value)
+    }
+
+    fn floats(value: Value) -> Vec<f32> {
+        value.comps.iter().map(|c| c.as_f32()).collect()
+    }
+}
+
+fn zeroed_uniforms(shader: &TypedShader) -> Result<HashMap<String, Value>, GlslError> {
+    shader
+        .uniforms
+        .iter()
+        .map(|decl| Ok((decl.name.clone(), Value::zero(&decl.ty)?)))
+        .collect()
+}
+
+/// Run the `main` function of an analyzed shader with `inputs` as its arguments
+///
+/// Uniforms read as zero and loops are unchecked; use [`GlslInterpreterModule`] to
+/// set uniforms or limit loops.
+pub fn interpret(shader: &TypedShader, inputs: &[GlslValue]) -> Result<GlslValue, GlslError> {
+    let uniforms = zeroed_uniforms(shader)?;
+    let mut interpreter = Interpreter::new(shader, &uniforms, None);
+    let args = inputs.iter().map(Value::from_glsl_value).collect();
+    interpreter
+        .call(crate::frontend::semantic::MAIN_FUNCTION_NAME, args)?
+        .to_glsl_value()
+}
+
+impl GlslExecutable for GlslInterpreterModule {
+    fn call_void(&mut self, name: &str, args: &[GlslValue]) -> Result<(), GlslError> {
+        self.run_returning(name, args, Some(Type::Void))?;
+        Ok(())
+    }
+
+    fn call_i32(&mut self, name: &str, args: &[GlslValue]) -> Result<i32, GlslError> {
+        let value = self.run_returning(name, args, Some(Type::Int))?;
+        Ok(value.first()?.as_i32())
+    }
+
+    fn call_f32(&mut self, name: &str, args: &[GlslValue]) -> Result<f32, GlslError> {
+        let value = self.run_returning(name, args, Some(Type::Float))?;
+        Ok(value.first()?.as_f32())
+    }
+
+    fn call_bool(&mut self, name: &str, args: &[GlslValue]) -> Result<bool, GlslError> {
+        let value = self.run_returning(name, args, Some(Type::Bool))?;
+        Ok(value.first()?.as_bool())
+    }
+
+    fn call_bvec(
+        &mut self,
+        name: &str,
+        args: &[GlslValue],
+        dim: usize,
+    ) -> Result<Vec<bool>, GlslError> {
+        let value = self.run_returning(name, args, Type::vector_type(&Type::Bool, dim))?;
+        Ok(value.comps.iter().map(|c| c.as_bool()).collect())
+    }
+
+    fn call_ivec(
+        &mut self,
+        name: &str,
+        args: &[GlslValue],
+        dim: usize,
+    ) -> Result<Vec<i32>, GlslError> {
+        let value = self.run_returning(name, args, Type::vector_type(&Type::Int, dim))?;
+        Ok(value.comps.iter().map(|c| c.as_i32()).collect())
+    }
+
+    fn call_uvec(
+        &mut self,
+        name: &str,
+        args: &[GlslValue],
+        dim: usize,
+    ) -> Result<Vec<u32>, GlslError> {
+        let value = self.run_returning(name, args, Type::vector_type(&Type::UInt, dim))?;
+        Ok(value.comps.iter().map(|c| c.as_u32()).collect())
+    }
+
+    fn call_vec(
+        &mut self,
+        name: &str,
+        args: &[GlslValue],
+        dim: usize,
+    ) -> Result<Vec<f32>, GlslError> {
+        let value = self.run_returning(name, args, Type::vector_type(&Type::Float, dim))?;
+        Ok(Self::floats(value))
+    }
+
+    fn call_mat(
+        &mut self,
+        name: &str,
+        args: &[GlslValue],
+        rows: usize,
+        cols: usize,
+    ) -> Result<Vec<f32>, GlslError> {
+        let ty = match (rows, cols) {
+            (2, 2) => Some(Type::Mat2),
+            (3, 3) => Some(Type::Mat3),
+            (4, 4) => Some(Type::Mat4),
+            _ => None,
+        };
+        let value = self.run_returning(name, args, ty)?;
+        Ok(Self::floats(value))
+    }
+
+    fn get_function_signature(&self, name: &str) -> Option<&FunctionSignature> {
+        self.signatures.get(name)
+    }
+
+    fn list_functions(&self) -> Vec<String> {
+        self.signatures.keys().cloned().collect()
+    }
+
+    fn is_pure(&self, name: &str) -> bool {
+        self.shader.purity.is_pure(name)
+    }
+
+    fn reads_time(&self, name: &str) -> bool {
+        self.shader.purity.reads_time(name)
+    }
+
+    fn set_uniform(&mut self, name: &str, value: GlslValue) -> Result<(), GlslError> {
+        let slot = self.uniforms.get_mut(name).ok_or_else(|| {
+            GlslError::new(ErrorCode::E0100, format!("uniform `{name}` not found"))
+        })?;
+        let new = Value::from_glsl_value(&value);
+        if new.ty != slot.ty {
+            return Err(GlslError::new(
+                ErrorCode::E0102,
+                format!("uniform `{name}` has type {:?}, got {value:?}", slot.ty),
+            ));
+        }
+        *slot = new;
+        Ok(())
+    }
+
+    fn list_uniforms(&self) -> Vec<String> {
+        self.shader
+            .uniforms
+            .iter()
+            .map(|decl| decl.name.clone())
+            .collect()
+    }
+
+    fn set_fuel(&mut self, fuel: u32) -> Result<(), GlslError> {
+        if self.fuel.is_none() {
+            return Err(GlslError::new(
+                ErrorCode::E0400,
+                "module was created without loop fuel",
+            ));
+        }
+        self.fuel = Some(fuel);
+        Ok(())
+    }
+
+    fn fuel_remaining(&self) -> Option<u32> {
+        self.fuel
+    }
+}
@@ -0,0 +1,242 @@
+//! Runtime values for the AST interpreter
+//!
+//! Every value is a flat list of scalar components tagged with its GLSL type.
+//! Vectors hold their components in order, matrices are column-major, and arrays
+//! hold their elements back to back, so indexing and swizzling reduce to picking
+//! component positions.
+
+use crate::error::{ErrorCode, GlslError};
+use crate::exec::glsl_value::GlslValue;
+use crate::frontend::semantic::types::Type;
+
+use alloc::{format, vec, vec::Vec};
+
+/// A single scalar component
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Scalar {
+    Float(f32),
+    Int(i32),
+    UInt(u32),
+    Bool(bool),
+}
+
+impl Scalar {
+    pub fn as_f32(self) -> f32 {
+        match self {
+            Scalar::Float(v) => v,
+            Scalar::Int(v) => v as f32,
+            Scalar::UInt(v) => v as f32,
+            Scalar::Bool(v) => {
+                if v {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    pub fn as_i32(self) -> i32 {
+        match self {
+            Scalar::Float(v) => v as i32,
+            Scalar::Int(v) => v,
+            Scalar::UInt(v) => v as i32,
+            Scalar::Bool(v) => v as i32,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        match self {
+            Scalar::Float(v) => v as u32,
+            Scalar::Int(v) => v as u32,
+            Scalar::UInt(v) => v,
+            Scalar::Bool(v) => v as u32,
+        }
+    }
+
+    pub fn as_bool(self) -> bool {
+        match self {
+            Scalar::Float(v) => v != 0.0,
+            Scalar::Int(v) => v != 0,
+            Scalar::UInt(v) => v != 0,
+            Scalar::Bool(v) => v,
+        }
+    }
+
+    /// Convert to the given scalar base type (GLSL constructor semantics)
+    pub fn convert(self, base: &Type) -> Scalar {
+        match base {
+            Type::Float => Scalar::Float(self.as_f32()),
+            Type::Int => Scalar::Int(self.as_i32()),
+            Type::UInt => Scalar::UInt(self.as_u32()),
+            _ => Scalar::Bool(self.as_bool()),
+        }
+    }
+
+    pub fn zero(base: &Type) -> Scalar {
+        Scalar::Int(0).convert(base)
+    }
+
+    pub fn ty(self) -> Type {
+        match self {
+            Scalar::Float(_) => Type::Float,
+            Scalar::Int(_) => Type::Int,
+            Scalar::UInt(_) => Type::UInt,
+            Scalar::Bool(_) => Type::Bool,
+        }
+    }
+}
+
+/// Scalar base type of a (possibly aggregate) type
+pub(crate) fn base_type(ty: &Type) -> Type {
+    match ty {
+        Type::Array(element, _) => base_type(element),
+        _ if ty.is_matrix() => Type::Float,
+        _ => ty.vector_base_type().unwrap_or_else(|| ty.clone()),
+    }
+}
+
+/// Number of scalar components in a type
+pub(crate) fn component_len(ty: &Type) -> Result<usize, GlslError> {
+    match ty {
+        Type::Void => Ok(0),
+        Type::Array(element, size) => Ok(component_len(element)? * size),
+        _ if ty.is_scalar() => Ok(1),
+        _ => ty
+            .component_count()
+            .or_else(|| ty.matrix_element_count())
+            .ok_or_else(|| unsupported(format!("type `{ty:?}`"))),
+    }
+}
+
+pub(crate) fn unsupported(what: impl core::fmt::Display) -> GlslError {
+    GlslError::new(
+        ErrorCode::E0400,
+        format!("{what} is not supported by the interpreter"),
+    )
+}
+
+pub(crate) fn runtime_error(message: impl Into<alloc::string::String>) -> GlslError {
+    GlslError::new(ErrorCode::E0400, message)
+}
+
+/// A typed value
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Value {
+    pub ty: Type,
+    pub comps: Vec<Scalar>,
+}
+
+impl Value {
+    pub fn new(ty: Type, comps: Vec<Scalar>) -> Self {
+        Self { ty, comps }
+    }
+
+    pub fn scalar(value: Scalar) -> Self {
+        Self::new(value.ty(), vec![value])
+    }
+
+    pub fn void() -> Self {
+        Self::new(Type::Void, Vec::new())
+    }
+
+    /// Zero-initialized value of a type, as for uninitialized variables
+    pub fn zero(ty: &Type) -> Result<Self, GlslError> {
+        let base = base_type(ty);
+        Ok(Self::new(
+            ty.clone(),
+            vec![Scalar::zero(&base); component_len(ty)?],
+        ))
+    }
+
+    /// Implicitly convert to `ty`, component by component
+    ///
+    /// The semantic pass has already checked the conversion is allowed, so this
+    /// only needs the component counts to line up.
+    pub fn convert_to(&self, ty: &Type) -> Result<Self, GlslError> {
+        if &self.ty == ty {
+            return Ok(self.clone());
+        }
+        let len = component_len(ty)?;
+        if self.comps.len() != len {
+            return Err(runtime_error(format!(
+                "cannot convert `{:?}` to `{ty:?}`",
+                self.ty
+            )));
+        }
+        let base = base_type(ty);
+        Ok(Self::new(
+            ty.clone(),
+            self.comps.iter().map(|c| c.convert(&base)).collect(),
+        ))
+    }
+
+    pub fn first(&self) -> Result<Scalar, GlslError> {
+        self.comps
+            .first()
+            .copied()
+            .ok_or_else(|| runtime_error(format!("`{:?}` value has no components", self.ty)))
+    }
+
+    pub fn from_glsl_value(value: &GlslValue) -> Self {
+        let floats = |ty: Type, values: &[f32]| {
+            Self::new(ty, values.iter().map(|v| Scalar::Float(*v)).collect())
+        };
+        match value {
+            GlslValue::I32(v) => Self::scalar(Scalar::Int(*v)),
+            GlslValue::U32(v) => Self::scalar(Scalar::UInt(*v)),
+            GlslValue::F32(v) => Self::scalar(Scalar::Float(*v)),
+            GlslValue::Bool(v) => Self::scalar(Scalar::Bool(*v)),
+            GlslValue::Vec2(v) => floats(Type::Vec2, v),
+            GlslValue::Vec3(v) => floats(Type::Vec3, v),
+            GlslValue::Vec4(v) => floats(Type::Vec4, v),
+            GlslValue::IVec2(v) => Self::new(Type::IVec2, v.map(Scalar::Int).to_vec()),
+            GlslValue::IVec3(v) => Self::new(Type::IVec3, v.map(Scalar::Int).to_vec()),
+            GlslValue::IVec4(v) => Self::new(Type::IVec4, v.map(Scalar::Int).to_vec()),
+            GlslValue::UVec2(v) => Self::new(Type::UVec2, v.map(Scalar::UInt).to_vec()),
+            GlslValue::UVec3(v) => Self::new(Type::UVec3, v.map(Scalar::UInt).to_vec()),
+            GlslValue::UVec4(v) => Self::new(Type::UVec4, v.map(Scalar::UInt).to_vec()),
+            GlslValue::BVec2(v) => Self::new(Type::BVec2, v.map(Scalar::Bool).to_vec()),
+            GlslValue::BVec3(v) => Self::new(Type::BVec3, v.map(Scalar::Bool).to_vec()),
+            GlslValue::BVec4(v) => Self::new(Type::BVec4, v.map(Scalar::Bool).to_vec()),
+            GlslValue::Mat2x2(m) => floats(Type::Mat2, m.as_flattened()),
+            GlslValue::Mat3x3(m) => floats(Type::Mat3, m.as_flattened()),
+            GlslValue::Mat4x4(m) => floats(Type::Mat4, m.as_flattened()),
+        }
+    }
+
+    pub fn to_glsl_value(&self) -> Result<GlslValue, GlslError> {
+        let f = |i: usize| self.comps[i].as_f32();
+        let i = |i: usize| self.comps[i].as_i32();
+        let u = |i: usize| self.comps[i].as_u32();
+        let b = |i: usize| self.comps[i].as_bool();
+        Ok(match self.ty {
+            Type::Float => GlslValue::F32(f(0)),
+            Type::Int => GlslValue::I32(i(0)),
+            Type::UInt => GlslValue::U32(u(0)),
+            Type::Bool => GlslValue::Bool(b(0)),
+            Type::Vec2 => GlslValue::Vec2(core::array::from_fn(f)),
+            Type::Vec3 => GlslValue::Vec3(core::array::from_fn(f)),
+            Type::Vec4 => GlslValue::Vec4(core::array::from_fn(f)),
+            Type::IVec2 => GlslValue::IVec2(core::array::from_fn(i)),
+            Type::IVec3 => GlslValue::IVec3(core::array::from_fn(i)),
+            Type::IVec4 => GlslValue::IVec4(core::array::from_fn(i)),
+            Type::UVec2 => GlslValue::UVec2(core::array::from_fn(u)),
+            Type::UVec3 => GlslValue::UVec3(core::array::from_fn(u)),
+            Type::UVec4 => GlslValue::UVec4(core::array::from_fn(u)),
+            Type::BVec2 => GlslValue::BVec2(core::array::from_fn(b)),
+            Type::BVec3 => GlslValue::BVec3(core::array::from_fn(b)),
+            Type::BVec4 => GlslValue::BVec4(core::array::from_fn(b)),
+            Type::Mat2 => GlslValue::Mat2x2(core::array::from_fn(|c| {
+                core::array::from_fn(|r| f(c * 2 + r))
+            })),
+            Type::Mat3 => GlslValue::Mat3x3(core::array::from_fn(|c| {
+                core::array::from_fn(|r| f(c * 3 + r))
+            })),
+            Type::Mat4 => GlslValue::Mat4x4(core::array::from_fn(|c| {
+                core::array::from_fn(|r| f(c * 4 + r))
+            })),
+            ref ty => return Err(unsupported(format!("returning `{ty:?}`"))),
+        })
+    }
+}
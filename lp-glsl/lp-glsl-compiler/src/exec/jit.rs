@@ -6,6 +6,8 @@ use crate::error::GlslError;
 use crate::exec::executable::GlslExecutable;
 use crate::exec::glsl_value::GlslValue;
 use crate::frontend::semantic::functions::FunctionSignature;
+use crate::frontend::semantic::passes::purity::Purity;
+use core::mem::ManuallyDrop;
 use cranelift_codegen::ir::types;
 use hashbrown::HashMap;
 use lp_glsl_jit_util::{call_structreturn, call_structreturn_with_args};
@@ -16,14 +18,20 @@ use log;
 /// JIT-compiled GLSL module (executes on host or embedded)
 /// Works in both std and no_std (JITModule supports no_std)
 pub struct GlslJitModule {
-    #[allow(dead_code, reason = "JIT module stored for future use")]
-    pub(crate) jit_module: cranelift_jit::JITModule,
+    // Owns the code memory behind `function_ptrs`, which is freed on drop
+    pub(crate) jit_module: ManuallyDrop<cranelift_jit::JITModule>,
     pub(crate) function_ptrs: HashMap<String, *const u8>,
     pub(crate) signatures: HashMap<String, FunctionSignature>,
+    // Which functions are pure or read time
+    pub(crate) purity: Purity,
     // Store Cranelift signatures for proper function calling with arguments
     pub(crate) cranelift_signatures: HashMap<String, cranelift_codegen::ir::Signature>,
     pub(crate) call_conv: cranelift_codegen::isa::CallConv,
     pub(crate) pointer_type: cranelift_codegen::ir::Type,
+    // Uniform values read by the compiled code (must outlive the function pointers)
+    pub(crate) uniforms: crate::backend::module::uniforms::UniformBlock,
+    // Loop iteration counter decremented by the compiled code, if compiled with fuel
+    pub(crate) fuel: Option<crate::backend::module::fuel::LoopFuel>,
 }
 
 impl GlslJitModule {
@@ -524,6 +532,16 @@ impl GlslJitModule {
     }
 }
 
+impl Drop for GlslJitModule {
+    fn drop(&mut self) {
+        // A JITModule keeps its code memory when dropped, as function pointers into it
+        // may still be around. Only this module hands them out (see `DirectCallInfo`),
+        // so without this every recompiled shader would leak its code.
+        let jit_module = unsafe { ManuallyDrop::take(&mut self.jit_module) };
+        unsafe { jit_module.free_memory() };
+    }
+}
+
 impl GlslExecutable for GlslJitModule {
     fn call_void(&mut self, name: &str, args: &[GlslValue]) -> Result<(), GlslError> {
         use crate::error::ErrorCode;
@@ -1450,6 +1468,14 @@ impl GlslExecutable for GlslJitModule {
         self.signatures.keys().cloned().collect()
     }
 
+    fn is_pure(&self, name: &str) -> bool {
+        self.purity.is_pure(name)
+    }
+
+    fn reads_time(&self, name: &str) -> bool {
+        self.purity.reads_time(name)
+    }
+
     fn get_direct_call_info(&self, name: &str) -> Option<crate::exec::executable::DirectCallInfo> {
         let func_ptr = *self.function_ptrs.get(name)?;
         Some(crate::exec::executable::DirectCallInfo {
@@ -1458,12 +1484,38 @@ impl GlslExecutable for GlslJitModule {
             pointer_type: self.pointer_type,
         })
     }
+
+    fn set_uniform(&mut self, name: &str, value: GlslValue) -> Result<(), GlslError> {
+        self.uniforms.set(name, &value)
+    }
+
+    fn list_uniforms(&self) -> Vec<String> {
+        self.uniforms
+            .entries()
+            .iter()
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    fn set_fuel(&mut self, fuel: u32) -> Result<(), GlslError> {
+        use crate::error::ErrorCode;
+        let loop_fuel = self.fuel.as_ref().ok_or_else(|| {
+            GlslError::new(ErrorCode::E0400, "module was compiled without loop fuel")
+        })?;
+        loop_fuel.set(fuel);
+        Ok(())
+    }
+
+    fn fuel_remaining(&self) -> Option<u32> {
+        self.fuel.as_ref().map(|fuel| fuel.remaining())
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "std")]
 mod tests {
-    use crate::{DecimalFormat, GlslOptions, RunMode, glsl_jit};
+    use crate::{DecimalFormat, GlslOptions, GlslValue, OptLevel, RunMode, glsl_jit};
+    use alloc::string::String;
 
     #[test]
     fn test_jit_int_literal() {
@@ -1476,6 +1528,10 @@ mod tests {
         let options = GlslOptions {
             run_mode: RunMode::HostJit,
             decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         };
 
         let mut executable = glsl_jit(source, options).expect("Compilation failed");
@@ -1496,6 +1552,10 @@ mod tests {
         let options = GlslOptions {
             run_mode: RunMode::HostJit,
             decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         };
 
         let mut executable = glsl_jit(source, options).expect("Compilation failed");
@@ -1514,6 +1574,10 @@ mod tests {
         let options = GlslOptions {
             run_mode: RunMode::HostJit,
             decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         };
 
         let mut executable = glsl_jit(source, options).expect("Compilation failed");
@@ -1533,10 +1597,53 @@ mod tests {
         let options = GlslOptions {
             run_mode: RunMode::HostJit,
             decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         };
 
         let mut executable = glsl_jit(source, options).expect("Compilation failed");
         let result = executable.call_bool("main", &[]).expect("Execution failed");
         assert_eq!(result, true);
     }
+
+    #[test]
+    fn test_jit_uniform_update() {
+        let source = r#"
+        uniform float speed;
+        float main() {
+            return speed * 2.0;
+        }
+    "#;
+
+        let options = GlslOptions {
+            run_mode: RunMode::HostJit,
+            decimal_format: DecimalFormat::Q32,
+            opt_level: OptLevel::None,
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
+        };
+
+        let mut executable = glsl_jit(source, options).expect("Compilation failed");
+        assert!(executable.list_uniforms().contains(&String::from("speed")));
+
+        // Uniforms start zeroed
+        let result = executable.call_f32("main", &[]).expect("Execution failed");
+        assert_eq!(result, 0.0);
+
+        // Updating the value takes effect on the next call without recompiling
+        executable
+            .set_uniform("speed", GlslValue::F32(1.5))
+            .expect("Failed to set uniform");
+        let result = executable.call_f32("main", &[]).expect("Execution failed");
+        assert!((result - 3.0).abs() < 0.01);
+
+        assert!(
+            executable
+                .set_uniform("missing", GlslValue::F32(1.0))
+                .is_err()
+        );
+    }
 }
@@ -9,10 +9,14 @@ pub(crate) mod executable;
 #[cfg(feature = "std")]
 pub mod execute_fn;
 pub(crate) mod glsl_value;
+pub(crate) mod interp;
 pub(crate) mod jit;
+#[cfg(all(feature = "std", feature = "emulator"))]
+pub mod test_util;
 
 #[cfg(feature = "emulator")]
 pub use emu::GlslEmulatorModule;
 pub use executable::{DecimalFormat, GlslExecutable, GlslOptions, RunMode};
 pub use glsl_value::GlslValue;
+pub use interp::{GlslInterpreterModule, interpret};
 pub use jit::GlslJitModule;
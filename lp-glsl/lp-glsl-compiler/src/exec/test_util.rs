@@ -0,0 +1,191 @@
+//! In-process shader test harness.
+//!
+//! Compiles a shader for the RISC-V 32-bit emulator, runs an entry point with the given
+//! arguments, and returns the result. This exercises the same code path as on-device
+//! execution (object file, builtins linking, guest calling convention) without going
+//! through the host JIT.
+//!
+//! [`find_divergences`] also runs the shader on the host JIT, as a differential test:
+//! subtle codegen bugs show up as host results the device doesn't reproduce.
+
+use crate::exec::GlslExecutable;
+use crate::exec::executable::{GlslOptions, RunMode};
+use crate::exec::execute_fn::execute_function;
+use crate::exec::glsl_value::GlslValue;
+use crate::frontend::{glsl_emu_riscv32, glsl_jit};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use anyhow::Result;
+
+/// Instruction budget for a single shader run
+const MAX_INSTRUCTIONS: u64 = 1_000_000;
+
+/// Result of running a shader under the emulator
+#[derive(Debug, Clone)]
+pub struct ShaderResult {
+    /// Value returned by the entry point.
+    /// Vector and matrix results are read back out of guest memory (StructReturn buffer).
+    pub value: GlslValue,
+    /// Disassembly of the compiled code, if available (useful in assertion messages)
+    pub disassembly: Option<String>,
+}
+
+/// Compile `source` and run `main` with `args` under the RISC-V emulator
+pub fn run_shader(source: &str, args: &[GlslValue]) -> Result<ShaderResult> {
+    run_shader_fn(source, "main", args)
+}
+
+/// Compile `source` and run function `name` with `args` under the RISC-V emulator
+pub fn run_shader_fn(source: &str, name: &str, args: &[GlslValue]) -> Result<ShaderResult> {
+    let mut executable = compile_shader(source)?;
+    let value = execute_function(executable.as_mut(), name, args)?;
+
+    Ok(ShaderResult {
+        value,
+        disassembly: executable.format_disassembly(),
+    })
+}
+
+/// Compile `source` for the RISC-V emulator, with the same options as [`run_shader`]
+///
+/// The instruction budget applies to each call, so the executable can be run repeatedly.
+pub fn compile_shader(source: &str) -> Result<Box<dyn GlslExecutable>> {
+    let mut options = GlslOptions::emu_riscv32_imac();
+    if let RunMode::Emulator {
+        max_instructions, ..
+    } = &mut options.run_mode
+    {
+        *max_instructions = MAX_INSTRUCTIONS;
+    }
+
+    glsl_emu_riscv32(source, options).map_err(|e| anyhow::anyhow!("{e:#}"))
+}
+
+/// Inputs for which the host and RISC-V builds of a shader disagree
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    /// Arguments `main` was called with
+    pub inputs: Vec<GlslValue>,
+    /// Result of the host JIT build (native floats)
+    pub host: GlslValue,
+    /// Result of the RISC-V build (Q32 fixed point, as on the device)
+    pub riscv: GlslValue,
+}
+
+/// Run `main` of `source` on the host JIT and under the RISC-V emulator for each set
+/// of `samples`, returning the ones whose results differ by more than `tolerance`
+///
+/// The host build uses native floats, so `tolerance` has to cover Q32 precision and
+/// the fixed-point approximations of builtins. Integer and bool results must match
+/// exactly. An empty result means the builds agree on every sample.
+pub fn find_divergences(
+    source: &str,
+    samples: &[Vec<GlslValue>],
+    tolerance: f32,
+) -> Result<Vec<Divergence>> {
+    let mut host =
+        glsl_jit(source, GlslOptions::jit()).map_err(|e| anyhow::anyhow!("host: {e:#}"))?;
+    let mut riscv = compile_shader(source)?;
+
+    let mut divergences = Vec::new();
+    for inputs in samples {
+        let host_value = execute_function(host.as_mut(), "main", inputs)?;
+        let riscv_value = execute_function(riscv.as_mut(), "main", inputs)?;
+        if !host_value.approx_eq(&riscv_value, tolerance) {
+            divergences.push(Divergence {
+                inputs: inputs.clone(),
+                host: host_value,
+                riscv: riscv_value,
+            });
+        }
+    }
+    Ok(divergences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_shader_int_arg() {
+        let source = r#"
+int main(int x) {
+    return x * 2 + 1;
+}
+"#;
+        let result = run_shader(source, &[GlslValue::I32(20)]).unwrap();
+        assert!(
+            matches!(result.value, GlslValue::I32(41)),
+            "got {:?}",
+            result.value
+        );
+    }
+
+    #[test]
+    fn test_run_shader_vec3_return() {
+        let source = r#"
+vec3 main(float x) {
+    return vec3(x, x * 2.0, x + 1.0);
+}
+"#;
+        let result = run_shader(source, &[GlslValue::F32(0.5)]).unwrap();
+        match result.value {
+            GlslValue::Vec3([a, b, c]) => {
+                assert!((a - 0.5).abs() < 0.001, "x: {a}");
+                assert!((b - 1.0).abs() < 0.001, "y: {b}");
+                assert!((c - 1.5).abs() < 0.001, "z: {c}");
+            }
+            other => panic!("expected vec3, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_run_shader_missing_function() {
+        let source = "int main() { return 1; }";
+        assert!(run_shader_fn(source, "nope", &[]).is_err());
+    }
+
+    #[test]
+    fn test_agreeing_shader_has_no_divergences() {
+        let source = r#"
+vec2 main(float x, float y) {
+    return vec2(x * 0.5 + y, clamp(x - y * 2.0, -1.0, 1.0));
+}
+"#;
+        let samples: Vec<Vec<GlslValue>> = (0..4)
+            .flat_map(|x| {
+                (0..4).map(move |y| {
+                    vec![
+                        GlslValue::F32(x as f32 * 0.75),
+                        GlslValue::F32(y as f32 * -0.5),
+                    ]
+                })
+            })
+            .collect();
+        let divergences = find_divergences(source, &samples, 1e-3).unwrap();
+        assert!(
+            divergences.is_empty(),
+            "unexpected divergences: {divergences:?}"
+        );
+    }
+
+    #[test]
+    fn test_rounding_divergence_is_reported() {
+        // 1/3 rounds down in Q32, so multiplying back gives just under 1 and `floor`
+        // turns the rounding error into a whole unit
+        let source = r#"
+float main(float x) {
+    return floor(x / 3.0 * 3.0);
+}
+"#;
+        let samples = [0.0, 1.0, 3.0].map(|x| vec![GlslValue::F32(x)]);
+        let divergences = find_divergences(source, &samples, 1e-3).unwrap();
+        let divergence = divergences
+            .iter()
+            .find(|d| matches!(d.inputs.as_slice(), [GlslValue::F32(x)] if *x == 1.0))
+            .unwrap_or_else(|| panic!("x = 1 not reported: {divergences:?}"));
+        assert!(matches!(divergence.host, GlslValue::F32(v) if v == 1.0));
+        assert!(matches!(divergence.riscv, GlslValue::F32(v) if v == 0.0));
+    }
+}
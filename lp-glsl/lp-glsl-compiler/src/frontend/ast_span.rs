@@ -0,0 +1,320 @@
+//! Source ranges for every AST node.
+//!
+//! The glsl parser records a single position on each expression and identifier, and
+//! nothing on statements, declarations or functions. [`AstSpan`] gives any node a
+//! [`GlSourceSpan`] covering the positions recorded inside it: it starts at the earliest
+//! one and ends at the latest. The end is the position of the last expression or name
+//! in the node, not the end of its last token.
+//!
+//! Nodes with nothing positioned inside them (`break;`, an empty block) have no span.
+
+use crate::frontend::src_loc::{GlFileId, GlSourceSpan};
+use glsl::syntax::{
+    ArraySpecifier, ArraySpecifierDimension, Condition, Declaration, Expr, ExternalDeclaration,
+    ForInitStatement, FunIdentifier, FunctionDefinition, FunctionParameterDeclaration,
+    FunctionPrototype, Identifier, Initializer, IterationStatement, JumpStatement,
+    SelectionRestStatement, SelectionStatement, SimpleStatement, SourceSpan, Statement,
+    TranslationUnit,
+};
+
+/// Earliest and latest known positions seen so far, as (line, column)
+#[derive(Default)]
+pub struct Positions {
+    range: Option<((usize, usize), (usize, usize))>,
+}
+
+impl Positions {
+    /// Include `span`, ignoring unknown spans
+    pub fn add(&mut self, span: &SourceSpan) {
+        if span.is_unknown() {
+            return;
+        }
+        let pos = (span.line, span.column);
+        self.range = Some(match self.range {
+            Some((start, end)) => (start.min(pos), end.max(pos)),
+            None => (pos, pos),
+        });
+    }
+
+    /// Range covering every position added (file 0, like parser locations)
+    pub fn span(&self) -> Option<GlSourceSpan> {
+        self.range
+            .map(|((start_line, start_column), (end_line, end_column))| {
+                GlSourceSpan::new(GlFileId(0), start_line, start_column, end_line, end_column)
+            })
+    }
+}
+
+/// An AST node with a source range
+pub trait AstSpan {
+    /// Add the positions recorded in this node and its children
+    fn add_positions(&self, positions: &mut Positions);
+
+    /// Range covering this node, if anything inside it has a known position
+    fn ast_span(&self) -> Option<GlSourceSpan> {
+        let mut positions = Positions::default();
+        self.add_positions(&mut positions);
+        positions.span()
+    }
+
+    /// Line this node starts on, if known
+    fn start_line(&self) -> Option<usize> {
+        self.ast_span().map(|span| span.start_line)
+    }
+}
+
+impl<T: AstSpan> AstSpan for Option<T> {
+    fn add_positions(&self, positions: &mut Positions) {
+        if let Some(node) = self {
+            node.add_positions(positions);
+        }
+    }
+}
+
+impl<T: AstSpan> AstSpan for [T] {
+    fn add_positions(&self, positions: &mut Positions) {
+        for node in self {
+            node.add_positions(positions);
+        }
+    }
+}
+
+impl AstSpan for Identifier {
+    fn add_positions(&self, positions: &mut Positions) {
+        positions.add(&self.span);
+    }
+}
+
+impl AstSpan for Expr {
+    fn add_positions(&self, positions: &mut Positions) {
+        positions.add(&crate::error::extract_span_from_expr(self));
+        match self {
+            Expr::Variable(ident, _) => ident.add_positions(positions),
+            Expr::IntConst(..)
+            | Expr::UIntConst(..)
+            | Expr::FloatConst(..)
+            | Expr::DoubleConst(..)
+            | Expr::BoolConst(..) => {}
+            Expr::Unary(_, operand, _) | Expr::PostInc(operand, _) | Expr::PostDec(operand, _) => {
+                operand.add_positions(positions)
+            }
+            Expr::Dot(base, field, _) => {
+                base.add_positions(positions);
+                field.add_positions(positions);
+            }
+            Expr::Binary(_, lhs, rhs, _)
+            | Expr::Assignment(lhs, _, rhs, _)
+            | Expr::Comma(lhs, rhs, _) => {
+                lhs.add_positions(positions);
+                rhs.add_positions(positions);
+            }
+            Expr::Ternary(cond, then_expr, else_expr, _) => {
+                cond.add_positions(positions);
+                then_expr.add_positions(positions);
+                else_expr.add_positions(positions);
+            }
+            Expr::Bracket(base, spec, _) => {
+                base.add_positions(positions);
+                spec.add_positions(positions);
+            }
+            Expr::FunCall(fun, args, _) => {
+                if let FunIdentifier::Identifier(ident) = fun {
+                    ident.add_positions(positions);
+                }
+                args.add_positions(positions);
+            }
+        }
+    }
+}
+
+impl AstSpan for ArraySpecifier {
+    fn add_positions(&self, positions: &mut Positions) {
+        for dimension in &self.dimensions.0 {
+            if let ArraySpecifierDimension::ExplicitlySized(size) = dimension {
+                size.add_positions(positions);
+            }
+        }
+    }
+}
+
+impl AstSpan for Initializer {
+    fn add_positions(&self, positions: &mut Positions) {
+        match self {
+            Initializer::Simple(expr) => expr.add_positions(positions),
+            Initializer::List(list) => list.0.add_positions(positions),
+        }
+    }
+}
+
+impl AstSpan for Condition {
+    fn add_positions(&self, positions: &mut Positions) {
+        match self {
+            Condition::Expr(expr) => expr.add_positions(positions),
+            Condition::Assignment(_, ident, init) => {
+                ident.add_positions(positions);
+                init.add_positions(positions);
+            }
+        }
+    }
+}
+
+impl AstSpan for Declaration {
+    fn add_positions(&self, positions: &mut Positions) {
+        match self {
+            Declaration::InitDeclaratorList(list) => {
+                list.head.name.add_positions(positions);
+                list.head.array_specifier.add_positions(positions);
+                list.head.initializer.add_positions(positions);
+                for declarator in &list.tail {
+                    declarator.ident.ident.add_positions(positions);
+                    declarator.ident.array_spec.add_positions(positions);
+                    declarator.initializer.add_positions(positions);
+                }
+            }
+            Declaration::FunctionPrototype(proto) => proto.add_positions(positions),
+            Declaration::Global(_, idents) => idents.add_positions(positions),
+            Declaration::Precision(..) | Declaration::Block(_) => {}
+        }
+    }
+}
+
+impl AstSpan for FunctionPrototype {
+    fn add_positions(&self, positions: &mut Positions) {
+        self.name.add_positions(positions);
+        for param in &self.parameters {
+            if let FunctionParameterDeclaration::Named(_, decl) = param {
+                decl.ident.ident.add_positions(positions);
+                decl.ident.array_spec.add_positions(positions);
+            }
+        }
+    }
+}
+
+impl AstSpan for Statement {
+    fn add_positions(&self, positions: &mut Positions) {
+        match self {
+            Statement::Compound(compound) => compound.statement_list.add_positions(positions),
+            Statement::Simple(simple) => simple.add_positions(positions),
+        }
+    }
+}
+
+impl AstSpan for SimpleStatement {
+    fn add_positions(&self, positions: &mut Positions) {
+        match self {
+            SimpleStatement::Declaration(decl) => decl.add_positions(positions),
+            SimpleStatement::Expression(expr) => expr.add_positions(positions),
+            SimpleStatement::Selection(selection) => selection.add_positions(positions),
+            SimpleStatement::Iteration(iteration) => iteration.add_positions(positions),
+            SimpleStatement::Jump(JumpStatement::Return(expr)) => expr.add_positions(positions),
+            _ => {}
+        }
+    }
+}
+
+impl AstSpan for SelectionStatement {
+    fn add_positions(&self, positions: &mut Positions) {
+        self.cond.add_positions(positions);
+        match &self.rest {
+            SelectionRestStatement::Statement(then_stmt) => then_stmt.add_positions(positions),
+            SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                then_stmt.add_positions(positions);
+                else_stmt.add_positions(positions);
+            }
+        }
+    }
+}
+
+impl AstSpan for IterationStatement {
+    fn add_positions(&self, positions: &mut Positions) {
+        match self {
+            IterationStatement::While(condition, body) => {
+                condition.add_positions(positions);
+                body.add_positions(positions);
+            }
+            IterationStatement::DoWhile(body, cond) => {
+                body.add_positions(positions);
+                cond.add_positions(positions);
+            }
+            IterationStatement::For(init, rest, body) => {
+                match init {
+                    ForInitStatement::Declaration(decl) => decl.add_positions(positions),
+                    ForInitStatement::Expression(expr) => expr.add_positions(positions),
+                }
+                rest.condition.add_positions(positions);
+                rest.post_expr.add_positions(positions);
+                body.add_positions(positions);
+            }
+        }
+    }
+}
+
+impl AstSpan for FunctionDefinition {
+    fn add_positions(&self, positions: &mut Positions) {
+        self.prototype.add_positions(positions);
+        self.statement.statement_list.add_positions(positions);
+    }
+}
+
+impl AstSpan for ExternalDeclaration {
+    fn add_positions(&self, positions: &mut Positions) {
+        match self {
+            ExternalDeclaration::FunctionDefinition(func) => func.add_positions(positions),
+            ExternalDeclaration::Declaration(decl) => decl.add_positions(positions),
+            _ => {}
+        }
+    }
+}
+
+impl AstSpan for TranslationUnit {
+    fn add_positions(&self, positions: &mut Positions) {
+        self.0.0.add_positions(positions);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glsl::parser::Parse;
+
+    fn start_and_end(span: GlSourceSpan) -> ((usize, usize), (usize, usize)) {
+        (
+            (span.start_line, span.start_column),
+            (span.end_line, span.end_column),
+        )
+    }
+
+    #[test]
+    fn test_sub_expression_span() {
+        let expr = Expr::parse("a + b * c").unwrap();
+        let Expr::Binary(_, lhs, rhs, _) = &expr else {
+            panic!("expected a binary expression, got {expr:?}");
+        };
+        assert_eq!(start_and_end(lhs.ast_span().unwrap()), ((1, 1), (1, 1)));
+        assert_eq!(start_and_end(rhs.ast_span().unwrap()), ((1, 5), (1, 9)));
+        assert_eq!(start_and_end(expr.ast_span().unwrap()), ((1, 1), (1, 9)));
+    }
+
+    #[test]
+    fn test_statement_spans() {
+        let unit = TranslationUnit::parse(
+            "float f(float x) {\n    float y = x * 2.0;\n    if (y > 1.0) {\n        y = 1.0;\n    }\n    return y;\n}\n",
+        )
+        .unwrap();
+        let ExternalDeclaration::FunctionDefinition(func) = &unit.0.0[0] else {
+            panic!("expected a function definition");
+        };
+        let body = &func.statement.statement_list;
+        assert_eq!(body[0].start_line(), Some(2));
+        let selection = body[1].ast_span().unwrap();
+        assert_eq!((selection.start_line, selection.end_line), (3, 4));
+        assert_eq!(body[2].start_line(), Some(6));
+        assert_eq!(func.start_line(), Some(1));
+    }
+
+    #[test]
+    fn test_no_positions() {
+        let stmt = Statement::parse("break;").unwrap();
+        assert!(stmt.ast_span().is_none());
+    }
+}
@@ -209,10 +209,12 @@ impl<'a, M: cranelift_module::Module> CodegenContext<'a, M> {
                     y_vals[i],
                 )
             } else {
-                // Integer comparison (works for both signed and unsigned)
-                self.builder
-                    .ins()
-                    .icmp(IntCC::SignedGreaterThan, x_vals[i], y_vals[i])
+                let cc = if base_ty == Type::UInt {
+                    IntCC::UnsignedGreaterThan
+                } else {
+                    IntCC::SignedGreaterThan
+                };
+                self.builder.ins().icmp(cc, x_vals[i], y_vals[i])
             };
             // Convert I1 to I8
             let result = self.builder.ins().select(cmp, one, zero);
@@ -258,10 +260,12 @@ impl<'a, M: cranelift_module::Module> CodegenContext<'a, M> {
                     y_vals[i],
                 )
             } else {
-                // Integer comparison (works for both signed and unsigned)
-                self.builder
-                    .ins()
-                    .icmp(IntCC::SignedGreaterThanOrEqual, x_vals[i], y_vals[i])
+                let cc = if base_ty == Type::UInt {
+                    IntCC::UnsignedGreaterThanOrEqual
+                } else {
+                    IntCC::SignedGreaterThanOrEqual
+                };
+                self.builder.ins().icmp(cc, x_vals[i], y_vals[i])
             };
             // Convert I1 to I8
             let result = self.builder.ins().select(cmp, one, zero);
@@ -307,10 +311,12 @@ impl<'a, M: cranelift_module::Module> CodegenContext<'a, M> {
                     y_vals[i],
                 )
             } else {
-                // Integer comparison (works for both signed and unsigned)
-                self.builder
-                    .ins()
-                    .icmp(IntCC::SignedLessThan, x_vals[i], y_vals[i])
+                let cc = if base_ty == Type::UInt {
+                    IntCC::UnsignedLessThan
+                } else {
+                    IntCC::SignedLessThan
+                };
+                self.builder.ins().icmp(cc, x_vals[i], y_vals[i])
             };
             // Convert I1 to I8
             let result = self.builder.ins().select(cmp, one, zero);
@@ -356,10 +362,12 @@ impl<'a, M: cranelift_module::Module> CodegenContext<'a, M> {
                     y_vals[i],
                 )
             } else {
-                // Integer comparison (works for both signed and unsigned)
-                self.builder
-                    .ins()
-                    .icmp(IntCC::SignedLessThanOrEqual, x_vals[i], y_vals[i])
+                let cc = if base_ty == Type::UInt {
+                    IntCC::UnsignedLessThanOrEqual
+                } else {
+                    IntCC::SignedLessThanOrEqual
+                };
+                self.builder.ins().icmp(cc, x_vals[i], y_vals[i])
             };
             // Convert I1 to I8
             let result = self.builder.ins().select(cmp, one, zero);
@@ -14,3 +14,8 @@ pub const FIXED16X16_SCALE: f32 = 65536.0;
 
 /// Precision of fixed16x16 format (1/65536)
 pub const FIXED16X16_PRECISION: f32 = 1.0 / 65536.0;
+
+/// Alpha of the sentinel color returned by `discard`
+///
+/// A shader returning a color with exactly this alpha leaves the pixel untouched.
+pub const DISCARD_ALPHA: f32 = -1.0;
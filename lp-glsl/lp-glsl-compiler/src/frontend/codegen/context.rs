@@ -269,6 +269,82 @@ impl<'a, M: Module> CodegenContext<'a, M> {
         Ok(vars)
     }
 
+    /// Declare every uniform as a variable loaded from the module's uniform block
+    ///
+    /// Emitted at function entry, so each call sees the values most recently set
+    /// by the host without recompiling.
+    pub fn declare_uniforms(&mut self) -> Result<(), crate::error::GlslError> {
+        use crate::backend::module::uniforms::UNIFORM_COMPONENT_BYTES;
+        use cranelift_codegen::ir::MemFlags;
+
+        if self.gl_module.uniforms.is_empty() {
+            return Ok(());
+        }
+
+        let pointer_type = self.gl_module.module_internal().isa().pointer_type();
+        let block_addr = self.gl_module.uniforms.base_address() as i64;
+        let base = self.builder.ins().iconst(pointer_type, block_addr);
+
+        let entries = self.gl_module.uniforms.entries().to_vec();
+        for entry in entries {
+            let base_ty = entry
+                .ty
+                .vector_base_type()
+                .unwrap_or_else(|| entry.ty.clone());
+            let cranelift_ty = base_ty.to_cranelift_type().map_err(|e| {
+                crate::error::GlslError::new(
+                    crate::error::ErrorCode::E0400,
+                    format!(
+                        "Failed to convert uniform `{}` to Cranelift type: {}",
+                        entry.name, e.message
+                    ),
+                )
+            })?;
+
+            let vars = self.declare_variable(entry.name.clone(), entry.ty.clone())?;
+            for (i, var) in vars.iter().enumerate() {
+                let offset = (entry.offset + i * UNIFORM_COMPONENT_BYTES) as i32;
+                let val = self
+                    .builder
+                    .ins()
+                    .load(cranelift_ty, MemFlags::trusted(), base, offset);
+                self.builder.def_var(*var, val);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Spend one unit of loop fuel, leaving the loop through `exit_block` once it has run out
+    ///
+    /// Emitted before every loop iteration. Does nothing unless the module was compiled with
+    /// loop fuel.
+    pub fn emit_fuel_check(&mut self, exit_block: Block) -> Result<(), GlslError> {
+        use cranelift_codegen::ir::{MemFlags, types};
+
+        let Some(fuel) = &self.gl_module.fuel else {
+            return Ok(());
+        };
+        let fuel_addr = fuel.as_ptr() as usize as i64;
+
+        let pointer_type = self.gl_module.module_internal().isa().pointer_type();
+        let addr = self.builder.ins().iconst(pointer_type, fuel_addr);
+        let remaining = self
+            .builder
+            .ins()
+            .load(types::I32, MemFlags::trusted(), addr, 0);
+
+        let fueled_block = self.builder.create_block();
+        self.emit_cond_branch(remaining, fueled_block, exit_block)?;
+
+        self.emit_block(fueled_block);
+        let remaining = self.builder.ins().iadd_imm(remaining, -1);
+        self.builder
+            .ins()
+            .store(MemFlags::trusted(), remaining, addr, 0);
+        Ok(())
+    }
+
     pub fn lookup_variable(&self, name: &str) -> Option<Variable> {
         // Legacy method: returns first component (for scalars)
         // Search scopes from innermost to outermost
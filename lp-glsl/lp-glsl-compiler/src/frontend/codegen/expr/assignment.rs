@@ -26,7 +26,7 @@ pub fn emit_assignment_rvalue<M: cranelift_module::Module>(
 
 /// Translate assignment expression (simple and compound)
 ///
-/// Handles both simple assignment (=) and compound assignment (+=, -=, *=, /=).
+/// Handles both simple assignment (=) and compound assignment (+=, -=, <<=, &=, ...).
 /// Returns the assigned value(s) and type.
 pub fn emit_assignment_typed<M: cranelift_module::Module>(
     ctx: &mut CodegenContext<'_, M>,
@@ -40,7 +40,7 @@ pub fn emit_assignment_typed<M: cranelift_module::Module>(
     use crate::frontend::codegen::lvalue::resolve_lvalue;
     use crate::semantic::type_check::conversion::check_assignment;
 
-    // Handle compound assignment operators (+=, -=, *=, /=)
+    // Handle compound assignment operators (+=, -=, <<=, &=, ...)
     if !matches!(op, glsl::syntax::AssignmentOp::Equal) {
         return emit_compound_assignment_typed(ctx, lhs, op, rhs);
     }
@@ -207,7 +207,7 @@ pub fn emit_assignment_typed<M: cranelift_module::Module>(
     Ok(result_vals)
 }
 
-/// Handle compound assignment operators (+=, -=, *=, /=)
+/// Handle compound assignment operators (+=, -=, <<=, &=, ...)
 fn emit_compound_assignment_typed<M: cranelift_module::Module>(
     ctx: &mut CodegenContext<'_, M>,
     lhs: &Expr,
@@ -239,8 +239,13 @@ fn emit_compound_assignment_typed<M: cranelift_module::Module>(
     // Only validate direct assignment compatibility for same-type operations
     let is_scalar_op_on_matrix = (lhs_ty.is_matrix() || lhs_ty.is_vector()) && rhs_ty.is_scalar();
     let is_scalar_op_on_vector = lhs_ty.is_vector() && rhs_ty.is_scalar();
+    // Shift amounts don't need to match the LHS type (`i <<= 1u` is fine)
+    let is_shift = matches!(
+        op,
+        glsl::syntax::AssignmentOp::LShift | glsl::syntax::AssignmentOp::RShift
+    );
 
-    if !is_scalar_op_on_matrix && !is_scalar_op_on_vector {
+    if !is_scalar_op_on_matrix && !is_scalar_op_on_vector && !is_shift {
         // For same-type operations, validate assignment compatibility
         match check_assignment(&lhs_ty, &rhs_ty) {
             Ok(()) => {}
@@ -262,6 +267,12 @@ fn emit_compound_assignment_typed<M: cranelift_module::Module>(
         glsl::syntax::AssignmentOp::Sub => BinaryOp::Sub,
         glsl::syntax::AssignmentOp::Mult => BinaryOp::Mult,
         glsl::syntax::AssignmentOp::Div => BinaryOp::Div,
+        glsl::syntax::AssignmentOp::Mod => BinaryOp::Mod,
+        glsl::syntax::AssignmentOp::LShift => BinaryOp::LShift,
+        glsl::syntax::AssignmentOp::RShift => BinaryOp::RShift,
+        glsl::syntax::AssignmentOp::And => BinaryOp::BitAnd,
+        glsl::syntax::AssignmentOp::Xor => BinaryOp::BitXor,
+        glsl::syntax::AssignmentOp::Or => BinaryOp::BitOr,
         _ => {
             return Err(GlslError::new(
                 ErrorCode::E0400,
@@ -271,7 +282,17 @@ fn emit_compound_assignment_typed<M: cranelift_module::Module>(
     };
 
     // Perform the compound operation
-    let (operation_result_vals, operation_result_ty) = if lhs_ty.is_matrix() || rhs_ty.is_matrix() {
+    let (operation_result_vals, operation_result_ty) = if is_shift {
+        binary::emit_shift(
+            ctx,
+            &binary_op,
+            lhs_vals,
+            &lhs_ty,
+            rhs_vals,
+            &rhs_ty,
+            rhs_span.clone(),
+        )?
+    } else if lhs_ty.is_matrix() || rhs_ty.is_matrix() {
         // Use matrix operations for matrix compound assignments
         matrix::emit_matrix_binary(
             ctx,
@@ -320,6 +341,16 @@ fn emit_compound_assignment_typed<M: cranelift_module::Module>(
         (vec![result_val], base_ty)
     };
 
+    // The promoted result must still fit the LHS (e.g. `ivec3 *= float` is an error)
+    if operation_result_ty != lhs_ty {
+        if let Err(mut error) = check_assignment(&lhs_ty, &operation_result_ty) {
+            if error.location.is_none() {
+                error = error.with_location(source_span_to_location(&rhs_span));
+            }
+            return Err(ctx.add_span_to_error(error, &rhs_span));
+        }
+    }
+
     // Write result back to LHS
     write_lvalue(ctx, &lvalue, &operation_result_vals)?;
 
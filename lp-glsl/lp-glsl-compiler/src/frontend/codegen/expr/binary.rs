@@ -8,7 +8,7 @@ use cranelift_codegen::ir::{
     condcodes::{FloatCC, IntCC},
     types,
 };
-use glsl::syntax::Expr;
+use glsl::syntax::{BinaryOp, Expr};
 
 use super::coercion;
 use super::matrix;
@@ -35,8 +35,11 @@ pub fn emit_binary_rvalue<M: cranelift_module::Module>(
     let lhs_vals = lhs_rvalue.into_values();
     let rhs_vals = rhs_rvalue.into_values();
 
-    // Delegate to matrix/vector/scalar handlers
-    if lhs_ty.is_matrix() || rhs_ty.is_matrix() {
+    // Delegate to shift/matrix/vector/scalar handlers
+    if matches!(op, BinaryOp::LShift | BinaryOp::RShift) {
+        let (vals, ty) = emit_shift(ctx, op, lhs_vals, &lhs_ty, rhs_vals, &rhs_ty, span.clone())?;
+        Ok(RValue::from_aggregate(vals, ty))
+    } else if lhs_ty.is_matrix() || rhs_ty.is_matrix() {
         let (vals, ty) = matrix::emit_matrix_binary(
             ctx,
             op,
@@ -82,6 +85,48 @@ pub fn emit_binary<M: cranelift_module::Module>(
     Ok((rvalue.into_values(), ty))
 }
 
+/// Emit `<<` or `>>` on scalars or vectors
+///
+/// Unlike other operators the operands aren't promoted: the result has the type of the
+/// left operand, which alone decides between arithmetic and logical right shift. The
+/// shift amount is a scalar or a vector with one amount per component.
+///
+/// GLSL leaves shifts by a negative amount or by 32 or more undefined. Here only the
+/// low 5 bits of the amount are used, as by Cranelift's `ishl`/`sshr`/`ushr` and the
+/// RV32 shift instructions, so `x << 32` is `x` and `x >> -1` is `x >> 31`. Constant
+/// folding and the interpreter do the same.
+pub fn emit_shift<M: cranelift_module::Module>(
+    ctx: &mut CodegenContext<'_, M>,
+    op: &BinaryOp,
+    lhs_vals: Vec<Value>,
+    lhs_ty: &GlslType,
+    rhs_vals: Vec<Value>,
+    rhs_ty: &GlslType,
+    span: glsl::syntax::SourceSpan,
+) -> Result<(Vec<Value>, GlslType), GlslError> {
+    let result_ty = infer_binary_result_type(op, lhs_ty, rhs_ty, span.clone())?;
+    let base_ty = lhs_ty.vector_base_type().unwrap_or_else(|| lhs_ty.clone());
+
+    let mut result_vals = Vec::with_capacity(lhs_vals.len());
+    for (i, &lhs_val) in lhs_vals.iter().enumerate() {
+        // int and uint are both i32, so the amount needs no conversion
+        let amount = if rhs_vals.len() == 1 {
+            rhs_vals[0]
+        } else {
+            rhs_vals[i]
+        };
+        result_vals.push(emit_scalar_binary_op(
+            ctx,
+            op,
+            lhs_val,
+            amount,
+            &base_ty,
+            span.clone(),
+        )?);
+    }
+    Ok((result_vals, result_ty))
+}
+
 fn emit_scalar_binary<M: cranelift_module::Module>(
     ctx: &mut CodegenContext<'_, M>,
     op: &glsl::syntax::BinaryOp,
@@ -234,6 +279,28 @@ fn emit_scalar_binary_op<M: cranelift_module::Module>(
             }
         }
 
+        // Bitwise operators (integer only, already validated by type_check)
+        BitAnd | BitOr | BitXor if matches!(operand_ty, GlslType::Int | GlslType::UInt) => match op
+        {
+            BitAnd => ctx.builder.ins().band(lhs, rhs),
+            BitOr => ctx.builder.ins().bor(lhs, rhs),
+            _ => ctx.builder.ins().bxor(lhs, rhs),
+        },
+        // Shifts: operand_ty is the left operand's type, which picks sshr or ushr
+        LShift if matches!(operand_ty, GlslType::Int | GlslType::UInt) => {
+            ctx.builder.ins().ishl(lhs, rhs)
+        }
+        RShift => match operand_ty {
+            GlslType::Int => ctx.builder.ins().sshr(lhs, rhs),
+            GlslType::UInt => ctx.builder.ins().ushr(lhs, rhs),
+            _ => {
+                return Err(GlslError::new(
+                    ErrorCode::E0400,
+                    format!("right shift not supported for {operand_ty:?}"),
+                ));
+            }
+        },
+
         // Comparison operators - dispatch based on type
         // icmp/fcmp return I1, but GLSL bool is I8, so convert
         Equal => {
@@ -78,7 +78,8 @@ pub fn emit_vector_constructor<M: cranelift_module::Module>(
                 ty.clone()
             };
 
-            for &val in vals {
+            // The last argument may be only partially consumed
+            for &val in vals.iter().take(component_count - components.len()) {
                 components.push(coercion::coerce_to_type(ctx, val, &arg_base, &base_type)?);
             }
         }
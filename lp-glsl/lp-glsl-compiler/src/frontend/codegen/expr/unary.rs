@@ -90,7 +90,7 @@ fn emit_unary_op<M: cranelift_module::Module>(
                 operand_ty.clone()
             };
             match base_ty {
-                GlslType::Int => ctx.builder.ins().ineg(val),
+                GlslType::Int | GlslType::UInt => ctx.builder.ins().ineg(val),
                 GlslType::Float => ctx.builder.ins().fneg(val),
                 _ => {
                     return Err(GlslError::new(
@@ -110,6 +110,18 @@ fn emit_unary_op<M: cranelift_module::Module>(
             let zero = ctx.builder.ins().iconst(types::I8, 0);
             ctx.builder.ins().icmp(IntCC::Equal, val, zero)
         }
+        Complement => {
+            let base_ty = operand_ty
+                .vector_base_type()
+                .unwrap_or_else(|| operand_ty.clone());
+            if !matches!(base_ty, GlslType::Int | GlslType::UInt) {
+                return Err(GlslError::new(
+                    ErrorCode::E0112,
+                    format!("bitwise complement requires an integer, got {operand_ty:?}"),
+                ));
+            }
+            ctx.builder.ins().bnot(val)
+        }
         Inc => {
             // Handle pre-increment directly here since dispatch isn't working
             return Err(GlslError::new(
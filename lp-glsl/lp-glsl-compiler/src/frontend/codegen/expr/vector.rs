@@ -1,5 +1,6 @@
 use crate::error::{ErrorCode, GlslError, source_span_to_location};
 use crate::frontend::codegen::context::CodegenContext;
+use crate::semantic::type_check::promote_operands;
 use crate::semantic::types::Type as GlslType;
 use cranelift_codegen::ir::{InstBuilder, Value};
 
@@ -22,6 +23,7 @@ pub fn emit_vector_binary<M: cranelift_module::Module>(
     // Validate operation is allowed on vectors
     match op {
         Add | Sub | Mult | Div | Mod => {} // arithmetic operations
+        BitAnd | BitOr | BitXor => {}      // bitwise operations (shifts use binary::emit_shift)
         Equal | NonEqual => {} // comparison operations (aggregate comparison, returns bool)
         _ => {
             return Err(GlslError::new(
@@ -31,54 +33,52 @@ pub fn emit_vector_binary<M: cranelift_module::Module>(
         }
     }
 
-    // Determine result type and operation mode
+    // Determine operation mode
     enum VectorOpMode {
         ComponentWise, // vec + vec
         VectorScalar,  // vec + scalar
         ScalarVector,  // scalar + vec
     }
 
-    let (result_ty, mode) = if lhs_ty.is_vector() && rhs_ty.is_vector() {
-        // vec + vec: component-wise, types must match
-        if lhs_ty != rhs_ty {
-            let mut error = GlslError::new(
-                ErrorCode::E0106,
-                format!("vector operation requires matching types, got {lhs_ty:?} and {rhs_ty:?}"),
-            );
-            if let Some(s) = span {
-                error = error.with_location(source_span_to_location(&s));
-            }
-            return Err(error);
-        }
-        (lhs_ty.clone(), VectorOpMode::ComponentWise)
-    } else if lhs_ty.is_vector() && rhs_ty.is_scalar() {
-        // vec + scalar: scalar applied to each component
-        let vec_base = lhs_ty.vector_base_type().unwrap();
-        // Check if scalar can be used with vector base type
-        if !rhs_ty.is_numeric() || !vec_base.is_numeric() {
-            return Err(GlslError::new(
-                ErrorCode::E0106,
-                format!("cannot use {rhs_ty:?} with {lhs_ty:?}"),
-            ));
-        }
-        (lhs_ty.clone(), VectorOpMode::VectorScalar)
-    } else if lhs_ty.is_scalar() && rhs_ty.is_vector() {
-        // scalar + vec: scalar applied to each component
-        let vec_base = rhs_ty.vector_base_type().unwrap();
-        if !lhs_ty.is_numeric() || !vec_base.is_numeric() {
-            return Err(GlslError::new(
-                ErrorCode::E0400,
-                format!("Cannot use {lhs_ty:?} with {rhs_ty:?}"),
-            ));
-        }
-        (rhs_ty.clone(), VectorOpMode::ScalarVector)
+    let mode = match (lhs_ty.is_vector(), rhs_ty.is_vector()) {
+        (true, true) => VectorOpMode::ComponentWise,
+        (true, false) => VectorOpMode::VectorScalar,
+        (false, true) => VectorOpMode::ScalarVector,
+        (false, false) => unreachable!("translate_vector_binary called with non-vector types"),
+    };
+
+    // Promote operands to a common type (e.g. ivec3 * float -> vec3)
+    let promoted = if lhs_ty == rhs_ty {
+        Some(lhs_ty.clone())
     } else {
-        unreachable!("translate_vector_binary called with non-vector types");
+        promote_operands(lhs_ty, rhs_ty)
+    };
+    let Some(result_ty) = promoted else {
+        let mut error = GlslError::new(
+            ErrorCode::E0106,
+            format!("vector operation requires compatible types, got {lhs_ty:?} and {rhs_ty:?}"),
+        );
+        if let Some(s) = span {
+            error = error.with_location(source_span_to_location(&s));
+        }
+        return Err(error);
     };
 
     let base_ty = result_ty.vector_base_type().unwrap();
     let component_count = result_ty.component_count().unwrap();
 
+    // Coerce both operands' components to the promoted base type
+    let lhs_base = lhs_ty.vector_base_type().unwrap_or_else(|| lhs_ty.clone());
+    let rhs_base = rhs_ty.vector_base_type().unwrap_or_else(|| rhs_ty.clone());
+    let lhs_vals = lhs_vals
+        .into_iter()
+        .map(|val| coercion::coerce_to_type(ctx, val, &lhs_base, &base_ty))
+        .collect::<Result<Vec<_>, _>>()?;
+    let rhs_vals = rhs_vals
+        .into_iter()
+        .map(|val| coercion::coerce_to_type(ctx, val, &rhs_base, &base_ty))
+        .collect::<Result<Vec<_>, _>>()?;
+
     // Handle comparison operators specially - they return scalar bool (aggregate comparison)
     if matches!(
         op,
@@ -87,7 +87,7 @@ pub fn emit_vector_binary<M: cranelift_module::Module>(
         if !matches!(mode, VectorOpMode::ComponentWise) {
             return Err(GlslError::new(
                 ErrorCode::E0400,
-                "comparison operators require matching vector types",
+                "comparison operators require vector operands of the same size",
             ));
         }
         // Aggregate comparison: compare all components and return bool (true if all equal)
@@ -170,8 +170,6 @@ pub fn emit_vector_binary<M: cranelift_module::Module>(
         VectorOpMode::VectorScalar => {
             // vec3(a,b,c) * s = vec3(a*s, b*s, c*s)
             let scalar = rhs_vals[0];
-            // Coerce scalar to vector base type if needed
-            let scalar = coercion::coerce_to_type(ctx, scalar, rhs_ty, &base_ty)?;
             for &comp in &lhs_vals {
                 let result_comp = binary::emit_scalar_binary_op_internal(
                     ctx,
@@ -187,8 +185,6 @@ pub fn emit_vector_binary<M: cranelift_module::Module>(
         VectorOpMode::ScalarVector => {
             // s * vec3(a,b,c) = vec3(s*a, s*b, s*c)
             let scalar = lhs_vals[0];
-            // Coerce scalar to vector base type if needed
-            let scalar = coercion::coerce_to_type(ctx, scalar, lhs_ty, &base_ty)?;
             for &comp in &rhs_vals {
                 let result_comp = binary::emit_scalar_binary_op_internal(
                     ctx,
@@ -1,11 +1,9 @@
 use glsl::syntax::JumpStatement;
 
-use crate::error::{ErrorCode, GlslError};
+use crate::error::GlslError;
 use crate::frontend::codegen::context::CodegenContext;
 
-use alloc::format;
-
-/// Emit jump statement (dispatch to break, continue, return)
+/// Emit jump statement (dispatch to break, continue, return, discard)
 pub fn emit_jump_stmt<M: cranelift_module::Module>(
     ctx: &mut CodegenContext<'_, M>,
     jump: &JumpStatement,
@@ -21,9 +19,6 @@ pub fn emit_jump_stmt<M: cranelift_module::Module>(
             ctx,
             expr.as_ref().map(|v| &**v),
         ),
-        _ => Err(GlslError::new(
-            ErrorCode::E0400,
-            format!("jump statement not supported: {jump:?}"),
-        )),
+        JumpStatement::Discard => crate::frontend::codegen::stmt::r#return::emit_discard_stmt(ctx),
     }
 }
@@ -45,6 +45,7 @@ pub fn emit_loop_do_while_stmt<M: cranelift_module::Module>(
     //   will create block parameters to merge values from all predecessors
     ctx.switch_to_block(cond_block);
     ctx.seal_block(cond_block);
+    ctx.emit_fuel_check(exit_block)?;
 
     // Now translate the condition expression, which may use variables from body_block
     // or from continue statements. Since cond_block is sealed with all predecessors
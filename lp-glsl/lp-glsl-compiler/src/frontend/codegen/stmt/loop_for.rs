@@ -43,9 +43,10 @@ pub fn emit_loop_for_stmt<M: cranelift_module::Module>(
 
     ctx.emit_branch(header_block)?;
 
-    // Header: evaluate condition
+    // Header: spend loop fuel, then evaluate condition
     // Don't seal header yet - it will receive a back edge from update block
     ctx.switch_to_block(header_block);
+    ctx.emit_fuel_check(exit_block)?;
     let condition_value = if let Some(condition) = &rest.condition {
         emit_condition(ctx, condition)?
     } else {
@@ -30,9 +30,10 @@ pub fn emit_loop_while_stmt<M: cranelift_module::Module>(
     // in scope until the end of the sub-statement of the while loop"
     ctx.enter_scope();
 
-    // Header: evaluate condition
+    // Header: spend loop fuel, then evaluate condition
     // Don't seal header yet - it will receive a back edge from body
     ctx.switch_to_block(header_block);
+    ctx.emit_fuel_check(exit_block)?;
     let condition_value = emit_condition(ctx, condition)?;
     ctx.emit_cond_branch(condition_value, body_block, exit_block)?;
 
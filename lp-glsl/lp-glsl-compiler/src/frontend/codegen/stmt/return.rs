@@ -1,9 +1,12 @@
 use glsl::syntax::Expr;
 
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
+use cranelift_codegen::ir::Value;
 
-use crate::error::GlslError;
+use crate::error::{ErrorCode, GlslError};
+use crate::frontend::codegen::constants::DISCARD_ALPHA;
 use crate::frontend::codegen::context::CodegenContext;
+use crate::semantic::types::Type as GlslType;
 
 /// Emit return statement
 pub fn emit_return_stmt<M: cranelift_module::Module>(
@@ -11,154 +14,196 @@ pub fn emit_return_stmt<M: cranelift_module::Module>(
     expr: Option<&Expr>,
 ) -> Result<(), GlslError> {
     use crate::error::extract_span_from_expr;
-    use cranelift_codegen::ir::{ArgumentPurpose, InstBuilder, MemFlags};
+    use cranelift_codegen::ir::InstBuilder;
 
     if let Some(ret_expr) = expr {
         let span = extract_span_from_expr(ret_expr);
         let (ret_vals, ret_ty) = ctx.emit_expr_typed(ret_expr)?;
-        log::trace!(
-            "return statement: ret_ty={:?}, ret_vals.len()={}",
-            ret_ty,
-            ret_vals.len()
-        );
-
-        // Validate return type matches function signature
-        if let Some(expected_ty) = &ctx.return_type {
-            log::trace!("  expected_ty={expected_ty:?}");
-            // Check if function uses StructReturn
-            let uses_struct_return = ctx
+        emit_return_values(ctx, ret_vals, ret_ty, span)
+    } else {
+        // Void return - return empty
+        ctx.builder.ins().return_(&[]);
+        emit_unreachable_block(ctx);
+        Ok(())
+    }
+}
+
+/// Emit `discard` statement
+///
+/// Shaders return their color as a `vec4`, so `discard` returns a sentinel color whose
+/// alpha is [`DISCARD_ALPHA`]. The caller skips writing pixels that carry the sentinel.
+/// Validation only allows `discard` in `main`, so the sentinel never reaches shader code.
+pub fn emit_discard_stmt<M: cranelift_module::Module>(
+    ctx: &mut CodegenContext<'_, M>,
+) -> Result<(), GlslError> {
+    use cranelift_codegen::ir::InstBuilder;
+
+    if ctx.return_type != Some(GlslType::Vec4) {
+        return Err(GlslError::new(
+            ErrorCode::E0400,
+            "`discard` is only supported in functions returning `vec4`",
+        ));
+    }
+
+    let zero = ctx.builder.ins().f32const(0.0);
+    let alpha = ctx.builder.ins().f32const(DISCARD_ALPHA);
+    emit_return_values(
+        ctx,
+        vec![zero, zero, zero, alpha],
+        GlslType::Vec4,
+        glsl::syntax::SourceSpan::unknown(),
+    )
+}
+
+/// Emit a return of already-evaluated values, coercing them to the function's return type
+fn emit_return_values<M: cranelift_module::Module>(
+    ctx: &mut CodegenContext<'_, M>,
+    ret_vals: Vec<Value>,
+    ret_ty: GlslType,
+    span: glsl::syntax::SourceSpan,
+) -> Result<(), GlslError> {
+    use cranelift_codegen::ir::{ArgumentPurpose, InstBuilder, MemFlags};
+
+    log::trace!(
+        "return statement: ret_ty={:?}, ret_vals.len()={}",
+        ret_ty,
+        ret_vals.len()
+    );
+
+    // Validate return type matches function signature
+    if let Some(expected_ty) = &ctx.return_type {
+        log::trace!("  expected_ty={expected_ty:?}");
+        // Check if function uses StructReturn
+        let uses_struct_return = ctx
+            .builder
+            .func
+            .signature
+            .uses_special_param(ArgumentPurpose::StructReturn);
+
+        if uses_struct_return {
+            // Function uses StructReturn - write values to buffer
+            // Use special_param() method (like cranelift-examples) to get the StructReturn pointer
+            let struct_ret_ptr = ctx
                 .builder
                 .func
-                .signature
-                .uses_special_param(ArgumentPurpose::StructReturn);
-
-            if uses_struct_return {
-                // Function uses StructReturn - write values to buffer
-                // Use special_param() method (like cranelift-examples) to get the StructReturn pointer
-                let struct_ret_ptr = ctx
-                    .builder
-                    .func
-                    .special_param(ArgumentPurpose::StructReturn)
-                    .ok_or_else(|| {
-                        GlslError::new(
-                            crate::error::ErrorCode::E0400,
-                            "StructReturn parameter not found (internal error)",
-                        )
-                    })?;
-
-                // Coerce and write values to buffer at offsets (4 bytes per f32)
-                let expected_base = if expected_ty.is_vector() {
-                    expected_ty.vector_base_type().unwrap()
-                } else {
-                    crate::frontend::semantic::types::Type::Float
-                };
-                let ret_base = if ret_ty.is_vector() {
-                    ret_ty.vector_base_type().unwrap()
-                } else if ret_ty.is_matrix() {
-                    crate::frontend::semantic::types::Type::Float
-                } else {
-                    ret_ty.clone()
-                };
+                .special_param(ArgumentPurpose::StructReturn)
+                .ok_or_else(|| {
+                    GlslError::new(
+                        crate::error::ErrorCode::E0400,
+                        "StructReturn parameter not found (internal error)",
+                    )
+                })?;
 
+            // Coerce and write values to buffer at offsets (4 bytes per f32)
+            let expected_base = if expected_ty.is_vector() {
+                expected_ty.vector_base_type().unwrap()
+            } else {
+                crate::frontend::semantic::types::Type::Float
+            };
+            let ret_base = if ret_ty.is_vector() {
+                ret_ty.vector_base_type().unwrap()
+            } else if ret_ty.is_matrix() {
+                crate::frontend::semantic::types::Type::Float
+            } else {
+                ret_ty.clone()
+            };
+
+            log::trace!(
+                "  StructReturn: coercing {} values from {:?} to {:?}",
+                ret_vals.len(),
+                ret_base,
+                expected_base
+            );
+            for (i, val) in ret_vals.iter().enumerate() {
                 log::trace!(
-                    "  StructReturn: coercing {} values from {:?} to {:?}",
-                    ret_vals.len(),
-                    ret_base,
-                    expected_base
+                    "    processing element {i}: val={val:?}, val type should match ret_base={ret_base:?}"
                 );
-                for (i, val) in ret_vals.iter().enumerate() {
+                let coerced = if ret_base == expected_base {
+                    log::trace!("      no coercion needed for element {i}");
+                    *val
+                } else {
                     log::trace!(
-                        "    processing element {i}: val={val:?}, val type should match ret_base={ret_base:?}"
+                        "      coercing element {i}: {ret_base:?} -> {expected_base:?}, val={val:?}"
                     );
-                    let coerced = if ret_base == expected_base {
-                        log::trace!("      no coercion needed for element {i}");
-                        *val
-                    } else {
-                        log::trace!(
-                            "      coercing element {i}: {ret_base:?} -> {expected_base:?}, val={val:?}"
-                        );
-                        ctx.coerce_to_type_with_location(
-                            *val,
-                            &ret_base,
-                            &expected_base,
-                            Some(span.clone()),
-                        )?
-                    };
-                    log::trace!("      coerced value for element {i}: {coerced:?}");
-                    let offset = (i * crate::frontend::codegen::constants::F32_SIZE_BYTES) as i32;
-                    log::trace!("      storing coerced value at offset {offset}");
-                    ctx.builder
-                        .ins()
-                        .store(MemFlags::trusted(), coerced, struct_ret_ptr, offset);
-                }
-
-                // Return void for StructReturn functions
-                ctx.builder.ins().return_(&[]);
-            } else if expected_ty.is_vector() || expected_ty.is_matrix() {
-                // For vectors/matrices without StructReturn (shouldn't happen with this plan)
-                // Keep existing behavior as fallback
-                let expected_base = if expected_ty.is_vector() {
-                    expected_ty.vector_base_type().unwrap()
-                } else {
-                    crate::frontend::semantic::types::Type::Float
-                };
-                let ret_base = if ret_ty.is_vector() {
-                    ret_ty.vector_base_type().unwrap()
-                } else if ret_ty.is_matrix() {
-                    crate::frontend::semantic::types::Type::Float
-                } else {
-                    ret_ty.clone()
+                    ctx.coerce_to_type_with_location(
+                        *val,
+                        &ret_base,
+                        &expected_base,
+                        Some(span.clone()),
+                    )?
                 };
+                log::trace!("      coerced value for element {i}: {coerced:?}");
+                let offset = (i * crate::frontend::codegen::constants::F32_SIZE_BYTES) as i32;
+                log::trace!("      storing coerced value at offset {offset}");
+                ctx.builder
+                    .ins()
+                    .store(MemFlags::trusted(), coerced, struct_ret_ptr, offset);
+            }
 
-                let mut coerced_vals = Vec::new();
-                for val in ret_vals {
-                    let coerced = if ret_base == expected_base {
-                        val
-                    } else {
-                        ctx.coerce_to_type_with_location(
-                            val,
-                            &ret_base,
-                            &expected_base,
-                            Some(span.clone()),
-                        )?
-                    };
-                    coerced_vals.push(coerced);
-                }
-                ctx.builder.ins().return_(&coerced_vals);
+            // Return void for StructReturn functions
+            ctx.builder.ins().return_(&[]);
+        } else if expected_ty.is_vector() || expected_ty.is_matrix() {
+            // For vectors/matrices without StructReturn (shouldn't happen with this plan)
+            // Keep existing behavior as fallback
+            let expected_base = if expected_ty.is_vector() {
+                expected_ty.vector_base_type().unwrap()
             } else {
-                // For scalars, return single value with coercion if needed
-                let expected_base = expected_ty.clone();
-                let ret_base = ret_ty.clone();
-                log::trace!(
-                    "  scalar return: ret_base={ret_base:?}, expected_base={expected_base:?}"
-                );
+                crate::frontend::semantic::types::Type::Float
+            };
+            let ret_base = if ret_ty.is_vector() {
+                ret_ty.vector_base_type().unwrap()
+            } else if ret_ty.is_matrix() {
+                crate::frontend::semantic::types::Type::Float
+            } else {
+                ret_ty.clone()
+            };
 
-                let return_val = if ret_base == expected_base {
-                    log::trace!("  types match, no coercion");
-                    ret_vals[0]
+            let mut coerced_vals = Vec::new();
+            for val in ret_vals {
+                let coerced = if ret_base == expected_base {
+                    val
                 } else {
-                    log::trace!("  coercing return value: {ret_base:?} -> {expected_base:?}");
                     ctx.coerce_to_type_with_location(
-                        ret_vals[0],
+                        val,
                         &ret_base,
                         &expected_base,
                         Some(span.clone()),
                     )?
                 };
-                ctx.builder.ins().return_(&[return_val]);
+                coerced_vals.push(coerced);
             }
+            ctx.builder.ins().return_(&coerced_vals);
         } else {
-            // No return type specified, use first value as-is
-            ctx.builder.ins().return_(&[ret_vals[0]]);
+            // For scalars, return single value with coercion if needed
+            let expected_base = expected_ty.clone();
+            let ret_base = ret_ty.clone();
+            log::trace!("  scalar return: ret_base={ret_base:?}, expected_base={expected_base:?}");
+
+            let return_val = if ret_base == expected_base {
+                log::trace!("  types match, no coercion");
+                ret_vals[0]
+            } else {
+                log::trace!("  coercing return value: {ret_base:?} -> {expected_base:?}");
+                ctx.coerce_to_type_with_location(
+                    ret_vals[0],
+                    &ret_base,
+                    &expected_base,
+                    Some(span.clone()),
+                )?
+            };
+            ctx.builder.ins().return_(&[return_val]);
         }
     } else {
-        // Void return - return empty
-        ctx.builder.ins().return_(&[]);
+        // No return type specified, use first value as-is
+        ctx.builder.ins().return_(&[ret_vals[0]]);
     }
 
-    // Create unreachable block for subsequent code
+    emit_unreachable_block(ctx);
+    Ok(())
+}
+
+/// Create unreachable block for code following a return
+fn emit_unreachable_block<M: cranelift_module::Module>(ctx: &mut CodegenContext<'_, M>) {
     let unreachable = ctx.builder.create_block();
     ctx.emit_block(unreachable);
-
-    Ok(())
 }
@@ -0,0 +1,768 @@
+//! GLSL source formatter.
+//!
+//! Parses a shader and prints it back as canonical GLSL: four-space indentation, one
+//! statement per line, single spaces around binary operators and only the parentheses
+//! that precedence requires. Formatting is idempotent, so formatting the output again
+//! changes nothing.
+//!
+//! The parser drops comments and preprocessor lines, so they are collected from the
+//! source separately and reattached by line number: before the first statement that
+//! starts after them, or at the end of a single-line statement they follow. Blank
+//! lines inside functions are not preserved.
+
+use crate::error::{ErrorCode, GlslError};
+use crate::frontend::ast_span::AstSpan;
+use crate::frontend::pipeline::CompilationPipeline;
+use glsl::syntax::{
+    ArraySpecifier, ArraySpecifierDimension, AssignmentOp, BinaryOp, Condition, Declaration, Expr,
+    ExternalDeclaration, ForInitStatement, FullySpecifiedType, FunIdentifier,
+    FunctionParameterDeclaration, FunctionPrototype, Initializer, IterationStatement,
+    JumpStatement, PrecisionQualifier, SelectionRestStatement, SelectionStatement, SimpleStatement,
+    Statement, StorageQualifier, TranslationUnit, TypeQualifier, TypeQualifierSpec, TypeSpecifier,
+    TypeSpecifierNonArray, UnaryOp,
+};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+const INDENT: &str = "    ";
+
+/// Parse `source` and print it back as canonically formatted GLSL
+///
+/// Fails on parse errors, and on constructs the compiler doesn't support either
+/// (structs, interface blocks, switch statements and layout qualifiers, for example).
+pub fn format_glsl(source: &str) -> Result<String, GlslError> {
+    let shader = CompilationPipeline::parse(source)?.shader;
+    let mut formatter = Formatter {
+        out: String::new(),
+        indent: 0,
+        comments: collect_comments(source),
+        next_comment: 0,
+    };
+    formatter.translation_unit(&shader)?;
+    Ok(formatter.finish())
+}
+
+/// A comment or preprocessor line from the source
+struct Comment {
+    /// Line the comment starts on (1-indexed, like source spans)
+    line: usize,
+    text: String,
+    /// Follows code on the same line
+    trailing: bool,
+    /// Preprocessor line, printed without indentation
+    directive: bool,
+}
+
+/// Find the comments and preprocessor lines in `source`, in order
+fn collect_comments(source: &str) -> Vec<Comment> {
+    let bytes = source.as_bytes();
+    let mut comments = Vec::new();
+    let mut line = 1;
+    let mut code_on_line = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &source[i..];
+        let (len, directive) = if rest.starts_with("//") {
+            (rest.find('\n').unwrap_or(rest.len()), false)
+        } else if rest.starts_with("/*") {
+            (rest.find("*/").map_or(rest.len(), |end| end + 2), false)
+        } else if bytes[i] == b'#' && !code_on_line {
+            (rest.find('\n').unwrap_or(rest.len()), true)
+        } else {
+            if bytes[i] == b'\n' {
+                line += 1;
+                code_on_line = false;
+            } else if !bytes[i].is_ascii_whitespace() {
+                code_on_line = true;
+            }
+            i += 1;
+            continue;
+        };
+
+        let text = &rest[..len];
+        comments.push(Comment {
+            line,
+            text: text.trim_end().to_string(),
+            trailing: code_on_line,
+            directive,
+        });
+        line += text.matches('\n').count();
+        code_on_line = true;
+        i += len;
+    }
+    comments
+}
+
+struct Formatter {
+    out: String,
+    indent: usize,
+    comments: Vec<Comment>,
+    /// Index of the first comment not yet printed
+    next_comment: usize,
+}
+
+impl Formatter {
+    fn finish(mut self) -> String {
+        self.indent = 0;
+        self.comments_before(Some(usize::MAX));
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        self.out
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    /// Print the comments that come before something starting on `line`
+    fn comments_before(&mut self, line: Option<usize>) {
+        let Some(line) = line else {
+            return;
+        };
+        while let Some(comment) = self.comments.get(self.next_comment)
+            && (comment.line < line || (comment.line == line && !comment.trailing))
+        {
+            let text = comment.text.clone();
+            if comment.directive {
+                self.out.push_str(&text);
+                self.out.push('\n');
+            } else {
+                self.line(&text);
+            }
+            self.next_comment += 1;
+        }
+    }
+
+    /// Print a single-line statement starting on `line`, with any comments after it
+    fn statement_line(&mut self, mut text: String, line: Option<usize>) {
+        while let Some(comment) = self.comments.get(self.next_comment)
+            && Some(comment.line) == line
+            && comment.trailing
+        {
+            text.push(' ');
+            text.push_str(&comment.text);
+            self.next_comment += 1;
+        }
+        self.line(&text);
+    }
+
+    fn translation_unit(&mut self, shader: &TranslationUnit) -> Result<(), GlslError> {
+        // Blank lines go around functions; consecutive declarations stay together
+        let mut previous_was_function = None;
+        for decl in &shader.0 {
+            let is_function = match decl {
+                ExternalDeclaration::FunctionDefinition(_) => true,
+                ExternalDeclaration::Declaration(_) => false,
+                // Preprocessor lines are printed with the comments
+                _ => continue,
+            };
+            if previous_was_function.is_some_and(|previous| previous || is_function) {
+                self.out.push('\n');
+            }
+            previous_was_function = Some(is_function);
+
+            match decl {
+                ExternalDeclaration::FunctionDefinition(func) => {
+                    self.comments_before(func.prototype.start_line());
+                    let head = prototype(&func.prototype)?;
+                    self.block(&head, &func.statement.statement_list)?;
+                    self.line("}");
+                }
+                ExternalDeclaration::Declaration(decl) => {
+                    let line = decl.start_line();
+                    self.comments_before(line);
+                    let text = declaration(decl)?;
+                    self.statement_line(text, line);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Print `head {` and the indented statements, leaving the closing brace to the caller
+    fn block(&mut self, head: &str, stmts: &[Statement]) -> Result<(), GlslError> {
+        if head.is_empty() {
+            self.line("{");
+        } else {
+            self.line(&format!("{head} {{"));
+        }
+        self.indent += 1;
+        for stmt in stmts {
+            self.statement(stmt)?;
+        }
+        self.indent -= 1;
+        Ok(())
+    }
+
+    /// Print `head` and the body of an `if`, `else` or loop, returning true if the body
+    /// is a block whose closing brace is still to be printed
+    fn body(&mut self, head: &str, body: &Statement) -> Result<bool, GlslError> {
+        if let Statement::Compound(compound) = body {
+            self.block(head, &compound.statement_list)?;
+            return Ok(true);
+        }
+        self.line(head);
+        self.indent += 1;
+        self.statement(body)?;
+        self.indent -= 1;
+        Ok(false)
+    }
+
+    fn statement(&mut self, stmt: &Statement) -> Result<(), GlslError> {
+        let line = stmt.start_line();
+        self.comments_before(line);
+        match stmt {
+            Statement::Compound(compound) => {
+                self.block("", &compound.statement_list)?;
+                self.line("}");
+                Ok(())
+            }
+            Statement::Simple(simple) => self.simple_statement(simple, line),
+        }
+    }
+
+    fn simple_statement(
+        &mut self,
+        stmt: &SimpleStatement,
+        line: Option<usize>,
+    ) -> Result<(), GlslError> {
+        match stmt {
+            SimpleStatement::Declaration(decl) => {
+                let text = declaration(decl)?;
+                self.statement_line(text, line);
+            }
+            SimpleStatement::Expression(Some(expr)) => {
+                let text = format!("{};", expr_text(expr)?);
+                self.statement_line(text, line);
+            }
+            SimpleStatement::Expression(None) => self.statement_line(";".to_string(), line),
+            SimpleStatement::Selection(selection) => self.selection("", selection)?,
+            SimpleStatement::Iteration(iteration) => self.iteration(iteration)?,
+            SimpleStatement::Jump(jump) => {
+                let text = match jump {
+                    JumpStatement::Continue => "continue;".to_string(),
+                    JumpStatement::Break => "break;".to_string(),
+                    JumpStatement::Discard => "discard;".to_string(),
+                    JumpStatement::Return(None) => "return;".to_string(),
+                    JumpStatement::Return(Some(expr)) => format!("return {};", expr_text(expr)?),
+                };
+                self.statement_line(text, line);
+            }
+            _ => return Err(unsupported("switch statements")),
+        }
+        Ok(())
+    }
+
+    /// Print an `if` statement, with `prefix` before the `if` (for `else if` chains)
+    fn selection(&mut self, prefix: &str, selection: &SelectionStatement) -> Result<(), GlslError> {
+        let head = format!("{prefix}if ({})", expr_text(&selection.cond)?);
+        match &selection.rest {
+            SelectionRestStatement::Statement(then_stmt) => {
+                if self.body(&head, then_stmt)? {
+                    self.line("}");
+                }
+            }
+            SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                let braced = self.body(&head, then_stmt)?;
+                let else_head = if braced { "} else" } else { "else" };
+                self.else_branch(else_head, else_stmt)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Print `head` and an `else` branch, keeping `else if` chains flat
+    fn else_branch(&mut self, head: &str, stmt: &Statement) -> Result<(), GlslError> {
+        if let Statement::Simple(simple) = stmt
+            && self.else_if(head, simple)?
+        {
+            return Ok(());
+        }
+        if self.body(head, stmt)? {
+            self.line("}");
+        }
+        Ok(())
+    }
+
+    /// Print `stmt` as the `if` of an `else if`, returning false if it isn't one
+    fn else_if(&mut self, head: &str, stmt: &SimpleStatement) -> Result<bool, GlslError> {
+        let SimpleStatement::Selection(selection) = stmt else {
+            return Ok(false);
+        };
+        self.selection(&format!("{head} "), selection)?;
+        Ok(true)
+    }
+
+    fn iteration(&mut self, iteration: &IterationStatement) -> Result<(), GlslError> {
+        match iteration {
+            IterationStatement::While(condition, body) => {
+                let head = format!("while ({})", condition_text(condition)?);
+                if self.body(&head, body)? {
+                    self.line("}");
+                }
+            }
+            IterationStatement::DoWhile(body, cond) => {
+                let cond = expr_text(cond)?;
+                if self.body("do", body)? {
+                    self.line(&format!("}} while ({cond});"));
+                } else {
+                    self.line(&format!("while ({cond});"));
+                }
+            }
+            IterationStatement::For(init, rest, body) => {
+                let mut head = String::from("for (");
+                match init {
+                    ForInitStatement::Declaration(decl) => head.push_str(&declaration(decl)?),
+                    ForInitStatement::Expression(Some(expr)) => {
+                        head.push_str(&format!("{};", expr_text(expr)?));
+                    }
+                    ForInitStatement::Expression(None) => head.push(';'),
+                }
+                if let Some(condition) = &rest.condition {
+                    head.push(' ');
+                    head.push_str(&condition_text(condition)?);
+                }
+                head.push(';');
+                if let Some(post_expr) = &rest.post_expr {
+                    head.push(' ');
+                    head.push_str(&expr_text(post_expr)?);
+                }
+                head.push(')');
+                if self.body(&head, body)? {
+                    self.line("}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn unsupported(what: &str) -> GlslError {
+    GlslError::new(ErrorCode::E0400, format!("cannot format {what}"))
+}
+
+// Declarations and types
+
+fn declaration(decl: &Declaration) -> Result<String, GlslError> {
+    let text = match decl {
+        Declaration::InitDeclaratorList(list) => {
+            let mut text = fully_specified_type(&list.head.ty)?;
+            if let Some(name) = &list.head.name {
+                text.push(' ');
+                text.push_str(&name.name);
+            }
+            text.push_str(&optional_array_spec(list.head.array_specifier.as_ref())?);
+            if let Some(init) = &list.head.initializer {
+                text.push_str(&format!(" = {}", initializer(init)?));
+            }
+            for declarator in &list.tail {
+                text.push_str(&format!(
+                    ", {}{}",
+                    declarator.ident.ident.name,
+                    optional_array_spec(declarator.ident.array_spec.as_ref())?
+                ));
+                if let Some(init) = &declarator.initializer {
+                    text.push_str(&format!(" = {}", initializer(init)?));
+                }
+            }
+            text
+        }
+        Declaration::FunctionPrototype(proto) => prototype(proto)?,
+        Declaration::Precision(precision, ty) => {
+            format!(
+                "precision {} {}",
+                precision_qualifier(precision),
+                type_specifier(ty)?
+            )
+        }
+        Declaration::Global(qualifier, idents) => {
+            let names: Vec<&str> = idents.iter().map(|ident| ident.name.as_str()).collect();
+            format!("{} {}", type_qualifier(qualifier)?, names.join(", "))
+        }
+        Declaration::Block(_) => return Err(unsupported("interface blocks")),
+    };
+    Ok(text + ";")
+}
+
+fn prototype(proto: &FunctionPrototype) -> Result<String, GlslError> {
+    let mut params = Vec::new();
+    for param in &proto.parameters {
+        params.push(match param {
+            FunctionParameterDeclaration::Named(qualifier, decl) => format!(
+                "{}{} {}{}",
+                qualifier_prefix(qualifier.as_ref())?,
+                type_specifier(&decl.ty)?,
+                decl.ident.ident.name,
+                optional_array_spec(decl.ident.array_spec.as_ref())?
+            ),
+            FunctionParameterDeclaration::Unnamed(qualifier, ty) => format!(
+                "{}{}",
+                qualifier_prefix(qualifier.as_ref())?,
+                type_specifier(ty)?
+            ),
+        });
+    }
+    Ok(format!(
+        "{} {}({})",
+        fully_specified_type(&proto.ty)?,
+        proto.name.name,
+        params.join(", ")
+    ))
+}
+
+fn fully_specified_type(ty: &FullySpecifiedType) -> Result<String, GlslError> {
+    Ok(format!(
+        "{}{}",
+        qualifier_prefix(ty.qualifier.as_ref())?,
+        type_specifier(&ty.ty)?
+    ))
+}
+
+/// The qualifiers followed by a space, or nothing
+fn qualifier_prefix(qualifier: Option<&TypeQualifier>) -> Result<String, GlslError> {
+    match qualifier {
+        Some(qualifier) => Ok(type_qualifier(qualifier)? + " "),
+        None => Ok(String::new()),
+    }
+}
+
+fn type_qualifier(qualifier: &TypeQualifier) -> Result<String, GlslError> {
+    let mut words = Vec::new();
+    for spec in &qualifier.qualifiers.0 {
+        words.push(match spec {
+            TypeQualifierSpec::Storage(storage) => match storage {
+                StorageQualifier::Const => "const",
+                StorageQualifier::In => "in",
+                StorageQualifier::Out => "out",
+                StorageQualifier::InOut => "inout",
+                StorageQualifier::Uniform => "uniform",
+                _ => return Err(unsupported(&format!("storage qualifier `{storage:?}`"))),
+            },
+            TypeQualifierSpec::Precision(precision) => precision_qualifier(precision),
+            _ => return Err(unsupported(&format!("qualifier `{spec:?}`"))),
+        });
+    }
+    Ok(words.join(" "))
+}
+
+fn precision_qualifier(precision: &PrecisionQualifier) -> &'static str {
+    match precision {
+        PrecisionQualifier::High => "highp",
+        PrecisionQualifier::Medium => "mediump",
+        PrecisionQualifier::Low => "lowp",
+    }
+}
+
+fn type_specifier(ty: &TypeSpecifier) -> Result<String, GlslError> {
+    let name = match &ty.ty {
+        TypeSpecifierNonArray::Void => "void",
+        TypeSpecifierNonArray::Bool => "bool",
+        TypeSpecifierNonArray::Int => "int",
+        TypeSpecifierNonArray::UInt => "uint",
+        TypeSpecifierNonArray::Float => "float",
+        TypeSpecifierNonArray::Vec2 => "vec2",
+        TypeSpecifierNonArray::Vec3 => "vec3",
+        TypeSpecifierNonArray::Vec4 => "vec4",
+        TypeSpecifierNonArray::IVec2 => "ivec2",
+        TypeSpecifierNonArray::IVec3 => "ivec3",
+        TypeSpecifierNonArray::IVec4 => "ivec4",
+        TypeSpecifierNonArray::UVec2 => "uvec2",
+        TypeSpecifierNonArray::UVec3 => "uvec3",
+        TypeSpecifierNonArray::UVec4 => "uvec4",
+        TypeSpecifierNonArray::BVec2 => "bvec2",
+        TypeSpecifierNonArray::BVec3 => "bvec3",
+        TypeSpecifierNonArray::BVec4 => "bvec4",
+        TypeSpecifierNonArray::Mat2 => "mat2",
+        TypeSpecifierNonArray::Mat3 => "mat3",
+        TypeSpecifierNonArray::Mat4 => "mat4",
+        other => return Err(unsupported(&format!("type `{other:?}`"))),
+    };
+    Ok(format!(
+        "{name}{}",
+        optional_array_spec(ty.array_specifier.as_ref())?
+    ))
+}
+
+fn optional_array_spec(spec: Option<&ArraySpecifier>) -> Result<String, GlslError> {
+    spec.map_or(Ok(String::new()), array_spec)
+}
+
+fn array_spec(spec: &ArraySpecifier) -> Result<String, GlslError> {
+    let mut text = String::new();
+    for dimension in &spec.dimensions.0 {
+        match dimension {
+            ArraySpecifierDimension::ExplicitlySized(size) => {
+                text.push_str(&format!("[{}]", expr_text(size)?));
+            }
+            ArraySpecifierDimension::Unsized => text.push_str("[]"),
+        }
+    }
+    Ok(text)
+}
+
+fn initializer(init: &Initializer) -> Result<String, GlslError> {
+    match init {
+        Initializer::Simple(expr) => operand(expr, ASSIGNMENT),
+        Initializer::List(list) => {
+            let items = list
+                .0
+                .iter()
+                .map(initializer)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(format!("{{{}}}", items.join(", ")))
+        }
+    }
+}
+
+fn condition_text(condition: &Condition) -> Result<String, GlslError> {
+    match condition {
+        Condition::Expr(expr) => expr_text(expr),
+        Condition::Assignment(ty, ident, init) => Ok(format!(
+            "{} {} = {}",
+            fully_specified_type(ty)?,
+            ident.name,
+            initializer(init)?
+        )),
+    }
+}
+
+// Expressions
+//
+// Precedence levels follow the GLSL spec, from tightest (1) to loosest (17). An operand
+// is parenthesized when it binds more loosely than its position allows.
+
+const POSTFIX: u8 = 2;
+const UNARY: u8 = 3;
+const TERNARY: u8 = 15;
+const ASSIGNMENT: u8 = 16;
+const COMMA: u8 = 17;
+
+fn binary_precedence(op: &BinaryOp) -> u8 {
+    match op {
+        BinaryOp::Mult | BinaryOp::Div | BinaryOp::Mod => 4,
+        BinaryOp::Add | BinaryOp::Sub => 5,
+        BinaryOp::LShift | BinaryOp::RShift => 6,
+        BinaryOp::LT | BinaryOp::GT | BinaryOp::LTE | BinaryOp::GTE => 7,
+        BinaryOp::Equal | BinaryOp::NonEqual => 8,
+        BinaryOp::BitAnd => 9,
+        BinaryOp::BitXor => 10,
+        BinaryOp::BitOr => 11,
+        BinaryOp::And => 12,
+        BinaryOp::Xor => 13,
+        BinaryOp::Or => 14,
+    }
+}
+
+fn precedence(expr: &Expr) -> u8 {
+    match expr {
+        Expr::IntConst(v, _) if *v < 0 => UNARY,
+        Expr::FloatConst(v, _) if v.is_sign_negative() => UNARY,
+        Expr::DoubleConst(v, _) if v.is_sign_negative() => UNARY,
+        Expr::Variable(..)
+        | Expr::IntConst(..)
+        | Expr::UIntConst(..)
+        | Expr::FloatConst(..)
+        | Expr::DoubleConst(..)
+        | Expr::BoolConst(..) => 1,
+        Expr::Dot(..)
+        | Expr::PostInc(..)
+        | Expr::PostDec(..)
+        | Expr::Bracket(..)
+        | Expr::FunCall(..) => POSTFIX,
+        Expr::Unary(..) => UNARY,
+        Expr::Binary(op, ..) => binary_precedence(op),
+        Expr::Ternary(..) => TERNARY,
+        Expr::Assignment(..) => ASSIGNMENT,
+        Expr::Comma(..) => COMMA,
+    }
+}
+
+/// Print `expr` where anything binding more loosely than `max` needs parentheses
+fn operand(expr: &Expr, max: u8) -> Result<String, GlslError> {
+    let text = expr_text(expr)?;
+    if precedence(expr) > max {
+        Ok(format!("({text})"))
+    } else {
+        Ok(text)
+    }
+}
+
+fn expr_text(expr: &Expr) -> Result<String, GlslError> {
+    let text = match expr {
+        Expr::Variable(ident, _) => ident.name.clone(),
+        Expr::IntConst(v, _) => v.to_string(),
+        Expr::UIntConst(v, _) => format!("{v}u"),
+        // Debug output always has a decimal point or exponent, and round-trips exactly
+        Expr::FloatConst(v, _) => format!("{v:?}"),
+        Expr::DoubleConst(v, _) => format!("{v:?}lf"),
+        Expr::BoolConst(v, _) => v.to_string(),
+        Expr::Unary(op, operand_expr, _) => {
+            let op = match op {
+                UnaryOp::Inc => "++",
+                UnaryOp::Dec => "--",
+                UnaryOp::Add => "+",
+                UnaryOp::Minus => "-",
+                UnaryOp::Not => "!",
+                UnaryOp::Complement => "~",
+            };
+            let mut text = operand(operand_expr, UNARY)?;
+            // `-(-x)` must not become `--x`
+            if text.starts_with(&op[..1]) && matches!(op, "+" | "-" | "++" | "--") {
+                text = format!("({text})");
+            }
+            format!("{op}{text}")
+        }
+        Expr::Binary(op, lhs, rhs, _) => {
+            let precedence = binary_precedence(op);
+            let op = match op {
+                BinaryOp::Or => "||",
+                BinaryOp::Xor => "^^",
+                BinaryOp::And => "&&",
+                BinaryOp::BitOr => "|",
+                BinaryOp::BitXor => "^",
+                BinaryOp::BitAnd => "&",
+                BinaryOp::Equal => "==",
+                BinaryOp::NonEqual => "!=",
+                BinaryOp::LT => "<",
+                BinaryOp::GT => ">",
+                BinaryOp::LTE => "<=",
+                BinaryOp::GTE => ">=",
+                BinaryOp::LShift => "<<",
+                BinaryOp::RShift => ">>",
+                BinaryOp::Add => "+",
+                BinaryOp::Sub => "-",
+                BinaryOp::Mult => "*",
+                BinaryOp::Div => "/",
+                BinaryOp::Mod => "%",
+            };
+            // Binary operators are left-associative, so the right side must bind tighter
+            format!(
+                "{} {op} {}",
+                operand(lhs, precedence)?,
+                operand(rhs, precedence - 1)?
+            )
+        }
+        Expr::Ternary(cond, then_expr, else_expr, _) => format!(
+            "{} ? {} : {}",
+            operand(cond, TERNARY - 1)?,
+            operand(then_expr, ASSIGNMENT)?,
+            operand(else_expr, ASSIGNMENT)?
+        ),
+        Expr::Assignment(lhs, op, rhs, _) => {
+            let op = match op {
+                AssignmentOp::Equal => "=",
+                AssignmentOp::Mult => "*=",
+                AssignmentOp::Div => "/=",
+                AssignmentOp::Mod => "%=",
+                AssignmentOp::Add => "+=",
+                AssignmentOp::Sub => "-=",
+                AssignmentOp::LShift => "<<=",
+                AssignmentOp::RShift => ">>=",
+                AssignmentOp::And => "&=",
+                AssignmentOp::Xor => "^=",
+                AssignmentOp::Or => "|=",
+            };
+            format!(
+                "{} {op} {}",
+                operand(lhs, UNARY)?,
+                operand(rhs, ASSIGNMENT)?
+            )
+        }
+        Expr::Dot(base, field, _) => format!("{}.{}", operand(base, POSTFIX)?, field.name),
+        Expr::PostInc(base, _) => format!("{}++", operand(base, POSTFIX)?),
+        Expr::PostDec(base, _) => format!("{}--", operand(base, POSTFIX)?),
+        Expr::Bracket(base, spec, _) => {
+            format!("{}{}", operand(base, POSTFIX)?, array_spec(spec)?)
+        }
+        Expr::FunCall(fun, args, _) => {
+            let FunIdentifier::Identifier(ident) = fun else {
+                return Err(unsupported("complex function identifiers"));
+            };
+            let args = args
+                .iter()
+                .map(|arg| operand(arg, ASSIGNMENT))
+                .collect::<Result<Vec<_>, _>>()?;
+            format!("{}({})", ident.name, args.join(", "))
+        }
+        Expr::Comma(lhs, rhs, _) => {
+            format!("{}, {}", operand(lhs, COMMA)?, operand(rhs, ASSIGNMENT)?)
+        }
+    };
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> String {
+        format_glsl(source).unwrap_or_else(|e| panic!("formatting failed: {e}"))
+    }
+
+    #[test]
+    fn test_format_messy_input() {
+        let messy = "// Brightness ramp\nuniform   float u_gain ;\nfloat ramp( float x,float k ){\nfloat y=x*k+(1.0-k) ; // blend\nif(y>1.0){y=1.0;}else if (y<0.0) y = 0.0;\n  else { y=y*y ; }\n\n\n   for(int i=0;i<3;i++){ y+=0.1*float(i); }\nreturn (y);}\nvec4 main(vec2 fragCoord,vec2 outputSize,float time){return vec4(ramp(fragCoord.x/outputSize.x,u_gain));}";
+        let expected = "\
+// Brightness ramp
+uniform float u_gain;
+
+float ramp(float x, float k) {
+    float y = x * k + (1.0 - k); // blend
+    if (y > 1.0) {
+        y = 1.0;
+    } else if (y < 0.0)
+        y = 0.0;
+    else {
+        y = y * y;
+    }
+    for (int i = 0; i < 3; i++) {
+        y += 0.1 * float(i);
+    }
+    return y;
+}
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    return vec4(ramp(fragCoord.x / outputSize.x, u_gain));
+}
+";
+        let formatted = format(messy);
+        assert_eq!(formatted, expected);
+        assert_eq!(
+            format(&formatted),
+            formatted,
+            "formatting is not idempotent"
+        );
+    }
+
+    #[test]
+    fn test_format_keeps_needed_parentheses() {
+        let formatted = format(
+            "float f(float a, float b, float c) { return (a - (b - c)) * -(-a) / (a * b) + (a > b ? a : b); }",
+        );
+        assert!(
+            formatted.contains("return (a - (b - c)) * -(-a) / (a * b) + (a > b ? a : b);"),
+            "unexpected output:\n{formatted}"
+        );
+    }
+
+    #[test]
+    fn test_format_comments_and_loops() {
+        let source = "void f() {\n  int n = 0; /* counter */\n  // spin\n  while (n < 4) n++;\n  do { n--; } while (n > 0);\n}\n// trailing\n";
+        let formatted = format(source);
+        assert_eq!(
+            formatted,
+            "void f() {\n    int n = 0; /* counter */\n    // spin\n    while (n < 4)\n        n++;\n    do {\n        n--;\n    } while (n > 0);\n}\n// trailing\n"
+        );
+        assert_eq!(format(&formatted), formatted);
+    }
+}
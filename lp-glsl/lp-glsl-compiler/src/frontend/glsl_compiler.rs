@@ -1,9 +1,15 @@
 //! GLSL compiler that compiles GLSL source to GlModule
 
+#[cfg(feature = "emulator")]
+use crate::backend::alloc_tracker::{CompileStats, HeapSpan};
 use crate::backend::module::gl_module::GlModule;
+#[cfg(feature = "emulator")]
+use crate::backend::target::OptLevel;
 use crate::backend::target::Target;
 use crate::error::GlslError;
 use crate::frontend::pipeline::CompilationPipeline;
+use crate::frontend::semantic::TypedShader;
+use crate::frontend::semantic::complexity::ComplexityBudget;
 use crate::frontend::src_loc::GlSourceMap;
 use cranelift_codegen::ir::Function;
 use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext};
@@ -22,15 +28,52 @@ use alloc::format;
 pub struct GlslCompiler {
     #[allow(dead_code, reason = "Builder context stored for future use")]
     builder_context: FunctionBuilderContext,
+    /// Checked after semantic analysis, before codegen
+    complexity_budget: Option<ComplexityBudget>,
+    /// Initial loop fuel for JIT modules (loops are unchecked if None)
+    loop_fuel: Option<u32>,
+    /// Fail compilation on warnings
+    warnings_as_errors: bool,
 }
 
 impl GlslCompiler {
     pub fn new() -> Self {
         Self {
             builder_context: FunctionBuilderContext::new(),
+            complexity_budget: None,
+            loop_fuel: None,
+            warnings_as_errors: false,
         }
     }
 
+    /// Reject shaders that exceed `budget` (no limit if None) before attempting codegen
+    pub fn with_complexity_budget(mut self, budget: Option<ComplexityBudget>) -> Self {
+        self.complexity_budget = budget;
+        self
+    }
+
+    /// Check a fuel counter on every loop back-edge of JIT-compiled code, starting with
+    /// `fuel` iterations (loops are unchecked if None)
+    pub fn with_loop_fuel(mut self, fuel: Option<u32>) -> Self {
+        self.loop_fuel = fuel;
+        self
+    }
+
+    /// Treat warnings from semantic analysis as errors (strict mode)
+    pub fn with_warnings_as_errors(mut self, warnings_as_errors: bool) -> Self {
+        self.warnings_as_errors = warnings_as_errors;
+        self
+    }
+
+    /// Fail if the analyzed shader is over the complexity budget, or has warnings in
+    /// strict mode
+    fn check_analysis(&self, typed_ast: &TypedShader) -> Result<(), GlslError> {
+        if let Some(budget) = &self.complexity_budget {
+            budget.check(typed_ast)?;
+        }
+        typed_ast.check_warnings(self.warnings_as_errors)
+    }
+
     /// Compile GLSL source to a GlModule<JITModule>
     /// All functions are compiled with float types initially (no fixed-point conversion)
     pub fn compile_to_gl_module_jit(
@@ -44,6 +87,7 @@ impl GlslCompiler {
         // 1. Parse and analyze GLSL
         let semantic_result = CompilationPipeline::parse_and_analyze(source)?;
         let typed_ast = semantic_result.typed_ast;
+        self.check_analysis(&typed_ast)?;
 
         // 2. Create ISA for signature building (before creating gl_module to avoid borrow conflicts)
         let mut target_for_isa = target.clone();
@@ -54,6 +98,13 @@ impl GlslCompiler {
         // 3. Create GlModule
         let mut gl_module = GlModule::new_jit(target)?;
 
+        // 3b. Allocate the uniform block before codegen embeds its address
+        gl_module.uniforms =
+            crate::backend::module::uniforms::UniformBlock::new(&typed_ast.uniforms);
+        gl_module.fuel = self
+            .loop_fuel
+            .map(crate::backend::module::fuel::LoopFuel::new);
+
         // 4. Create a shared source location manager for all functions
         use crate::frontend::src_loc_manager::SourceLocManager;
         let mut source_loc_manager = SourceLocManager::new();
@@ -129,6 +180,7 @@ impl GlslCompiler {
             gl_module.add_function(&name, Linkage::Local, sig, func)?;
             gl_module.glsl_signatures.insert(name, glsl_sig);
         }
+        gl_module.purity = typed_ast.purity.clone();
 
         // 8. Compile main function to CLIF with FLOAT types (if present)
         if let Some(ref main_function) = typed_ast.main_function {
@@ -190,6 +242,7 @@ impl GlslCompiler {
         // 1. Parse and analyze GLSL
         let semantic_result = CompilationPipeline::parse_and_analyze(source)?;
         let typed_ast = semantic_result.typed_ast;
+        self.check_analysis(&typed_ast)?;
 
         // 2. Create ISA for signature building (before creating gl_module to avoid borrow conflicts)
         let mut target_for_isa = target.clone();
@@ -200,6 +253,17 @@ impl GlslCompiler {
         // 3. Create GlModule
         let mut gl_module = GlModule::new_object(target)?;
 
+        // 3b. Lay out the declared uniforms in the guest RAM the emulator reserves for them
+        // (implicit uniforms are left out of the module)
+        let declared: Vec<_> = typed_ast
+            .uniforms
+            .iter()
+            .filter(|u| !u.implicit)
+            .cloned()
+            .collect();
+        gl_module.uniforms =
+            crate::backend::module::uniforms::UniformBlock::new_emulated(&declared)?;
+
         // 4. Create a shared source location manager for all functions
         use crate::frontend::src_loc_manager::SourceLocManager;
         let mut source_loc_manager = SourceLocManager::new();
@@ -275,6 +339,7 @@ impl GlslCompiler {
             gl_module.add_function(&name, Linkage::Local, sig, func)?;
             gl_module.glsl_signatures.insert(name, glsl_sig);
         }
+        gl_module.purity = typed_ast.purity.clone();
 
         // 8. Compile main function to CLIF with FLOAT types (if present)
         if let Some(ref main_function) = typed_ast.main_function {
@@ -322,6 +387,103 @@ impl GlslCompiler {
         Ok(gl_module)
     }
 
+    /// Compile GLSL source to RISC-V 32-bit machine code with default options
+    ///
+    /// See [`GlslCompiler::compile_to_code_with_opts`].
+    #[cfg(feature = "emulator")]
+    pub fn compile_to_code(&mut self, source: &str) -> Result<Vec<u8>, GlslError> {
+        self.compile_to_code_with_opts(source, OptLevel::default())
+    }
+
+    /// Compile GLSL source to RISC-V 32-bit machine code at the given optimization level
+    ///
+    /// Applies the Q32 transform and returns the emitted `.text` section. Calls to
+    /// builtins are left as unresolved relocations, so the bytes are meant for
+    /// inspection (size, disassembly), not direct execution.
+    #[cfg(feature = "emulator")]
+    pub fn compile_to_code_with_opts(
+        &mut self,
+        source: &str,
+        opt_level: OptLevel,
+    ) -> Result<Vec<u8>, GlslError> {
+        let target = Target::riscv32_emulator()?.with_opt_level(opt_level)?;
+        self.compile_to_code_for_target(source, target)
+    }
+
+    /// Compile GLSL source to RISC-V 32-bit machine code and its disassembly
+    ///
+    /// `target` must be a RISC-V emulator target (see [`Target::riscv32_emulator`]).
+    /// The disassembly lists one instruction per line with its offset into the code
+    /// (see [`lp_riscv_inst::disassemble`]).
+    #[cfg(feature = "emulator")]
+    pub fn compile_with_disasm(
+        &mut self,
+        source: &str,
+        target: Target,
+    ) -> Result<(Vec<u8>, String), GlslError> {
+        let code = self.compile_to_code_for_target(source, target)?;
+        let disasm = lp_riscv_inst::disassemble(&code, 0, &[]);
+        Ok((code, disasm))
+    }
+
+    /// Compile, apply the Q32 transform, and emit the `.text` section for `target`
+    #[cfg(feature = "emulator")]
+    fn compile_to_code_for_target(
+        &mut self,
+        source: &str,
+        target: Target,
+    ) -> Result<Vec<u8>, GlslError> {
+        let module = self.compile_to_q32_module_object(source, target)?;
+        crate::backend::codegen::emu::emit_text_section(module)
+    }
+
+    /// Compile and apply the Q32 transform, leaving the module ready for codegen
+    #[cfg(feature = "emulator")]
+    fn compile_to_q32_module_object(
+        &mut self,
+        source: &str,
+        target: Target,
+    ) -> Result<GlModule<ObjectModule>, GlslError> {
+        use crate::backend::transform::q32::{FixedPointFormat, Q32Transform};
+
+        let module = self.compile_to_gl_module_object(source, target)?;
+        let mut module = module.apply_transform(Q32Transform::new(FixedPointFormat::Fixed16x16))?;
+        module.schedule_functions();
+        Ok(module)
+    }
+
+    /// Like [`GlslCompiler::compile_to_code_with_opts`], also reporting [`CompileStats`]
+    ///
+    /// Peak heap usage is only measured when [`TrackingAllocator`] is installed as the
+    /// global allocator; otherwise it is reported as zero. Spills are counted from a
+    /// second compile with VCode disassembly enabled, so the extra text doesn't
+    /// inflate the measured peak.
+    ///
+    /// [`TrackingAllocator`]: crate::backend::alloc_tracker::TrackingAllocator
+    #[cfg(feature = "emulator")]
+    pub fn compile_to_code_with_stats(
+        &mut self,
+        source: &str,
+        opt_level: OptLevel,
+    ) -> Result<(Vec<u8>, CompileStats), GlslError> {
+        let span = HeapSpan::start();
+        let code = self.compile_to_code_with_opts(source, opt_level)?;
+        let peak_heap_bytes = span.peak();
+
+        let target = Target::riscv32_emulator()?.with_opt_level(opt_level)?;
+        let module = self.compile_to_q32_module_object(source, target)?;
+        let (_, spill_counts) =
+            crate::backend::codegen::emu::emit_text_section_with_spills(module)?;
+
+        let stats = CompileStats {
+            peak_heap_bytes,
+            code_size: code.len(),
+            spills: spill_counts.spills,
+            reloads: spill_counts.reloads,
+        };
+        Ok((code, stats))
+    }
+
     fn compile_function_to_clif<M: Module>(
         &mut self,
         func: &crate::frontend::semantic::TypedFunction,
@@ -371,6 +533,9 @@ impl GlslCompiler {
         // Copy the shared SourceLocManager into the context
         codegen_ctx.source_loc_manager = source_loc_manager.clone();
 
+        // Load uniforms first so parameters can shadow them
+        codegen_ctx.declare_uniforms()?;
+
         // Declare parameters as variables in the function
         let block_params = codegen_ctx.builder.block_params(entry_block).to_vec();
 
@@ -601,6 +766,9 @@ impl GlslCompiler {
         // Replace the default SourceLocManager with the shared one
         codegen_ctx.source_loc_manager = source_loc_manager.clone();
 
+        // Load uniforms first so parameters can shadow them
+        codegen_ctx.declare_uniforms()?;
+
         // Declare parameters as variables in the function (same as compile_function_to_clif)
         let block_params = codegen_ctx.builder.block_params(entry_block).to_vec();
 
@@ -6,7 +6,9 @@
 pub(crate) mod glsl_compiler;
 pub(crate) mod pipeline;
 // Public modules
+pub mod ast_span;
 pub mod codegen;
+pub mod formatter;
 pub mod semantic;
 pub mod src_loc;
 pub mod src_loc_manager;
@@ -28,8 +30,9 @@ pub use pipeline::{
 use crate::backend::codegen::emu::EmulatorOptions;
 use crate::backend::module::gl_module::GlModule;
 use crate::backend::target::Target;
-use crate::error::{ErrorCode, GlslError};
+use crate::error::{Diagnostic, ErrorCode, GlslError};
 use crate::exec::executable::{GlslExecutable, GlslOptions, RunMode};
+use crate::exec::interp::GlslInterpreterModule;
 #[cfg(not(feature = "std"))]
 use cranelift_codegen::settings::{self, Configurable};
 use cranelift_jit::JITModule;
@@ -39,6 +42,7 @@ use cranelift_object::ObjectModule;
 use alloc::boxed::Box;
 #[cfg(feature = "emulator")]
 use alloc::string::String;
+use alloc::vec::Vec;
 
 /// Compile GLSL to GlModule<JITModule> (internal, reusable)
 /// This is the core compilation step for JIT execution
@@ -102,6 +106,7 @@ pub fn compile_glsl_to_gl_module_jit(
                 arch: None,
                 flags,
                 isa: None,
+                schedule: false,
             }
         }
         RunMode::Emulator { .. } => {
@@ -112,8 +117,13 @@ pub fn compile_glsl_to_gl_module_jit(
         }
     };
 
+    let target = target.with_opt_level(options.opt_level)?;
+
     // Compile to GlModule (works in both std and no_std)
-    let mut compiler = GlslCompiler::new();
+    let mut compiler = GlslCompiler::new()
+        .with_complexity_budget(options.complexity_budget)
+        .with_loop_fuel(options.loop_fuel)
+        .with_warnings_as_errors(options.warnings_as_errors);
     let mut module = compiler.compile_to_gl_module_jit(source, target)?;
 
     // Apply transformations
@@ -122,6 +132,7 @@ pub fn compile_glsl_to_gl_module_jit(
             use crate::backend::transform::q32::{FixedPointFormat, Q32Transform};
             let transform = Q32Transform::new(FixedPointFormat::Fixed16x16);
             module = module.apply_transform(transform)?;
+            module.uniforms.set_decimal_format(DecimalFormat::Q32);
         }
         DecimalFormat::Float => {
             return Err(GlslError::new(
@@ -131,6 +142,7 @@ pub fn compile_glsl_to_gl_module_jit(
             ));
         }
     }
+    module.schedule_functions();
 
     Ok(module)
 }
@@ -149,7 +161,9 @@ pub fn compile_glsl_to_gl_module_object(
 
     options.validate()?;
 
-    let mut compiler = GlslCompiler::new();
+    let mut compiler = GlslCompiler::new()
+        .with_complexity_budget(options.complexity_budget)
+        .with_warnings_as_errors(options.warnings_as_errors);
 
     // Determine target based on run mode
     let target = match &options.run_mode {
@@ -162,6 +176,8 @@ pub fn compile_glsl_to_gl_module_object(
         }
     };
 
+    let target = target.with_opt_level(options.opt_level)?;
+
     // Compile to GlModule
     let mut module = compiler.compile_to_gl_module_object(source, target)?;
 
@@ -177,7 +193,10 @@ pub fn compile_glsl_to_gl_module_object(
             use crate::backend::transform::q32::{FixedPointFormat, Q32Transform};
             let transform = Q32Transform::new(FixedPointFormat::Fixed16x16);
             module = module.apply_transform(transform)?;
-            // Capture transformed CLIF IR after transformation (only in std builds)
+            module.schedule_functions();
+            module.uniforms.set_decimal_format(DecimalFormat::Q32);
+            // Capture transformed CLIF IR after transformation and scheduling (only in
+            // std builds)
             #[cfg(feature = "std")]
             {
                 format_clif_module(&module).ok()
@@ -188,10 +207,12 @@ pub fn compile_glsl_to_gl_module_object(
             }
         }
         DecimalFormat::Float => {
-            // No transformation needed, so transformed_clif is same as original_clif
+            // No transformation needed; only scheduling (if the opt level asks for it)
+            // changes the CLIF
+            module.schedule_functions();
             #[cfg(feature = "std")]
             {
-                original_clif.clone()
+                format_clif_module(&module).ok()
             }
             #[cfg(not(feature = "std"))]
             {
@@ -210,6 +231,35 @@ pub fn glsl_jit(source: &str, options: GlslOptions) -> Result<Box<dyn GlslExecut
     module.build_executable()
 }
 
+/// Parse and type-check GLSL for the AST interpreter
+/// Slower than `glsl_jit`, but needs no code generation, so it can run shaders the
+/// JIT can't compile. `run_mode`, `decimal_format` and `opt_level` are ignored.
+pub fn glsl_interpret(
+    source: &str,
+    options: GlslOptions,
+) -> Result<Box<dyn GlslExecutable>, GlslError> {
+    let semantic_result = CompilationPipeline::parse_and_analyze(source)?;
+    let typed_ast = semantic_result.typed_ast;
+    if let Some(budget) = &options.complexity_budget {
+        budget.check(&typed_ast)?;
+    }
+    typed_ast.check_warnings(options.warnings_as_errors)?;
+    let module = GlslInterpreterModule::new(typed_ast, options.loop_fuel)?;
+    Ok(Box::new(module))
+}
+
+/// Parse and type-check GLSL without compiling it, returning its warnings
+/// Fails on the first error, or on the first warning if `options.warnings_as_errors`
+/// is set. Only the complexity budget and strictness of `options` are used.
+pub fn glsl_check(source: &str, options: &GlslOptions) -> Result<Vec<Diagnostic>, GlslError> {
+    let typed_ast = CompilationPipeline::parse_and_analyze(source)?.typed_ast;
+    if let Some(budget) = &options.complexity_budget {
+        budget.check(&typed_ast)?;
+    }
+    typed_ast.check_warnings(options.warnings_as_errors)?;
+    Ok(typed_ast.warnings)
+}
+
 /// Compile and execute GLSL in RISC-V 32-bit emulator
 /// Requires `emulator` feature flag to be enabled
 #[cfg(feature = "emulator")]
@@ -0,0 +1,297 @@
+//! Shader complexity estimation.
+//!
+//! Codegen for a large shader can need more heap than an embedded target has. Checking
+//! the typed AST against a [`ComplexityBudget`] before codegen turns that into a
+//! descriptive compile error instead of an out-of-memory crash.
+
+use crate::error::{ErrorCode, GlslError};
+use crate::frontend::semantic::TypedShader;
+use glsl::syntax::{
+    ArraySpecifierDimension, AssignmentOp, BinaryOp, Condition, Declaration, Expr,
+    ForInitStatement, ForRestStatement, Initializer, IterationStatement, JumpStatement,
+    SelectionRestStatement, SimpleStatement, Statement, UnaryOp,
+};
+
+use alloc::format;
+
+/// Limits a shader must stay within to be compiled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComplexityBudget {
+    /// Maximum statements and expressions across all functions
+    pub max_ast_nodes: usize,
+    /// Maximum iterations of the most deeply nested loops, multiplied together
+    pub max_loop_iterations: u64,
+}
+
+/// Estimated size of a shader
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShaderComplexity {
+    /// Statements and expressions across all functions
+    pub ast_nodes: usize,
+    /// Iterations of the most expensive loop nest
+    ///
+    /// Only `for` loops over an `int` counter with constant bounds are counted; other
+    /// loops count as a single iteration.
+    pub loop_iterations: u64,
+}
+
+impl ComplexityBudget {
+    /// Estimate the shader's complexity, failing if it is over budget
+    pub fn check(&self, shader: &TypedShader) -> Result<ShaderComplexity, GlslError> {
+        let complexity = ShaderComplexity::estimate(shader);
+        if complexity.ast_nodes > self.max_ast_nodes {
+            return Err(GlslError::new(
+                ErrorCode::E0117,
+                format!(
+                    "shader exceeds complexity budget: {} AST nodes (limit {})",
+                    complexity.ast_nodes, self.max_ast_nodes
+                ),
+            )
+            .with_note("split the shader into smaller functions or simplify expressions"));
+        }
+        if complexity.loop_iterations > self.max_loop_iterations {
+            return Err(GlslError::new(
+                ErrorCode::E0117,
+                format!(
+                    "shader exceeds complexity budget: loops run {} iterations (limit {})",
+                    complexity.loop_iterations, self.max_loop_iterations
+                ),
+            )
+            .with_note("reduce loop bounds or nesting"));
+        }
+        Ok(complexity)
+    }
+}
+
+impl ShaderComplexity {
+    /// Estimate the complexity of every function in a shader
+    pub fn estimate(shader: &TypedShader) -> Self {
+        let mut complexity = Self::default();
+        for func in shader.main_function.iter().chain(&shader.user_functions) {
+            for stmt in &func.body {
+                let cost = statement_cost(stmt);
+                complexity.ast_nodes += cost.nodes;
+                complexity.loop_iterations = complexity.loop_iterations.max(cost.iterations);
+            }
+        }
+        complexity
+    }
+}
+
+/// Size of a subtree and the iterations of its most expensive loop nest
+struct Cost {
+    nodes: usize,
+    iterations: u64,
+}
+
+impl Cost {
+    fn node() -> Self {
+        Self {
+            nodes: 1,
+            iterations: 1,
+        }
+    }
+
+    fn add(&mut self, other: Cost) {
+        self.nodes += other.nodes;
+        self.iterations = self.iterations.max(other.iterations);
+    }
+
+    fn add_expr(&mut self, expr: &Expr) {
+        self.nodes += expr_nodes(expr);
+    }
+}
+
+fn statement_cost(stmt: &Statement) -> Cost {
+    let mut cost = Cost::node();
+    match stmt {
+        Statement::Compound(compound) => {
+            for stmt in &compound.statement_list {
+                cost.add(statement_cost(stmt));
+            }
+        }
+        Statement::Simple(simple) => cost.add(simple_statement_cost(simple)),
+    }
+    cost
+}
+
+fn simple_statement_cost(stmt: &SimpleStatement) -> Cost {
+    let mut cost = Cost {
+        nodes: 0,
+        iterations: 1,
+    };
+    match stmt {
+        SimpleStatement::Declaration(decl) => cost.nodes += declaration_nodes(decl),
+        SimpleStatement::Expression(Some(expr)) => cost.add_expr(expr),
+        SimpleStatement::Selection(selection) => {
+            cost.add_expr(&selection.cond);
+            match &selection.rest {
+                SelectionRestStatement::Statement(then_stmt) => {
+                    cost.add(statement_cost(then_stmt));
+                }
+                SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                    cost.add(statement_cost(then_stmt));
+                    cost.add(statement_cost(else_stmt));
+                }
+            }
+        }
+        SimpleStatement::Iteration(iteration) => cost.add(iteration_cost(iteration)),
+        SimpleStatement::Jump(JumpStatement::Return(Some(expr))) => cost.add_expr(expr),
+        _ => {}
+    }
+    cost
+}
+
+fn iteration_cost(iteration: &IterationStatement) -> Cost {
+    let mut cost = Cost::node();
+    let (body, trip_count) = match iteration {
+        IterationStatement::While(condition, body) => {
+            cost.nodes += condition_nodes(condition);
+            (body, 1)
+        }
+        IterationStatement::DoWhile(body, cond) => {
+            cost.add_expr(cond);
+            (body, 1)
+        }
+        IterationStatement::For(init, rest, body) => {
+            match init {
+                ForInitStatement::Declaration(decl) => cost.nodes += declaration_nodes(decl),
+                ForInitStatement::Expression(Some(expr)) => cost.add_expr(expr),
+                ForInitStatement::Expression(None) => {}
+            }
+            if let Some(condition) = &rest.condition {
+                cost.nodes += condition_nodes(condition);
+            }
+            if let Some(post_expr) = &rest.post_expr {
+                cost.add_expr(post_expr);
+            }
+            (body, for_trip_count(init, rest).unwrap_or(1))
+        }
+    };
+
+    let body = statement_cost(body);
+    cost.nodes += body.nodes;
+    cost.iterations = body.iterations.saturating_mul(trip_count);
+    cost
+}
+
+/// Iterations of `for (int i = a; i < b; i += step)` and its variations, when `a`, `b`
+/// and `step` are integer constants
+fn for_trip_count(init: &ForInitStatement, rest: &ForRestStatement) -> Option<u64> {
+    let ForInitStatement::Declaration(decl) = init else {
+        return None;
+    };
+    let counter_decl: &Declaration = decl;
+    let Declaration::InitDeclaratorList(list) = counter_decl else {
+        return None;
+    };
+    let counter = &list.head.name.as_ref()?.name;
+    let Some(Initializer::Simple(start)) = &list.head.initializer else {
+        return None;
+    };
+    let Expr::IntConst(start, _) = start.as_ref() else {
+        return None;
+    };
+
+    let Some(Condition::Expr(cond)) = &rest.condition else {
+        return None;
+    };
+    let Expr::Binary(op, lhs, rhs, _) = cond.as_ref() else {
+        return None;
+    };
+    let (Expr::Variable(var, _), Expr::IntConst(bound, _)) = (lhs.as_ref(), rhs.as_ref()) else {
+        return None;
+    };
+    if &var.name != counter {
+        return None;
+    }
+
+    let step = match rest.post_expr.as_deref()? {
+        Expr::PostInc(..) | Expr::Unary(UnaryOp::Inc, ..) => 1,
+        Expr::PostDec(..) | Expr::Unary(UnaryOp::Dec, ..) => -1,
+        Expr::Assignment(_, AssignmentOp::Add, step, _) => match step.as_ref() {
+            Expr::IntConst(step, _) => *step as i64,
+            _ => return None,
+        },
+        Expr::Assignment(_, AssignmentOp::Sub, step, _) => match step.as_ref() {
+            Expr::IntConst(step, _) => -(*step as i64),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let (start, bound) = (*start as i64, *bound as i64);
+    let count = match op {
+        BinaryOp::LT if step > 0 => (bound - start + step - 1) / step,
+        BinaryOp::LTE if step > 0 => (bound - start) / step + 1,
+        BinaryOp::GT if step < 0 => (start - bound - step - 1) / -step,
+        BinaryOp::GTE if step < 0 => (start - bound) / -step + 1,
+        _ => return None,
+    };
+    Some(count.max(0) as u64)
+}
+
+fn declaration_nodes(decl: &Declaration) -> usize {
+    let Declaration::InitDeclaratorList(list) = decl else {
+        return 1;
+    };
+    let mut nodes = 1;
+    if let Some(init) = &list.head.initializer {
+        nodes += initializer_nodes(init);
+    }
+    for declarator in &list.tail {
+        nodes += 1;
+        if let Some(init) = &declarator.initializer {
+            nodes += initializer_nodes(init);
+        }
+    }
+    nodes
+}
+
+fn initializer_nodes(init: &Initializer) -> usize {
+    match init {
+        Initializer::Simple(expr) => expr_nodes(expr),
+        Initializer::List(list) => list.0.iter().map(initializer_nodes).sum(),
+    }
+}
+
+fn condition_nodes(condition: &Condition) -> usize {
+    match condition {
+        Condition::Expr(expr) => expr_nodes(expr),
+        Condition::Assignment(_, _, init) => 1 + initializer_nodes(init),
+    }
+}
+
+fn expr_nodes(expr: &Expr) -> usize {
+    1 + match expr {
+        Expr::Variable(..)
+        | Expr::IntConst(..)
+        | Expr::UIntConst(..)
+        | Expr::FloatConst(..)
+        | Expr::DoubleConst(..)
+        | Expr::BoolConst(..) => 0,
+        Expr::Unary(_, operand, _)
+        | Expr::Dot(operand, _, _)
+        | Expr::PostInc(operand, _)
+        | Expr::PostDec(operand, _) => expr_nodes(operand),
+        Expr::Binary(_, lhs, rhs, _)
+        | Expr::Assignment(lhs, _, rhs, _)
+        | Expr::Comma(lhs, rhs, _) => expr_nodes(lhs) + expr_nodes(rhs),
+        Expr::Ternary(cond, then_expr, else_expr, _) => {
+            expr_nodes(cond) + expr_nodes(then_expr) + expr_nodes(else_expr)
+        }
+        Expr::Bracket(array, spec, _) => {
+            expr_nodes(array)
+                + spec
+                    .dimensions
+                    .0
+                    .iter()
+                    .map(|dimension| match dimension {
+                        ArraySpecifierDimension::ExplicitlySized(index) => expr_nodes(index),
+                        ArraySpecifierDimension::Unsized => 0,
+                    })
+                    .sum::<usize>()
+        }
+        Expr::FunCall(_, args, _) => args.iter().map(expr_nodes).sum(),
+    }
+}
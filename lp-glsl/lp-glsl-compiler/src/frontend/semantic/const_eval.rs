@@ -0,0 +1,273 @@
+//! Compile-time evaluation of constant expressions
+//!
+//! Folds scalar expressions built from literals, operators, the ternary operator and
+//! scalar constructors. Array sizes must evaluate here, and `const` declarations with
+//! scalar initializers are folded to literals. Anything else (vectors, built-in calls,
+//! variables) is not evaluated and left to codegen.
+
+use crate::error::{ErrorCode, GlslError, source_span_to_location};
+use crate::frontend::semantic::type_check::{infer_binary_result_type, promote_numeric};
+use crate::frontend::semantic::types::Type;
+use glsl::syntax::{BinaryOp, Expr, FunIdentifier, SourceSpan, UnaryOp};
+
+/// Value of a scalar constant expression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstValue {
+    Int(i32),
+    UInt(u32),
+    Float(f32),
+    Bool(bool),
+}
+
+impl ConstValue {
+    pub fn ty(&self) -> Type {
+        match self {
+            ConstValue::Int(_) => Type::Int,
+            ConstValue::UInt(_) => Type::UInt,
+            ConstValue::Float(_) => Type::Float,
+            ConstValue::Bool(_) => Type::Bool,
+        }
+    }
+
+    /// Convert to scalar type `ty` (constructor semantics, so any conversion is allowed)
+    pub fn convert(self, ty: &Type) -> Option<ConstValue> {
+        let (int, float, boolean) = match self {
+            ConstValue::Int(v) => (v, v as f32, v != 0),
+            ConstValue::UInt(v) => (v as i32, v as f32, v != 0),
+            ConstValue::Float(v) => (v as i32, v, v != 0.0),
+            ConstValue::Bool(v) => (v as i32, v as i32 as f32, v),
+        };
+        Some(match ty {
+            Type::Int => ConstValue::Int(int),
+            Type::UInt => ConstValue::UInt(match self {
+                ConstValue::UInt(v) => v,
+                ConstValue::Float(v) => v as u32,
+                _ => int as u32,
+            }),
+            Type::Float => ConstValue::Float(float),
+            Type::Bool => ConstValue::Bool(boolean),
+            _ => return None,
+        })
+    }
+
+    /// Literal expression for this value
+    pub fn to_expr(self, span: SourceSpan) -> Expr {
+        match self {
+            ConstValue::Int(v) => Expr::IntConst(v, span),
+            ConstValue::UInt(v) => Expr::UIntConst(v, span),
+            ConstValue::Float(v) => Expr::FloatConst(v, span),
+            ConstValue::Bool(v) => Expr::BoolConst(v, span),
+        }
+    }
+}
+
+/// Evaluate `expr` if it is a scalar constant expression
+///
+/// Returns `Ok(None)` for expressions that can't be evaluated at compile time, and
+/// an error for ones that are constant but invalid (like integer division by zero).
+pub fn eval_const_expr(expr: &Expr) -> Result<Option<ConstValue>, GlslError> {
+    let value = match expr {
+        Expr::IntConst(v, _) => ConstValue::Int(*v),
+        Expr::UIntConst(v, _) => ConstValue::UInt(*v),
+        Expr::FloatConst(v, _) => ConstValue::Float(*v),
+        Expr::DoubleConst(v, _) => ConstValue::Float(*v as f32),
+        Expr::BoolConst(v, _) => ConstValue::Bool(*v),
+        Expr::Unary(op, operand, _) => {
+            let Some(operand) = eval_const_expr(operand)? else {
+                return Ok(None);
+            };
+            match (op, operand) {
+                (UnaryOp::Minus, ConstValue::Int(v)) => ConstValue::Int(v.wrapping_neg()),
+                (UnaryOp::Minus, ConstValue::UInt(v)) => ConstValue::UInt(v.wrapping_neg()),
+                (UnaryOp::Minus, ConstValue::Float(v)) => ConstValue::Float(-v),
+                (UnaryOp::Not, ConstValue::Bool(v)) => ConstValue::Bool(!v),
+                (UnaryOp::Complement, ConstValue::Int(v)) => ConstValue::Int(!v),
+                (UnaryOp::Complement, ConstValue::UInt(v)) => ConstValue::UInt(!v),
+                _ => return Ok(None),
+            }
+        }
+        Expr::Binary(op, lhs, rhs, span) => {
+            let (Some(lhs), Some(rhs)) = (eval_const_expr(lhs)?, eval_const_expr(rhs)?) else {
+                return Ok(None);
+            };
+            return eval_binary(op, lhs, rhs, span);
+        }
+        Expr::Ternary(cond, then_expr, else_expr, _) => {
+            let Some(ConstValue::Bool(cond)) = eval_const_expr(cond)? else {
+                return Ok(None);
+            };
+            let (Some(then_value), Some(else_value)) =
+                (eval_const_expr(then_expr)?, eval_const_expr(else_expr)?)
+            else {
+                return Ok(None);
+            };
+            if then_value.ty() != else_value.ty() {
+                return Ok(None);
+            }
+            if cond { then_value } else { else_value }
+        }
+        Expr::FunCall(FunIdentifier::Identifier(ident), args, _) if args.len() == 1 => {
+            let ty = match ident.name.as_str() {
+                "int" => Type::Int,
+                "uint" => Type::UInt,
+                "float" => Type::Float,
+                "bool" => Type::Bool,
+                _ => return Ok(None),
+            };
+            let Some(arg) = eval_const_expr(&args[0])? else {
+                return Ok(None);
+            };
+            return Ok(arg.convert(&ty));
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+fn eval_binary(
+    op: &BinaryOp,
+    lhs: ConstValue,
+    rhs: ConstValue,
+    span: &SourceSpan,
+) -> Result<Option<ConstValue>, GlslError> {
+    let ty = infer_binary_result_type(op, &lhs.ty(), &rhs.ty(), span.clone())?;
+    let operand_ty = if ty == Type::Bool && lhs.ty() != Type::Bool {
+        promote_numeric(&lhs.ty(), &rhs.ty())
+    } else {
+        ty
+    };
+    let (Some(lhs), Some(rhs)) = (lhs.convert(&operand_ty), rhs.convert(&operand_ty)) else {
+        return Ok(None);
+    };
+
+    let division_by_zero = || {
+        GlslError::new(
+            ErrorCode::E0118,
+            "integer division by zero in constant expression",
+        )
+        .with_location(source_span_to_location(span))
+    };
+    let value = match (op, lhs, rhs) {
+        (BinaryOp::Equal, a, b) => ConstValue::Bool(a == b),
+        (BinaryOp::NonEqual, a, b) => ConstValue::Bool(a != b),
+        (BinaryOp::And, ConstValue::Bool(a), ConstValue::Bool(b)) => ConstValue::Bool(a && b),
+        (BinaryOp::Or, ConstValue::Bool(a), ConstValue::Bool(b)) => ConstValue::Bool(a || b),
+        (BinaryOp::Xor, ConstValue::Bool(a), ConstValue::Bool(b)) => ConstValue::Bool(a != b),
+        (BinaryOp::LT | BinaryOp::GT | BinaryOp::LTE | BinaryOp::GTE, a, b) => {
+            let ordering = match (a, b) {
+                (ConstValue::Int(a), ConstValue::Int(b)) => a.partial_cmp(&b),
+                (ConstValue::UInt(a), ConstValue::UInt(b)) => a.partial_cmp(&b),
+                (ConstValue::Float(a), ConstValue::Float(b)) => a.partial_cmp(&b),
+                _ => return Ok(None),
+            };
+            let Some(ordering) = ordering else {
+                return Ok(Some(ConstValue::Bool(false)));
+            };
+            ConstValue::Bool(match op {
+                BinaryOp::LT => ordering.is_lt(),
+                BinaryOp::GT => ordering.is_gt(),
+                BinaryOp::LTE => ordering.is_le(),
+                _ => ordering.is_ge(),
+            })
+        }
+        (_, ConstValue::Int(a), ConstValue::Int(b)) => ConstValue::Int(match op {
+            BinaryOp::Add => a.wrapping_add(b),
+            BinaryOp::Sub => a.wrapping_sub(b),
+            BinaryOp::Mult => a.wrapping_mul(b),
+            BinaryOp::Div | BinaryOp::Mod if b == 0 => return Err(division_by_zero()),
+            BinaryOp::Div => a.wrapping_div(b),
+            BinaryOp::Mod => a.wrapping_rem(b),
+            BinaryOp::BitAnd => a & b,
+            BinaryOp::BitOr => a | b,
+            BinaryOp::BitXor => a ^ b,
+            BinaryOp::LShift => a.wrapping_shl(b as u32),
+            BinaryOp::RShift => a.wrapping_shr(b as u32),
+            _ => return Ok(None),
+        }),
+        (_, ConstValue::UInt(a), ConstValue::UInt(b)) => ConstValue::UInt(match op {
+            BinaryOp::Add => a.wrapping_add(b),
+            BinaryOp::Sub => a.wrapping_sub(b),
+            BinaryOp::Mult => a.wrapping_mul(b),
+            BinaryOp::Div | BinaryOp::Mod if b == 0 => return Err(division_by_zero()),
+            BinaryOp::Div => a / b,
+            BinaryOp::Mod => a % b,
+            BinaryOp::BitAnd => a & b,
+            BinaryOp::BitOr => a | b,
+            BinaryOp::BitXor => a ^ b,
+            BinaryOp::LShift => a.wrapping_shl(b),
+            BinaryOp::RShift => a.wrapping_shr(b),
+            _ => return Ok(None),
+        }),
+        (_, ConstValue::Float(a), ConstValue::Float(b)) => ConstValue::Float(match op {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mult => a * b,
+            BinaryOp::Div => a / b,
+            _ => return Ok(None),
+        }),
+        _ => return Ok(None),
+    };
+    Ok(Some(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glsl::parser::Parse;
+
+    fn eval(source: &str) -> Result<Option<ConstValue>, GlslError> {
+        eval_const_expr(&Expr::parse(source).unwrap())
+    }
+
+    #[test]
+    fn test_eval_int_arithmetic() {
+        assert_eq!(
+            eval("(2 + 3) * 4 - 10 / 3").unwrap(),
+            Some(ConstValue::Int(17))
+        );
+        assert_eq!(eval("10 % 3").unwrap(), Some(ConstValue::Int(1)));
+        assert_eq!(eval("-(5 - 8)").unwrap(), Some(ConstValue::Int(3)));
+    }
+
+    #[test]
+    fn test_eval_promotes_to_float() {
+        assert_eq!(eval("2 * 1.5").unwrap(), Some(ConstValue::Float(3.0)));
+        assert_eq!(
+            eval("float(7) / 2.0").unwrap(),
+            Some(ConstValue::Float(3.5))
+        );
+    }
+
+    #[test]
+    fn test_eval_comparisons_and_ternary() {
+        assert_eq!(
+            eval("3 > 2 && 1.0 <= 0.5").unwrap(),
+            Some(ConstValue::Bool(false))
+        );
+        assert_eq!(eval("2u < 3u ? 4 : 5").unwrap(), Some(ConstValue::Int(4)));
+    }
+
+    #[test]
+    fn test_eval_signed_and_unsigned_bits() {
+        assert_eq!(eval("-16 >> 2").unwrap(), Some(ConstValue::Int(-4)));
+        assert_eq!(
+            eval("uint(-16) >> 2u").unwrap(),
+            Some(ConstValue::UInt(0x3FFF_FFFC))
+        );
+        assert_eq!(eval("(1 << 4) | 3").unwrap(), Some(ConstValue::Int(19)));
+        assert_eq!(eval("~0u ^ 15u").unwrap(), Some(ConstValue::UInt(!15)));
+    }
+
+    #[test]
+    fn test_eval_non_constant() {
+        assert_eq!(eval("x + 1").unwrap(), None);
+        assert_eq!(eval("sin(1.0)").unwrap(), None);
+        assert_eq!(eval("vec2(1.0)").unwrap(), None);
+    }
+
+    #[test]
+    fn test_eval_division_by_zero() {
+        let error = eval("4 / (2 - 2)").unwrap_err();
+        assert_eq!(error.code, ErrorCode::E0118);
+    }
+}
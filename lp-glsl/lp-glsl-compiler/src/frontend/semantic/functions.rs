@@ -1,7 +1,7 @@
 //! User-defined function registry and type checking
 
 use crate::error::{ErrorCode, GlslError};
-use crate::frontend::semantic::type_check::can_implicitly_convert;
+use crate::frontend::semantic::type_check::{can_implicitly_convert, explicit_conversion_hint};
 use crate::frontend::semantic::types::Type;
 use hashbrown::HashMap;
 
@@ -71,13 +71,26 @@ impl FunctionRegistry {
             }
         }
 
-        Err(GlslError::new(
+        let mut error = GlslError::new(
             ErrorCode::E0114,
             format!("no matching overload for function `{name}`"),
         )
         .with_note(format!(
             "cannot find function `{name}` that accepts arguments of type {arg_types:?}"
-        )))
+        ));
+
+        // With a single candidate, point at arguments that need an explicit conversion
+        if let [sig] = overloads.as_slice() {
+            if sig.parameters.len() == arg_types.len() {
+                for (param, arg_ty) in sig.parameters.iter().zip(arg_types) {
+                    if let Some(hint) = explicit_conversion_hint(arg_ty, &param.ty) {
+                        error = error.with_note(format!("parameter `{}`: {hint}", param.name));
+                    }
+                }
+            }
+        }
+
+        Err(error)
     }
 
     fn exact_match(sig: &FunctionSignature, arg_types: &[Type]) -> bool {
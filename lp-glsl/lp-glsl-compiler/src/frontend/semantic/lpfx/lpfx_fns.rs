@@ -711,6 +711,28 @@ fn init_functions() -> &'static [LpfxFn] {
                 q32_impl: BuiltinId::LpfxSrandom3VecQ32,
             },
         },
+        LpfxFn {
+            glsl_sig: FunctionSignature {
+                name: String::from("lpfx_vnoise"),
+                return_type: Type::Float,
+                parameters: vec![
+                    Parameter {
+                        name: String::from("p"),
+                        ty: Type::Vec2,
+                        qualifier: ParamQualifier::In,
+                    },
+                    Parameter {
+                        name: String::from("seed"),
+                        ty: Type::UInt,
+                        qualifier: ParamQualifier::In,
+                    },
+                ],
+            },
+            impls: LpfxFnImpl::Decimal {
+                float_impl: BuiltinId::LpfxVnoise2F32,
+                q32_impl: BuiltinId::LpfxVnoise2Q32,
+            },
+        },
         LpfxFn {
             glsl_sig: FunctionSignature {
                 name: String::from("lpfx_worley"),
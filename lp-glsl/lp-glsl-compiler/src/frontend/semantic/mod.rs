@@ -1,11 +1,14 @@
-use crate::error::GlslError;
+use crate::error::{Diagnostic, GlslError};
 use glsl::syntax::TranslationUnit;
 use passes::SemanticPass;
+use passes::purity::Purity;
 
 use alloc::vec::Vec;
 
 use alloc::string::String;
 pub mod builtins;
+pub mod complexity;
+pub mod const_eval;
 pub mod functions;
 pub mod lpfx;
 pub mod passes;
@@ -18,10 +21,40 @@ pub mod validator;
 /// Name of the main entry point function in GLSL shaders
 pub const MAIN_FUNCTION_NAME: &str = "main";
 
+/// Implicit uniform holding the current time in seconds
+pub const TIME_UNIFORM_NAME: &str = "u_time";
+
+/// Implicit uniform holding the current frame number
+pub const FRAME_UNIFORM_NAME: &str = "u_frame";
+
 pub struct TypedShader {
     pub main_function: Option<TypedFunction>,
     pub user_functions: Vec<TypedFunction>,
     pub function_registry: functions::FunctionRegistry,
+    pub uniforms: Vec<UniformDecl>,
+    /// Which functions are pure or read time
+    pub purity: Purity,
+    /// Warnings found during analysis (errors stop analysis instead)
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl TypedShader {
+    /// Fail with the first warning if warnings are treated as errors
+    pub fn check_warnings(&self, warnings_as_errors: bool) -> Result<(), GlslError> {
+        match self.warnings.first() {
+            Some(warning) if warnings_as_errors => Err(warning.clone().into_error()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A `uniform` declared at global scope
+#[derive(Clone)]
+pub struct UniformDecl {
+    pub name: String,
+    pub ty: types::Type,
+    /// Provided by the runtime rather than declared in the source
+    pub implicit: bool,
 }
 
 pub struct TypedFunction {
@@ -49,6 +82,12 @@ impl SemanticAnalyzer {
         shader: &TranslationUnit,
         source: &str,
     ) -> Result<TypedShader, GlslError> {
+        // Pass 0: Fold `const` declarations into the code that uses them
+        let mut const_pass = passes::const_inlining::ConstInliningPass::new();
+        const_pass.run(shader, source)?;
+        let inlined = const_pass.into_shader();
+        let shader = &inlined;
+
         // Pass 1: Collect function signatures
         let mut registry_pass = passes::function_registry::FunctionRegistryPass::new();
         registry_pass.run(shader, source)?;
@@ -59,19 +98,35 @@ impl SemanticAnalyzer {
         extraction_pass.run(shader, source)?;
         let (main_func, user_functions) = extraction_pass.into_results();
 
+        // Pass 2b: Collect uniform declarations
+        let mut uniform_pass = passes::uniform_collection::UniformCollectionPass::new();
+        uniform_pass.run(shader, source)?;
+        let uniforms = uniform_pass.into_uniforms();
+
         // Pass 3: Validate
         // Main function is optional for filetests (functions can be called directly)
         // For backward compatibility, we still allow requiring main, but don't enforce it here
-        let typed_shader = TypedShader {
+        let mut typed_shader = TypedShader {
             main_function: main_func,
             user_functions,
             function_registry: registry,
+            uniforms,
+            purity: Purity::default(),
+            warnings: Vec::new(),
         };
 
         // Pass 3 (continued): Validate (using reference to registry from typed_shader)
         let mut validation_pass = passes::validation::ValidationPass;
         validation_pass.validate(&typed_shader, source)?;
 
+        // Pass 4: Lint for warnings
+        let mut unused_pass = passes::unused_variables::UnusedVariablePass;
+        typed_shader.warnings = unused_pass.check(&typed_shader, source);
+
+        // Pass 5: Find pure functions, whose output can be cached
+        let mut purity_pass = passes::purity::PurityPass;
+        typed_shader.purity = purity_pass.purity(&typed_shader);
+
         Ok(typed_shader)
     }
 }
@@ -0,0 +1,561 @@
+//! Pass for folding `const` declarations into the code that uses them
+//!
+//! Codegen has no notion of constants, so this pass rewrites a copy of the AST before
+//! the other passes see it. Uses of scalar constants become literals (evaluated at
+//! compile time), uses of vector and matrix constants become copies of their
+//! initializers, and local declarations of either are removed. Array sizes naming a
+//! constant are rewritten too, so the type resolver can evaluate them.
+
+use super::SemanticPass;
+use crate::error::{ErrorCode, GlslError, source_span_to_location};
+use crate::frontend::semantic::builtins::is_builtin_function;
+use crate::frontend::semantic::const_eval::{ConstValue, eval_const_expr};
+use crate::frontend::semantic::scope::SymbolTable;
+use crate::frontend::semantic::type_check::{
+    check_assignment_with_span, infer_expr_type, is_matrix_type_name, is_scalar_type_name,
+    is_vector_type_name,
+};
+use crate::frontend::semantic::type_resolver;
+use crate::frontend::semantic::types::Type;
+use glsl::syntax::{
+    ArraySpecifier, ArraySpecifierDimension, Condition, Declaration, Expr, ExternalDeclaration,
+    ForInitStatement, FunIdentifier, FunctionDefinition, FunctionParameterDeclaration, Identifier,
+    Initializer, IterationStatement, JumpStatement, SelectionRestStatement, SimpleStatement,
+    SourceSpan, Statement, TranslationUnit, TypeQualifier, UnaryOp,
+};
+use hashbrown::HashMap;
+
+use alloc::{format, string::String, vec, vec::Vec};
+
+pub struct ConstInliningPass {
+    shader: Option<TranslationUnit>,
+}
+
+impl ConstInliningPass {
+    pub fn new() -> Self {
+        Self { shader: None }
+    }
+
+    /// Take the rewritten translation unit
+    pub fn into_shader(self) -> TranslationUnit {
+        self.shader
+            .expect("const inlining pass must run before taking its output")
+    }
+}
+
+impl SemanticPass for ConstInliningPass {
+    fn run(&mut self, shader: &TranslationUnit, _source: &str) -> Result<(), GlslError> {
+        let mut shader = shader.clone();
+        let mut inliner = Inliner {
+            scopes: vec![HashMap::new()],
+        };
+        for decl in &mut shader.0 {
+            match decl {
+                // Global declarations are left in place; nothing reads their initializers
+                ExternalDeclaration::Declaration(decl) => {
+                    inliner.declare(decl)?;
+                }
+                ExternalDeclaration::FunctionDefinition(func) => inliner.function(func)?,
+                _ => {}
+            }
+        }
+        self.shader = Some(shader);
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "const_inlining"
+    }
+}
+
+/// What a use of a constant is replaced with
+#[derive(Clone)]
+enum ConstDef {
+    /// Scalar constant, replaced by a literal
+    Value(ConstValue),
+    /// Vector, matrix or unevaluated scalar constant, replaced by its initializer
+    Expr(Expr),
+    /// Array constant; kept as a variable, but still read-only
+    ReadOnly,
+}
+
+struct Inliner {
+    /// Names declared in each scope, outermost first (`None` for non-const variables)
+    scopes: Vec<HashMap<String, Option<ConstDef>>>,
+}
+
+impl Inliner {
+    fn lookup(&self, name: &str) -> Option<&ConstDef> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .and_then(Option::as_ref)
+    }
+
+    fn bind(&mut self, name: &str, def: Option<ConstDef>) {
+        self.scopes
+            .last_mut()
+            .expect("inliner always has a global scope")
+            .insert(String::from(name), def);
+    }
+
+    /// Run `f` in a fresh scope
+    fn scoped(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), GlslError>,
+    ) -> Result<(), GlslError> {
+        self.scopes.push(HashMap::new());
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn function(&mut self, func: &mut FunctionDefinition) -> Result<(), GlslError> {
+        if let Some(spec) = &mut func.prototype.ty.ty.array_specifier {
+            self.array_spec(spec)?;
+        }
+        self.scoped(|this| {
+            for param in &mut func.prototype.parameters {
+                match param {
+                    FunctionParameterDeclaration::Named(_, decl) => {
+                        if let Some(spec) = &mut decl.ty.array_specifier {
+                            this.array_spec(spec)?;
+                        }
+                        if let Some(spec) = &mut decl.ident.array_spec {
+                            this.array_spec(spec)?;
+                        }
+                        this.bind(&decl.ident.ident.name, None);
+                    }
+                    FunctionParameterDeclaration::Unnamed(_, ty) => {
+                        if let Some(spec) = &mut ty.array_specifier {
+                            this.array_spec(spec)?;
+                        }
+                    }
+                }
+            }
+            this.statements(&mut func.statement.statement_list)
+        })
+    }
+
+    fn statements(&mut self, stmts: &mut [Statement]) -> Result<(), GlslError> {
+        for stmt in stmts {
+            self.statement(stmt)?;
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, stmt: &mut Statement) -> Result<(), GlslError> {
+        match stmt {
+            Statement::Compound(compound) => {
+                self.scoped(|this| this.statements(&mut compound.statement_list))
+            }
+            Statement::Simple(simple) => self.simple_statement(simple),
+        }
+    }
+
+    fn simple_statement(&mut self, stmt: &mut SimpleStatement) -> Result<(), GlslError> {
+        match stmt {
+            SimpleStatement::Declaration(decl) => {
+                if self.declare(decl)? {
+                    *stmt = SimpleStatement::Expression(None);
+                }
+            }
+            SimpleStatement::Expression(Some(expr)) => self.expr(expr)?,
+            SimpleStatement::Selection(selection) => {
+                self.expr(&mut selection.cond)?;
+                match &mut selection.rest {
+                    SelectionRestStatement::Statement(then_stmt) => {
+                        self.scoped(|this| this.statement(then_stmt))?;
+                    }
+                    SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                        self.scoped(|this| this.statement(then_stmt))?;
+                        self.scoped(|this| this.statement(else_stmt))?;
+                    }
+                }
+            }
+            SimpleStatement::Iteration(iteration) => {
+                self.scoped(|this| this.iteration(iteration))?;
+            }
+            SimpleStatement::Jump(JumpStatement::Return(Some(expr))) => self.expr(expr)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn iteration(&mut self, iteration: &mut IterationStatement) -> Result<(), GlslError> {
+        match iteration {
+            IterationStatement::While(condition, body) => {
+                self.condition(condition)?;
+                self.statement(body)
+            }
+            IterationStatement::DoWhile(body, cond) => {
+                self.statement(body)?;
+                self.expr(cond)
+            }
+            IterationStatement::For(init, rest, body) => {
+                match init {
+                    ForInitStatement::Declaration(decl) => {
+                        if self.declare(decl)? {
+                            *init = ForInitStatement::Expression(None);
+                        }
+                    }
+                    ForInitStatement::Expression(Some(expr)) => self.expr(expr)?,
+                    ForInitStatement::Expression(None) => {}
+                }
+                if let Some(condition) = &mut rest.condition {
+                    self.condition(condition)?;
+                }
+                if let Some(post_expr) = &mut rest.post_expr {
+                    self.expr(post_expr)?;
+                }
+                self.statement(body)
+            }
+        }
+    }
+
+    fn condition(&mut self, condition: &mut Condition) -> Result<(), GlslError> {
+        match condition {
+            Condition::Expr(expr) => self.expr(expr),
+            Condition::Assignment(_, ident, init) => {
+                self.initializer(init)?;
+                self.bind(&ident.name, None);
+                Ok(())
+            }
+        }
+    }
+
+    /// Rewrite a declaration and bind the names it declares, returning true if it only
+    /// declares constants whose uses have all been replaced (so it can be removed)
+    fn declare(&mut self, decl: &mut Declaration) -> Result<bool, GlslError> {
+        let Declaration::InitDeclaratorList(list) = decl else {
+            return Ok(false);
+        };
+        let declares_const = is_const(&list.head.ty.qualifier);
+
+        if let Some(spec) = &mut list.head.ty.ty.array_specifier {
+            self.array_spec(spec)?;
+        }
+        let base_ty = if declares_const {
+            Some(type_resolver::parse_return_type(&list.head.ty, None)?)
+        } else {
+            None
+        };
+
+        let mut inlined = true;
+        if let Some(name) = list.head.name.clone() {
+            if let Some(spec) = &mut list.head.array_specifier {
+                self.array_spec(spec)?;
+            }
+            if let Some(init) = &mut list.head.initializer {
+                self.initializer(init)?;
+            }
+            let def = match &base_ty {
+                Some(base_ty) => {
+                    let ty = type_resolver::parse_declaration_type(
+                        base_ty,
+                        list.head.array_specifier.as_ref(),
+                        Some(name.span.clone()),
+                    )?;
+                    Some(const_def(&name, &ty, list.head.initializer.as_ref())?)
+                }
+                None => None,
+            };
+            inlined &= matches!(def, Some(ConstDef::Value(_) | ConstDef::Expr(_)));
+            self.bind(&name.name, def);
+        }
+
+        for declarator in &mut list.tail {
+            if let Some(spec) = &mut declarator.ident.array_spec {
+                self.array_spec(spec)?;
+            }
+            if let Some(init) = &mut declarator.initializer {
+                self.initializer(init)?;
+            }
+            let def = match &base_ty {
+                Some(base_ty) => {
+                    let ty = type_resolver::parse_tail_declarator_type(base_ty, declarator)?;
+                    let name = &declarator.ident.ident;
+                    Some(const_def(name, &ty, declarator.initializer.as_ref())?)
+                }
+                None => None,
+            };
+            inlined &= matches!(def, Some(ConstDef::Value(_) | ConstDef::Expr(_)));
+            self.bind(&declarator.ident.ident.name, def);
+        }
+
+        Ok(declares_const && inlined)
+    }
+
+    fn initializer(&mut self, init: &mut Initializer) -> Result<(), GlslError> {
+        match init {
+            Initializer::Simple(expr) => self.expr(expr),
+            Initializer::List(list) => {
+                for item in list.0.iter_mut() {
+                    self.initializer(item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn array_spec(&mut self, spec: &mut ArraySpecifier) -> Result<(), GlslError> {
+        for dimension in spec.dimensions.0.iter_mut() {
+            if let ArraySpecifierDimension::ExplicitlySized(size) = dimension {
+                self.expr(size)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &mut Expr) -> Result<(), GlslError> {
+        match expr {
+            Expr::Variable(ident, span) => {
+                let replacement = match self.lookup(&ident.name) {
+                    Some(ConstDef::Value(value)) => value.to_expr(span.clone()),
+                    Some(ConstDef::Expr(init)) => init.clone(),
+                    Some(ConstDef::ReadOnly) | None => return Ok(()),
+                };
+                *expr = replacement;
+                Ok(())
+            }
+            Expr::IntConst(..)
+            | Expr::UIntConst(..)
+            | Expr::FloatConst(..)
+            | Expr::DoubleConst(..)
+            | Expr::BoolConst(..) => Ok(()),
+            Expr::Assignment(lhs, _, rhs, span) => {
+                self.check_writable(lhs, span)?;
+                self.expr(lhs)?;
+                self.expr(rhs)
+            }
+            Expr::Unary(op, operand, span) => {
+                if matches!(op, UnaryOp::Inc | UnaryOp::Dec) {
+                    self.check_writable(operand, span)?;
+                }
+                self.expr(operand)
+            }
+            Expr::PostInc(operand, span) | Expr::PostDec(operand, span) => {
+                self.check_writable(operand, span)?;
+                self.expr(operand)
+            }
+            Expr::Dot(operand, _, _) => self.expr(operand),
+            Expr::Binary(_, lhs, rhs, _) | Expr::Comma(lhs, rhs, _) => {
+                self.expr(lhs)?;
+                self.expr(rhs)
+            }
+            Expr::Ternary(cond, then_expr, else_expr, _) => {
+                self.expr(cond)?;
+                self.expr(then_expr)?;
+                self.expr(else_expr)
+            }
+            Expr::Bracket(array, spec, _) => {
+                self.expr(array)?;
+                self.array_spec(spec)
+            }
+            Expr::FunCall(_, args, _) => {
+                for arg in args {
+                    self.expr(arg)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Reject writes to a constant (or a component or element of one)
+    fn check_writable(&self, target: &Expr, span: &SourceSpan) -> Result<(), GlslError> {
+        let root = match target {
+            Expr::Variable(ident, _) => ident,
+            Expr::Dot(base, _, _) | Expr::Bracket(base, _, _) => {
+                return self.check_writable(base, span);
+            }
+            _ => return Ok(()),
+        };
+        if self.lookup(&root.name).is_some() {
+            return Err(GlslError::new(
+                ErrorCode::E0115,
+                format!("cannot assign to const `{}`", root.name),
+            )
+            .with_location(source_span_to_location(span)));
+        }
+        Ok(())
+    }
+}
+
+/// Work out what uses of const `name` of type `ty` are replaced with
+///
+/// `init` has already been rewritten, so any constants it names have been replaced.
+fn const_def(
+    name: &Identifier,
+    ty: &Type,
+    init: Option<&Initializer>,
+) -> Result<ConstDef, GlslError> {
+    let location = source_span_to_location(&name.span);
+    let Some(init) = init else {
+        return Err(GlslError::new(
+            ErrorCode::E0118,
+            format!("const `{}` must be initialized", name.name),
+        )
+        .with_location(location));
+    };
+    // Arrays (and anything else that isn't a scalar, vector or matrix) stay read-only variables
+    let Initializer::Simple(expr) = init else {
+        return Ok(ConstDef::ReadOnly);
+    };
+    if !(ty.is_scalar() || ty.is_vector() || ty.is_matrix()) {
+        return Ok(ConstDef::ReadOnly);
+    }
+    if !is_constant_expr(expr) {
+        return Err(GlslError::new(
+            ErrorCode::E0118,
+            format!(
+                "initializer of const `{}` is not a constant expression",
+                name.name
+            ),
+        )
+        .with_location(location)
+        .with_note(
+            "only literals, other constants, constructors and built-in functions can be used",
+        ));
+    }
+
+    let init_ty = infer_expr_type(expr, &SymbolTable::new())?;
+    check_assignment_with_span(ty, &init_ty, Some(name.span.clone()))?;
+
+    if ty.is_scalar() {
+        if let Some(value) = eval_const_expr(expr)?
+            && let Some(value) = value.convert(ty)
+        {
+            return Ok(ConstDef::Value(value));
+        }
+        return Ok(ConstDef::Expr((**expr).clone()));
+    }
+    Ok(ConstDef::Expr((**expr).clone()))
+}
+
+/// Check that an expression only uses literals, constructors and built-in functions
+fn is_constant_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::IntConst(..)
+        | Expr::UIntConst(..)
+        | Expr::FloatConst(..)
+        | Expr::DoubleConst(..)
+        | Expr::BoolConst(..) => true,
+        Expr::Variable(..)
+        | Expr::Assignment(..)
+        | Expr::PostInc(..)
+        | Expr::PostDec(..)
+        | Expr::Comma(..)
+        | Expr::Unary(UnaryOp::Inc | UnaryOp::Dec, _, _) => false,
+        Expr::Unary(_, operand, _) | Expr::Dot(operand, _, _) => is_constant_expr(operand),
+        Expr::Binary(_, lhs, rhs, _) => is_constant_expr(lhs) && is_constant_expr(rhs),
+        Expr::Ternary(cond, then_expr, else_expr, _) => {
+            is_constant_expr(cond) && is_constant_expr(then_expr) && is_constant_expr(else_expr)
+        }
+        Expr::Bracket(array, spec, _) => {
+            is_constant_expr(array)
+                && spec.dimensions.0.iter().all(|dimension| match dimension {
+                    ArraySpecifierDimension::ExplicitlySized(index) => is_constant_expr(index),
+                    ArraySpecifierDimension::Unsized => true,
+                })
+        }
+        Expr::FunCall(FunIdentifier::Identifier(ident), args, _) => {
+            let name = ident.name.as_str();
+            (is_scalar_type_name(name)
+                || is_vector_type_name(name)
+                || is_matrix_type_name(name)
+                || is_builtin_function(name))
+                && args.iter().all(is_constant_expr)
+        }
+        Expr::FunCall(..) => false,
+    }
+}
+
+/// Check whether a type qualifier contains the `const` storage qualifier
+fn is_const(qualifier: &Option<TypeQualifier>) -> bool {
+    use glsl::syntax::{StorageQualifier, TypeQualifierSpec};
+
+    qualifier.as_ref().is_some_and(|q| {
+        q.qualifiers
+            .0
+            .iter()
+            .any(|spec| matches!(spec, TypeQualifierSpec::Storage(StorageQualifier::Const)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glsl::parser::Parse;
+
+    fn inline(source: &str) -> Result<TranslationUnit, GlslError> {
+        let shader = TranslationUnit::parse(source).unwrap();
+        let mut pass = ConstInliningPass::new();
+        pass.run(&shader, source)?;
+        Ok(pass.into_shader())
+    }
+
+    fn return_expr(shader: &TranslationUnit) -> Expr {
+        for decl in &shader.0 {
+            if let ExternalDeclaration::FunctionDefinition(func) = decl {
+                for stmt in &func.statement.statement_list {
+                    if let Statement::Simple(simple) = stmt
+                        && let SimpleStatement::Jump(JumpStatement::Return(Some(expr))) =
+                            simple.as_ref()
+                    {
+                        return (**expr).clone();
+                    }
+                }
+            }
+        }
+        panic!("no return statement");
+    }
+
+    #[test]
+    fn test_scalar_const_is_folded() {
+        let shader = inline(
+            "const float PI = 3.0 + 0.5;\n\
+             const float TAU = 2.0 * PI;\n\
+             float f() { return TAU; }",
+        )
+        .unwrap();
+        assert!(matches!(return_expr(&shader), Expr::FloatConst(v, _) if v == 7.0));
+    }
+
+    #[test]
+    fn test_int_const_converts_to_declared_type() {
+        let shader = inline("float f() { const float X = 2; return X; }").unwrap();
+        assert!(matches!(return_expr(&shader), Expr::FloatConst(v, _) if v == 2.0));
+    }
+
+    #[test]
+    fn test_shadowed_const_is_not_replaced() {
+        let shader = inline("const int N = 4;\nint f(int N) { return N; }").unwrap();
+        assert!(matches!(return_expr(&shader), Expr::Variable(..)));
+    }
+
+    #[test]
+    fn test_vector_const_is_replaced_by_initializer() {
+        let shader = inline("const vec2 V = vec2(1.0, 2.0);\nvec2 f() { return V; }").unwrap();
+        assert!(matches!(return_expr(&shader), Expr::FunCall(..)));
+    }
+
+    #[test]
+    fn test_assign_to_const_is_error() {
+        let error = inline("void f() { const int N = 1; N = 2; }").unwrap_err();
+        assert_eq!(error.code, ErrorCode::E0115);
+        assert!(error.message.contains("cannot assign to const `N`"));
+
+        let error = inline("const vec2 V = vec2(0.0);\nvoid f() { V.x++; }").unwrap_err();
+        assert_eq!(error.code, ErrorCode::E0115);
+    }
+
+    #[test]
+    fn test_const_requires_constant_initializer() {
+        let error = inline("void f(float x) { const float Y = x * 2.0; }").unwrap_err();
+        assert_eq!(error.code, ErrorCode::E0118);
+
+        let error = inline("void f() { const float Y; }").unwrap_err();
+        assert_eq!(error.code, ErrorCode::E0118);
+    }
+}
@@ -13,7 +13,11 @@ pub trait SemanticPass {
     fn name(&self) -> &str;
 }
 
+pub mod const_inlining;
 pub mod function_extraction;
 pub mod function_registry;
 pub mod function_signature;
+pub mod purity;
+pub mod uniform_collection;
+pub mod unused_variables;
 pub mod validation;
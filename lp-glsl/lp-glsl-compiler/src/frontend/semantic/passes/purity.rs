@@ -0,0 +1,409 @@
+//! Pass for finding functions whose result depends only on their arguments
+
+use crate::frontend::semantic::{
+    FRAME_UNIFORM_NAME, TIME_UNIFORM_NAME, TypedFunction, TypedShader,
+};
+use glsl::syntax::{
+    ArraySpecifierDimension, Condition, Declaration, Expr, ForInitStatement, FunIdentifier,
+    Identifier, Initializer, IterationStatement, JumpStatement, SelectionRestStatement,
+    SimpleStatement, Statement,
+};
+use hashbrown::HashSet;
+
+use alloc::{string::String, vec, vec::Vec};
+
+/// Which functions of a shader can have their output cached
+///
+/// A function is pure if it reads no uniforms (including the implicit `u_time` and
+/// `u_frame`) and, for `main`, not its time argument (the third, as in
+/// `vec4 main(vec2 fragCoord, vec2 outputSize, float time)`), and only calls pure
+/// functions, so calling it again with the same arguments gives the same result and
+/// its output can be cached. Builtins and `lpfx_` functions are pure; no host function
+/// with side effects can be called from GLSL.
+///
+/// A function that reads uniforms but not time is still only stale when a uniform
+/// changes, so it is tracked separately for callers that know when that happens.
+#[derive(Debug, Clone, Default)]
+pub struct Purity {
+    pure: HashSet<String>,
+    /// Functions that don't read time, directly or through calls
+    untimed: HashSet<String>,
+}
+
+impl Purity {
+    /// Whether `name` is a pure function (false for unknown functions)
+    pub fn is_pure(&self, name: &str) -> bool {
+        self.pure.contains(name)
+    }
+
+    /// Whether `name` reads `main`'s time argument, `u_time` or `u_frame`, directly or
+    /// through calls (true for unknown functions)
+    pub fn reads_time(&self, name: &str) -> bool {
+        !self.untimed.contains(name)
+    }
+}
+
+/// Finds the pure functions of a shader (see [`Purity`])
+pub struct PurityPass;
+
+impl PurityPass {
+    /// Find the pure and time-reading functions of `shader`
+    pub fn purity(&mut self, shader: &TypedShader) -> Purity {
+        let uniforms: HashSet<&str> = shader.uniforms.iter().map(|u| u.name.as_str()).collect();
+        let functions: Vec<&TypedFunction> = shader
+            .user_functions
+            .iter()
+            .chain(&shader.main_function)
+            .collect();
+        let mut effects: Vec<Effects> = shader
+            .user_functions
+            .iter()
+            .map(|func| function_effects(func, &uniforms, None))
+            .collect();
+        // Only `main` is passed the time
+        if let Some(main) = &shader.main_function {
+            let time_param = main.parameters.get(2).map(|param| param.name.as_str());
+            effects.push(function_effects(main, &uniforms, time_param));
+        }
+
+        Purity {
+            pure: without_effect(&functions, &effects, |effects| {
+                effects.reads_uniform || effects.reads_time
+            }),
+            untimed: without_effect(&functions, &effects, |effects| effects.reads_time),
+        }
+    }
+}
+
+/// Names of the functions that have no effect matching `has_effect`, directly or
+/// through the user functions they call
+fn without_effect(
+    functions: &[&TypedFunction],
+    effects: &[Effects],
+    has_effect: impl Fn(&Effects) -> bool,
+) -> HashSet<String> {
+    // The effect spreads to callers until nothing changes (GLSL has no recursion,
+    // so this takes at most one round per call depth)
+    let mut clean: HashSet<String> = functions
+        .iter()
+        .zip(effects)
+        .filter(|(_, effects)| !has_effect(effects))
+        .map(|(func, _)| func.name.clone())
+        .collect();
+    let user_names: HashSet<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+    loop {
+        let tainted: Vec<&str> = functions
+            .iter()
+            .zip(effects)
+            .filter(|(func, effects)| {
+                clean.contains(&func.name)
+                    && effects.calls.iter().any(|callee| {
+                        user_names.contains(callee.as_str()) && !clean.contains(callee)
+                    })
+            })
+            .map(|(func, _)| func.name.as_str())
+            .collect();
+        if tainted.is_empty() {
+            return clean;
+        }
+        for name in tainted {
+            clean.remove(name);
+        }
+    }
+}
+
+/// What a function body does besides computing from its arguments
+struct Effects {
+    reads_uniform: bool,
+    /// Reads `main`'s time argument or the implicit time/frame uniforms
+    reads_time: bool,
+    /// Names of the functions it calls (user functions and builtins alike)
+    calls: Vec<String>,
+}
+
+fn function_effects(
+    func: &TypedFunction,
+    uniforms: &HashSet<&str>,
+    time_param: Option<&str>,
+) -> Effects {
+    let mut walker = Walker {
+        uniforms,
+        time_param,
+        scopes: vec![func.parameters.iter().map(|p| p.name.as_str()).collect()],
+        effects: Effects {
+            reads_uniform: false,
+            reads_time: false,
+            calls: Vec::new(),
+        },
+    };
+    walker.statements(&func.body);
+    walker.effects
+}
+
+struct Walker<'a> {
+    uniforms: &'a HashSet<&'a str>,
+    /// Name of `main`'s time parameter, when walking `main`
+    time_param: Option<&'a str>,
+    /// Parameters (the first scope) and locals in each scope, which shadow uniforms of
+    /// the same name
+    scopes: Vec<Vec<&'a str>>,
+    effects: Effects,
+}
+
+impl<'a> Walker<'a> {
+    fn declare(&mut self, ident: &'a Identifier) {
+        self.scopes
+            .last_mut()
+            .expect("walker always has a function scope")
+            .push(ident.name.as_str());
+    }
+
+    /// Run `f` in a fresh scope
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(Vec::new());
+        f(self);
+        self.scopes.pop();
+    }
+
+    fn read(&mut self, name: &str) {
+        match self.scopes.iter().rposition(|scope| scope.contains(&name)) {
+            // A parameter, not shadowed by a local
+            Some(0) => {
+                if self.time_param == Some(name) {
+                    self.effects.reads_time = true;
+                }
+            }
+            Some(_) => {}
+            None => {
+                if self.uniforms.contains(name) {
+                    self.effects.reads_uniform = true;
+                    if name == TIME_UNIFORM_NAME || name == FRAME_UNIFORM_NAME {
+                        self.effects.reads_time = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn statements(&mut self, stmts: &'a [Statement]) {
+        for stmt in stmts {
+            self.statement(stmt);
+        }
+    }
+
+    fn statement(&mut self, stmt: &'a Statement) {
+        match stmt {
+            Statement::Compound(compound) => {
+                self.scoped(|this| this.statements(&compound.statement_list))
+            }
+            Statement::Simple(simple) => self.simple_statement(simple),
+        }
+    }
+
+    fn simple_statement(&mut self, stmt: &'a SimpleStatement) {
+        match stmt {
+            SimpleStatement::Declaration(decl) => self.declaration(decl),
+            SimpleStatement::Expression(Some(expr)) => self.expr(expr),
+            SimpleStatement::Selection(selection) => {
+                self.expr(&selection.cond);
+                match &selection.rest {
+                    SelectionRestStatement::Statement(then_stmt) => {
+                        self.scoped(|this| this.statement(then_stmt));
+                    }
+                    SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                        self.scoped(|this| this.statement(then_stmt));
+                        self.scoped(|this| this.statement(else_stmt));
+                    }
+                }
+            }
+            SimpleStatement::Iteration(iteration) => {
+                self.scoped(|this| this.iteration(iteration));
+            }
+            SimpleStatement::Jump(JumpStatement::Return(Some(expr))) => self.expr(expr),
+            _ => {}
+        }
+    }
+
+    fn iteration(&mut self, iteration: &'a IterationStatement) {
+        match iteration {
+            IterationStatement::While(condition, body) => {
+                self.condition(condition);
+                self.statement(body);
+            }
+            IterationStatement::DoWhile(body, cond) => {
+                self.statement(body);
+                self.expr(cond);
+            }
+            IterationStatement::For(init, rest, body) => {
+                match init {
+                    ForInitStatement::Declaration(decl) => self.declaration(decl),
+                    ForInitStatement::Expression(Some(expr)) => self.expr(expr),
+                    ForInitStatement::Expression(None) => {}
+                }
+                if let Some(condition) = &rest.condition {
+                    self.condition(condition);
+                }
+                if let Some(post_expr) = &rest.post_expr {
+                    self.expr(post_expr);
+                }
+                self.statement(body);
+            }
+        }
+    }
+
+    fn condition(&mut self, condition: &'a Condition) {
+        match condition {
+            Condition::Expr(expr) => self.expr(expr),
+            Condition::Assignment(_, ident, init) => {
+                self.initializer(init);
+                self.declare(ident);
+            }
+        }
+    }
+
+    fn declaration(&mut self, decl: &'a Declaration) {
+        let Declaration::InitDeclaratorList(list) = decl else {
+            return;
+        };
+        // Initializers come first: in `float u_time = u_time;` the right side is the uniform
+        if let Some(init) = &list.head.initializer {
+            self.initializer(init);
+        }
+        if let Some(name) = &list.head.name {
+            self.declare(name);
+        }
+        for declarator in &list.tail {
+            if let Some(init) = &declarator.initializer {
+                self.initializer(init);
+            }
+            self.declare(&declarator.ident.ident);
+        }
+    }
+
+    fn initializer(&mut self, init: &'a Initializer) {
+        match init {
+            Initializer::Simple(expr) => self.expr(expr),
+            Initializer::List(list) => {
+                for init in &list.0 {
+                    self.initializer(init);
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Variable(ident, _) => self.read(&ident.name),
+            Expr::IntConst(..)
+            | Expr::UIntConst(..)
+            | Expr::FloatConst(..)
+            | Expr::DoubleConst(..)
+            | Expr::BoolConst(..) => {}
+            Expr::Unary(_, operand, _)
+            | Expr::Dot(operand, _, _)
+            | Expr::PostInc(operand, _)
+            | Expr::PostDec(operand, _) => self.expr(operand),
+            Expr::Binary(_, lhs, rhs, _)
+            | Expr::Assignment(lhs, _, rhs, _)
+            | Expr::Comma(lhs, rhs, _) => {
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            Expr::Ternary(cond, then_expr, else_expr, _) => {
+                self.expr(cond);
+                self.expr(then_expr);
+                self.expr(else_expr);
+            }
+            Expr::Bracket(array, spec, _) => {
+                self.expr(array);
+                for dimension in &spec.dimensions.0 {
+                    if let ArraySpecifierDimension::ExplicitlySized(index) = dimension {
+                        self.expr(index);
+                    }
+                }
+            }
+            Expr::FunCall(func_ident, args, _) => {
+                if let FunIdentifier::Identifier(ident) = func_ident {
+                    self.effects.calls.push(ident.name.clone());
+                }
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frontend::pipeline::CompilationPipeline;
+
+    fn purity(source: &str) -> super::Purity {
+        CompilationPipeline::parse_and_analyze(source)
+            .unwrap()
+            .typed_ast
+            .purity
+    }
+
+    fn is_pure(source: &str, name: &str) -> bool {
+        purity(source).is_pure(name)
+    }
+
+    #[test]
+    fn test_arguments_only_is_pure() {
+        let source = "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {\n    vec2 uv = fragCoord / outputSize;\n    return vec4(uv, sin(uv.x), 1.0);\n}";
+        assert!(is_pure(source, "main"));
+    }
+
+    #[test]
+    fn test_reading_time_is_impure() {
+        let source = "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {\n    return vec4(fragCoord / outputSize, sin(u_time), 1.0);\n}";
+        assert!(!is_pure(source, "main"));
+    }
+
+    #[test]
+    fn test_reading_time_argument_is_impure() {
+        let source = "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {\n    return vec4(fragCoord / outputSize, sin(time), 1.0);\n}";
+        assert!(!is_pure(source, "main"));
+        assert!(purity(source).reads_time("main"));
+
+        // The argument is found by position, whatever it is named
+        let source = "vec4 main(vec2 fragCoord, vec2 outputSize, float t) {\n    return vec4(fract(t), 0.0, 0.0, 1.0);\n}";
+        assert!(purity(source).reads_time("main"));
+    }
+
+    #[test]
+    fn test_reading_frame_reads_time() {
+        let source = "vec4 main(vec2 fragCoord, vec2 outputSize, float time) {\n    return vec4(float(u_frame % 2), 0.0, 0.0, 1.0);\n}";
+        assert!(purity(source).reads_time("main"));
+    }
+
+    #[test]
+    fn test_reading_uniform_is_impure_but_untimed() {
+        let source = "uniform float level;\nvec4 main(vec2 fragCoord, vec2 outputSize, float time) {\n    return vec4(level, 0.0, 0.0, 1.0);\n}";
+        assert!(!is_pure(source, "main"));
+        assert!(!purity(source).reads_time("main"));
+    }
+
+    #[test]
+    fn test_time_spreads_to_callers() {
+        let source = "float wave(float x) {\n    return sin(x + u_time);\n}\nvec4 main(vec2 fragCoord, vec2 outputSize, float time) {\n    return vec4(wave(fragCoord.x), 0.0, 0.0, 1.0);\n}";
+        assert!(purity(source).reads_time("wave"));
+        assert!(purity(source).reads_time("main"));
+    }
+
+    #[test]
+    fn test_impurity_spreads_to_callers() {
+        let source = "uniform float speed;\nfloat wave(float x) {\n    return sin(x * speed);\n}\nfloat twice(float x) {\n    return wave(x) * 2.0;\n}\nfloat square(float x) {\n    return x * x;\n}\nfloat main() {\n    return square(3.0);\n}";
+        assert!(!is_pure(source, "wave"));
+        assert!(!is_pure(source, "twice"));
+        assert!(is_pure(source, "square"));
+        assert!(is_pure(source, "main"));
+    }
+
+    #[test]
+    fn test_local_shadows_uniform() {
+        let source = "float main() {\n    float u_time = 2.0;\n    return u_time;\n}";
+        assert!(is_pure(source, "main"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Pass for collecting `uniform` declarations from the AST
+
+use super::SemanticPass;
+use crate::error::{ErrorCode, GlslError, source_span_to_location};
+use crate::frontend::semantic::type_resolver;
+use crate::frontend::semantic::types::Type;
+use crate::frontend::semantic::{FRAME_UNIFORM_NAME, TIME_UNIFORM_NAME, UniformDecl};
+
+use alloc::{format, string::String, vec::Vec};
+
+pub struct UniformCollectionPass {
+    uniforms: Vec<UniformDecl>,
+}
+
+impl UniformCollectionPass {
+    pub fn new() -> Self {
+        Self {
+            uniforms: Vec::new(),
+        }
+    }
+
+    /// Finish the pass, adding the implicit uniforms every shader can read
+    /// (unless the shader declares them itself)
+    pub fn into_uniforms(mut self) -> Vec<UniformDecl> {
+        for (name, ty) in [
+            (TIME_UNIFORM_NAME, Type::Float),
+            (FRAME_UNIFORM_NAME, Type::Int),
+        ] {
+            if !self.uniforms.iter().any(|u| u.name == name) {
+                self.uniforms.push(UniformDecl {
+                    name: String::from(name),
+                    ty,
+                    implicit: true,
+                });
+            }
+        }
+        self.uniforms
+    }
+
+    fn declare(
+        &mut self,
+        name: &glsl::syntax::Identifier,
+        ty: Type,
+        has_initializer: bool,
+    ) -> Result<(), GlslError> {
+        let location = source_span_to_location(&name.span);
+
+        // Uniform values live in a flat block of 32-bit components, so only
+        // numeric scalars and vectors can be laid out
+        if !ty.is_numeric() || !(ty.is_scalar() || ty.is_vector()) {
+            return Err(GlslError::new(
+                ErrorCode::E0109,
+                format!("uniform `{}` has unsupported type {:?}", name.name, ty),
+            )
+            .with_location(location));
+        }
+
+        if has_initializer {
+            return Err(GlslError::new(
+                ErrorCode::E0109,
+                format!("uniform `{}` cannot have an initializer", name.name),
+            )
+            .with_location(location));
+        }
+
+        if self.uniforms.iter().any(|u| u.name == name.name) {
+            return Err(GlslError::new(
+                ErrorCode::E0400,
+                format!("uniform `{}` already declared", name.name),
+            )
+            .with_location(location));
+        }
+
+        self.uniforms.push(UniformDecl {
+            name: name.name.clone(),
+            ty,
+            implicit: false,
+        });
+        Ok(())
+    }
+}
+
+impl SemanticPass for UniformCollectionPass {
+    fn run(
+        &mut self,
+        shader: &glsl::syntax::TranslationUnit,
+        _source: &str,
+    ) -> Result<(), GlslError> {
+        use glsl::syntax::{Declaration, ExternalDeclaration};
+
+        for decl in &shader.0 {
+            let ExternalDeclaration::Declaration(Declaration::InitDeclaratorList(list)) = decl
+            else {
+                continue;
+            };
+            if !is_uniform(&list.head.ty.qualifier) {
+                continue;
+            }
+
+            if let Some(name) = &list.head.name {
+                let ty = type_resolver::parse_head_declarator_type(list, &name.span)?;
+                self.declare(name, ty, list.head.initializer.is_some())?;
+            }
+
+            let base_ty = type_resolver::parse_return_type(&list.head.ty, None)?;
+            for declarator in &list.tail {
+                let ty = type_resolver::parse_tail_declarator_type(&base_ty, declarator)?;
+                self.declare(
+                    &declarator.ident.ident,
+                    ty,
+                    declarator.initializer.is_some(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        "uniform_collection"
+    }
+}
+
+/// Check whether a type qualifier contains the `uniform` storage qualifier
+fn is_uniform(qualifier: &Option<glsl::syntax::TypeQualifier>) -> bool {
+    use glsl::syntax::{StorageQualifier, TypeQualifierSpec};
+
+    qualifier.as_ref().is_some_and(|q| {
+        q.qualifiers
+            .0
+            .iter()
+            .any(|spec| matches!(spec, TypeQualifierSpec::Storage(StorageQualifier::Uniform)))
+    })
+}
@@ -0,0 +1,304 @@
+//! Pass for warning about local variables that are never used
+
+use crate::error::{
+    Diagnostic, ErrorCode, GlslError, add_span_text_to_error, source_span_to_location,
+};
+use crate::frontend::semantic::{TypedFunction, TypedShader};
+use glsl::syntax::{
+    ArraySpecifierDimension, Condition, Declaration, Expr, ForInitStatement, Identifier,
+    Initializer, IterationStatement, JumpStatement, SelectionRestStatement, SimpleStatement,
+    Statement,
+};
+
+use alloc::{format, vec, vec::Vec};
+
+/// Warns about locals that are declared but never read or written
+///
+/// Like validation, this runs on the TypedShader rather than the TranslationUnit.
+/// Parameters aren't checked (`main` has to declare inputs it may not use), and
+/// names starting with `_` are skipped so a variable can be kept on purpose.
+pub struct UnusedVariablePass;
+
+impl UnusedVariablePass {
+    /// Collect a warning for each unused local, in source order per function
+    pub fn check(&mut self, shader: &TypedShader, source: &str) -> Vec<Diagnostic> {
+        let mut warnings = Vec::new();
+        for func in shader.user_functions.iter().chain(&shader.main_function) {
+            warnings.extend(check_function(func, source));
+        }
+        warnings
+    }
+}
+
+fn check_function(func: &TypedFunction, source: &str) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        scopes: vec![Vec::new()],
+        unused: Vec::new(),
+    };
+    checker.statements(&func.body);
+    checker.pop_scope();
+    checker
+        .unused
+        .sort_by_key(|ident| (ident.span.line, ident.span.column));
+
+    checker
+        .unused
+        .into_iter()
+        .map(|ident| {
+            let error = GlslError::new(
+                ErrorCode::W0001,
+                format!("unused variable `{}`", ident.name),
+            )
+            .with_location(source_span_to_location(&ident.span))
+            .with_note(format!(
+                "if this is intentional, prefix it with an underscore: `_{}`",
+                ident.name
+            ));
+            Diagnostic::warning(add_span_text_to_error(error, Some(source), &ident.span))
+        })
+        .collect()
+}
+
+/// A local declared in the current function
+struct Local<'a> {
+    ident: &'a Identifier,
+    used: bool,
+}
+
+struct Checker<'a> {
+    /// Locals declared in each scope, outermost first
+    scopes: Vec<Vec<Local<'a>>>,
+    /// Locals that went out of scope without being used
+    unused: Vec<&'a Identifier>,
+}
+
+impl<'a> Checker<'a> {
+    fn declare(&mut self, ident: &'a Identifier) {
+        self.scopes
+            .last_mut()
+            .expect("checker always has a function scope")
+            .push(Local { ident, used: false });
+    }
+
+    fn pop_scope(&mut self) {
+        let scope = self.scopes.pop().expect("scope push/pop mismatch");
+        self.unused.extend(
+            scope
+                .into_iter()
+                .filter(|local| !local.used && !local.ident.name.starts_with('_'))
+                .map(|local| local.ident),
+        );
+    }
+
+    /// Run `f` in a fresh scope
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.scopes.push(Vec::new());
+        f(self);
+        self.pop_scope();
+    }
+
+    fn mark_used(&mut self, name: &str) {
+        // Parameters and globals aren't tracked, so an unknown name is fine
+        if let Some(local) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.iter_mut().rev().find(|l| l.ident.name == name))
+        {
+            local.used = true;
+        }
+    }
+
+    fn statements(&mut self, stmts: &'a [Statement]) {
+        for stmt in stmts {
+            self.statement(stmt);
+        }
+    }
+
+    fn statement(&mut self, stmt: &'a Statement) {
+        match stmt {
+            Statement::Compound(compound) => {
+                self.scoped(|this| this.statements(&compound.statement_list))
+            }
+            Statement::Simple(simple) => self.simple_statement(simple),
+        }
+    }
+
+    fn simple_statement(&mut self, stmt: &'a SimpleStatement) {
+        match stmt {
+            SimpleStatement::Declaration(decl) => self.declaration(decl),
+            SimpleStatement::Expression(Some(expr)) => self.expr(expr),
+            SimpleStatement::Selection(selection) => {
+                self.expr(&selection.cond);
+                match &selection.rest {
+                    SelectionRestStatement::Statement(then_stmt) => {
+                        self.scoped(|this| this.statement(then_stmt));
+                    }
+                    SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                        self.scoped(|this| this.statement(then_stmt));
+                        self.scoped(|this| this.statement(else_stmt));
+                    }
+                }
+            }
+            SimpleStatement::Iteration(iteration) => {
+                self.scoped(|this| this.iteration(iteration));
+            }
+            SimpleStatement::Jump(JumpStatement::Return(Some(expr))) => self.expr(expr),
+            _ => {}
+        }
+    }
+
+    fn iteration(&mut self, iteration: &'a IterationStatement) {
+        match iteration {
+            IterationStatement::While(condition, body) => {
+                self.condition(condition);
+                self.statement(body);
+            }
+            IterationStatement::DoWhile(body, cond) => {
+                self.statement(body);
+                self.expr(cond);
+            }
+            IterationStatement::For(init, rest, body) => {
+                match init {
+                    ForInitStatement::Declaration(decl) => self.declaration(decl),
+                    ForInitStatement::Expression(Some(expr)) => self.expr(expr),
+                    ForInitStatement::Expression(None) => {}
+                }
+                if let Some(condition) = &rest.condition {
+                    self.condition(condition);
+                }
+                if let Some(post_expr) = &rest.post_expr {
+                    self.expr(post_expr);
+                }
+                self.statement(body);
+            }
+        }
+    }
+
+    fn condition(&mut self, condition: &'a Condition) {
+        match condition {
+            Condition::Expr(expr) => self.expr(expr),
+            Condition::Assignment(_, ident, init) => {
+                self.initializer(init);
+                self.declare(ident);
+            }
+        }
+    }
+
+    fn declaration(&mut self, decl: &'a Declaration) {
+        let Declaration::InitDeclaratorList(list) = decl else {
+            return;
+        };
+        // Initializers are checked first: in `float x = x;` the right side is an outer `x`
+        if let Some(init) = &list.head.initializer {
+            self.initializer(init);
+        }
+        if let Some(name) = &list.head.name {
+            self.declare(name);
+        }
+        for declarator in &list.tail {
+            if let Some(init) = &declarator.initializer {
+                self.initializer(init);
+            }
+            self.declare(&declarator.ident.ident);
+        }
+    }
+
+    fn initializer(&mut self, init: &'a Initializer) {
+        match init {
+            Initializer::Simple(expr) => self.expr(expr),
+            Initializer::List(list) => {
+                for init in &list.0 {
+                    self.initializer(init);
+                }
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &'a Expr) {
+        match expr {
+            Expr::Variable(ident, _) => self.mark_used(&ident.name),
+            Expr::IntConst(..)
+            | Expr::UIntConst(..)
+            | Expr::FloatConst(..)
+            | Expr::DoubleConst(..)
+            | Expr::BoolConst(..) => {}
+            Expr::Unary(_, operand, _)
+            | Expr::Dot(operand, _, _)
+            | Expr::PostInc(operand, _)
+            | Expr::PostDec(operand, _) => self.expr(operand),
+            Expr::Binary(_, lhs, rhs, _)
+            | Expr::Assignment(lhs, _, rhs, _)
+            | Expr::Comma(lhs, rhs, _) => {
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            Expr::Ternary(cond, then_expr, else_expr, _) => {
+                self.expr(cond);
+                self.expr(then_expr);
+                self.expr(else_expr);
+            }
+            Expr::Bracket(array, spec, _) => {
+                self.expr(array);
+                for dimension in &spec.dimensions.0 {
+                    if let ArraySpecifierDimension::ExplicitlySized(index) = dimension {
+                        self.expr(index);
+                    }
+                }
+            }
+            Expr::FunCall(_, args, _) => {
+                for arg in args {
+                    self.expr(arg);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::pipeline::CompilationPipeline;
+    use alloc::string::String;
+
+    fn unused(source: &str) -> Vec<String> {
+        let shader = CompilationPipeline::parse_and_analyze(source)
+            .unwrap()
+            .typed_ast;
+        shader
+            .warnings
+            .iter()
+            .map(|warning| warning.details.message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_unused_local() {
+        let warnings = unused(
+            "int main() {\n    int a = 1;\n    int b = 2;\n    int c;\n    c = a;\n    return c;\n}",
+        );
+        assert_eq!(warnings, ["unused variable `b`"]);
+    }
+
+    #[test]
+    fn test_used_in_nested_scopes() {
+        let warnings = unused(
+            "float main() {\n    float sum = 0.0;\n    for (int i = 0; i < 4; i++) {\n        float x = float(i);\n        if (x > 1.0) { sum += x; }\n    }\n    return sum;\n}",
+        );
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+    }
+
+    #[test]
+    fn test_shadowed_local() {
+        let warnings = unused(
+            "int main() {\n    int a = 1;\n    int b = 0;\n    {\n        int a = 2;\n        b = a;\n    }\n    return b;\n}",
+        );
+        assert_eq!(warnings, ["unused variable `a`"]);
+    }
+
+    #[test]
+    fn test_underscore_and_parameters_skipped() {
+        let warnings = unused("int f(int unused_param) {\n    int _scratch = 1;\n    return 0;\n}");
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+    }
+}
@@ -14,11 +14,21 @@ impl ValidationPass {
         // Validate all functions (third pass logic)
         // Use the registry from the typed shader
         for func in &shader.user_functions {
-            validator::validate_function(func, &shader.function_registry, source)?;
+            validator::validate_function(
+                func,
+                &shader.uniforms,
+                &shader.function_registry,
+                source,
+            )?;
         }
         // Validate main function if present (optional for filetests)
         if let Some(ref main_function) = shader.main_function {
-            validator::validate_function(main_function, &shader.function_registry, source)?;
+            validator::validate_function(
+                main_function,
+                &shader.uniforms,
+                &shader.function_registry,
+                source,
+            )?;
         }
         Ok(())
     }
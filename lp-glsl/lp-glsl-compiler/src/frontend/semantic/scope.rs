@@ -21,7 +21,8 @@ pub struct VarDecl {
 #[derive(Debug, Clone, Copy)]
 pub enum StorageClass {
     Local,
-    // Future: Uniform, In, Out
+    Uniform,
+    // Future: In, Out
 }
 
 impl SymbolTable {
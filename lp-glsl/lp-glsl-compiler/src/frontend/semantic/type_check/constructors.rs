@@ -5,7 +5,7 @@ use crate::error::{ErrorCode, GlslError, source_span_to_location};
 use crate::frontend::semantic::types::Type;
 use glsl::syntax::SourceSpan;
 
-use super::conversion::can_implicitly_convert;
+use super::conversion::can_explicitly_convert;
 
 use alloc::format;
 
@@ -43,13 +43,13 @@ pub fn check_vector_constructor_with_span(
     // Case 1: Single scalar - broadcast to all components
     if args.len() == 1 && args[0].is_scalar() {
         // Check implicit conversion is allowed
-        if !can_implicitly_convert(&args[0], &base_type) {
+        if !can_explicitly_convert(&args[0], &base_type) {
             return Err(add_location(
                 GlslError::new(
                     ErrorCode::E0103,
                     format!("cannot construct `{}` from `{:?}`", type_name, args[0]),
                 )
-                .with_note("type cannot be implicitly converted"),
+                .with_note("type cannot be converted"),
             ));
         }
         return Ok(result_type);
@@ -62,39 +62,58 @@ pub fn check_vector_constructor_with_span(
         if src_component_count < component_count {
             return Err(add_location(
                 GlslError::new(
-                    ErrorCode::E0115,
-                    format!("cannot construct `{}` from `{:?}`", type_name, args[0]),
+                    ErrorCode::E0110,
+                    format!(
+                        "`{type_name}` constructor expects {component_count} components, found {src_component_count}"
+                    ),
                 )
                 .with_note(format!(
-                    "expected at least {component_count} components, found {src_component_count}"
+                    "a single `{:?}` argument can only construct vectors of up to {src_component_count} components",
+                    args[0]
                 )),
             ));
         }
         // Check base type conversion is allowed
         let src_base = args[0].vector_base_type().unwrap();
-        if !can_implicitly_convert(&src_base, &base_type) {
+        if !can_explicitly_convert(&src_base, &base_type) {
             return Err(add_location(
                 GlslError::new(
                     ErrorCode::E0103,
                     format!("cannot construct `{}` from `{:?}`", type_name, args[0]),
                 )
-                .with_note("component type cannot be implicitly converted"),
+                .with_note("component type cannot be converted"),
             ));
         }
         return Ok(result_type);
     }
 
     // Case 3: Multiple arguments - concatenation
-    let total_components = count_total_components(args)?;
-    if total_components != component_count {
+    // Components are consumed in order; the last argument may be only partially
+    // used, but every argument must contribute at least one component.
+    let mut total_components = 0;
+    for (i, arg) in args.iter().enumerate() {
+        if total_components >= component_count {
+            return Err(add_location(
+                GlslError::new(
+                    ErrorCode::E0110,
+                    format!("too many arguments to `{type_name}` constructor"),
+                )
+                .with_note(format!(
+                    "all {component_count} components are provided by the first {i} arguments"
+                )),
+            ));
+        }
+        total_components += constructor_arg_components(arg).map_err(&add_location)?;
+    }
+    if total_components < component_count {
         return Err(add_location(
             GlslError::new(
-                ErrorCode::E0115,
-                format!("`{type_name}` constructor has wrong number of components"),
+                ErrorCode::E0110,
+                format!(
+                    "`{type_name}` constructor expects {component_count} components, found {total_components}"
+                ),
             )
-            .with_note(format!(
-                "expected {component_count} components, found {total_components}"
-            )),
+            .with_note("provide a scalar to fill every component, or enough components in total"),
         ));
     }
 
@@ -106,13 +125,13 @@ pub fn check_vector_constructor_with_span(
             arg.clone()
         };
 
-        if !can_implicitly_convert(&arg_base, &base_type) {
+        if !can_explicitly_convert(&arg_base, &base_type) {
             return Err(add_location(
                 GlslError::new(
                     ErrorCode::E0103,
                     format!("cannot use `{arg:?}` in `{type_name}` constructor"),
                 )
-                .with_note("component type cannot be implicitly converted"),
+                .with_note("component type cannot be converted"),
             ));
         }
     }
@@ -138,21 +157,19 @@ fn parse_vector_type_name(name: &str) -> Result<Type, GlslError> {
     }
 }
 
-fn count_total_components(args: &[Type]) -> Result<usize, GlslError> {
-    let mut total = 0;
-    for arg in args {
-        if let Some(count) = arg.component_count() {
-            total += count;
-        } else if arg.is_scalar() {
-            total += 1;
-        } else {
-            return Err(GlslError::new(
-                ErrorCode::E0112,
-                format!("invalid constructor argument: `{arg:?}`"),
-            ));
-        }
+/// Number of components a vector constructor argument contributes
+fn constructor_arg_components(arg: &Type) -> Result<usize, GlslError> {
+    if let Some(count) = arg.component_count() {
+        Ok(count)
+    } else if arg.is_scalar() {
+        Ok(1)
+    } else {
+        Err(GlslError::new(
+            ErrorCode::E0110,
+            format!("invalid vector constructor argument: `{arg:?}`"),
+        )
+        .with_note("vector constructor arguments must be scalars or vectors"))
     }
-    Ok(total)
 }
 
 /// Check if a name is a vector type constructor
@@ -255,7 +272,7 @@ pub fn check_matrix_constructor(type_name: &str, args: &[Type]) -> Result<Type,
 
     // Case 1: Single scalar - identity matrix (diagonal = scalar, rest = 0.0)
     if args.len() == 1 && args[0].is_scalar() {
-        if !can_implicitly_convert(&args[0], &Type::Float) {
+        if !can_explicitly_convert(&args[0], &Type::Float) {
             return Err(GlslError::new(
                 ErrorCode::E0103,
                 format!("cannot construct `{}` from `{:?}`", type_name, args[0]),
@@ -290,7 +307,7 @@ pub fn check_matrix_constructor(type_name: &str, args: &[Type]) -> Result<Type,
                 }
                 // Check base type can convert to float
                 let arg_base = arg.vector_base_type().unwrap();
-                if !can_implicitly_convert(&arg_base, &Type::Float) {
+                if !can_explicitly_convert(&arg_base, &Type::Float) {
                     return Err(GlslError::new(
                         ErrorCode::E0103,
                         format!("matrix column {i} has incompatible base type: `{arg_base:?}`"),
@@ -322,7 +339,7 @@ pub fn check_matrix_constructor(type_name: &str, args: &[Type]) -> Result<Type,
         }
         if all_scalars {
             for (i, arg) in args.iter().enumerate() {
-                if !can_implicitly_convert(arg, &Type::Float) {
+                if !can_explicitly_convert(arg, &Type::Float) {
                     return Err(GlslError::new(
                         ErrorCode::E0103,
                         format!("matrix element {i} cannot be converted to float: `{arg:?}`"),
@@ -352,7 +369,7 @@ pub fn check_matrix_constructor(type_name: &str, args: &[Type]) -> Result<Type,
                 ));
             };
 
-            if !can_implicitly_convert(&arg_base, &Type::Float) {
+            if !can_explicitly_convert(&arg_base, &Type::Float) {
                 return Err(GlslError::new(
                     ErrorCode::E0103,
                     format!(
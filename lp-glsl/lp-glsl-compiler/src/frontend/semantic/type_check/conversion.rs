@@ -5,7 +5,7 @@ use crate::error::{ErrorCode, GlslError, source_span_to_location};
 use crate::frontend::semantic::types::Type;
 use glsl::syntax::SourceSpan;
 
-use alloc::format;
+use alloc::{format, string::String};
 
 /// Promote numeric types (GLSL spec implicit conversion rules)
 /// Implements GLSL spec: variables.adoc:1182-1229
@@ -24,7 +24,32 @@ pub fn promote_numeric(lhs: &Type, rhs: &Type) -> Type {
     }
 }
 
+/// Promote scalar or vector operands to their common type
+///
+/// Scalars and vectors promote by base type (e.g. `ivec3` and `float` give `vec3`).
+/// Returns `None` if the operands are not numeric or vector sizes differ.
+pub fn promote_operands(lhs: &Type, rhs: &Type) -> Option<Type> {
+    let is_operand = |ty: &Type| ty.is_numeric() && (ty.is_scalar() || ty.is_vector());
+    if !is_operand(lhs) || !is_operand(rhs) {
+        return None;
+    }
+    let lhs_base = lhs.vector_base_type().unwrap_or_else(|| lhs.clone());
+    let rhs_base = rhs.vector_base_type().unwrap_or_else(|| rhs.clone());
+    let base = promote_numeric(&lhs_base, &rhs_base);
+    match (lhs.component_count(), rhs.component_count()) {
+        (None, None) => Some(base),
+        (Some(count), None) | (None, Some(count)) => Type::vector_type(&base, count),
+        (Some(lhs_count), Some(rhs_count)) if lhs_count == rhs_count => {
+            Type::vector_type(&base, lhs_count)
+        }
+        _ => None,
+    }
+}
+
 /// Check if implicit conversion is allowed (GLSL spec: variables.adoc:1182-1229)
+///
+/// Only widening conversions are implicit: `int` → `uint`, and `int`/`uint` → `float`,
+/// applied component-wise to vectors. Everything else needs an explicit constructor.
 pub fn can_implicitly_convert(from: &Type, to: &Type) -> bool {
     // Exact match always allowed
     if from == to {
@@ -32,39 +57,39 @@ pub fn can_implicitly_convert(from: &Type, to: &Type) -> bool {
     }
 
     // Scalar conversions
-    if matches!((from, to), (Type::Int, Type::Float)) {
-        return true;
-    }
-    // Float to int conversion (for constructors: truncates toward zero)
-    if matches!((from, to), (Type::Float, Type::Int)) {
-        return true;
-    }
-    // int ↔ uint conversions (bit pattern preserved)
     if matches!(
         (from, to),
-        (Type::Int, Type::UInt) | (Type::UInt, Type::Int)
+        (Type::Int, Type::UInt) | (Type::Int, Type::Float) | (Type::UInt, Type::Float)
     ) {
         return true;
     }
-    // uint ↔ float conversions
-    if matches!(
-        (from, to),
-        (Type::UInt, Type::Float) | (Type::Float, Type::UInt)
+
+    // Vector conversions: same size, implicitly convertible base types
+    if let (Some(from_base), Some(to_base), Some(from_count), Some(to_count)) = (
+        from.vector_base_type(),
+        to.vector_base_type(),
+        from.component_count(),
+        to.component_count(),
     ) {
-        return true;
+        if from_count == to_count {
+            return can_implicitly_convert(&from_base, &to_base);
+        }
     }
-    // Numeric to bool conversions (for constructors: 0/0.0 → false, non-zero → true)
-    if matches!(
-        (from, to),
-        (Type::Int, Type::Bool) | (Type::UInt, Type::Bool) | (Type::Float, Type::Bool)
-    ) {
+
+    false
+}
+
+/// Check if explicit conversion is allowed, as performed by constructors
+/// (GLSL spec: variables.adoc:72-97)
+pub fn can_explicitly_convert(from: &Type, to: &Type) -> bool {
+    if can_implicitly_convert(from, to) {
         return true;
     }
-    // Bool to numeric conversions (for constructors: false → 0/0.0, true → 1/1.0)
-    if matches!(
-        (from, to),
-        (Type::Bool, Type::Int) | (Type::Bool, Type::UInt) | (Type::Bool, Type::Float)
-    ) {
+
+    // Any scalar type converts to any other scalar type in a constructor:
+    // float → int truncates toward zero, numeric → bool tests for non-zero,
+    // bool → numeric gives 0/1
+    if from.is_scalar() && to.is_scalar() {
         return true;
     }
 
@@ -78,7 +103,7 @@ pub fn can_implicitly_convert(from: &Type, to: &Type) -> bool {
         }
     }
 
-    // Vector conversions: same size, compatible base types
+    // Vector conversions: same size, convertible base types
     if let (Some(from_base), Some(to_base), Some(from_count), Some(to_count)) = (
         from.vector_base_type(),
         to.vector_base_type(),
@@ -86,13 +111,47 @@ pub fn can_implicitly_convert(from: &Type, to: &Type) -> bool {
         to.component_count(),
     ) {
         if from_count == to_count {
-            return can_implicitly_convert(&from_base, &to_base);
+            return can_explicitly_convert(&from_base, &to_base);
         }
     }
 
     false
 }
 
+/// Suggest an explicit conversion when `from` only converts to `to` explicitly
+pub fn explicit_conversion_hint(from: &Type, to: &Type) -> Option<String> {
+    if can_implicitly_convert(from, to) || !can_explicitly_convert(from, to) {
+        return None;
+    }
+    let constructor = constructor_name(to)?;
+    Some(format!(
+        "help: `{from:?}` does not implicitly convert to `{to:?}`; use an explicit conversion such as `{constructor}(...)`"
+    ))
+}
+
+/// GLSL constructor name for scalar and vector types
+fn constructor_name(ty: &Type) -> Option<&'static str> {
+    Some(match ty {
+        Type::Bool => "bool",
+        Type::Int => "int",
+        Type::UInt => "uint",
+        Type::Float => "float",
+        Type::Vec2 => "vec2",
+        Type::Vec3 => "vec3",
+        Type::Vec4 => "vec4",
+        Type::IVec2 => "ivec2",
+        Type::IVec3 => "ivec3",
+        Type::IVec4 => "ivec4",
+        Type::UVec2 => "uvec2",
+        Type::UVec3 => "uvec3",
+        Type::UVec4 => "uvec4",
+        Type::BVec2 => "bvec2",
+        Type::BVec3 => "bvec3",
+        Type::BVec4 => "bvec4",
+        _ => return None,
+    })
+}
+
 /// Validate assignment types
 pub fn check_assignment(lhs_ty: &Type, rhs_ty: &Type) -> Result<(), GlslError> {
     check_assignment_with_span(lhs_ty, rhs_ty, None)
@@ -109,7 +168,10 @@ pub fn check_assignment_with_span(
             .with_note(format!(
                 "cannot assign value of type `{rhs_ty:?}` to variable of type `{lhs_ty:?}`"
             ))
-            .with_note("help: consider using an explicit type conversion");
+            .with_note(
+                explicit_conversion_hint(rhs_ty, lhs_ty)
+                    .unwrap_or_else(|| "help: consider using an explicit type conversion".into()),
+            );
 
         if let Some(span) = span {
             error = error.with_location(source_span_to_location(&span));
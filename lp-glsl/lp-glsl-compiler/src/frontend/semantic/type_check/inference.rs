@@ -6,7 +6,7 @@ use crate::error::{
     source_span_to_location,
 };
 use crate::frontend::semantic::functions::FunctionRegistry;
-use crate::frontend::semantic::scope::SymbolTable;
+use crate::frontend::semantic::scope::{StorageClass, SymbolTable};
 use crate::frontend::semantic::types::Type;
 use glsl::syntax::Expr;
 
@@ -76,7 +76,19 @@ pub fn infer_expr_type_with_registry(
             infer_unary_result_type(op, &expr_ty, span.clone())
         }
 
-        Expr::Assignment(lhs, _op, _rhs, _span) => {
+        Expr::Assignment(lhs, _op, _rhs, span) => {
+            // Uniforms are read-only inside the shader
+            if let Some(ident) = assignment_root_variable(lhs)
+                && let Some(var) = symbols.lookup_variable(&ident.name)
+                && matches!(var.storage_class, StorageClass::Uniform)
+            {
+                return Err(GlslError::new(
+                    ErrorCode::E0115,
+                    format!("cannot assign to uniform `{}`", ident.name),
+                )
+                .with_location(source_span_to_location(span)));
+            }
+
             // Assignment result has same type as LHS
             infer_expr_type_with_registry(lhs, symbols, func_registry)
         }
@@ -452,3 +464,13 @@ pub fn infer_expr_type_in_context(
         format!("failed to parse expression: `{expr_str}`"),
     ))
 }
+
+/// Find the variable an assignment target ultimately writes to
+/// (`v`, `v.xy`, and `v[i]` all write to `v`)
+fn assignment_root_variable(expr: &Expr) -> Option<&glsl::syntax::Identifier> {
+    match expr {
+        Expr::Variable(ident, _) => Some(ident),
+        Expr::Dot(base, _, _) | Expr::Bracket(base, _, _) => assignment_root_variable(base),
+        _ => None,
+    }
+}
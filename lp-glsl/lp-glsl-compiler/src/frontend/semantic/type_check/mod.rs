@@ -15,7 +15,8 @@ pub use constructors::{
     is_vector_type_name,
 };
 pub use conversion::{
-    can_implicitly_convert, check_assignment, check_assignment_with_span, promote_numeric,
+    can_explicitly_convert, can_implicitly_convert, check_assignment, check_assignment_with_span,
+    explicit_conversion_hint, promote_numeric, promote_operands,
 };
 pub use inference::{infer_expr_type, infer_expr_type_in_context, infer_expr_type_with_registry};
 pub use matrix::infer_matrix_binary_result_type;
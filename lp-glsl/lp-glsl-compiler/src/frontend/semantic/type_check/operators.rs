@@ -5,7 +5,7 @@ use crate::error::{ErrorCode, GlslError, source_span_to_location};
 use crate::frontend::semantic::types::Type;
 use glsl::syntax::{BinaryOp, SourceSpan, UnaryOp};
 
-use super::conversion::promote_numeric;
+use super::conversion::{promote_numeric, promote_operands};
 use super::matrix;
 
 use alloc::format;
@@ -28,46 +28,18 @@ pub fn infer_binary_result_type(
                 return matrix::infer_matrix_binary_result_type(op, lhs_ty, rhs_ty, span);
             }
 
-            // Vector operations
+            // Vector operations: component-wise, or scalar applied to each component.
+            // Operands are promoted to a common base type (e.g. `ivec3 * float` is `vec3`).
             if lhs_ty.is_vector() || rhs_ty.is_vector() {
-                // Vector + Vector: component-wise, types must match
-                if lhs_ty.is_vector() && rhs_ty.is_vector() {
-                    if lhs_ty != rhs_ty {
-                        return Err(GlslError::new(
-                            ErrorCode::E0106,
-                            format!(
-                                "vector operation requires matching types, got {lhs_ty:?} and {rhs_ty:?}"
-                            ),
-                        )
-                        .with_location(source_span_to_location(&span)));
-                    }
-                    return Ok(lhs_ty.clone());
-                }
-
-                // Vector + Scalar or Scalar + Vector: result is vector type
-                if lhs_ty.is_vector() {
-                    let vec_base = lhs_ty.vector_base_type().unwrap();
-                    if !rhs_ty.is_numeric() || !vec_base.is_numeric() {
-                        return Err(GlslError::new(
-                            ErrorCode::E0106,
-                            format!("cannot use {rhs_ty:?} with {lhs_ty:?}"),
-                        )
-                        .with_location(source_span_to_location(&span)));
-                    }
-                    return Ok(lhs_ty.clone());
-                }
-
-                if rhs_ty.is_vector() {
-                    let vec_base = rhs_ty.vector_base_type().unwrap();
-                    if !lhs_ty.is_numeric() || !vec_base.is_numeric() {
-                        return Err(GlslError::new(
-                            ErrorCode::E0106,
-                            format!("cannot use {lhs_ty:?} with {rhs_ty:?}"),
-                        )
-                        .with_location(source_span_to_location(&span)));
-                    }
-                    return Ok(rhs_ty.clone());
-                }
+                return promote_operands(lhs_ty, rhs_ty).ok_or_else(|| {
+                    GlslError::new(
+                        ErrorCode::E0106,
+                        format!(
+                            "vector operation requires compatible types, got {lhs_ty:?} and {rhs_ty:?}"
+                        ),
+                    )
+                    .with_location(source_span_to_location(&span))
+                });
             }
 
             // Scalar operations
@@ -158,7 +130,7 @@ pub fn infer_binary_result_type(
                         )
                         .with_location(source_span_to_location(&span)));
                     }
-                    return Ok(lhs_ty.clone());
+                    return Ok(promote_operands(lhs_ty, rhs_ty).unwrap());
                 }
 
                 if rhs_ty.is_vector() {
@@ -172,7 +144,7 @@ pub fn infer_binary_result_type(
                         )
                         .with_location(source_span_to_location(&span)));
                     }
-                    return Ok(rhs_ty.clone());
+                    return Ok(promote_operands(lhs_ty, rhs_ty).unwrap());
                 }
             }
 
@@ -200,8 +172,12 @@ pub fn infer_binary_result_type(
         // Note: The == operator does aggregate comparison (all components must match)
         // For component-wise comparison, use the equal() builtin function
         Equal | NonEqual => {
-            // Equality operators work on all types (including bool)
-            if lhs_ty != rhs_ty {
+            // Equality operators work on all types (including bool); mixed numeric
+            // scalars and vectors are compared after promotion
+            if lhs_ty != rhs_ty
+                && (lhs_ty.is_vector() != rhs_ty.is_vector()
+                    || promote_operands(lhs_ty, rhs_ty).is_none())
+            {
                 return Err(GlslError::new(
                     ErrorCode::E0106,
                     format!("equality operator {op:?} requires matching types"),
@@ -243,6 +219,50 @@ pub fn infer_binary_result_type(
             Ok(Type::Bool)
         }
 
+        // Bitwise operators: integer scalars or vectors, promoted like arithmetic
+        // (`int & uint` is `uint`)
+        BitAnd | BitOr | BitXor => {
+            let promoted = if is_integer(lhs_ty) && is_integer(rhs_ty) {
+                promote_operands(lhs_ty, rhs_ty)
+            } else {
+                None
+            };
+            promoted.ok_or_else(|| {
+                GlslError::new(
+                    ErrorCode::E0106,
+                    format!("bitwise operator {op:?} requires integer operands"),
+                )
+                .with_location(source_span_to_location(&span))
+                .with_note(format!(
+                    "left operand has type `{lhs_ty:?}`, right operand has type `{rhs_ty:?}`"
+                ))
+            })
+        }
+
+        // Shift operators: the result has the type of the left operand, so `>>` is
+        // arithmetic for int and logical for uint whatever the type of the shift amount
+        LShift | RShift => {
+            let shape_ok = match (lhs_ty.component_count(), rhs_ty.component_count()) {
+                (_, None) => true,
+                (Some(lhs_count), Some(rhs_count)) => lhs_count == rhs_count,
+                (None, Some(_)) => false,
+            };
+            if !is_integer(lhs_ty) || !is_integer(rhs_ty) || !shape_ok {
+                return Err(GlslError::new(
+                    ErrorCode::E0106,
+                    format!("shift operator {op:?} requires integer operands"),
+                )
+                .with_location(source_span_to_location(&span))
+                .with_note(format!(
+                    "left operand has type `{lhs_ty:?}`, right operand has type `{rhs_ty:?}`"
+                ))
+                .with_note(
+                    "the shift amount must be a scalar or a vector the size of the left operand",
+                ));
+            }
+            Ok(lhs_ty.clone())
+        }
+
         _ => Err(GlslError::new(
             ErrorCode::E0112,
             format!("unsupported binary operator: {op:?}"),
@@ -283,6 +303,18 @@ pub fn infer_unary_result_type(
             Ok(Type::Bool)
         }
 
+        Complement => {
+            if !is_integer(operand_ty) {
+                return Err(GlslError::new(
+                    ErrorCode::E0112,
+                    "bitwise complement requires integer operand",
+                )
+                .with_location(source_span_to_location(&span))
+                .with_note(format!("operand has type `{operand_ty:?}`")));
+            }
+            Ok(operand_ty.clone())
+        }
+
         Inc | Dec => {
             if !operand_ty.is_numeric() {
                 return Err(GlslError::new(
@@ -303,6 +335,14 @@ pub fn infer_unary_result_type(
     }
 }
 
+/// True for int and uint scalars and vectors
+fn is_integer(ty: &Type) -> bool {
+    matches!(
+        ty.vector_base_type().unwrap_or_else(|| ty.clone()),
+        Type::Int | Type::UInt
+    )
+}
+
 /// Validate condition expression type (must be bool)
 pub fn check_condition(cond_ty: &Type) -> Result<(), GlslError> {
     if cond_ty != &Type::Bool {
@@ -1,6 +1,7 @@
 //! Type parsing utilities for converting GLSL AST types to our Type enum
 
 use crate::error::{GlslError, source_span_to_location};
+use crate::frontend::semantic::const_eval::{self, ConstValue};
 use crate::frontend::semantic::types;
 use alloc::{boxed::Box, format, vec::Vec};
 
@@ -18,18 +19,28 @@ fn parse_array_dimensions(
     for dimension in &array_spec.dimensions.0 {
         let size = match dimension {
             ArraySpecifierDimension::ExplicitlySized(expr) => {
-                // Extract literal integer constant
-                if let glsl::syntax::Expr::IntConst(n, _) = expr.as_ref() {
-                    *n as usize
-                } else {
-                    let mut error = GlslError::new(
-                        crate::error::ErrorCode::E0400,
-                        "array size must be a compile-time constant integer",
-                    );
-                    if let Some(s) = span {
-                        error = error.with_location(source_span_to_location(&s));
+                // Evaluate the size at compile time (negative sizes are caught below)
+                let size = const_eval::eval_const_expr(expr).map_err(|mut error| {
+                    if error.location.is_none()
+                        && let Some(s) = &span
+                    {
+                        error = error.with_location(source_span_to_location(s));
+                    }
+                    error
+                })?;
+                match size {
+                    Some(ConstValue::Int(n)) => n.max(0) as usize,
+                    Some(ConstValue::UInt(n)) => n as usize,
+                    _ => {
+                        let mut error = GlslError::new(
+                            crate::error::ErrorCode::E0400,
+                            "array size must be a compile-time constant integer",
+                        );
+                        if let Some(s) = span {
+                            error = error.with_location(source_span_to_location(&s));
+                        }
+                        return Err(error);
                     }
-                    return Err(error);
                 }
             }
             ArraySpecifierDimension::Unsized => {
@@ -295,6 +306,25 @@ mod tests {
         assert!(result.unwrap_err().message.contains("positive"));
     }
 
+    #[test]
+    fn test_parse_array_dimensions_constant_expression() {
+        let ty = parse_type_specifier_str("float[2 * 3][4u - 1u]").unwrap();
+        let result = parse_type_specifier(&ty, None).unwrap();
+        let expected = types::Type::Array(
+            Box::new(types::Type::Array(Box::new(types::Type::Float), 6)),
+            3,
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_parse_array_dimensions_negative_size() {
+        let ty = parse_type_specifier_str("int[1 - 2]").unwrap();
+        let array_spec = ty.array_specifier.unwrap();
+        let result = parse_array_dimensions(&array_spec, None);
+        assert!(result.unwrap_err().message.contains("positive"));
+    }
+
     #[test]
     fn test_parse_array_dimensions_unsized() {
         // Create an unsized array specifier (should fail)
@@ -20,11 +20,22 @@ use alloc::format;
 /// Validate a function body, checking all statements and expressions.
 pub fn validate_function(
     func: &crate::frontend::semantic::TypedFunction,
+    uniforms: &[crate::frontend::semantic::UniformDecl],
     func_registry: &FunctionRegistry,
     source: &str,
 ) -> Result<(), GlslError> {
     let mut symbols = SymbolTable::new();
 
+    // Uniforms live in an outer scope so parameters and locals can shadow them
+    for uniform in uniforms {
+        symbols.declare_variable(
+            uniform.name.clone(),
+            uniform.ty.clone(),
+            StorageClass::Uniform,
+        )?;
+    }
+    symbols.push_scope();
+
     // Add function parameters to symbol table
     for param in &func.parameters {
         symbols.declare_variable(param.name.clone(), param.ty.clone(), StorageClass::Local)?;
@@ -35,9 +46,45 @@ pub fn validate_function(
         validate_statement(stmt, &mut symbols, &func.return_type, func_registry, source)?;
     }
 
+    // `discard` returns a sentinel color that only the pixel loop understands; in a
+    // helper it would reach the caller as an ordinary value
+    if func.name != "main" && func.body.iter().any(contains_discard) {
+        return Err(GlslError::new(
+            ErrorCode::E0400,
+            format!(
+                "`discard` is only supported in `main`, not in `{}`",
+                func.name
+            ),
+        ));
+    }
+
     Ok(())
 }
 
+/// Whether a statement contains a `discard`, looking into nested blocks and loops.
+fn contains_discard(stmt: &Statement) -> bool {
+    use glsl::syntax::{IterationStatement, SelectionRestStatement};
+
+    match stmt {
+        Statement::Compound(compound) => compound.statement_list.iter().any(contains_discard),
+        Statement::Simple(simple) => match &**simple {
+            SimpleStatement::Jump(JumpStatement::Discard) => true,
+            SimpleStatement::Selection(selection) => match &selection.rest {
+                SelectionRestStatement::Statement(then_stmt) => contains_discard(then_stmt),
+                SelectionRestStatement::Else(then_stmt, else_stmt) => {
+                    contains_discard(then_stmt) || contains_discard(else_stmt)
+                }
+            },
+            SimpleStatement::Iteration(iteration) => match iteration {
+                IterationStatement::While(_, body)
+                | IterationStatement::DoWhile(body, _)
+                | IterationStatement::For(_, _, body) => contains_discard(body),
+            },
+            _ => false,
+        },
+    }
+}
+
 /// Validate a statement and update the symbol table.
 fn validate_statement(
     stmt: &Statement,
@@ -344,7 +391,7 @@ fn validate_jump(
     func_registry: &FunctionRegistry,
     source: &str,
 ) -> Result<(), GlslError> {
-    use crate::frontend::semantic::type_check::can_implicitly_convert;
+    use crate::frontend::semantic::type_check::{can_implicitly_convert, explicit_conversion_hint};
     use glsl::syntax::JumpStatement;
 
     match jump {
@@ -361,7 +408,7 @@ fn validate_jump(
                 })?;
 
             if !can_implicitly_convert(&expr_type, return_type) {
-                let error = GlslError::new(
+                let mut error = GlslError::new(
                     ErrorCode::E0116,
                     format!(
                         "return type mismatch: expected `{return_type:?}`, found `{expr_type:?}`"
@@ -371,6 +418,9 @@ fn validate_jump(
                 .with_note(format!(
                     "function returns `{return_type:?}` but expression has type `{expr_type:?}`"
                 ));
+                if let Some(hint) = explicit_conversion_hint(&expr_type, return_type) {
+                    error = error.with_note(hint);
+                }
                 return Err(add_span_text_to_error(error, Some(source), &expr_span));
             }
             Ok(())
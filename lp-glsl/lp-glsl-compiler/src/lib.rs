@@ -16,15 +16,25 @@ pub mod frontend;
 
 // Backend2 module (public for filetests)
 pub mod backend;
+#[cfg(feature = "bench")]
+pub mod bench;
 mod exec;
 
 // Re-exports
+pub use backend::alloc_tracker::{CompileStats, TrackingAllocator};
+pub use backend::target::OptLevel;
 #[cfg(feature = "emulator")]
 pub use exec::GlslEmulatorModule;
 pub use exec::GlslJitModule;
 pub use exec::{DecimalFormat, GlslExecutable, GlslOptions, GlslValue, RunMode};
+pub use exec::{GlslInterpreterModule, interpret};
+pub use frontend::ast_span::AstSpan;
 pub use frontend::codegen;
+pub use frontend::codegen::constants::DISCARD_ALPHA;
+pub use frontend::formatter::format_glsl;
 pub use frontend::semantic;
+pub use frontend::semantic::complexity::{ComplexityBudget, ShaderComplexity};
+pub use frontend::semantic::{FRAME_UNIFORM_NAME, TIME_UNIFORM_NAME};
 pub use frontend::{
     Backend, CompilationPipeline, CompiledShader, GlslCompiler, ParseResult, SemanticResult,
     TransformationPass, parse_program_with_registry,
@@ -32,15 +42,19 @@ pub use frontend::{
 
 /// Type alias for convenience
 pub type Compiler = GlslCompiler;
-pub use error::{ErrorCode, GlslError};
+pub use error::{Diagnostic, ErrorCode, GlslError, Severity};
 pub use frontend::semantic::type_check::inference::infer_expr_type_in_context;
 
 // Public API functions
-pub use frontend::glsl_jit;
+pub use frontend::{glsl_check, glsl_interpret, glsl_jit};
 
 #[cfg(feature = "emulator")]
 pub use frontend::{glsl_emu_riscv32, glsl_emu_riscv32_with_metadata};
 
 #[cfg(feature = "std")]
 pub use exec::execute_fn::{execute_function, execute_main};
+#[cfg(all(feature = "std", feature = "emulator"))]
+pub use exec::test_util::{
+    Divergence, ShaderResult, compile_shader, find_divergences, run_shader, run_shader_fn,
+};
 pub use frontend::src_loc::GlSourceLoc;
@@ -0,0 +1,37 @@
+//! Test the compile-time and throughput benchmark harness
+
+#![cfg(feature = "bench")]
+
+use lp_glsl_compiler::bench::{Backend, SHADERS, bench_shader, bench_throughput};
+use lp_glsl_compiler::{Compiler, OptLevel};
+
+#[test]
+fn test_bench_reports_time_and_size() {
+    let mut compiler = Compiler::new();
+    let result = bench_shader(&mut compiler, &SHADERS[0], 1, OptLevel::None).unwrap();
+
+    assert_eq!(result.name, SHADERS[0].name);
+    assert_eq!(result.iterations, 1);
+    assert!(!result.min_time.is_zero(), "compile time should be nonzero");
+    assert_eq!(result.min_time, result.mean_time);
+    assert!(result.code_size > 0, "code size should be nonzero");
+}
+
+#[test]
+fn test_throughput_emulator_matches_jit() {
+    // The gradient's pixel values are exact in Q32, so quantizing can't differ
+    let shader = &SHADERS[0];
+    let emulated = bench_throughput(shader, Backend::Emulator, 4, 2).unwrap();
+    let jit = bench_throughput(shader, Backend::HostJit, 4, 2).unwrap();
+
+    for result in [&emulated, &jit] {
+        assert_eq!(result.pixels, 8);
+        assert_eq!(result.output.len(), 8);
+        assert!(
+            result.pixels_per_sec() > 0.0,
+            "{} throughput should be positive",
+            result.backend
+        );
+    }
+    assert_eq!(emulated.output, jit.output);
+}
@@ -0,0 +1,71 @@
+//! Test heap usage reporting during compilation
+//!
+//! Installs the tracking allocator for this test binary so the compiler can
+//! measure peak allocation.
+
+#![cfg(feature = "emulator")]
+
+use lp_glsl_compiler::{Compiler, OptLevel, TrackingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator<std::alloc::System> =
+    TrackingAllocator::new(std::alloc::System);
+
+#[test]
+fn test_compile_reports_peak_heap() {
+    let shader = r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    float v = sin(uv.x * 6.0 + time) * 0.5 + 0.5;
+    return vec4(uv, v, 1.0);
+}
+"#;
+
+    let mut compiler = Compiler::new();
+    let (code, stats) = compiler
+        .compile_to_code_with_stats(shader, OptLevel::None)
+        .unwrap();
+
+    assert_eq!(stats.code_size, code.len());
+    assert!(stats.peak_heap_bytes > 0, "peak heap should be nonzero");
+    // A shader this small should compile well within a few MB, even on a 64-bit host
+    assert!(
+        stats.peak_heap_bytes < 16 * 1024 * 1024,
+        "peak heap unexpectedly large: {} bytes",
+        stats.peak_heap_bytes
+    );
+}
+
+#[test]
+fn test_simple_shader_reports_no_spills() {
+    let shader = r#"
+int main() {
+    return 42;
+}
+"#;
+
+    let (_, stats) = Compiler::new()
+        .compile_to_code_with_stats(shader, OptLevel::None)
+        .unwrap();
+    assert_eq!(stats.spills, 0);
+    assert_eq!(stats.reloads, 0);
+}
+
+#[test]
+fn test_high_pressure_shader_reports_spills() {
+    // 40 values all computed before any is used, more than there are registers
+    let count = 40;
+    let mut shader = String::from("int pressure(int x) {\n");
+    for i in 0..count {
+        shader.push_str(&format!("    int v{i} = x * {} + {i};\n", i + 2));
+    }
+    let sum: Vec<String> = (0..count).map(|i| format!("v{i}")).collect();
+    shader.push_str(&format!("    return {};\n}}\n", sum.join(" + ")));
+    shader.push_str("int main() {\n    return pressure(3);\n}\n");
+
+    let (_, stats) = Compiler::new()
+        .compile_to_code_with_stats(&shader, OptLevel::None)
+        .unwrap();
+    assert!(stats.spills > 0, "expected spills: {stats:?}");
+    assert!(stats.reloads > 0, "expected reloads: {stats:?}");
+}
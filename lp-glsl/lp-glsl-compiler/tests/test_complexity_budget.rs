@@ -0,0 +1,96 @@
+//! Test rejecting shaders over the complexity budget before codegen
+
+use lp_glsl_compiler::{
+    ComplexityBudget, DecimalFormat, ErrorCode, GlslError, GlslOptions, OptLevel, RunMode, glsl_jit,
+};
+
+const NESTED_LOOPS: &str = r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    float sum = 0.0;
+    for (int i = 0; i < 100; i++) {
+        for (int j = 10; j > 0; j -= 2) {
+            sum += 0.001;
+        }
+    }
+    return vec4(sum, 0.0, 0.0, 1.0);
+}
+"#;
+
+fn options(budget: ComplexityBudget) -> GlslOptions {
+    GlslOptions {
+        run_mode: RunMode::HostJit,
+        decimal_format: DecimalFormat::Q32,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
+    }
+    .with_complexity_budget(budget)
+}
+
+fn compile_error(source: &str, budget: ComplexityBudget) -> GlslError {
+    match glsl_jit(source, options(budget)) {
+        Ok(_) => panic!("expected the shader to be rejected"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn test_too_many_ast_nodes_rejected() {
+    let shader = r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    float a = sin(uv.x * 6.0 + time) * 0.5 + 0.5;
+    float b = cos(uv.y * 4.0 - time) * 0.5 + 0.5;
+    float c = a * b + a / (b + 1.0) - (a - b) * 0.25;
+    return vec4(a, b, c, 1.0);
+}
+"#;
+    let error = compile_error(
+        shader,
+        ComplexityBudget {
+            max_ast_nodes: 20,
+            max_loop_iterations: 1_000,
+        },
+    );
+
+    assert_eq!(error.code, ErrorCode::E0117);
+    assert!(
+        error.message.contains("AST nodes (limit 20)"),
+        "unexpected message: {}",
+        error.message
+    );
+}
+
+#[test]
+fn test_too_many_loop_iterations_rejected() {
+    let error = compile_error(
+        NESTED_LOOPS,
+        ComplexityBudget {
+            max_ast_nodes: 1_000,
+            max_loop_iterations: 100,
+        },
+    );
+
+    assert_eq!(error.code, ErrorCode::E0117);
+    // 100 outer iterations times 5 inner ones
+    assert!(
+        error
+            .message
+            .contains("loops run 500 iterations (limit 100)"),
+        "unexpected message: {}",
+        error.message
+    );
+}
+
+#[test]
+fn test_shader_within_budget_compiles() {
+    let result = glsl_jit(
+        NESTED_LOOPS,
+        options(ComplexityBudget {
+            max_ast_nodes: 1_000,
+            max_loop_iterations: 500,
+        }),
+    );
+    assert!(result.is_ok(), "compilation failed: {:?}", result.err());
+}
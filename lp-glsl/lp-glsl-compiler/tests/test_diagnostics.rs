@@ -0,0 +1,59 @@
+//! Test warnings from semantic analysis and escalating them in strict mode
+
+use lp_glsl_compiler::{ErrorCode, GlslOptions, Severity, glsl_check, glsl_jit};
+
+const UNUSED_VARIABLE: &str = r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    float unused = uv.x * 2.0;
+    return vec4(uv, 0.0, 1.0);
+}
+"#;
+
+#[test]
+fn test_unused_variable_is_a_warning() {
+    let warnings = glsl_check(UNUSED_VARIABLE, &GlslOptions::jit())
+        .unwrap_or_else(|e| panic!("expected only warnings, got error: {e}"));
+
+    assert_eq!(warnings.len(), 1, "unexpected warnings: {warnings:?}");
+    let warning = &warnings[0];
+    assert_eq!(warning.severity, Severity::Warning);
+    assert!(!warning.is_error());
+    assert_eq!(warning.details.code, ErrorCode::W0001);
+    assert_eq!(warning.details.message, "unused variable `unused`");
+    assert_eq!(
+        warning.details.location.as_ref().map(|loc| loc.line),
+        Some(4)
+    );
+    assert!(warning.to_string().starts_with("warning[W0001]"));
+
+    // Warnings don't stop compilation
+    assert!(glsl_jit(UNUSED_VARIABLE, GlslOptions::jit()).is_ok());
+}
+
+#[test]
+fn test_strict_mode_escalates_warnings() {
+    let strict = GlslOptions::jit().with_warnings_as_errors();
+
+    let error = glsl_check(UNUSED_VARIABLE, &strict).expect_err("strict mode should fail");
+    assert_eq!(error.code, ErrorCode::W0001);
+    assert!(error.to_string().starts_with("error[W0001]"));
+
+    let error = match glsl_jit(UNUSED_VARIABLE, strict) {
+        Ok(_) => panic!("strict mode should fail compilation"),
+        Err(e) => e,
+    };
+    assert_eq!(error.code, ErrorCode::W0001);
+}
+
+#[test]
+fn test_clean_shader_has_no_warnings() {
+    let shader = r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    return vec4(uv, 0.0, 1.0);
+}
+"#;
+    let strict = GlslOptions::jit().with_warnings_as_errors();
+    assert!(glsl_check(shader, &strict).unwrap().is_empty());
+}
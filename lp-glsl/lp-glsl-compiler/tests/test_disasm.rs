@@ -0,0 +1,26 @@
+//! Test disassembly of compiled RISC-V code
+
+#![cfg(feature = "emulator")]
+
+use lp_glsl_compiler::Compiler;
+use lp_glsl_compiler::backend::target::Target;
+
+#[test]
+fn test_compile_with_disasm_trivial_shader() {
+    let shader = r#"
+int main() {
+    return 42;
+}
+"#;
+
+    let mut compiler = Compiler::new();
+    let target = Target::riscv32_emulator().unwrap();
+    let (code, disasm) = compiler.compile_with_disasm(shader, target).unwrap();
+
+    assert!(!code.is_empty());
+    assert!(!disasm.is_empty());
+    assert!(
+        disasm.contains("jalr zero, (ra)") || disasm.contains("c.jr ra"),
+        "expected a return instruction in disassembly:\n{disasm}"
+    );
+}
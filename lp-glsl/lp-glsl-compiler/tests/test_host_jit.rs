@@ -0,0 +1,135 @@
+//! Test native execution of shaders compiled for the host
+//!
+//! Desktop previews JIT shaders to host code and call `main` directly through the
+//! function pointer, the same way the engine renders. The results must match the
+//! RISC-V emulator, which runs the code devices execute.
+
+#![cfg(feature = "emulator")]
+
+use lp_glsl_compiler::{
+    DecimalFormat, GlslOptions, GlslValue, OptLevel, RunMode, glsl_jit, run_shader,
+};
+use lp_glsl_jit_util::call_structreturn_with_args;
+
+const SHADER: &str = r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    float wave = sin(uv.x * 6.0 + time) * 0.5 + 0.5;
+    return vec4(uv.x, uv.y, wave, clamp(time * 0.25, 0.0, 1.0));
+}
+"#;
+
+/// Pixels and times to compare at
+const SAMPLES: [([f32; 2], f32); 4] = [
+    ([0.5, 0.5], 0.0),
+    ([3.5, 1.5], 0.5),
+    ([7.5, 2.5], 1.25),
+    ([12.5, 7.5], 6.0),
+];
+
+const OUTPUT_SIZE: [f32; 2] = [16.0, 8.0];
+
+fn host_options() -> GlslOptions {
+    GlslOptions {
+        run_mode: RunMode::HostJit,
+        decimal_format: DecimalFormat::Q32,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
+    }
+}
+
+fn to_q32(value: f32) -> i32 {
+    (value * 65536.0) as i32
+}
+
+fn emulated(frag_coord: [f32; 2], time: f32) -> [f32; 4] {
+    let result = run_shader(
+        SHADER,
+        &[
+            GlslValue::Vec2(frag_coord),
+            GlslValue::Vec2(OUTPUT_SIZE),
+            GlslValue::F32(time),
+        ],
+    )
+    .unwrap_or_else(|e| panic!("emulator run failed: {e:#}"));
+    match result.value {
+        GlslValue::Vec4(v) => v,
+        other => panic!("expected vec4 from emulator, got {other:?}"),
+    }
+}
+
+fn assert_close(actual: [f32; 4], expected: [f32; 4], context: &str) {
+    for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+        // Both paths run the same Q32 code, so allow only rounding in the conversion
+        assert!(
+            (a - e).abs() <= 2.0 / 65536.0,
+            "{context}: component {i} is {a}, emulator gave {e}"
+        );
+    }
+}
+
+#[test]
+fn test_host_jit_matches_emulator() {
+    let mut executable = glsl_jit(SHADER, host_options())
+        .unwrap_or_else(|e| panic!("host compilation failed: {e:#}"));
+
+    for (frag_coord, time) in SAMPLES {
+        let host = executable
+            .call_vec(
+                "main",
+                &[
+                    GlslValue::Vec2(frag_coord),
+                    GlslValue::Vec2(OUTPUT_SIZE),
+                    GlslValue::F32(time),
+                ],
+                4,
+            )
+            .unwrap();
+        let host: [f32; 4] = host.try_into().expect("main returns vec4");
+        assert_close(
+            host,
+            emulated(frag_coord, time),
+            &format!("fragCoord {frag_coord:?} at time {time}"),
+        );
+    }
+}
+
+#[test]
+fn test_host_jit_direct_call_matches_emulator() {
+    let executable = glsl_jit(SHADER, host_options())
+        .unwrap_or_else(|e| panic!("host compilation failed: {e:#}"));
+    let info = executable
+        .get_direct_call_info("main")
+        .expect("host JIT exposes a callable pointer for main");
+
+    for (frag_coord, time) in SAMPLES {
+        // vec2 arguments are passed as two Q32 scalars each
+        let args = [
+            to_q32(frag_coord[0]) as u64,
+            to_q32(frag_coord[1]) as u64,
+            to_q32(OUTPUT_SIZE[0]) as u64,
+            to_q32(OUTPUT_SIZE[1]) as u64,
+            to_q32(time) as u64,
+        ];
+        let mut result = [0i32; 4];
+        unsafe {
+            call_structreturn_with_args(
+                info.func_ptr,
+                result.as_mut_ptr(),
+                16,
+                &args,
+                info.call_conv,
+                info.pointer_type,
+            )
+            .unwrap();
+        }
+        let host = result.map(|v| v as f32 / 65536.0);
+        assert_close(
+            host,
+            emulated(frag_coord, time),
+            &format!("direct call at fragCoord {frag_coord:?}, time {time}"),
+        );
+    }
+}
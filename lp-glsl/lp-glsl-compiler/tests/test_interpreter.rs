@@ -0,0 +1,112 @@
+//! Test the AST interpreter against the emulator
+//!
+//! The interpreter is the fallback where shaders can't be compiled to native code,
+//! so it must render the same images. It evaluates floats as `f32` while the
+//! emulator runs Q32 code, so results only agree to within fixed-point precision.
+
+#![cfg(feature = "emulator")]
+
+use lp_glsl_compiler::{
+    CompilationPipeline, GlslOptions, GlslValue, glsl_interpret, interpret, run_shader,
+};
+
+const SHADER: &str = r#"
+float ring(vec2 p, float radius) {
+    return 1.0 - smoothstep(0.0, 0.05, abs(length(p) - radius));
+}
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    vec2 p = uv - vec2(0.5);
+    float glow = 0.0;
+    for (int i = 1; i <= 3; i++) {
+        glow += ring(p, float(i) * 0.1 + time * 0.05) / float(i);
+    }
+    float bands = floor(uv.x * 4.0) / 4.0;
+    if (uv.y > 0.5) {
+        bands = 1.0 - bands;
+    }
+    return vec4(clamp(glow, 0.0, 1.0), bands, mix(uv.y, sin(time), 0.5), 1.0);
+}
+"#;
+
+/// Pixels and times to compare at
+const SAMPLES: [([f32; 2], f32); 5] = [
+    ([0.5, 0.5], 0.0),
+    ([4.5, 2.5], 0.5),
+    ([8.5, 4.5], 1.0),
+    ([11.5, 6.5], 2.5),
+    ([15.5, 7.5], 4.0),
+];
+
+const OUTPUT_SIZE: [f32; 2] = [16.0, 8.0];
+
+fn inputs(frag_coord: [f32; 2], time: f32) -> [GlslValue; 3] {
+    [
+        GlslValue::Vec2(frag_coord),
+        GlslValue::Vec2(OUTPUT_SIZE),
+        GlslValue::F32(time),
+    ]
+}
+
+fn emulated(frag_coord: [f32; 2], time: f32) -> [f32; 4] {
+    let result = run_shader(SHADER, &inputs(frag_coord, time))
+        .unwrap_or_else(|e| panic!("emulator run failed: {e:#}"));
+    match result.value {
+        GlslValue::Vec4(v) => v,
+        other => panic!("expected vec4 from emulator, got {other:?}"),
+    }
+}
+
+fn assert_close(actual: [f32; 4], expected: [f32; 4], context: &str) {
+    for (i, (a, e)) in actual.iter().zip(expected.iter()).enumerate() {
+        assert!(
+            (a - e).abs() <= 2e-3,
+            "{context}: component {i} is {a}, emulator gave {e}"
+        );
+    }
+}
+
+#[test]
+fn test_interpret_matches_emulator() {
+    let shader = CompilationPipeline::parse_and_analyze(SHADER)
+        .unwrap_or_else(|e| panic!("analysis failed: {e:#}"))
+        .typed_ast;
+
+    for (frag_coord, time) in SAMPLES {
+        let result = interpret(&shader, &inputs(frag_coord, time))
+            .unwrap_or_else(|e| panic!("interpreter run failed: {e:#}"));
+        let GlslValue::Vec4(interpreted) = result else {
+            panic!("expected vec4 from interpreter, got {result:?}");
+        };
+        assert_close(
+            interpreted,
+            emulated(frag_coord, time),
+            &format!("fragCoord {frag_coord:?} at time {time}"),
+        );
+    }
+}
+
+#[test]
+fn test_interpreter_executable_matches_emulator() {
+    let mut executable = glsl_interpret(SHADER, GlslOptions::jit().with_loop_fuel(1_000))
+        .unwrap_or_else(|e| panic!("interpreter setup failed: {e:#}"));
+
+    for (frag_coord, time) in SAMPLES {
+        let result = executable
+            .call_vec("main", &inputs(frag_coord, time), 4)
+            .unwrap();
+        let interpreted: [f32; 4] = result.try_into().expect("main returns vec4");
+        assert_close(
+            interpreted,
+            emulated(frag_coord, time),
+            &format!("executable at fragCoord {frag_coord:?}, time {time}"),
+        );
+    }
+
+    // Each call checks the loop header four times: three iterations and the exit
+    assert_eq!(
+        executable.fuel_remaining(),
+        Some(1_000 - 4 * SAMPLES.len() as u32)
+    );
+}
@@ -0,0 +1,92 @@
+//! Test aborting unbounded loops in JIT-compiled shaders with loop fuel
+
+use lp_glsl_compiler::{DecimalFormat, GlslOptions, OptLevel, RunMode, glsl_jit};
+
+fn options(fuel: u32) -> GlslOptions {
+    GlslOptions {
+        run_mode: RunMode::HostJit,
+        decimal_format: DecimalFormat::Q32,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
+    }
+    .with_loop_fuel(fuel)
+}
+
+#[test]
+fn test_infinite_loop_runs_out_of_fuel() {
+    let shader = r#"
+int main() {
+    int count = 0;
+    while (true) {
+        count = count + 1;
+    }
+    return count;
+}
+"#;
+    let mut executable = glsl_jit(shader, options(1_000)).unwrap();
+
+    // Returns instead of hanging, with the loop cut short
+    let count = executable.call_i32("main", &[]).unwrap();
+    assert_eq!(executable.fuel_remaining(), Some(0));
+    assert!(count < 1_000, "loop ran {count} times");
+
+    // Nested loops all bail out once the fuel is gone
+    let shader = r#"
+int main() {
+    int count = 0;
+    for (;;) {
+        do {
+            count = count + 1;
+        } while (count > 0);
+    }
+    return count;
+}
+"#;
+    let mut executable = glsl_jit(shader, options(1_000)).unwrap();
+    executable.call_i32("main", &[]).unwrap();
+    assert_eq!(executable.fuel_remaining(), Some(0));
+}
+
+#[test]
+fn test_bounded_loop_within_fuel() {
+    let shader = r#"
+int main() {
+    int sum = 0;
+    for (int i = 0; i < 10; i++) {
+        sum = sum + i;
+    }
+    return sum;
+}
+"#;
+    let mut executable = glsl_jit(shader, options(100)).unwrap();
+
+    assert_eq!(executable.call_i32("main", &[]).unwrap(), 45);
+    let remaining = executable.fuel_remaining().unwrap();
+    assert!(remaining > 0 && remaining < 100, "remaining: {remaining}");
+
+    // Fuel is shared across calls until refilled
+    executable.set_fuel(5).unwrap();
+    executable.call_i32("main", &[]).unwrap();
+    assert_eq!(executable.fuel_remaining(), Some(0));
+
+    executable.set_fuel(100).unwrap();
+    assert_eq!(executable.call_i32("main", &[]).unwrap(), 45);
+}
+
+#[test]
+fn test_no_fuel_by_default() {
+    let mut executable = glsl_jit(
+        "int main() { return 1; }",
+        GlslOptions {
+            loop_fuel: None,
+            warnings_as_errors: false,
+            ..options(0)
+        },
+    )
+    .unwrap();
+
+    assert_eq!(executable.fuel_remaining(), None);
+    assert!(executable.set_fuel(10).is_err());
+}
@@ -0,0 +1,68 @@
+//! Test compiling shaders at different Cranelift optimization levels
+//!
+//! The same shader must produce the same results regardless of opt level,
+//! while optimized builds should produce less code for redundant arithmetic.
+
+#![cfg(feature = "emulator")]
+
+use lp_glsl_compiler::{Compiler, GlslOptions, OptLevel, RunMode, glsl_emu_riscv32};
+
+const SHADER: &str = r#"
+int helper(int x) {
+    int a = 2 * 3;
+    int b = a * 4;
+    return x + a + b + a + b;
+}
+
+int main() {
+    int sum = 0;
+    for (int i = 0; i < 4; i++) {
+        sum = sum + helper(i);
+    }
+    return sum;
+}
+"#;
+
+#[test]
+fn test_opt_levels_produce_same_results() {
+    for opt_level in [
+        OptLevel::None,
+        OptLevel::Speed,
+        OptLevel::SpeedAndSize,
+        OptLevel::Scheduled,
+    ] {
+        let mut options = GlslOptions::emu_riscv32_imac().with_opt_level(opt_level);
+        if let RunMode::Emulator {
+            max_instructions, ..
+        } = &mut options.run_mode
+        {
+            *max_instructions = 100_000;
+        }
+
+        let mut executable = glsl_emu_riscv32(SHADER, options)
+            .unwrap_or_else(|e| panic!("compilation at {opt_level:?} failed: {e:#}"));
+        let result = executable.call_i32("main", &[]).unwrap();
+        // helper(i) = i + 60, summed over i = 0..4
+        assert_eq!(result, 246, "wrong result at {opt_level:?}");
+    }
+}
+
+#[test]
+fn test_opt_levels_change_code_size() {
+    let mut compiler = Compiler::new();
+    let unoptimized = compiler
+        .compile_to_code_with_opts(SHADER, OptLevel::None)
+        .unwrap();
+    let optimized = compiler
+        .compile_to_code_with_opts(SHADER, OptLevel::Speed)
+        .unwrap();
+
+    assert!(!unoptimized.is_empty());
+    assert!(!optimized.is_empty());
+    assert!(
+        optimized.len() < unoptimized.len(),
+        "expected optimized code ({} bytes) to be smaller than unoptimized ({} bytes)",
+        optimized.len(),
+        unoptimized.len()
+    );
+}
@@ -0,0 +1,78 @@
+//! Test that compiled shaders report which functions are pure
+//!
+//! The engine can cache the output of a pure shader, as it only depends on the
+//! pixel it is called for.
+
+use lp_glsl_compiler::{DecimalFormat, GlslOptions, OptLevel, RunMode, glsl_jit};
+
+fn options() -> GlslOptions {
+    GlslOptions {
+        run_mode: RunMode::HostJit,
+        decimal_format: DecimalFormat::Q32,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
+    }
+}
+
+#[test]
+fn test_pure_shader_is_flagged_pure() {
+    let shader = r#"
+float checker(vec2 uv) {
+    return mod(floor(uv.x * 8.0) + floor(uv.y * 8.0), 2.0);
+}
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    return vec4(vec3(checker(uv)), 1.0);
+}
+"#;
+    let executable = glsl_jit(shader, options()).unwrap();
+    assert!(executable.is_pure("main"));
+    assert!(executable.is_pure("checker"));
+}
+
+#[test]
+fn test_time_reading_shader_is_flagged_impure() {
+    let shader = r#"
+float pulse(vec2 uv) {
+    return 0.5 + 0.5 * sin(uv.x * 6.0 + u_time);
+}
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    return vec4(pulse(uv), uv, 1.0);
+}
+"#;
+    let executable = glsl_jit(shader, options()).unwrap();
+    assert!(!executable.is_pure("main"));
+    assert!(!executable.is_pure("pulse"));
+}
+
+#[test]
+fn test_time_argument_shader_is_flagged_impure() {
+    let shader = r#"
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    vec2 uv = fragCoord / outputSize;
+    return vec4(0.5 + 0.5 * sin(uv.x * 6.0 + time), uv, 1.0);
+}
+"#;
+    let executable = glsl_jit(shader, options()).unwrap();
+    assert!(!executable.is_pure("main"));
+    assert!(executable.reads_time("main"));
+}
+
+#[test]
+fn test_uniform_reading_shader_does_not_read_time() {
+    let shader = r#"
+uniform float level;
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    return vec4(level, 0.0, 0.0, 1.0);
+}
+"#;
+    let executable = glsl_jit(shader, options()).unwrap();
+    assert!(!executable.is_pure("main"));
+    assert!(!executable.reads_time("main"));
+}
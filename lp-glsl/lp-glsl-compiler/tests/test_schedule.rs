@@ -0,0 +1,74 @@
+//! Test latency-aware instruction scheduling
+//!
+//! The scheduled build must compute the same result while stalling less on the
+//! emulator's in-order pipeline model.
+
+#![cfg(feature = "emulator")]
+
+use cranelift_codegen::data_value::DataValue;
+use cranelift_codegen::ir::{AbiParam, Signature, types};
+use cranelift_codegen::isa::CallConv;
+use lp_glsl_compiler::backend::schedule;
+use lp_glsl_compiler::{Compiler, OptLevel};
+use lp_riscv_emu::Riscv32Emulator;
+use lp_riscv_emu::emu::cycle_model;
+
+/// Two independent multiply chains, written one after the other. `chains` sorts
+/// first, so it is at the start of the code.
+const SHADER: &str = r#"
+int chains(int a, int b) {
+    int a2 = a * a;
+    int a3 = a2 * a;
+    int a4 = a3 * a;
+    int b2 = b * b;
+    int b3 = b2 * b;
+    int b4 = b3 * b;
+    return a4 + b4;
+}
+
+int main() {
+    return chains(2, 3);
+}
+"#;
+
+/// Run `chains(2, 3)` and return its result and the stall cycles
+fn run_chains(opt_level: OptLevel) -> (i32, u64) {
+    let code = Compiler::new()
+        .compile_to_code_with_opts(SHADER, opt_level)
+        .unwrap_or_else(|e| panic!("compilation at {opt_level:?} failed: {e:#}"));
+    let mut emu = Riscv32Emulator::new(code, vec![0; 64 * 1024]).with_cycle_model();
+
+    let mut sig = Signature::new(CallConv::SystemV);
+    sig.params.push(AbiParam::new(types::I32));
+    sig.params.push(AbiParam::new(types::I32));
+    sig.returns.push(AbiParam::new(types::I32));
+    let results = emu
+        .call_function(0, &[DataValue::I32(2), DataValue::I32(3)], &sig)
+        .unwrap();
+    let result = match results.as_slice() {
+        [DataValue::I32(result)] => *result,
+        other => panic!("unexpected results at {opt_level:?}: {other:?}"),
+    };
+    (result, emu.cycle_model().unwrap().stall_cycles())
+}
+
+#[test]
+fn test_scheduling_reduces_stalls() {
+    let (unscheduled, unscheduled_stalls) = run_chains(OptLevel::None);
+    let (scheduled, scheduled_stalls) = run_chains(OptLevel::Scheduled);
+
+    // 2^4 + 3^4
+    assert_eq!(unscheduled, 97);
+    assert_eq!(scheduled, 97);
+    assert!(
+        scheduled_stalls < unscheduled_stalls,
+        "expected scheduled code to stall less ({scheduled_stalls} vs {unscheduled_stalls} cycles)"
+    );
+}
+
+#[test]
+fn test_scheduler_latencies_match_cycle_model() {
+    assert_eq!(u64::from(schedule::MUL_LATENCY), cycle_model::MUL_LATENCY);
+    assert_eq!(u64::from(schedule::DIV_LATENCY), cycle_model::DIV_LATENCY);
+    assert_eq!(u64::from(schedule::LOAD_LATENCY), cycle_model::LOAD_LATENCY);
+}
@@ -4,7 +4,7 @@
 //! that causes a panic when compiling on macOS due to unimplemented
 //! TestCase relocation handling.
 
-use lp_glsl_compiler::{DecimalFormat, GlslOptions, RunMode, glsl_jit};
+use lp_glsl_compiler::{DecimalFormat, GlslOptions, OptLevel, RunMode, glsl_jit};
 
 #[test]
 fn test_default_project_shader_compilation() {
@@ -58,6 +58,10 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
     let options_q32 = GlslOptions {
         run_mode: RunMode::HostJit,
         decimal_format: DecimalFormat::Q32,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
     };
 
     // This should not panic - Q32 format goes through transform that converts TestCase names
@@ -83,6 +87,10 @@ vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
     let options_float = GlslOptions {
         run_mode: RunMode::HostJit,
         decimal_format: DecimalFormat::Float,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
     };
 
     match glsl_jit(glsl, options_float) {
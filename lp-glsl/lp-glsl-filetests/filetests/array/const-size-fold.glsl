@@ -0,0 +1,72 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Const Declarations: sizing arrays and folding into arithmetic
+// ============================================================================
+
+const int COUNT = 4;
+const float PI = 3.14159;
+const float TAU = 2.0 * PI;
+const int HALF = COUNT / 2;
+
+float test_const_array_size_loop() {
+    // Const sizes the array and bounds the loop
+    float values[COUNT];
+    for (int i = 0; i < COUNT; i++) {
+        values[i] = float(i) * 0.5;
+    }
+    return values[COUNT - 1];
+}
+
+// run: test_const_array_size_loop() ~= 1.5
+
+int test_const_expression_array_size() {
+    // Array size computed from other constants
+    int values[COUNT * HALF + 1];
+    values[8] = 7;
+    return values[8] + HALF;
+}
+
+// run: test_const_expression_array_size() == 9
+
+int test_local_const_array_size() {
+    const int LOCAL = COUNT - 1;
+    int values[LOCAL];
+    values[0] = 10;
+    values[LOCAL - 1] = 20;
+    return values[0] + values[2];
+}
+
+// run: test_local_const_array_size() == 30
+
+float test_const_arithmetic() {
+    // Constants folded into expressions
+    return TAU * 0.5 + float(HALF);
+}
+
+// run: test_const_arithmetic() ~= 5.14159
+
+float test_const_int_promoted() {
+    // Int initializer converted to the declared float type
+    const float SCALE = 3;
+    return SCALE / 2.0;
+}
+
+// run: test_const_int_promoted() ~= 1.5
+
+vec2 test_const_vector() {
+    const vec2 OFFSET = vec2(PI, float(COUNT));
+    return OFFSET * 0.5;
+}
+
+// run: test_const_vector() ~= vec2(1.570795, 2.0)
+
+float test_const_shadowed() {
+    // A local variable hides the global constant
+    float PI = 1.0;
+    PI = PI + 1.0;
+    return PI;
+}
+
+// run: test_const_shadowed() ~= 2.0
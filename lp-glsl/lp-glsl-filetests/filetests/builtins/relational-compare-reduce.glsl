@@ -0,0 +1,131 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Relational functions: component-wise vec3 comparisons reduced with any()/all()
+// Values differ only in their fractional part or sign to exercise the
+// fixed-point comparison of scaled values
+// ============================================================================
+
+bvec3 test_less_than_fractional() {
+    vec3 a = vec3(0.25, 1.5, -0.5);
+    vec3 b = vec3(0.5, 1.25, -0.25);
+    return lessThan(a, b);
+}
+
+// run: test_less_than_fractional() == bvec3(true, false, true)
+
+bvec3 test_greater_than_fractional() {
+    vec3 a = vec3(0.25, 1.5, -0.5);
+    vec3 b = vec3(0.5, 1.25, -0.25);
+    return greaterThan(a, b);
+}
+
+// run: test_greater_than_fractional() == bvec3(false, true, false)
+
+bvec3 test_less_than_equal_lanes() {
+    vec3 a = vec3(2.0, -3.0, 0.125);
+    vec3 b = vec3(2.0, -3.5, 0.25);
+    return lessThanEqual(a, b);
+}
+
+// run: test_less_than_equal_lanes() == bvec3(true, false, true)
+
+bvec3 test_greater_than_equal_lanes() {
+    vec3 a = vec3(2.0, -3.0, 0.125);
+    vec3 b = vec3(2.0, -3.5, 0.25);
+    return greaterThanEqual(a, b);
+}
+
+// run: test_greater_than_equal_lanes() == bvec3(true, true, false)
+
+bvec3 test_equal_lanes() {
+    vec3 a = vec3(1.0, 0.5, -2.0);
+    vec3 b = vec3(1.0, 0.75, -2.0);
+    return equal(a, b);
+}
+
+// run: test_equal_lanes() == bvec3(true, false, true)
+
+bvec3 test_not_equal_lanes() {
+    vec3 a = vec3(1.0, 0.5, -2.0);
+    vec3 b = vec3(1.0, 0.75, -2.0);
+    return notEqual(a, b);
+}
+
+// run: test_not_equal_lanes() == bvec3(false, true, false)
+
+bool test_any_less_than_mixed() {
+    vec3 a = vec3(5.0, 8.0, 7.0);
+    vec3 b = vec3(7.0, 6.0, 9.0);
+    return any(lessThan(a, b));
+}
+
+// run: test_any_less_than_mixed() == true
+
+bool test_any_less_than_none() {
+    vec3 a = vec3(5.0, 6.0, 7.0);
+    vec3 b = vec3(1.0, 2.0, 3.0);
+    return any(lessThan(a, b));
+}
+
+// run: test_any_less_than_none() == false
+
+bool test_all_less_than_mixed() {
+    vec3 a = vec3(5.0, 8.0, 7.0);
+    vec3 b = vec3(7.0, 6.0, 9.0);
+    return all(lessThan(a, b));
+}
+
+// run: test_all_less_than_mixed() == false
+
+bool test_all_less_than_every_lane() {
+    vec3 a = vec3(-1.0, 0.0, 0.5);
+    vec3 b = vec3(-0.5, 0.25, 0.75);
+    return all(lessThan(a, b));
+}
+
+// run: test_all_less_than_every_lane() == true
+
+bool test_all_equal_same_vectors() {
+    vec3 a = vec3(0.1, 0.2, 0.3);
+    return all(equal(a, a));
+}
+
+// run: test_all_equal_same_vectors() == true
+
+bool test_any_not_equal_same_vectors() {
+    vec3 a = vec3(0.1, 0.2, 0.3);
+    return any(notEqual(a, a));
+}
+
+// run: test_any_not_equal_same_vectors() == false
+
+bool test_all_greater_than_equal_boundary() {
+    vec3 a = vec3(1.0, 2.0, 3.0);
+    vec3 b = vec3(1.0, 2.0, 3.0);
+    return all(greaterThanEqual(a, b));
+}
+
+// run: test_all_greater_than_equal_boundary() == true
+
+int test_count_lanes_less_than() {
+    vec3 a = vec3(0.5, 2.0, -1.0);
+    vec3 b = vec3(1.0, 1.0, 0.0);
+    bvec3 lt = lessThan(a, b);
+    int count = 0;
+    if (lt.x) count++;
+    if (lt.y) count++;
+    if (lt.z) count++;
+    return count;
+}
+
+// run: test_count_lanes_less_than() == 2
+
+bool test_any_not_reduces_inverted() {
+    vec3 a = vec3(1.0, 2.0, 3.0);
+    vec3 b = vec3(0.0, 2.5, 2.0);
+    return any(not(greaterThan(a, b)));
+}
+
+// run: test_any_not_reduces_inverted() == true
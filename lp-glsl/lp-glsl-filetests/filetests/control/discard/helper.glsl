@@ -0,0 +1,17 @@
+// test error
+// target riscv32.q32
+
+vec4 test_discard_in_helper(float x) {
+    // This should fail - the sentinel would be returned to the caller as a color
+    if (x < 0.5) {
+        discard;
+    }
+    return vec4(1.0, 0.0, 0.0, 1.0);
+}
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    return test_discard_in_helper(fragCoord.x);
+}
+
+// EXPECT_ERROR_CODE: E0400
+// EXPECT_ERROR: `discard` is only supported in `main`, not in `test_discard_in_helper`
@@ -0,0 +1,16 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Discard: returns a sentinel color with alpha -1.0 so the pixel is left untouched
+// ============================================================================
+
+vec4 main(vec2 fragCoord, vec2 outputSize, float time) {
+    if (fragCoord.x < 2.0) {
+        discard;
+    }
+    return vec4(1.0, 0.0, 0.0, 1.0);
+}
+
+// run: main(vec2(1.0, 0.0), vec2(4.0, 2.0), 0.0) ~= vec4(0.0, 0.0, 0.0, -1.0)
+// run: main(vec2(3.0, 0.0), vec2(4.0, 2.0), 0.0) ~= vec4(1.0, 0.0, 0.0, 1.0)
@@ -36,28 +36,28 @@ int test_const_expression_int_math() {
     return DOUBLE_ANSWER / 2;
 }
 
-// run: test_const_expression_int_math() == 42 [expect-fail]
+// run: test_const_expression_int_math() == 42
 
 vec2 test_const_expression_vector() {
     // Constant vector expressions
     return SCALED_VECTOR + vec2(1.0, 1.0);
 }
 
-// run: test_const_expression_vector() ~= vec2(3.0, 1.0) [expect-fail]
+// run: test_const_expression_vector() ~= vec2(3.0, 1.0)
 
 vec3 test_const_expression_vector_ops() {
     // Constant vector operations
     return UP_VECTOR + RIGHT_VECTOR + FORWARD_VECTOR;
 }
 
-// run: test_const_expression_vector_ops() ~= vec3(1.0, 1.0, 1.0) [expect-fail]
+// run: test_const_expression_vector_ops() ~= vec3(1.0, 1.0, 1.0)
 
 mat2 test_const_expression_matrix() {
     // Constant matrix expressions
     return SCALED_IDENTITY;
 }
 
-// run: test_const_expression_matrix() ~= mat2(2.0, 0.0, 0.0, 2.0) [expect-fail]
+// run: test_const_expression_matrix() ~= mat2(2.0, 0.0, 0.0, 2.0)
 
 float test_const_expression_nested() {
     // Nested constant expressions
@@ -78,7 +78,7 @@ vec3 test_const_expression_complex() {
     return OFFSET_BASIS;
 }
 
-// run: test_const_expression_complex() ~= vec3(0.6, 0.6, 0.6) [expect-fail]
+// run: test_const_expression_complex() ~= vec3(0.6, 0.6, 0.6)
 
 float test_const_expression_builtin() {
     // Built-in functions that are constant (if supported)
@@ -88,4 +88,4 @@ float test_const_expression_builtin() {
     return LENGTH_UNIT;
 }
 
-// run: test_const_expression_builtin() ~= 1.0 [expect-fail]
+// run: test_const_expression_builtin() ~= 1.0
@@ -24,14 +24,14 @@ float test_const_must_init_float() {
     return PI;
 }
 
-// run: test_const_must_init_float() ~= 3.14159 [expect-fail]
+// run: test_const_must_init_float() ~= 3.14159
 
 int test_const_must_init_int() {
     // Const int that is properly initialized
     return ANSWER;
 }
 
-// run: test_const_must_init_int() == 42 [expect-fail]
+// run: test_const_must_init_int() == 42
 
 uint test_const_must_init_uint() {
     // Const uint that is properly initialized
@@ -45,28 +45,28 @@ bool test_const_must_init_bool() {
     return FLAG;
 }
 
-// run: test_const_must_init_bool() == true [expect-fail]
+// run: test_const_must_init_bool() == true
 
 vec2 test_const_must_init_vec2() {
     // Const vec2 that is properly initialized
     return VECTOR_CONST;
 }
 
-// run: test_const_must_init_vec2() ~= vec2(1.0, 2.0) [expect-fail]
+// run: test_const_must_init_vec2() ~= vec2(1.0, 2.0)
 
 vec3 test_const_must_init_vec3() {
     // Const vec3 that is properly initialized
     return COLOR_CONST;
 }
 
-// run: test_const_must_init_vec3() ~= vec3(0.5, 0.5, 0.5) [expect-fail]
+// run: test_const_must_init_vec3() ~= vec3(0.5, 0.5, 0.5)
 
 mat2 test_const_must_init_mat2() {
     // Const mat2 that is properly initialized
     return MATRIX_CONST;
 }
 
-// run: test_const_must_init_mat2() ~= mat2(1.0, 0.0, 0.0, 1.0) [expect-fail]
+// run: test_const_must_init_mat2() ~= mat2(1.0, 0.0, 0.0, 1.0)
 
 float test_const_must_init_expressions() {
     // Const variables with complex expressions
@@ -19,14 +19,14 @@ float test_declare_const_float() {
     return PI * 2.0;
 }
 
-// run: test_declare_const_float() ~= 6.28318 [expect-fail]
+// run: test_declare_const_float() ~= 6.28318
 
 int test_declare_const_int() {
     // Const global int declaration
     return MAX_INT / 2;
 }
 
-// run: test_declare_const_int() == 1073741823 [expect-fail]
+// run: test_declare_const_int() == 1073741823
 
 uint test_declare_const_uint() {
     // Const global uint declaration
@@ -40,35 +40,35 @@ bool test_declare_const_bool() {
     return TRUE_CONST;
 }
 
-// run: test_declare_const_bool() == true [expect-fail]
+// run: test_declare_const_bool() == true
 
 vec2 test_declare_const_vec2() {
     // Const global vec2 declaration
     return UNIT_X * 2.0;
 }
 
-// run: test_declare_const_vec2() ~= vec2(2.0, 0.0) [expect-fail]
+// run: test_declare_const_vec2() ~= vec2(2.0, 0.0)
 
 vec3 test_declare_const_vec3() {
     // Const global vec3 declaration
     return UP_VECTOR + vec3(0.0, 0.0, 1.0);
 }
 
-// run: test_declare_const_vec3() ~= vec3(0.0, 1.0, 1.0) [expect-fail]
+// run: test_declare_const_vec3() ~= vec3(0.0, 1.0, 1.0)
 
 vec4 test_declare_const_vec4() {
     // Const global vec4 declaration
     return WHITE * 0.5;
 }
 
-// run: test_declare_const_vec4() ~= vec4(0.5, 0.5, 0.5, 0.5) [expect-fail]
+// run: test_declare_const_vec4() ~= vec4(0.5, 0.5, 0.5, 0.5)
 
 mat2 test_declare_const_mat2() {
     // Const global mat2 declaration
     return IDENTITY_2D * 2.0;
 }
 
-// run: test_declare_const_mat2() ~= mat2(2.0, 0.0, 0.0, 2.0) [expect-fail]
+// run: test_declare_const_mat2() ~= mat2(2.0, 0.0, 0.0, 2.0)
 
 float test_declare_const_calculated() {
     // Const globals used in calculations
@@ -78,4 +78,4 @@ float test_declare_const_calculated() {
     return CIRCUMFERENCE;
 }
 
-// run: test_declare_const_calculated() ~= 31.4159 [expect-fail]
+// run: test_declare_const_calculated() ~= 31.4159
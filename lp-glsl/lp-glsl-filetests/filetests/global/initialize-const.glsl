@@ -26,14 +26,14 @@ float test_initialize_const_float() {
     return PI;
 }
 
-// run: test_initialize_const_float() ~= 3.14159 [expect-fail]
+// run: test_initialize_const_float() ~= 3.14159
 
 int test_initialize_const_int() {
     // Const global int initialization
     return MAX_VALUE;
 }
 
-// run: test_initialize_const_int() == 1000 [expect-fail]
+// run: test_initialize_const_int() == 1000
 
 uint test_initialize_const_uint() {
     // Const global uint initialization
@@ -47,35 +47,35 @@ bool test_initialize_const_bool() {
     return ENABLED;
 }
 
-// run: test_initialize_const_bool() == true [expect-fail]
+// run: test_initialize_const_bool() == true
 
 vec2 test_initialize_const_vec2() {
     // Const global vec2 initialization
     return ORIGIN + vec2(1.0, 1.0);
 }
 
-// run: test_initialize_const_vec2() ~= vec2(1.0, 1.0) [expect-fail]
+// run: test_initialize_const_vec2() ~= vec2(1.0, 1.0)
 
 vec3 test_initialize_const_vec3() {
     // Const global vec3 initialization
     return UP + RIGHT;
 }
 
-// run: test_initialize_const_vec3() ~= vec3(1.0, 1.0, 0.0) [expect-fail]
+// run: test_initialize_const_vec3() ~= vec3(1.0, 1.0, 0.0)
 
 vec4 test_initialize_const_vec4() {
     // Const global vec4 initialization
     return BLACK;
 }
 
-// run: test_initialize_const_vec4() ~= vec4(0.0, 0.0, 0.0, 1.0) [expect-fail]
+// run: test_initialize_const_vec4() ~= vec4(0.0, 0.0, 0.0, 1.0)
 
 mat2 test_initialize_const_mat2() {
     // Const global mat2 initialization
     return IDENTITY;
 }
 
-// run: test_initialize_const_mat2() ~= mat2(1.0, 0.0, 0.0, 1.0) [expect-fail]
+// run: test_initialize_const_mat2() ~= mat2(1.0, 0.0, 0.0, 1.0)
 
 float test_initialize_const_expression() {
     // Const global with constant expressions
@@ -89,11 +89,11 @@ int test_initialize_const_int_expr() {
     return DOUBLE_MAX / 4;
 }
 
-// run: test_initialize_const_int_expr() == 500 [expect-fail]
+// run: test_initialize_const_int_expr() == 500
 
 vec3 test_initialize_const_vec_expr() {
     // Const global vectors with expressions
     return UP * 2.0 + RIGHT * 3.0 + FORWARD;
 }
 
-// run: test_initialize_const_vec_expr() ~= vec3(3.0, 2.0, 1.0) [expect-fail]
+// run: test_initialize_const_vec_expr() ~= vec3(3.0, 2.0, 1.0)
@@ -19,21 +19,21 @@ float test_shared_multiple_init_pi() {
     return SHARED_PI * 2.0;
 }
 
-// run: test_shared_multiple_init_pi() ~= 6.28318 [expect-fail]
+// run: test_shared_multiple_init_pi() ~= 6.28318
 
 vec3 test_shared_multiple_init_up() {
     // Access shared const UP vector
     return SHARED_UP;
 }
 
-// run: test_shared_multiple_init_up() ~= vec3(0.0, 1.0, 0.0) [expect-fail]
+// run: test_shared_multiple_init_up() ~= vec3(0.0, 1.0, 0.0)
 
 mat2 test_shared_multiple_init_rotate() {
     // Access shared const rotation matrix
     return SHARED_ROTATE_90;
 }
 
-// run: test_shared_multiple_init_rotate() ~= mat2(0.0, -1.0, 1.0, 0.0) [expect-fail]
+// run: test_shared_multiple_init_rotate() ~= mat2(0.0, -1.0, 1.0, 0.0)
 
 vec2 test_shared_multiple_init_apply_rotate() {
     // Apply shared rotation to a vector
@@ -42,7 +42,7 @@ vec2 test_shared_multiple_init_apply_rotate() {
     return rotated;
 }
 
-// run: test_shared_multiple_init_apply_rotate() ~= vec2(0.0, -1.0) [expect-fail]
+// run: test_shared_multiple_init_apply_rotate() ~= vec2(0.0, -1.0)
 
 float test_shared_multiple_init_trig() {
     // Use shared PI in trigonometric calculations
@@ -50,7 +50,7 @@ float test_shared_multiple_init_trig() {
     return angle * 2.0;
 }
 
-// run: test_shared_multiple_init_trig() ~= 1.570795 [expect-fail]
+// run: test_shared_multiple_init_trig() ~= 1.570795
 
 vec3 test_shared_multiple_init_cross() {
     // Use shared UP vector in cross product
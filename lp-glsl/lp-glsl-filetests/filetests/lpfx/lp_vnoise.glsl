@@ -0,0 +1,44 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// lpfx_vnoise(): Value noise functions
+// ============================================================================
+
+float test_lpfx_vnoise_2d() {
+    // Test 2D value noise - should be in [0, 1]
+    vec2 p = vec2(0.5, 0.5);
+    uint seed = 0u;
+    float n = lpfx_vnoise(p, seed);
+    return (n >= 0.0 && n <= 1.0) ? 1.0 : 0.0;
+}
+
+// run: test_lpfx_vnoise_2d() == 1.0
+
+float test_lpfx_vnoise_deterministic() {
+    // Same inputs should produce same output
+    float n1 = lpfx_vnoise(vec2(3.25, -1.75), 7u);
+    float n2 = lpfx_vnoise(vec2(3.25, -1.75), 7u);
+    return n1 == n2 ? 1.0 : 0.0;
+}
+
+// run: test_lpfx_vnoise_deterministic() == 1.0
+
+float test_lpfx_vnoise_different_seeds() {
+    // Different seeds should produce different outputs
+    float diff1 = abs(lpfx_vnoise(vec2(0.5, 0.5), 0u) - lpfx_vnoise(vec2(0.5, 0.5), 1u));
+    float diff2 = abs(lpfx_vnoise(vec2(1.5, 2.5), 0u) - lpfx_vnoise(vec2(1.5, 2.5), 1u));
+    bool has_diff = diff1 > 0.01 || diff2 > 0.01;
+    return has_diff ? 1.0 : 0.0;
+}
+
+// run: test_lpfx_vnoise_different_seeds() == 1.0
+
+float test_lpfx_vnoise_smoothness() {
+    // Small changes in input produce small changes in output, including across a cell edge
+    float n1 = lpfx_vnoise(vec2(0.99, 0.5), 0u);
+    float n2 = lpfx_vnoise(vec2(1.01, 0.5), 0u);
+    return abs(n1 - n2) < 0.05 ? 1.0 : 0.0;
+}
+
+// run: test_lpfx_vnoise_smoothness() == 1.0
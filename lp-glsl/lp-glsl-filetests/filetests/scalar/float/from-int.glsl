@@ -81,3 +81,9 @@ float test_float_from_int_min() {
 
 // run: test_float_from_int_min() ~= -32768.0
 
+
+float test_float_from_int_literal() {
+    return float(5);
+}
+
+// run: test_float_from_int_literal() ~= 5.0
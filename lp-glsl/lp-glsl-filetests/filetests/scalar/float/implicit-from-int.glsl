@@ -0,0 +1,72 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Implicit conversion: int promotes to float in mixed expressions
+// ============================================================================
+
+float test_implicit_int_plus_float() {
+    return 1 + 2.0;
+}
+
+// run: test_implicit_int_plus_float() ~= 3.0
+
+float test_implicit_float_times_int_variable() {
+    int i = 3;
+    float f = 1.5;
+    return f * i;
+}
+
+// run: test_implicit_float_times_int_variable() ~= 4.5
+
+float test_implicit_int_assigned_to_float() {
+    float f = 7;
+    return f;
+}
+
+// run: test_implicit_int_assigned_to_float() ~= 7.0
+
+float half_of(float x) {
+    return x / 2.0;
+}
+
+float test_implicit_int_argument_to_float_param() {
+    int i = 5;
+    return half_of(i);
+}
+
+// run: test_implicit_int_argument_to_float_param() ~= 2.5
+
+float test_implicit_int_return_as_float() {
+    int i = 4;
+    return i;
+}
+
+// run: test_implicit_int_return_as_float() ~= 4.0
+
+bool test_implicit_int_compared_to_float() {
+    return 2 == 2.0;
+}
+
+// run: test_implicit_int_compared_to_float() == true
+
+vec3 test_implicit_vec3_times_int() {
+    return vec3(1.0, 2.0, 3.0) * 2;
+}
+
+// run: test_implicit_vec3_times_int() ~= vec3(2.0, 4.0, 6.0)
+
+vec2 test_implicit_ivec2_plus_float() {
+    ivec2 v = ivec2(1, 2);
+    return v + 0.5;
+}
+
+// run: test_implicit_ivec2_plus_float() ~= vec2(1.5, 2.5)
+
+float test_implicit_compound_float_plus_int() {
+    float f = 1.5;
+    f += 2;
+    return f;
+}
+
+// run: test_implicit_compound_float_plus_int() ~= 3.5
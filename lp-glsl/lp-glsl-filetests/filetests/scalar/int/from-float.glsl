@@ -81,3 +81,15 @@ int test_int_from_float_expression_negative() {
 
 // run: test_int_from_float_expression_negative() == -2
 
+
+int test_int_from_float_literal_truncate() {
+    return int(3.7);
+}
+
+// run: test_int_from_float_literal_truncate() == 3
+
+int test_int_from_float_literal_negative_truncate() {
+    return int(-3.7);
+}
+
+// run: test_int_from_float_literal_negative_truncate() == -3
@@ -0,0 +1,40 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Bitwise AND: int & int -> int
+// ============================================================================
+
+int test_int_bit_and_simple() {
+    return 12 & 10;
+}
+
+// run: test_int_bit_and_simple() == 8
+
+int test_int_bit_and_mask() {
+    int x = 0x12345678;
+    return x & 0xFF;
+}
+
+// run: test_int_bit_and_mask() == 120
+
+int test_int_bit_and_negative() {
+    // -1 has every bit set
+    return -1 & 0x7F;
+}
+
+// run: test_int_bit_and_negative() == 127
+
+int test_int_bit_and_sign_bits() {
+    return -8 & -12;
+}
+
+// run: test_int_bit_and_sign_bits() == -16
+
+int test_int_bit_and_assign() {
+    int x = 255;
+    x &= 0x0F;
+    return x;
+}
+
+// run: test_int_bit_and_assign() == 15
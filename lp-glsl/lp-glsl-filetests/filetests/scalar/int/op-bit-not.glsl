@@ -0,0 +1,38 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Bitwise complement: ~int -> int
+// ============================================================================
+
+int test_int_complement_zero() {
+    return ~0;
+}
+
+// run: test_int_complement_zero() == -1
+
+int test_int_complement_positive() {
+    int x = 41;
+    return ~x;
+}
+
+// run: test_int_complement_positive() == -42
+
+int test_int_complement_negative() {
+    return ~-100;
+}
+
+// run: test_int_complement_negative() == 99
+
+int test_int_complement_twice() {
+    int x = 0x1234;
+    return ~~x;
+}
+
+// run: test_int_complement_twice() == 4660
+
+ivec2 test_ivec2_complement() {
+    return ~ivec2(0, 7);
+}
+
+// run: test_ivec2_complement() == ivec2(-1, -8)
@@ -0,0 +1,35 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Bitwise OR: int | int -> int
+// ============================================================================
+
+int test_int_bit_or_simple() {
+    return 12 | 10;
+}
+
+// run: test_int_bit_or_simple() == 14
+
+int test_int_bit_or_disjoint() {
+    int high = 0x0F00;
+    int low = 0x00F0;
+    return high | low;
+}
+
+// run: test_int_bit_or_disjoint() == 4080
+
+int test_int_bit_or_negative() {
+    return -16 | 5;
+}
+
+// run: test_int_bit_or_negative() == -11
+
+int test_int_bit_or_assign() {
+    int flags = 1;
+    flags |= 4;
+    flags |= 1;
+    return flags;
+}
+
+// run: test_int_bit_or_assign() == 5
@@ -0,0 +1,35 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Bitwise XOR: int ^ int -> int
+// ============================================================================
+
+int test_int_bit_xor_simple() {
+    return 12 ^ 10;
+}
+
+// run: test_int_bit_xor_simple() == 6
+
+int test_int_bit_xor_self() {
+    int x = 123456;
+    return x ^ x;
+}
+
+// run: test_int_bit_xor_self() == 0
+
+int test_int_bit_xor_minus_one() {
+    // XOR with all ones is the complement
+    return 5 ^ -1;
+}
+
+// run: test_int_bit_xor_minus_one() == -6
+
+int test_int_bit_xor_assign_twice() {
+    int x = 1000;
+    x ^= 0x5A5A;
+    x ^= 0x5A5A;
+    return x;
+}
+
+// run: test_int_bit_xor_assign_twice() == 1000
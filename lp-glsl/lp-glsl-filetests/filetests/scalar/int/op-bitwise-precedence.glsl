@@ -0,0 +1,48 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Precedence: + before <<, << before <, < before &, & before ^, ^ before |
+// ============================================================================
+
+int test_precedence_add_before_shift() {
+    // 1 << (2 + 1)
+    return 1 << 2 + 1;
+}
+
+// run: test_precedence_add_before_shift() == 8
+
+int test_precedence_and_before_or() {
+    // 1 | (6 & 3)
+    return 1 | 6 & 3;
+}
+
+// run: test_precedence_and_before_or() == 3
+
+int test_precedence_xor_before_or() {
+    // 8 | (5 ^ 1)
+    return 8 | 5 ^ 1;
+}
+
+// run: test_precedence_xor_before_or() == 12
+
+int test_precedence_and_before_xor() {
+    // 5 ^ (3 & 6)
+    return 5 ^ 3 & 6;
+}
+
+// run: test_precedence_and_before_xor() == 7
+
+bool test_precedence_shift_before_compare() {
+    // (1 << 4) > 15
+    return 1 << 4 > 15;
+}
+
+// run: test_precedence_shift_before_compare() == true
+
+int test_precedence_unary_complement() {
+    // (~0) << 4
+    return ~0 << 4;
+}
+
+// run: test_precedence_unary_complement() == -16
@@ -0,0 +1,57 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Left shift: int << int -> int
+// Shift amounts use their low 5 bits, so shifting by 32 leaves the value unchanged
+// ============================================================================
+
+int test_int_shift_left_simple() {
+    return 3 << 4;
+}
+
+// run: test_int_shift_left_simple() == 48
+
+int test_int_shift_left_negative_value() {
+    int x = -5;
+    return x << 2;
+}
+
+// run: test_int_shift_left_negative_value() == -20
+
+int test_int_shift_left_into_sign_bit() {
+    return 1 << 31;
+}
+
+// run: test_int_shift_left_into_sign_bit() == -2147483648
+
+int test_int_shift_left_out_of_range() {
+    // Bits shifted past bit 31 are lost
+    return 0x40000001 << 2;
+}
+
+// run: test_int_shift_left_out_of_range() == 4
+
+int test_int_shift_left_by_bit_width() {
+    int x = 7;
+    int amount = 32;
+    return x << amount;
+}
+
+// run: test_int_shift_left_by_bit_width() == 7
+
+int test_int_shift_left_by_33() {
+    int x = 7;
+    int amount = 33;
+    return x << amount;
+}
+
+// run: test_int_shift_left_by_33() == 14
+
+int test_int_shift_left_assign() {
+    int x = 1;
+    x <<= 10;
+    return x;
+}
+
+// run: test_int_shift_left_assign() == 1024
@@ -0,0 +1,50 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Right shift: int >> int -> int (arithmetic, the sign bit is copied in)
+// Shift amounts use their low 5 bits, so shifting by 32 leaves the value unchanged
+// ============================================================================
+
+int test_int_shift_right_simple() {
+    return 100 >> 2;
+}
+
+// run: test_int_shift_right_simple() == 25
+
+int test_int_shift_right_negative() {
+    int x = -100;
+    return x >> 2;
+}
+
+// run: test_int_shift_right_negative() == -25
+
+int test_int_shift_right_rounds_down() {
+    // Arithmetic shift rounds toward negative infinity, unlike division
+    return -7 >> 1;
+}
+
+// run: test_int_shift_right_rounds_down() == -4
+
+int test_int_shift_right_by_31() {
+    int x = -2;
+    return x >> 31;
+}
+
+// run: test_int_shift_right_by_31() == -1
+
+int test_int_shift_right_by_bit_width() {
+    int x = -64;
+    int amount = 32;
+    return x >> amount;
+}
+
+// run: test_int_shift_right_by_bit_width() == -64
+
+int test_int_shift_right_assign() {
+    int x = 4096;
+    x >>= 6;
+    return x;
+}
+
+// run: test_int_shift_right_assign() == 64
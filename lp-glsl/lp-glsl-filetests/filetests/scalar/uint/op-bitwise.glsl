@@ -0,0 +1,82 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Bitwise: &, |, ^, ~ and << on uint and int
+// ============================================================================
+
+uint test_uint_bit_and() {
+    return 0xF0F0u & 0xFF00u;
+}
+
+// run: test_uint_bit_and() == 61440u
+
+uint test_uint_bit_or() {
+    return 0xF0u | 0x0Fu;
+}
+
+// run: test_uint_bit_or() == 255u
+
+uint test_uint_bit_xor() {
+    uint a = 0xFFu;
+    uint b = 0x0Fu;
+    return a ^ b;
+}
+
+// run: test_uint_bit_xor() == 240u
+
+uint test_uint_complement() {
+    return ~0xFu;
+}
+
+// run: test_uint_complement() == 4294967280u
+
+int test_int_complement() {
+    return ~5;
+}
+
+// run: test_int_complement() == -6
+
+uint test_uint_shift_left() {
+    uint x = 1u;
+    return x << 31u;
+}
+
+// run: test_uint_shift_left() == 2147483648u
+
+int test_int_shift_left_into_sign() {
+    return 3 << 30;
+}
+
+// run: test_int_shift_left_into_sign() == -1073741824
+
+uint test_uint_mixed_int() {
+    // int & uint promotes to uint
+    uint mask = 0xFFu;
+    int value = -1;
+    return value & mask;
+}
+
+// run: test_uint_mixed_int() == 255u
+
+uint test_uint_compound_assign() {
+    uint x = 0u;
+    x |= 0x0Fu;
+    x <<= 4u;
+    x ^= 0xFFu;
+    x &= 0x3Cu;
+    return x;
+}
+
+// run: test_uint_compound_assign() == 12u
+
+uint test_uint_hash() {
+    // Typical bit-mixing step from a hash function
+    uint x = 12345u;
+    x ^= x >> 16u;
+    x *= 0x45d9f3bu;
+    x ^= x >> 16u;
+    return x;
+}
+
+// run: test_uint_hash() == 2259878544u
@@ -0,0 +1,75 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Comparison: uint compares unsigned (bltu/sltu), int compares signed
+// ============================================================================
+
+bool test_uint_less_than_high_bit() {
+    // 0x80000000u is 2147483648u, larger than 1u
+    uint big = 0x80000000u;
+    return big < 1u;
+}
+
+// run: test_uint_less_than_high_bit() == false
+
+bool test_int_less_than_high_bit() {
+    // The same bits as an int are -2147483648, smaller than 1
+    int big = int(0x80000000u);
+    return big < 1;
+}
+
+// run: test_int_less_than_high_bit() == true
+
+bool test_uint_greater_than_all_ones() {
+    uint x = uint(-1);
+    return x > 100u;
+}
+
+// run: test_uint_greater_than_all_ones() == true
+
+bool test_int_greater_than_minus_one() {
+    int x = -1;
+    return x > 100;
+}
+
+// run: test_int_greater_than_minus_one() == false
+
+bool test_uint_less_equal_wrapped() {
+    // 0u - 1u wraps to the largest uint
+    uint x = 0u - 1u;
+    return x <= 0u;
+}
+
+// run: test_uint_less_equal_wrapped() == false
+
+bool test_uint_greater_equal_wrapped() {
+    uint x = 0u - 1u;
+    return x >= 4294967295u;
+}
+
+// run: test_uint_greater_equal_wrapped() == true
+
+bvec2 test_uvec2_less_than_high_bit() {
+    uvec2 a = uvec2(0x80000000u, 1u);
+    uvec2 b = uvec2(1u, 0x80000000u);
+    return lessThan(a, b);
+}
+
+// run: test_uvec2_less_than_high_bit() == bvec2(false, true)
+
+bvec2 test_ivec2_less_than_high_bit() {
+    ivec2 a = ivec2(int(0x80000000u), 1);
+    ivec2 b = ivec2(1, int(0x80000000u));
+    return lessThan(a, b);
+}
+
+// run: test_ivec2_less_than_high_bit() == bvec2(true, false)
+
+bvec2 test_uvec2_greater_equal_high_bit() {
+    uvec2 a = uvec2(4294967295u, 0u);
+    uvec2 b = uvec2(0u, 4294967295u);
+    return greaterThanEqual(a, b);
+}
+
+// run: test_uvec2_greater_equal_high_bit() == bvec2(true, false)
@@ -0,0 +1,75 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Right Shift: uint >> is logical (srl), int >> is arithmetic (sra)
+// ============================================================================
+
+uint test_uint_shift_right_simple() {
+    return 40u >> 3u;
+}
+
+// run: test_uint_shift_right_simple() == 5u
+
+uint test_uint_shift_right_high_bit() {
+    // The sign bit is shifted out like any other bit
+    uint x = 0x80000000u;
+    return x >> 4u;
+}
+
+// run: test_uint_shift_right_high_bit() == 134217728u
+
+int test_int_shift_right_high_bit() {
+    // Same bits as above, but the sign is extended
+    int x = int(0x80000000u);
+    return x >> 4;
+}
+
+// run: test_int_shift_right_high_bit() == -134217728
+
+uint test_uint_shift_right_all_ones() {
+    uint x = 4294967295u;
+    return x >> 28u;
+}
+
+// run: test_uint_shift_right_all_ones() == 15u
+
+int test_int_shift_right_minus_one() {
+    int x = -1;
+    return x >> 28;
+}
+
+// run: test_int_shift_right_minus_one() == -1
+
+uint test_uint_shift_right_int_amount() {
+    // The left operand decides the kind of shift, not the amount
+    uint x = 4294967280u;
+    int amount = 4;
+    return x >> amount;
+}
+
+// run: test_uint_shift_right_int_amount() == 268435455u
+
+int test_int_shift_right_uint_amount() {
+    int x = -16;
+    uint amount = 2u;
+    return x >> amount;
+}
+
+// run: test_int_shift_right_uint_amount() == -4
+
+uint test_uint_shift_right_assign() {
+    uint x = 4294967295u;
+    x >>= 31u;
+    return x;
+}
+
+// run: test_uint_shift_right_assign() == 1u
+
+int test_int_shift_right_assign() {
+    int x = -2147483648;
+    x >>= 31;
+    return x;
+}
+
+// run: test_int_shift_right_assign() == -1
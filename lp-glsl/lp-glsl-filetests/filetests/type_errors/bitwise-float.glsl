@@ -0,0 +1,12 @@
+// test error
+// target riscv32.q32
+
+int test_bitwise_float() {
+    float f = 1.0;
+    // This should fail - bitwise operators only take integers
+    return 3 & f;
+}
+
+// EXPECT_ERROR_CODE: E0106
+// EXPECT_ERROR: requires integer operands
+// EXPECT_LOCATION: 7
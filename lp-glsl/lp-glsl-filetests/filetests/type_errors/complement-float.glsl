@@ -0,0 +1,12 @@
+// test error
+// target riscv32.q32
+
+float test_complement_float() {
+    float f = 2.0;
+    // This should fail - ~ only takes integers
+    return ~f;
+}
+
+// EXPECT_ERROR_CODE: E0112
+// EXPECT_ERROR: bitwise complement requires integer operand
+// EXPECT_LOCATION: 7
@@ -0,0 +1,14 @@
+// test error
+// target riscv32.q32
+
+const float PI = 3.14159;
+
+float test_const_assign() {
+    // This should fail - constants are read-only
+    PI = 3.0;
+    return PI;
+}
+
+// EXPECT_ERROR_CODE: E0115
+// EXPECT_ERROR: cannot assign to const `PI`
+// EXPECT_LOCATION: 8
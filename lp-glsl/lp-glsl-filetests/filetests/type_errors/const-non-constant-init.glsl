@@ -0,0 +1,12 @@
+// test error
+// target riscv32.q32
+
+float test_const_non_constant_init(float x) {
+    // This should fail - const initializers must be constant expressions
+    const float DOUBLED = x * 2.0;
+    return DOUBLED;
+}
+
+// EXPECT_ERROR_CODE: E0118
+// EXPECT_ERROR: initializer of const `DOUBLED` is not a constant expression
+// EXPECT_LOCATION: 6
@@ -0,0 +1,15 @@
+// test error
+// target riscv32.q32
+
+int twice(int x) {
+    return x * 2;
+}
+
+int test_implicit_float_to_int_argument() {
+    // This should fail - float arguments do not implicitly narrow to int parameters
+    return twice(1.5);
+}
+
+// EXPECT_ERROR_CODE: E0114
+// EXPECT_ERROR: no matching overload for function `twice`
+// EXPECT_LOCATION: 10
@@ -0,0 +1,12 @@
+// test error
+// target riscv32.q32
+
+int test_implicit_float_to_int() {
+    // This should fail - float does not implicitly narrow to int
+    int i = 1.5;
+    return i;
+}
+
+// EXPECT_ERROR_CODE: E0102
+// EXPECT_ERROR: type mismatch in assignment
+// EXPECT_LOCATION: 6
@@ -0,0 +1,11 @@
+// test error
+// target riscv32.q32
+
+int test_shift_scalar_by_vector() {
+    // This should fail - a scalar can't be shifted by a vector
+    return 1 << ivec2(1, 2);
+}
+
+// EXPECT_ERROR_CODE: E0106
+// EXPECT_ERROR: requires integer operands
+// EXPECT_LOCATION: 6
@@ -0,0 +1,14 @@
+// test error
+// target riscv32.q32
+
+uniform float speed;
+
+float test_uniform_assign() {
+    // This should fail - uniforms are read-only
+    speed = 2.0;
+    return speed;
+}
+
+// EXPECT_ERROR_CODE: E0115
+// EXPECT_ERROR: cannot assign to uniform `speed`
+// EXPECT_LOCATION: 8
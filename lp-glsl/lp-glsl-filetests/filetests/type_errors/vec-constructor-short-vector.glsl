@@ -0,0 +1,12 @@
+// test error
+// target riscv32.q32
+
+vec4 test_vec4_from_vec3() {
+    vec3 v = vec3(1.0, 2.0, 3.0);
+    // This should fail - a single vec3 cannot fill a vec4
+    return vec4(v);
+}
+
+// EXPECT_ERROR_CODE: E0110
+// EXPECT_ERROR: `vec4` constructor expects 4 components, found 3
+// EXPECT_LOCATION: 7
@@ -0,0 +1,11 @@
+// test error
+// target riscv32.q32
+
+vec3 test_vec3_too_few_components() {
+    // This should fail - vec3 needs 3 components, only 2 are provided
+    return vec3(1.0, 2.0);
+}
+
+// EXPECT_ERROR_CODE: E0110
+// EXPECT_ERROR: `vec3` constructor expects 3 components, found 2
+// EXPECT_LOCATION: 6
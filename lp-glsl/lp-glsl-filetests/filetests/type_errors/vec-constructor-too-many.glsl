@@ -0,0 +1,11 @@
+// test error
+// target riscv32.q32
+
+vec2 test_vec2_too_many_arguments() {
+    // This should fail - the third argument is never used
+    return vec2(1.0, 2.0, 3.0);
+}
+
+// EXPECT_ERROR_CODE: E0110
+// EXPECT_ERROR: too many arguments to `vec2` constructor
+// EXPECT_LOCATION: 6
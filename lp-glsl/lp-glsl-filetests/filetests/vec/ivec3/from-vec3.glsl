@@ -0,0 +1,33 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// From vec3: ivec3(vec3) - componentwise float-to-int truncation toward zero
+// ============================================================================
+
+ivec3 test_ivec3_from_vec3_truncate() {
+    vec3 v = vec3(3.7, -3.7, 0.5);
+    return ivec3(v);
+}
+
+// run: test_ivec3_from_vec3_truncate() == ivec3(3, -3, 0)
+
+ivec3 test_ivec3_from_vec3_whole() {
+    return ivec3(vec3(1.0, -2.0, 100.0));
+}
+
+// run: test_ivec3_from_vec3_whole() == ivec3(1, -2, 100)
+
+ivec3 test_ivec3_from_vec4_shortening() {
+    vec4 v = vec4(1.9, 2.9, 3.9, 4.9);
+    return ivec3(v);
+}
+
+// run: test_ivec3_from_vec4_shortening() == ivec3(1, 2, 3)
+
+int test_int_from_vec3_first_component() {
+    vec3 v = vec3(7.9, 8.0, 9.0);
+    return int(v);
+}
+
+// run: test_int_from_vec3_first_component() == 7
@@ -0,0 +1,42 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// Shift: uvec2 >> and << (logical), compared with ivec2 (arithmetic)
+// ============================================================================
+
+uvec2 test_uvec2_shift_right_scalar() {
+    uvec2 v = uvec2(0x80000000u, 64u);
+    return v >> 4u;
+}
+
+// run: test_uvec2_shift_right_scalar() == uvec2(134217728u, 4u)
+
+ivec2 test_ivec2_shift_right_scalar() {
+    ivec2 v = ivec2(int(0x80000000u), 64);
+    return v >> 4;
+}
+
+// run: test_ivec2_shift_right_scalar() == ivec2(-134217728, 4)
+
+uvec2 test_uvec2_shift_component_wise() {
+    uvec2 v = uvec2(4294967295u, 4294967295u);
+    return v >> uvec2(28u, 31u);
+}
+
+// run: test_uvec2_shift_component_wise() == uvec2(15u, 1u)
+
+uvec2 test_uvec2_shift_left_assign() {
+    uvec2 v = uvec2(1u, 3u);
+    v <<= 30u;
+    return v;
+}
+
+// run: test_uvec2_shift_left_assign() == uvec2(1073741824u, 3221225472u)
+
+uvec2 test_uvec2_bit_and_scalar() {
+    uvec2 v = uvec2(0x1234u, 0xABCDu);
+    return v & 0xFFu;
+}
+
+// run: test_uvec2_bit_and_scalar() == uvec2(52u, 205u)
@@ -0,0 +1,27 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// From ivec3: vec3(ivec3) - componentwise int-to-float conversion
+// ============================================================================
+
+vec3 test_vec3_from_ivec3() {
+    ivec3 v = ivec3(5, -2, 0);
+    return vec3(v);
+}
+
+// run: test_vec3_from_ivec3() ~= vec3(5.0, -2.0, 0.0)
+
+vec3 test_vec3_from_ivec3_roundtrip() {
+    vec3 v = vec3(1.5, -2.5, 3.25);
+    return vec3(ivec3(v));
+}
+
+// run: test_vec3_from_ivec3_roundtrip() ~= vec3(1.0, -2.0, 3.0)
+
+float test_float_from_ivec3_first_component() {
+    ivec3 v = ivec3(4, 5, 6);
+    return float(v);
+}
+
+// run: test_float_from_ivec3_first_component() ~= 4.0
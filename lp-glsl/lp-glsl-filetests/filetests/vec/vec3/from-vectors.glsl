@@ -0,0 +1,39 @@
+// test run
+// target riscv32.q32
+
+// ============================================================================
+// From Vectors: vec3(vec2, float), vec3(float, vec2), vec3(vec2, vec2)
+// ============================================================================
+
+vec3 test_vec3_from_vec2_float() {
+    return vec3(vec2(1.0, 2.0), 3.0);
+}
+
+// run: test_vec3_from_vec2_float() ~= vec3(1.0, 2.0, 3.0)
+
+vec3 test_vec3_from_float_vec2() {
+    return vec3(1.0, vec2(2.0, 3.0));
+}
+
+// run: test_vec3_from_float_vec2() ~= vec3(1.0, 2.0, 3.0)
+
+vec3 test_vec3_from_vec4_shortening() {
+    return vec3(vec4(1.0, 2.0, 3.0, 4.0));
+}
+
+// run: test_vec3_from_vec4_shortening() ~= vec3(1.0, 2.0, 3.0)
+
+vec3 test_vec3_from_vec2_vec2_partial() {
+    // Only the first component of the last argument is used
+    vec2 a = vec2(1.0, 2.0);
+    vec2 b = vec2(3.0, 4.0);
+    return vec3(a, b);
+}
+
+// run: test_vec3_from_vec2_vec2_partial() ~= vec3(1.0, 2.0, 3.0)
+
+vec3 test_vec3_from_int_components() {
+    return vec3(1, ivec2(2, 3));
+}
+
+// run: test_vec3_from_int_components() ~= vec3(1.0, 2.0, 3.0)
@@ -8,7 +8,7 @@ use crate::test_run::record_failure;
 use crate::test_run::target;
 use anyhow::Result;
 use lp_glsl_compiler::glsl_emu_riscv32_with_metadata;
-use lp_glsl_compiler::{GlslOptions, RunMode};
+use lp_glsl_compiler::{GlslOptions, OptLevel, RunMode};
 use lp_riscv_emu::LogLevel;
 use std::path::Path;
 
@@ -44,6 +44,10 @@ pub fn run(
     let options = GlslOptions {
         run_mode,
         decimal_format,
+        opt_level: OptLevel::None,
+        complexity_budget: None,
+        loop_fuel: None,
+        warnings_as_errors: false,
     };
 
     // Count total test cases before compilation (so we can show counts even if compilation fails)
@@ -0,0 +1,100 @@
+//! Converting compiler diagnostics to LSP diagnostics
+
+use lp_glsl_compiler::{Diagnostic, GlslOptions, Severity, glsl_check};
+use serde_json::{Value, json};
+
+/// Check `source` and return its LSP diagnostics
+///
+/// Compilation stops at the first error, so there is at most one error, reported
+/// instead of any warnings.
+pub fn check(source: &str) -> Vec<Value> {
+    let diagnostics = match glsl_check(source, &GlslOptions::jit()) {
+        Ok(warnings) => warnings,
+        Err(error) => vec![Diagnostic::from(error)],
+    };
+    diagnostics
+        .iter()
+        .map(|diagnostic| to_lsp(diagnostic, source))
+        .collect()
+}
+
+fn to_lsp(diagnostic: &Diagnostic, source: &str) -> Value {
+    let details = &diagnostic.details;
+    let mut message = details.message.clone();
+    for note in &details.notes {
+        message.push('\n');
+        message.push_str(note);
+    }
+    let severity = match diagnostic.severity {
+        Severity::Error => 1,
+        Severity::Warning => 2,
+    };
+    let (line, column) = details
+        .location
+        .as_ref()
+        .filter(|loc| !loc.is_unknown())
+        .map_or((1, 1), |loc| (loc.line, loc.column));
+
+    json!({
+        "range": range(source, line, column),
+        "severity": severity,
+        "code": details.code.as_str(),
+        "source": "lp-glsl",
+        "message": message,
+    })
+}
+
+/// LSP range of the token at a 1-indexed compiler location
+///
+/// Compiler locations are the start of a construct, so the range runs to the end of
+/// the identifier or number there (or covers a single character otherwise). LSP
+/// positions are 0-indexed and count UTF-16 code units.
+pub fn range(source: &str, line: usize, column: usize) -> Value {
+    let line_index = line.saturating_sub(1);
+    let text = source.lines().nth(line_index).unwrap_or("");
+    let start: Vec<char> = text.chars().take(column.saturating_sub(1)).collect();
+    let rest: Vec<char> = text.chars().skip(start.len()).collect();
+
+    let numeric = rest
+        .first()
+        .is_some_and(|c| c.is_ascii_digit() || *c == '.');
+    let token_len = rest
+        .iter()
+        .take_while(|c| c.is_alphanumeric() || **c == '_' || (numeric && **c == '.'))
+        .count()
+        .max(rest.len().min(1));
+
+    let start_character = utf16_len(&start);
+    let end_character = start_character + utf16_len(&rest[..token_len]);
+    json!({
+        "start": {"line": line_index, "character": start_character},
+        "end": {"line": line_index, "character": end_character},
+    })
+}
+
+fn utf16_len(chars: &[char]) -> usize {
+    chars.iter().map(|c| c.len_utf16()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(range: Value) -> (u64, u64, u64) {
+        (
+            range["start"]["line"].as_u64().unwrap(),
+            range["start"]["character"].as_u64().unwrap(),
+            range["end"]["character"].as_u64().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_token_ranges() {
+        let source = "int f() {\n    int count = 1.5 + x;\n}";
+        assert_eq!(span(range(source, 2, 9)), (1, 8, 13));
+        assert_eq!(span(range(source, 2, 17)), (1, 16, 19));
+        assert_eq!(span(range(source, 2, 21)), (1, 20, 21));
+        // Past the end of the line
+        assert_eq!(span(range(source, 1, 40)), (0, 9, 9));
+    }
+}
@@ -0,0 +1,12 @@
+//! Language server for lp-glsl shaders.
+//!
+//! Speaks the Language Server Protocol over any reader/writer pair (stdio in the
+//! `lp-glsl-lsp` binary). Documents are synced in full, and every open or change
+//! re-checks the shader and publishes its errors and warnings as diagnostics.
+//! Hover and completion aren't implemented yet.
+
+pub mod diagnostics;
+pub mod server;
+pub mod transport;
+
+pub use server::{Server, run};
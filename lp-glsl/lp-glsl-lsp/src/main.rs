@@ -0,0 +1,6 @@
+//! GLSL language server over stdio
+
+fn main() -> std::io::Result<()> {
+    let stdin = std::io::stdin();
+    lp_glsl_lsp::run(stdin.lock(), std::io::stdout().lock())
+}
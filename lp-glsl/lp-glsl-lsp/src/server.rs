@@ -0,0 +1,126 @@
+//! Request and notification handling
+
+use crate::diagnostics;
+use crate::transport::{read_message, write_message};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+/// JSON-RPC error code for requests the server doesn't implement
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// LSP state: the text of each open document, by URI
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<String, String>,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle one incoming message, returning the messages to send back
+    ///
+    /// Returns None once the client has sent `exit`.
+    pub fn handle(&mut self, message: &Value) -> Option<Vec<Value>> {
+        let method = message["method"].as_str().unwrap_or("");
+        let params = &message["params"];
+        let id = message.get("id");
+
+        let mut out = Vec::new();
+        match method {
+            "initialize" => out.push(response(
+                id,
+                json!({
+                    "capabilities": {
+                        // Full document sync
+                        "textDocumentSync": 1,
+                    },
+                    "serverInfo": {"name": "lp-glsl-lsp"},
+                }),
+            )),
+            "shutdown" => out.push(response(id, Value::Null)),
+            "exit" => return None,
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                let uri = document["uri"].as_str().unwrap_or_default();
+                let text = document["text"].as_str().unwrap_or_default();
+                out.push(self.update(uri, text.to_string()));
+            }
+            "textDocument/didChange" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                // With full sync the last change holds the whole document
+                if let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                {
+                    out.push(self.update(uri, text.to_string()));
+                }
+            }
+            "textDocument/didSave" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                // Clients only send the text when asked to; otherwise re-check what we have
+                let text = params["text"]
+                    .as_str()
+                    .map(str::to_string)
+                    .or_else(|| self.documents.get(uri).cloned());
+                if let Some(text) = text {
+                    out.push(self.update(uri, text));
+                }
+            }
+            "textDocument/didClose" => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                self.documents.remove(uri);
+                out.push(publish_diagnostics(uri, Vec::new()));
+            }
+            _ => {
+                // Other notifications (`initialized`, ...) need no reply
+                if let Some(id) = id {
+                    out.push(json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": METHOD_NOT_FOUND,
+                            "message": format!("method not found: {method}"),
+                        },
+                    }));
+                }
+            }
+        }
+        Some(out)
+    }
+
+    fn update(&mut self, uri: &str, text: String) -> Value {
+        let diagnostics = diagnostics::check(&text);
+        self.documents.insert(uri.to_string(), text);
+        publish_diagnostics(uri, diagnostics)
+    }
+}
+
+/// Serve LSP messages from `reader` until `exit` or end of input
+pub fn run(mut reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+    let mut server = Server::new();
+    while let Some(message) = read_message(&mut reader)? {
+        let Some(replies) = server.handle(&message) else {
+            break;
+        };
+        for reply in &replies {
+            write_message(&mut writer, reply)?;
+        }
+    }
+    Ok(())
+}
+
+fn response(id: Option<&Value>, result: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "result": result})
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {"uri": uri, "diagnostics": diagnostics},
+    })
+}
@@ -0,0 +1,147 @@
+//! Drive the server through a full session over in-memory stdio
+
+use lp_glsl_lsp::run;
+use lp_glsl_lsp::transport::{read_message, write_message};
+use serde_json::{Value, json};
+
+const URI: &str = "file:///shaders/test.glsl";
+
+/// Run a session with `messages` and return everything the server sent
+fn session(messages: &[Value]) -> Vec<Value> {
+    let mut input = Vec::new();
+    for message in messages {
+        write_message(&mut input, message).unwrap();
+    }
+    let mut output = Vec::new();
+    run(input.as_slice(), &mut output).unwrap();
+
+    let mut reader = output.as_slice();
+    let mut sent = Vec::new();
+    while let Some(message) = read_message(&mut reader).unwrap() {
+        sent.push(message);
+    }
+    sent
+}
+
+fn did_open(text: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {
+            "textDocument": {"uri": URI, "languageId": "glsl", "version": 1, "text": text},
+        },
+    })
+}
+
+fn published(sent: &[Value]) -> Vec<&Value> {
+    sent.iter()
+        .filter(|m| m["method"] == "textDocument/publishDiagnostics")
+        .collect()
+}
+
+#[test]
+fn test_did_open_publishes_type_error() {
+    let source = "int f() {\n    int i = 1.5;\n    return i;\n}\n";
+    let sent = session(&[
+        json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+        json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}),
+        did_open(source),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+        json!({"jsonrpc": "2.0", "method": "exit"}),
+    ]);
+
+    assert_eq!(sent[0]["id"], 1);
+    assert_eq!(sent[0]["result"]["capabilities"]["textDocumentSync"], 1);
+
+    let published = published(&sent);
+    assert_eq!(published.len(), 1);
+    let params = &published[0]["params"];
+    assert_eq!(params["uri"], URI);
+    let diagnostics = params["diagnostics"].as_array().unwrap();
+    assert_eq!(
+        diagnostics.len(),
+        1,
+        "unexpected diagnostics: {diagnostics:?}"
+    );
+
+    // `1.5` on the second line
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic["code"], "E0102");
+    assert_eq!(diagnostic["severity"], 1);
+    assert_eq!(
+        diagnostic["range"],
+        json!({
+            "start": {"line": 1, "character": 12},
+            "end": {"line": 1, "character": 15},
+        })
+    );
+
+    assert_eq!(sent.last().unwrap()["id"], 2);
+}
+
+#[test]
+fn test_did_change_clears_diagnostics() {
+    let fixed = "int f() {\n    int i = 1;\n    return i;\n}\n";
+    let sent = session(&[
+        did_open("int f() {\n    int i = 1.5;\n    return i;\n}\n"),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": {"uri": URI, "version": 2},
+                "contentChanges": [{"text": fixed}],
+            },
+        }),
+    ]);
+
+    let published = published(&sent);
+    assert_eq!(published.len(), 2);
+    assert_eq!(
+        published[0]["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(published[1]["params"]["diagnostics"], json!([]));
+}
+
+#[test]
+fn test_did_save_rechecks_stored_text() {
+    let sent = session(&[
+        did_open("int f() {\n    int i = 1.5;\n    return i;\n}\n"),
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didSave",
+            "params": {"textDocument": {"uri": URI}},
+        }),
+    ]);
+
+    let published = published(&sent);
+    assert_eq!(published.len(), 2);
+    assert_eq!(published[1]["params"]["uri"], URI);
+    assert_eq!(published[1]["params"]["diagnostics"][0]["code"], "E0102");
+}
+
+#[test]
+fn test_did_save_unknown_document() {
+    let sent = session(&[json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didSave",
+        "params": {"textDocument": {"uri": URI}},
+    })]);
+    assert!(sent.is_empty());
+}
+
+#[test]
+fn test_unknown_request() {
+    let sent = session(&[json!({
+        "jsonrpc": "2.0",
+        "id": 7,
+        "method": "textDocument/hover",
+        "params": {},
+    })]);
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0]["id"], 7);
+    assert_eq!(sent[0]["error"]["code"], -32601);
+}
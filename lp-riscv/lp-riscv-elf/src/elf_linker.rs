@@ -1,4 +1,11 @@
-//! ELF linker error types and utilities.
+//! Static linking of RISC-V object files.
+
+use hashbrown::{HashMap, HashSet};
+use object::write::{self, SectionId, SymbolId};
+use object::{
+    Architecture, BinaryFormat, Endianness, Object, ObjectSection, ObjectSymbol, RelocationTarget,
+    SectionIndex, SectionKind, SymbolIndex, SymbolKind, SymbolScope, SymbolSection,
+};
 
 /// Errors that can occur during ELF linking.
 #[derive(Debug, Clone)]
@@ -7,6 +14,10 @@ pub enum LinkerError {
     ParseError(String),
     /// Write error (failed to write ELF file)
     WriteError(String),
+    /// A symbol has more than one strong definition
+    DuplicateSymbol(String),
+    /// Symbols referenced (not weakly) but defined by no object, sorted by name
+    UndefinedSymbols(Vec<String>),
 }
 
 impl core::fmt::Display for LinkerError {
@@ -14,6 +25,12 @@ impl core::fmt::Display for LinkerError {
         match self {
             LinkerError::ParseError(msg) => write!(f, "Parse error: {msg}"),
             LinkerError::WriteError(msg) => write!(f, "Write error: {msg}"),
+            LinkerError::DuplicateSymbol(name) => {
+                write!(f, "Duplicate definition of symbol '{name}'")
+            }
+            LinkerError::UndefinedSymbols(names) => {
+                write!(f, "Undefined symbols: {}", names.join(", "))
+            }
         }
     }
 }
@@ -34,9 +51,739 @@ impl From<object::write::Error> for LinkerError {
     }
 }
 
-/// Link a static library (placeholder - not implemented yet).
-pub fn link_static_library(_objects: &[&[u8]]) -> Result<Vec<u8>, LinkerError> {
-    Err(LinkerError::ParseError(
-        "link_static_library not implemented".to_string(),
-    ))
+/// The definition a global symbol resolved to.
+#[derive(Debug, Clone, Copy)]
+struct Definition {
+    /// Index of the defining object in the link inputs
+    object: usize,
+    symbol: SymbolIndex,
+    weak: bool,
+}
+
+/// What a global symbol resolved to.
+#[derive(Debug, Clone, Copy)]
+enum Resolution {
+    /// Defined in an input section (or absolute)
+    Defined(Definition),
+    /// Common (tentative) definitions, allocated in .bss with the largest size and
+    /// alignment seen
+    Common { size: u64, align: u64 },
+    /// Only referenced weakly; resolves to 0
+    UndefinedWeak,
+}
+
+/// Link RISC-V 32-bit relocatable object files into a single relocatable object.
+///
+/// Sections with the same name are concatenated (in input order) and relocations are
+/// rewritten to point into the merged sections. Global symbols are resolved like a
+/// standard linker would:
+/// - a strong definition overrides any weak definitions
+/// - among several weak definitions, the first one (in input order) is used
+/// - more than one strong definition is an error
+/// - common (tentative) definitions of the same name are merged into one .bss
+///   allocation of the largest size and alignment; a strong definition overrides them,
+///   and they override weak definitions
+/// - an unresolved weak reference becomes an absolute symbol with value 0, while an
+///   unresolved strong reference is an error
+///
+/// Only allocated sections (code, data, read-only data, bss) are linked; debug info and
+/// other metadata sections are dropped.
+pub fn link_static_library(objects: &[&[u8]]) -> Result<Vec<u8>, LinkerError> {
+    link(objects, None)
+}
+
+/// Link like [`link_static_library`], dropping sections not reachable from `roots`.
+///
+/// Equivalent to `--gc-sections`: a section is kept if it defines one of the root symbols
+/// (e.g. the entry point) or is the target of a relocation in a kept section. Removal is
+/// per input section, so objects should be compiled with one section per function/data
+/// item (`-ffunction-sections -fdata-sections`) for dead code to be dropped.
+///
+/// Roots that are not defined by any input are reported as
+/// [`LinkerError::UndefinedSymbols`].
+pub fn link_static_library_with_gc(
+    objects: &[&[u8]],
+    roots: &[&str],
+) -> Result<Vec<u8>, LinkerError> {
+    link(objects, Some(roots))
+}
+
+fn link(objects: &[&[u8]], gc_roots: Option<&[&str]>) -> Result<Vec<u8>, LinkerError> {
+    log::debug!("=== Linking {} objects ===", objects.len());
+
+    let inputs = objects
+        .iter()
+        .map(|data| object::File::parse(*data))
+        .collect::<Result<Vec<_>, _>>()?;
+    let Some(first) = inputs.first() else {
+        return Err(LinkerError::ParseError("No objects to link".to_string()));
+    };
+    for (index, obj) in inputs.iter().enumerate() {
+        if obj.architecture() != Architecture::Riscv32 {
+            return Err(LinkerError::ParseError(format!(
+                "Object {index} is not RISC-V 32-bit: {:?}",
+                obj.architecture()
+            )));
+        }
+    }
+
+    let resolutions = resolve_symbols(&inputs)?;
+    let kept = match gc_roots {
+        Some(roots) => Some(reachable_sections(&inputs, &resolutions, roots)?),
+        None => None,
+    };
+
+    let mut out = write::Object::new(BinaryFormat::Elf, Architecture::Riscv32, Endianness::Little);
+    out.flags = first.flags();
+
+    // Merge sections, recording where each input section was placed
+    let mut out_sections: HashMap<String, SectionId> = HashMap::new();
+    let mut placements: Vec<HashMap<SectionIndex, (SectionId, u64)>> = Vec::new();
+    for (object, obj) in inputs.iter().enumerate() {
+        let mut placement = HashMap::new();
+        for section in obj.sections() {
+            if !is_linked_section(section.kind()) {
+                continue;
+            }
+            let name = section.name()?;
+            if let Some(kept) = &kept
+                && !kept.contains(&(object, section.index()))
+            {
+                log::debug!("  Removing unused section '{name}' of object {object}");
+                continue;
+            }
+            let id = *out_sections.entry(name.to_string()).or_insert_with(|| {
+                out.add_section(Vec::new(), name.as_bytes().to_vec(), section.kind())
+            });
+            let align = section.align().max(1);
+            let offset = if section.kind() == SectionKind::UninitializedData {
+                out.append_section_bss(id, section.size(), align)
+            } else {
+                out.append_section_data(id, section.data()?, align)
+            };
+            log::trace!("  Section '{name}' placed at offset 0x{offset:x}");
+            placement.insert(section.index(), (id, offset));
+        }
+        placements.push(placement);
+    }
+
+    // Global symbols, in input order so the output is deterministic
+    let mut ordered: Vec<(&String, &Resolution)> = resolutions.iter().collect();
+    ordered.sort_by_key(|(name, resolution)| {
+        let order = match resolution {
+            Resolution::Defined(definition) => (0, definition.object, definition.symbol.0),
+            Resolution::Common { .. } => (1, 0, 0),
+            Resolution::UndefinedWeak => (2, 0, 0),
+        };
+        (order, (*name).clone())
+    });
+    let mut global_ids: HashMap<String, SymbolId> = HashMap::new();
+    for (name, resolution) in ordered {
+        let symbol = match *resolution {
+            Resolution::Defined(definition) => {
+                let symbol = inputs[definition.object].symbol_by_index(definition.symbol)?;
+                let Some((section, value)) = place_symbol(
+                    &inputs[definition.object],
+                    &placements[definition.object],
+                    &symbol,
+                )?
+                else {
+                    continue;
+                };
+                write::Symbol {
+                    name: name.as_bytes().to_vec(),
+                    value,
+                    size: symbol.size(),
+                    kind: defined_kind(symbol.kind()),
+                    scope: symbol.scope(),
+                    weak: definition.weak,
+                    section,
+                    flags: object::SymbolFlags::None,
+                }
+            }
+            Resolution::Common { size, align } => {
+                let bss = *out_sections.entry(".bss".to_string()).or_insert_with(|| {
+                    out.add_section(Vec::new(), b".bss".to_vec(), SectionKind::UninitializedData)
+                });
+                let offset = out.append_section_bss(bss, size, align);
+                log::debug!("  Common symbol '{name}': {size} bytes at .bss+0x{offset:x}");
+                write::Symbol {
+                    name: name.as_bytes().to_vec(),
+                    value: offset,
+                    size,
+                    kind: SymbolKind::Data,
+                    scope: SymbolScope::Linkage,
+                    weak: false,
+                    section: write::SymbolSection::Section(bss),
+                    flags: object::SymbolFlags::None,
+                }
+            }
+            Resolution::UndefinedWeak => {
+                log::debug!("  Unresolved weak symbol '{name}' resolved to 0");
+                write::Symbol {
+                    name: name.as_bytes().to_vec(),
+                    value: 0,
+                    size: 0,
+                    kind: SymbolKind::Label,
+                    scope: SymbolScope::Linkage,
+                    weak: true,
+                    section: write::SymbolSection::Absolute,
+                    flags: object::SymbolFlags::None,
+                }
+            }
+        };
+        global_ids.insert(name.clone(), out.add_symbol(symbol));
+    }
+
+    // Local symbols and relocations, per object
+    for (obj, placement) in inputs.iter().zip(&placements) {
+        let mut local_ids: HashMap<SymbolIndex, (SymbolId, i64)> = HashMap::new();
+        for symbol in obj.symbols() {
+            if !symbol.is_local() {
+                continue;
+            }
+            if symbol.kind() == SymbolKind::Section {
+                if let Some(&(id, offset)) = symbol.section_index().and_then(|i| placement.get(&i))
+                {
+                    local_ids.insert(symbol.index(), (out.section_symbol(id), offset as i64));
+                }
+                continue;
+            }
+            let name = symbol.name()?;
+            if name.is_empty() || symbol.kind() == SymbolKind::File {
+                continue;
+            }
+            let Some((section, value)) = place_symbol(obj, placement, &symbol)? else {
+                continue;
+            };
+            let id = out.add_symbol(write::Symbol {
+                name: name.as_bytes().to_vec(),
+                value,
+                size: symbol.size(),
+                kind: defined_kind(symbol.kind()),
+                scope: SymbolScope::Compilation,
+                weak: false,
+                section,
+                flags: object::SymbolFlags::None,
+            });
+            local_ids.insert(symbol.index(), (id, 0));
+        }
+
+        for section in obj.sections() {
+            let Some(&(out_section, base)) = placement.get(&section.index()) else {
+                continue;
+            };
+            for (offset, reloc) in section.relocations() {
+                // Relocations without a symbol (R_RISCV_RELAX, R_RISCV_ALIGN) only matter
+                // for linker relaxation, which is not performed
+                let RelocationTarget::Symbol(index) = reloc.target() else {
+                    log::trace!("  Dropping relocation without symbol at 0x{offset:x}");
+                    continue;
+                };
+                let (symbol, addend) = match local_ids.get(&index) {
+                    Some(&target) => target,
+                    None => {
+                        let target = obj.symbol_by_index(index)?;
+                        let name = target.name()?;
+                        let Some(&id) = global_ids.get(name) else {
+                            return Err(LinkerError::ParseError(format!(
+                                "Relocation at 0x{offset:x} in '{}' targets unlinked symbol '{name}'",
+                                section.name().unwrap_or("<unnamed>")
+                            )));
+                        };
+                        (id, 0)
+                    }
+                };
+                out.add_relocation(
+                    out_section,
+                    write::Relocation {
+                        offset: base + offset,
+                        symbol,
+                        addend: reloc.addend() + addend,
+                        flags: reloc.flags(),
+                    },
+                )?;
+            }
+        }
+    }
+
+    Ok(out.write()?)
+}
+
+/// Resolve global symbols across all inputs.
+fn resolve_symbols(inputs: &[object::File]) -> Result<HashMap<String, Resolution>, LinkerError> {
+    let mut definitions: HashMap<String, Definition> = HashMap::new();
+    // Common symbols: (size, alignment)
+    let mut commons: HashMap<String, (u64, u64)> = HashMap::new();
+    // Referenced symbols, and whether every reference is weak
+    let mut references: HashMap<String, bool> = HashMap::new();
+
+    for (object, obj) in inputs.iter().enumerate() {
+        for symbol in obj.symbols() {
+            if symbol.is_local() {
+                continue;
+            }
+            let name = symbol.name()?;
+            if name.is_empty() {
+                continue;
+            }
+            let weak = symbol.is_weak();
+            match symbol.section() {
+                SymbolSection::Undefined => {
+                    let weak_only = references.entry(name.to_string()).or_insert(true);
+                    *weak_only &= weak;
+                }
+                SymbolSection::Common => {
+                    // For common symbols, the ELF symbol value holds the alignment
+                    let align = symbol.address().max(1);
+                    let common = commons.entry(name.to_string()).or_insert((0, 1));
+                    common.0 = common.0.max(symbol.size());
+                    common.1 = common.1.max(align);
+                }
+                _ => {
+                    let definition = Definition {
+                        object,
+                        symbol: symbol.index(),
+                        weak,
+                    };
+                    match definitions.get(name) {
+                        None => {
+                            definitions.insert(name.to_string(), definition);
+                        }
+                        Some(existing) if existing.weak && !weak => {
+                            log::debug!("  Strong '{name}' in object {object} overrides weak");
+                            definitions.insert(name.to_string(), definition);
+                        }
+                        Some(existing) if !existing.weak && !weak => {
+                            return Err(LinkerError::DuplicateSymbol(name.to_string()));
+                        }
+                        // Weak after any definition: keep the existing one
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    let mut undefined: Vec<String> = references
+        .iter()
+        .filter(|(name, weak_only)| {
+            !**weak_only && !definitions.contains_key(*name) && !commons.contains_key(*name)
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    if !undefined.is_empty() {
+        undefined.sort();
+        return Err(LinkerError::UndefinedSymbols(undefined));
+    }
+
+    let mut resolved: HashMap<String, Resolution> = references
+        .into_keys()
+        .map(|name| (name, Resolution::UndefinedWeak))
+        .collect();
+    resolved.extend(
+        definitions
+            .into_iter()
+            .map(|(name, d)| (name, Resolution::Defined(d))),
+    );
+    // Commons override weak definitions, but not strong ones
+    for (name, (size, align)) in commons {
+        if !matches!(resolved.get(&name), Some(Resolution::Defined(d)) if !d.weak) {
+            resolved.insert(name, Resolution::Common { size, align });
+        }
+    }
+    Ok(resolved)
+}
+
+/// Input sections (object index, section index) reachable from the sections defining
+/// `roots`, following relocations.
+fn reachable_sections(
+    inputs: &[object::File],
+    resolutions: &HashMap<String, Resolution>,
+    roots: &[&str],
+) -> Result<HashSet<(usize, SectionIndex)>, LinkerError> {
+    // Section defining a resolved global symbol, if any
+    let defining_section = |name: &str| -> Result<Option<(usize, SectionIndex)>, LinkerError> {
+        match resolutions.get(name) {
+            Some(Resolution::Defined(definition)) => Ok(inputs[definition.object]
+                .symbol_by_index(definition.symbol)?
+                .section_index()
+                .map(|index| (definition.object, index))),
+            _ => Ok(None),
+        }
+    };
+
+    let mut worklist = Vec::new();
+    let mut missing = Vec::new();
+    for root in roots {
+        if !resolutions.contains_key(*root) {
+            missing.push(root.to_string());
+        }
+        worklist.extend(defining_section(root)?);
+    }
+    if !missing.is_empty() {
+        missing.sort();
+        return Err(LinkerError::UndefinedSymbols(missing));
+    }
+
+    let mut kept = HashSet::new();
+    while let Some((object, index)) = worklist.pop() {
+        if !kept.insert((object, index)) {
+            continue;
+        }
+        let obj = &inputs[object];
+        for (_, reloc) in obj.section_by_index(index)?.relocations() {
+            let RelocationTarget::Symbol(target) = reloc.target() else {
+                continue;
+            };
+            let target = obj.symbol_by_index(target)?;
+            if target.is_local() {
+                worklist.extend(target.section_index().map(|index| (object, index)));
+            } else {
+                worklist.extend(defining_section(target.name()?)?);
+            }
+        }
+    }
+    Ok(kept)
+}
+
+/// Whether a section is copied into the linked output.
+fn is_linked_section(kind: SectionKind) -> bool {
+    matches!(
+        kind,
+        SectionKind::Text
+            | SectionKind::Data
+            | SectionKind::ReadOnlyData
+            | SectionKind::ReadOnlyDataWithRel
+            | SectionKind::ReadOnlyString
+            | SectionKind::UninitializedData
+    )
+}
+
+/// Kind for a defined output symbol. Untyped (`STT_NOTYPE`) symbols read back as
+/// `Unknown`, which the writer only accepts for undefined symbols.
+fn defined_kind(kind: SymbolKind) -> SymbolKind {
+    match kind {
+        SymbolKind::Unknown => SymbolKind::Label,
+        kind => kind,
+    }
+}
+
+/// Output section and value of a defined symbol, or `None` if its section is not linked.
+fn place_symbol(
+    obj: &object::File,
+    placement: &HashMap<SectionIndex, (SectionId, u64)>,
+    symbol: &object::Symbol,
+) -> Result<Option<(write::SymbolSection, u64)>, LinkerError> {
+    match symbol.section() {
+        SymbolSection::Section(index) => {
+            let Some(&(id, offset)) = placement.get(&index) else {
+                return Ok(None);
+            };
+            let section_addr = obj.section_by_index(index)?.address();
+            Ok(Some((
+                write::SymbolSection::Section(id),
+                offset + symbol.address() - section_addr,
+            )))
+        }
+        SymbolSection::Absolute => Ok(Some((write::SymbolSection::Absolute, symbol.address()))),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_elf;
+    use crate::test_util::call_function;
+    use object::{RelocationFlags, elf};
+
+    const LI_A0_1: [u8; 4] = [0x13, 0x05, 0x10, 0x00];
+    const LI_A0_2: [u8; 4] = [0x13, 0x05, 0x20, 0x00];
+    const RET: [u8; 4] = [0x67, 0x80, 0x00, 0x00];
+
+    /// Build a relocatable RISC-V object with a single .text section.
+    fn build_object(build: impl FnOnce(&mut write::Object<'static>, SectionId)) -> Vec<u8> {
+        let mut obj =
+            write::Object::new(BinaryFormat::Elf, Architecture::Riscv32, Endianness::Little);
+        let text = obj.add_section(Vec::new(), b".text".to_vec(), SectionKind::Text);
+        build(&mut obj, text);
+        obj.write().unwrap()
+    }
+
+    /// Define a function `name` with the given code in .text.
+    fn add_function(
+        obj: &mut write::Object<'static>,
+        text: SectionId,
+        name: &str,
+        code: &[u8],
+        weak: bool,
+    ) -> u64 {
+        let offset = obj.append_section_data(text, code, 4);
+        obj.add_symbol(write::Symbol {
+            name: name.as_bytes().to_vec(),
+            value: offset,
+            size: code.len() as u64,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak,
+            section: write::SymbolSection::Section(text),
+            flags: object::SymbolFlags::None,
+        });
+        offset
+    }
+
+    /// Add a reference to an external symbol.
+    fn add_import(obj: &mut write::Object<'static>, name: &str, weak: bool) -> SymbolId {
+        obj.add_symbol(write::Symbol {
+            name: name.as_bytes().to_vec(),
+            value: 0,
+            size: 0,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Unknown,
+            weak,
+            section: write::SymbolSection::Undefined,
+            flags: object::SymbolFlags::None,
+        })
+    }
+
+    /// `value() -> 2`, defined with the given weakness.
+    fn value_object(code: [u8; 4], weak: bool) -> Vec<u8> {
+        build_object(|obj, text| {
+            let mut function = code.to_vec();
+            function.extend_from_slice(&RET);
+            add_function(obj, text, "value", &function, weak);
+        })
+    }
+
+    /// Code of `main() -> value()`; the call to `value` is at offset 8.
+    fn main_object_code() -> Vec<u8> {
+        vec![
+            0x13, 0x01, 0x01, 0xff, // addi sp, sp, -16
+            0x23, 0x26, 0x11, 0x00, // sw ra, 12(sp)
+            0x97, 0x00, 0x00, 0x00, // auipc ra, 0
+            0xe7, 0x80, 0x00, 0x00, // jalr ra
+            0x83, 0x20, 0xc1, 0x00, // lw ra, 12(sp)
+            0x13, 0x01, 0x01, 0x01, // addi sp, sp, 16
+            0x67, 0x80, 0x00, 0x00, // ret
+        ]
+    }
+
+    /// Add an R_RISCV_CALL_PLT relocation for an auipc+jalr pair.
+    fn add_call_relocation(
+        obj: &mut write::Object<'static>,
+        section: SectionId,
+        offset: u64,
+        symbol: SymbolId,
+    ) {
+        obj.add_relocation(
+            section,
+            write::Relocation {
+                offset,
+                symbol,
+                addend: 0,
+                flags: RelocationFlags::Elf {
+                    r_type: elf::R_RISCV_CALL_PLT,
+                },
+            },
+        )
+        .unwrap();
+    }
+
+    /// `main() -> value()`, calling `value` through the PLT.
+    fn main_object() -> Vec<u8> {
+        build_object(|obj, text| {
+            let offset = add_function(obj, text, "main", &main_object_code(), false);
+            let value = add_import(obj, "value", false);
+            add_call_relocation(obj, text, offset + 8, value);
+        })
+    }
+
+    fn find_symbol<'data>(
+        obj: &'data object::File<'data>,
+        name: &str,
+    ) -> object::Symbol<'data, 'data> {
+        obj.symbols()
+            .find(|symbol| symbol.name() == Ok(name))
+            .unwrap_or_else(|| panic!("Symbol '{name}' not found"))
+    }
+
+    #[test]
+    fn test_strong_overrides_weak() {
+        let main = main_object();
+        let weak = value_object(LI_A0_2, true);
+        let strong = value_object(LI_A0_1, false);
+
+        // Regardless of order, the strong definition wins
+        for objects in [
+            [&main[..], &weak[..], &strong[..]],
+            [&main[..], &strong[..], &weak[..]],
+        ] {
+            let linked = link_static_library(&objects).expect("Link failed");
+            let obj = object::File::parse(&linked[..]).unwrap();
+            assert!(!find_symbol(&obj, "value").is_weak());
+            assert_eq!(call_function(&load_elf(&linked).unwrap(), "main"), 1);
+        }
+    }
+
+    #[test]
+    fn test_first_weak_definition_wins() {
+        let main = main_object();
+        let first = value_object(LI_A0_2, true);
+        let second = value_object(LI_A0_1, true);
+
+        let linked = link_static_library(&[&main, &first, &second]).expect("Link failed");
+        let obj = object::File::parse(&linked[..]).unwrap();
+        assert!(find_symbol(&obj, "value").is_weak());
+        assert_eq!(call_function(&load_elf(&linked).unwrap(), "main"), 2);
+    }
+
+    /// Object with a tentative definition of `counter`.
+    fn common_object(size: u64, align: u64) -> Vec<u8> {
+        build_object(|obj, _text| {
+            obj.add_common_symbol(
+                write::Symbol {
+                    name: b"counter".to_vec(),
+                    // ELF common symbols store their alignment in the value
+                    value: align,
+                    size: 0,
+                    kind: SymbolKind::Data,
+                    scope: SymbolScope::Linkage,
+                    weak: false,
+                    section: write::SymbolSection::Undefined,
+                    flags: object::SymbolFlags::None,
+                },
+                size,
+                align,
+            );
+        })
+    }
+
+    #[test]
+    fn test_common_symbols_merged() {
+        let small = common_object(4, 4);
+        let large = common_object(16, 8);
+
+        let linked = link_static_library(&[&small, &large]).expect("Link failed");
+        let obj = object::File::parse(&linked[..]).unwrap();
+
+        let counters: Vec<_> = obj
+            .symbols()
+            .filter(|symbol| symbol.name() == Ok("counter"))
+            .collect();
+        assert_eq!(counters.len(), 1);
+        let counter = &counters[0];
+        assert_eq!(counter.size(), 16);
+        assert_eq!(counter.address() % 8, 0);
+
+        let bss = obj.section_by_name(".bss").expect("No .bss section");
+        assert_eq!(counter.section_index(), Some(bss.index()));
+        assert_eq!(bss.size(), 16);
+        assert!(bss.align() >= 8);
+    }
+
+    #[test]
+    fn test_gc_sections_drops_unreferenced_function() {
+        // One section per function, as with -ffunction-sections
+        let object = build_object(|obj, _text| {
+            let mut function_section = |name: &str, code: &[u8]| {
+                let section = obj.add_section(
+                    Vec::new(),
+                    format!(".text.{name}").into_bytes(),
+                    SectionKind::Text,
+                );
+                add_function(obj, section, name, code, false);
+                section
+            };
+            let main = function_section("main", &main_object_code());
+            function_section("value", &[LI_A0_1, RET].concat());
+            function_section("unused", &[LI_A0_2, RET].concat());
+
+            let value_symbol = obj.symbol_id(b"value").unwrap();
+            add_call_relocation(obj, main, 8, value_symbol);
+        });
+
+        let linked = link_static_library_with_gc(&[&object], &["main"]).expect("Link failed");
+        let obj = object::File::parse(&linked[..]).unwrap();
+        let symbol_names: Vec<&str> = obj.symbols().filter_map(|s| s.name().ok()).collect();
+        assert!(symbol_names.contains(&"main"));
+        assert!(symbol_names.contains(&"value"));
+        assert!(!symbol_names.contains(&"unused"));
+        assert!(obj.section_by_name(".text.main").is_some());
+        assert!(obj.section_by_name(".text.value").is_some());
+        assert!(obj.section_by_name(".text.unused").is_none());
+
+        // Without GC everything is kept
+        let linked = link_static_library(&[&object]).expect("Link failed");
+        let obj = object::File::parse(&linked[..]).unwrap();
+        assert!(obj.section_by_name(".text.unused").is_some());
+
+        match link_static_library_with_gc(&[&object], &["missing"]) {
+            Err(LinkerError::UndefinedSymbols(names)) => assert_eq!(names, vec!["missing"]),
+            other => panic!("Expected UndefinedSymbols, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_strong_definitions() {
+        let first = value_object(LI_A0_1, false);
+        let second = value_object(LI_A0_2, false);
+
+        match link_static_library(&[&first, &second]) {
+            Err(LinkerError::DuplicateSymbol(name)) => assert_eq!(name, "value"),
+            other => panic!("Expected DuplicateSymbol, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unresolved_weak_reference_is_zero() {
+        // hook_addr() -> &hook, loaded from the GOT
+        let object = build_object(|obj, text| {
+            let code = [
+                0x17, 0x05, 0x00, 0x00, // auipc a0, 0
+                0x03, 0x25, 0x05, 0x00, // lw a0, 0(a0)
+                0x67, 0x80, 0x00, 0x00, // ret
+            ];
+            let offset = add_function(obj, text, "hook_addr", &code, false);
+            let label = obj.add_symbol(write::Symbol {
+                name: b".Lpcrel_hi0".to_vec(),
+                value: offset,
+                size: 0,
+                kind: SymbolKind::Label,
+                scope: SymbolScope::Compilation,
+                weak: false,
+                section: write::SymbolSection::Section(text),
+                flags: object::SymbolFlags::None,
+            });
+            let hook = add_import(obj, "hook", true);
+            for (at, symbol, r_type) in [
+                (offset, hook, elf::R_RISCV_GOT_HI20),
+                (offset + 4, label, elf::R_RISCV_PCREL_LO12_I),
+            ] {
+                obj.add_relocation(
+                    text,
+                    write::Relocation {
+                        offset: at,
+                        symbol,
+                        addend: 0,
+                        flags: RelocationFlags::Elf { r_type },
+                    },
+                )
+                .unwrap();
+            }
+        });
+
+        let linked = link_static_library(&[&object]).expect("Link failed");
+        let obj = object::File::parse(&linked[..]).unwrap();
+        let hook = find_symbol(&obj, "hook");
+        assert_eq!(hook.section(), SymbolSection::Absolute);
+        assert_eq!(hook.address(), 0);
+        assert_eq!(call_function(&load_elf(&linked).unwrap(), "hook_addr"), 0);
+
+        // A strong reference to the same missing symbol is an error
+        match link_static_library(&[&main_object()]) {
+            Err(LinkerError::UndefinedSymbols(names)) => assert_eq!(names, vec!["value"]),
+            other => panic!("Expected UndefinedSymbols, got {other:?}"),
+        }
+    }
 }
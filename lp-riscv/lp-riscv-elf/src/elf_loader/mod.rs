@@ -4,6 +4,7 @@
 //! It handles section loading and relocation application.
 
 use hashbrown::HashMap;
+use lp_riscv_emu::{MemoryRegion, Riscv32Emulator};
 use object::{Object, ObjectSection};
 
 mod layout;
@@ -12,6 +13,7 @@ mod object_file;
 mod parse;
 mod relocations;
 mod sections;
+mod segments;
 mod symbols;
 
 // Re-export functions from submodules
@@ -32,10 +34,34 @@ pub struct ElfLoadInfo {
     pub code_end: u32,
     /// End offset of RAM sections (relative to RAM_START, where RAM sections end)
     pub ram_end: u32,
+    /// PT_LOAD segments with their permissions, applied by [`ElfLoadInfo::into_emulator`]
+    ///
+    /// The heap (`__heap_start..__heap_end`, if the ELF defines it) is listed last as
+    /// read/write/execute, so guests can JIT into it even though its segment is RW.
+    pub segments: Vec<MemoryRegion>,
+}
+
+impl ElfLoadInfo {
+    /// Build an emulator with this ELF's code and RAM, enforcing its segment permissions.
+    pub fn into_emulator(self) -> Riscv32Emulator {
+        Riscv32Emulator::new(self.code, self.ram).with_region_permissions(&self.segments)
+    }
 }
 
 /// Load a RISC-V ELF file and extract code and data sections for the emulator.
 ///
+/// Equivalent to [`load_elf_with_bias`] with a bias of 0 (code at its link address).
+pub fn load_elf(elf_data: &[u8]) -> Result<ElfLoadInfo, String> {
+    load_elf_with_bias(elf_data, 0)
+}
+
+/// Load a RISC-V ELF file with its code placed `load_bias` bytes above its link address.
+///
+/// ROM sections (and ROM symbols, the entry point and ROM segments) move by `load_bias`;
+/// RAM stays at `RAM_START`. Relocations are applied against the biased addresses, so
+/// position-independent code (e.g. compiled with `is_pic=true`) can be loaded at any
+/// base. Code without relocations only runs correctly at a bias of 0.
+///
 /// This function:
 /// - Parses the ELF file using the object crate
 /// - Validates it's RISC-V 32-bit
@@ -43,9 +69,10 @@ pub struct ElfLoadInfo {
 /// - Loads sections into appropriate buffers
 /// - Builds symbol map for relocations
 /// - Applies relocations to all sections (code and data)
+/// - Collects segment permissions (from PT_LOAD flags) for [`ElfLoadInfo::into_emulator`]
 /// - Returns the entry point address
-pub fn load_elf(elf_data: &[u8]) -> Result<ElfLoadInfo, String> {
-    log::debug!("=== Loading ELF file ===");
+pub fn load_elf_with_bias(elf_data: &[u8], load_bias: u32) -> Result<ElfLoadInfo, String> {
+    log::debug!("=== Loading ELF file (load bias 0x{load_bias:x}) ===");
 
     // Step 1: Parse ELF
     let obj = parse::parse_elf(elf_data)?;
@@ -54,17 +81,20 @@ pub fn load_elf(elf_data: &[u8]) -> Result<ElfLoadInfo, String> {
     parse::validate_elf(&obj)?;
 
     // Step 3: Extract entry point
-    let entry_point = parse::extract_entry_point(&obj);
+    let mut entry_point = parse::extract_entry_point(&obj);
+    if memory::is_rom_address(entry_point as u64) {
+        entry_point = entry_point.wrapping_add(load_bias);
+    }
 
     // Step 4: Calculate memory layout
     let layout = layout::calculate_memory_layout(&obj, entry_point)?;
 
-    // Step 5: Allocate buffers
-    let mut code = vec![0u8; layout.rom_size];
+    // Step 5: Allocate buffers (ROM grows by the bias)
+    let mut code = vec![0u8; layout.rom_size + load_bias as usize];
     let mut ram = vec![0u8; layout.ram_size];
 
     // Step 6: Load sections
-    sections::load_sections(&obj, &mut code, &mut ram)?;
+    sections::load_sections(&obj, &mut code, &mut ram, load_bias)?;
 
     // Step 7: Build symbol map
     // Find text section base for symbol address calculation
@@ -75,10 +105,10 @@ pub fn load_elf(elf_data: &[u8]) -> Result<ElfLoadInfo, String> {
             break;
         }
     }
-    let symbol_map = symbols::build_symbol_map(&obj, text_base);
+    let symbol_map = symbols::build_symbol_map(&obj, text_base, load_bias);
 
     // Step 8: Apply relocations
-    relocations::apply_relocations(&obj, &mut code, &mut ram, &symbol_map)?;
+    relocations::apply_relocations(&obj, &mut code, &mut ram, &symbol_map, load_bias)?;
 
     // Step 9: Calculate code_end and ram_end
     // Find maximum end address of ROM sections
@@ -95,7 +125,7 @@ pub fn load_elf(elf_data: &[u8]) -> Result<ElfLoadInfo, String> {
         }
 
         if memory::is_rom_address(section_addr) && section_size > 0 {
-            let end_addr = (section_addr + section_size) as u32;
+            let end_addr = (section_addr + section_size) as u32 + load_bias;
             code_end = code_end.max(end_addr);
         }
     }
@@ -120,6 +150,10 @@ pub fn load_elf(elf_data: &[u8]) -> Result<ElfLoadInfo, String> {
         }
     }
 
+    // Step 10: Collect segment permissions, keeping the heap executable
+    let mut segments = segments::segment_permissions(&obj, load_bias);
+    segments.extend(segments::heap_region(&symbol_map));
+
     log::debug!("=== ELF loading complete ===");
     log::debug!(
         "Code size: {} bytes, RAM size: {} bytes, Entry point: 0x{:x}",
@@ -136,12 +170,14 @@ pub fn load_elf(elf_data: &[u8]) -> Result<ElfLoadInfo, String> {
         symbol_map,
         code_end,
         ram_end,
+        segments,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::call_function;
     use lp_riscv_emu::{LogLevel, Riscv32Emulator, StepResult};
     use lp_riscv_inst::Gpr;
     use std::println;
@@ -373,4 +409,136 @@ mod tests {
             load_info.code_end, load_info.ram_end
         );
     }
+
+    /// Create a PIC object where `get_callee_addr` takes the address of `callee` (via the GOT)
+    /// and `call_callee` calls it (via the PLT)
+    fn create_pic_object() -> Vec<u8> {
+        use cranelift_codegen::ir::types;
+        use cranelift_codegen::ir::{AbiParam, Function, InstBuilder, Signature};
+        use cranelift_codegen::settings::Configurable;
+        use cranelift_codegen::{Context, isa::lookup};
+        use cranelift_frontend::{FunctionBuilder, FunctionBuilderContext};
+        use cranelift_module::{Linkage, Module};
+        use cranelift_object::{ObjectBuilder, ObjectModule};
+        use target_lexicon::Triple;
+
+        let triple = Triple {
+            architecture: target_lexicon::Architecture::Riscv32(
+                target_lexicon::Riscv32Architecture::Riscv32imac,
+            ),
+            vendor: target_lexicon::Vendor::Unknown,
+            operating_system: target_lexicon::OperatingSystem::None_,
+            environment: target_lexicon::Environment::Unknown,
+            binary_format: target_lexicon::BinaryFormat::Elf,
+        };
+
+        let isa_builder = lookup(triple).unwrap();
+        let mut flag_builder = cranelift_codegen::settings::builder();
+        flag_builder.set("is_pic", "true").unwrap();
+        let isa = isa_builder
+            .finish(cranelift_codegen::settings::Flags::new(flag_builder))
+            .unwrap();
+        let mut module = ObjectModule::new(
+            ObjectBuilder::new(isa, "pic", cranelift_module::default_libcall_names()).unwrap(),
+        );
+
+        let sig = Signature {
+            params: vec![],
+            returns: vec![AbiParam::new(types::I32)],
+            call_conv: cranelift_codegen::isa::CallConv::SystemV,
+        };
+        let callee_id = module
+            .declare_function("callee", Linkage::Export, &sig)
+            .unwrap();
+        let get_addr_id = module
+            .declare_function("get_callee_addr", Linkage::Export, &sig)
+            .unwrap();
+        let call_id = module
+            .declare_function("call_callee", Linkage::Export, &sig)
+            .unwrap();
+
+        let mut ctx = Context::new();
+        let mut func_ctx = FunctionBuilderContext::new();
+
+        // callee() -> 42
+        ctx.func = Function::with_name_signature(
+            cranelift_codegen::ir::UserFuncName::user(0, callee_id.as_u32()),
+            sig.clone(),
+        );
+        {
+            let mut builder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+            let block = builder.create_block();
+            builder.switch_to_block(block);
+            builder.seal_block(block);
+            let value = builder.ins().iconst(types::I32, 42);
+            builder.ins().return_(&[value]);
+            builder.finalize();
+        }
+        module.define_function(callee_id, &mut ctx).unwrap();
+        module.clear_context(&mut ctx);
+
+        // get_callee_addr() -> &callee
+        ctx.func = Function::with_name_signature(
+            cranelift_codegen::ir::UserFuncName::user(0, get_addr_id.as_u32()),
+            sig.clone(),
+        );
+        {
+            let mut builder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+            let block = builder.create_block();
+            builder.switch_to_block(block);
+            builder.seal_block(block);
+            let callee_ref = module.declare_func_in_func(callee_id, &mut builder.func);
+            let addr = builder.ins().func_addr(types::I32, callee_ref);
+            builder.ins().return_(&[addr]);
+            builder.finalize();
+        }
+        module.define_function(get_addr_id, &mut ctx).unwrap();
+        module.clear_context(&mut ctx);
+
+        // call_callee() -> callee() + 1
+        ctx.func = Function::with_name_signature(
+            cranelift_codegen::ir::UserFuncName::user(0, call_id.as_u32()),
+            sig.clone(),
+        );
+        {
+            let mut builder = FunctionBuilder::new(&mut ctx.func, &mut func_ctx);
+            let block = builder.create_block();
+            builder.switch_to_block(block);
+            builder.seal_block(block);
+            let callee_ref = module.declare_func_in_func(callee_id, &mut builder.func);
+            let call = builder.ins().call(callee_ref, &[]);
+            let result = builder.inst_results(call)[0];
+            let result = builder.ins().iadd_imm(result, 1);
+            builder.ins().return_(&[result]);
+            builder.finalize();
+        }
+        module.define_function(call_id, &mut ctx).unwrap();
+
+        module.finish().emit().unwrap()
+    }
+
+    #[test]
+    fn test_load_pic_object_at_different_biases() {
+        let object = create_pic_object();
+
+        let base = load_elf(&object).expect("Failed to load PIC object");
+        let callee_offset = base.symbol_map["callee"];
+
+        for bias in [0u32, 0x1000, 0x24680] {
+            let load_info = load_elf_with_bias(&object, bias).expect("Failed to load PIC object");
+
+            let callee_addr = load_info.symbol_map["callee"];
+            assert_eq!(callee_addr, callee_offset + bias);
+            assert_eq!(load_info.entry_point, base.entry_point + bias);
+
+            // GOT-relative address of `callee` resolves to where it was placed
+            assert_eq!(
+                call_function(&load_info, "get_callee_addr") as u32,
+                callee_addr,
+                "Wrong callee address at bias 0x{bias:x}"
+            );
+            // PLT-relative call reaches `callee`
+            assert_eq!(call_function(&load_info, "call_callee"), 43);
+        }
+    }
 }
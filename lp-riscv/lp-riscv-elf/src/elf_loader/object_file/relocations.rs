@@ -55,7 +55,7 @@ pub fn apply_object_relocations(
 
     // Phase 1: Analyze relocations (this works with section-relative addresses)
     let (relocations, got_tracker, _section_addrs) =
-        analyze_relocations(obj, code, ram, merged_symbol_map)?;
+        analyze_relocations(obj, code, ram, merged_symbol_map, 0)?;
 
     // Build adjusted section address map for object file sections
     // Object file sections are placed at specific addresses, so we need to adjust
@@ -18,17 +18,21 @@ use alloc::string::String;
 use hashbrown::HashMap;
 
 /// Apply all relocations from the ELF to ROM and RAM buffers.
+///
+/// `symbol_map` must already include `load_bias` for ROM symbols; ROM sections are
+/// resolved at their link address plus `load_bias`.
 pub fn apply_relocations(
     obj: &object::File,
     rom: &mut [u8],
     ram: &mut [u8],
     symbol_map: &HashMap<String, u32>,
+    load_bias: u32,
 ) -> Result<(), String> {
     log::debug!("=== Applying relocations ===");
 
     // Phase 1: Analyze relocations and identify GOT entries
     let (relocations, got_tracker, section_addrs) =
-        phase1::analyze_relocations(obj, rom, ram, symbol_map)?;
+        phase1::analyze_relocations(obj, rom, ram, symbol_map, load_bias)?;
 
     // Phase 2: Apply relocations
     phase2::apply_relocations_phase2(
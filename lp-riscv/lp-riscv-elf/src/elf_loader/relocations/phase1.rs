@@ -38,6 +38,7 @@ pub fn analyze_relocations(
     _rom: &[u8],
     _ram: &[u8],
     symbol_map: &HashMap<String, u32>,
+    load_bias: u32,
 ) -> Result<
     (
         Vec<RelocationInfo>,
@@ -49,7 +50,7 @@ pub fn analyze_relocations(
     log::debug!("=== Phase 1: Relocation Analysis ===");
 
     // Resolve section addresses
-    let section_addrs = resolve_section_addresses(obj, _rom, _ram, symbol_map)?;
+    let section_addrs = resolve_section_addresses(obj, _rom, _ram, symbol_map, load_bias)?;
 
     // Collect all relocations
     let mut relocations = Vec::new();
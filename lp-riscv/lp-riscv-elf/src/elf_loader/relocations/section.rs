@@ -25,11 +25,14 @@ pub enum BufferSlice {
 }
 
 /// Resolve section addresses (VMA/LMA) and determine buffer locations.
+///
+/// Addresses are computed at link time and then ROM addresses are moved by `load_bias`.
 pub fn resolve_section_addresses(
     obj: &object::File,
     _rom: &[u8],
     _ram: &[u8],
     symbol_map: &HashMap<String, u32>,
+    load_bias: u32,
 ) -> Result<HashMap<String, SectionAddressInfo>, String> {
     log::debug!("=== Resolving section addresses ===");
 
@@ -70,8 +73,10 @@ pub fn resolve_section_addresses(
     }
 
     // Find __data_source_start symbol to determine .data section LMA
+    // Without a bias the symbol map holds link addresses; with one, read the ELF directly
     let data_source_start: Option<u64> = symbol_map
         .get("__data_source_start")
+        .filter(|_| load_bias == 0)
         .map(|&addr| addr as u64)
         .or_else(|| {
             for symbol in obj.symbols() {
@@ -163,6 +168,18 @@ pub fn resolve_section_addresses(
             rodata_end = Some(vma + data.len() as u64);
         }
 
+        // Apply the load bias to ROM addresses
+        let vma = if is_rom_address(vma) {
+            vma + load_bias as u64
+        } else {
+            vma
+        };
+        let lma = if is_rom_address(lma) {
+            lma + load_bias as u64
+        } else {
+            lma
+        };
+
         // Determine buffer slice
         let buffer = if is_rom_address(vma) {
             BufferSlice::Rom {
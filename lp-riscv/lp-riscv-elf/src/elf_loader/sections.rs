@@ -9,7 +9,13 @@ use object::{Object, ObjectSection, ObjectSymbol};
 ///
 /// For executable ELF files, sections may have LMA (load address) different from VMA (virtual address).
 /// We use symbol addresses to determine the actual VMA where sections should be loaded.
-pub fn load_sections(obj: &object::File, rom: &mut [u8], _ram: &mut [u8]) -> Result<(), String> {
+/// Everything placed in ROM is moved up by `load_bias` bytes.
+pub fn load_sections(
+    obj: &object::File,
+    rom: &mut [u8],
+    _ram: &mut [u8],
+    load_bias: u32,
+) -> Result<(), String> {
     log::debug!("=== Loading sections ===");
 
     // Build a map of section names to their actual VMA addresses from symbols
@@ -160,7 +166,7 @@ pub fn load_sections(obj: &object::File, rom: &mut [u8], _ram: &mut [u8]) -> Res
 
         if is_rom_address(vma) {
             // ROM section - copy to code buffer
-            let offset = vma as usize;
+            let offset = vma as usize + load_bias as usize;
             if offset + data.len() > rom.len() {
                 return Err(format!(
                     "Section '{}' (ROM) out of bounds: offset=0x{:x}, size={}, rom_len={}",
@@ -179,9 +185,9 @@ pub fn load_sections(obj: &object::File, rom: &mut [u8], _ram: &mut [u8]) -> Res
             rom[offset..offset + data.len()].copy_from_slice(data);
             log::trace!("    -> Copied successfully");
 
-            // Track .rodata end for .data LMA calculation
+            // Track .rodata end for .data LMA calculation (link address, unbiased)
             if section_name == ".rodata" {
-                rodata_end = Some((offset + data.len()) as u64);
+                rodata_end = Some(vma + data.len() as u64);
                 log::trace!(
                     "Tracked .rodata end at 0x{:x} for .data LMA calculation",
                     rodata_end.unwrap()
@@ -205,6 +211,7 @@ pub fn load_sections(obj: &object::File, rom: &mut [u8], _ram: &mut [u8]) -> Res
             } else {
                 next_rom_offset as usize
             };
+            let rom_offset = rom_offset + load_bias as usize;
 
             if section_name == ".data" {
                 log::trace!(
@@ -0,0 +1,86 @@
+//! Memory permissions from ELF load segments.
+
+use super::memory::is_rom_address;
+use alloc::string::String;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+use lp_riscv_emu::{MemoryPermissions, MemoryRegion, MemoryRegionKind};
+use object::elf::{PF_R, PF_W, PF_X};
+use object::{Object, ObjectSegment, SegmentFlags};
+
+/// Convert ELF program header flags to memory permissions.
+pub fn permissions_from_flags(p_flags: u32) -> MemoryPermissions {
+    MemoryPermissions {
+        read: p_flags & PF_R != 0,
+        write: p_flags & PF_W != 0,
+        execute: p_flags & PF_X != 0,
+    }
+}
+
+/// Collect the address range and permissions of each PT_LOAD segment.
+///
+/// ROM segments are moved by `load_bias`.
+pub fn segment_permissions(obj: &object::File, load_bias: u32) -> Vec<MemoryRegion> {
+    let mut regions = Vec::new();
+    for segment in obj.segments() {
+        let SegmentFlags::Elf { p_flags } = segment.flags() else {
+            continue;
+        };
+        let start = segment.address();
+        let size = segment.size();
+        if size == 0 {
+            continue;
+        }
+
+        let permissions = permissions_from_flags(p_flags);
+        log::debug!("  Segment: addr=0x{start:x}, size={size}, permissions={permissions}");
+
+        let (start, kind) = if is_rom_address(start) {
+            (start as u32 + load_bias, MemoryRegionKind::Rom)
+        } else {
+            (start as u32, MemoryRegionKind::Ram)
+        };
+        regions.push(MemoryRegion {
+            start,
+            size: size as u32,
+            kind,
+            permissions,
+        });
+    }
+    regions
+}
+
+/// The heap between the `__heap_start` and `__heap_end` linker symbols, as RWX.
+///
+/// The heap is a NOLOAD section, so it lands in a read/write segment, but guests
+/// (e.g. the firmware's shader JIT) execute code they allocate there.
+pub fn heap_region(symbol_map: &HashMap<String, u32>) -> Option<MemoryRegion> {
+    let start = *symbol_map.get("__heap_start")?;
+    let end = *symbol_map.get("__heap_end")?;
+    if end <= start {
+        return None;
+    }
+    Some(MemoryRegion {
+        start,
+        size: end - start,
+        kind: MemoryRegionKind::Ram,
+        permissions: MemoryPermissions::RWX,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permissions_from_flags() {
+        assert_eq!(permissions_from_flags(PF_R | PF_X), MemoryPermissions::RX);
+        assert_eq!(permissions_from_flags(PF_R), MemoryPermissions::R);
+        assert_eq!(permissions_from_flags(PF_R | PF_W), MemoryPermissions::RW);
+        assert_eq!(
+            permissions_from_flags(PF_R | PF_W | PF_X),
+            MemoryPermissions::RWX
+        );
+        assert_eq!(permissions_from_flags(0), MemoryPermissions::NONE);
+    }
+}
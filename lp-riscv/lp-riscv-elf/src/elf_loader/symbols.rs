@@ -1,6 +1,6 @@
 //! Symbol map building for relocations.
 
-use super::memory::is_ram_address;
+use super::memory::{is_ram_address, is_rom_address};
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
@@ -10,9 +10,15 @@ use object::{Object, ObjectSymbol, SymbolSection};
 /// Build a comprehensive symbol map for relocations.
 ///
 /// Returns a map from symbol name to address (offset for ROM symbols, absolute for RAM symbols).
-pub fn build_symbol_map(obj: &object::File, text_base: u64) -> HashMap<String, u32> {
+/// ROM symbols defined in a section are moved by `load_bias`; absolute symbols (e.g. sizes
+/// from the linker script) are left as-is.
+pub fn build_symbol_map(
+    obj: &object::File,
+    text_base: u64,
+    load_bias: u32,
+) -> HashMap<String, u32> {
     log::debug!("=== Building symbol map for relocations ===");
-    log::debug!("text_section_base: 0x{text_base:x}");
+    log::debug!("text_section_base: 0x{text_base:x}, load_bias: 0x{load_bias:x}");
 
     let mut symbol_map: HashMap<String, u32> = HashMap::new();
 
@@ -33,7 +39,7 @@ pub fn build_symbol_map(obj: &object::File, text_base: u64) -> HashMap<String, u
             // Calculate offset/address
             // For RAM symbols, use absolute address
             // For ROM symbols, use offset relative to text base
-            let offset = if is_ram_address(addr) {
+            let mut offset = if is_ram_address(addr) {
                 // RAM symbol - use absolute address
                 addr as u32
             } else if addr >= text_base {
@@ -43,6 +49,9 @@ pub fn build_symbol_map(obj: &object::File, text_base: u64) -> HashMap<String, u
                 // Other ROM sections - use as-is
                 addr as u32
             };
+            if is_rom_address(addr) && matches!(symbol_section, SymbolSection::Section(_)) {
+                offset = offset.wrapping_add(load_bias);
+            }
 
             if is_defined {
                 defined_symbols.push((name.to_string(), offset, symbol_section));
@@ -10,6 +10,10 @@ extern crate alloc;
 
 mod elf_linker;
 mod elf_loader;
+#[cfg(test)]
+mod test_util;
 
-pub use elf_linker::{LinkerError, link_static_library};
-pub use elf_loader::{ElfLoadInfo, find_symbol_address, load_elf, load_object_file};
+pub use elf_linker::{LinkerError, link_static_library, link_static_library_with_gc};
+pub use elf_loader::{
+    ElfLoadInfo, find_symbol_address, load_elf, load_elf_with_bias, load_object_file,
+};
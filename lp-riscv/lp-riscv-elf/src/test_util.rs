@@ -0,0 +1,24 @@
+//! Helpers shared by the loader and linker tests.
+
+use crate::ElfLoadInfo;
+use lp_riscv_emu::Riscv32Emulator;
+use lp_riscv_inst::Gpr;
+
+/// Call a loaded function and return a0
+pub fn call_function(load_info: &ElfLoadInfo, name: &str) -> i32 {
+    let ram_size = load_info.ram.len() as u32;
+    let mut emu = Riscv32Emulator::new(load_info.code.clone(), load_info.ram.clone());
+    emu.set_register(Gpr::Sp, 0x80000000u32.wrapping_add(ram_size - 16) as i32);
+    let halt_address = 0x80000000u32.wrapping_add(ram_size);
+    emu.set_register(Gpr::Ra, halt_address as i32);
+    emu.set_pc(load_info.symbol_map[name]);
+
+    for _ in 0..1000 {
+        emu.step()
+            .unwrap_or_else(|e| panic!("Emulator error in {name}: {e}"));
+        if emu.get_pc() == halt_address {
+            return emu.get_register(Gpr::A0);
+        }
+    }
+    panic!("{name} did not return");
+}
@@ -0,0 +1,248 @@
+//! Segment permissions applied when a linked ELF is loaded into the emulator
+//!
+//! Builds a small executable with the same shape as a guest image: code in ROM,
+//! read-only and read/write data in RAM, and a NOLOAD heap bounded by
+//! `__heap_start` / `__heap_end`.
+
+use lp_riscv_elf::load_elf;
+use lp_riscv_emu::{EmulatorError, MemoryAccessKind, StepResult};
+use lp_riscv_inst::{Gpr, encode};
+use object::Endianness;
+use object::elf;
+use object::write::elf::{FileHeader, ProgramHeader, SectionHeader, Sym, Writer};
+
+const RODATA_ADDR: u32 = 0x8000_0000;
+const DATA_ADDR: u32 = 0x8000_0100;
+const HEAP_START: u32 = 0x8000_1000;
+const HEAP_SIZE: u32 = 0x1000;
+
+/// Link a RISC-V executable whose entry point stores `a1` to the address in `a0`
+fn build_executable() -> Vec<u8> {
+    let text: Vec<u8> = [encode::sw(Gpr::A0, Gpr::A1, 0), encode::ebreak()]
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect();
+    let rodata = [0x5a; 16];
+    let data = [0; 16];
+
+    let mut buffer = Vec::new();
+    let mut writer = Writer::new(Endianness::Little, false, &mut buffer);
+
+    // Reserve the file layout
+    writer.reserve_file_header();
+    writer.reserve_program_headers(4);
+    let text_offset = writer.reserve(text.len(), 4);
+    let rodata_offset = writer.reserve(rodata.len(), 4);
+    let data_offset = writer.reserve(data.len(), 4);
+
+    writer.reserve_null_section_index();
+    let text_name = writer.add_section_name(b".text");
+    let text_index = writer.reserve_section_index();
+    let rodata_name = writer.add_section_name(b".rodata");
+    writer.reserve_section_index();
+    let data_name = writer.add_section_name(b".data");
+    writer.reserve_section_index();
+    let heap_name = writer.add_section_name(b".heap");
+    let heap_index = writer.reserve_section_index();
+    writer.reserve_symtab_section_index();
+    writer.reserve_strtab_section_index();
+    writer.reserve_shstrtab_section_index();
+
+    writer.reserve_null_symbol_index();
+    let symbols = [
+        (writer.add_string(b"_start"), text_index, 0),
+        (writer.add_string(b"__heap_start"), heap_index, HEAP_START),
+        (
+            writer.add_string(b"__heap_end"),
+            heap_index,
+            HEAP_START + HEAP_SIZE,
+        ),
+    ];
+    for (_, section, _) in &symbols {
+        writer.reserve_symbol_index(Some(*section));
+    }
+    writer.reserve_symtab();
+    writer.reserve_strtab();
+    writer.reserve_shstrtab();
+    writer.reserve_section_headers();
+
+    // Write it out in the same order
+    writer
+        .write_file_header(&FileHeader {
+            os_abi: elf::ELFOSABI_NONE,
+            abi_version: 0,
+            e_type: elf::ET_EXEC,
+            e_machine: elf::EM_RISCV,
+            e_entry: 0,
+            e_flags: 0,
+        })
+        .unwrap();
+
+    writer.write_align_program_headers();
+    let load = |offset: usize, addr: u32, filesz: usize, memsz: u32, p_flags| ProgramHeader {
+        p_type: elf::PT_LOAD,
+        p_flags,
+        p_offset: offset as u64,
+        p_vaddr: u64::from(addr),
+        p_paddr: u64::from(addr),
+        p_filesz: filesz as u64,
+        p_memsz: u64::from(memsz),
+        p_align: 4,
+    };
+    for header in [
+        load(
+            text_offset,
+            0,
+            text.len(),
+            text.len() as u32,
+            elf::PF_R | elf::PF_X,
+        ),
+        load(
+            rodata_offset,
+            RODATA_ADDR,
+            rodata.len(),
+            rodata.len() as u32,
+            elf::PF_R,
+        ),
+        load(
+            data_offset,
+            DATA_ADDR,
+            data.len(),
+            data.len() as u32,
+            elf::PF_R | elf::PF_W,
+        ),
+        load(data_offset, HEAP_START, 0, HEAP_SIZE, elf::PF_R | elf::PF_W),
+    ] {
+        writer.write_program_header(&header);
+    }
+
+    writer.write_align(4);
+    writer.write(&text);
+    writer.write_align(4);
+    writer.write(&rodata);
+    writer.write_align(4);
+    writer.write(&data);
+
+    writer.write_null_symbol();
+    for (name, section, value) in symbols {
+        writer.write_symbol(&Sym {
+            name: Some(name),
+            section: Some(section),
+            st_info: (elf::STB_GLOBAL << 4) | elf::STT_NOTYPE,
+            st_other: elf::STV_DEFAULT,
+            st_shndx: 0,
+            st_value: u64::from(value),
+            st_size: 0,
+        });
+    }
+    writer.write_strtab();
+    writer.write_shstrtab();
+
+    writer.write_null_section_header();
+    let section =
+        |name, sh_type, sh_flags: u32, addr: u32, offset: usize, size: u32| SectionHeader {
+            name: Some(name),
+            sh_type,
+            sh_flags: u64::from(sh_flags),
+            sh_addr: u64::from(addr),
+            sh_offset: offset as u64,
+            sh_size: u64::from(size),
+            sh_link: 0,
+            sh_info: 0,
+            sh_addralign: 4,
+            sh_entsize: 0,
+        };
+    for header in [
+        section(
+            text_name,
+            elf::SHT_PROGBITS,
+            elf::SHF_ALLOC | elf::SHF_EXECINSTR,
+            0,
+            text_offset,
+            text.len() as u32,
+        ),
+        section(
+            rodata_name,
+            elf::SHT_PROGBITS,
+            elf::SHF_ALLOC,
+            RODATA_ADDR,
+            rodata_offset,
+            rodata.len() as u32,
+        ),
+        section(
+            data_name,
+            elf::SHT_PROGBITS,
+            elf::SHF_ALLOC | elf::SHF_WRITE,
+            DATA_ADDR,
+            data_offset,
+            data.len() as u32,
+        ),
+        section(
+            heap_name,
+            elf::SHT_NOBITS,
+            elf::SHF_ALLOC | elf::SHF_WRITE,
+            HEAP_START,
+            data_offset,
+            HEAP_SIZE,
+        ),
+    ] {
+        writer.write_section_header(&header);
+    }
+    writer.write_symtab_section_header(1);
+    writer.write_strtab_section_header();
+    writer.write_shstrtab_section_header();
+
+    buffer
+}
+
+#[test]
+fn test_store_to_rodata_faults() {
+    let load_info = load_elf(&build_executable()).expect("Failed to load ELF");
+    let mut emu = load_info.into_emulator();
+
+    let target = RODATA_ADDR + 4;
+    emu.set_register(Gpr::A0, target as i32);
+    emu.set_register(Gpr::A1, 42);
+    match emu.step() {
+        Err(EmulatorError::InvalidMemoryAccess {
+            address, kind, pc, ..
+        }) => {
+            assert_eq!(address, target);
+            assert_eq!(kind, MemoryAccessKind::Write);
+            assert_eq!(pc, 0);
+        }
+        other => panic!("Expected store fault, got {other:?}"),
+    }
+
+    // The same store into .data goes through
+    emu.set_pc(0);
+    emu.set_register(Gpr::A0, DATA_ADDR as i32);
+    emu.step().expect("Store to .data should succeed");
+    assert_eq!(emu.memory().read_word(DATA_ADDR).unwrap(), 42);
+}
+
+#[test]
+fn test_data_is_not_executable_but_heap_is() {
+    let load_info = load_elf(&build_executable()).expect("Failed to load ELF");
+    let mut emu = load_info.into_emulator();
+
+    let ebreak = encode::ebreak() as i32;
+    emu.memory_mut().write_word(DATA_ADDR, ebreak).unwrap();
+    emu.memory_mut().write_word(HEAP_START, ebreak).unwrap();
+
+    emu.set_pc(DATA_ADDR);
+    match emu.step() {
+        Err(EmulatorError::InvalidMemoryAccess { address, kind, .. }) => {
+            assert_eq!(address, DATA_ADDR);
+            assert_eq!(kind, MemoryAccessKind::InstructionFetch);
+        }
+        other => panic!("Expected instruction fetch fault, got {other:?}"),
+    }
+
+    // Guests JIT into the heap, so it stays executable despite its RW segment
+    emu.set_pc(HEAP_START);
+    match emu.step() {
+        Ok(StepResult::Halted) => {}
+        other => panic!("Expected Halted, got {other:?}"),
+    }
+}
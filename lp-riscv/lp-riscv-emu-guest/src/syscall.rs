@@ -1,7 +1,7 @@
 // Re-export syscall constants from shared crate
 pub use lp_riscv_emu_shared::{
-    SYSCALL_ARGS, SYSCALL_LOG, SYSCALL_PANIC, SYSCALL_SERIAL_HAS_DATA, SYSCALL_SERIAL_READ,
-    SYSCALL_SERIAL_WRITE, SYSCALL_TIME_MS, SYSCALL_WRITE, SYSCALL_YIELD,
+    SYSCALL_ARGS, SYSCALL_LOG, SYSCALL_PANIC, SYSCALL_SCRATCH_ALLOC, SYSCALL_SERIAL_HAS_DATA,
+    SYSCALL_SERIAL_READ, SYSCALL_SERIAL_WRITE, SYSCALL_TIME_MS, SYSCALL_WRITE, SYSCALL_YIELD,
 };
 
 /// System call implementation
@@ -76,3 +76,17 @@ pub fn sys_serial_has_data() -> bool {
     let args = [0i32; SYSCALL_ARGS];
     syscall(SYSCALL_SERIAL_HAS_DATA, &args) != 0
 }
+
+/// Allocate from the emulator's scratch region
+///
+/// The memory is zeroed and is freed when the host next calls into the guest.
+///
+/// # Returns
+/// * Pointer to `size` bytes aligned to `align`
+/// * Null if there is no scratch region or no room left
+pub fn sys_scratch_alloc(size: usize, align: usize) -> *mut u8 {
+    let mut args = [0i32; SYSCALL_ARGS];
+    args[0] = size as i32;
+    args[1] = align as i32;
+    syscall(SYSCALL_SCRATCH_ALLOC, &args) as u32 as *mut u8
+}
@@ -12,9 +12,9 @@ mod simple_elf;
 mod syscall;
 
 pub use syscall::{
-    SYSCALL_ARGS, SYSCALL_LOG, SYSCALL_PANIC, SYSCALL_SERIAL_HAS_DATA, SYSCALL_SERIAL_READ,
-    SYSCALL_SERIAL_WRITE, SYSCALL_TIME_MS, SYSCALL_WRITE, SYSCALL_YIELD, level_to_syscall,
-    syscall_to_level,
+    SYSCALL_ARGS, SYSCALL_LOG, SYSCALL_PANIC, SYSCALL_SCRATCH_ALLOC, SYSCALL_SERIAL_HAS_DATA,
+    SYSCALL_SERIAL_READ, SYSCALL_SERIAL_WRITE, SYSCALL_TIME_MS, SYSCALL_WRITE, SYSCALL_YIELD,
+    level_to_syscall, syscall_to_level,
 };
 
 pub use guest_serial::{
@@ -24,6 +24,13 @@ pub const SYSCALL_SERIAL_HAS_DATA: i32 = 7;
 /// Syscall number for time_ms (get elapsed milliseconds since emulator start)
 pub const SYSCALL_TIME_MS: i32 = 8;
 
+/// Syscall number for scratch_alloc (allocate from the emulator's scratch region)
+///
+/// args[0] = size in bytes, args[1] = alignment (a power of two). Returns the address
+/// of the allocation, or 0 if there is no room. The scratch region is reset before each
+/// function call, so allocations are never freed individually.
+pub const SYSCALL_SCRATCH_ALLOC: i32 = 9;
+
 /// Number of syscall arguments
 pub const SYSCALL_ARGS: usize = 7;
 
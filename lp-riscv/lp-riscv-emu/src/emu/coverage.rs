@@ -0,0 +1,115 @@
+//! Instruction coverage tracking.
+//!
+//! Records which instructions a run executes so codegen and emulator coverage
+//! gaps can be found. Raw instruction words are counted while running and only
+//! decoded into mnemonics when a report is requested, keeping the hot path cheap.
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+use hashbrown::{HashMap, HashSet};
+use lp_riscv_inst::decode_instruction;
+
+/// Mnemonic reported for words that fail to decode.
+pub const UNKNOWN_MNEMONIC: &str = ".word";
+
+/// Set of executed instructions, optionally with the PCs they ran at.
+#[derive(Debug, Clone, Default)]
+pub struct InstCoverage {
+    /// Execution count per raw instruction word
+    words: HashMap<u32, u64>,
+    /// Executed PCs (only when PC tracking is enabled)
+    pcs: Option<HashSet<u32>>,
+}
+
+impl InstCoverage {
+    /// Create an empty coverage set.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_pcs` - Also record the distinct PCs executed
+    pub fn new(track_pcs: bool) -> Self {
+        Self {
+            words: HashMap::new(),
+            pcs: track_pcs.then(HashSet::new),
+        }
+    }
+
+    /// Record one executed instruction.
+    #[inline]
+    pub fn record(&mut self, pc: u32, inst_word: u32) {
+        *self.words.entry(inst_word).or_insert(0) += 1;
+        if let Some(pcs) = &mut self.pcs {
+            pcs.insert(pc);
+        }
+    }
+
+    /// Merge another run's coverage into this one (for aggregating a test suite).
+    pub fn merge(&mut self, other: &InstCoverage) {
+        for (word, count) in &other.words {
+            *self.words.entry(*word).or_insert(0) += count;
+        }
+        if let (Some(pcs), Some(other_pcs)) = (&mut self.pcs, &other.pcs) {
+            pcs.extend(other_pcs);
+        }
+    }
+
+    /// Execution count per opcode mnemonic (e.g. `addi`, `c.lw`), sorted by mnemonic.
+    pub fn opcodes(&self) -> BTreeMap<String, u64> {
+        let mut opcodes = BTreeMap::new();
+        for (word, count) in &self.words {
+            *opcodes.entry(mnemonic(*word)).or_insert(0) += count;
+        }
+        opcodes
+    }
+
+    /// Whether an opcode mnemonic was executed.
+    pub fn contains_opcode(&self, opcode: &str) -> bool {
+        self.words.keys().any(|word| mnemonic(*word) == opcode)
+    }
+
+    /// Distinct PCs executed, if PC tracking is enabled.
+    pub fn pcs(&self) -> Option<BTreeSet<u32>> {
+        self.pcs.as_ref().map(|pcs| pcs.iter().copied().collect())
+    }
+
+    /// Total number of instructions recorded.
+    pub fn instruction_count(&self) -> u64 {
+        self.words.values().sum()
+    }
+
+    /// Format a human-readable report: one line per opcode with its count.
+    pub fn report(&self) -> String {
+        let opcodes = self.opcodes();
+        let mut report = format!(
+            "Instruction coverage: {} distinct opcodes, {} instructions",
+            opcodes.len(),
+            self.instruction_count()
+        );
+        if let Some(pcs) = &self.pcs {
+            let _ = write!(report, ", {} distinct PCs", pcs.len());
+        }
+        report.push('\n');
+        for (opcode, count) in &opcodes {
+            let _ = writeln!(report, "  {opcode:<10} {count}");
+        }
+        report
+    }
+}
+
+/// Mnemonic of an instruction word (first token of its disassembly).
+fn mnemonic(inst_word: u32) -> String {
+    match decode_instruction(inst_word) {
+        Ok(inst) => {
+            let text = inst.format();
+            match text.split_once(' ') {
+                Some((mnemonic, _)) => String::from(mnemonic),
+                None => text,
+            }
+        }
+        Err(_) => String::from(UNKNOWN_MNEMONIC),
+    }
+}
@@ -0,0 +1,163 @@
+//! In-order pipeline cycle model.
+//!
+//! Estimates how many cycles a run would take on a single-issue, in-order core:
+//! every instruction issues in one cycle, but an instruction reading a register
+//! whose value is still being produced by a multiply, divide or load waits until
+//! it is ready. The waits are counted as stall cycles, so codegen changes that
+//! reorder instructions can be compared without hardware. Branch and cache
+//! effects are not modeled.
+
+use lp_riscv_inst::Inst;
+use lp_riscv_inst::decode_rvc::decode_compressed;
+
+// These latencies are the source of truth for the compiler's instruction scheduler
+// (`lp_glsl_compiler::backend::schedule`), which keeps its own copy; change both.
+
+/// Cycles until a multiply result can be used
+pub const MUL_LATENCY: u64 = 3;
+/// Cycles until a divide or remainder result can be used
+pub const DIV_LATENCY: u64 = 8;
+/// Cycles until loaded data can be used
+pub const LOAD_LATENCY: u64 = 2;
+
+/// Cycle and stall counts for the instructions executed so far.
+#[derive(Debug, Clone)]
+pub struct CycleModel {
+    /// Cycle at which each register's pending value is ready
+    ready_at: [u64; 32],
+    cycles: u64,
+    stall_cycles: u64,
+}
+
+/// Registers an instruction reads and writes (x0 for none) and its result latency.
+struct Operands {
+    rd: u8,
+    rs1: u8,
+    rs2: u8,
+    latency: u64,
+}
+
+impl CycleModel {
+    /// Create a model with all registers ready.
+    pub fn new() -> Self {
+        Self {
+            ready_at: [0; 32],
+            cycles: 0,
+            stall_cycles: 0,
+        }
+    }
+
+    /// Record one executed instruction.
+    #[inline]
+    pub fn record(&mut self, inst_word: u32) {
+        let ops = operands(inst_word);
+        let issue = self
+            .cycles
+            .max(self.ready_at[ops.rs1 as usize])
+            .max(self.ready_at[ops.rs2 as usize]);
+        self.stall_cycles += issue - self.cycles;
+        self.cycles = issue + 1;
+        if ops.rd != 0 {
+            self.ready_at[ops.rd as usize] = issue + ops.latency;
+        }
+    }
+
+    /// Total cycles, including stalls.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Cycles spent waiting for results.
+    pub fn stall_cycles(&self) -> u64 {
+        self.stall_cycles
+    }
+}
+
+impl Default for CycleModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decode the register operands of an instruction word.
+fn operands(inst_word: u32) -> Operands {
+    if inst_word & 0x3 != 0x3 {
+        return compressed_operands(inst_word as u16);
+    }
+
+    let rd = ((inst_word >> 7) & 0x1f) as u8;
+    let rs1 = ((inst_word >> 15) & 0x1f) as u8;
+    let rs2 = ((inst_word >> 20) & 0x1f) as u8;
+    let funct3 = (inst_word >> 12) & 0x7;
+    let ops = |rd, rs1, rs2, latency| Operands {
+        rd,
+        rs1,
+        rs2,
+        latency,
+    };
+    match inst_word & 0x7f {
+        // OP, including the M extension (funct7 = 1)
+        0x33 => {
+            let latency = match (inst_word >> 25, funct3) {
+                (0x01, 0..=3) => MUL_LATENCY,
+                (0x01, _) => DIV_LATENCY,
+                _ => 1,
+            };
+            ops(rd, rs1, rs2, latency)
+        }
+        // OP-IMM, JALR
+        0x13 | 0x67 => ops(rd, rs1, 0, 1),
+        // LOAD
+        0x03 => ops(rd, rs1, 0, LOAD_LATENCY),
+        // AMO
+        0x2f => ops(rd, rs1, rs2, LOAD_LATENCY),
+        // STORE, BRANCH
+        0x23 | 0x63 => ops(0, rs1, rs2, 1),
+        // LUI, AUIPC, JAL
+        0x37 | 0x17 | 0x6f => ops(rd, 0, 0, 1),
+        // CSR instructions (register forms read rs1)
+        0x73 if funct3 != 0 => ops(rd, if funct3 < 4 { rs1 } else { 0 }, 0, 1),
+        _ => ops(0, 0, 0, 1),
+    }
+}
+
+/// Decode the register operands of a compressed instruction.
+fn compressed_operands(inst: u16) -> Operands {
+    let ops = |rd: u8, rs1: u8, rs2: u8, latency| Operands {
+        rd,
+        rs1,
+        rs2,
+        latency,
+    };
+    const SP: u8 = 2;
+    const RA: u8 = 1;
+    match decode_compressed(inst) {
+        Ok(Inst::CLw { rd, rs, .. }) => ops(rd.num(), rs.num(), 0, LOAD_LATENCY),
+        Ok(Inst::CLwsp { rd, .. }) => ops(rd.num(), SP, 0, LOAD_LATENCY),
+        Ok(Inst::CSw { rs1, rs2, .. }) => ops(0, rs1.num(), rs2.num(), 1),
+        Ok(Inst::CSwsp { rs, .. }) => ops(0, SP, rs.num(), 1),
+        Ok(
+            Inst::CAddi { rd, .. }
+            | Inst::CSlli { rd, .. }
+            | Inst::CSrli { rd, .. }
+            | Inst::CSrai { rd, .. }
+            | Inst::CAndi { rd, .. },
+        ) => ops(rd.num(), rd.num(), 0, 1),
+        Ok(Inst::CLi { rd, .. } | Inst::CLui { rd, .. }) => ops(rd.num(), 0, 0, 1),
+        Ok(Inst::CAddi4spn { rd, .. }) => ops(rd.num(), SP, 0, 1),
+        Ok(Inst::CAddi16sp { .. }) => ops(SP, SP, 0, 1),
+        Ok(Inst::CMv { rd, rs }) => ops(rd.num(), rs.num(), 0, 1),
+        Ok(
+            Inst::CAdd { rd, rs }
+            | Inst::CSub { rd, rs }
+            | Inst::CAnd { rd, rs }
+            | Inst::COr { rd, rs }
+            | Inst::CXor { rd, rs },
+        ) => ops(rd.num(), rd.num(), rs.num(), 1),
+        Ok(Inst::CJr { rs }) => ops(0, rs.num(), 0, 1),
+        Ok(Inst::CJalr { rs }) => ops(RA, rs.num(), 0, 1),
+        Ok(Inst::CBeqz { rs, .. } | Inst::CBnez { rs, .. }) => ops(0, rs.num(), 0, 1),
+        Ok(Inst::CJal { .. }) => ops(RA, 0, 0, 1),
+        _ => ops(0, 0, 0, 1),
+    }
+}
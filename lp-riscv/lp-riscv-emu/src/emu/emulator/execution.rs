@@ -8,6 +8,7 @@ use super::super::{
     logging::LogLevel,
     memory::Memory,
 };
+use super::replay::ReplayInput;
 use super::state::Riscv32Emulator;
 use super::types::{PanicInfo, StepResult, SyscallInfo};
 use alloc::{format, string::String, vec, vec::Vec};
@@ -46,6 +47,13 @@ impl Riscv32Emulator {
             e
         })?;
 
+        if let Some(coverage) = &mut self.coverage {
+            coverage.record(self.pc, inst_word);
+        }
+        if let Some(cycle_model) = &mut self.cycle_model {
+            cycle_model.record(inst_word);
+        }
+
         // Check if compressed instruction (bits [1:0] != 0b11)
         let is_compressed = (inst_word & 0x3) != 0x3;
 
@@ -285,14 +293,18 @@ impl Riscv32Emulator {
                     self.regs[Gpr::A0.num() as usize] = SERIAL_ERROR_INVALID_POINTER;
                     Ok(StepResult::Continue)
                 } else {
-                    let serial = self.get_or_create_serial_host();
-                    log::trace!(
-                        "SYSCALL_SERIAL_WRITE: Writing {} bytes to serial",
-                        data.len()
-                    );
-                    let result = serial.guest_write(&data);
-                    log::trace!("SYSCALL_SERIAL_WRITE: guest_write returned {result}");
-                    self.regs[Gpr::A0.num() as usize] = result;
+                    let syscall = lp_riscv_emu_shared::SYSCALL_SERIAL_WRITE;
+                    let input = self.external_input(syscall, |emu| {
+                        let serial = emu.get_or_create_serial_host();
+                        log::trace!(
+                            "SYSCALL_SERIAL_WRITE: Writing {} bytes to serial",
+                            data.len()
+                        );
+                        let result = serial.guest_write(&data);
+                        log::trace!("SYSCALL_SERIAL_WRITE: guest_write returned {result}");
+                        ReplayInput::value(syscall, result)
+                    })?;
+                    self.regs[Gpr::A0.num() as usize] = input.a0;
                     Ok(StepResult::Continue)
                 }
             } else if syscall_info.number == lp_riscv_emu_shared::SYSCALL_SERIAL_READ {
@@ -307,17 +319,29 @@ impl Riscv32Emulator {
                 const MAX_READ_LEN: usize = 64 * 1024; // 64KB max per read
                 let max_len = max_len.min(MAX_READ_LEN);
 
-                // Allocate buffer for reading
-                let mut buffer = vec![0u8; max_len];
-                let serial = self.get_or_create_serial_host();
-                let bytes_read = serial.guest_read(&mut buffer);
+                let syscall = lp_riscv_emu_shared::SYSCALL_SERIAL_READ;
+                let input = self.external_input(syscall, |emu| {
+                    // Allocate buffer for reading
+                    let mut buffer = vec![0u8; max_len];
+                    let serial = emu.get_or_create_serial_host();
+                    let bytes_read = serial.guest_read(&mut buffer);
 
-                log::trace!(
-                    "SYSCALL_SERIAL_READ: max_len={}, bytes_read={}, buffer[0..10]={:?}",
-                    max_len,
-                    bytes_read,
-                    &buffer[..buffer.len().min(10)]
-                );
+                    log::trace!(
+                        "SYSCALL_SERIAL_READ: max_len={}, bytes_read={}, buffer[0..10]={:?}",
+                        max_len,
+                        bytes_read,
+                        &buffer[..buffer.len().min(10)]
+                    );
+
+                    buffer.truncate(bytes_read.max(0) as usize);
+                    ReplayInput {
+                        syscall,
+                        a0: bytes_read,
+                        data: buffer,
+                    }
+                })?;
+                let bytes_read = input.a0;
+                let buffer = input.data;
 
                 if bytes_read < 0 {
                     // Error
@@ -356,29 +380,45 @@ impl Riscv32Emulator {
             } else if syscall_info.number == lp_riscv_emu_shared::SYSCALL_SERIAL_HAS_DATA {
                 // SYSCALL_SERIAL_HAS_DATA: Check if serial input has data
                 // Returns: a0 = 1 if data available, 0 otherwise
-                let has_data = self
-                    .serial_host
-                    .as_ref()
-                    .map(|s| s.has_data())
-                    .unwrap_or(false);
-
-                self.regs[Gpr::A0.num() as usize] = if has_data { 1 } else { 0 };
+                let syscall = lp_riscv_emu_shared::SYSCALL_SERIAL_HAS_DATA;
+                let input = self.external_input(syscall, |emu| {
+                    let has_data = emu
+                        .serial_host
+                        .as_ref()
+                        .map(|s| s.has_data())
+                        .unwrap_or(false);
+                    ReplayInput::value(syscall, if has_data { 1 } else { 0 })
+                })?;
+                self.regs[Gpr::A0.num() as usize] = input.a0;
                 Ok(StepResult::Continue)
             } else if syscall_info.number == lp_riscv_emu_shared::SYSCALL_TIME_MS {
                 // SYSCALL_TIME_MS: Get elapsed milliseconds since emulator start
                 // Returns: a0 = elapsed milliseconds (u32)
-                #[cfg(feature = "std")]
-                {
-                    self.init_start_time_if_needed();
-                    let elapsed = self.elapsed_ms();
-                    self.regs[Gpr::A0.num() as usize] = elapsed as i32;
-                }
-                #[cfg(not(feature = "std"))]
-                {
+                let syscall = lp_riscv_emu_shared::SYSCALL_TIME_MS;
+                let input = self.external_input(syscall, |emu| {
+                    #[cfg(feature = "std")]
+                    let elapsed = {
+                        emu.init_start_time_if_needed();
+                        emu.elapsed_ms() as i32
+                    };
                     // Return 0 if std feature not enabled
-                    self.regs[Gpr::A0.num() as usize] = 0;
-                }
+                    #[cfg(not(feature = "std"))]
+                    let elapsed = {
+                        let _ = emu;
+                        0
+                    };
+                    ReplayInput::value(syscall, elapsed)
+                })?;
+                self.regs[Gpr::A0.num() as usize] = input.a0;
 
+                Ok(StepResult::Continue)
+            } else if syscall_info.number == lp_riscv_emu_shared::SYSCALL_SCRATCH_ALLOC {
+                // SYSCALL_SCRATCH_ALLOC: Allocate from the scratch region
+                // args[0] = size, args[1] = alignment
+                // Returns: a0 = address (0 if there is no room)
+                let address =
+                    self.scratch_alloc(syscall_info.args[0] as u32, syscall_info.args[1] as u32);
+                self.regs[Gpr::A0.num() as usize] = address as i32;
                 Ok(StepResult::Continue)
             } else {
                 Ok(StepResult::Syscall(syscall_info))
@@ -394,7 +434,10 @@ impl Riscv32Emulator {
     /// For running multiple instructions efficiently, use `run()` or `run_fuel()`.
     pub fn step(&mut self) -> Result<StepResult, EmulatorError> {
         // No fuel check - fuel is per-run, not global
-        self.step_inner()
+        self.record_host_writes();
+        let result = self.step_inner();
+        self.record_call(None, &result);
+        result
     }
 }
 
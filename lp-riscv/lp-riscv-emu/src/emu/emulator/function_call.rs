@@ -42,6 +42,8 @@ impl Riscv32Emulator {
         // Clear log buffer for clean output on this function call
         self.clear_logs();
         self.instruction_count = 0;
+        // Each call starts with empty, zeroed scratch memory
+        self.reset_scratch();
 
         // Check if function uses StructReturn
         if has_struct_return(signature) {
@@ -191,6 +193,9 @@ impl Riscv32Emulator {
             });
         }
 
+        // Each call starts with empty, zeroed scratch memory
+        self.reset_scratch();
+
         // Allocate buffer for struct return
         let buffer_addr = allocate_struct_return_buffer(self, struct_size)?;
 
@@ -7,15 +7,21 @@
 //! - `execution`: Instruction execution
 //! - `function_call`: Function calling with ABI setup
 //! - `run_loops`: High-level run methods
+//! - `replay`: Recording and replay of external inputs
+//! - `scratch`: Scratch memory reset before each function call
 //! - `debug`: Debug formatting and logging
 
 mod debug;
 mod execution;
 mod function_call;
 mod registers;
+mod replay;
 mod run_loops;
+mod scratch;
 mod state;
 mod types;
 
+pub use replay::{ReplayCall, ReplayInput, ReplayLog};
+pub use scratch::ScratchConfig;
 pub use state::{DEFAULT_RAM_START, Riscv32Emulator};
 pub use types::{PanicInfo, StepResult, SyscallInfo};
@@ -1,7 +1,8 @@
 //! Register, PC, and memory accessor methods.
 
-use super::super::memory::Memory;
+use super::super::memory::{Memory, MemoryPermissions, MemoryRegion, MemoryRegionKind};
 use super::state::Riscv32Emulator;
+use alloc::vec::Vec;
 use lp_riscv_inst::Gpr;
 
 impl Riscv32Emulator {
@@ -42,4 +43,45 @@ impl Riscv32Emulator {
     pub fn memory_mut(&mut self) -> &mut Memory {
         &mut self.memory
     }
+
+    /// Get the memory map: code, RAM, guard, and scratch regions, sorted by start address.
+    pub fn memory_regions(&self) -> Vec<MemoryRegion> {
+        let mut regions = self.memory.memory_regions();
+        if let Some(scratch) = self.scratch_config() {
+            regions.push(MemoryRegion {
+                start: scratch.start,
+                size: scratch.size,
+                kind: MemoryRegionKind::Scratch,
+                permissions: MemoryPermissions::RW,
+            });
+            regions.sort_by_key(|region| region.start);
+        }
+        regions
+    }
+
+    /// Set the access permissions of part of the code or RAM region.
+    pub fn with_permissions(
+        mut self,
+        start: u32,
+        size: u32,
+        permissions: MemoryPermissions,
+    ) -> Self {
+        self.memory.set_permissions(start, size, permissions);
+        self
+    }
+
+    /// Apply the permissions of each region (e.g. ELF load segments).
+    pub fn with_region_permissions(mut self, regions: &[MemoryRegion]) -> Self {
+        for region in regions {
+            self.memory
+                .set_permissions(region.start, region.size, region.permissions);
+        }
+        self
+    }
+
+    /// Add a guard region; any access overlapping it faults.
+    pub fn with_guard_region(mut self, start: u32, size: u32) -> Self {
+        self.memory.add_guard_region(start, size);
+        self
+    }
 }
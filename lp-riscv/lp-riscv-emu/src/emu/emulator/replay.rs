@@ -0,0 +1,257 @@
+//! Recording and deterministic replay of external inputs.
+//!
+//! While recording, everything that reaches the guest from outside the program is
+//! logged: values returned by host-dependent syscalls (time, serial), and registers
+//! the host changes between calls (e.g. return values of syscalls it handles).
+//! Replaying the log on an emulator loaded with the same program reproduces the
+//! run without the host, so flaky firmware behavior can be re-run exactly.
+//!
+//! Host writes to guest memory are not captured.
+
+extern crate alloc;
+
+use super::super::error::EmulatorError;
+use super::state::Riscv32Emulator;
+use super::types::StepResult;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A value handed to the guest by a host-dependent syscall.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayInput {
+    /// Syscall number that produced the input
+    pub syscall: i32,
+    /// Value returned in a0
+    pub a0: i32,
+    /// Bytes copied into guest memory (serial reads)
+    pub data: Vec<u8>,
+}
+
+impl ReplayInput {
+    /// Input that only returns a value in a0.
+    pub fn value(syscall: i32, a0: i32) -> Self {
+        Self {
+            syscall,
+            a0,
+            data: Vec::new(),
+        }
+    }
+}
+
+/// A call into the emulator made while recording.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayCall {
+    /// Registers (index, value) and PC changed by the host between calls
+    HostWrites {
+        regs: Vec<(u8, i32)>,
+        pc: Option<u32>,
+    },
+    /// `step()` (fuel `None`) or `run_fuel(fuel)`, and the result it returned
+    Run {
+        fuel: Option<u64>,
+        result: StepResult,
+    },
+}
+
+/// Log of a recorded run, replayable with [`Riscv32Emulator::replay`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayLog {
+    /// Registers when recording started
+    pub start_regs: [i32; 32],
+    /// PC when recording started
+    pub start_pc: u32,
+    /// Calls in the order they were made
+    pub calls: Vec<ReplayCall>,
+    /// External inputs in the order the guest consumed them
+    pub inputs: Vec<ReplayInput>,
+}
+
+/// Recording/replay state of an emulator.
+pub(super) enum ReplayState {
+    Off,
+    Recording {
+        log: ReplayLog,
+        /// Registers and PC after the last recorded call (to detect host writes)
+        last_regs: [i32; 32],
+        last_pc: u32,
+    },
+    Replaying(VecDeque<ReplayInput>),
+}
+
+impl Riscv32Emulator {
+    /// Start recording external inputs, discarding any recording in progress.
+    pub fn start_recording(&mut self) {
+        self.replay = ReplayState::Recording {
+            log: ReplayLog {
+                start_regs: self.regs,
+                start_pc: self.pc,
+                calls: Vec::new(),
+                inputs: Vec::new(),
+            },
+            last_regs: self.regs,
+            last_pc: self.pc,
+        };
+    }
+
+    /// Stop recording and return the log (empty if recording was not started).
+    pub fn stop_recording(&mut self) -> ReplayLog {
+        self.record_host_writes();
+        match core::mem::replace(&mut self.replay, ReplayState::Off) {
+            ReplayState::Recording { log, .. } => log,
+            _ => ReplayLog {
+                start_regs: self.regs,
+                start_pc: self.pc,
+                calls: Vec::new(),
+                inputs: Vec::new(),
+            },
+        }
+    }
+
+    /// Whether external inputs are being recorded.
+    pub fn is_recording(&self) -> bool {
+        matches!(self.replay, ReplayState::Recording { .. })
+    }
+
+    /// Replay a recorded run.
+    ///
+    /// The emulator must be loaded with the same program and memory as when recording
+    /// started. Registers and PC are reset to the recorded start, then every call is
+    /// re-run with inputs taken from the log.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<StepResult>)` - Results of each call, identical to the recorded ones
+    /// * `Err(EmulatorError::ReplayDivergence)` - The run did not match the log
+    pub fn replay(&mut self, log: &ReplayLog) -> Result<Vec<StepResult>, EmulatorError> {
+        self.regs = log.start_regs;
+        self.pc = log.start_pc;
+        self.replay = ReplayState::Replaying(log.inputs.iter().cloned().collect());
+
+        let result = self.replay_calls(&log.calls);
+        let remaining = match core::mem::replace(&mut self.replay, ReplayState::Off) {
+            ReplayState::Replaying(inputs) => inputs.len(),
+            _ => 0,
+        };
+        let results = result?;
+        if remaining > 0 {
+            return Err(self.replay_divergence(format!("{remaining} recorded inputs not consumed")));
+        }
+        Ok(results)
+    }
+
+    fn replay_calls(&mut self, calls: &[ReplayCall]) -> Result<Vec<StepResult>, EmulatorError> {
+        let mut results = Vec::new();
+        for call in calls {
+            match call {
+                ReplayCall::HostWrites { regs, pc } => {
+                    for (reg, value) in regs {
+                        self.regs[*reg as usize] = *value;
+                    }
+                    if let Some(pc) = pc {
+                        self.pc = *pc;
+                    }
+                }
+                ReplayCall::Run { fuel, result } => {
+                    let actual = match fuel {
+                        None => self.step_inner()?,
+                        Some(fuel) => self.run_inner(*fuel)?,
+                    };
+                    if actual != *result {
+                        return Err(
+                            self.replay_divergence(format!("expected {result:?}, got {actual:?}"))
+                        );
+                    }
+                    results.push(actual);
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Record registers and PC the host changed since the last call.
+    pub(super) fn record_host_writes(&mut self) {
+        let ReplayState::Recording {
+            log,
+            last_regs,
+            last_pc,
+        } = &mut self.replay
+        else {
+            return;
+        };
+
+        let regs: Vec<(u8, i32)> = (0..32)
+            .filter(|&i| self.regs[i] != last_regs[i])
+            .map(|i| (i as u8, self.regs[i]))
+            .collect();
+        let pc = (self.pc != *last_pc).then_some(self.pc);
+        if !regs.is_empty() || pc.is_some() {
+            log.calls.push(ReplayCall::HostWrites { regs, pc });
+        }
+    }
+
+    /// Record a completed call. Failed calls are not recorded, so a log ends at the
+    /// last call that returned normally.
+    pub(super) fn record_call(
+        &mut self,
+        fuel: Option<u64>,
+        result: &Result<StepResult, EmulatorError>,
+    ) {
+        if let (
+            ReplayState::Recording {
+                log,
+                last_regs,
+                last_pc,
+            },
+            Ok(result),
+        ) = (&mut self.replay, result)
+        {
+            log.calls.push(ReplayCall::Run {
+                fuel,
+                result: result.clone(),
+            });
+            *last_regs = self.regs;
+            *last_pc = self.pc;
+        }
+    }
+
+    /// Pass an external input from `syscall` to the guest: produced by `live` and
+    /// recorded when recording, taken from the log when replaying.
+    ///
+    /// `live` is not called while replaying, so a replay neither reads nor writes the
+    /// host (serial buffers, clock).
+    pub(super) fn external_input(
+        &mut self,
+        syscall: i32,
+        live: impl FnOnce(&mut Self) -> ReplayInput,
+    ) -> Result<ReplayInput, EmulatorError> {
+        let recorded = match &mut self.replay {
+            ReplayState::Replaying(inputs) => inputs.pop_front(),
+            _ => {
+                let input = live(self);
+                if let ReplayState::Recording { log, .. } = &mut self.replay {
+                    log.inputs.push(input.clone());
+                }
+                return Ok(input);
+            }
+        };
+        match recorded {
+            Some(input) if input.syscall == syscall => Ok(input),
+            Some(input) => Err(self.replay_divergence(format!(
+                "expected input for syscall {}, guest made syscall {syscall}",
+                input.syscall
+            ))),
+            None => {
+                Err(self.replay_divergence(format!("no recorded input left for syscall {syscall}")))
+            }
+        }
+    }
+
+    fn replay_divergence(&self, reason: String) -> EmulatorError {
+        EmulatorError::ReplayDivergence {
+            pc: self.pc,
+            reason,
+            regs: self.regs,
+        }
+    }
+}
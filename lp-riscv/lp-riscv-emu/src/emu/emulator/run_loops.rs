@@ -8,13 +8,14 @@ use super::super::{
     logging::LogLevel,
     memory::Memory,
 };
+use super::replay::ReplayInput;
 use super::state::Riscv32Emulator;
 use super::types::{PanicInfo, StepResult, SyscallInfo};
 use alloc::{format, string::String, vec, vec::Vec};
 use lp_riscv_emu_shared::{
-    SERIAL_ERROR_INVALID_POINTER, SYSCALL_LOG, SYSCALL_PANIC, SYSCALL_SERIAL_HAS_DATA,
-    SYSCALL_SERIAL_READ, SYSCALL_SERIAL_WRITE, SYSCALL_TIME_MS, SYSCALL_WRITE, SYSCALL_YIELD,
-    syscall_to_level,
+    SERIAL_ERROR_INVALID_POINTER, SYSCALL_LOG, SYSCALL_PANIC, SYSCALL_SCRATCH_ALLOC,
+    SYSCALL_SERIAL_HAS_DATA, SYSCALL_SERIAL_READ, SYSCALL_SERIAL_WRITE, SYSCALL_TIME_MS,
+    SYSCALL_WRITE, SYSCALL_YIELD, syscall_to_level,
 };
 use lp_riscv_inst::Gpr;
 
@@ -79,6 +80,13 @@ impl Riscv32Emulator {
                 e
             })?;
 
+            if let Some(coverage) = &mut self.coverage {
+                coverage.record(self.pc, inst_word);
+            }
+            if let Some(cycle_model) = &mut self.cycle_model {
+                cycle_model.record(inst_word);
+            }
+
             // Check if compressed instruction (bits [1:0] != 0b11)
             let is_compressed = (inst_word & 0x3) != 0x3;
 
@@ -182,6 +190,13 @@ impl Riscv32Emulator {
                 e
             })?;
 
+            if let Some(coverage) = &mut self.coverage {
+                coverage.record(self.pc, inst_word);
+            }
+            if let Some(cycle_model) = &mut self.cycle_model {
+                cycle_model.record(inst_word);
+            }
+
             // Check if compressed instruction (bits [1:0] != 0b11)
             let is_compressed = (inst_word & 0x3) != 0x3;
 
@@ -340,9 +355,11 @@ impl Riscv32Emulator {
             if !read_ok {
                 self.regs[Gpr::A0.num() as usize] = SERIAL_ERROR_INVALID_POINTER;
             } else {
-                let serial = self.get_or_create_serial_host();
-                let result = serial.guest_write(&data);
-                self.regs[Gpr::A0.num() as usize] = result;
+                let input = self.external_input(SYSCALL_SERIAL_WRITE, |emu| {
+                    let result = emu.get_or_create_serial_host().guest_write(&data);
+                    ReplayInput::value(SYSCALL_SERIAL_WRITE, result)
+                })?;
+                self.regs[Gpr::A0.num() as usize] = input.a0;
             }
             Ok(StepResult::Continue)
         } else if syscall_info.number == SYSCALL_SERIAL_READ {
@@ -351,9 +368,18 @@ impl Riscv32Emulator {
             const MAX_READ_LEN: usize = 64 * 1024;
             let max_len = max_len.min(MAX_READ_LEN);
 
-            let mut buffer = vec![0u8; max_len];
-            let serial = self.get_or_create_serial_host();
-            let bytes_read = serial.guest_read(&mut buffer);
+            let input = self.external_input(SYSCALL_SERIAL_READ, |emu| {
+                let mut buffer = vec![0u8; max_len];
+                let bytes_read = emu.get_or_create_serial_host().guest_read(&mut buffer);
+                buffer.truncate(bytes_read.max(0) as usize);
+                ReplayInput {
+                    syscall: SYSCALL_SERIAL_READ,
+                    a0: bytes_read,
+                    data: buffer,
+                }
+            })?;
+            let bytes_read = input.a0;
+            let buffer = input.data;
 
             if bytes_read < 0 {
                 self.regs[Gpr::A0.num() as usize] = bytes_read;
@@ -381,24 +407,36 @@ impl Riscv32Emulator {
             }
             Ok(StepResult::Continue)
         } else if syscall_info.number == SYSCALL_SERIAL_HAS_DATA {
-            let has_data = self
-                .serial_host
-                .as_ref()
-                .map(|s| s.has_data())
-                .unwrap_or(false);
-            self.regs[Gpr::A0.num() as usize] = if has_data { 1 } else { 0 };
+            let input = self.external_input(SYSCALL_SERIAL_HAS_DATA, |emu| {
+                let has_data = emu
+                    .serial_host
+                    .as_ref()
+                    .map(|s| s.has_data())
+                    .unwrap_or(false);
+                ReplayInput::value(SYSCALL_SERIAL_HAS_DATA, if has_data { 1 } else { 0 })
+            })?;
+            self.regs[Gpr::A0.num() as usize] = input.a0;
             Ok(StepResult::Continue)
         } else if syscall_info.number == SYSCALL_TIME_MS {
-            #[cfg(feature = "std")]
-            {
-                self.init_start_time_if_needed();
-                let elapsed = self.elapsed_ms();
-                self.regs[Gpr::A0.num() as usize] = elapsed as i32;
-            }
-            #[cfg(not(feature = "std"))]
-            {
-                self.regs[Gpr::A0.num() as usize] = 0;
-            }
+            let input = self.external_input(SYSCALL_TIME_MS, |emu| {
+                #[cfg(feature = "std")]
+                let elapsed = {
+                    emu.init_start_time_if_needed();
+                    emu.elapsed_ms() as i32
+                };
+                #[cfg(not(feature = "std"))]
+                let elapsed = {
+                    let _ = emu;
+                    0
+                };
+                ReplayInput::value(SYSCALL_TIME_MS, elapsed)
+            })?;
+            self.regs[Gpr::A0.num() as usize] = input.a0;
+            Ok(StepResult::Continue)
+        } else if syscall_info.number == SYSCALL_SCRATCH_ALLOC {
+            let address =
+                self.scratch_alloc(syscall_info.args[0] as u32, syscall_info.args[1] as u32);
+            self.regs[Gpr::A0.num() as usize] = address as i32;
             Ok(StepResult::Continue)
         } else {
             Ok(StepResult::Syscall(syscall_info))
@@ -476,7 +514,10 @@ impl Riscv32Emulator {
     /// * `Ok(StepResult::FuelExhausted(count))` - Fuel exhausted (instructions executed)
     /// * `Err(EmulatorError)` - Error occurred (memory access violation, etc.)
     pub fn run_fuel(&mut self, fuel: u64) -> Result<StepResult, EmulatorError> {
-        self.run_inner(fuel)
+        self.record_host_writes();
+        let result = self.run_inner(fuel);
+        self.record_call(Some(fuel), &result);
+        result
     }
 
     /// Run until EBREAK is encountered, returning the value in a0.
@@ -0,0 +1,159 @@
+//! Scratch memory for guest code, reset before every function call.
+//!
+//! Guest code allocates from the scratch region with `SYSCALL_SCRATCH_ALLOC`, which
+//! bumps a pointer through the region. [`Riscv32Emulator::call_function`] and
+//! [`Riscv32Emulator::call_function_with_struct_return`] zero the region and free
+//! everything in it before running, so a shader called once per pixel always starts from
+//! the same memory and nothing one pixel writes can leak into the next.
+
+use super::state::{DEFAULT_RAM_START, Riscv32Emulator};
+
+/// Location of the scratch region, which must lie within RAM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScratchConfig {
+    /// First address of the region
+    pub start: u32,
+    /// Size of the region in bytes
+    pub size: u32,
+}
+
+/// Scratch region and how much of it is allocated
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ScratchState {
+    pub(super) config: ScratchConfig,
+    pub(super) used: u32,
+}
+
+impl Riscv32Emulator {
+    /// Reserve part of RAM as the scratch region.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region does not lie within RAM.
+    pub fn with_scratch_region(mut self, config: ScratchConfig) -> Self {
+        let ram_end = DEFAULT_RAM_START as u64 + self.memory.ram().len() as u64;
+        assert!(
+            config.start >= DEFAULT_RAM_START
+                && config.start as u64 + config.size as u64 <= ram_end,
+            "scratch region 0x{:08x}..0x{:08x} is outside RAM",
+            config.start,
+            config.start as u64 + config.size as u64
+        );
+        self.scratch = Some(ScratchState { config, used: 0 });
+        self
+    }
+
+    /// Get the scratch region, if one is configured.
+    pub fn scratch_config(&self) -> Option<ScratchConfig> {
+        self.scratch.map(|scratch| scratch.config)
+    }
+
+    /// Get the number of scratch bytes allocated since the last reset, including
+    /// alignment padding.
+    pub fn scratch_used(&self) -> u32 {
+        self.scratch.map_or(0, |scratch| scratch.used)
+    }
+
+    /// Zero the scratch region and free everything allocated from it.
+    ///
+    /// Done automatically at the start of each function call.
+    pub fn reset_scratch(&mut self) {
+        let Some(scratch) = self.scratch.as_mut() else {
+            return;
+        };
+        scratch.used = 0;
+        // The region was checked to be within RAM when it was configured
+        let offset = (scratch.config.start - DEFAULT_RAM_START) as usize;
+        let size = scratch.config.size as usize;
+        self.memory.ram_mut()[offset..offset + size].fill(0);
+    }
+
+    /// Allocate `size` bytes of scratch memory aligned to `align` bytes.
+    ///
+    /// Returns the address of the allocation, or 0 if there is no scratch region, it
+    /// is full, or `align` is not a power of two.
+    pub(super) fn scratch_alloc(&mut self, size: u32, align: u32) -> u32 {
+        let Some(scratch) = self.scratch.as_mut() else {
+            return 0;
+        };
+        if !align.is_power_of_two() {
+            return 0;
+        }
+        let start = scratch.config.start as u64;
+        let end = start + scratch.config.size as u64;
+        let align = align as u64;
+        let address = (start + scratch.used as u64).next_multiple_of(align);
+        if address + size as u64 > end {
+            return 0;
+        }
+        scratch.used = (address + size as u64 - start) as u32;
+        address as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const SCRATCH: ScratchConfig = ScratchConfig {
+        start: DEFAULT_RAM_START + 256,
+        size: 64,
+    };
+
+    #[test]
+    fn test_scratch_alloc_aligns_and_fills() {
+        let mut emu = Riscv32Emulator::new(vec![], vec![0; 1024]).with_scratch_region(SCRATCH);
+
+        assert_eq!(emu.scratch_alloc(3, 1), SCRATCH.start);
+        // Padded up to the next word
+        assert_eq!(emu.scratch_alloc(8, 4), SCRATCH.start + 4);
+        assert_eq!(emu.scratch_used(), 12);
+        assert_eq!(
+            emu.scratch_alloc(4, 3),
+            0,
+            "alignment must be a power of two"
+        );
+
+        // Exactly fills the rest, then nothing is left
+        assert_eq!(emu.scratch_alloc(52, 4), SCRATCH.start + 12);
+        assert_eq!(emu.scratch_alloc(1, 1), 0);
+        assert_eq!(emu.scratch_used(), 64);
+    }
+
+    #[test]
+    fn test_reset_scratch_zeroes_region() {
+        let mut emu = Riscv32Emulator::new(vec![], vec![0; 1024]).with_scratch_region(SCRATCH);
+        let address = emu.scratch_alloc(4, 4);
+        emu.memory_mut().write_word(address, 42).unwrap();
+        // Just past the region, untouched by the reset
+        emu.memory_mut()
+            .write_word(SCRATCH.start + SCRATCH.size, 7)
+            .unwrap();
+
+        emu.reset_scratch();
+        assert_eq!(emu.scratch_used(), 0);
+        assert_eq!(emu.memory().read_word(address).unwrap(), 0);
+        assert_eq!(
+            emu.memory()
+                .read_word(SCRATCH.start + SCRATCH.size)
+                .unwrap(),
+            7
+        );
+        assert_eq!(emu.scratch_alloc(4, 4), address);
+    }
+
+    #[test]
+    fn test_no_scratch_region() {
+        let mut emu = Riscv32Emulator::new(vec![], vec![0; 1024]);
+        assert_eq!(emu.scratch_config(), None);
+        assert_eq!(emu.scratch_alloc(4, 4), 0);
+        emu.reset_scratch();
+    }
+
+    #[test]
+    #[should_panic(expected = "outside RAM")]
+    fn test_scratch_region_must_be_in_ram() {
+        let _ = Riscv32Emulator::new(vec![], vec![0; 128]).with_scratch_region(SCRATCH);
+    }
+}
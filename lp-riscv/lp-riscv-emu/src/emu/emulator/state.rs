@@ -2,10 +2,14 @@
 
 extern crate alloc;
 
-use super::super::{logging::LogLevel, memory::Memory};
+use super::super::{
+    coverage::InstCoverage, cycle_model::CycleModel, logging::LogLevel, memory::Memory,
+};
+use super::replay::ReplayState;
+use super::scratch::ScratchState;
 use crate::serial::host_serial::HostSerial;
 use crate::time::TimeMode;
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use cranelift_codegen::ir::TrapCode;
 
 #[cfg(feature = "std")]
@@ -30,6 +34,16 @@ pub struct Riscv32Emulator {
     pub(super) start_time: Option<Instant>,
     /// Time mode for controlling time advancement
     pub(super) time_mode: TimeMode,
+    /// External clock, overriding `time_mode` when set
+    pub(super) clock: Option<Box<dyn Fn() -> u32 + Send>>,
+    /// Instruction coverage (only recorded when enabled)
+    pub(super) coverage: Option<InstCoverage>,
+    /// Pipeline cycle model (only recorded when enabled)
+    pub(super) cycle_model: Option<CycleModel>,
+    /// Recording/replay of external inputs
+    pub(super) replay: ReplayState,
+    /// Scratch region for guest allocations, reset before each function call
+    pub(super) scratch: Option<ScratchState>,
 }
 
 impl Riscv32Emulator {
@@ -57,6 +71,11 @@ impl Riscv32Emulator {
             #[cfg(feature = "std")]
             start_time: None,
             time_mode: TimeMode::RealTime,
+            clock: None,
+            coverage: None,
+            cycle_model: None,
+            replay: ReplayState::Off,
+            scratch: None,
         }
     }
 
@@ -76,6 +95,39 @@ impl Riscv32Emulator {
         self
     }
 
+    /// Enable instruction coverage tracking.
+    ///
+    /// # Arguments
+    ///
+    /// * `track_pcs` - Also record the distinct PCs executed
+    pub fn with_coverage(mut self, track_pcs: bool) -> Self {
+        self.coverage = Some(InstCoverage::new(track_pcs));
+        self
+    }
+
+    /// Get the instruction coverage recorded so far (None if tracking is disabled).
+    pub fn coverage(&self) -> Option<&InstCoverage> {
+        self.coverage.as_ref()
+    }
+
+    /// Take the recorded instruction coverage, leaving an empty set in its place.
+    pub fn take_coverage(&mut self) -> Option<InstCoverage> {
+        let coverage = self.coverage.as_mut()?;
+        let track_pcs = coverage.pcs().is_some();
+        Some(core::mem::replace(coverage, InstCoverage::new(track_pcs)))
+    }
+
+    /// Enable the in-order pipeline cycle model, for estimating stall cycles.
+    pub fn with_cycle_model(mut self) -> Self {
+        self.cycle_model = Some(CycleModel::new());
+        self
+    }
+
+    /// Get the cycle model recorded so far (None if it is disabled).
+    pub fn cycle_model(&self) -> Option<&CycleModel> {
+        self.cycle_model.as_ref()
+    }
+
     /// Get the number of instructions executed so far.
     pub fn get_instruction_count(&self) -> u64 {
         self.instruction_count
@@ -212,6 +264,15 @@ impl Riscv32Emulator {
         self.time_mode = mode;
     }
 
+    /// Read guest time from an external clock instead of the time mode
+    ///
+    /// Lets a test share one clock between the emulator and the host side (for example
+    /// `lp_shared::time::TestClock`), so both observe the same advance.
+    pub fn with_clock(mut self, clock: impl Fn() -> u32 + Send + 'static) -> Self {
+        self.clock = Some(Box::new(clock));
+        self
+    }
+
     /// Advance simulated time (only works in Simulated mode)
     ///
     /// # Arguments
@@ -228,6 +289,9 @@ impl Riscv32Emulator {
     /// Returns 0 if start time not initialized (RealTime mode) or std feature disabled.
     #[cfg(feature = "std")]
     pub(super) fn elapsed_ms(&self) -> u32 {
+        if let Some(clock) = &self.clock {
+            return clock();
+        }
         match self.time_mode {
             TimeMode::RealTime => {
                 if let Some(start) = self.start_time {
@@ -242,6 +306,9 @@ impl Riscv32Emulator {
 
     #[cfg(not(feature = "std"))]
     pub(super) fn elapsed_ms(&self) -> u32 {
+        if let Some(clock) = &self.clock {
+            return clock();
+        }
         match self.time_mode {
             TimeMode::RealTime => 0,
             TimeMode::Simulated(current) => current,
@@ -284,6 +351,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_clock_overrides_time_mode() {
+        use alloc::sync::Arc;
+        use core::sync::atomic::{AtomicU32, Ordering};
+
+        let now = Arc::new(AtomicU32::new(7));
+        let emu = Riscv32Emulator::new(vec![], vec![])
+            .with_time_mode(TimeMode::Simulated(100))
+            .with_clock({
+                let now = now.clone();
+                move || now.load(Ordering::SeqCst)
+            });
+        assert_eq!(emu.elapsed_ms(), 7);
+
+        now.store(23, Ordering::SeqCst);
+        assert_eq!(emu.elapsed_ms(), 23);
+    }
+
     #[test]
     fn test_set_time_mode() {
         let mut emu = Riscv32Emulator::new(vec![], vec![]);
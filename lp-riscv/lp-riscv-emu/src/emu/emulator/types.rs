@@ -6,7 +6,7 @@ use alloc::string::String;
 use cranelift_codegen::ir::TrapCode;
 
 /// Result of a single step.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum StepResult {
     /// Normal step completed, continue execution
     Continue,
@@ -24,7 +24,7 @@ pub enum StepResult {
 }
 
 /// Information about a syscall (ECALL).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct SyscallInfo {
     /// Syscall number (from a7 register)
     pub number: i32,
@@ -33,7 +33,7 @@ pub struct SyscallInfo {
 }
 
 /// Information about a panic that occurred in the emulated program.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PanicInfo {
     /// Panic message
     pub message: String,
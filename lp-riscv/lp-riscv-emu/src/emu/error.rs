@@ -68,6 +68,12 @@ pub enum EmulatorError {
         pc: u32,
         regs: [i32; 32],
     },
+    /// Replayed run did not match the recorded log.
+    ReplayDivergence {
+        pc: u32,
+        reason: String,
+        regs: [i32; 32],
+    },
 }
 
 /// Convert a TrapCode to a human-readable string.
@@ -102,6 +108,7 @@ impl EmulatorError {
             EmulatorError::InvalidRegister { pc, .. } => *pc,
             EmulatorError::Trap { pc, .. } => *pc,
             EmulatorError::Panic { pc, .. } => *pc,
+            EmulatorError::ReplayDivergence { pc, .. } => *pc,
         }
     }
 
@@ -116,6 +123,7 @@ impl EmulatorError {
             EmulatorError::InvalidRegister { .. } => None,
             EmulatorError::Trap { regs, .. } => Some(regs),
             EmulatorError::Panic { regs, .. } => Some(regs),
+            EmulatorError::ReplayDivergence { regs, .. } => Some(regs),
         }
     }
 }
@@ -197,6 +205,9 @@ impl core::fmt::Display for EmulatorError {
                 }
                 Ok(())
             }
+            EmulatorError::ReplayDivergence { pc, reason, .. } => {
+                write!(f, "Replay diverged at PC 0x{pc:08x}: {reason}")
+            }
         }
     }
 }
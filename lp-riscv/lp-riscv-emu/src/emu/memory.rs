@@ -1,18 +1,140 @@
 //! Memory model for the RISC-V 32 emu.
 
 use alloc::vec::Vec;
+use core::fmt;
 
 use super::error::{EmulatorError, MemoryAccessKind};
 
 /// Default RAM start address (0x80000000, matching embive's RAM_OFFSET).
 pub const DEFAULT_RAM_START: u32 = 0x80000000;
 
+/// Kind of a region in the emulator's memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryRegionKind {
+    /// Code region (read-only)
+    Rom,
+    /// RAM region
+    Ram,
+    /// Guard region; any access faults
+    Guard,
+    /// Scratch region within RAM, zeroed before each function call
+    Scratch,
+}
+
+/// Access permissions of a memory region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryPermissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl MemoryPermissions {
+    /// Read only (constants)
+    pub const R: Self = Self {
+        read: true,
+        write: false,
+        execute: false,
+    };
+    /// Read and write (data)
+    pub const RW: Self = Self {
+        read: true,
+        write: true,
+        execute: false,
+    };
+    /// Read and execute (code)
+    pub const RX: Self = Self {
+        read: true,
+        write: false,
+        execute: true,
+    };
+    /// Read, write, and execute (RAM; JIT code runs from RAM)
+    pub const RWX: Self = Self {
+        read: true,
+        write: true,
+        execute: true,
+    };
+    /// No access (guard)
+    pub const NONE: Self = Self {
+        read: false,
+        write: false,
+        execute: false,
+    };
+
+    /// Whether these permissions allow an access of the given kind.
+    pub fn allows(&self, kind: MemoryAccessKind) -> bool {
+        match kind {
+            MemoryAccessKind::Read => self.read,
+            MemoryAccessKind::Write => self.write,
+            MemoryAccessKind::InstructionFetch => self.execute,
+        }
+    }
+}
+
+impl fmt::Display for MemoryPermissions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            if self.read { 'r' } else { '-' },
+            if self.write { 'w' } else { '-' },
+            if self.execute { 'x' } else { '-' }
+        )
+    }
+}
+
+/// A region of the emulator's memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// First address of the region
+    pub start: u32,
+    /// Size of the region in bytes
+    pub size: u32,
+    pub kind: MemoryRegionKind,
+    pub permissions: MemoryPermissions,
+}
+
+impl MemoryRegion {
+    /// One past the last address of the region (may be 2^32 for a region ending at the top)
+    pub fn end(&self) -> u64 {
+        self.start as u64 + self.size as u64
+    }
+
+    /// Whether the region contains the address.
+    pub fn contains(&self, address: u32) -> bool {
+        address >= self.start && (address as u64) < self.end()
+    }
+
+    /// Whether the region overlaps an access of `size` bytes at `address`.
+    pub fn overlaps(&self, address: u32, size: u32) -> bool {
+        (address as u64) < self.end() && (self.start as u64) < address as u64 + size as u64
+    }
+}
+
+impl fmt::Display for MemoryRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x{:08x}..0x{:08x} {} {:?} ({} bytes)",
+            self.start,
+            self.end(),
+            self.permissions,
+            self.kind,
+            self.size
+        )
+    }
+}
+
 /// Memory model with separate code and RAM regions.
 pub struct Memory {
     code: Vec<u8>,
     ram: Vec<u8>,
     code_start: u32,
     ram_start: u32,
+    /// Permission overrides for parts of code or RAM (later entries win)
+    protections: Vec<MemoryRegion>,
+    /// Guard regions; accesses overlapping these fault
+    guards: Vec<MemoryRegion>,
 }
 
 impl Memory {
@@ -30,6 +152,8 @@ impl Memory {
             ram,
             code_start,
             ram_start,
+            protections: Vec::new(),
+            guards: Vec::new(),
         }
     }
 
@@ -42,6 +166,8 @@ impl Memory {
     ///
     /// Returns an error if the address is out of bounds or unaligned.
     pub fn read_word(&self, address: u32) -> Result<i32, EmulatorError> {
+        self.check_access(address, 4, MemoryAccessKind::Read)?;
+
         // Check alignment
         if address % 4 != 0 {
             return Err(EmulatorError::UnalignedAccess {
@@ -98,6 +224,8 @@ impl Memory {
     ///
     /// Returns an error if the address is out of bounds, unaligned, or in the code region.
     pub fn write_word(&mut self, address: u32, value: i32) -> Result<(), EmulatorError> {
+        self.check_access(address, 4, MemoryAccessKind::Write)?;
+
         // Check alignment
         if address % 4 != 0 {
             return Err(EmulatorError::UnalignedAccess {
@@ -151,6 +279,8 @@ impl Memory {
 
     /// Read a byte from memory.
     pub fn read_byte(&self, address: u32) -> Result<i8, EmulatorError> {
+        self.check_access(address, 1, MemoryAccessKind::Read)?;
+
         // Determine which region
         if address >= self.ram_start {
             // RAM region
@@ -183,6 +313,8 @@ impl Memory {
 
     /// Read a halfword (16-bit) from memory.
     pub fn read_halfword(&self, address: u32) -> Result<i16, EmulatorError> {
+        self.check_access(address, 2, MemoryAccessKind::Read)?;
+
         // Check alignment
         if address % 2 != 0 {
             return Err(EmulatorError::UnalignedAccess {
@@ -227,6 +359,8 @@ impl Memory {
 
     /// Write a byte to memory.
     pub fn write_byte(&mut self, address: u32, value: i8) -> Result<(), EmulatorError> {
+        self.check_access(address, 1, MemoryAccessKind::Write)?;
+
         // Prevent writes to address 0 (null pointer)
         if address == 0 {
             return Err(EmulatorError::InvalidMemoryAccess {
@@ -266,6 +400,8 @@ impl Memory {
 
     /// Write a halfword (16-bit) to memory.
     pub fn write_halfword(&mut self, address: u32, value: i16) -> Result<(), EmulatorError> {
+        self.check_access(address, 2, MemoryAccessKind::Write)?;
+
         // Check alignment
         if address % 2 != 0 {
             return Err(EmulatorError::UnalignedAccess {
@@ -320,6 +456,8 @@ impl Memory {
     /// For compressed instructions (RVC), this may return a 16-bit value in the lower 16 bits.
     /// Returns an error if the address is out of bounds or not 2-byte aligned.
     pub fn fetch_instruction(&self, address: u32) -> Result<u32, EmulatorError> {
+        self.check_access(address, 2, MemoryAccessKind::InstructionFetch)?;
+
         // Check 2-byte alignment (required for compressed instructions)
         if address % 2 != 0 {
             return Err(EmulatorError::UnalignedAccess {
@@ -390,6 +528,8 @@ impl Memory {
 
     /// Read a single byte from memory.
     pub fn read_u8(&self, address: u32) -> Result<u8, EmulatorError> {
+        self.check_access(address, 1, MemoryAccessKind::Read)?;
+
         if address >= self.ram_start {
             // RAM region
             let offset = (address - self.ram_start) as usize;
@@ -438,4 +578,102 @@ impl Memory {
     pub fn code_start(&self) -> u32 {
         self.code_start
     }
+
+    /// Add a guard region; any access overlapping it faults.
+    ///
+    /// Guard regions may overlap code or RAM (e.g. a stack guard at the bottom of
+    /// the stack) and take precedence over them.
+    pub fn add_guard_region(&mut self, start: u32, size: u32) {
+        self.guards.push(MemoryRegion {
+            start,
+            size,
+            kind: MemoryRegionKind::Guard,
+            permissions: MemoryPermissions::NONE,
+        });
+    }
+
+    /// Set the access permissions of part of the code or RAM region.
+    ///
+    /// Code defaults to read/execute and RAM to read/write/execute. Overrides
+    /// are typically set from ELF segment flags so that e.g. `.rodata` is
+    /// read-only and data is not executable. Later calls take precedence where
+    /// ranges overlap. Writes to the code region always fault.
+    pub fn set_permissions(&mut self, start: u32, size: u32, permissions: MemoryPermissions) {
+        let kind = if start >= self.ram_start {
+            MemoryRegionKind::Ram
+        } else {
+            MemoryRegionKind::Rom
+        };
+        self.protections.push(MemoryRegion {
+            start,
+            size,
+            kind,
+            permissions,
+        });
+    }
+
+    /// Get the memory map, sorted by start address.
+    ///
+    /// Permission overrides and guard regions are listed after the code or RAM
+    /// region they overlap.
+    pub fn memory_regions(&self) -> Vec<MemoryRegion> {
+        let mut regions = Vec::with_capacity(2 + self.protections.len() + self.guards.len());
+        regions.push(MemoryRegion {
+            start: self.code_start,
+            size: self.code.len() as u32,
+            kind: MemoryRegionKind::Rom,
+            permissions: MemoryPermissions::RX,
+        });
+        regions.push(MemoryRegion {
+            start: self.ram_start,
+            size: self.ram.len() as u32,
+            kind: MemoryRegionKind::Ram,
+            permissions: MemoryPermissions::RWX,
+        });
+        regions.extend_from_slice(&self.protections);
+        regions.extend_from_slice(&self.guards);
+        regions.sort_by_key(|region| region.start);
+        regions
+    }
+
+    /// Permissions at an address, before guard regions are applied.
+    fn permissions_at(&self, address: u32) -> MemoryPermissions {
+        if let Some(region) = self
+            .protections
+            .iter()
+            .rev()
+            .find(|region| region.contains(address))
+        {
+            region.permissions
+        } else if address >= self.ram_start {
+            MemoryPermissions::RWX
+        } else {
+            MemoryPermissions::RX
+        }
+    }
+
+    /// Fault if an access overlaps a guard region or lacks permission.
+    fn check_access(
+        &self,
+        address: u32,
+        size: u32,
+        kind: MemoryAccessKind,
+    ) -> Result<(), EmulatorError> {
+        let guarded = self
+            .guards
+            .iter()
+            .any(|guard| guard.overlaps(address, size));
+        let denied = !self.protections.is_empty()
+            && (0..size).any(|i| !self.permissions_at(address.wrapping_add(i)).allows(kind));
+        if guarded || denied {
+            return Err(EmulatorError::InvalidMemoryAccess {
+                address,
+                size: size as usize,
+                kind,
+                pc: 0, // Will be filled in by caller
+                regs: [0; 32],
+            });
+        }
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
 pub mod abi_helper;
+pub mod coverage;
+pub mod cycle_model;
 mod decoder;
 pub mod emulator;
 pub mod error;
@@ -6,6 +8,12 @@ mod executor;
 pub mod logging;
 mod memory;
 
-pub use emulator::{DEFAULT_RAM_START, PanicInfo, Riscv32Emulator, StepResult, SyscallInfo};
+pub use coverage::InstCoverage;
+pub use cycle_model::CycleModel;
+pub use emulator::{
+    DEFAULT_RAM_START, PanicInfo, ReplayCall, ReplayInput, ReplayLog, Riscv32Emulator,
+    ScratchConfig, StepResult, SyscallInfo,
+};
 pub use error::{EmulatorError, MemoryAccessKind, trap_code_to_string};
 pub use logging::{InstLog, LogLevel};
+pub use memory::{MemoryPermissions, MemoryRegion, MemoryRegionKind};
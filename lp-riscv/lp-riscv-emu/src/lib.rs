@@ -26,13 +26,16 @@ pub mod test_util;
 
 // Re-exports for convenience
 pub use emu::{
-    EmulatorError, InstLog, LogLevel, MemoryAccessKind, PanicInfo, Riscv32Emulator, StepResult,
-    SyscallInfo, trap_code_to_string,
+    CycleModel, EmulatorError, InstCoverage, InstLog, LogLevel, MemoryAccessKind,
+    MemoryPermissions, MemoryRegion, MemoryRegionKind, PanicInfo, ReplayCall, ReplayInput,
+    ReplayLog, Riscv32Emulator, ScratchConfig, StepResult, SyscallInfo, trap_code_to_string,
 };
 pub use time::TimeMode;
 
 #[cfg(feature = "std")]
-pub use test_util::{BinaryBuildConfig, ensure_binary_built, find_workspace_root};
+pub use test_util::{
+    BinaryBuildConfig, disassemble_range, ensure_binary_built, find_workspace_root,
+};
 
 /// Initialize logging for emulator host
 ///
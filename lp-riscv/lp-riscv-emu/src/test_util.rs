@@ -2,10 +2,15 @@
 
 #[cfg(feature = "std")]
 mod std_impl {
+    use crate::Riscv32Emulator;
+    use core::fmt::Write;
+    use core::ops::Range;
+    use lp_riscv_inst::disassemble;
     use std::collections::HashMap;
     use std::path::PathBuf;
     use std::string::{String, ToString};
     use std::sync::Mutex;
+    use std::vec::Vec;
 
     /// Configuration for building a RISC-V binary
     #[derive(Debug, Clone)]
@@ -184,10 +189,57 @@ mod std_impl {
             }
         }
     }
+
+    /// Disassemble the instructions in `range`, one line per instruction with its address
+    ///
+    /// A `<symbol>:` label line is inserted before each address a symbol in `symbol_map`
+    /// points to (pass `ElfLoadInfo::symbol_map`). Formatting is done by
+    /// [`lp_riscv_inst::disassemble`]; the listing stops early at memory that cannot be read.
+    ///
+    /// # Example output
+    /// ```text
+    /// <main>:
+    ///   0x00000000: 02a00513  addi a0, zero, 0x0000002a
+    ///   0x00000004:     8082  c.jr ra
+    /// ```
+    pub fn disassemble_range(
+        emu: &Riscv32Emulator,
+        symbol_map: &hashbrown::HashMap<String, u32>,
+        range: Range<u32>,
+    ) -> String {
+        // Labels sorted by address then name for stable output
+        let mut labels: Vec<(u32, &str)> = symbol_map
+            .iter()
+            // Skip mapping symbols ($x, $d)
+            .filter(|(name, &&address)| range.contains(&address) && !name.starts_with('$'))
+            .map(|(name, &address)| (address, name.as_str()))
+            .collect();
+        labels.sort_unstable();
+
+        let mut code = Vec::new();
+        let mut fetch_error = None;
+        for address in range.clone() {
+            match emu.memory().read_u8(address) {
+                Ok(byte) => code.push(byte),
+                Err(e) => {
+                    fetch_error = Some((address, e));
+                    break;
+                }
+            }
+        }
+
+        let mut result = disassemble(&code, range.start, &labels);
+        if let Some((address, e)) = fetch_error {
+            let _ = writeln!(result, "  0x{address:08x}: <{e}>");
+        }
+        result
+    }
 }
 
 #[cfg(feature = "std")]
-pub use std_impl::{BinaryBuildConfig, ensure_binary_built, find_workspace_root};
+pub use std_impl::{
+    BinaryBuildConfig, disassemble_range, ensure_binary_built, find_workspace_root,
+};
 
 #[cfg(test)]
 #[cfg(feature = "std")]
@@ -213,4 +265,32 @@ mod tests {
         assert_eq!(config.profile, "release");
         assert!(config.rustflags.is_some());
     }
+
+    #[test]
+    fn test_disassemble_range() {
+        use crate::Riscv32Emulator;
+        use std::string::ToString;
+        use std::vec;
+        use std::vec::Vec;
+
+        let code = vec![
+            0x13, 0x05, 0xa0, 0x02, // addi a0, zero, 42
+            0x82, 0x80, // c.jr ra
+            0x13, 0x05, 0x10, 0x00, // addi a0, zero, 1
+        ];
+        let emu = Riscv32Emulator::new(code, vec![0; 16]);
+        let mut symbol_map = hashbrown::HashMap::new();
+        symbol_map.insert("answer".to_string(), 0);
+        symbol_map.insert("one".to_string(), 6);
+
+        let listing = disassemble_range(&emu, &symbol_map, 0..10);
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "<answer>:");
+        assert!(lines[1].starts_with("  0x00000000: 02a00513  addi a0, zero,"));
+        // Compressed instruction advances by 2 bytes
+        assert_eq!(lines[2], "  0x00000004:     8082  c.jr ra");
+        assert_eq!(lines[3], "<one>:");
+        assert!(lines[4].starts_with("  0x00000006: 00100513"));
+    }
 }
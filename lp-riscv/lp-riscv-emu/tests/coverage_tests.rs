@@ -0,0 +1,82 @@
+//! Instruction coverage tracking tests for RISC-V emulator.
+
+use lp_riscv_emu::{Riscv32Emulator, StepResult};
+use lp_riscv_inst::Gpr;
+
+const RAM_START: u32 = 0x80000000;
+
+fn program() -> Vec<u8> {
+    vec![
+        0x93, 0x00, 0x50, 0x00, // addi x1, x0, 5
+        0x93, 0x80, 0x10, 0x00, // addi x1, x1, 1
+        0x33, 0x81, 0x10, 0x00, // add x2, x1, x1
+        0x23, 0xa2, 0x21, 0x00, // sw x2, 4(x3)
+        0x73, 0x00, 0x10, 0x00, // ebreak
+    ]
+}
+
+fn run(emu: &mut Riscv32Emulator) {
+    emu.set_register(Gpr::Gp, RAM_START as i32);
+    loop {
+        match emu.step().unwrap() {
+            StepResult::Continue => {}
+            StepResult::Halted => break,
+            other => panic!("Unexpected step result: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_coverage_records_opcodes() {
+    let mut emu = Riscv32Emulator::new(program(), vec![0; 1024]).with_coverage(false);
+    run(&mut emu);
+
+    let coverage = emu.coverage().expect("Coverage should be enabled");
+    assert!(coverage.contains_opcode("addi"));
+    assert!(coverage.contains_opcode("add"));
+    assert!(coverage.contains_opcode("sw"));
+    assert!(coverage.contains_opcode("ebreak"));
+    assert!(!coverage.contains_opcode("lw"));
+
+    let opcodes = coverage.opcodes();
+    assert_eq!(opcodes.get("addi"), Some(&2));
+    assert_eq!(opcodes.len(), 4);
+    assert_eq!(coverage.instruction_count(), 5);
+    assert!(coverage.pcs().is_none());
+
+    let report = coverage.report();
+    assert!(report.contains("4 distinct opcodes, 5 instructions"));
+}
+
+#[test]
+fn test_coverage_records_pcs() {
+    let mut emu = Riscv32Emulator::new(program(), vec![0; 1024]).with_coverage(true);
+    run(&mut emu);
+
+    let coverage = emu.take_coverage().expect("Coverage should be enabled");
+    let pcs: Vec<u32> = coverage.pcs().unwrap().into_iter().collect();
+    assert_eq!(pcs, vec![0, 4, 8, 12, 16]);
+
+    // Taking the coverage leaves an empty set that keeps PC tracking on
+    let remaining = emu.coverage().unwrap();
+    assert_eq!(remaining.instruction_count(), 0);
+    assert!(remaining.pcs().unwrap().is_empty());
+}
+
+#[test]
+fn test_coverage_recorded_by_run() {
+    let mut emu = Riscv32Emulator::new(program(), vec![0; 1024]).with_coverage(false);
+    emu.set_register(Gpr::Gp, RAM_START as i32);
+    assert!(matches!(emu.run().unwrap(), StepResult::Halted));
+
+    let coverage = emu.coverage().unwrap();
+    assert_eq!(coverage.instruction_count(), 5);
+    assert!(coverage.contains_opcode("sw"));
+}
+
+#[test]
+fn test_coverage_disabled_by_default() {
+    let mut emu = Riscv32Emulator::new(program(), vec![0; 1024]);
+    run(&mut emu);
+    assert!(emu.coverage().is_none());
+}
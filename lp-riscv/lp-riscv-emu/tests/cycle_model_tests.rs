@@ -0,0 +1,69 @@
+//! Pipeline cycle model tests for RISC-V emulator.
+
+use lp_riscv_emu::{Riscv32Emulator, StepResult};
+use lp_riscv_inst::{Gpr, encode};
+
+const RAM_START: u32 = 0x80000000;
+
+fn program(insts: &[u32]) -> Vec<u8> {
+    insts
+        .iter()
+        .chain(&[encode::ebreak()])
+        .flat_map(|inst| inst.to_le_bytes())
+        .collect()
+}
+
+/// Run to the ebreak and return (cycles, stall cycles)
+fn run(insts: &[u32]) -> (u64, u64) {
+    let mut emu = Riscv32Emulator::new(program(insts), vec![0; 1024]).with_cycle_model();
+    emu.set_register(Gpr::Gp, RAM_START as i32);
+    loop {
+        match emu.step().unwrap() {
+            StepResult::Continue => {}
+            StepResult::Halted => break,
+            other => panic!("Unexpected step result: {other:?}"),
+        }
+    }
+    let model = emu.cycle_model().expect("Cycle model should be enabled");
+    (model.cycles(), model.stall_cycles())
+}
+
+#[test]
+fn test_dependent_multiply_stalls() {
+    let (cycles, stalls) = run(&[
+        encode::mul(Gpr::T0, Gpr::A0, Gpr::A0),
+        encode::mul(Gpr::T1, Gpr::T0, Gpr::A0),
+    ]);
+    assert_eq!(stalls, 2);
+    assert_eq!(cycles, 5);
+}
+
+#[test]
+fn test_interleaved_multiplies_stall_less() {
+    let (_, stalls) = run(&[
+        encode::mul(Gpr::T0, Gpr::A0, Gpr::A0),
+        encode::mul(Gpr::T2, Gpr::A1, Gpr::A1),
+        encode::mul(Gpr::T1, Gpr::T0, Gpr::A0),
+        encode::mul(Gpr::T3, Gpr::T2, Gpr::A1),
+    ]);
+    assert_eq!(stalls, 1);
+}
+
+#[test]
+fn test_load_use_stalls() {
+    let (_, stalls) = run(&[
+        encode::lw(Gpr::T0, Gpr::Gp, 0),
+        encode::addi(Gpr::T0, Gpr::T0, 1),
+        // Independent of the load result
+        encode::lw(Gpr::T1, Gpr::Gp, 4),
+        encode::addi(Gpr::T2, Gpr::Gp, 1),
+        encode::addi(Gpr::T1, Gpr::T1, 1),
+    ]);
+    assert_eq!(stalls, 1);
+}
+
+#[test]
+fn test_cycle_model_disabled_by_default() {
+    let emu = Riscv32Emulator::new(program(&[]), vec![0; 1024]);
+    assert!(emu.cycle_model().is_none());
+}
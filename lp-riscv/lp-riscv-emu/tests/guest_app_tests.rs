@@ -183,12 +183,12 @@ mod tests {
         }
 
         // Create emulator with fuel limit and logging enabled
-        let mut emu =
-            Riscv32Emulator::new(elf_info.code, elf_info.ram).with_log_level(LogLevel::None);
+        let entry_point = elf_info.entry_point;
+        let mut emu = elf_info.into_emulator().with_log_level(LogLevel::None);
         // Fuel is now per-run, passed to run_until_yield()
 
         // Set PC to entry point
-        emu.set_pc(elf_info.entry_point);
+        emu.set_pc(entry_point);
         println!("Set PC to: 0x{:08x}", emu.get_pc());
 
         emu
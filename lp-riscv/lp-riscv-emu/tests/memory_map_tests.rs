@@ -0,0 +1,69 @@
+//! Memory map reporting tests for RISC-V emulator.
+
+use lp_riscv_emu::{
+    EmulatorError, MemoryAccessKind, MemoryPermissions, MemoryRegion, MemoryRegionKind,
+    Riscv32Emulator,
+};
+
+const RAM_START: u32 = 0x80000000;
+
+#[test]
+fn test_memory_regions_ram_and_guard() {
+    // 4 bytes of code, 64KB of RAM, and a 256-byte stack guard at the bottom of RAM
+    let emu = Riscv32Emulator::new(vec![0x73, 0x00, 0x10, 0x00], vec![0; 64 * 1024])
+        .with_guard_region(RAM_START, 256);
+
+    let regions = emu.memory_regions();
+    assert_eq!(
+        regions,
+        vec![
+            MemoryRegion {
+                start: 0,
+                size: 4,
+                kind: MemoryRegionKind::Rom,
+                permissions: MemoryPermissions::RX,
+            },
+            MemoryRegion {
+                start: RAM_START,
+                size: 64 * 1024,
+                kind: MemoryRegionKind::Ram,
+                permissions: MemoryPermissions::RWX,
+            },
+            MemoryRegion {
+                start: RAM_START,
+                size: 256,
+                kind: MemoryRegionKind::Guard,
+                permissions: MemoryPermissions::NONE,
+            },
+        ]
+    );
+
+    assert_eq!(
+        regions[1].to_string(),
+        "0x80000000..0x80010000 rwx Ram (65536 bytes)"
+    );
+    assert_eq!(
+        regions[2].to_string(),
+        "0x80000000..0x80000100 --- Guard (256 bytes)"
+    );
+}
+
+#[test]
+fn test_guard_region_faults() {
+    let mut emu = Riscv32Emulator::new(vec![0x73, 0x00, 0x10, 0x00], vec![0; 1024])
+        .with_guard_region(RAM_START, 16);
+
+    // Inside the guard
+    match emu.memory_mut().write_word(RAM_START + 12, 1) {
+        Err(EmulatorError::InvalidMemoryAccess { address, kind, .. }) => {
+            assert_eq!(address, RAM_START + 12);
+            assert_eq!(kind, MemoryAccessKind::Write);
+        }
+        other => panic!("Expected InvalidMemoryAccess, got {other:?}"),
+    }
+    assert!(emu.memory().read_byte(RAM_START).is_err());
+
+    // Just past the guard
+    emu.memory_mut().write_word(RAM_START + 16, 7).unwrap();
+    assert_eq!(emu.memory().read_word(RAM_START + 16).unwrap(), 7);
+}
@@ -0,0 +1,105 @@
+//! Memory permission enforcement tests for RISC-V emulator.
+
+use lp_riscv_emu::{
+    EmulatorError, MemoryAccessKind, MemoryPermissions, MemoryRegion, MemoryRegionKind,
+    Riscv32Emulator, StepResult,
+};
+use lp_riscv_inst::Gpr;
+
+const RAM_START: u32 = 0x80000000;
+
+/// Segments as a linker would emit them: code, then read-only data and
+/// read/write data in RAM
+fn segments() -> Vec<MemoryRegion> {
+    vec![
+        MemoryRegion {
+            start: 0,
+            size: 8,
+            kind: MemoryRegionKind::Rom,
+            permissions: MemoryPermissions::RX,
+        },
+        // .rodata
+        MemoryRegion {
+            start: RAM_START,
+            size: 256,
+            kind: MemoryRegionKind::Ram,
+            permissions: MemoryPermissions::R,
+        },
+        // .data
+        MemoryRegion {
+            start: RAM_START + 256,
+            size: 256,
+            kind: MemoryRegionKind::Ram,
+            permissions: MemoryPermissions::RW,
+        },
+    ]
+}
+
+#[test]
+fn test_write_to_rodata_faults() {
+    let code = vec![
+        0x23, 0xa0, 0x20, 0x00, // sw x2, 0(x1)
+        0x73, 0x00, 0x10, 0x00, // ebreak
+    ];
+    let mut emu = Riscv32Emulator::new(code, vec![0; 1024]).with_region_permissions(&segments());
+
+    let rodata_addr = RAM_START + 16;
+    emu.set_register(Gpr::Ra, rodata_addr as i32);
+    emu.set_register(Gpr::Sp, 42);
+
+    match emu.step() {
+        Err(EmulatorError::InvalidMemoryAccess {
+            address, kind, pc, ..
+        }) => {
+            assert_eq!(address, rodata_addr);
+            assert_eq!(kind, MemoryAccessKind::Write);
+            assert_eq!(pc, 0);
+        }
+        other => panic!("Expected store fault, got {other:?}"),
+    }
+
+    // Reads of .rodata and writes to .data are still allowed
+    assert_eq!(emu.memory().read_word(rodata_addr).unwrap(), 0);
+    emu.memory_mut().write_word(RAM_START + 256, 7).unwrap();
+}
+
+#[test]
+fn test_execute_non_executable_faults() {
+    let mut ram = vec![0; 1024];
+    // ebreak in .data
+    ram[256..260].copy_from_slice(&[0x73, 0x00, 0x10, 0x00]);
+    // ebreak past the segments (heap), which keeps the RAM default of rwx
+    ram[768..772].copy_from_slice(&[0x73, 0x00, 0x10, 0x00]);
+    let mut emu = Riscv32Emulator::new(vec![0x73, 0x00, 0x10, 0x00], ram)
+        .with_region_permissions(&segments());
+
+    emu.set_pc(RAM_START + 256);
+    match emu.step() {
+        Err(EmulatorError::InvalidMemoryAccess { address, kind, .. }) => {
+            assert_eq!(address, RAM_START + 256);
+            assert_eq!(kind, MemoryAccessKind::InstructionFetch);
+        }
+        other => panic!("Expected instruction fetch fault, got {other:?}"),
+    }
+
+    emu.set_pc(RAM_START + 768);
+    match emu.step() {
+        Ok(StepResult::Halted) => {}
+        other => panic!("Expected Halted, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_permissions_in_memory_map() {
+    let emu = Riscv32Emulator::new(vec![0; 8], vec![0; 1024]).with_permissions(
+        RAM_START,
+        256,
+        MemoryPermissions::R,
+    );
+
+    let regions = emu.memory_regions();
+    assert_eq!(regions.len(), 3);
+    assert_eq!(regions[2].start, RAM_START);
+    assert_eq!(regions[2].kind, MemoryRegionKind::Ram);
+    assert_eq!(regions[2].permissions.to_string(), "r--");
+}
@@ -0,0 +1,165 @@
+//! Record/replay tests for RISC-V emulator.
+
+use lp_riscv_emu::{EmulatorError, Riscv32Emulator, StepResult, TimeMode};
+use lp_riscv_inst::Gpr;
+
+const RAM_START: u32 = 0x80000000;
+
+/// Syscall handled by the test host (returns a scripted value in a0)
+const SYSCALL_SCRIPTED: i32 = 100;
+
+/// Sums two host syscall returns and the time syscall into s0, stores it, and halts
+fn program() -> Vec<u8> {
+    vec![
+        0x93, 0x08, 0x40, 0x06, // addi a7, zero, 100
+        0x73, 0x00, 0x00, 0x00, // ecall
+        0x33, 0x04, 0xa0, 0x00, // add s0, zero, a0
+        0x93, 0x08, 0x80, 0x00, // addi a7, zero, 8 (SYSCALL_TIME_MS)
+        0x73, 0x00, 0x00, 0x00, // ecall
+        0x33, 0x04, 0xa4, 0x00, // add s0, s0, a0
+        0x93, 0x08, 0x40, 0x06, // addi a7, zero, 100
+        0x73, 0x00, 0x00, 0x00, // ecall
+        0x33, 0x04, 0xa4, 0x00, // add s0, s0, a0
+        0x23, 0xa0, 0x81, 0x00, // sw s0, 0(gp)
+        0x73, 0x00, 0x10, 0x00, // ebreak
+    ]
+}
+
+fn new_emulator(time_ms: u32) -> Riscv32Emulator {
+    let mut emu =
+        Riscv32Emulator::new(program(), vec![0; 1024]).with_time_mode(TimeMode::Simulated(time_ms));
+    emu.set_register(Gpr::Gp, RAM_START as i32);
+    emu
+}
+
+/// Run to completion, answering scripted syscalls from `script`
+fn run_scripted(emu: &mut Riscv32Emulator, script: &[i32]) -> Vec<StepResult> {
+    let mut script = script.iter();
+    let mut results = Vec::new();
+    loop {
+        let result = emu.run().unwrap();
+        results.push(result.clone());
+        match result {
+            StepResult::Syscall(info) if info.number == SYSCALL_SCRIPTED => {
+                emu.set_register(Gpr::A0, *script.next().expect("Script exhausted"));
+            }
+            StepResult::Halted => return results,
+            other => panic!("Unexpected result: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_replay_reproduces_recorded_run() {
+    let mut recorded = new_emulator(1000);
+    recorded.start_recording();
+    assert!(recorded.is_recording());
+    let recorded_results = run_scripted(&mut recorded, &[40, 2]);
+    let log = recorded.stop_recording();
+    assert!(!recorded.is_recording());
+
+    assert_eq!(recorded.get_register(Gpr::S0), 1042);
+    assert_eq!(log.inputs.len(), 1);
+
+    // Different clock and no host: everything comes from the log
+    let mut replayed = new_emulator(0);
+    let replayed_results = replayed.replay(&log).unwrap();
+
+    assert_eq!(replayed_results, recorded_results);
+    assert_eq!(replayed.get_pc(), recorded.get_pc());
+    for reg in 0..32 {
+        let reg = Gpr::new(reg);
+        assert_eq!(replayed.get_register(reg), recorded.get_register(reg));
+    }
+    assert_eq!(
+        replayed.memory().read_word(RAM_START).unwrap(),
+        recorded.memory().read_word(RAM_START).unwrap()
+    );
+}
+
+#[test]
+fn test_replay_with_single_steps() {
+    let mut recorded = new_emulator(5);
+    recorded.start_recording();
+    let mut recorded_results = Vec::new();
+    loop {
+        let result = recorded.step().unwrap();
+        recorded_results.push(result.clone());
+        match result {
+            StepResult::Syscall(_) => recorded.set_register(Gpr::A0, 1),
+            StepResult::Halted => break,
+            _ => {}
+        }
+    }
+    let log = recorded.stop_recording();
+
+    let mut replayed = new_emulator(0);
+    assert_eq!(replayed.replay(&log).unwrap(), recorded_results);
+    assert_eq!(replayed.get_register(Gpr::S0), 7);
+}
+
+#[test]
+fn test_replay_divergence() {
+    let mut recorded = new_emulator(1000);
+    recorded.start_recording();
+    run_scripted(&mut recorded, &[40, 2]);
+    let mut log = recorded.stop_recording();
+
+    // Input for a syscall the guest never makes
+    log.inputs[0].syscall = 7;
+
+    let mut replayed = new_emulator(1000);
+    match replayed.replay(&log) {
+        Err(EmulatorError::ReplayDivergence { .. }) => {}
+        other => panic!("Expected ReplayDivergence, got {other:?}"),
+    }
+}
+
+/// Reads two serial bytes into RAM, echoes them back over serial, and halts
+fn serial_echo_program() -> Vec<u8> {
+    vec![
+        0x93, 0x08, 0x60, 0x00, // addi a7, zero, 6 (SYSCALL_SERIAL_READ)
+        0x13, 0x85, 0x01, 0x00, // addi a0, gp, 0
+        0x93, 0x05, 0x20, 0x00, // addi a1, zero, 2
+        0x73, 0x00, 0x00, 0x00, // ecall
+        0x93, 0x08, 0x50, 0x00, // addi a7, zero, 5 (SYSCALL_SERIAL_WRITE)
+        0x13, 0x85, 0x01, 0x00, // addi a0, gp, 0
+        0x93, 0x05, 0x20, 0x00, // addi a1, zero, 2
+        0x73, 0x00, 0x00, 0x00, // ecall
+        0x73, 0x00, 0x10, 0x00, // ebreak
+    ]
+}
+
+fn new_serial_emulator(serial_input: &[u8]) -> Riscv32Emulator {
+    let mut emu = Riscv32Emulator::new(serial_echo_program(), vec![0; 1024]);
+    emu.set_register(Gpr::Gp, RAM_START as i32);
+    emu.serial_write(serial_input);
+    emu
+}
+
+fn read_ram(emu: &Riscv32Emulator, len: u32) -> Vec<u8> {
+    (0..len)
+        .map(|i| emu.memory().read_u8(RAM_START + i).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_replay_does_not_touch_host_serial() {
+    let mut recorded = new_serial_emulator(b"hi");
+    recorded.start_recording();
+    assert_eq!(recorded.run().unwrap(), StepResult::Halted);
+    let log = recorded.stop_recording();
+    assert_eq!(recorded.drain_serial_output(), b"hi");
+
+    // Serial input the guest must not see during replay
+    let mut replayed = new_serial_emulator(b"XYZ");
+    assert_eq!(replayed.replay(&log).unwrap(), vec![StepResult::Halted]);
+    assert_eq!(read_ram(&replayed, 2), b"hi");
+    assert!(replayed.drain_serial_output().is_empty());
+
+    // The host's input is still there for a live run
+    replayed.set_pc(log.start_pc);
+    assert_eq!(replayed.run().unwrap(), StepResult::Halted);
+    assert_eq!(read_ram(&replayed, 2), b"XY");
+    assert_eq!(replayed.drain_serial_output(), b"XY");
+}
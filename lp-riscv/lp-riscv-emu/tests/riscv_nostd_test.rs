@@ -5,7 +5,7 @@
 
 #[cfg(feature = "std")]
 use lp_riscv_elf::load_elf;
-use lp_riscv_emu::{Riscv32Emulator, StepResult};
+use lp_riscv_emu::StepResult;
 use lp_riscv_inst::Gpr;
 use std::{sync::mpsc, thread, time::Duration};
 
@@ -105,7 +105,7 @@ fn run_nostd_test() -> Result<(), String> {
     // Create emulator
     println!("[3/4] Running in RISC-V emulator...");
     // Fuel is now per-run, not global
-    let mut emu = Riscv32Emulator::new(elf_info.code, elf_info.ram);
+    let mut emu = elf_info.into_emulator();
 
     let mut output_lines = Vec::new();
     let mut result_value = None;
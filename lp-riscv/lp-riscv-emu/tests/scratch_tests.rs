@@ -0,0 +1,86 @@
+//! Scratch region tests for RISC-V emulator.
+
+use cranelift_codegen::data_value::DataValue;
+use cranelift_codegen::ir::{AbiParam, Signature, types};
+use cranelift_codegen::isa::CallConv;
+use lp_riscv_emu::{
+    MemoryPermissions, MemoryRegion, MemoryRegionKind, Riscv32Emulator, ScratchConfig,
+};
+
+const RAM_START: u32 = 0x80000000;
+
+const SCRATCH: ScratchConfig = ScratchConfig {
+    start: RAM_START + 4096,
+    size: 256,
+};
+
+/// `int f(int x)` keeping a 4-element array in scratch memory:
+/// `array = scratch_alloc(16, 4); array[0] += x; return array[0];`
+fn accumulate_code() -> Vec<u8> {
+    [
+        0x00050293u32, // mv t0, a0
+        0x01000513,    // li a0, 16
+        0x00400593,    // li a1, 4
+        0x00900893,    // li a7, 9 (SYSCALL_SCRATCH_ALLOC)
+        0x00000073,    // ecall
+        0x00052303,    // lw t1, 0(a0)
+        0x00530333,    // add t1, t1, t0
+        0x00652023,    // sw t1, 0(a0)
+        0x00030513,    // mv a0, t1
+        0x00008067,    // ret
+    ]
+    .iter()
+    .flat_map(|inst| inst.to_le_bytes())
+    .collect()
+}
+
+fn int_signature() -> Signature {
+    let mut sig = Signature::new(CallConv::SystemV);
+    sig.params.push(AbiParam::new(types::I32));
+    sig.returns.push(AbiParam::new(types::I32));
+    sig
+}
+
+#[test]
+fn test_scratch_resets_between_calls() {
+    let mut emu =
+        Riscv32Emulator::new(accumulate_code(), vec![0; 64 * 1024]).with_scratch_region(SCRATCH);
+    let sig = int_signature();
+
+    // Were the array carried over, each pixel would see the sum of the ones before it
+    for x in 1..=100 {
+        let results = emu.call_function(0, &[DataValue::I32(x)], &sig).unwrap();
+        assert_eq!(results, vec![DataValue::I32(x)], "call with x = {x}");
+        assert_eq!(emu.scratch_used(), 16);
+    }
+
+    // The last call's array is still there until the next call
+    assert_eq!(emu.memory().read_word(SCRATCH.start).unwrap(), 100);
+}
+
+#[test]
+fn test_scratch_alloc_without_region_returns_null() {
+    // The function then reads address 0, which is its own first instruction
+    let mut emu = Riscv32Emulator::new(accumulate_code(), vec![0; 64 * 1024]);
+    let results = emu
+        .call_function(0, &[DataValue::I32(1)], &int_signature())
+        .unwrap();
+    assert_eq!(results, vec![DataValue::I32(0x00050293 + 1)]);
+}
+
+#[test]
+fn test_scratch_in_memory_map() {
+    let emu = Riscv32Emulator::new(vec![0; 4], vec![0; 64 * 1024]).with_scratch_region(SCRATCH);
+
+    assert_eq!(emu.scratch_config(), Some(SCRATCH));
+    let regions = emu.memory_regions();
+    assert_eq!(
+        regions.last(),
+        Some(&MemoryRegion {
+            start: SCRATCH.start,
+            size: SCRATCH.size,
+            kind: MemoryRegionKind::Scratch,
+            permissions: MemoryPermissions::RW,
+        })
+    );
+}
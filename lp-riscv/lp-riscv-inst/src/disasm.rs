@@ -0,0 +1,80 @@
+//! Disassembly listings for RISC-V 32-bit machine code.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::format_instruction;
+
+/// Disassemble `code`, one line per instruction with its address
+///
+/// `base_address` is the address of `code[0]`. A `<name>:` label line is inserted before
+/// each instruction whose address appears in `labels`, in the order given. Compressed
+/// instructions are decoded as 16-bit; a trailing partial instruction is listed as `.half`.
+///
+/// # Example output
+/// ```text
+/// <main>:
+///   0x00000000: 02a00513  addi a0, zero, 0x0000002a
+///   0x00000004:     8082  c.jr ra
+/// ```
+pub fn disassemble(code: &[u8], base_address: u32, labels: &[(u32, &str)]) -> String {
+    let mut result = String::new();
+    let mut offset = 0usize;
+
+    while offset + 2 <= code.len() {
+        let pc = base_address.wrapping_add(offset as u32);
+        for (_, name) in labels.iter().filter(|(address, _)| *address == pc) {
+            let _ = writeln!(result, "<{name}>:");
+        }
+
+        let low = u16::from_le_bytes([code[offset], code[offset + 1]]);
+        if low & 0x3 != 0x3 {
+            let _ = writeln!(
+                result,
+                "  0x{pc:08x}: {low:8x}  {}",
+                format_instruction(low as u32)
+            );
+            offset += 2;
+        } else if offset + 4 <= code.len() {
+            let inst_word = u32::from_le_bytes([
+                code[offset],
+                code[offset + 1],
+                code[offset + 2],
+                code[offset + 3],
+            ]);
+            let _ = writeln!(
+                result,
+                "  0x{pc:08x}: {inst_word:08x}  {}",
+                format_instruction(inst_word)
+            );
+            offset += 4;
+        } else {
+            let _ = writeln!(result, "  0x{pc:08x}: {low:8x}  .half 0x{low:04x}");
+            break;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_disassemble() {
+        let code = [
+            0x13, 0x05, 0xa0, 0x02, // addi a0, zero, 42
+            0x82, 0x80, // c.jr ra
+            0x13, 0x05, // truncated
+        ];
+        let listing = disassemble(&code, 0x100, &[(0x100, "answer")]);
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "<answer>:");
+        assert!(lines[1].starts_with("  0x00000100: 02a00513  addi a0, zero,"));
+        assert_eq!(lines[2], "  0x00000104:     8082  c.jr ra");
+        assert_eq!(lines[3], "  0x00000106:      513  .half 0x0513");
+    }
+}
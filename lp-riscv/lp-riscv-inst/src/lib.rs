@@ -15,6 +15,7 @@ extern crate std;
 pub mod auipc_imm;
 pub mod decode;
 pub mod decode_rvc;
+pub mod disasm;
 pub mod encode;
 pub mod format;
 pub mod inst;
@@ -23,5 +24,6 @@ pub mod regs;
 
 // Re-exports for convenience
 pub use decode::decode_instruction;
+pub use disasm::disassemble;
 pub use inst::{Inst, format_instruction};
 pub use regs::Gpr;